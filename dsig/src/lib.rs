@@ -25,3 +25,7 @@ pub mod ecdsa;
 /// XML-Signature XPath Filter 2.0
 /// See https://www.w3.org/TR/xmldsig-filter2/
 pub mod xpath;
+
+/// XML Encryption Syntax and Processing Version 1.1
+/// See https://www.w3.org/TR/xmlenc-core1/
+pub mod xenc;
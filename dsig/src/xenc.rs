@@ -0,0 +1,410 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//
+// See XML Encryption Syntax and Processing Version 1.1
+// https://www.w3.org/TR/xmlenc-core1/
+//
+// Elements of the `xenc`, `xenc11`, `ds` and `dsig11` namespaces are
+// serialized using a default `xmlns` attribute rather than a prefix.
+
+use validator::Validate;
+
+/// Returns the namespace of the schema
+pub fn namespace() -> String {
+    "http://www.w3.org/2001/04/xmlenc#".to_string()
+}
+
+/// Returns the namespace of the XML Encryption 1.1 schema
+pub fn namespace_11() -> String {
+    "http://www.w3.org/2009/xmlenc11#".to_string()
+}
+
+/// Returns the namespace of the XML Signature schema
+pub fn namespace_dsig() -> String {
+    "http://www.w3.org/2000/09/xmldsig#".to_string()
+}
+
+/// Returns the namespace of the XML Signature 1.1 schema
+pub fn namespace_dsig_11() -> String {
+    "http://www.w3.org/2009/xmldsig11#".to_string()
+}
+
+/// `Type` attribute value for an encrypted element
+pub const TYPE_ELEMENT: &str = "http://www.w3.org/2001/04/xmlenc#Element";
+
+/// `Type` attribute value for encrypted element content
+pub const TYPE_CONTENT: &str = "http://www.w3.org/2001/04/xmlenc#Content";
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+#[serde(rename = "EncryptedData")]
+pub struct EncryptedData {
+    #[serde(rename = "@Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "@Type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(rename = "@MimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(rename = "@Encoding", skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    #[serde(rename = "EncryptionMethod", skip_serializing_if = "Option::is_none")]
+    pub encryption_method: Option<EncryptionMethod>,
+    #[serde(rename = "KeyInfo", skip_serializing_if = "Option::is_none")]
+    pub key_info: Option<KeyInfo>,
+    #[validate]
+    #[serde(rename = "CipherData")]
+    pub cipher_data: CipherData,
+    #[serde(rename = "@xmlns", default = "namespace")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct EncryptedKey {
+    #[serde(rename = "@Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "@Recipient", skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    #[serde(rename = "EncryptionMethod", skip_serializing_if = "Option::is_none")]
+    pub encryption_method: Option<EncryptionMethod>,
+    #[serde(rename = "KeyInfo", skip_serializing_if = "Option::is_none")]
+    pub key_info: Option<KeyInfo>,
+    #[validate]
+    #[serde(rename = "CipherData")]
+    pub cipher_data: CipherData,
+    #[serde(rename = "CarriedKeyName", skip_serializing_if = "Option::is_none")]
+    pub carried_key_name: Option<String>,
+    #[serde(rename = "@xmlns", default = "namespace")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct EncryptionMethod {
+    #[serde(rename = "KeySize", skip_serializing_if = "Option::is_none")]
+    pub key_size: Option<i64>,
+    #[serde(rename = "OAEPparams", skip_serializing_if = "Option::is_none")]
+    pub oae_pparams: Option<String>,
+    #[serde(rename = "DigestMethod", skip_serializing_if = "Option::is_none")]
+    pub digest_method: Option<DigestMethod>,
+    #[serde(rename = "MGF", skip_serializing_if = "Option::is_none")]
+    pub mgf: Option<Mgf>,
+    #[serde(rename = "@Algorithm")]
+    pub algorithm: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct DigestMethod {
+    #[serde(rename = "@Algorithm")]
+    pub algorithm: String,
+    #[serde(rename = "@xmlns", default = "namespace_dsig")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Mgf {
+    #[serde(rename = "@Algorithm")]
+    pub algorithm: String,
+    #[serde(rename = "@xmlns", default = "namespace_11")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct CipherData {
+    #[serde(rename = "CipherValue", skip_serializing_if = "Option::is_none")]
+    pub cipher_value: Option<CipherValue>,
+    #[serde(rename = "CipherReference", skip_serializing_if = "Option::is_none")]
+    pub cipher_reference: Option<CipherReference>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct CipherValue {
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct CipherReference {
+    #[serde(rename = "@URI")]
+    pub uri: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct AgreementMethod {
+    #[serde(rename = "KA-Nonce", skip_serializing_if = "Option::is_none")]
+    pub ka_nonce: Option<String>,
+    #[serde(rename = "KeyDerivationMethod", skip_serializing_if = "Option::is_none")]
+    pub key_derivation_method: Option<KeyDerivationMethod>,
+    #[serde(rename = "OriginatorKeyInfo", skip_serializing_if = "Option::is_none")]
+    pub originator_key_info: Option<OriginatorKeyInfo>,
+    #[serde(rename = "RecipientKeyInfo", skip_serializing_if = "Option::is_none")]
+    pub recipient_key_info: Option<RecipientKeyInfo>,
+    #[serde(rename = "@Algorithm")]
+    pub algorithm: String,
+    #[serde(rename = "@xmlns", default = "namespace")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct KeyDerivationMethod {
+    #[serde(rename = "ConcatKDFParams", skip_serializing_if = "Option::is_none")]
+    pub concat_kdf_params: Option<ConcatKdfParams>,
+    #[serde(rename = "@Algorithm")]
+    pub algorithm: String,
+    #[serde(rename = "@xmlns", default = "namespace_11")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ConcatKdfParams {
+    #[serde(rename = "DigestMethod")]
+    pub digest_method: DigestMethod,
+    #[serde(rename = "@AlgorithmID", skip_serializing_if = "Option::is_none")]
+    pub algorithm_id: Option<String>,
+    #[serde(rename = "@PartyUInfo", skip_serializing_if = "Option::is_none")]
+    pub party_u_info: Option<String>,
+    #[serde(rename = "@PartyVInfo", skip_serializing_if = "Option::is_none")]
+    pub party_v_info: Option<String>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OriginatorKeyInfo {
+    #[serde(rename = "KeyValue", skip_serializing_if = "Option::is_none")]
+    pub key_value: Option<KeyValue>,
+    #[serde(rename = "X509Data", skip_serializing_if = "Option::is_none")]
+    pub x_509_data: Option<X509Data>,
+    #[serde(rename = "@xmlns", default = "namespace_dsig")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct RecipientKeyInfo {
+    #[serde(rename = "KeyName", skip_serializing_if = "Option::is_none")]
+    pub key_name: Option<String>,
+    #[serde(rename = "KeyValue", skip_serializing_if = "Option::is_none")]
+    pub key_value: Option<KeyValue>,
+    #[serde(rename = "X509Data", skip_serializing_if = "Option::is_none")]
+    pub x_509_data: Option<X509Data>,
+    #[serde(rename = "@xmlns", default = "namespace_dsig")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct KeyInfo {
+    #[serde(rename = "KeyName", skip_serializing_if = "Option::is_none")]
+    pub key_name: Option<String>,
+    #[serde(rename = "KeyValue", skip_serializing_if = "Option::is_none")]
+    pub key_value: Option<KeyValue>,
+    #[serde(rename = "X509Data", skip_serializing_if = "Option::is_none")]
+    pub x_509_data: Option<X509Data>,
+    #[serde(rename = "EncryptedKey", skip_serializing_if = "Option::is_none")]
+    pub encrypted_key: Option<Box<EncryptedKey>>,
+    #[serde(rename = "AgreementMethod", skip_serializing_if = "Option::is_none")]
+    pub agreement_method: Option<Box<AgreementMethod>>,
+    #[serde(rename = "@xmlns", default = "namespace_dsig")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct KeyValue {
+    #[serde(rename = "ECKeyValue", skip_serializing_if = "Option::is_none")]
+    pub ec_key_value: Option<EcKeyValue>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct EcKeyValue {
+    #[serde(rename = "NamedCurve")]
+    pub named_curve: NamedCurve,
+    #[serde(rename = "PublicKey")]
+    pub public_key: String,
+    #[serde(rename = "@xmlns", default = "namespace_dsig_11")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct NamedCurve {
+    #[serde(rename = "@URI")]
+    pub uri: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct X509Data {
+    #[serde(rename = "X509IssuerSerial", skip_serializing_if = "Option::is_none")]
+    pub x_509_issuer_serial: Option<X509IssuerSerial>,
+    #[serde(rename = "X509SubjectName", skip_serializing_if = "Option::is_none")]
+    pub x_509_subject_name: Option<String>,
+    #[serde(rename = "X509Certificate", skip_serializing_if = "Option::is_none")]
+    pub x_509_certificate: Option<String>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct X509IssuerSerial {
+    #[serde(rename = "X509IssuerName")]
+    pub x_509_issuer_name: String,
+    #[serde(rename = "X509SerialNumber")]
+    pub x_509_serial_number: String,
+}
@@ -0,0 +1,915 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+// See ISO-20022 Intellectual Property Rights Policy at
+// <https://www.iso20022.org/intellectual-property-rights>
+// for more information.
+
+use validator::Validate;
+
+::lazy_static::lazy_static! {
+    static ref PHONE_NUMBER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"\+[0-9]{1,3}-[0-9()+\-]{1,30}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref BICFI_IDENTIFIER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z]{6,6}[A-Z2-9][A-NP-Z0-9]([A-Z0-9]{3,3}){0,1}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref COUNTRY_CODE_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z]{2,2}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref ANY_BIC_IDENTIFIER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z]{6,6}[A-Z2-9][A-NP-Z0-9]([A-Z0-9]{3,3}){0,1}"#).unwrap();
+}
+
+/// Returns the namespace of the schema
+pub fn namespace() -> String {
+    "urn:iso:std:iso:20022:tech:xsd:head.001.001.01".to_string()
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+#[serde(rename = "AppHdr")]
+pub struct BusinessApplicationHeaderV01<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(rename = "CharSet", skip_serializing_if = "Option::is_none")]
+    pub char_set: Option<UnicodeChartsCode>,
+    #[serde(rename = "Fr")]
+    pub fr: Party9Choice,
+    #[serde(rename = "To")]
+    pub to: Party9Choice,
+    #[validate]
+    #[serde(rename = "BizMsgIdr")]
+    pub biz_msg_idr: Max35Text,
+    #[validate]
+    #[serde(rename = "MsgDefIdr")]
+    pub msg_def_idr: Max35Text,
+    #[serde(rename = "BizSvc", skip_serializing_if = "Option::is_none")]
+    pub biz_svc: Option<Max35Text>,
+    #[validate]
+    #[serde(rename = "CreDt")]
+    pub cre_dt: IsoDateTime,
+    #[serde(rename = "CpyDplct", skip_serializing_if = "Option::is_none")]
+    pub cpy_dplct: Option<CopyDuplicate1Code>,
+    #[serde(rename = "PssblDplct", skip_serializing_if = "Option::is_none")]
+    pub pssbl_dplct: Option<YesNoIndicator>,
+    #[serde(rename = "Prty", skip_serializing_if = "Option::is_none")]
+    pub prty: Option<BusinessMessagePriorityCode>,
+    #[serde(rename = "Sgntr", skip_serializing_if = "Option::is_none")]
+    pub sgntr: Option<SignatureEnvelope<A>>,
+    #[serde(rename = "Rltd", skip_serializing_if = "Option::is_none")]
+    pub rltd: Option<BusinessApplicationHeader1<B>>,
+    #[serde(rename = "@xmlns", default = "namespace")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BusinessApplicationHeader1<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(rename = "CharSet", skip_serializing_if = "Option::is_none")]
+    pub char_set: Option<UnicodeChartsCode>,
+    #[serde(rename = "Fr")]
+    pub fr: Party9Choice,
+    #[serde(rename = "To")]
+    pub to: Party9Choice,
+    #[validate]
+    #[serde(rename = "BizMsgIdr")]
+    pub biz_msg_idr: Max35Text,
+    #[validate]
+    #[serde(rename = "MsgDefIdr")]
+    pub msg_def_idr: Max35Text,
+    #[serde(rename = "BizSvc", skip_serializing_if = "Option::is_none")]
+    pub biz_svc: Option<Max35Text>,
+    #[validate]
+    #[serde(rename = "CreDt")]
+    pub cre_dt: IsoDateTime,
+    #[serde(rename = "CpyDplct", skip_serializing_if = "Option::is_none")]
+    pub cpy_dplct: Option<CopyDuplicate1Code>,
+    #[serde(rename = "PssblDplct", skip_serializing_if = "Option::is_none")]
+    pub pssbl_dplct: Option<YesNoIndicator>,
+    #[serde(rename = "Prty", skip_serializing_if = "Option::is_none")]
+    pub prty: Option<BusinessMessagePriorityCode>,
+    #[serde(rename = "Sgntr", skip_serializing_if = "Option::is_none")]
+    pub sgntr: Option<SignatureEnvelope<A>>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+#[serde(transparent)]
+pub struct AppHdr<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    pub value: BusinessApplicationHeaderV01<A, B>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct SignatureEnvelope<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[validate]
+    #[serde(flatten)]
+    pub value: A,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party9ChoiceEnum {
+    #[serde(rename = "OrgId", skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<PartyIdentification42>,
+    #[serde(rename = "FIId", skip_serializing_if = "Option::is_none")]
+    pub fi_id: Option<BranchAndFinancialInstitutionIdentification5>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party9Choice {
+    #[serde(flatten)]
+    pub value: Party9ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PartyIdentification42 {
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PstlAdr", skip_serializing_if = "Option::is_none")]
+    pub pstl_adr: Option<PostalAddress6>,
+    #[serde(rename = "Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Party10Choice>,
+    #[serde(rename = "CtryOfRes", skip_serializing_if = "Option::is_none")]
+    pub ctry_of_res: Option<CountryCode>,
+    #[serde(rename = "CtctDtls", skip_serializing_if = "Option::is_none")]
+    pub ctct_dtls: Option<ContactDetails2>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party10ChoiceEnum {
+    #[serde(rename = "OrgId", skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<OrganisationIdentification7>,
+    #[serde(rename = "PrvtId", skip_serializing_if = "Option::is_none")]
+    pub prvt_id: Option<PersonIdentification5>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party10Choice {
+    #[serde(flatten)]
+    pub value: Party10ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OrganisationIdentification7 {
+    #[serde(rename = "AnyBIC", skip_serializing_if = "Option::is_none")]
+    pub any_bic: Option<AnyBicIdentifier>,
+    #[validate(length(min = 0,))]
+    #[serde(rename = "Othr", default)]
+    pub othr: Vec<GenericOrganisationIdentification1>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericOrganisationIdentification1 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<OrganisationIdentificationSchemeName1Choice>,
+    #[serde(rename = "Issr", skip_serializing_if = "Option::is_none")]
+    pub issr: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OrganisationIdentificationSchemeName1ChoiceEnum {
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalOrganisationIdentification1Code>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OrganisationIdentificationSchemeName1Choice {
+    #[serde(flatten)]
+    pub value: OrganisationIdentificationSchemeName1ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalOrganisationIdentification1Code {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PersonIdentification5 {
+    #[serde(rename = "DtAndPlcOfBirth", skip_serializing_if = "Option::is_none")]
+    pub dt_and_plc_of_birth: Option<DateAndPlaceOfBirth>,
+    #[validate(length(min = 0,))]
+    #[serde(rename = "Othr", default)]
+    pub othr: Vec<GenericPersonIdentification1>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct DateAndPlaceOfBirth {
+    #[validate]
+    #[serde(rename = "BirthDt")]
+    pub birth_dt: IsoDate,
+    #[serde(rename = "PrvcOfBirth", skip_serializing_if = "Option::is_none")]
+    pub prvc_of_birth: Option<Max35Text>,
+    #[validate]
+    #[serde(rename = "CityOfBirth")]
+    pub city_of_birth: Max35Text,
+    #[serde(rename = "CtryOfBirth")]
+    pub ctry_of_birth: CountryCode,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericPersonIdentification1 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<PersonIdentificationSchemeName1Choice>,
+    #[serde(rename = "Issr", skip_serializing_if = "Option::is_none")]
+    pub issr: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PersonIdentificationSchemeName1ChoiceEnum {
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalPersonIdentification1Code>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PersonIdentificationSchemeName1Choice {
+    #[serde(flatten)]
+    pub value: PersonIdentificationSchemeName1ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalPersonIdentification1Code {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ContactDetails2 {
+    #[serde(rename = "NmPrfx", skip_serializing_if = "Option::is_none")]
+    pub nm_prfx: Option<NamePrefix1Code>,
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PhneNb", skip_serializing_if = "Option::is_none")]
+    pub phne_nb: Option<PhoneNumber>,
+    #[serde(rename = "MobNb", skip_serializing_if = "Option::is_none")]
+    pub mob_nb: Option<PhoneNumber>,
+    #[serde(rename = "FaxNb", skip_serializing_if = "Option::is_none")]
+    pub fax_nb: Option<PhoneNumber>,
+    #[serde(rename = "EmailAdr", skip_serializing_if = "Option::is_none")]
+    pub email_adr: Option<Max2048Text>,
+    #[serde(rename = "Othr", skip_serializing_if = "Option::is_none")]
+    pub othr: Option<Max35Text>,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum NamePrefix1Code {
+    #[serde(rename = "DOCT")]
+    Doct,
+    #[serde(rename = "MADM")]
+    Madm,
+    #[serde(rename = "MISS")]
+    Miss,
+    #[serde(rename = "MIST")]
+    Mist,
+    #[default]
+    Unknown,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PostalAddress6 {
+    #[serde(rename = "AdrTp", skip_serializing_if = "Option::is_none")]
+    pub adr_tp: Option<AddressType2Code>,
+    #[serde(rename = "Dept", skip_serializing_if = "Option::is_none")]
+    pub dept: Option<Max70Text>,
+    #[serde(rename = "SubDept", skip_serializing_if = "Option::is_none")]
+    pub sub_dept: Option<Max70Text>,
+    #[serde(rename = "StrtNm", skip_serializing_if = "Option::is_none")]
+    pub strt_nm: Option<Max70Text>,
+    #[serde(rename = "BldgNb", skip_serializing_if = "Option::is_none")]
+    pub bldg_nb: Option<Max16Text>,
+    #[serde(rename = "PstCd", skip_serializing_if = "Option::is_none")]
+    pub pst_cd: Option<Max16Text>,
+    #[serde(rename = "TwnNm", skip_serializing_if = "Option::is_none")]
+    pub twn_nm: Option<Max35Text>,
+    #[serde(rename = "CtrySubDvsn", skip_serializing_if = "Option::is_none")]
+    pub ctry_sub_dvsn: Option<Max35Text>,
+    #[serde(rename = "Ctry", skip_serializing_if = "Option::is_none")]
+    pub ctry: Option<CountryCode>,
+    #[validate(length(min = 0, max = 7,))]
+    #[serde(rename = "AdrLine", default)]
+    pub adr_line: Vec<Max70Text>,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum AddressType2Code {
+    #[serde(rename = "ADDR")]
+    Addr,
+    #[serde(rename = "PBOX")]
+    Pbox,
+    #[serde(rename = "HOME")]
+    Home,
+    #[serde(rename = "BIZZ")]
+    Bizz,
+    #[serde(rename = "MLTO")]
+    Mlto,
+    #[serde(rename = "DLVY")]
+    Dlvy,
+    #[default]
+    Unknown,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BranchAndFinancialInstitutionIdentification5 {
+    #[validate]
+    #[serde(rename = "FinInstnId")]
+    pub fin_instn_id: FinancialInstitutionIdentification8,
+    #[serde(rename = "BrnchId", skip_serializing_if = "Option::is_none")]
+    pub brnch_id: Option<BranchData2>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct FinancialInstitutionIdentification8 {
+    #[serde(rename = "BICFI", skip_serializing_if = "Option::is_none")]
+    pub bicfi: Option<BicfiIdentifier>,
+    #[serde(rename = "ClrSysMmbId", skip_serializing_if = "Option::is_none")]
+    pub clr_sys_mmb_id: Option<ClearingSystemMemberIdentification2>,
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PstlAdr", skip_serializing_if = "Option::is_none")]
+    pub pstl_adr: Option<PostalAddress6>,
+    #[serde(rename = "Othr", skip_serializing_if = "Option::is_none")]
+    pub othr: Option<GenericFinancialIdentification1>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BranchData2 {
+    #[serde(rename = "Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Max35Text>,
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PstlAdr", skip_serializing_if = "Option::is_none")]
+    pub pstl_adr: Option<PostalAddress6>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ClearingSystemMemberIdentification2 {
+    #[serde(rename = "ClrSysId", skip_serializing_if = "Option::is_none")]
+    pub clr_sys_id: Option<ClearingSystemIdentification2Choice>,
+    #[validate]
+    #[serde(rename = "MmbId")]
+    pub mmb_id: Max35Text,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ClearingSystemIdentification2ChoiceEnum {
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalClearingSystemIdentification1Code>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ClearingSystemIdentification2Choice {
+    #[serde(flatten)]
+    pub value: ClearingSystemIdentification2ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalClearingSystemIdentification1Code {
+    #[validate(length(min = 1, max = 5,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericFinancialIdentification1 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<FinancialIdentificationSchemeName1Choice>,
+    #[serde(rename = "Issr", skip_serializing_if = "Option::is_none")]
+    pub issr: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct FinancialIdentificationSchemeName1ChoiceEnum {
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalFinancialInstitutionIdentification1Code>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct FinancialIdentificationSchemeName1Choice {
+    #[serde(flatten)]
+    pub value: FinancialIdentificationSchemeName1ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalFinancialInstitutionIdentification1Code {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BicfiIdentifier {
+    #[validate(regex = "BICFI_IDENTIFIER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct AnyBicIdentifier {
+    #[validate(regex = "ANY_BIC_IDENTIFIER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct CountryCode {
+    #[validate(regex = "COUNTRY_CODE_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PhoneNumber {
+    #[validate(regex = "PHONE_NUMBER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max35Text {
+    #[validate(length(min = 1, max = 35,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max70Text {
+    #[validate(length(min = 1, max = 70,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max140Text {
+    #[validate(length(min = 1, max = 140,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max16Text {
+    #[validate(length(min = 1, max = 16,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max2048Text {
+    #[validate(length(min = 1, max = 2048,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct IsoDate {
+    #[serde(rename = "$text")]
+    pub value: ::chrono::NaiveDate,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct IsoDateTime {
+    #[serde(rename = "$text")]
+    pub value: ::chrono::DateTime<::chrono::Utc>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct UnicodeChartsCode {
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct YesNoIndicator {
+    #[serde(rename = "$text")]
+    pub value: bool,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BusinessMessagePriorityCode {
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum CopyDuplicate1Code {
+    #[serde(rename = "CODU")]
+    Codu,
+    #[serde(rename = "COPY")]
+    Copy,
+    #[serde(rename = "DUPL")]
+    Dupl,
+    #[default]
+    Unknown,
+}
@@ -0,0 +1,1134 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+// See ISO-20022 Intellectual Property Rights Policy at
+// <https://www.iso20022.org/intellectual-property-rights>
+// for more information.
+
+use validator::Validate;
+
+::lazy_static::lazy_static! {
+    static ref LEI_IDENTIFIER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z0-9]{18,18}[0-9]{2,2}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref PHONE_NUMBER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"\+[0-9]{1,3}-[0-9()+\-]{1,30}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref BICFI_DEC_2014_IDENTIFIER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z0-9]{4,4}[A-Z]{2,2}[A-Z0-9]{2,2}([A-Z0-9]{3,3}){0,1}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref COUNTRY_CODE_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z]{2,2}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref ANY_BIC_DEC_2014_IDENTIFIER_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[A-Z0-9]{4,4}[A-Z]{2,2}[A-Z0-9]{2,2}([A-Z0-9]{3,3}){0,1}"#).unwrap();
+}
+
+::lazy_static::lazy_static! {
+    static ref EXACT_4_ALPHA_NUMERIC_TEXT_REGEX: ::regex::Regex = ::regex::Regex::new(r#"[a-zA-Z0-9]{4}"#).unwrap();
+}
+
+/// Returns the namespace of the schema
+pub fn namespace() -> String {
+    "urn:iso:std:iso:20022:tech:xsd:head.001.001.02".to_string()
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+#[serde(rename = "AppHdr")]
+pub struct BusinessApplicationHeaderV02<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(rename = "CharSet", skip_serializing_if = "Option::is_none")]
+    pub char_set: Option<UnicodeChartsCode>,
+    #[serde(rename = "Fr")]
+    pub fr: Party44Choice,
+    #[serde(rename = "To")]
+    pub to: Party44Choice,
+    #[validate]
+    #[serde(rename = "BizMsgIdr")]
+    pub biz_msg_idr: Max35Text,
+    #[validate]
+    #[serde(rename = "MsgDefIdr")]
+    pub msg_def_idr: Max35Text,
+    #[serde(rename = "BizSvc", skip_serializing_if = "Option::is_none")]
+    pub biz_svc: Option<Max35Text>,
+    #[serde(rename = "MktPrctc", skip_serializing_if = "Option::is_none")]
+    pub mkt_prctc: Option<ImplementationSpecification1>,
+    #[validate]
+    #[serde(rename = "CreDt")]
+    pub cre_dt: IsoDateTime,
+    #[serde(rename = "BizPrcgDt", skip_serializing_if = "Option::is_none")]
+    pub biz_prcg_dt: Option<IsoDateTime>,
+    #[serde(rename = "CpyDplct", skip_serializing_if = "Option::is_none")]
+    pub cpy_dplct: Option<CopyDuplicate1Code>,
+    #[serde(rename = "PssblDplct", skip_serializing_if = "Option::is_none")]
+    pub pssbl_dplct: Option<YesNoIndicator>,
+    #[serde(rename = "Prty", skip_serializing_if = "Option::is_none")]
+    pub prty: Option<BusinessMessagePriorityCode>,
+    #[serde(rename = "Sgntr", skip_serializing_if = "Option::is_none")]
+    pub sgntr: Option<SignatureEnvelope<A>>,
+    #[validate(length(min = 0,))]
+    #[serde(rename = "Rltd", default)]
+    pub rltd: Vec<BusinessApplicationHeader5<B>>,
+    #[serde(rename = "@xmlns", default = "namespace")]
+    pub xmlns: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericIdentification30 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Exact4AlphaNumericText,
+    #[validate]
+    #[serde(rename = "Issr")]
+    pub issr: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct AddressType3ChoiceEnum {
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<AddressType2Code>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<GenericIdentification30>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct AddressType3Choice {
+    #[serde(flatten)]
+    pub value: AddressType3ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct LeiIdentifier {
+    #[validate(regex = "LEI_IDENTIFIER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum NamePrefix2Code {
+    #[serde(rename = "DOCT")]
+    Doct,
+    #[serde(rename = "MADM")]
+    Madm,
+    #[serde(rename = "MISS")]
+    Miss,
+    #[serde(rename = "MIST")]
+    Mist,
+    #[serde(rename = "MIKS")]
+    Miks,
+    #[default]
+    Unknown,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OrganisationIdentification29 {
+    #[serde(rename = "AnyBIC", skip_serializing_if = "Option::is_none")]
+    pub any_bic: Option<AnyBicDec2014Identifier>,
+    #[serde(rename = "LEI", skip_serializing_if = "Option::is_none")]
+    pub lei: Option<LeiIdentifier>,
+    #[validate(length(min = 0,))]
+    #[serde(rename = "Othr", default)]
+    pub othr: Vec<GenericOrganisationIdentification1>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BranchData3 {
+    #[serde(rename = "Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Max35Text>,
+    #[serde(rename = "LEI", skip_serializing_if = "Option::is_none")]
+    pub lei: Option<LeiIdentifier>,
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PstlAdr", skip_serializing_if = "Option::is_none")]
+    pub pstl_adr: Option<PostalAddress24>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PersonIdentification13 {
+    #[serde(rename = "DtAndPlcOfBirth", skip_serializing_if = "Option::is_none")]
+    pub dt_and_plc_of_birth: Option<DateAndPlaceOfBirth1>,
+    #[validate(length(min = 0,))]
+    #[serde(rename = "Othr", default)]
+    pub othr: Vec<GenericPersonIdentification1>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max350Text {
+    #[validate(length(min = 1, max = 350,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max2048Text {
+    #[validate(length(min = 1, max = 2048,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericPersonIdentification1 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<PersonIdentificationSchemeName1Choice>,
+    #[serde(rename = "Issr", skip_serializing_if = "Option::is_none")]
+    pub issr: Option<Max35Text>,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum CopyDuplicate1Code {
+    #[serde(rename = "CODU")]
+    Codu,
+    #[serde(rename = "COPY")]
+    Copy,
+    #[serde(rename = "DUPL")]
+    Dupl,
+    #[default]
+    Unknown,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericOrganisationIdentification1 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<OrganisationIdentificationSchemeName1Choice>,
+    #[serde(rename = "Issr", skip_serializing_if = "Option::is_none")]
+    pub issr: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ImplementationSpecification1 {
+    #[validate]
+    #[serde(rename = "Regy")]
+    pub regy: Max350Text,
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max2048Text,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PartyIdentification135 {
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PstlAdr", skip_serializing_if = "Option::is_none")]
+    pub pstl_adr: Option<PostalAddress24>,
+    #[serde(rename = "Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Party38Choice>,
+    #[serde(rename = "CtryOfRes", skip_serializing_if = "Option::is_none")]
+    pub ctry_of_res: Option<CountryCode>,
+    #[serde(rename = "CtctDtls", skip_serializing_if = "Option::is_none")]
+    pub ctct_dtls: Option<Contact4>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PhoneNumber {
+    #[validate(regex = "PHONE_NUMBER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max35Text {
+    #[validate(length(min = 1, max = 35,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BusinessApplicationHeader5<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(rename = "CharSet", skip_serializing_if = "Option::is_none")]
+    pub char_set: Option<UnicodeChartsCode>,
+    #[serde(rename = "Fr")]
+    pub fr: Party44Choice,
+    #[serde(rename = "To")]
+    pub to: Party44Choice,
+    #[validate]
+    #[serde(rename = "BizMsgIdr")]
+    pub biz_msg_idr: Max35Text,
+    #[validate]
+    #[serde(rename = "MsgDefIdr")]
+    pub msg_def_idr: Max35Text,
+    #[serde(rename = "BizSvc", skip_serializing_if = "Option::is_none")]
+    pub biz_svc: Option<Max35Text>,
+    #[validate]
+    #[serde(rename = "CreDt")]
+    pub cre_dt: IsoDateTime,
+    #[serde(rename = "CpyDplct", skip_serializing_if = "Option::is_none")]
+    pub cpy_dplct: Option<CopyDuplicate1Code>,
+    #[serde(rename = "PssblDplct", skip_serializing_if = "Option::is_none")]
+    pub pssbl_dplct: Option<YesNoIndicator>,
+    #[serde(rename = "Prty", skip_serializing_if = "Option::is_none")]
+    pub prty: Option<BusinessMessagePriorityCode>,
+    #[serde(rename = "Sgntr", skip_serializing_if = "Option::is_none")]
+    pub sgntr: Option<SignatureEnvelope<A>>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+#[serde(transparent)]
+pub struct AppHdr<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    pub value: BusinessApplicationHeaderV02<A, B>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PersonIdentificationSchemeName1ChoiceEnum {
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalPersonIdentification1Code>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PersonIdentificationSchemeName1Choice {
+    #[serde(flatten)]
+    pub value: PersonIdentificationSchemeName1ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct SignatureEnvelope<
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[validate]
+    #[serde(flatten)]
+    pub value: A,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct PostalAddress24 {
+    #[serde(rename = "AdrTp", skip_serializing_if = "Option::is_none")]
+    pub adr_tp: Option<AddressType3Choice>,
+    #[serde(rename = "Dept", skip_serializing_if = "Option::is_none")]
+    pub dept: Option<Max70Text>,
+    #[serde(rename = "SubDept", skip_serializing_if = "Option::is_none")]
+    pub sub_dept: Option<Max70Text>,
+    #[serde(rename = "StrtNm", skip_serializing_if = "Option::is_none")]
+    pub strt_nm: Option<Max70Text>,
+    #[serde(rename = "BldgNb", skip_serializing_if = "Option::is_none")]
+    pub bldg_nb: Option<Max16Text>,
+    #[serde(rename = "BldgNm", skip_serializing_if = "Option::is_none")]
+    pub bldg_nm: Option<Max35Text>,
+    #[serde(rename = "Flr", skip_serializing_if = "Option::is_none")]
+    pub flr: Option<Max70Text>,
+    #[serde(rename = "PstBx", skip_serializing_if = "Option::is_none")]
+    pub pst_bx: Option<Max16Text>,
+    #[serde(rename = "Room", skip_serializing_if = "Option::is_none")]
+    pub room: Option<Max70Text>,
+    #[serde(rename = "PstCd", skip_serializing_if = "Option::is_none")]
+    pub pst_cd: Option<Max16Text>,
+    #[serde(rename = "TwnNm", skip_serializing_if = "Option::is_none")]
+    pub twn_nm: Option<Max35Text>,
+    #[serde(rename = "TwnLctnNm", skip_serializing_if = "Option::is_none")]
+    pub twn_lctn_nm: Option<Max35Text>,
+    #[serde(rename = "DstrctNm", skip_serializing_if = "Option::is_none")]
+    pub dstrct_nm: Option<Max35Text>,
+    #[serde(rename = "CtrySubDvsn", skip_serializing_if = "Option::is_none")]
+    pub ctry_sub_dvsn: Option<Max35Text>,
+    #[serde(rename = "Ctry", skip_serializing_if = "Option::is_none")]
+    pub ctry: Option<CountryCode>,
+    #[validate(length(min = 0, max = 7,))]
+    #[serde(rename = "AdrLine", default)]
+    pub adr_line: Vec<Max70Text>,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum AddressType2Code {
+    #[serde(rename = "ADDR")]
+    Addr,
+    #[serde(rename = "PBOX")]
+    Pbox,
+    #[serde(rename = "HOME")]
+    Home,
+    #[serde(rename = "BIZZ")]
+    Bizz,
+    #[serde(rename = "MLTO")]
+    Mlto,
+    #[serde(rename = "DLVY")]
+    Dlvy,
+    #[default]
+    Unknown,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalPersonIdentification1Code {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max128Text {
+    #[validate(length(min = 1, max = 128,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct UnicodeChartsCode {
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct IsoDateTime {
+    #[serde(rename = "$text")]
+    pub value: ::chrono::DateTime<::chrono::Utc>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OtherContact1 {
+    #[validate]
+    #[serde(rename = "ChanlTp")]
+    pub chanl_tp: Max4Text,
+    #[serde(rename = "Id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Max128Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ClearingSystemIdentification2ChoiceEnum {
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalClearingSystemIdentification1Code>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ClearingSystemIdentification2Choice {
+    #[serde(flatten)]
+    pub value: ClearingSystemIdentification2ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max16Text {
+    #[validate(length(min = 1, max = 16,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BicfiDec2014Identifier {
+    #[validate(regex = "BICFI_DEC_2014_IDENTIFIER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max140Text {
+    #[validate(length(min = 1, max = 140,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BranchAndFinancialInstitutionIdentification6 {
+    #[validate]
+    #[serde(rename = "FinInstnId")]
+    pub fin_instn_id: FinancialInstitutionIdentification18,
+    #[serde(rename = "BrnchId", skip_serializing_if = "Option::is_none")]
+    pub brnch_id: Option<BranchData3>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct CountryCode {
+    #[validate(regex = "COUNTRY_CODE_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct AnyBicDec2014Identifier {
+    #[validate(regex = "ANY_BIC_DEC_2014_IDENTIFIER_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalClearingSystemIdentification1Code {
+    #[validate(length(min = 1, max = 5,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct FinancialIdentificationSchemeName1ChoiceEnum {
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalFinancialInstitutionIdentification1Code>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct FinancialIdentificationSchemeName1Choice {
+    #[serde(flatten)]
+    pub value: FinancialIdentificationSchemeName1ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct IsoDate {
+    #[serde(rename = "$text")]
+    pub value: ::chrono::NaiveDate,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct DateAndPlaceOfBirth1 {
+    #[validate]
+    #[serde(rename = "BirthDt")]
+    pub birth_dt: IsoDate,
+    #[serde(rename = "PrvcOfBirth", skip_serializing_if = "Option::is_none")]
+    pub prvc_of_birth: Option<Max35Text>,
+    #[validate]
+    #[serde(rename = "CityOfBirth")]
+    pub city_of_birth: Max35Text,
+    #[serde(rename = "CtryOfBirth")]
+    pub ctry_of_birth: CountryCode,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max4Text {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Max70Text {
+    #[validate(length(min = 1, max = 70,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OrganisationIdentificationSchemeName1ChoiceEnum {
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    pub cd: Option<ExternalOrganisationIdentification1Code>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    pub prtry: Option<Max35Text>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct OrganisationIdentificationSchemeName1Choice {
+    #[serde(flatten)]
+    pub value: OrganisationIdentificationSchemeName1ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct GenericFinancialIdentification1 {
+    #[validate]
+    #[serde(rename = "Id")]
+    pub id: Max35Text,
+    #[serde(rename = "SchmeNm", skip_serializing_if = "Option::is_none")]
+    pub schme_nm: Option<FinancialIdentificationSchemeName1Choice>,
+    #[serde(rename = "Issr", skip_serializing_if = "Option::is_none")]
+    pub issr: Option<Max35Text>,
+}
+#[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum PreferredContactMethod1Code {
+    #[serde(rename = "LETT")]
+    Lett,
+    #[serde(rename = "MAIL")]
+    Mail,
+    #[serde(rename = "PHON")]
+    Phon,
+    #[serde(rename = "FAXX")]
+    Faxx,
+    #[serde(rename = "CELL")]
+    Cell,
+    #[default]
+    Unknown,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ClearingSystemMemberIdentification2 {
+    #[serde(rename = "ClrSysId", skip_serializing_if = "Option::is_none")]
+    pub clr_sys_id: Option<ClearingSystemIdentification2Choice>,
+    #[validate]
+    #[serde(rename = "MmbId")]
+    pub mmb_id: Max35Text,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalOrganisationIdentification1Code {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct ExternalFinancialInstitutionIdentification1Code {
+    #[validate(length(min = 1, max = 4,))]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party38ChoiceEnum {
+    #[serde(rename = "PrvtId", skip_serializing_if = "Option::is_none")]
+    pub prvt_id: Option<PersonIdentification13>,
+    #[serde(rename = "OrgId", skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<OrganisationIdentification29>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party38Choice {
+    #[serde(flatten)]
+    pub value: Party38ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct BusinessMessagePriorityCode {
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Contact4 {
+    #[serde(rename = "NmPrfx", skip_serializing_if = "Option::is_none")]
+    pub nm_prfx: Option<NamePrefix2Code>,
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PhneNb", skip_serializing_if = "Option::is_none")]
+    pub phne_nb: Option<PhoneNumber>,
+    #[serde(rename = "MobNb", skip_serializing_if = "Option::is_none")]
+    pub mob_nb: Option<PhoneNumber>,
+    #[serde(rename = "FaxNb", skip_serializing_if = "Option::is_none")]
+    pub fax_nb: Option<PhoneNumber>,
+    #[serde(rename = "EmailAdr", skip_serializing_if = "Option::is_none")]
+    pub email_adr: Option<Max2048Text>,
+    #[serde(rename = "EmailPurp", skip_serializing_if = "Option::is_none")]
+    pub email_purp: Option<Max35Text>,
+    #[serde(rename = "JobTitl", skip_serializing_if = "Option::is_none")]
+    pub job_titl: Option<Max35Text>,
+    #[serde(rename = "Rspnsblty", skip_serializing_if = "Option::is_none")]
+    pub rspnsblty: Option<Max35Text>,
+    #[serde(rename = "Dept", skip_serializing_if = "Option::is_none")]
+    pub dept: Option<Max70Text>,
+    #[validate(length(min = 0,))]
+    #[serde(rename = "Othr", default)]
+    pub othr: Vec<OtherContact1>,
+    #[serde(rename = "PrefrdMtd", skip_serializing_if = "Option::is_none")]
+    pub prefrd_mtd: Option<PreferredContactMethod1Code>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party44ChoiceEnum {
+    #[serde(rename = "FIId", skip_serializing_if = "Option::is_none")]
+    pub fi_id: Option<BranchAndFinancialInstitutionIdentification6>,
+    #[serde(rename = "OrgId", skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<PartyIdentification135>,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Party44Choice {
+    #[serde(flatten)]
+    pub value: Party44ChoiceEnum,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct Exact4AlphaNumericText {
+    #[validate(regex = "EXACT_4_ALPHA_NUMERIC_TEXT_REGEX")]
+    #[serde(rename = "$text")]
+    pub value: String,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct YesNoIndicator {
+    #[serde(rename = "$text")]
+    pub value: bool,
+}
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    ::serde::Serialize,
+    ::serde::Deserialize,
+    ::derive_builder::Builder,
+    ::validator::Validate,
+)]
+pub struct FinancialInstitutionIdentification18 {
+    #[serde(rename = "BICFI", skip_serializing_if = "Option::is_none")]
+    pub bicfi: Option<BicfiDec2014Identifier>,
+    #[serde(rename = "ClrSysMmbId", skip_serializing_if = "Option::is_none")]
+    pub clr_sys_mmb_id: Option<ClearingSystemMemberIdentification2>,
+    #[serde(rename = "LEI", skip_serializing_if = "Option::is_none")]
+    pub lei: Option<LeiIdentifier>,
+    #[serde(rename = "Nm", skip_serializing_if = "Option::is_none")]
+    pub nm: Option<Max140Text>,
+    #[serde(rename = "PstlAdr", skip_serializing_if = "Option::is_none")]
+    pub pstl_adr: Option<PostalAddress24>,
+    #[serde(rename = "Othr", skip_serializing_if = "Option::is_none")]
+    pub othr: Option<GenericFinancialIdentification1>,
+}
@@ -18,4 +18,6 @@
 // See ISO-20022 Intellectual Property Rights Policy at
 // <https://www.iso20022.org/intellectual-property-rights>
 // for more information.
+pub mod head_001_001_01;
+pub mod head_001_001_02;
 pub mod head_001_001_03;
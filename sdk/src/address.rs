@@ -0,0 +1,821 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Postal Addresses
+//!
+//! CBPR+ and HVPS+ reject fully unstructured postal addresses, i.e. `PstlAdr` with `AdrLine`
+//! only. The `PostalAddress` converter parses the address lines into the structured `StrtNm`,
+//! `BldgNb`, `PstBx`, `PstCd`, `TwnNm`, `CtrySubDvsn` and `Ctry` elements, using the address
+//! format of the country, and reports the `Confidence` of the result. The lines that cannot
+//! be parsed are kept as address lines, i.e. a hybrid address.
+//!
+//! `structure_document_addresses` applies the conversion to every `PstlAdr` of a document,
+//! e.g. pacs.008, pacs.009 or pain.001, and `check_document_addresses` validates the hybrid
+//! address rules, i.e. `TwnNm` and `Ctry` are mandatory, with at most two `AdrLine`.
+//!
+//! ```rust
+//! use iso_20022_sdk::address::{structure_document_addresses, Confidence};
+//!
+//! let (doc, conversions) = structure_document_addresses(&doc)?;
+//!
+//! for conversion in conversions.iter().filter(|c| c.confidence < Confidence::High) {
+//!     println!("review {}: {:?}", conversion.path, conversion.issues);
+//! }
+//! ```
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Name of the postal address elements.
+const POSTAL_ADDRESS: &str = "PstlAdr";
+
+/// Maximum number of address lines of a hybrid address.
+const MAX_HYBRID_ADDRESS_LINES: usize = 2;
+
+/// Maximum length of an address line, and of the street name.
+const MAX_70_TEXT: usize = 70;
+
+/// Maximum length of the town name and the country subdivision.
+const MAX_35_TEXT: usize = 35;
+
+/// Maximum length of the building number, the post box and the post code.
+const MAX_16_TEXT: usize = 16;
+
+lazy_static! {
+    static ref NUMBER_AFTER_STREET: Regex = Regex::new(
+        r"^(?P<strt>.*[^\d\s,])[\s,]+(?P<nb>\d+\s?[A-Za-z]?(?:\s?[-/]\s?\d+[A-Za-z]?)?)$"
+    )
+    .unwrap();
+    static ref NUMBER_BEFORE_STREET: Regex =
+        Regex::new(r"^(?P<nb>\d+[A-Za-z]?(?:[-/]\d+[A-Za-z]?)?)[\s,]+(?P<strt>\D.*)$").unwrap();
+    static ref POST_BOX: Regex = Regex::new(
+        r"(?i)^(?:p\.?\s?o\.?\s?box|postfach|case postale|bo[iî]te postale|casella postale|apartado|postbus)\s+(?P<pb>[\w-]+)$"
+    )
+    .unwrap();
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// Error Reading / Writing XML
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
+}
+
+/// Confidence of a structured address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// The town name or the country is missing, i.e. the address is not a valid hybrid address
+    Low,
+    /// The town name and the country are structured, but address lines remain, or the
+    /// street name or the post code is missing
+    Medium,
+    /// The street name, post code, town name and country are structured, without address lines
+    High,
+}
+
+/// Issue of the hybrid address rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressIssue {
+    /// The town name `TwnNm` is missing
+    MissingTwnNm,
+    /// The country `Ctry` is missing
+    MissingCtry,
+    /// The country `Ctry` is not an ISO 3166 alpha-2 code
+    InvalidCtry(String),
+    /// More than two address lines `AdrLine`
+    TooManyAdrLines(usize),
+    /// The address line `AdrLine`, by index, is longer than 70 characters
+    AdrLineTooLong(usize),
+}
+
+/// Postal address, with the elements of `PostalAddress24` except the address type `AdrTp`,
+/// so it converts to and from the `PostalAddress24` of any message set.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "PstlAdr")]
+pub struct PostalAddress {
+    #[serde(rename = "Dept", skip_serializing_if = "Option::is_none")]
+    pub dept: Option<String>,
+    #[serde(rename = "SubDept", skip_serializing_if = "Option::is_none")]
+    pub sub_dept: Option<String>,
+    #[serde(rename = "StrtNm", skip_serializing_if = "Option::is_none")]
+    pub strt_nm: Option<String>,
+    #[serde(rename = "BldgNb", skip_serializing_if = "Option::is_none")]
+    pub bldg_nb: Option<String>,
+    #[serde(rename = "BldgNm", skip_serializing_if = "Option::is_none")]
+    pub bldg_nm: Option<String>,
+    #[serde(rename = "Flr", skip_serializing_if = "Option::is_none")]
+    pub flr: Option<String>,
+    #[serde(rename = "PstBx", skip_serializing_if = "Option::is_none")]
+    pub pst_bx: Option<String>,
+    #[serde(rename = "Room", skip_serializing_if = "Option::is_none")]
+    pub room: Option<String>,
+    #[serde(rename = "PstCd", skip_serializing_if = "Option::is_none")]
+    pub pst_cd: Option<String>,
+    #[serde(rename = "TwnNm", skip_serializing_if = "Option::is_none")]
+    pub twn_nm: Option<String>,
+    #[serde(rename = "TwnLctnNm", skip_serializing_if = "Option::is_none")]
+    pub twn_lctn_nm: Option<String>,
+    #[serde(rename = "DstrctNm", skip_serializing_if = "Option::is_none")]
+    pub dstrct_nm: Option<String>,
+    #[serde(rename = "CtrySubDvsn", skip_serializing_if = "Option::is_none")]
+    pub ctry_sub_dvsn: Option<String>,
+    #[serde(rename = "Ctry", skip_serializing_if = "Option::is_none")]
+    pub ctry: Option<String>,
+    #[serde(rename = "AdrLine", default)]
+    pub adr_line: Vec<String>,
+}
+
+/// Address formats of the countries, by post code pattern, building number first, post
+/// code first and country subdivision before the post code.
+const ADDRESS_FORMATS: &[(&[&str], &str, bool, bool, bool)] = &[
+    (&["DE", "IT", "ES", "FI"], r"\d{5}", false, true, false),
+    (
+        &["AT", "CH", "LI", "BE", "LU", "DK", "NO"],
+        r"\d{4}",
+        false,
+        true,
+        false,
+    ),
+    (&["NL"], r"\d{4}\s?[A-Z]{2}", false, true, false),
+    (&["SE", "CZ", "SK"], r"\d{3}\s?\d{2}", false, true, false),
+    (&["PL"], r"\d{2}-\d{3}", false, true, false),
+    (&["PT"], r"\d{4}-\d{3}", false, true, false),
+    (&["FR", "MC"], r"\d{5}", true, true, false),
+    (&["US"], r"\d{5}(?:-\d{4})?", true, false, true),
+    (&["CA"], r"[A-Z]\d[A-Z]\s?\d[A-Z]\d", true, false, true),
+    (&["AU"], r"\d{4}", true, false, true),
+    (
+        &["GB"],
+        r"[A-Z]{1,2}\d[A-Z\d]?\s?\d[A-Z]{2}",
+        true,
+        false,
+        false,
+    ),
+    (&["IE"], r"[A-Z]\d{2}\s?[A-Z\d]{4}", true, false, false),
+];
+
+lazy_static! {
+    static ref COUNTRY_ADDRESS_FORMATS: HashMap<&'static str, AddressFormat> = ADDRESS_FORMATS
+        .iter()
+        .flat_map(
+            |(ctrys, pst_cd, number_first, pst_cd_first, ctry_sub_dvsn)| {
+                ctrys.iter().map(move |ctry| {
+                    (
+                        *ctry,
+                        AddressFormat::new(pst_cd, *number_first, *pst_cd_first, *ctry_sub_dvsn),
+                    )
+                })
+            }
+        )
+        .collect();
+    static ref OTHER_ADDRESS_FORMAT: AddressFormat =
+        AddressFormat::new(r"\d{3,6}", false, true, false);
+}
+
+/// Address format of a country.
+struct AddressFormat {
+    /// The building number precedes the street name, e.g. `10 Downing Street`
+    number_first: bool,
+    /// Pattern of a line with the town name and the post code
+    town_line: Regex,
+    /// Pattern of a line with the post code only
+    post_code_line: Regex,
+}
+
+impl AddressFormat {
+    /// Compile the line patterns of an address format, where the post code precedes the
+    /// town name with `pst_cd_first`, e.g. `8001 Zürich`, and the country subdivision
+    /// precedes the post code with `ctry_sub_dvsn`, e.g. `Springfield, IL 62704`.
+    fn new(pst_cd: &str, number_first: bool, pst_cd_first: bool, ctry_sub_dvsn: bool) -> Self {
+        let town_line = match (pst_cd_first, ctry_sub_dvsn) {
+            (true, _) => format!(r"^(?:[A-Z]{{1,2}}-)?(?P<pc>{})\s+(?P<twn>.+)$", pst_cd),
+            (false, true) => format!(
+                r"^(?P<twn>.+?),?\s+(?P<sub>[A-Z]{{2,3}})\s+(?P<pc>{})$",
+                pst_cd
+            ),
+            (false, false) => format!(r"^(?P<twn>.+?),?\s+(?P<pc>{})$", pst_cd),
+        };
+
+        Self {
+            number_first,
+            town_line: Regex::new(&town_line).unwrap(),
+            post_code_line: Regex::new(&format!(r"^(?P<pc>{})$", pst_cd)).unwrap(),
+        }
+    }
+
+    fn of(ctry: Option<&str>) -> &'static Self {
+        ctry.and_then(|ctry| COUNTRY_ADDRESS_FORMATS.get(ctry))
+            .unwrap_or(&OTHER_ADDRESS_FORMAT)
+    }
+
+    /// Return the pattern of a line with the street name and building number.
+    fn street_line(&self) -> &'static Regex {
+        if self.number_first {
+            &NUMBER_BEFORE_STREET
+        } else {
+            &NUMBER_AFTER_STREET
+        }
+    }
+}
+
+impl PostalAddress {
+    /// Parse the address lines into the structured elements, with the address format of the
+    /// country, i.e. `Ctry`, or the country of the last address line.
+    ///
+    /// Structured elements already present are kept, and the lines that cannot be parsed
+    /// are kept as address lines.
+    pub fn structure(&self) -> (Self, Confidence) {
+        let mut address = self.clone();
+        let mut lines = address
+            .adr_line
+            .drain(..)
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        if address.ctry.is_none() {
+            if let Some(ctry) = lines.last().and_then(|line| country_code(line)) {
+                address.ctry = Some(ctry.to_string());
+                lines.pop();
+            }
+        }
+
+        let format = AddressFormat::of(address.ctry.as_deref());
+
+        if address.twn_nm.is_none() {
+            let town_line = &format.town_line;
+            let post_code_line = &format.post_code_line;
+
+            for index in (0..lines.len()).rev() {
+                if let Some(captures) = town_line.captures(&lines[index]) {
+                    let twn_nm = captures["twn"].trim().trim_end_matches(',');
+
+                    if twn_nm.chars().count() <= MAX_35_TEXT {
+                        address.twn_nm = Some(twn_nm.to_string());
+                        address.pst_cd = Some(captures["pc"].to_string());
+                        address.ctry_sub_dvsn = captures
+                            .name("sub")
+                            .map(|sub| sub.as_str().to_string())
+                            .or(address.ctry_sub_dvsn);
+                        lines.remove(index);
+                        break;
+                    }
+                }
+
+                // A post code on its own line, after the town name, e.g. in GB
+                if index > 0 && post_code_line.is_match(&lines[index]) {
+                    let twn_nm = lines[index - 1].clone();
+
+                    if twn_nm.chars().count() <= MAX_35_TEXT && !twn_nm.contains(char::is_numeric) {
+                        address.pst_cd = Some(lines.remove(index));
+                        address.twn_nm = Some(twn_nm);
+                        lines.remove(index - 1);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if address.strt_nm.is_none() && address.pst_bx.is_none() {
+            let street_line = format.street_line();
+
+            for index in 0..lines.len() {
+                if let Some(captures) = POST_BOX.captures(&lines[index]) {
+                    if captures["pb"].len() <= MAX_16_TEXT {
+                        address.pst_bx = Some(captures["pb"].to_string());
+                        lines.remove(index);
+                        break;
+                    }
+                }
+
+                if let Some(captures) = street_line.captures(&lines[index]) {
+                    let strt_nm = captures["strt"].trim();
+                    let bldg_nb = captures["nb"].replace(' ', "");
+
+                    if strt_nm.chars().count() <= MAX_70_TEXT && bldg_nb.len() <= MAX_16_TEXT {
+                        address.strt_nm = Some(strt_nm.to_string());
+                        address.bldg_nb = Some(bldg_nb);
+                        lines.remove(index);
+                        break;
+                    }
+                }
+            }
+        }
+
+        address.adr_line = lines;
+
+        let confidence = address.confidence();
+
+        (address, confidence)
+    }
+
+    /// Return the confidence of the structured address.
+    pub fn confidence(&self) -> Confidence {
+        if self.twn_nm.is_none() || self.ctry.is_none() {
+            Confidence::Low
+        } else if self.adr_line.is_empty()
+            && (self.strt_nm.is_some() || self.pst_bx.is_some())
+            && self.pst_cd.is_some()
+        {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        }
+    }
+
+    /// Validate the hybrid address rules, i.e. `TwnNm` and `Ctry` are mandatory, with at
+    /// most two `AdrLine` of 70 characters.
+    pub fn check_hybrid(&self) -> Vec<AddressIssue> {
+        let mut issues = vec![];
+
+        if self.twn_nm.is_none() || self.twn_nm.as_deref() == Some("") {
+            issues.push(AddressIssue::MissingTwnNm);
+        }
+
+        match &self.ctry {
+            None => issues.push(AddressIssue::MissingCtry),
+            Some(ctry) if ctry.len() != 2 || !ctry.chars().all(|c| c.is_ascii_uppercase()) => {
+                issues.push(AddressIssue::InvalidCtry(ctry.clone()))
+            }
+            Some(_) => {}
+        }
+
+        if self.adr_line.len() > MAX_HYBRID_ADDRESS_LINES {
+            issues.push(AddressIssue::TooManyAdrLines(self.adr_line.len()));
+        }
+
+        issues.extend(
+            self.adr_line
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.chars().count() > MAX_70_TEXT)
+                .map(|(index, _)| AddressIssue::AdrLineTooLong(index)),
+        );
+
+        issues
+    }
+}
+
+/// Postal address of a document, converted to structured elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressConversion {
+    /// Path of the postal address in the document, e.g. `CdtTrfTxInf/Dbtr/PstlAdr`
+    pub path: String,
+    /// Structured postal address
+    pub address: PostalAddress,
+    /// Confidence of the structured address
+    pub confidence: Confidence,
+    /// Issues of the hybrid address rules of the structured address
+    pub issues: Vec<AddressIssue>,
+}
+
+/// Structure the postal address of any message set, e.g. a `PostalAddress24`, keeping its
+/// address type `AdrTp`.
+pub fn structure_postal_address<P>(address: &P) -> Result<(P, Confidence), Error>
+where
+    P: Serialize + DeserializeOwned,
+{
+    let mut confidence = Confidence::Low;
+
+    let xml = rewrite_postal_addresses(
+        &quick_xml::se::to_string_with_root(POSTAL_ADDRESS, address)?,
+        |_, address| {
+            let (address, address_confidence) = address.structure();
+            confidence = address_confidence;
+            address
+        },
+    )?;
+
+    Ok((quick_xml::de::from_reader(xml.as_slice())?, confidence))
+}
+
+/// Structure every postal address `PstlAdr` of the document.
+pub fn structure_document_addresses<D>(document: &D) -> Result<(D, Vec<AddressConversion>), Error>
+where
+    D: Serialize + DeserializeOwned,
+{
+    let mut conversions = vec![];
+
+    let xml = rewrite_postal_addresses(&quick_xml::se::to_string(document)?, |path, address| {
+        let (address, confidence) = address.structure();
+
+        conversions.push(AddressConversion {
+            path: path.to_string(),
+            issues: address.check_hybrid(),
+            address: address.clone(),
+            confidence,
+        });
+
+        address
+    })?;
+
+    Ok((quick_xml::de::from_reader(xml.as_slice())?, conversions))
+}
+
+/// Validate the hybrid address rules of every postal address `PstlAdr` of the document,
+/// returning the path and the issues of the addresses with issues.
+pub fn check_document_addresses<D: Serialize>(
+    document: &D,
+) -> Result<Vec<(String, Vec<AddressIssue>)>, Error> {
+    let mut issues = vec![];
+
+    rewrite_postal_addresses(&quick_xml::se::to_string(document)?, |path, address| {
+        let address_issues = address.check_hybrid();
+
+        if !address_issues.is_empty() {
+            issues.push((path.to_string(), address_issues));
+        }
+
+        address
+    })?;
+
+    Ok(issues)
+}
+
+/// Rewrite the elements of every postal address `PstlAdr` of the XML document, keeping the
+/// address type `AdrTp` and writing the elements in the order of `PostalAddress24`.
+fn rewrite_postal_addresses<F>(xml: &str, mut rewrite: F) -> Result<Vec<u8>, Error>
+where
+    F: FnMut(&str, PostalAddress) -> PostalAddress,
+{
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::new());
+    let mut path = vec![];
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) if start.local_name().as_ref() == POSTAL_ADDRESS.as_bytes() => {
+                path.push(POSTAL_ADDRESS.to_string());
+
+                let (address, adr_tp) = read_postal_address(&mut reader)?;
+                let address = rewrite(&path[1..].join("/"), address);
+
+                writer.write_event(Event::Start(start.clone()))?;
+                for event in adr_tp {
+                    writer.write_event(event)?;
+                }
+                write_postal_address(&mut writer, &address)?;
+                writer.write_event(Event::End(start.to_end()))?;
+
+                path.pop();
+            }
+            Event::Start(start) => {
+                path.push(String::from_utf8_lossy(start.local_name().as_ref()).into_owned());
+                writer.write_event(Event::Start(start))?;
+            }
+            Event::End(end) => {
+                path.pop();
+                writer.write_event(Event::End(end))?;
+            }
+            Event::Eof => break,
+            event => writer.write_event(event)?,
+        }
+    }
+
+    Ok(writer.into_inner())
+}
+
+/// Read the elements of a postal address, up to its end, returning the address and the
+/// events of the address type `AdrTp`.
+fn read_postal_address(
+    reader: &mut Reader<&[u8]>,
+) -> Result<(PostalAddress, Vec<Event<'static>>), Error> {
+    let mut address = PostalAddress::default();
+    let mut adr_tp = vec![];
+
+    loop {
+        let (name, value) = match reader.read_event()? {
+            Event::Start(start) if start.local_name().as_ref() == b"AdrTp" => {
+                let end = start.to_end().into_owned();
+                adr_tp.push(Event::Start(start.into_owned()));
+
+                let mut depth = 0;
+                loop {
+                    let event = reader.read_event()?.into_owned();
+                    match &event {
+                        Event::Start(_) => depth += 1,
+                        Event::End(_) if depth == 0 => break,
+                        Event::End(_) => depth -= 1,
+                        Event::Eof => return Err(unexpected_eof()),
+                        _ => {}
+                    }
+                    adr_tp.push(event);
+                }
+                adr_tp.push(Event::End(end));
+
+                continue;
+            }
+            Event::Start(start) => {
+                let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+                let mut value = String::new();
+
+                loop {
+                    match reader.read_event()? {
+                        Event::Text(text) => value.push_str(&text.unescape()?),
+                        Event::CData(cdata) => {
+                            value.push_str(&String::from_utf8_lossy(&cdata.into_inner()))
+                        }
+                        Event::End(_) => break,
+                        Event::Eof => return Err(unexpected_eof()),
+                        _ => {}
+                    }
+                }
+
+                (name, value)
+            }
+            Event::Empty(start) => (
+                String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+                String::new(),
+            ),
+            Event::End(_) => break,
+            Event::Eof => return Err(unexpected_eof()),
+            _ => continue,
+        };
+
+        let field = match name.as_str() {
+            "Dept" => &mut address.dept,
+            "SubDept" => &mut address.sub_dept,
+            "StrtNm" => &mut address.strt_nm,
+            "BldgNb" => &mut address.bldg_nb,
+            "BldgNm" => &mut address.bldg_nm,
+            "Flr" => &mut address.flr,
+            "PstBx" => &mut address.pst_bx,
+            "Room" => &mut address.room,
+            "PstCd" => &mut address.pst_cd,
+            "TwnNm" => &mut address.twn_nm,
+            "TwnLctnNm" => &mut address.twn_lctn_nm,
+            "DstrctNm" => &mut address.dstrct_nm,
+            "CtrySubDvsn" => &mut address.ctry_sub_dvsn,
+            "Ctry" => &mut address.ctry,
+            "AdrLine" => {
+                address.adr_line.push(value);
+                continue;
+            }
+            _ => continue,
+        };
+
+        *field = Some(value);
+    }
+
+    Ok((address, adr_tp))
+}
+
+/// Write the elements of the postal address, in the order of `PostalAddress24`.
+fn write_postal_address(
+    writer: &mut Writer<Vec<u8>>,
+    address: &PostalAddress,
+) -> Result<(), Error> {
+    let fields = [
+        ("Dept", &address.dept),
+        ("SubDept", &address.sub_dept),
+        ("StrtNm", &address.strt_nm),
+        ("BldgNb", &address.bldg_nb),
+        ("BldgNm", &address.bldg_nm),
+        ("Flr", &address.flr),
+        ("PstBx", &address.pst_bx),
+        ("Room", &address.room),
+        ("PstCd", &address.pst_cd),
+        ("TwnNm", &address.twn_nm),
+        ("TwnLctnNm", &address.twn_lctn_nm),
+        ("DstrctNm", &address.dstrct_nm),
+        ("CtrySubDvsn", &address.ctry_sub_dvsn),
+        ("Ctry", &address.ctry),
+    ];
+
+    let values = fields
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, Cow::from(value.as_deref()?))))
+        .chain(
+            address
+                .adr_line
+                .iter()
+                .map(|line| ("AdrLine", Cow::from(line.as_str()))),
+        );
+
+    for (name, value) in values {
+        writer.write_event(Event::Start(BytesStart::new(name)))?;
+        writer.write_event(Event::Text(BytesText::new(&value)))?;
+        writer.write_event(Event::End(BytesEnd::new(name)))?;
+    }
+
+    Ok(())
+}
+
+fn unexpected_eof() -> Error {
+    Error::Xml(quick_xml::Error::UnexpectedEof(POSTAL_ADDRESS.to_string()))
+}
+
+/// Return the ISO 3166 alpha-2 code of a country line, i.e. a code or the name of a country
+/// with a known address format.
+fn country_code(line: &str) -> Option<&'static str> {
+    const COUNTRIES: [(&str, &[&str]); 24] = [
+        ("AT", &["AUSTRIA", "ÖSTERREICH", "OESTERREICH"]),
+        ("AU", &["AUSTRALIA"]),
+        (
+            "BE",
+            &["BELGIUM", "BELGIQUE", "BELGIË", "BELGIE", "BELGIEN"],
+        ),
+        ("CA", &["CANADA"]),
+        ("CH", &["SWITZERLAND", "SCHWEIZ", "SUISSE", "SVIZZERA"]),
+        ("CZ", &["CZECH REPUBLIC", "CZECHIA", "ČESKO"]),
+        ("DE", &["GERMANY", "DEUTSCHLAND"]),
+        ("DK", &["DENMARK", "DANMARK"]),
+        ("ES", &["SPAIN", "ESPAÑA", "ESPANA"]),
+        ("FI", &["FINLAND", "SUOMI"]),
+        ("FR", &["FRANCE"]),
+        (
+            "GB",
+            &[
+                "UNITED KINGDOM",
+                "UK",
+                "GREAT BRITAIN",
+                "ENGLAND",
+                "SCOTLAND",
+                "WALES",
+            ],
+        ),
+        ("IE", &["IRELAND", "ÉIRE", "EIRE"]),
+        ("IT", &["ITALY", "ITALIA"]),
+        ("LI", &["LIECHTENSTEIN"]),
+        ("LU", &["LUXEMBOURG", "LUXEMBURG"]),
+        ("MC", &["MONACO"]),
+        ("NL", &["NETHERLANDS", "THE NETHERLANDS", "NEDERLAND"]),
+        ("NO", &["NORWAY", "NORGE"]),
+        ("PL", &["POLAND", "POLSKA"]),
+        ("PT", &["PORTUGAL"]),
+        ("SE", &["SWEDEN", "SVERIGE"]),
+        ("SK", &["SLOVAKIA", "SLOVENSKO"]),
+        (
+            "US",
+            &["UNITED STATES", "UNITED STATES OF AMERICA", "USA", "U.S.A."],
+        ),
+    ];
+
+    let line = line.trim().to_uppercase();
+
+    COUNTRIES
+        .iter()
+        .find(|(code, names)| *code == line || names.contains(&line.as_str()))
+        .map(|(code, _)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename = "Document")]
+    struct TestDocument {
+        #[serde(rename = "Dbtr")]
+        dbtr: TestParty,
+        #[serde(rename = "Cdtr")]
+        cdtr: TestParty,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct TestParty {
+        #[serde(rename = "Nm")]
+        nm: String,
+        #[serde(rename = "PstlAdr")]
+        pstl_adr: PostalAddress,
+    }
+
+    fn unstructured(lines: &[&str]) -> PostalAddress {
+        PostalAddress {
+            adr_line: lines.iter().map(|line| line.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_structure_by_country_format() {
+        let (address, confidence) =
+            unstructured(&["Bahnhofstrasse 45a", "CH-8001 Zürich", "Switzerland"]).structure();
+        assert_eq!(confidence, Confidence::High);
+        assert_eq!(address.strt_nm.as_deref(), Some("Bahnhofstrasse"));
+        assert_eq!(address.bldg_nb.as_deref(), Some("45a"));
+        assert_eq!(address.pst_cd.as_deref(), Some("8001"));
+        assert_eq!(address.twn_nm.as_deref(), Some("Zürich"));
+        assert_eq!(address.ctry.as_deref(), Some("CH"));
+        assert!(address.adr_line.is_empty());
+
+        let (address, confidence) =
+            unstructured(&["1600 Pennsylvania Avenue NW", "Washington, DC 20500", "USA"])
+                .structure();
+        assert_eq!(confidence, Confidence::High);
+        assert_eq!(address.strt_nm.as_deref(), Some("Pennsylvania Avenue NW"));
+        assert_eq!(address.bldg_nb.as_deref(), Some("1600"));
+        assert_eq!(address.twn_nm.as_deref(), Some("Washington"));
+        assert_eq!(address.ctry_sub_dvsn.as_deref(), Some("DC"));
+        assert_eq!(address.pst_cd.as_deref(), Some("20500"));
+
+        let (address, confidence) = PostalAddress {
+            ctry: Some("GB".to_string()),
+            ..unstructured(&[
+                "Acme Ltd, Floor 3",
+                "10 Downing Street",
+                "London",
+                "SW1A 2AA",
+            ])
+        }
+        .structure();
+        assert_eq!(confidence, Confidence::Medium);
+        assert_eq!(address.strt_nm.as_deref(), Some("Downing Street"));
+        assert_eq!(address.bldg_nb.as_deref(), Some("10"));
+        assert_eq!(address.twn_nm.as_deref(), Some("London"));
+        assert_eq!(address.pst_cd.as_deref(), Some("SW1A 2AA"));
+        assert_eq!(address.adr_line, vec!["Acme Ltd, Floor 3".to_string()]);
+
+        let (address, confidence) = PostalAddress {
+            ctry: Some("GB".to_string()),
+            ..unstructured(&["Downing Street", "London", "SW1A 2AA"])
+        }
+        .structure();
+        assert_eq!(confidence, Confidence::Medium);
+        assert_eq!(address.strt_nm, None);
+        assert_eq!(address.twn_nm.as_deref(), Some("London"));
+        assert_eq!(address.adr_line, vec!["Downing Street".to_string()]);
+
+        let (address, confidence) = unstructured(&["Somewhere without a town"]).structure();
+        assert_eq!(confidence, Confidence::Low);
+        assert_eq!(
+            address.check_hybrid(),
+            vec![AddressIssue::MissingTwnNm, AddressIssue::MissingCtry]
+        );
+    }
+
+    #[test]
+    fn test_check_hybrid() {
+        let address = PostalAddress {
+            twn_nm: Some("Berlin".to_string()),
+            ctry: Some("de".to_string()),
+            ..unstructured(&["Line 1", "Line 2", &"x".repeat(71)])
+        };
+
+        assert_eq!(
+            address.check_hybrid(),
+            vec![
+                AddressIssue::InvalidCtry("de".to_string()),
+                AddressIssue::TooManyAdrLines(3),
+                AddressIssue::AdrLineTooLong(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_structure_document_addresses() {
+        let document = TestDocument {
+            dbtr: TestParty {
+                nm: "Debtor".to_string(),
+                pstl_adr: unstructured(&["Hauptstraße 1", "10115 Berlin", "DE"]),
+            },
+            cdtr: TestParty {
+                nm: "Creditor".to_string(),
+                pstl_adr: unstructured(&["Unknown"]),
+            },
+        };
+
+        let issues = check_document_addresses(&document).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].0, "Dbtr/PstlAdr");
+
+        let (document, conversions) = structure_document_addresses(&document).unwrap();
+        assert_eq!(conversions.len(), 2);
+        assert_eq!(conversions[0].path, "Dbtr/PstlAdr");
+        assert_eq!(conversions[0].confidence, Confidence::High);
+        assert!(conversions[0].issues.is_empty());
+        assert_eq!(conversions[1].confidence, Confidence::Low);
+
+        assert_eq!(document.dbtr.nm, "Debtor");
+        assert_eq!(
+            document.dbtr.pstl_adr.strt_nm.as_deref(),
+            Some("Hauptstraße")
+        );
+        assert_eq!(document.dbtr.pstl_adr.twn_nm.as_deref(), Some("Berlin"));
+        assert_eq!(document.dbtr.pstl_adr.ctry.as_deref(), Some("DE"));
+        assert_eq!(document.cdtr.pstl_adr.strt_nm, None);
+        assert_eq!(document.cdtr.pstl_adr.adr_line, vec!["Unknown".to_string()]);
+        assert!(check_document_addresses(&document).unwrap().len() == 1);
+    }
+}
@@ -0,0 +1,576 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Batch Splitting and Merging
+//!
+//! The `Batch` trait is implemented by the messages whose transactions, or blocks of
+//! transactions, can be moved between messages of the same type:
+//!
+//! | Message           | Item                               | Group header totals                       |
+//! |-------------------|------------------------------------|-------------------------------------------|
+//! | `pain.001.001.11` | payment information block `PmtInf` | `NbOfTxs`, `CtrlSum`                      |
+//! | `pain.008.001.10` | payment information block `PmtInf` | `NbOfTxs`, `CtrlSum`                      |
+//! | `pacs.008.001.10` | transaction `CdtTrfTxInf`          | `NbOfTxs`, `CtrlSum`, `TtlIntrBkSttlmAmt` |
+//! | `pacs.003.001.09` | transaction `DrctDbtTxInf`         | `NbOfTxs`, `CtrlSum`, `TtlIntrBkSttlmAmt` |
+//! | `camt.054.001.10` | notification `Ntfctn`              | -                                         |
+//!
+//! `split` and `merge` group the items by a caller-supplied key, and return one message per
+//! key, with a new message identification `MsgId` of the `MessageIdStrategy` and the totals of
+//! its group header updated. `NbOfTxs` is always updated, `CtrlSum` and `TtlIntrBkSttlmAmt`
+//! only when present in the original message. The other elements of the group header are
+//! those of the first message of the key, i.e. the key of a merge should include the elements
+//! of the group header that may differ between the messages, e.g. `IntrBkSttlmDt`.
+//!
+//! ```rust
+//! use iso_20022_sdk::batch::{merge, split, IndexSuffix};
+//!
+//! // Split a pain.001 by debtor account and requested execution date
+//! let initns = split(
+//!     &pain_001.cstmr_cdt_trf_initn,
+//!     |_, pmt_inf| (pmt_inf.dbtr_acct.clone(), pmt_inf.reqd_exctn_dt.clone()),
+//!     &mut IndexSuffix,
+//! )?;
+//!
+//! // Merge pacs.008 messages by interbank settlement date and instructed agent
+//! let mut next = 0;
+//! let transfers = merge(
+//!     &transfers,
+//!     |msg, tx| {
+//!         (
+//!             tx.intr_bk_sttlm_dt.clone().or(msg.grp_hdr.intr_bk_sttlm_dt.clone()),
+//!             tx.instd_agt.clone().or(msg.grp_hdr.instd_agt.clone()),
+//!         )
+//!     },
+//!     &mut |_: &str, _: usize| {
+//!         next += 1;
+//!         format!("BATCH-{}", next)
+//!     },
+//! )?;
+//! ```
+use iso_20022_camt::camt_054_001_10 as camt_054;
+use iso_20022_pacs::pacs_003_001_09 as pacs_003;
+use iso_20022_pacs::pacs_008_001_10 as pacs_008;
+use iso_20022_pain::pain_001_001_11 as pain_001;
+use iso_20022_pain::pain_008_001_10 as pain_008;
+use serde::Serialize;
+
+use crate::mapping::sum;
+
+/// Maximum length of a message identification `Max35Text`.
+const MAX_MSG_ID_LEN: usize = 35;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The transactions of a message have different currencies, and cannot be totalled in
+    /// `TtlIntrBkSttlmAmt`
+    #[error("expected currency {expected}, found {found}")]
+    CurrencyMismatch { expected: String, found: String },
+}
+
+/// Message whose items can be split into, or merged from, messages of the same type.
+pub trait Batch: Clone {
+    /// Item moved between messages, e.g. a payment information block `PmtInf`
+    type Item: Clone;
+
+    /// Return the message identification `GrpHdr/MsgId`.
+    fn msg_id(&self) -> &str;
+
+    /// Set the message identification `GrpHdr/MsgId`.
+    fn set_msg_id(&mut self, msg_id: String);
+
+    /// Return the items of the message.
+    fn items(&self) -> &[Self::Item];
+
+    /// Replace the items of the message, and update the totals of the group header.
+    fn set_items(&mut self, items: Vec<Self::Item>) -> Result<(), Error>;
+}
+
+/// Strategy generating the message identifications of the messages of a split or a merge.
+pub trait MessageIdStrategy {
+    /// Return the message identification of the `index`th message, starting at 0, produced
+    /// from the message identified by `orgnl_msg_id`, i.e. the first message of the key of a
+    /// merge.
+    fn msg_id(&mut self, orgnl_msg_id: &str, index: usize) -> String;
+}
+
+impl<F> MessageIdStrategy for F
+where
+    F: FnMut(&str, usize) -> String,
+{
+    fn msg_id(&mut self, orgnl_msg_id: &str, index: usize) -> String {
+        self(orgnl_msg_id, index)
+    }
+}
+
+/// Suffix the original message identification with the position of the message, e.g.
+/// `MSG-1-2` for the second message produced from `MSG-1`, truncating the original message
+/// identification to 35 characters.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexSuffix;
+
+impl MessageIdStrategy for IndexSuffix {
+    fn msg_id(&mut self, orgnl_msg_id: &str, index: usize) -> String {
+        let suffix = format!("-{}", index + 1);
+        let prefix: String = orgnl_msg_id
+            .chars()
+            .take(MAX_MSG_ID_LEN.saturating_sub(suffix.len()))
+            .collect();
+
+        format!("{}{}", prefix, suffix)
+    }
+}
+
+/// Split a message into one message per key of its items, in the order of the first item
+/// of each key.
+pub fn split<T, K, F, S>(msg: &T, key: F, msg_ids: &mut S) -> Result<Vec<T>, Error>
+where
+    T: Batch,
+    K: PartialEq,
+    F: Fn(&T, &T::Item) -> K,
+    S: MessageIdStrategy,
+{
+    batches(std::slice::from_ref(msg), key, msg_ids)
+}
+
+/// Merge messages into one message per key of their items, in the order of the first item
+/// of each key.
+pub fn merge<T, K, F, S>(msgs: &[T], key: F, msg_ids: &mut S) -> Result<Vec<T>, Error>
+where
+    T: Batch,
+    K: PartialEq,
+    F: Fn(&T, &T::Item) -> K,
+    S: MessageIdStrategy,
+{
+    batches(msgs, key, msg_ids)
+}
+
+fn batches<T, K, F, S>(msgs: &[T], key: F, msg_ids: &mut S) -> Result<Vec<T>, Error>
+where
+    T: Batch,
+    K: PartialEq,
+    F: Fn(&T, &T::Item) -> K,
+    S: MessageIdStrategy,
+{
+    // Keys are compared with `PartialEq` only, the generated types do not implement `Hash`
+    let mut groups: Vec<(K, &T, Vec<T::Item>)> = vec![];
+
+    for msg in msgs.iter() {
+        for item in msg.items().iter() {
+            let item_key = key(msg, item);
+            match groups.iter_mut().find(|(key, _, _)| *key == item_key) {
+                Some((_, _, items)) => items.push(item.clone()),
+                None => groups.push((item_key, msg, vec![item.clone()])),
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, template, items))| {
+            let mut msg = template.clone();
+            msg.set_msg_id(msg_ids.msg_id(template.msg_id(), index));
+            msg.set_items(items)?;
+
+            Ok(msg)
+        })
+        .collect()
+}
+
+/// Return the currency shared by all the currencies, or `Error::CurrencyMismatch`.
+fn currency<'a>(mut ccys: impl Iterator<Item = &'a str>) -> Result<Option<&'a str>, Error> {
+    let expected = match ccys.next() {
+        Some(expected) => expected,
+        None => return Ok(None),
+    };
+
+    match ccys.find(|ccy| *ccy != expected) {
+        Some(found) => Err(Error::CurrencyMismatch {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }),
+        None => Ok(Some(expected)),
+    }
+}
+
+impl<A, B> Batch for pain_001::CustomerCreditTransferInitiationV11<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    type Item = pain_001::PaymentInstruction40<A>;
+
+    fn msg_id(&self) -> &str {
+        &self.grp_hdr.msg_id.value
+    }
+
+    fn set_msg_id(&mut self, msg_id: String) {
+        self.grp_hdr.msg_id.value = msg_id;
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        &self.pmt_inf
+    }
+
+    fn set_items(&mut self, items: Vec<Self::Item>) -> Result<(), Error> {
+        let txs = || {
+            items
+                .iter()
+                .flat_map(|pmt_inf| pmt_inf.cdt_trf_tx_inf.iter())
+        };
+
+        self.grp_hdr.nb_of_txs.value = txs().count().to_string();
+        if let Some(ctrl_sum) = self.grp_hdr.ctrl_sum.as_mut() {
+            // The amount of an equivalent amount transaction is in the currency of the debtor
+            ctrl_sum.value = sum(txs().filter_map(|tx| {
+                let amt = &tx.amt.value;
+                amt.instd_amt
+                    .as_ref()
+                    .or(amt.eqvt_amt.as_ref().map(|eqvt_amt| &eqvt_amt.amt))
+                    .map(|amt| amt.value.value)
+            }));
+        }
+        self.pmt_inf = items;
+
+        Ok(())
+    }
+}
+
+impl<A, B> Batch for pain_008::CustomerDirectDebitInitiationV10<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    type Item = pain_008::PaymentInstruction39<A>;
+
+    fn msg_id(&self) -> &str {
+        &self.grp_hdr.msg_id.value
+    }
+
+    fn set_msg_id(&mut self, msg_id: String) {
+        self.grp_hdr.msg_id.value = msg_id;
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        &self.pmt_inf
+    }
+
+    fn set_items(&mut self, items: Vec<Self::Item>) -> Result<(), Error> {
+        let txs = || {
+            items
+                .iter()
+                .flat_map(|pmt_inf| pmt_inf.drct_dbt_tx_inf.iter())
+        };
+
+        self.grp_hdr.nb_of_txs.value = txs().count().to_string();
+        if let Some(ctrl_sum) = self.grp_hdr.ctrl_sum.as_mut() {
+            ctrl_sum.value = sum(txs().map(|tx| tx.instd_amt.value.value));
+        }
+        self.pmt_inf = items;
+
+        Ok(())
+    }
+}
+
+impl<A, B> Batch for pacs_008::FiToFiCustomerCreditTransferV10<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    type Item = pacs_008::CreditTransferTransaction50<A>;
+
+    fn msg_id(&self) -> &str {
+        &self.grp_hdr.msg_id.value
+    }
+
+    fn set_msg_id(&mut self, msg_id: String) {
+        self.grp_hdr.msg_id.value = msg_id;
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        &self.cdt_trf_tx_inf
+    }
+
+    fn set_items(&mut self, items: Vec<Self::Item>) -> Result<(), Error> {
+        let grp_hdr = &mut self.grp_hdr;
+        let total = sum(items.iter().map(|tx| tx.intr_bk_sttlm_amt.value.value));
+
+        grp_hdr.nb_of_txs.value = items.len().to_string();
+        if let Some(ctrl_sum) = grp_hdr.ctrl_sum.as_mut() {
+            ctrl_sum.value = total;
+        }
+        if let Some(ttl_intr_bk_sttlm_amt) = grp_hdr.ttl_intr_bk_sttlm_amt.as_mut() {
+            let ccy = currency(
+                items
+                    .iter()
+                    .map(|tx| tx.intr_bk_sttlm_amt.ccy.value.as_str()),
+            )?;
+            if let Some(ccy) = ccy {
+                ttl_intr_bk_sttlm_amt.ccy.value = ccy.to_string();
+            }
+            ttl_intr_bk_sttlm_amt.value.value = total;
+        }
+        self.cdt_trf_tx_inf = items;
+
+        Ok(())
+    }
+}
+
+impl<A, B> Batch for pacs_003::FiToFiCustomerDirectDebitV09<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    type Item = pacs_003::DirectDebitTransactionInformation29<A>;
+
+    fn msg_id(&self) -> &str {
+        &self.grp_hdr.msg_id.value
+    }
+
+    fn set_msg_id(&mut self, msg_id: String) {
+        self.grp_hdr.msg_id.value = msg_id;
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        &self.drct_dbt_tx_inf
+    }
+
+    fn set_items(&mut self, items: Vec<Self::Item>) -> Result<(), Error> {
+        let grp_hdr = &mut self.grp_hdr;
+        let total = sum(items.iter().map(|tx| tx.intr_bk_sttlm_amt.value.value));
+
+        grp_hdr.nb_of_txs.value = items.len().to_string();
+        if let Some(ctrl_sum) = grp_hdr.ctrl_sum.as_mut() {
+            ctrl_sum.value = total;
+        }
+        if let Some(ttl_intr_bk_sttlm_amt) = grp_hdr.ttl_intr_bk_sttlm_amt.as_mut() {
+            let ccy = currency(
+                items
+                    .iter()
+                    .map(|tx| tx.intr_bk_sttlm_amt.ccy.value.as_str()),
+            )?;
+            if let Some(ccy) = ccy {
+                ttl_intr_bk_sttlm_amt.ccy.value = ccy.to_string();
+            }
+            ttl_intr_bk_sttlm_amt.value.value = total;
+        }
+        self.drct_dbt_tx_inf = items;
+
+        Ok(())
+    }
+}
+
+impl<A, B> Batch for camt_054::BankToCustomerDebitCreditNotificationV10<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    type Item = camt_054::AccountNotification20<A>;
+
+    fn msg_id(&self) -> &str {
+        &self.grp_hdr.msg_id.value
+    }
+
+    fn set_msg_id(&mut self, msg_id: String) {
+        self.grp_hdr.msg_id.value = msg_id;
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        &self.ntfctn
+    }
+
+    fn set_items(&mut self, items: Vec<Self::Item>) -> Result<(), Error> {
+        // The totals `TxsSummry` are per notification, but the pagination of the original
+        // message no longer applies
+        self.grp_hdr.msg_pgntn = None;
+        self.ntfctn = items;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::documents::Dmkr;
+
+    fn pmt_inf(id: &str, dt: &str, amounts: &[f64]) -> pain_001::PaymentInstruction40<Dmkr> {
+        pain_001::PaymentInstruction40 {
+            pmt_inf_id: pain_001::Max35Text {
+                value: id.to_string(),
+            },
+            reqd_exctn_dt: pain_001::DateAndDateTime2Choice {
+                value: pain_001::DateAndDateTime2ChoiceEnum {
+                    dt: Some(pain_001::IsoDate {
+                        value: NaiveDate::parse_from_str(dt, "%Y-%m-%d").unwrap(),
+                    }),
+                    ..Default::default()
+                },
+            },
+            cdt_trf_tx_inf: amounts
+                .iter()
+                .map(|amount| pain_001::CreditTransferTransaction54 {
+                    amt: pain_001::AmountType4Choice {
+                        value: pain_001::AmountType4ChoiceEnum {
+                            instd_amt: Some(pain_001::ActiveOrHistoricCurrencyAndAmount {
+                                value: pain_001::ActiveOrHistoricCurrencyAndAmountSimpleType {
+                                    value: *amount,
+                                },
+                                ccy: pain_001::ActiveOrHistoricCurrencyCode {
+                                    value: "EUR".to_string(),
+                                },
+                            }),
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn credit_transfer(
+        msg_id: &str,
+        dt: &str,
+        txs: &[(f64, &str)],
+    ) -> pacs_008::FiToFiCustomerCreditTransferV10<Dmkr, Dmkr> {
+        pacs_008::FiToFiCustomerCreditTransferV10 {
+            grp_hdr: pacs_008::GroupHeader96 {
+                msg_id: pacs_008::Max35Text {
+                    value: msg_id.to_string(),
+                },
+                nb_of_txs: pacs_008::Max15NumericText {
+                    value: txs.len().to_string(),
+                },
+                ttl_intr_bk_sttlm_amt: Some(Default::default()),
+                intr_bk_sttlm_dt: Some(pacs_008::IsoDate {
+                    value: NaiveDate::parse_from_str(dt, "%Y-%m-%d").unwrap(),
+                }),
+                ..Default::default()
+            },
+            cdt_trf_tx_inf: txs
+                .iter()
+                .map(|(amount, ccy)| pacs_008::CreditTransferTransaction50 {
+                    intr_bk_sttlm_amt: pacs_008::ActiveCurrencyAndAmount {
+                        value: pacs_008::ActiveCurrencyAndAmountSimpleType { value: *amount },
+                        ccy: pacs_008::ActiveCurrencyCode {
+                            value: ccy.to_string(),
+                        },
+                    },
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_index_suffix() {
+        assert_eq!(IndexSuffix.msg_id("MSG-1", 1), "MSG-1-2");
+        assert_eq!(
+            IndexSuffix.msg_id(&"X".repeat(35), 0),
+            format!("{}-1", "X".repeat(33))
+        );
+    }
+
+    #[test]
+    fn test_split() -> Result<(), Error> {
+        let initn = pain_001::CustomerCreditTransferInitiationV11::<Dmkr, Dmkr> {
+            grp_hdr: pain_001::GroupHeader95 {
+                msg_id: pain_001::Max35Text {
+                    value: "PAIN-1".to_string(),
+                },
+                nb_of_txs: pain_001::Max15NumericText {
+                    value: "4".to_string(),
+                },
+                ctrl_sum: Some(pain_001::DecimalNumber { value: 60.6 }),
+                ..Default::default()
+            },
+            pmt_inf: vec![
+                pmt_inf("PMT-1", "2023-04-03", &[10.1, 20.2]),
+                pmt_inf("PMT-2", "2023-04-04", &[0.1]),
+                pmt_inf("PMT-3", "2023-04-03", &[30.2]),
+            ],
+            ..Default::default()
+        };
+
+        let initns = split(
+            &initn,
+            |_, pmt_inf| pmt_inf.reqd_exctn_dt.clone(),
+            &mut IndexSuffix,
+        )?;
+        assert_eq!(initns.len(), 2);
+
+        assert_eq!(initns[0].grp_hdr.msg_id.value, "PAIN-1-1");
+        assert_eq!(initns[0].grp_hdr.nb_of_txs.value, "3");
+        assert_eq!(initns[0].grp_hdr.ctrl_sum.as_ref().unwrap().value, 60.5);
+        assert_eq!(
+            initns[0]
+                .pmt_inf
+                .iter()
+                .map(|pmt_inf| pmt_inf.pmt_inf_id.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["PMT-1", "PMT-3"]
+        );
+
+        assert_eq!(initns[1].grp_hdr.msg_id.value, "PAIN-1-2");
+        assert_eq!(initns[1].grp_hdr.nb_of_txs.value, "1");
+        assert_eq!(initns[1].grp_hdr.ctrl_sum.as_ref().unwrap().value, 0.1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Error> {
+        let transfers = vec![
+            credit_transfer("PACS-1", "2023-04-03", &[(100.0, "EUR")]),
+            credit_transfer("PACS-2", "2023-04-04", &[(0.2, "EUR")]),
+            credit_transfer("PACS-3", "2023-04-03", &[(0.1, "EUR")]),
+        ];
+
+        let mut msg_ids = |_: &str, index: usize| format!("BATCH-{}", index + 1);
+        let merged = merge(
+            &transfers,
+            |msg, _| msg.grp_hdr.intr_bk_sttlm_dt.clone(),
+            &mut msg_ids,
+        )?;
+        assert_eq!(merged.len(), 2);
+
+        assert_eq!(merged[0].grp_hdr.msg_id.value, "BATCH-1");
+        assert_eq!(merged[0].grp_hdr.nb_of_txs.value, "2");
+        assert_eq!(merged[0].grp_hdr.ctrl_sum, None);
+        let ttl_intr_bk_sttlm_amt = merged[0].grp_hdr.ttl_intr_bk_sttlm_amt.as_ref().unwrap();
+        assert_eq!(ttl_intr_bk_sttlm_amt.value.value, 100.1);
+        assert_eq!(ttl_intr_bk_sttlm_amt.ccy.value, "EUR");
+
+        assert_eq!(merged[1].grp_hdr.msg_id.value, "BATCH-2");
+        assert_eq!(merged[1].grp_hdr.nb_of_txs.value, "1");
+
+        let transfers = vec![
+            credit_transfer("PACS-1", "2023-04-03", &[(100.0, "EUR")]),
+            credit_transfer("PACS-2", "2023-04-03", &[(100.0, "CHF")]),
+        ];
+        assert!(matches!(
+            merge(&transfers, |_, _| (), &mut IndexSuffix),
+            Err(Error::CurrencyMismatch { .. })
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1095 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # STEP2 Bulk Files
+//!
+//! The `BulkFile` type is a bulk file exchanged with an EBA STEP2-style clearing and
+//! settlement mechanism (CSM), i.e. an input file `ICF` sent to the CSM, a distribution file
+//! `DCF` received from the CSM, or a validation file `CVF` reporting the transactions of an
+//! input file that were rejected by the CSM.
+//!
+//! The file header is followed by blocks of documents, i.e. the root element of a document,
+//! e.g. `FIToFICstmrCdtTrf`, in the namespace of the document. The file header carries, per
+//! message type, the number of blocks, the number of transactions and their control sum.
+//!
+//! | Message type | Block                | Counters                                |
+//! |--------------|----------------------|-----------------------------------------|
+//! | `pacs.008`   | `FIToFICstmrCdtTrf`  | `NumCTBlk`, `NumCTTxs`, `CTCtrlSum`     |
+//! | `pacs.004`   | `PmtRtr`             | `NumRETBlk`, `NumRETTxs`, `RETCtrlSum`  |
+//! | `pacs.007`   | `FIToFIPmtRvsl`      | `NumREVBlk`, `NumREVTxs`, `REVCtrlSum`  |
+//! | `camt.056`   | `FIToFIPmtCxlReq`    | `NumPCRBlk`, `NumPCRTxs`, `PCRCtrlSum`  |
+//! | `camt.029`   | `RsltnOfInvstgtn`    | `NumROIBlk`, `NumROITxs`, `ROICtrlSum`  |
+//! | `pacs.002`   | `FIToFIPmtStsRpt`    | `NumPSRBlk`, `NumPSRTxs`, `PSRCtrlSum`  |
+//!
+//! The control sums of `camt.029` and `pacs.002` blocks are the sums of the original
+//! interbank settlement amounts of their transactions, if any. Direct debits `pacs.003` are
+//! exchanged in SDD files, with their own roots and service identification, and are not
+//! supported in the credit transfer files.
+//!
+//! ```rust
+//! use iso_20022_sdk::bulk::{BulkFile, FileHeader, FileType};
+//!
+//! let header = FileHeader {
+//!     sndg_inst: "AAAADEFF".to_string(),
+//!     rcvg_inst: Some("EBAPFRPP".to_string()),
+//!     file_ref: "ICF-2304-001".to_string(),
+//!     ..FileHeader::new(FileType::Icf)
+//! };
+//!
+//! // The counters and control sums of the file header are computed from the documents
+//! let mut file = BulkFile::new(header);
+//! file.push(credit_transfer)?;
+//! file.push(cancellation_request)?;
+//! let xml = file.to_xml()?;
+//!
+//! // Split an inbound file into its documents, checking the counters of the file header
+//! let file = BulkFile::from_xml(&dcf)?;
+//! for doc in file.documents { /* process the document */ }
+//!
+//! // Read the rejected transactions of a validation file
+//! let results = BulkFile::from_xml(&cvf)?.transaction_results();
+//! ```
+use chrono::NaiveDate;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::documents::camt::{self, camt_029_001_11, camt_056_001_10};
+use crate::documents::pacs::{
+    self, pacs_002_001_12, pacs_004_001_11, pacs_007_001_11, pacs_008_001_10,
+};
+use crate::documents::{Dmkr, Document, DocumentType};
+
+/// Service identifier of the SEPA credit transfer service.
+const CREDIT_TRANSFER_SERVICE: &str = "SCT";
+
+/// Rejected status of a group or a transaction of a status report.
+const REJECTED: &str = "RJCT";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// Error Reading / Writing XML
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
+    /// The root element is not a bulk file, e.g. `SCTIcfBlkCredTrf`
+    #[error("unsupported bulk file: {0}")]
+    UnsupportedFile(String),
+    /// The document cannot be carried in a bulk file, e.g. a `camt.053`
+    #[error("unsupported bulk file document")]
+    UnsupportedDocument,
+    /// A block of the file cannot be carried in a bulk file of its type, e.g. a
+    /// `FIToFICstmrDrctDbt` in a credit transfer file
+    #[error("unsupported bulk file block: {0}")]
+    UnsupportedBlock(String),
+    /// A mandatory element of the file header is missing
+    #[error("missing element {0}")]
+    MissingElement(&'static str),
+    /// An element of the file header is invalid, e.g. a date or a counter
+    #[error("invalid element {element}: {value}")]
+    InvalidElement { element: String, value: String },
+    /// A counter of the file header does not match the documents of the file
+    #[error("{element} of the file header is {header}, but {computed} in the file")]
+    CounterMismatch {
+        element: String,
+        header: String,
+        computed: String,
+    },
+}
+
+/// Type of a bulk file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileType {
+    /// Input file, sent by a participant to the CSM
+    #[default]
+    Icf,
+    /// Distribution file, sent by the CSM to a participant
+    Dcf,
+    /// Validation file, reporting the rejections of an input file
+    Cvf,
+}
+
+impl FileType {
+    /// Return the code of the file type, e.g. `ICF`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Icf => "ICF",
+            Self::Dcf => "DCF",
+            Self::Cvf => "CVF",
+        }
+    }
+
+    /// Return the root element of the file, e.g. `SCTIcfBlkCredTrf`.
+    pub fn root(&self) -> &'static str {
+        match self {
+            Self::Icf => "SCTIcfBlkCredTrf",
+            Self::Dcf => "SCTDcfBlkCredTrf",
+            Self::Cvf => "SCTCvfBlkCredTrf",
+        }
+    }
+
+    /// Return the namespace of the file, e.g. `urn:S2SCTICF:xsd:$SCTIcfBlkCredTrf`.
+    pub fn namespace(&self) -> String {
+        format!("urn:S2SCT{}:xsd:${}", self.code(), self.root())
+    }
+
+    /// Return the file type of the root element.
+    pub fn from_root(root: &str) -> Option<Self> {
+        [Self::Icf, Self::Dcf, Self::Cvf]
+            .into_iter()
+            .find(|file_type| file_type.root() == root)
+    }
+}
+
+/// Type of a block of a bulk file, i.e. the message type of its document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockType {
+    /// `pacs.008` customer credit transfer
+    CreditTransfer,
+    /// `pacs.004` payment return
+    PaymentReturn,
+    /// `pacs.007` payment reversal
+    PaymentReversal,
+    /// `camt.056` payment cancellation request
+    CancellationRequest,
+    /// `camt.029` resolution of investigation
+    ResolutionOfInvestigation,
+    /// `pacs.002` payment status report
+    StatusReport,
+}
+
+impl BlockType {
+    const ALL: [Self; 6] = [
+        Self::CreditTransfer,
+        Self::PaymentReturn,
+        Self::PaymentReversal,
+        Self::CancellationRequest,
+        Self::ResolutionOfInvestigation,
+        Self::StatusReport,
+    ];
+
+    /// Return the root element of the document, e.g. `FIToFICstmrCdtTrf`.
+    pub fn element(&self) -> &'static str {
+        match self {
+            Self::CreditTransfer => "FIToFICstmrCdtTrf",
+            Self::PaymentReturn => "PmtRtr",
+            Self::PaymentReversal => "FIToFIPmtRvsl",
+            Self::CancellationRequest => "FIToFIPmtCxlReq",
+            Self::ResolutionOfInvestigation => "RsltnOfInvstgtn",
+            Self::StatusReport => "FIToFIPmtStsRpt",
+        }
+    }
+
+    /// Return the abbreviation of the counters of the file header, e.g. `CT` for `NumCTBlk`.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::CreditTransfer => "CT",
+            Self::PaymentReturn => "RET",
+            Self::PaymentReversal => "REV",
+            Self::CancellationRequest => "PCR",
+            Self::ResolutionOfInvestigation => "ROI",
+            Self::StatusReport => "PSR",
+        }
+    }
+
+    /// Return the block type of the root element of a document.
+    pub fn from_element(element: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|block_type| block_type.element() == element)
+    }
+}
+
+/// Header of a bulk file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileHeader {
+    /// Sending institution, e.g. `AAAADEFF`
+    pub sndg_inst: String,
+    /// Receiving institution, e.g. `EBAPFRPP`
+    pub rcvg_inst: Option<String>,
+    /// Service identifier, e.g. `SCT`
+    pub srvc_id: String,
+    /// Test code, i.e. `T` for a test file or `P` for a production file
+    pub tst_code: String,
+    pub file_type: FileType,
+    /// Reference of the file
+    pub file_ref: String,
+    /// Routing indicator of an input file, e.g. `ALL`
+    pub routing_ind: Option<String>,
+    /// Business date of the file
+    pub file_bus_dt: Option<NaiveDate>,
+    /// Reference of the input file of a validation file
+    pub orig_f_ref: Option<String>,
+    /// Reason of the rejection of the input file of a validation file, e.g. `FF01`
+    pub file_rjct_rsn: Option<String>,
+}
+
+impl FileHeader {
+    /// Return a production file header of the SEPA credit transfer service.
+    pub fn new(file_type: FileType) -> Self {
+        Self {
+            srvc_id: CREDIT_TRANSFER_SERVICE.to_string(),
+            tst_code: "P".to_string(),
+            file_type,
+            ..Default::default()
+        }
+    }
+}
+
+/// Totals of the blocks of a message type of a bulk file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockTotals {
+    pub block_type: BlockType,
+    /// Number of blocks, i.e. of documents
+    pub blocks: usize,
+    /// Number of transactions of the blocks
+    pub transactions: usize,
+    /// Sum of the interbank settlement amounts of the transactions, or `None` if none of the
+    /// transactions carries an amount, e.g. status reports without original amounts
+    pub control_sum: Option<f64>,
+}
+
+/// Result of a transaction of a validation file, or of a group without transactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionResult {
+    /// Message identification of the original document
+    pub orgnl_msg_id: Option<String>,
+    /// Transaction identification of the original transaction
+    pub orgnl_tx_id: Option<String>,
+    /// End-to-end identification of the original transaction
+    pub orgnl_end_to_end_id: Option<String>,
+    /// Status of the transaction or the group, e.g. `RJCT`
+    pub status: Option<String>,
+    /// Reason of the status, e.g. `AC01`
+    pub reason: Option<String>,
+}
+
+impl TransactionResult {
+    /// Return whether the transaction, or the group, was rejected.
+    pub fn is_rejected(&self) -> bool {
+        self.status.as_deref() == Some(REJECTED)
+    }
+}
+
+/// Bulk file of a header and documents.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BulkFile {
+    pub header: FileHeader,
+    pub documents: Vec<Document>,
+}
+
+impl BulkFile {
+    pub fn new(header: FileHeader) -> Self {
+        Self {
+            header,
+            documents: vec![],
+        }
+    }
+
+    /// Add a document to the file.
+    /// Returns `Error::UnsupportedDocument` for a document that cannot be carried in a bulk
+    /// file, e.g. a `camt.053`.
+    pub fn push(&mut self, doc: Document) -> Result<(), Error> {
+        block(&doc)?;
+        self.documents.push(doc);
+
+        Ok(())
+    }
+
+    /// Return the totals of the blocks of the file, per message type, in the order of the
+    /// file header.
+    pub fn totals(&self) -> Result<Vec<BlockTotals>, Error> {
+        let mut totals: Vec<BlockTotals> = vec![];
+
+        for doc in self.documents.iter() {
+            let block = block(doc)?;
+            match totals
+                .iter_mut()
+                .find(|totals| totals.block_type == block.block_type)
+            {
+                Some(totals) => {
+                    totals.blocks += 1;
+                    totals.transactions += block.transactions;
+                    totals.control_sum = match (totals.control_sum, block.control_sum) {
+                        (Some(sum), Some(control_sum)) => Some(sum + control_sum),
+                        (sum, control_sum) => sum.or(control_sum),
+                    };
+                }
+                None => totals.push(BlockTotals {
+                    block_type: block.block_type,
+                    blocks: 1,
+                    transactions: block.transactions,
+                    control_sum: block.control_sum,
+                }),
+            }
+        }
+        totals.sort_by_key(|totals| {
+            BlockType::ALL
+                .iter()
+                .position(|block_type| *block_type == totals.block_type)
+        });
+
+        Ok(totals)
+    }
+
+    /// Return the serialized xml string of the file, with the counters and control sums of
+    /// the file header computed from the documents.
+    pub fn to_xml(&self) -> Result<String, Error> {
+        let mut writer = Writer::new(Vec::new());
+        let header = &self.header;
+        let file_type = header.file_type;
+
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        let mut root = BytesStart::new(file_type.root());
+        root.push_attribute(("xmlns", file_type.namespace().as_str()));
+        writer.write_event(Event::Start(root))?;
+
+        write_element(&mut writer, "SndgInst", &header.sndg_inst)?;
+        if let Some(rcvg_inst) = &header.rcvg_inst {
+            write_element(&mut writer, "RcvgInst", rcvg_inst)?;
+        }
+        write_element(&mut writer, "SrvcId", &header.srvc_id)?;
+        write_element(&mut writer, "TstCode", &header.tst_code)?;
+        write_element(&mut writer, "FType", file_type.code())?;
+        write_element(&mut writer, "FileRef", &header.file_ref)?;
+        if let Some(routing_ind) = &header.routing_ind {
+            write_element(&mut writer, "RoutingInd", routing_ind)?;
+        }
+        if let Some(file_bus_dt) = &header.file_bus_dt {
+            write_element(&mut writer, "FileBusDt", &file_bus_dt.to_string())?;
+        }
+        if let Some(orig_f_ref) = &header.orig_f_ref {
+            write_element(&mut writer, "OrigFRef", orig_f_ref)?;
+        }
+        if let Some(file_rjct_rsn) = &header.file_rjct_rsn {
+            write_element(&mut writer, "FileRjctRsn", file_rjct_rsn)?;
+        }
+        for (element, value) in counters(&self.totals()?) {
+            write_element(&mut writer, &element, &value)?;
+        }
+
+        for doc in self.documents.iter() {
+            write_block(&mut writer, &block(doc)?.xml)?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new(file_type.root())))?;
+
+        Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+    }
+
+    /// Parse a bulk file, splitting its blocks into documents.
+    /// Returns `Error::CounterMismatch` if a counter or a control sum of the file header
+    /// does not match the documents of the file.
+    pub fn from_xml(xml_string: &str) -> Result<Self, Error> {
+        let mut reader = Reader::from_str(xml_string);
+        reader.trim_text(true);
+
+        let file_type = loop {
+            match reader.read_event()? {
+                Event::Start(start) | Event::Empty(start) => {
+                    let root = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+                    break FileType::from_root(&root).ok_or(Error::UnsupportedFile(root))?;
+                }
+                Event::Eof => return Err(unexpected_eof()),
+                _ => {}
+            }
+        };
+
+        let mut file = Self::new(FileHeader {
+            file_type,
+            ..Default::default()
+        });
+        let mut header_counters = vec![];
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(start) => {
+                    let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+
+                    match BlockType::from_element(&name) {
+                        Some(block_type) => {
+                            let xml = read_block(&mut reader, &start)?;
+                            file.documents.push(parse_document(block_type, &xml)?);
+                        }
+                        // Blocks are in the namespace of their document, header elements in
+                        // the namespace of the file
+                        None if start.try_get_attribute("xmlns")?.is_some() => {
+                            return Err(Error::UnsupportedBlock(name));
+                        }
+                        None => {
+                            let value = reader.read_text(start.name())?.trim().to_string();
+                            if !file.read_header_element(&name, &value)? {
+                                header_counters.push((name, value));
+                            }
+                        }
+                    }
+                }
+                Event::End(_) | Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if file.header.sndg_inst.is_empty() {
+            return Err(Error::MissingElement("SndgInst"));
+        }
+        if file.header.file_ref.is_empty() {
+            return Err(Error::MissingElement("FileRef"));
+        }
+        file.check_counters(&header_counters)?;
+
+        Ok(file)
+    }
+
+    /// Return the results of the transactions of the status reports `pacs.002` of a
+    /// validation file, and of the groups reported without transactions.
+    pub fn transaction_results(&self) -> Vec<TransactionResult> {
+        let mut results = vec![];
+
+        for doc in self.documents.iter() {
+            let report = match &doc.value {
+                Some(DocumentType::pacs(pacs::Document::pacs_002_001_12(doc))) => {
+                    &doc.fi_to_fi_pmt_sts_rpt
+                }
+                _ => continue,
+            };
+            let orgnl_msg_id = report
+                .orgnl_grp_inf_and_sts
+                .first()
+                .map(|grp| grp.orgnl_msg_id.value.clone());
+
+            for grp in report.orgnl_grp_inf_and_sts.iter() {
+                let grp_sts = grp.grp_sts.as_ref().map(|sts| sts.value.clone());
+                if report.tx_inf_and_sts.is_empty() && grp_sts.is_some() {
+                    results.push(TransactionResult {
+                        orgnl_msg_id: Some(grp.orgnl_msg_id.value.clone()),
+                        status: grp_sts,
+                        reason: status_reason(&grp.sts_rsn_inf),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            for tx in report.tx_inf_and_sts.iter() {
+                results.push(TransactionResult {
+                    orgnl_msg_id: tx
+                        .orgnl_grp_inf
+                        .as_ref()
+                        .map(|grp| grp.orgnl_msg_id.value.clone())
+                        .or_else(|| orgnl_msg_id.clone()),
+                    orgnl_tx_id: tx.orgnl_tx_id.as_ref().map(|id| id.value.clone()),
+                    orgnl_end_to_end_id: tx.orgnl_end_to_end_id.as_ref().map(|id| id.value.clone()),
+                    status: tx.tx_sts.as_ref().map(|sts| sts.value.clone()),
+                    reason: status_reason(&tx.sts_rsn_inf),
+                });
+            }
+        }
+
+        results
+    }
+
+    /// Read an element of the file header, returning `false` for an unknown element, e.g. a
+    /// counter.
+    fn read_header_element(&mut self, name: &str, value: &str) -> Result<bool, Error> {
+        let header = &mut self.header;
+
+        match name {
+            "SndgInst" => header.sndg_inst = value.to_string(),
+            "RcvgInst" => header.rcvg_inst = Some(value.to_string()),
+            "SrvcId" => header.srvc_id = value.to_string(),
+            "TstCode" => header.tst_code = value.to_string(),
+            "FType" if value == header.file_type.code() => {}
+            "FType" => return Err(invalid_element(name, value)),
+            "FileRef" => header.file_ref = value.to_string(),
+            "RoutingInd" => header.routing_ind = Some(value.to_string()),
+            "FileBusDt" => {
+                header.file_bus_dt = Some(
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .map_err(|_| invalid_element(name, value))?,
+                )
+            }
+            "OrigFRef" => header.orig_f_ref = Some(value.to_string()),
+            "FileRjctRsn" => header.file_rjct_rsn = Some(value.to_string()),
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Check the counters of the file header against the totals of the documents. Counters
+    /// missing from the file header, and control sums of blocks without amounts, are not
+    /// checked.
+    fn check_counters(&self, header_counters: &[(String, String)]) -> Result<(), Error> {
+        let totals = self.totals()?;
+        let computed = counters(&totals);
+
+        for (element, header) in header_counters.iter() {
+            if !is_counter(element) {
+                continue;
+            }
+
+            let computed = match computed.iter().find(|(name, _)| name == element) {
+                Some((_, value)) => value.as_str(),
+                None if totals.iter().any(|totals| {
+                    *element == format!("{}CtrlSum", totals.block_type.abbreviation())
+                }) =>
+                {
+                    continue
+                }
+                None => "0",
+            };
+            let matches = match (header.parse::<f64>(), computed.parse::<f64>()) {
+                (Ok(header), Ok(computed)) => (header - computed).abs() < 0.005,
+                _ => false,
+            };
+
+            if !matches {
+                return Err(Error::CounterMismatch {
+                    element: element.clone(),
+                    header: header.clone(),
+                    computed: computed.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Block of a document, with its serialized xml string and its totals.
+struct Block {
+    block_type: BlockType,
+    xml: String,
+    transactions: usize,
+    control_sum: Option<f64>,
+}
+
+/// Return the block of a document, or `Error::UnsupportedDocument` for a document that
+/// cannot be carried in a bulk file.
+fn block(doc: &Document) -> Result<Block, Error> {
+    let (block_type, xml, amounts): (BlockType, String, Vec<Option<f64>>) = match &doc.value {
+        Some(DocumentType::pacs(pacs::Document::pacs_008_001_10(doc))) => (
+            BlockType::CreditTransfer,
+            quick_xml::se::to_string(doc)?,
+            doc.fi_to_fi_cstmr_cdt_trf
+                .cdt_trf_tx_inf
+                .iter()
+                .map(|tx| Some(tx.intr_bk_sttlm_amt.value.value))
+                .collect(),
+        ),
+        Some(DocumentType::pacs(pacs::Document::pacs_004_001_11(doc))) => (
+            BlockType::PaymentReturn,
+            quick_xml::se::to_string(doc)?,
+            doc.pmt_rtr
+                .tx_inf
+                .iter()
+                .map(|tx| Some(tx.rtrd_intr_bk_sttlm_amt.value.value))
+                .collect(),
+        ),
+        Some(DocumentType::pacs(pacs::Document::pacs_007_001_11(doc))) => (
+            BlockType::PaymentReversal,
+            quick_xml::se::to_string(doc)?,
+            doc.fi_to_fi_pmt_rvsl
+                .tx_inf
+                .iter()
+                .map(|tx| Some(tx.rvsd_intr_bk_sttlm_amt.value.value))
+                .collect(),
+        ),
+        Some(DocumentType::pacs(pacs::Document::pacs_002_001_12(doc))) => (
+            BlockType::StatusReport,
+            quick_xml::se::to_string(doc)?,
+            doc.fi_to_fi_pmt_sts_rpt
+                .tx_inf_and_sts
+                .iter()
+                .map(|tx| {
+                    tx.orgnl_tx_ref
+                        .as_ref()
+                        .and_then(|orgnl_tx_ref| orgnl_tx_ref.intr_bk_sttlm_amt.as_ref())
+                        .map(|amt| amt.value.value)
+                })
+                .collect(),
+        ),
+        Some(DocumentType::camt(camt::Document::camt_056_001_10(doc))) => (
+            BlockType::CancellationRequest,
+            quick_xml::se::to_string(doc)?,
+            doc.fi_to_fi_pmt_cxl_req
+                .undrlyg
+                .iter()
+                .flat_map(|undrlyg| undrlyg.tx_inf.iter())
+                .map(|tx| {
+                    tx.orgnl_intr_bk_sttlm_amt
+                        .as_ref()
+                        .map(|amt| amt.value.value)
+                })
+                .collect(),
+        ),
+        Some(DocumentType::camt(camt::Document::camt_029_001_11(doc))) => (
+            BlockType::ResolutionOfInvestigation,
+            quick_xml::se::to_string(doc)?,
+            doc.rsltn_of_invstgtn
+                .cxl_dtls
+                .iter()
+                .flat_map(|cxl_dtls| cxl_dtls.tx_inf_and_sts.iter())
+                .map(|tx| {
+                    tx.orgnl_intr_bk_sttlm_amt
+                        .as_ref()
+                        .map(|amt| amt.value.value)
+                })
+                .collect(),
+        ),
+        _ => return Err(Error::UnsupportedDocument),
+    };
+
+    Ok(Block {
+        block_type,
+        xml,
+        transactions: amounts.len(),
+        control_sum: amounts
+            .iter()
+            .any(Option::is_some)
+            .then(|| amounts.iter().flatten().sum()),
+    })
+}
+
+/// Parse the document of a block, i.e. a root element wrapped in a `Document` in the
+/// namespace of the block.
+fn parse_document(block_type: BlockType, xml: &str) -> Result<Document, Error> {
+    let value = match block_type {
+        BlockType::CreditTransfer => {
+            DocumentType::pacs(pacs::Document::pacs_008_001_10(quick_xml::de::from_str::<
+                pacs_008_001_10::Document<Dmkr, Dmkr>,
+            >(xml)?))
+        }
+        BlockType::PaymentReturn => {
+            DocumentType::pacs(pacs::Document::pacs_004_001_11(quick_xml::de::from_str::<
+                pacs_004_001_11::Document<Dmkr, Dmkr>,
+            >(xml)?))
+        }
+        BlockType::PaymentReversal => {
+            DocumentType::pacs(pacs::Document::pacs_007_001_11(quick_xml::de::from_str::<
+                pacs_007_001_11::Document<Dmkr, Dmkr>,
+            >(xml)?))
+        }
+        BlockType::StatusReport => {
+            DocumentType::pacs(pacs::Document::pacs_002_001_12(quick_xml::de::from_str::<
+                pacs_002_001_12::Document<Dmkr, Dmkr>,
+            >(xml)?))
+        }
+        BlockType::CancellationRequest => {
+            DocumentType::camt(camt::Document::camt_056_001_10(quick_xml::de::from_str::<
+                camt_056_001_10::Document<Dmkr, Dmkr>,
+            >(xml)?))
+        }
+        BlockType::ResolutionOfInvestigation => {
+            DocumentType::camt(camt::Document::camt_029_001_11(quick_xml::de::from_str::<
+                camt_029_001_11::Document<Dmkr>,
+            >(xml)?))
+        }
+    };
+
+    Ok(Document { value: Some(value) })
+}
+
+/// Return the counters of the file header of the totals, e.g. `NumCTBlk`, `NumCTTxs` and
+/// `CTCtrlSum`.
+fn counters(totals: &[BlockTotals]) -> Vec<(String, String)> {
+    totals
+        .iter()
+        .flat_map(|totals| {
+            let abbreviation = totals.block_type.abbreviation();
+
+            [
+                (format!("Num{}Blk", abbreviation), totals.blocks.to_string()),
+                (
+                    format!("Num{}Txs", abbreviation),
+                    totals.transactions.to_string(),
+                ),
+            ]
+            .into_iter()
+            .chain(totals.control_sum.map(|control_sum| {
+                (
+                    format!("{}CtrlSum", abbreviation),
+                    format!("{:.2}", control_sum),
+                )
+            }))
+        })
+        .collect()
+}
+
+/// Return whether the element is a counter of the file header, e.g. `NumCTBlk`.
+fn is_counter(element: &str) -> bool {
+    BlockType::ALL.into_iter().any(|block_type| {
+        let abbreviation = block_type.abbreviation();
+
+        element == format!("Num{}Blk", abbreviation)
+            || element == format!("Num{}Txs", abbreviation)
+            || element == format!("{}CtrlSum", abbreviation)
+    })
+}
+
+/// Write the root element of a serialized document, i.e. without its `Document` element,
+/// in the namespace of the document.
+fn write_block(writer: &mut Writer<Vec<u8>>, xml: &str) -> Result<(), Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut namespace = None;
+    let mut depth = 0;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) if depth == 0 => {
+                namespace = start
+                    .try_get_attribute("xmlns")?
+                    .map(|xmlns| xmlns.unescape_value().map(|value| value.into_owned()))
+                    .transpose()?;
+                depth += 1;
+            }
+            Event::Start(start) if depth == 1 => {
+                let mut root = start.into_owned();
+                if let Some(namespace) = namespace.take() {
+                    root.push_attribute(("xmlns", namespace.as_str()));
+                }
+                writer.write_event(Event::Start(root))?;
+                depth += 1;
+            }
+            Event::Start(start) => {
+                writer.write_event(Event::Start(start))?;
+                depth += 1;
+            }
+            Event::End(_) if depth == 1 => break,
+            Event::End(end) => {
+                writer.write_event(Event::End(end))?;
+                depth -= 1;
+            }
+            Event::Eof => return Err(unexpected_eof()),
+            Event::Decl(_) => {}
+            event if depth > 1 => writer.write_event(event)?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a block, up to its end, returning the root element of the document wrapped in a
+/// `Document` element in the namespace of the block.
+fn read_block(reader: &mut Reader<&[u8]>, start: &BytesStart) -> Result<String, Error> {
+    let mut writer = Writer::new(Vec::new());
+
+    let mut document = BytesStart::new("Document");
+    if let Some(xmlns) = start.try_get_attribute("xmlns")? {
+        document.push_attribute(("xmlns", xmlns.unescape_value()?.as_ref()));
+    }
+    writer.write_event(Event::Start(document))?;
+    writer.write_event(Event::Start(BytesStart::new(
+        String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+    )))?;
+
+    let mut depth = 0;
+    loop {
+        let event = reader.read_event()?;
+        let end = match &event {
+            Event::Start(_) => {
+                depth += 1;
+                false
+            }
+            Event::End(_) if depth == 0 => true,
+            Event::End(_) => {
+                depth -= 1;
+                false
+            }
+            Event::Eof => return Err(unexpected_eof()),
+            _ => false,
+        };
+        writer.write_event(event)?;
+        if end {
+            break;
+        }
+    }
+    writer.write_event(Event::End(BytesEnd::new("Document")))?;
+
+    Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+}
+
+fn write_element(writer: &mut Writer<Vec<u8>>, name: &str, value: &str) -> Result<(), Error> {
+    writer.write_event(Event::Start(BytesStart::new(name)))?;
+    writer.write_event(Event::Text(BytesText::new(value)))?;
+    writer.write_event(Event::End(BytesEnd::new(name)))?;
+
+    Ok(())
+}
+
+/// Return the first reason code, or proprietary reason, of the status reasons.
+fn status_reason(sts_rsn_inf: &[pacs_002_001_12::StatusReasonInformation12]) -> Option<String> {
+    sts_rsn_inf
+        .iter()
+        .filter_map(|sts_rsn_inf| sts_rsn_inf.rsn.as_ref())
+        .find_map(|rsn| {
+            rsn.value
+                .cd
+                .as_ref()
+                .map(|cd| cd.value.clone())
+                .or_else(|| rsn.value.prtry.as_ref().map(|prtry| prtry.value.clone()))
+        })
+}
+
+fn invalid_element(element: &str, value: &str) -> Error {
+    Error::InvalidElement {
+        element: element.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn unexpected_eof() -> Error {
+    Error::Xml(quick_xml::Error::UnexpectedEof("bulk file".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::pacs::pacs_002_001_12 as pacs_002;
+    use crate::documents::pacs::pacs_008_001_10 as pacs_008;
+
+    fn credit_transfer(msg_id: &str, amounts: &[f64]) -> Document {
+        let doc = pacs_008::Document::<Dmkr, Dmkr> {
+            fi_to_fi_cstmr_cdt_trf: pacs_008::FiToFiCustomerCreditTransferV10 {
+                grp_hdr: pacs_008::GroupHeader96 {
+                    msg_id: pacs_008::Max35Text {
+                        value: msg_id.to_string(),
+                    },
+                    nb_of_txs: pacs_008::Max15NumericText {
+                        value: amounts.len().to_string(),
+                    },
+                    ..Default::default()
+                },
+                cdt_trf_tx_inf: amounts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, amount)| pacs_008::CreditTransferTransaction50 {
+                        pmt_id: pacs_008::PaymentIdentification13 {
+                            end_to_end_id: pacs_008::Max35Text {
+                                value: format!("{}-{}", msg_id, i + 1),
+                            },
+                            ..Default::default()
+                        },
+                        intr_bk_sttlm_amt: pacs_008::ActiveCurrencyAndAmount {
+                            value: pacs_008::ActiveCurrencyAndAmountSimpleType { value: *amount },
+                            ccy: pacs_008::ActiveCurrencyCode {
+                                value: "EUR".to_string(),
+                            },
+                        },
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            xmlns: pacs_008::namespace(),
+        };
+
+        Document {
+            value: Some(DocumentType::pacs(pacs::Document::pacs_008_001_10(doc))),
+        }
+    }
+
+    #[test]
+    fn test_bulk_file() -> Result<(), Error> {
+        let header = FileHeader {
+            sndg_inst: "AAAADEFF".to_string(),
+            rcvg_inst: Some("EBAPFRPP".to_string()),
+            file_ref: "ICF-2304-001".to_string(),
+            file_bus_dt: NaiveDate::from_ymd_opt(2023, 4, 3),
+            ..FileHeader::new(FileType::Icf)
+        };
+        let mut file = BulkFile::new(header);
+        file.push(credit_transfer("MSG-1", &[100.5, 20.25]))?;
+        file.push(credit_transfer("MSG-2", &[3.]))?;
+
+        assert_eq!(
+            file.totals()?,
+            vec![BlockTotals {
+                block_type: BlockType::CreditTransfer,
+                blocks: 2,
+                transactions: 3,
+                control_sum: Some(123.75),
+            }]
+        );
+        assert!(matches!(
+            file.push(Document::default()),
+            Err(Error::UnsupportedDocument)
+        ));
+
+        let xml = file.to_xml()?;
+        assert!(xml.contains("<SCTIcfBlkCredTrf xmlns=\"urn:S2SCTICF:xsd:$SCTIcfBlkCredTrf\">"));
+        assert!(xml
+            .contains("<NumCTBlk>2</NumCTBlk><NumCTTxs>3</NumCTTxs><CTCtrlSum>123.75</CTCtrlSum>"));
+        assert!(xml.contains(
+            "<FIToFICstmrCdtTrf xmlns=\"urn:iso:std:iso:20022:tech:xsd:pacs.008.001.10\">"
+        ));
+        assert!(!xml.contains("<Document"));
+
+        let parsed = BulkFile::from_xml(&xml)?;
+        assert_eq!(parsed.header, file.header);
+        assert_eq!(parsed.documents.len(), 2);
+        assert_eq!(parsed.totals()?, file.totals()?);
+
+        let xml = xml.replace(
+            "<CTCtrlSum>123.75</CTCtrlSum>",
+            "<CTCtrlSum>120.00</CTCtrlSum>",
+        );
+        match BulkFile::from_xml(&xml) {
+            Err(Error::CounterMismatch {
+                element,
+                header,
+                computed,
+            }) => {
+                assert_eq!(element, "CTCtrlSum");
+                assert_eq!(header, "120.00");
+                assert_eq!(computed, "123.75");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation_file() -> Result<(), Error> {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<SCTCvfBlkCredTrf xmlns="urn:S2SCTCVF:xsd:$SCTCvfBlkCredTrf">
+  <SndgInst>EBAPFRPP</SndgInst>
+  <RcvgInst>AAAADEFF</RcvgInst>
+  <SrvcId>SCT</SrvcId>
+  <TstCode>P</TstCode>
+  <FType>CVF</FType>
+  <FileRef>CVF-2304-001</FileRef>
+  <FileBusDt>2023-04-03</FileBusDt>
+  <OrigFRef>ICF-2304-001</OrigFRef>
+  <NumPSRBlk>1</NumPSRBlk>
+  <NumPSRTxs>1</NumPSRTxs>
+  <PSRCtrlSum>20.25</PSRCtrlSum>
+  <FIToFIPmtStsRpt xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.002.001.12">
+    <GrpHdr>
+      <MsgId>PSR-1</MsgId>
+      <CreDtTm>2023-04-03T10:00:00</CreDtTm>
+    </GrpHdr>
+    <OrgnlGrpInfAndSts>
+      <OrgnlMsgId>MSG-1</OrgnlMsgId>
+      <OrgnlMsgNmId>pacs.008.001.10</OrgnlMsgNmId>
+      <GrpSts>PART</GrpSts>
+    </OrgnlGrpInfAndSts>
+    <TxInfAndSts>
+      <OrgnlEndToEndId>MSG-1-2</OrgnlEndToEndId>
+      <OrgnlTxId>TX-2</OrgnlTxId>
+      <TxSts>RJCT</TxSts>
+      <StsRsnInf>
+        <Rsn>
+          <Cd>AC01</Cd>
+        </Rsn>
+      </StsRsnInf>
+    </TxInfAndSts>
+  </FIToFIPmtStsRpt>
+</SCTCvfBlkCredTrf>"#;
+
+        let file = BulkFile::from_xml(xml)?;
+        assert_eq!(file.header.file_type, FileType::Cvf);
+        assert_eq!(file.header.orig_f_ref.as_deref(), Some("ICF-2304-001"));
+
+        let results = file.transaction_results();
+        assert_eq!(
+            results,
+            vec![TransactionResult {
+                orgnl_msg_id: Some("MSG-1".to_string()),
+                orgnl_tx_id: Some("TX-2".to_string()),
+                orgnl_end_to_end_id: Some("MSG-1-2".to_string()),
+                status: Some("RJCT".to_string()),
+                reason: Some("AC01".to_string()),
+            }]
+        );
+        assert!(results[0].is_rejected());
+
+        assert!(matches!(
+            BulkFile::from_xml("<SCTIcfBlkCredTrf><FileRef>ICF</FileRef></SCTIcfBlkCredTrf>"),
+            Err(Error::MissingElement("SndgInst"))
+        ));
+        assert!(matches!(
+            BulkFile::from_xml("<Document/>"),
+            Err(Error::UnsupportedFile(_))
+        ));
+        assert!(matches!(
+            BulkFile::from_xml(concat!(
+                "<SCTIcfBlkCredTrf><SndgInst>AAAADEFF</SndgInst><FileRef>ICF</FileRef>",
+                "<FIToFICstmrDrctDbt xmlns=\"urn:iso:std:iso:20022:tech:xsd:pacs.003.001.09\">",
+                "</FIToFICstmrDrctDbt></SCTIcfBlkCredTrf>"
+            )),
+            Err(Error::UnsupportedBlock(element)) if element == "FIToFICstmrDrctDbt"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_status_report_control_sum() -> Result<(), Error> {
+        let doc =
+            pacs_002::Document::<Dmkr, Dmkr> {
+                fi_to_fi_pmt_sts_rpt: pacs_002::FiToFiPaymentStatusReportV12 {
+                    tx_inf_and_sts: [Some(100.5), Some(20.25), None]
+                        .into_iter()
+                        .map(|amount| pacs_002::PaymentTransaction130 {
+                            orgnl_tx_ref: amount.map(|amount| {
+                                pacs_002::OriginalTransactionReference35 {
+                            intr_bk_sttlm_amt: Some(pacs_002::ActiveOrHistoricCurrencyAndAmount {
+                                value: pacs_002::ActiveOrHistoricCurrencyAndAmountSimpleType {
+                                    value: amount,
+                                },
+                                ccy: pacs_002::ActiveOrHistoricCurrencyCode {
+                                    value: "EUR".to_string(),
+                                },
+                            }),
+                            ..Default::default()
+                        }
+                            }),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                },
+                xmlns: pacs_002::namespace(),
+            };
+
+        let mut file = BulkFile::new(FileHeader {
+            sndg_inst: "EBAPFRPP".to_string(),
+            file_ref: "DCF-2304-001".to_string(),
+            ..FileHeader::new(FileType::Dcf)
+        });
+        file.push(Document {
+            value: Some(DocumentType::pacs(pacs::Document::pacs_002_001_12(doc))),
+        })?;
+        assert_eq!(
+            file.totals()?,
+            vec![BlockTotals {
+                block_type: BlockType::StatusReport,
+                blocks: 1,
+                transactions: 3,
+                control_sum: Some(120.75),
+            }]
+        );
+
+        let xml = file.to_xml()?;
+        assert!(xml.contains("<PSRCtrlSum>120.75</PSRCtrlSum>"));
+        assert_eq!(BulkFile::from_xml(&xml)?.totals()?, file.totals()?);
+
+        Ok(())
+    }
+}
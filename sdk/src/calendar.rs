@@ -0,0 +1,1398 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Business Day Calendars
+//!
+//! The dates of a payment, e.g. the interbank settlement date `IntrBkSttlmDt`, the requested
+//! execution date `ReqdExctnDt` or the requested collection date `ReqdColltnDt`, are plain
+//! `IsoDate`s. A `Calendar` knows the business days and the settlement window of a payment
+//! system, and `Calendars` the calendar of each settlement currency.
+//!
+//! The bundled calendars compute the holidays of any year from their rules, e.g. Easter
+//! Monday, and carry the one-off holidays declared so far:
+//!
+//! | Currency | Calendar                  | Settlement window |
+//! |----------|---------------------------|-------------------|
+//! | `EUR`    | `Calendar::target2()`     | 07:00 - 18:00 CET |
+//! | `USD`    | `Calendar::fedwire()`     | - 19:00 ET        |
+//! | `GBP`    | `Calendar::chaps()`       | 06:00 - 18:00 UK  |
+//!
+//! The holidays of other currencies, or newly declared holidays, are loaded from a file of
+//! one date `YYYY-MM-DD` per line, optionally followed by a description. A date prefixed with
+//! `!` is a business day, e.g. a holiday moved to another date. Empty lines and lines
+//! starting with `#` are ignored.
+//!
+//! ```text
+//! # CHF
+//! 2023-01-02 Berchtoldstag
+//! 2023-04-07 Good Friday
+//! ```
+//!
+//! The `check_*` functions report the dates of a message that are not business days of the
+//! calendar of their currency, the requested collection dates of a direct debit that do not
+//! respect the SEPA lead time of their sequence type `SeqTp`, and the requested settlement
+//! times `SttlmTmReq` outside the settlement window of the RTGS or after the CLS cut-off.
+//! The lead times are selected with `DirectDebitLeadTimes`, i.e. the D-1 of the SEPA schemes
+//! by default, `DirectDebitLeadTimes::d2()` for a bank or CSM requiring D-2, or
+//! `DirectDebitLeadTimes::legacy()` for the D-5 / D-2 of the SEPA Core scheme before 2016.
+//!
+//! ```rust
+//! use iso_20022_sdk::calendar::{
+//!     check_direct_debit_initiation, Calendar, Calendars, DirectDebitLeadTimes, RollConvention,
+//! };
+//!
+//! let mut calendars = Calendars::default();
+//! calendars.insert("CHF", Calendar::new("SIC").load_file("holidays/chf.txt")?);
+//!
+//! let target2 = Calendar::target2();
+//! let dt = target2.roll(dt, RollConvention::ModifiedFollowing);
+//!
+//! let issues = check_direct_debit_initiation(
+//!     &pain_008.cstmr_drct_dbt_initn,
+//!     &calendars,
+//!     &DirectDebitLeadTimes::default(),
+//!     submission_dt,
+//! );
+//! ```
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+#[cfg(feature = "pacs")]
+use iso_20022_pacs::{
+    pacs_003_001_09 as pacs_003, pacs_008_001_10 as pacs_008, pacs_009_001_10 as pacs_009,
+};
+#[cfg(feature = "pain")]
+use iso_20022_pain::{pain_001_001_11 as pain_001, pain_008_001_10 as pain_008};
+#[cfg(any(feature = "pacs", feature = "pain"))]
+use serde::Serialize;
+
+/// One-off TARGET2 holidays.
+const TARGET2_HOLIDAYS: &[(i32, u32, u32)] = &[];
+
+/// One-off CHAPS holidays, e.g. royal weddings, jubilees and funerals, and bank holidays
+/// moved to another date.
+const CHAPS_HOLIDAYS: &[(i32, u32, u32)] = &[
+    (2011, 4, 29),
+    (2012, 6, 4),
+    (2012, 6, 5),
+    (2020, 5, 8),
+    (2022, 6, 2),
+    (2022, 6, 3),
+    (2022, 9, 19),
+    (2023, 5, 8),
+];
+
+/// CHAPS business days on the date of a bank holiday moved to another date.
+const CHAPS_BUSINESS_DAYS: &[(i32, u32, u32)] = &[(2012, 5, 28), (2020, 5, 4), (2022, 5, 30)];
+
+/// One-off Fedwire holidays.
+const FEDWIRE_HOLIDAYS: &[(i32, u32, u32)] = &[];
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Reading a Holiday File
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A line of a holiday file is not a date `YYYY-MM-DD`
+    #[error("invalid holiday at line {line}: {value}")]
+    InvalidHoliday { line: usize, value: String },
+    /// The weekend of a calendar covers every day of the week
+    #[error("calendar without business days: {0}")]
+    NoBusinessDays(String),
+}
+
+/// Convention rolling a date that is not a business day to a business day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollConvention {
+    /// The next business day
+    Following,
+    /// The next business day, unless in the next month, then the previous business day
+    ModifiedFollowing,
+    /// The previous business day
+    Preceding,
+    /// The previous business day, unless in the previous month, then the next business day
+    ModifiedPreceding,
+}
+
+/// Observance of a holiday falling on a weekend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Observance {
+    /// The holiday is only observed on its date
+    Actual,
+    /// A holiday falling on a Sunday is observed on the Monday, e.g. Fedwire
+    SundayToMonday,
+    /// A holiday falling on a weekend, or on another holiday, is observed on the next
+    /// business day, e.g. the substitute bank holidays of CHAPS
+    NextWeekday,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleDate {
+    Fixed {
+        month: u32,
+        day: u32,
+    },
+    Easter {
+        offset: i64,
+    },
+    /// The `n`th weekday of the month, or the last weekday of the month for 0
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        n: u32,
+    },
+}
+
+/// Rule of a yearly holiday, e.g. Christmas Day or Easter Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HolidayRule {
+    date: RuleDate,
+    observance: Observance,
+    since: Option<i32>,
+}
+
+impl HolidayRule {
+    /// Return the rule of a holiday on a fixed date, e.g. Christmas Day `fixed(12, 25)`.
+    pub fn fixed(month: u32, day: u32) -> Self {
+        Self::new(RuleDate::Fixed { month, day })
+    }
+
+    /// Return the rule of a holiday relative to Easter Sunday, e.g. Good Friday `easter(-2)`.
+    pub fn easter(offset: i64) -> Self {
+        Self::new(RuleDate::Easter { offset })
+    }
+
+    /// Return the rule of a holiday on the `n`th weekday of a month, e.g. Thanksgiving Day
+    /// `nth_weekday(11, Weekday::Thu, 4)`.
+    pub fn nth_weekday(month: u32, weekday: Weekday, n: u32) -> Self {
+        Self::new(RuleDate::NthWeekday { month, weekday, n })
+    }
+
+    /// Return the rule of a holiday on the last weekday of a month, e.g. Memorial Day
+    /// `last_weekday(5, Weekday::Mon)`.
+    pub fn last_weekday(month: u32, weekday: Weekday) -> Self {
+        Self::new(RuleDate::NthWeekday {
+            month,
+            weekday,
+            n: 0,
+        })
+    }
+
+    fn new(date: RuleDate) -> Self {
+        Self {
+            date,
+            observance: Observance::Actual,
+            since: None,
+        }
+    }
+
+    /// Set the observance of the holiday falling on a weekend.
+    pub fn observed(mut self, observance: Observance) -> Self {
+        self.observance = observance;
+        self
+    }
+
+    /// Set the first year of the holiday.
+    pub fn since(mut self, year: i32) -> Self {
+        self.since = Some(year);
+        self
+    }
+
+    /// Return the date of the holiday in the year, before its observance.
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        if self.since.is_some_and(|since| year < since) {
+            return None;
+        }
+
+        match self.date {
+            RuleDate::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            RuleDate::Easter { offset } => {
+                easter(year).map(|easter| easter + Duration::days(offset))
+            }
+            RuleDate::NthWeekday { month, weekday, n } => nth_weekday(year, month, weekday, n),
+        }
+    }
+}
+
+/// Calendar of the business days and the settlement window of a payment system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    /// Name of the calendar, e.g. `TARGET2`
+    pub name: String,
+    /// Days of the weekend
+    pub weekend: Vec<Weekday>,
+    /// Rules of the yearly holidays
+    pub rules: Vec<HolidayRule>,
+    /// One-off holidays
+    pub holidays: BTreeSet<NaiveDate>,
+    /// Business days overriding the rules, e.g. the date of a holiday moved to another date
+    pub business_days: BTreeSet<NaiveDate>,
+    /// Opening time of the settlement window, in the local time of the payment system
+    pub opening: Option<NaiveTime>,
+    /// Cut-off time of the settlement window, in the local time of the payment system
+    pub cut_off: Option<NaiveTime>,
+}
+
+impl Calendar {
+    /// Return a calendar of the Saturday and Sunday weekend, without holidays.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            weekend: vec![Weekday::Sat, Weekday::Sun],
+            rules: vec![],
+            holidays: BTreeSet::new(),
+            business_days: BTreeSet::new(),
+            opening: None,
+            cut_off: None,
+        }
+    }
+
+    /// Return the TARGET2 calendar of the euro.
+    pub fn target2() -> Self {
+        Self::new("TARGET2")
+            .with_rule(HolidayRule::fixed(1, 1))
+            .with_rule(HolidayRule::easter(-2))
+            .with_rule(HolidayRule::easter(1))
+            .with_rule(HolidayRule::fixed(5, 1))
+            .with_rule(HolidayRule::fixed(12, 25))
+            .with_rule(HolidayRule::fixed(12, 26))
+            .with_holidays(TARGET2_HOLIDAYS)
+            .with_settlement_window(time(7, 0), time(18, 0))
+    }
+
+    /// Return the Fedwire Funds Service calendar of the US dollar. The settlement window
+    /// opens at 21:00 ET of the previous calendar day, i.e. only the cut-off time is set.
+    pub fn fedwire() -> Self {
+        let mut calendar = Self::new("Fedwire");
+        for rule in [
+            HolidayRule::fixed(1, 1),
+            HolidayRule::nth_weekday(1, Weekday::Mon, 3),
+            HolidayRule::nth_weekday(2, Weekday::Mon, 3),
+            HolidayRule::last_weekday(5, Weekday::Mon),
+            HolidayRule::fixed(6, 19).since(2022),
+            HolidayRule::fixed(7, 4),
+            HolidayRule::nth_weekday(9, Weekday::Mon, 1),
+            HolidayRule::nth_weekday(10, Weekday::Mon, 2),
+            HolidayRule::fixed(11, 11),
+            HolidayRule::nth_weekday(11, Weekday::Thu, 4),
+            HolidayRule::fixed(12, 25),
+        ] {
+            // A holiday falling on a Saturday is not observed on the Friday
+            calendar = calendar.with_rule(rule.observed(Observance::SundayToMonday));
+        }
+        calendar.cut_off = time(19, 0);
+
+        calendar.with_holidays(FEDWIRE_HOLIDAYS)
+    }
+
+    /// Return the CHAPS calendar of the pound sterling, i.e. the bank holidays of England and
+    /// Wales.
+    pub fn chaps() -> Self {
+        let mut calendar = Self::new("CHAPS")
+            .with_rule(HolidayRule::fixed(1, 1).observed(Observance::NextWeekday))
+            .with_rule(HolidayRule::easter(-2))
+            .with_rule(HolidayRule::easter(1))
+            .with_rule(HolidayRule::nth_weekday(5, Weekday::Mon, 1))
+            .with_rule(HolidayRule::last_weekday(5, Weekday::Mon))
+            .with_rule(HolidayRule::last_weekday(8, Weekday::Mon))
+            .with_rule(HolidayRule::fixed(12, 25).observed(Observance::NextWeekday))
+            .with_rule(HolidayRule::fixed(12, 26).observed(Observance::NextWeekday))
+            .with_holidays(CHAPS_HOLIDAYS)
+            .with_settlement_window(time(6, 0), time(18, 0));
+        calendar.business_days.extend(dates(CHAPS_BUSINESS_DAYS));
+
+        calendar
+    }
+
+    /// Set the days of the weekend, e.g. `[Weekday::Fri, Weekday::Sat]`. Returns
+    /// `Error::NoBusinessDays` for a weekend covering every day of the week.
+    pub fn with_weekend(mut self, weekend: &[Weekday]) -> Result<Self, Error> {
+        self.weekend = weekend.to_vec();
+
+        if self.has_weekdays() {
+            Ok(self)
+        } else {
+            Err(Error::NoBusinessDays(self.name))
+        }
+    }
+
+    /// Add the rule of a yearly holiday.
+    pub fn with_rule(mut self, rule: HolidayRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Set the settlement window, in the local time of the payment system.
+    pub fn with_settlement_window(
+        mut self,
+        opening: Option<NaiveTime>,
+        cut_off: Option<NaiveTime>,
+    ) -> Self {
+        self.opening = opening;
+        self.cut_off = cut_off;
+        self
+    }
+
+    fn with_holidays(mut self, holidays: &[(i32, u32, u32)]) -> Self {
+        self.holidays.extend(dates(holidays));
+        self
+    }
+
+    /// Add the holidays, and business days prefixed with `!`, of the lines of a holiday file.
+    pub fn load_holidays(mut self, data: &str) -> Result<Self, Error> {
+        for (index, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let value = line.split_whitespace().next().unwrap_or_default();
+            let (value, business_day) = match value.strip_prefix('!') {
+                Some(value) => (value, true),
+                None => (value, false),
+            };
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                Error::InvalidHoliday {
+                    line: index + 1,
+                    value: line.to_string(),
+                }
+            })?;
+
+            if business_day {
+                self.holidays.remove(&date);
+                self.business_days.insert(date);
+            } else {
+                self.business_days.remove(&date);
+                self.holidays.insert(date);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Add the holidays of a holiday file, see `load_holidays`.
+    pub fn load_file<P: AsRef<Path>>(self, path: P) -> Result<Self, Error> {
+        let data = std::fs::read_to_string(path)?;
+        self.load_holidays(&data)
+    }
+
+    /// Return the holidays of the year, observed on a business day, except the business days
+    /// overriding the rules.
+    pub fn holidays(&self, year: i32) -> BTreeSet<NaiveDate> {
+        let mut holidays: BTreeSet<NaiveDate> = BTreeSet::new();
+
+        for rule in self.rules.iter() {
+            let date = match rule.date(year) {
+                Some(date) => date,
+                None => continue,
+            };
+            let observed = match rule.observance {
+                Observance::Actual => Some(date),
+                Observance::SundayToMonday if date.weekday() == Weekday::Sun => date.succ_opt(),
+                Observance::SundayToMonday => Some(date),
+                Observance::NextWeekday => {
+                    let mut observed = Some(date);
+                    while let Some(date) = observed.filter(|date| {
+                        self.has_weekdays() && (self.is_weekend(*date) || holidays.contains(date))
+                    }) {
+                        observed = date.succ_opt();
+                    }
+                    observed
+                }
+            };
+            holidays.extend(observed);
+        }
+        holidays.extend(self.holidays.iter().filter(|date| date.year() == year));
+        holidays.retain(|date| !self.business_days.contains(date));
+
+        holidays
+    }
+
+    /// Return whether a day of the week is not a day of the weekend. A calendar whose
+    /// weekend covers every day of the week has no business days.
+    pub fn has_weekdays(&self) -> bool {
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+        .iter()
+        .any(|weekday| !self.weekend.contains(weekday))
+    }
+
+    /// Return whether the date is a day of the weekend.
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.contains(&date.weekday())
+    }
+
+    /// Return whether the date is a holiday.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays(date.year()).contains(&date)
+    }
+
+    /// Return whether the date is a business day, i.e. neither a day of the weekend nor a
+    /// holiday.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Roll a date that is not a business day to a business day. The date is returned
+    /// unchanged by a calendar without business days, see `has_weekdays`.
+    pub fn roll(&self, date: NaiveDate, convention: RollConvention) -> NaiveDate {
+        match convention {
+            RollConvention::Following => self.step(date, 1),
+            RollConvention::Preceding => self.step(date, -1),
+            RollConvention::ModifiedFollowing => {
+                let following = self.step(date, 1);
+                if following.month() == date.month() {
+                    following
+                } else {
+                    self.step(date, -1)
+                }
+            }
+            RollConvention::ModifiedPreceding => {
+                let preceding = self.step(date, -1);
+                if preceding.month() == date.month() {
+                    preceding
+                } else {
+                    self.step(date, 1)
+                }
+            }
+        }
+    }
+
+    /// Add business days to a date, or subtract them for negative `days`. The date itself
+    /// need not be a business day. The date is returned unchanged by a calendar without
+    /// business days, see `has_weekdays`.
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> NaiveDate {
+        if !self.has_weekdays() {
+            return date;
+        }
+
+        let step = Duration::days(days.signum());
+        let mut date = date;
+
+        for _ in 0..days.abs() {
+            date = self.step(date + step, days.signum());
+        }
+
+        date
+    }
+
+    /// Return the first business day from the date, in the direction of the sign of `step`,
+    /// or the date for a calendar without business days.
+    fn step(&self, date: NaiveDate, step: i64) -> NaiveDate {
+        if !self.has_weekdays() {
+            return date;
+        }
+
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date += Duration::days(step);
+        }
+
+        date
+    }
+}
+
+/// Calendars of the settlement currencies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendars {
+    /// Calendars by currency code, e.g. `EUR`
+    pub calendars: HashMap<String, Calendar>,
+    /// CLS pay-in deadline of the requested CLS time `CLSTm`, in CET
+    pub cls_cut_off: Option<NaiveTime>,
+}
+
+impl Default for Calendars {
+    fn default() -> Self {
+        let mut calendars = Self {
+            calendars: HashMap::new(),
+            cls_cut_off: time(10, 0),
+        };
+        calendars.insert("EUR", Calendar::target2());
+        calendars.insert("USD", Calendar::fedwire());
+        calendars.insert("GBP", Calendar::chaps());
+
+        calendars
+    }
+}
+
+impl Calendars {
+    /// Set the calendar of a currency, replacing a bundled calendar.
+    pub fn insert(&mut self, ccy: &str, calendar: Calendar) {
+        self.calendars.insert(ccy.to_string(), calendar);
+    }
+
+    /// Return the calendar of a currency.
+    pub fn get(&self, ccy: &str) -> Option<&Calendar> {
+        self.calendars.get(ccy)
+    }
+
+    /// Check that the date is a business day of the calendar of the currency.
+    #[cfg(any(feature = "pacs", feature = "pain"))]
+    fn check_date(
+        &self,
+        issues: &mut Vec<DateIssue>,
+        reference: &str,
+        element: &'static str,
+        ccy: &str,
+        date: NaiveDate,
+    ) {
+        match self.get(ccy) {
+            Some(calendar) if !calendar.is_business_day(date) => {
+                issues.push(DateIssue::NonBusinessDay {
+                    reference: reference.to_string(),
+                    element,
+                    date,
+                    next: calendar.roll(date, RollConvention::Following),
+                })
+            }
+            Some(_) => {}
+            None => issues.push(DateIssue::UnknownCurrency {
+                reference: reference.to_string(),
+                ccy: ccy.to_string(),
+            }),
+        }
+    }
+
+    /// Check that the group date, e.g. the `IntrBkSttlmDt` of the group header, is a business
+    /// day of the calendars of every currency of the transactions.
+    #[cfg(feature = "pacs")]
+    fn check_group_date<'a>(
+        &self,
+        issues: &mut Vec<DateIssue>,
+        reference: &str,
+        element: &'static str,
+        ccys: impl Iterator<Item = &'a str>,
+        date: NaiveDate,
+    ) {
+        for ccy in ccys.collect::<BTreeSet<_>>() {
+            self.check_date(issues, reference, element, ccy, date);
+        }
+    }
+
+    /// Check that the date is at least `days` business days after the submission date.
+    #[cfg(any(feature = "pacs", feature = "pain"))]
+    fn check_lead_time(
+        &self,
+        issues: &mut Vec<DateIssue>,
+        reference: &str,
+        ccy: &str,
+        date: NaiveDate,
+        submission: NaiveDate,
+        days: i64,
+    ) {
+        let calendar = match self.get(ccy) {
+            Some(calendar) => calendar,
+            None => return,
+        };
+        let earliest =
+            calendar.add_business_days(calendar.roll(submission, RollConvention::Following), days);
+
+        if date < earliest {
+            issues.push(DateIssue::LeadTime {
+                reference: reference.to_string(),
+                date,
+                earliest,
+            });
+        }
+    }
+
+    /// Check the requested settlement times `SttlmTmReq` against the CLS cut-off and the
+    /// settlement window of the calendar of the currency.
+    #[cfg(feature = "pacs")]
+    fn check_settlement_times(
+        &self,
+        issues: &mut Vec<DateIssue>,
+        reference: &str,
+        ccy: &str,
+        cls_tm: Option<NaiveTime>,
+        times: [(&'static str, Option<NaiveTime>); 3],
+    ) {
+        let mut push = |element: &'static str, time: NaiveTime, limit: NaiveTime| {
+            issues.push(DateIssue::SettlementTime {
+                reference: reference.to_string(),
+                element,
+                time,
+                limit,
+            })
+        };
+
+        if let (Some(time), Some(cut_off)) = (cls_tm, self.cls_cut_off) {
+            if time > cut_off {
+                push("CLSTm", time, cut_off);
+            }
+        }
+
+        let calendar = match self.get(ccy) {
+            Some(calendar) => calendar,
+            None => return,
+        };
+        for (element, time) in times {
+            let time = match time {
+                Some(time) => time,
+                None => continue,
+            };
+            match (calendar.opening, calendar.cut_off) {
+                (Some(opening), _) if time < opening => push(element, time, opening),
+                (_, Some(cut_off)) if time > cut_off => push(element, time, cut_off),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// SEPA direct debit lead times, i.e. the number of TARGET2 business days between the
+/// submission of a collection and its requested collection date, by sequence type `SeqTp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectDebitLeadTimes {
+    pub frst: i64,
+    pub rcur: i64,
+    pub fnal: i64,
+    pub ooff: i64,
+    pub rpre: i64,
+}
+
+impl Default for DirectDebitLeadTimes {
+    /// Return the D-1 lead time of every sequence type of the SEPA Core and B2B schemes.
+    fn default() -> Self {
+        Self::uniform(1)
+    }
+}
+
+impl DirectDebitLeadTimes {
+    /// Return the same lead time for every sequence type.
+    pub fn uniform(days: i64) -> Self {
+        Self {
+            frst: days,
+            rcur: days,
+            fnal: days,
+            ooff: days,
+            rpre: days,
+        }
+    }
+
+    /// Return the D-2 lead time of every sequence type, for a creditor bank or CSM that
+    /// requires SEPA collections two TARGET2 business days before the requested collection
+    /// date, instead of the D-1 of the schemes.
+    pub fn d2() -> Self {
+        Self::uniform(2)
+    }
+
+    /// Return the lead times of the SEPA Core scheme before November 2016, i.e. D-5 for
+    /// `FRST` and `OOFF`, and D-2 for `RCUR` and `FNAL`.
+    pub fn legacy() -> Self {
+        Self {
+            frst: 5,
+            rcur: 2,
+            fnal: 2,
+            ooff: 5,
+            rpre: 1,
+        }
+    }
+
+    /// Return the lead time of a sequence type code, e.g. `RCUR`, or of a first collection
+    /// for an unknown sequence type.
+    pub fn days(&self, seq_tp: &str) -> i64 {
+        match seq_tp {
+            "RCUR" => self.rcur,
+            "FNAL" => self.fnal,
+            "OOFF" => self.ooff,
+            "RPRE" => self.rpre,
+            _ => self.frst,
+        }
+    }
+}
+
+/// Date or time of a message that cannot be settled as requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateIssue {
+    /// The date is not a business day of the calendar of its currency, `next` is the next
+    /// business day
+    NonBusinessDay {
+        reference: String,
+        element: &'static str,
+        date: NaiveDate,
+        next: NaiveDate,
+    },
+    /// The requested collection date is before the earliest date of the lead time of its
+    /// sequence type `SeqTp`
+    LeadTime {
+        reference: String,
+        date: NaiveDate,
+        earliest: NaiveDate,
+    },
+    /// The requested settlement time is outside the settlement window, or after the CLS
+    /// cut-off, `limit` is the opening or cut-off time
+    SettlementTime {
+        reference: String,
+        element: &'static str,
+        time: NaiveTime,
+        limit: NaiveTime,
+    },
+    /// No calendar for the currency
+    UnknownCurrency { reference: String, ccy: String },
+}
+
+/// Check the interbank settlement dates and the requested settlement times of a
+/// `pacs.008` credit transfer.
+#[cfg(feature = "pacs")]
+pub fn check_credit_transfer<A, B>(
+    msg: &pacs_008::FiToFiCustomerCreditTransferV10<A, B>,
+    calendars: &Calendars,
+) -> Vec<DateIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+    let grp_hdr = &msg.grp_hdr;
+
+    if let Some(dt) = &grp_hdr.intr_bk_sttlm_dt {
+        calendars.check_group_date(
+            &mut issues,
+            &grp_hdr.msg_id.value,
+            "IntrBkSttlmDt",
+            msg.cdt_trf_tx_inf
+                .iter()
+                .map(|tx| tx.intr_bk_sttlm_amt.ccy.value.as_str()),
+            dt.value,
+        );
+    }
+    for tx in msg.cdt_trf_tx_inf.iter() {
+        let reference = &tx.pmt_id.end_to_end_id.value;
+        let ccy = &tx.intr_bk_sttlm_amt.ccy.value;
+
+        if let Some(dt) = &tx.intr_bk_sttlm_dt {
+            calendars.check_date(&mut issues, reference, "IntrBkSttlmDt", ccy, dt.value);
+        }
+        if let Some(req) = &tx.sttlm_tm_req {
+            calendars.check_settlement_times(
+                &mut issues,
+                reference,
+                ccy,
+                req.cls_tm.as_ref().map(|tm| tm.value),
+                [
+                    ("TillTm", req.till_tm.as_ref().map(|tm| tm.value)),
+                    ("FrTm", req.fr_tm.as_ref().map(|tm| tm.value)),
+                    ("RjctTm", req.rjct_tm.as_ref().map(|tm| tm.value)),
+                ],
+            );
+        }
+    }
+
+    issues
+}
+
+/// Check the interbank settlement dates and the requested settlement times of a
+/// `pacs.009` financial institution credit transfer.
+#[cfg(feature = "pacs")]
+pub fn check_institution_transfer<A, B>(
+    msg: &pacs_009::FinancialInstitutionCreditTransferV10<A, B>,
+    calendars: &Calendars,
+) -> Vec<DateIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+    let grp_hdr = &msg.grp_hdr;
+
+    if let Some(dt) = &grp_hdr.intr_bk_sttlm_dt {
+        calendars.check_group_date(
+            &mut issues,
+            &grp_hdr.msg_id.value,
+            "IntrBkSttlmDt",
+            msg.cdt_trf_tx_inf
+                .iter()
+                .map(|tx| tx.intr_bk_sttlm_amt.ccy.value.as_str()),
+            dt.value,
+        );
+    }
+    for tx in msg.cdt_trf_tx_inf.iter() {
+        let reference = &tx.pmt_id.end_to_end_id.value;
+        let ccy = &tx.intr_bk_sttlm_amt.ccy.value;
+
+        if let Some(dt) = &tx.intr_bk_sttlm_dt {
+            calendars.check_date(&mut issues, reference, "IntrBkSttlmDt", ccy, dt.value);
+        }
+        if let Some(req) = &tx.sttlm_tm_req {
+            calendars.check_settlement_times(
+                &mut issues,
+                reference,
+                ccy,
+                req.cls_tm.as_ref().map(|tm| tm.value),
+                [
+                    ("TillTm", req.till_tm.as_ref().map(|tm| tm.value)),
+                    ("FrTm", req.fr_tm.as_ref().map(|tm| tm.value)),
+                    ("RjctTm", req.rjct_tm.as_ref().map(|tm| tm.value)),
+                ],
+            );
+        }
+    }
+
+    issues
+}
+
+/// Check the interbank settlement dates of a `pacs.003` direct debit, and their lead time
+/// from the submission date.
+#[cfg(feature = "pacs")]
+pub fn check_direct_debit<A, B>(
+    msg: &pacs_003::FiToFiCustomerDirectDebitV09<A, B>,
+    calendars: &Calendars,
+    lead_times: &DirectDebitLeadTimes,
+    submission: NaiveDate,
+) -> Vec<DateIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+    let grp_hdr = &msg.grp_hdr;
+
+    if let Some(dt) = &grp_hdr.intr_bk_sttlm_dt {
+        calendars.check_group_date(
+            &mut issues,
+            &grp_hdr.msg_id.value,
+            "IntrBkSttlmDt",
+            msg.drct_dbt_tx_inf
+                .iter()
+                .map(|tx| tx.intr_bk_sttlm_amt.ccy.value.as_str()),
+            dt.value,
+        );
+    }
+    for tx in msg.drct_dbt_tx_inf.iter() {
+        let reference = &tx.pmt_id.end_to_end_id.value;
+        let ccy = &tx.intr_bk_sttlm_amt.ccy.value;
+
+        if let Some(dt) = &tx.intr_bk_sttlm_dt {
+            calendars.check_date(&mut issues, reference, "IntrBkSttlmDt", ccy, dt.value);
+        }
+        let dt = match tx
+            .intr_bk_sttlm_dt
+            .as_ref()
+            .or(grp_hdr.intr_bk_sttlm_dt.as_ref())
+        {
+            Some(dt) => dt.value,
+            None => continue,
+        };
+        let seq_tp = tx
+            .pmt_tp_inf
+            .as_ref()
+            .or(grp_hdr.pmt_tp_inf.as_ref())
+            .and_then(|pmt_tp_inf| pmt_tp_inf.seq_tp.as_ref())
+            .map(|seq_tp| match seq_tp {
+                pacs_003::SequenceType3Code::Rcur => "RCUR",
+                pacs_003::SequenceType3Code::Fnal => "FNAL",
+                pacs_003::SequenceType3Code::Ooff => "OOFF",
+                pacs_003::SequenceType3Code::Rpre => "RPRE",
+                _ => "FRST",
+            });
+        calendars.check_lead_time(
+            &mut issues,
+            reference,
+            ccy,
+            dt,
+            submission,
+            lead_times.days(seq_tp.unwrap_or_default()),
+        );
+    }
+
+    issues
+}
+
+/// Check the requested execution dates `ReqdExctnDt` of a `pain.001` credit transfer
+/// initiation, in the currency of the debtor account, or else of the first transaction.
+#[cfg(feature = "pain")]
+pub fn check_credit_transfer_initiation<A, B>(
+    initn: &pain_001::CustomerCreditTransferInitiationV11<A, B>,
+    calendars: &Calendars,
+) -> Vec<DateIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+
+    for pmt_inf in initn.pmt_inf.iter() {
+        let reqd_exctn_dt = &pmt_inf.reqd_exctn_dt.value;
+        let dt = match (&reqd_exctn_dt.dt, &reqd_exctn_dt.dt_tm) {
+            (Some(dt), _) => dt.value,
+            (None, Some(dt_tm)) => dt_tm.value.date_naive(),
+            (None, None) => continue,
+        };
+        let ccy = pmt_inf.dbtr_acct.ccy.as_ref().or_else(|| {
+            pmt_inf.cdt_trf_tx_inf.first().and_then(|tx| {
+                let amt = &tx.amt.value;
+                amt.instd_amt
+                    .as_ref()
+                    .or(amt.eqvt_amt.as_ref().map(|eqvt_amt| &eqvt_amt.amt))
+                    .map(|amt| &amt.ccy)
+            })
+        });
+
+        if let Some(ccy) = ccy {
+            calendars.check_date(
+                &mut issues,
+                &pmt_inf.pmt_inf_id.value,
+                "ReqdExctnDt",
+                &ccy.value,
+                dt,
+            );
+        }
+    }
+
+    issues
+}
+
+/// Check the requested collection dates `ReqdColltnDt` of a `pain.008` direct debit
+/// initiation, and their lead time from the submission date by sequence type `SeqTp`. The
+/// lead time of a transaction is only checked when it has its own sequence type.
+#[cfg(feature = "pain")]
+pub fn check_direct_debit_initiation<A, B>(
+    initn: &pain_008::CustomerDirectDebitInitiationV10<A, B>,
+    calendars: &Calendars,
+    lead_times: &DirectDebitLeadTimes,
+    submission: NaiveDate,
+) -> Vec<DateIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let seq_tp_code = |pmt_tp_inf: &Option<pain_008::PaymentTypeInformation29>| {
+        pmt_tp_inf
+            .as_ref()
+            .and_then(|pmt_tp_inf| pmt_tp_inf.seq_tp.as_ref())
+            .map(|seq_tp| match seq_tp {
+                pain_008::SequenceType3Code::Rcur => "RCUR",
+                pain_008::SequenceType3Code::Fnal => "FNAL",
+                pain_008::SequenceType3Code::Ooff => "OOFF",
+                pain_008::SequenceType3Code::Rpre => "RPRE",
+                _ => "FRST",
+            })
+    };
+    let mut issues = vec![];
+
+    for pmt_inf in initn.pmt_inf.iter() {
+        let reference = &pmt_inf.pmt_inf_id.value;
+        let dt = pmt_inf.reqd_colltn_dt.value;
+        let ccy = match pmt_inf.drct_dbt_tx_inf.first() {
+            Some(tx) => &tx.instd_amt.ccy.value,
+            None => continue,
+        };
+
+        calendars.check_date(&mut issues, reference, "ReqdColltnDt", ccy, dt);
+        calendars.check_lead_time(
+            &mut issues,
+            reference,
+            ccy,
+            dt,
+            submission,
+            lead_times.days(seq_tp_code(&pmt_inf.pmt_tp_inf).unwrap_or_default()),
+        );
+        for tx in pmt_inf.drct_dbt_tx_inf.iter() {
+            if let Some(seq_tp) = seq_tp_code(&tx.pmt_tp_inf) {
+                calendars.check_lead_time(
+                    &mut issues,
+                    &tx.pmt_id.end_to_end_id.value,
+                    &tx.instd_amt.ccy.value,
+                    dt,
+                    submission,
+                    lead_times.days(seq_tp),
+                );
+            }
+        }
+    }
+
+    issues
+}
+
+/// Return Easter Sunday of the year, by the anonymous Gregorian algorithm.
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Return the `n`th weekday of the month, or the last weekday of the month for 0.
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u32) -> Option<NaiveDate> {
+    if n == 0 {
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+        let last = NaiveDate::from_ymd_opt(next_year, next_month, 1)?.pred_opt()?;
+        let offset =
+            (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+
+        return Some(last - Duration::days(offset as i64));
+    }
+
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let offset = (7 + weekday.num_days_from_monday() - first.weekday().num_days_from_monday()) % 7;
+    let date = first + Duration::days((offset + 7 * (n - 1)) as i64);
+
+    Some(date).filter(|date| date.month() == month)
+}
+
+fn dates(dates: &[(i32, u32, u32)]) -> impl Iterator<Item = NaiveDate> + '_ {
+    dates
+        .iter()
+        .filter_map(|(year, month, day)| NaiveDate::from_ymd_opt(*year, *month, *day))
+}
+
+fn time(hour: u32, min: u32) -> Option<NaiveTime> {
+    NaiveTime::from_hms_opt(hour, min, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_target2() {
+        let calendar = Calendar::target2();
+
+        for holiday in [
+            "2023-04-07",
+            "2023-04-10",
+            "2023-05-01",
+            "2023-12-25",
+            "2023-12-26",
+        ] {
+            assert!(!calendar.is_business_day(date(holiday)), "{}", holiday);
+        }
+        assert!(calendar.is_holiday(date("2023-01-01")));
+        // A holiday falling on a weekend is not observed on the Monday
+        assert!(calendar.is_business_day(date("2023-01-02")));
+        assert!(calendar.is_business_day(date("2023-04-11")));
+        assert!(calendar.is_business_day(date("2024-03-28")));
+        assert!(calendar.is_holiday(date("2024-03-29")));
+
+        assert_eq!(
+            calendar.roll(date("2023-04-07"), RollConvention::Following),
+            date("2023-04-11")
+        );
+        assert_eq!(
+            calendar.roll(date("2023-04-07"), RollConvention::Preceding),
+            date("2023-04-06")
+        );
+        assert_eq!(
+            calendar.roll(date("2023-09-30"), RollConvention::ModifiedFollowing),
+            date("2023-09-29")
+        );
+        assert_eq!(
+            calendar.roll(date("2023-04-01"), RollConvention::ModifiedPreceding),
+            date("2023-04-03")
+        );
+        assert_eq!(
+            calendar.add_business_days(date("2023-04-06"), 1),
+            date("2023-04-11")
+        );
+        assert_eq!(
+            calendar.add_business_days(date("2023-04-11"), -2),
+            date("2023-04-05")
+        );
+    }
+
+    #[test]
+    fn test_fedwire() {
+        let calendar = Calendar::fedwire();
+
+        for holiday in [
+            "2023-01-16",
+            "2023-05-29",
+            "2022-06-20",
+            "2023-11-23",
+            "2023-12-25",
+        ] {
+            assert!(calendar.is_holiday(date(holiday)), "{}", holiday);
+        }
+        // New Year's Day on a Saturday is not observed on the Friday
+        assert!(calendar.is_business_day(date("2021-12-31")));
+        assert!(calendar.is_business_day(date("2021-06-18")));
+    }
+
+    #[test]
+    fn test_chaps() {
+        let calendar = Calendar::chaps();
+
+        for holiday in [
+            "2021-12-27",
+            "2021-12-28",
+            "2022-01-03",
+            "2022-06-02",
+            "2022-06-03",
+            "2022-12-26",
+            "2022-12-27",
+            "2023-05-01",
+            "2023-05-08",
+            "2023-08-28",
+        ] {
+            assert!(calendar.is_holiday(date(holiday)), "{}", holiday);
+        }
+        assert!(calendar.is_business_day(date("2022-05-30")));
+        assert!(calendar.is_business_day(date("2020-05-04")));
+        assert!(calendar.is_holiday(date("2020-05-08")));
+    }
+
+    #[test]
+    fn test_load_holidays() -> Result<(), Error> {
+        let calendar =
+            Calendar::chaps().load_holidays("# CHAPS\n\n2030-01-02 New holiday\n!2023-08-28\n")?;
+        assert!(calendar.is_holiday(date("2030-01-02")));
+        assert!(calendar.is_business_day(date("2023-08-28")));
+
+        assert!(matches!(
+            Calendar::new("SIC").load_holidays("2023-01-02\n02.01.2023"),
+            Err(Error::InvalidHoliday { line: 2, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lead_times() {
+        let lead_times = DirectDebitLeadTimes::legacy();
+        assert_eq!(lead_times.days("FRST"), 5);
+        assert_eq!(lead_times.days("RCUR"), 2);
+        assert_eq!(DirectDebitLeadTimes::default().days("OOFF"), 1);
+
+        let lead_times = DirectDebitLeadTimes::d2();
+        for seq_tp in ["FRST", "RCUR", "FNAL", "OOFF", "RPRE"] {
+            assert_eq!(lead_times.days(seq_tp), 2, "{}", seq_tp);
+        }
+    }
+
+    #[test]
+    fn test_weekend() {
+        let calendar = Calendar::new("FRSA")
+            .with_weekend(&[Weekday::Fri, Weekday::Sat])
+            .unwrap();
+        assert!(!calendar.is_business_day(date("2023-04-07")));
+        assert!(calendar.is_business_day(date("2023-04-09")));
+
+        assert!(matches!(
+            Calendar::new("NONE").with_weekend(&[
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ]),
+            Err(Error::NoBusinessDays(_))
+        ));
+
+        // A calendar without business days returns the date instead of looping forever
+        let mut calendar = Calendar::new("NONE");
+        calendar.weekend.extend([
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]);
+        assert!(!calendar.has_weekdays());
+        assert_eq!(
+            calendar.roll(date("2023-04-07"), RollConvention::Following),
+            date("2023-04-07")
+        );
+        assert_eq!(
+            calendar.add_business_days(date("2023-04-07"), 2),
+            date("2023-04-07")
+        );
+    }
+
+    #[cfg(feature = "pain")]
+    #[test]
+    fn test_check_direct_debit_initiation() {
+        let pmt_inf = |id: &str, dt: &str, seq_tp: pain_008::SequenceType3Code| {
+            pain_008::PaymentInstruction39 {
+                pmt_inf_id: pain_008::Max35Text {
+                    value: id.to_string(),
+                },
+                pmt_tp_inf: Some(pain_008::PaymentTypeInformation29 {
+                    seq_tp: Some(seq_tp),
+                    ..Default::default()
+                }),
+                reqd_colltn_dt: pain_008::IsoDate { value: date(dt) },
+                drct_dbt_tx_inf: vec![pain_008::DirectDebitTransactionInformation28 {
+                    instd_amt: pain_008::ActiveOrHistoricCurrencyAndAmount {
+                        ccy: pain_008::ActiveOrHistoricCurrencyCode {
+                            value: "EUR".to_string(),
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }
+        };
+        let initn = pain_008::CustomerDirectDebitInitiationV10::<
+            crate::documents::Dmkr,
+            crate::documents::Dmkr,
+        > {
+            pmt_inf: vec![
+                pmt_inf("PMT-1", "2023-04-11", pain_008::SequenceType3Code::Rcur),
+                pmt_inf("PMT-2", "2023-04-10", pain_008::SequenceType3Code::Rcur),
+                pmt_inf("PMT-3", "2023-04-12", pain_008::SequenceType3Code::Frst),
+            ],
+            ..Default::default()
+        };
+
+        let issues = check_direct_debit_initiation(
+            &initn,
+            &Calendars::default(),
+            &DirectDebitLeadTimes::legacy(),
+            date("2023-04-06"),
+        );
+        assert_eq!(
+            issues,
+            vec![
+                DateIssue::LeadTime {
+                    reference: "PMT-1".to_string(),
+                    date: date("2023-04-11"),
+                    earliest: date("2023-04-12"),
+                },
+                DateIssue::NonBusinessDay {
+                    reference: "PMT-2".to_string(),
+                    element: "ReqdColltnDt",
+                    date: date("2023-04-10"),
+                    next: date("2023-04-11"),
+                },
+                DateIssue::LeadTime {
+                    reference: "PMT-2".to_string(),
+                    date: date("2023-04-10"),
+                    earliest: date("2023-04-12"),
+                },
+                DateIssue::LeadTime {
+                    reference: "PMT-3".to_string(),
+                    date: date("2023-04-12"),
+                    earliest: date("2023-04-17"),
+                },
+            ]
+        );
+
+        // The D-2 lead time applies to the first collection too
+        let issues = check_direct_debit_initiation(
+            &initn,
+            &Calendars::default(),
+            &DirectDebitLeadTimes::d2(),
+            date("2023-04-06"),
+        );
+        assert!(!issues.iter().any(|issue| matches!(
+            issue,
+            DateIssue::LeadTime { reference, .. } if reference == "PMT-3"
+        )));
+        assert!(issues.contains(&DateIssue::LeadTime {
+            reference: "PMT-1".to_string(),
+            date: date("2023-04-11"),
+            earliest: date("2023-04-12"),
+        }));
+    }
+
+    #[cfg(feature = "pacs")]
+    #[test]
+    fn test_check_credit_transfer() {
+        let tx = |end_to_end_id: &str, ccy: &str, till_tm: u32, cls_tm: u32| {
+            pacs_008::CreditTransferTransaction50::<crate::documents::Dmkr> {
+                pmt_id: pacs_008::PaymentIdentification13 {
+                    end_to_end_id: pacs_008::Max35Text {
+                        value: end_to_end_id.to_string(),
+                    },
+                    ..Default::default()
+                },
+                intr_bk_sttlm_amt: pacs_008::ActiveCurrencyAndAmount {
+                    ccy: pacs_008::ActiveCurrencyCode {
+                        value: ccy.to_string(),
+                    },
+                    ..Default::default()
+                },
+                sttlm_tm_req: Some(pacs_008::SettlementTimeRequest2 {
+                    till_tm: time(till_tm, 0).map(|value| pacs_008::IsoTime { value }),
+                    cls_tm: time(cls_tm, 0).map(|value| pacs_008::IsoTime { value }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        };
+        let msg = pacs_008::FiToFiCustomerCreditTransferV10::<
+            crate::documents::Dmkr,
+            crate::documents::Dmkr,
+        > {
+            grp_hdr: pacs_008::GroupHeader96 {
+                msg_id: pacs_008::Max35Text {
+                    value: "PACS-1".to_string(),
+                },
+                intr_bk_sttlm_dt: Some(pacs_008::IsoDate {
+                    value: date("2023-05-01"),
+                }),
+                ..Default::default()
+            },
+            cdt_trf_tx_inf: vec![tx("E2E-1", "EUR", 17, 9), tx("E2E-2", "JPY", 19, 11)],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            check_credit_transfer(&msg, &Calendars::default()),
+            vec![
+                DateIssue::NonBusinessDay {
+                    reference: "PACS-1".to_string(),
+                    element: "IntrBkSttlmDt",
+                    date: date("2023-05-01"),
+                    next: date("2023-05-02"),
+                },
+                // The group settlement date is checked in every currency of the transactions
+                DateIssue::UnknownCurrency {
+                    reference: "PACS-1".to_string(),
+                    ccy: "JPY".to_string(),
+                },
+                DateIssue::SettlementTime {
+                    reference: "E2E-2".to_string(),
+                    element: "CLSTm",
+                    time: time(11, 0).unwrap(),
+                    limit: time(10, 0).unwrap(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,1130 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Clearing System Member Identifications
+//!
+//! A financial institution is identified in a national clearing system by its clearing
+//! system member identification `ClrSysMmbId/MmbId`, with the code of the clearing system
+//! `ClrSysMmbId/ClrSysId/Cd`, and accounts without an IBAN by their domestic account number
+//! `Acct/Id/Othr/Id`. Each clearing system implements the `ClearingSystem` trait, to validate
+//! the member identifications and the account numbers held with a member:
+//!
+//! - `UsAba`, `USABA`, the ABA routing number and its check digit
+//! - `GbSortCode`, `GBDSC`, the sort code and the Vocalink modulus checking of accounts
+//! - `DeBlz`, `DEBLZ`, the Bankleitzahl and the check methods of the Kontonummer
+//! - `CaCpa`, `CACPA`, the institution and transit number
+//! - `AuBsb`, `AUBSB`, the bank state branch number
+//!
+//! The modulus checking of UK accounts, and the check methods of German accounts, depend on
+//! the member. Their tables are published by Vocalink, `valacdos.txt` and `scsubtab.txt`, and
+//! by the Deutsche Bundesbank, the Bankleitzahlendatei, and are loaded from a local copy. A
+//! valid account number is reported as `AccountCheck::Valid` when its check digits passed
+//! the check of the member, and as `AccountCheck::NoCheckDigits` or `AccountCheck::Unchecked`
+//! when only its format could be validated.
+//!
+//! ```rust
+//! use iso_20022_sdk::clearing_system::{check_identifiers, AuBsb, CaCpa, DeBlz, GbSortCode, UsAba};
+//!
+//! let gb = GbSortCode::default()
+//!     .load_weights(&std::fs::read_to_string("valacdos.txt")?)?
+//!     .load_substitutes(&std::fs::read_to_string("scsubtab.txt")?)?;
+//! let de = DeBlz::default().load_banks(&bankleitzahlen)?;
+//!
+//! // Validate the member identifications and the domestic accounts of a document
+//! let invalid = check_identifiers(&doc, &[&UsAba, &gb, &de, &CaCpa, &AuBsb])?;
+//! assert!(invalid.is_empty());
+//! ```
+use std::collections::HashMap;
+
+use serde::Serialize;
+use sxd_document::dom::{self, ChildOfElement, ParentOfChild};
+use sxd_document::parser;
+use sxd_xpath::evaluate_xpath;
+
+/// XPath to the clearing system member identifications.
+const MEMBER_X_PATH: &str = "//*[local-name()='ClrSysMmbId']";
+
+/// XPath to the accounts held with an agent, i.e. the debtor and creditor accounts, and the
+/// accounts of a statement or notification with their servicer.
+const ACCOUNT_X_PATH: &str =
+    "//*[local-name()='DbtrAcct' or local-name()='CdtrAcct' or local-name()='Acct']";
+
+/// Length of the account number of a Vocalink modulus check.
+const GB_ACCOUNT_LENGTH: usize = 8;
+
+/// Shortest account number of a Vocalink modulus check, padded with leading zeros.
+const GB_ACCOUNT_MIN_LENGTH: usize = 6;
+
+/// Sort code substituted by the Vocalink exception 8.
+const GB_EXCEPTION_8_SORT_CODE: &str = "090126";
+
+/// Sort code substituted by the Vocalink exception 9.
+const GB_EXCEPTION_9_SORT_CODE: &str = "309634";
+
+/// Weights substituted by the Vocalink exception 2, for an account with `a` not 0 and `g`
+/// not 9.
+const GB_EXCEPTION_2_WEIGHTS: [i32; 14] = [0, 0, 1, 2, 5, 3, 6, 4, 8, 7, 10, 9, 3, 1];
+
+/// Weights substituted by the Vocalink exception 2, for an account with `a` not 0 and `g` 9.
+const GB_EXCEPTION_2_WEIGHTS_G9: [i32; 14] = [0, 0, 0, 0, 0, 0, 0, 0, 8, 7, 10, 9, 3, 1];
+
+/// Length of the Kontonummer of a check method.
+const DE_ACCOUNT_LENGTH: usize = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// SXD Document Error
+    #[error(transparent)]
+    XsdDocument(#[from] sxd_document::parser::Error),
+    /// SXD XPath Error
+    #[error(transparent)]
+    XsdXPath(#[from] sxd_xpath::Error),
+    /// A line of a Vocalink or Bundesbank table is invalid
+    #[error("invalid table line {line}: {value}")]
+    InvalidTable { line: usize, value: String },
+}
+
+/// Error of a member identification or an account number.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IdentifierError {
+    /// The identifier contains a character not allowed by the clearing system
+    #[error("invalid character {0:?}")]
+    InvalidCharacter(char),
+    /// The length of the identifier is not allowed by the clearing system
+    #[error("invalid length {0}")]
+    InvalidLength(usize),
+    /// The identifier does not have the format of the clearing system, e.g. a leading zero
+    #[error("invalid format: {0}")]
+    InvalidFormat(&'static str),
+    /// The check digits of the identifier are not valid
+    #[error("invalid check digits")]
+    InvalidCheckDigits,
+}
+
+/// Outcome of the validation of an account number with a valid format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountCheck {
+    /// The check digits of the account number passed the check of the member
+    Valid,
+    /// The account number has no check digits, e.g. in the clearing system or with the
+    /// check method `09` of a German bank
+    NoCheckDigits,
+    /// The check digits of the account number could not be checked, e.g. for a member
+    /// missing from the table, an unsupported check method, or a foreign currency account
+    Unchecked,
+}
+
+/// National clearing system.
+pub trait ClearingSystem {
+    /// Return the code of the clearing system, e.g. `USABA`.
+    fn code(&self) -> &'static str;
+
+    /// Validate the member identification `MmbId`, e.g. a routing number.
+    fn validate_member_id(&self, mmb_id: &str) -> Result<(), IdentifierError>;
+
+    /// Validate a domestic account number held with the member, and return whether its check
+    /// digits were checked.
+    fn validate_account(
+        &self,
+        mmb_id: &str,
+        account: &str,
+    ) -> Result<AccountCheck, IdentifierError>;
+}
+
+/// US ABA routing number, i.e. 9 digits with a weighted modulus 10 check digit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UsAba;
+
+impl ClearingSystem for UsAba {
+    fn code(&self) -> &'static str {
+        "USABA"
+    }
+
+    fn validate_member_id(&self, mmb_id: &str) -> Result<(), IdentifierError> {
+        let mmb_id = digits(mmb_id, 9..=9)?;
+
+        check(weighted_sum(&mmb_id, &[1, 7, 3]).is_multiple_of(10))
+    }
+
+    /// US account numbers have no national format, up to 17 digits.
+    fn validate_account(
+        &self,
+        _mmb_id: &str,
+        account: &str,
+    ) -> Result<AccountCheck, IdentifierError> {
+        digits(account, 1..=17).map(|_| AccountCheck::NoCheckDigits)
+    }
+}
+
+/// Vocalink modulus check method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModulusMethod {
+    /// Weighted sum modulus 10
+    Mod10,
+    /// Weighted sum modulus 11
+    Mod11,
+    /// Sum of the digits of the weighted products, modulus 10
+    DoubleAlternate,
+}
+
+/// Row of the Vocalink modulus weight table `valacdos.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModulusWeight {
+    /// First sort code of the range
+    pub start: String,
+    /// Last sort code of the range
+    pub end: String,
+    pub method: ModulusMethod,
+    /// Weights of the 6 digits of the sort code `uvwxyz` and the 8 digits of the account
+    /// number `abcdefgh`
+    pub weights: [i32; 14],
+    /// Exception of the row, from 1 to 14
+    pub exception: Option<u32>,
+}
+
+/// UK sort code, i.e. 6 digits, and 8 digit account numbers validated by the Vocalink
+/// modulus checks of the sort code, with the exceptions 1 to 14. Account numbers of 6 or 7
+/// digits are padded with leading zeros.
+///
+/// An account with a sort code missing from the weight table, or with an unknown
+/// exception, cannot be checked. The foreign currency accounts of the exception 6 cannot be
+/// checked either.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GbSortCode {
+    pub weights: Vec<ModulusWeight>,
+    /// Substitute sort codes of the exception 5 by sort code, i.e. `scsubtab.txt`
+    pub substitutes: HashMap<String, String>,
+}
+
+impl GbSortCode {
+    /// Add the rows of a Vocalink modulus weight table, e.g. `valacdos.txt`.
+    pub fn load_weights(mut self, data: &str) -> Result<Self, Error> {
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid = || Error::InvalidTable {
+                line: index + 1,
+                value: line.to_string(),
+            };
+            let values: Vec<&str> = line.split_whitespace().collect();
+            if !(17..=18).contains(&values.len()) {
+                return Err(invalid());
+            }
+
+            let method = match values[2] {
+                "MOD10" => ModulusMethod::Mod10,
+                "MOD11" => ModulusMethod::Mod11,
+                "DBLAL" => ModulusMethod::DoubleAlternate,
+                _ => return Err(invalid()),
+            };
+            let mut weights = [0; 14];
+            for (weight, value) in weights.iter_mut().zip(values[3..17].iter()) {
+                *weight = value.parse().map_err(|_| invalid())?;
+            }
+            let exception = values
+                .get(17)
+                .map(|value| value.parse().map_err(|_| invalid()))
+                .transpose()?;
+
+            self.weights.push(ModulusWeight {
+                start: values[0].to_string(),
+                end: values[1].to_string(),
+                method,
+                weights,
+                exception,
+            });
+        }
+
+        Ok(self)
+    }
+
+    /// Add the substitute sort codes of the exception 5, i.e. the lines of a sort code and
+    /// its substitute of the Vocalink sort code substitution table `scsubtab.txt`.
+    pub fn load_substitutes(mut self, data: &str) -> Result<Self, Error> {
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let values: Vec<&str> = line.split_whitespace().collect();
+            match values[..] {
+                [sort_code, substitute]
+                    if digits(sort_code, 6..=6).is_ok() && digits(substitute, 6..=6).is_ok() =>
+                {
+                    self.substitutes
+                        .insert(sort_code.to_string(), substitute.to_string());
+                }
+                _ => {
+                    return Err(Error::InvalidTable {
+                        line: index + 1,
+                        value: line.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Return whether the account passes the modulus check of a row of the weight table,
+    /// with the substitutions of its exception.
+    fn check_row(&self, row: &ModulusWeight, sort_code: &str, account: &str) -> bool {
+        let sort_code = match row.exception {
+            Some(5) => self
+                .substitutes
+                .get(sort_code)
+                .map_or(sort_code, |substitute| substitute.as_str()),
+            Some(8) => GB_EXCEPTION_8_SORT_CODE,
+            Some(9) => GB_EXCEPTION_9_SORT_CODE,
+            _ => sort_code,
+        };
+        let digits = modulus_digits(sort_code, account);
+        let (a, b, g, h) = (digits[6], digits[7], digits[12], digits[13]);
+
+        let mut weights = row.weights;
+        match row.exception {
+            Some(2) if a != 0 && g != 9 => weights = GB_EXCEPTION_2_WEIGHTS,
+            Some(2) if a != 0 => weights = GB_EXCEPTION_2_WEIGHTS_G9,
+            // Zeroise the weights of `u` to `b`
+            Some(7) if g == 9 => weights[..8].fill(0),
+            Some(10) if (a == 0 || a == 9) && b == 9 && g == 9 => weights[..8].fill(0),
+            _ => {}
+        }
+
+        let sum = modulus_sum(row.method, &digits, &weights);
+        match (row.method, row.exception) {
+            (ModulusMethod::DoubleAlternate, Some(1)) => (sum + 27).rem_euclid(10) == 0,
+            // The remainder is the check number `gh`
+            (ModulusMethod::Mod11, Some(4)) => sum.rem_euclid(11) == g * 10 + h,
+            // The remainder gives the check digit `g`, and `h` of the second check
+            (ModulusMethod::Mod11, Some(5)) => match sum.rem_euclid(11) {
+                0 => g == 0,
+                1 => false,
+                remainder => 11 - remainder == g,
+            },
+            (ModulusMethod::DoubleAlternate, Some(5)) => match sum.rem_euclid(10) {
+                0 => h == 0,
+                remainder => 10 - remainder == h,
+            },
+            // Remove `h` and shift the account number right for `h` 0, 1 or 9
+            (ModulusMethod::Mod11, Some(14)) if sum.rem_euclid(11) != 0 => {
+                let shifted = format!("0{}", &account[..GB_ACCOUNT_LENGTH - 1]);
+                matches!(h, 0 | 1 | 9)
+                    && modulus_sum(row.method, &modulus_digits(sort_code, &shifted), &weights)
+                        .rem_euclid(11)
+                        == 0
+            }
+            (ModulusMethod::Mod11, _) => sum.rem_euclid(11) == 0,
+            (ModulusMethod::Mod10 | ModulusMethod::DoubleAlternate, _) => sum.rem_euclid(10) == 0,
+        }
+    }
+}
+
+impl ClearingSystem for GbSortCode {
+    fn code(&self) -> &'static str {
+        "GBDSC"
+    }
+
+    fn validate_member_id(&self, mmb_id: &str) -> Result<(), IdentifierError> {
+        digits(mmb_id, 6..=6).map(|_| ())
+    }
+
+    fn validate_account(
+        &self,
+        mmb_id: &str,
+        account: &str,
+    ) -> Result<AccountCheck, IdentifierError> {
+        let sort_code = digits(mmb_id, 6..=6)?;
+        let account = digits(account, GB_ACCOUNT_MIN_LENGTH..=GB_ACCOUNT_LENGTH)?;
+        let account = format!("{:0>1$}", account, GB_ACCOUNT_LENGTH);
+
+        let rows: Vec<&ModulusWeight> = self
+            .weights
+            .iter()
+            .filter(|row| {
+                row.start.as_str() <= sort_code.as_str() && sort_code.as_str() <= row.end.as_str()
+            })
+            .take(2)
+            .collect();
+        if rows.iter().any(|row| {
+            row.exception
+                .is_some_and(|exception| !(1..=14).contains(&exception))
+        }) {
+            return Ok(AccountCheck::Unchecked);
+        }
+
+        let digits = modulus_digits(&sort_code, &account);
+        let (a, c, g, h) = (digits[6], digits[8], digits[12], digits[13]);
+        // Exception 6: foreign currency accounts cannot be checked
+        if rows.iter().any(|row| row.exception == Some(6)) && (4..=8).contains(&a) && g == h {
+            return Ok(AccountCheck::Unchecked);
+        }
+
+        let valid = match rows[..] {
+            [] => return Ok(AccountCheck::Unchecked),
+            [first] => self.check_row(first, &sort_code, &account),
+            [first, second, ..] => {
+                let valid = self.check_row(first, &sort_code, &account);
+                match (first.exception, second.exception) {
+                    // Exceptions 2 & 9, 10 & 11 and 12 & 13: either check passes
+                    (Some(2 | 10 | 12), _) => valid || self.check_row(second, &sort_code, &account),
+                    // Exception 3: no second check for `c` 6 or 9
+                    (_, Some(3)) if c == 6 || c == 9 => valid,
+                    _ => valid && self.check_row(second, &sort_code, &account),
+                }
+            }
+        };
+
+        check(valid).map(|_| AccountCheck::Valid)
+    }
+}
+
+/// German Bankleitzahl, i.e. 8 digits not starting with 0, and Kontonummern of up to 10
+/// digits validated by the check method `Prüfzifferberechnungsmethode` of the bank.
+///
+/// The check methods `00`, `01`, `02`, `03`, `04`, `06` and `10` are supported, and the
+/// accounts of the check method `09` have no check digit. The accounts of a bank missing
+/// from the table, or with another check method, cannot be checked.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DeBlz {
+    /// Check methods by Bankleitzahl, e.g. `00`
+    pub methods: HashMap<String, String>,
+}
+
+impl DeBlz {
+    /// Set the check method of a bank.
+    pub fn with_method(mut self, blz: &str, method: &str) -> Self {
+        self.methods.insert(blz.to_string(), method.to_string());
+        self
+    }
+
+    /// Add the check methods of the banks of the fixed width Bankleitzahlendatei, i.e. the
+    /// Bankleitzahl in columns 1 to 8 and the check method in columns 151 and 152.
+    pub fn load_banks(mut self, data: &str) -> Result<Self, Error> {
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let blz: String = line.chars().take(8).collect();
+            let method: String = line.chars().skip(150).take(2).collect();
+            if digits(&blz, 8..=8).is_err() || method.chars().count() != 2 {
+                return Err(Error::InvalidTable {
+                    line: index + 1,
+                    value: line.to_string(),
+                });
+            }
+
+            self.methods.insert(blz, method);
+        }
+
+        Ok(self)
+    }
+}
+
+impl ClearingSystem for DeBlz {
+    fn code(&self) -> &'static str {
+        "DEBLZ"
+    }
+
+    fn validate_member_id(&self, mmb_id: &str) -> Result<(), IdentifierError> {
+        let blz = digits(mmb_id, 8..=8)?;
+
+        if blz.starts_with('0') {
+            return Err(IdentifierError::InvalidFormat("leading zero"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_account(
+        &self,
+        mmb_id: &str,
+        account: &str,
+    ) -> Result<AccountCheck, IdentifierError> {
+        let account = digits(account, 1..=DE_ACCOUNT_LENGTH)?;
+        let account = format!("{:0>1$}", account, DE_ACCOUNT_LENGTH);
+        let (base, check_digit) = account.split_at(DE_ACCOUNT_LENGTH - 1);
+        let check_digit = check_digit.parse::<u32>().unwrap_or_default();
+
+        let expected = match self
+            .methods
+            .get(&electronic_format(mmb_id))
+            .map(|m| m.as_str())
+        {
+            Some("00") => Some(mod_10(
+                base.chars()
+                    .rev()
+                    .filter_map(|c| c.to_digit(10))
+                    .zip([2, 1].iter().cycle())
+                    .map(|(digit, weight)| {
+                        let product = digit * weight;
+                        product / 10 + product % 10
+                    })
+                    .sum(),
+            )),
+            Some("01") => Some(mod_10(weighted_sum(base, &[3, 7, 1]))),
+            Some("02") => mod_11(weighted_sum(base, &[2, 3, 4, 5, 6, 7, 8, 9]), None),
+            Some("03") => Some(mod_10(weighted_sum(base, &[2, 1]))),
+            Some("04") => mod_11(weighted_sum(base, &[2, 3, 4, 5, 6, 7]), None),
+            Some("06") => mod_11(weighted_sum(base, &[2, 3, 4, 5, 6, 7]), Some(0)),
+            Some("10") => mod_11(weighted_sum(base, &[2, 3, 4, 5, 6, 7, 8, 9, 10]), Some(0)),
+            // The account number has no check digit
+            Some("09") => return Ok(AccountCheck::NoCheckDigits),
+            _ => return Ok(AccountCheck::Unchecked),
+        };
+
+        check(expected == Some(check_digit)).map(|_| AccountCheck::Valid)
+    }
+}
+
+/// Canadian Payments Association routing number, i.e. `0`, the 3 digit institution number
+/// and the 5 digit transit number, and account numbers of 7 to 12 digits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CaCpa;
+
+impl ClearingSystem for CaCpa {
+    fn code(&self) -> &'static str {
+        "CACPA"
+    }
+
+    fn validate_member_id(&self, mmb_id: &str) -> Result<(), IdentifierError> {
+        let mmb_id = digits(mmb_id, 9..=9)?;
+
+        if !mmb_id.starts_with('0') {
+            return Err(IdentifierError::InvalidFormat("missing leading zero"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_account(
+        &self,
+        _mmb_id: &str,
+        account: &str,
+    ) -> Result<AccountCheck, IdentifierError> {
+        digits(account, 7..=12).map(|_| AccountCheck::NoCheckDigits)
+    }
+}
+
+/// Australian bank state branch number, i.e. 6 digits, and account numbers of 5 to 9
+/// digits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AuBsb;
+
+impl ClearingSystem for AuBsb {
+    fn code(&self) -> &'static str {
+        "AUBSB"
+    }
+
+    fn validate_member_id(&self, mmb_id: &str) -> Result<(), IdentifierError> {
+        // The BSB is usually written with a hyphen, e.g. `062-000`
+        digits(&mmb_id.replacen('-', "", 1), 6..=6).map(|_| ())
+    }
+
+    fn validate_account(
+        &self,
+        _mmb_id: &str,
+        account: &str,
+    ) -> Result<AccountCheck, IdentifierError> {
+        digits(account, 5..=9).map(|_| AccountCheck::NoCheckDigits)
+    }
+}
+
+/// Invalid member identification, or account number held with the member, of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidIdentifier {
+    /// Code of the clearing system, e.g. `USABA`
+    pub clr_sys_id: String,
+    /// Member identification, as in the document
+    pub mmb_id: String,
+    /// Account number, as in the document, of an invalid account
+    pub acct_id: Option<String>,
+    pub error: IdentifierError,
+}
+
+/// Validate the clearing system member identifications `ClrSysMmbId` of the document, and
+/// the account numbers `Othr/Id` of the debtor and creditor accounts, and of the accounts
+/// of statements and notifications, held with a member.
+///
+/// Identifications of a clearing system missing from the clearing systems are valid. The
+/// account numbers are only validated for a valid member identification, and the account
+/// numbers that could not be checked, see `AccountCheck`, are not reported.
+pub fn check_identifiers<D: Serialize>(
+    document: &D,
+    systems: &[&dyn ClearingSystem],
+) -> Result<Vec<InvalidIdentifier>, Error> {
+    let xml = quick_xml::se::to_string(document)?;
+    let package = parser::parse(&xml)?;
+    let doc = package.as_document();
+    let mut invalid = vec![];
+
+    let find_system = |clr_sys_id: &str| systems.iter().find(|system| system.code() == clr_sys_id);
+
+    for element in elements(&doc, MEMBER_X_PATH)? {
+        let (clr_sys_id, mmb_id) = match member(element) {
+            Some(member) => member,
+            None => continue,
+        };
+        if let Some(Err(error)) = find_system(&clr_sys_id).map(|s| s.validate_member_id(&mmb_id)) {
+            invalid.push(InvalidIdentifier {
+                clr_sys_id,
+                mmb_id,
+                acct_id: None,
+                error,
+            });
+        }
+    }
+
+    for element in elements(&doc, ACCOUNT_X_PATH)? {
+        let acct_id = match ["Id", "Othr", "Id"]
+            .iter()
+            .try_fold(element, |element, name| child(element, name))
+        {
+            Some(acct_id) => text(acct_id),
+            None => continue,
+        };
+        let agent = match element.name().local_part() {
+            "DbtrAcct" => sibling(element, "DbtrAgt"),
+            "CdtrAcct" => sibling(element, "CdtrAgt"),
+            _ => child(element, "Svcr"),
+        };
+        let (clr_sys_id, mmb_id) = match agent
+            .and_then(|agent| child(agent, "FinInstnId"))
+            .and_then(|fin_instn_id| child(fin_instn_id, "ClrSysMmbId"))
+            .and_then(member)
+        {
+            Some(member) => member,
+            None => continue,
+        };
+        let system = match find_system(&clr_sys_id) {
+            Some(system) if system.validate_member_id(&mmb_id).is_ok() => system,
+            _ => continue,
+        };
+
+        if let Err(error) = system.validate_account(&mmb_id, &acct_id) {
+            invalid.push(InvalidIdentifier {
+                clr_sys_id,
+                mmb_id,
+                acct_id: Some(acct_id),
+                error,
+            });
+        }
+    }
+
+    Ok(invalid)
+}
+
+fn elements<'d>(doc: &'d dom::Document<'d>, x_path: &str) -> Result<Vec<dom::Element<'d>>, Error> {
+    Ok(match evaluate_xpath(doc, x_path)? {
+        sxd_xpath::Value::Nodeset(nodes) => nodes
+            .document_order()
+            .into_iter()
+            .filter_map(|node| node.element())
+            .collect(),
+        _ => vec![],
+    })
+}
+
+/// Return the clearing system code `ClrSysId/Cd` and the member identification `MmbId` of a
+/// `ClrSysMmbId` element.
+fn member(element: dom::Element) -> Option<(String, String)> {
+    let clr_sys_id = child(element, "ClrSysId").and_then(|clr_sys_id| child(clr_sys_id, "Cd"))?;
+    let mmb_id = child(element, "MmbId")?;
+
+    Some((text(clr_sys_id), text(mmb_id)))
+}
+
+fn child<'d>(element: dom::Element<'d>, name: &str) -> Option<dom::Element<'d>> {
+    element
+        .children()
+        .into_iter()
+        .filter_map(|child| child.element())
+        .find(|child| child.name().local_part() == name)
+}
+
+fn sibling<'d>(element: dom::Element<'d>, name: &str) -> Option<dom::Element<'d>> {
+    match element.parent() {
+        Some(ParentOfChild::Element(parent)) => child(parent, name),
+        _ => None,
+    }
+}
+
+fn text(element: dom::Element) -> String {
+    element
+        .children()
+        .into_iter()
+        .filter_map(|child| match child {
+            ChildOfElement::Text(text) => Some(text.text().to_string()),
+            _ => None,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Return the identifier without spaces, in upper case.
+fn electronic_format(identifier: &str) -> String {
+    identifier
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// Return the digits of the identifier, without spaces, with a length in the range.
+fn digits(
+    identifier: &str,
+    length: std::ops::RangeInclusive<usize>,
+) -> Result<String, IdentifierError> {
+    let identifier = electronic_format(identifier);
+
+    if let Some(c) = identifier.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(IdentifierError::InvalidCharacter(c));
+    }
+    if !length.contains(&identifier.len()) {
+        return Err(IdentifierError::InvalidLength(identifier.len()));
+    }
+
+    Ok(identifier)
+}
+
+/// Return the 14 digits of the sort code `uvwxyz` and the account number `abcdefgh` of a
+/// Vocalink modulus check.
+fn modulus_digits(sort_code: &str, account: &str) -> [i32; 14] {
+    let mut digits = [0; 14];
+    for (digit, c) in digits
+        .iter_mut()
+        .zip(sort_code.chars().chain(account.chars()))
+    {
+        *digit = c.to_digit(10).unwrap_or_default() as i32;
+    }
+
+    digits
+}
+
+/// Return the weighted sum of the digits of a Vocalink modulus check, i.e. the sum of the
+/// digits of the weighted products for the double alternate method.
+fn modulus_sum(method: ModulusMethod, digits: &[i32; 14], weights: &[i32; 14]) -> i32 {
+    let products = digits
+        .iter()
+        .zip(weights.iter())
+        .map(|(digit, weight)| digit * weight);
+
+    match method {
+        ModulusMethod::DoubleAlternate => products.map(|product| product / 10 + product % 10).sum(),
+        ModulusMethod::Mod10 | ModulusMethod::Mod11 => products.sum(),
+    }
+}
+
+/// Return the sum of the digits, weighted from the right with the repeated weights.
+fn weighted_sum(digits: &str, weights: &[u32]) -> u32 {
+    digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .zip(weights.iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum()
+}
+
+/// Return the modulus 10 check digit of the sum.
+fn mod_10(sum: u32) -> u32 {
+    (10 - sum % 10) % 10
+}
+
+/// Return the modulus 11 check digit of the sum, or the check digit of a remainder of 1,
+/// i.e. none if such a base is not a valid account number.
+fn mod_11(sum: u32, remainder_1: Option<u32>) -> Option<u32> {
+    match sum % 11 {
+        0 => Some(0),
+        1 => remainder_1,
+        remainder => Some(11 - remainder),
+    }
+}
+
+fn check(valid: bool) -> Result<(), IdentifierError> {
+    if valid {
+        Ok(())
+    } else {
+        Err(IdentifierError::InvalidCheckDigits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALACDOS: &str = "\
+089999 089999 MOD10    0    0    0    0    0    0    7    1    3    7    1    3    7    1
+107999 107999 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1
+202959 202959 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    1
+";
+
+    const VALACDOS_EXCEPTIONS: &str = "\
+110000 119280 DBLAL    0    0    2    1    2    1    2    1    2    1    2    1    2    1   1
+180002 180002 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1  14
+200915 200915 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1   6
+200915 200915 DBLAL    0    0    0    0    0    0    2    1    2    1    2    1    2    1   6
+309070 309070 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1   2
+309070 309070 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1   9
+820000 820000 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1
+820000 820000 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    1   3
+938000 938696 MOD11    7    6    5    4    3    2    7    6    5    4    3    2    0    0   5
+938000 938696 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    0   5
+999999 999999 MOD11   -1    0    0    0    0    0    8    7    6    5    4    3    2    1  15
+";
+
+    #[derive(Debug, Serialize)]
+    #[serde(rename = "Document")]
+    struct TestDocument {
+        #[serde(rename = "CdtTrfTxInf")]
+        cdt_trf_tx_inf: Vec<TestTransaction>,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestTransaction {
+        #[serde(rename = "CdtrAgt")]
+        cdtr_agt: TestAgent,
+        #[serde(rename = "CdtrAcct")]
+        cdtr_acct: TestAccount,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestAgent {
+        #[serde(rename = "FinInstnId")]
+        fin_instn_id: TestFinancialInstitution,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestFinancialInstitution {
+        #[serde(rename = "ClrSysMmbId")]
+        clr_sys_mmb_id: TestMember,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestMember {
+        #[serde(rename = "ClrSysId")]
+        clr_sys_id: TestCode,
+        #[serde(rename = "MmbId")]
+        mmb_id: String,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestCode {
+        #[serde(rename = "Cd")]
+        cd: String,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestAccount {
+        #[serde(rename = "Id")]
+        id: TestAccountIdentification,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestAccountIdentification {
+        #[serde(rename = "Othr")]
+        othr: TestOther,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestOther {
+        #[serde(rename = "Id")]
+        id: String,
+    }
+
+    fn transaction(cd: &str, mmb_id: &str, acct_id: &str) -> TestTransaction {
+        TestTransaction {
+            cdtr_agt: TestAgent {
+                fin_instn_id: TestFinancialInstitution {
+                    clr_sys_mmb_id: TestMember {
+                        clr_sys_id: TestCode { cd: cd.to_string() },
+                        mmb_id: mmb_id.to_string(),
+                    },
+                },
+            },
+            cdtr_acct: TestAccount {
+                id: TestAccountIdentification {
+                    othr: TestOther {
+                        id: acct_id.to_string(),
+                    },
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_us_aba() {
+        assert_eq!(UsAba.validate_member_id("021000021"), Ok(()));
+        assert_eq!(UsAba.validate_member_id("011000015"), Ok(()));
+        assert_eq!(
+            UsAba.validate_member_id("021000022"),
+            Err(IdentifierError::InvalidCheckDigits)
+        );
+        assert_eq!(
+            UsAba.validate_member_id("02100002"),
+            Err(IdentifierError::InvalidLength(8))
+        );
+        assert_eq!(
+            UsAba.validate_member_id("02100002A"),
+            Err(IdentifierError::InvalidCharacter('A'))
+        );
+    }
+
+    #[test]
+    fn test_gb_sort_code() -> Result<(), Error> {
+        let gb = GbSortCode::default().load_weights(VALACDOS)?;
+        assert_eq!(gb.weights.len(), 3);
+
+        assert_eq!(
+            gb.validate_account("089999", "66374958"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            gb.validate_account("107999", "88837491"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            gb.validate_account("202959", "63748472"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            gb.validate_account("089999", "66374959"),
+            Err(IdentifierError::InvalidCheckDigits)
+        );
+        // Sort codes missing from the weight table cannot be checked
+        assert_eq!(
+            gb.validate_account("400000", "12345678"),
+            Ok(AccountCheck::Unchecked)
+        );
+        // Account numbers of 7 digits are padded with a leading zero
+        assert_eq!(
+            gb.validate_account("089999", "4637916"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            gb.validate_account("089999", "04637916"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            gb.validate_account("089999", "66374"),
+            Err(IdentifierError::InvalidLength(5))
+        );
+
+        assert!(matches!(
+            GbSortCode::default().load_weights("089999 089999 MOD12 0"),
+            Err(Error::InvalidTable { line: 1, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gb_sort_code_exceptions() -> Result<(), Error> {
+        let gb = GbSortCode::default()
+            .load_weights(VALACDOS_EXCEPTIONS)?
+            .load_substitutes("938611 938063\n")?;
+        assert_eq!(gb.weights[10].weights[0], -1);
+
+        for (sort_code, account, result) in [
+            // Exception 1
+            ("118765", "64371389", Ok(AccountCheck::Valid)),
+            (
+                "118765",
+                "64371388",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            // Exception 14, with the account number shifted right for `h` 0, 1 or 9
+            ("180002", "00000190", Ok(AccountCheck::Valid)),
+            ("180002", "00000191", Ok(AccountCheck::Valid)),
+            (
+                "180002",
+                "00000192",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            // Exception 6, foreign currency accounts
+            ("200915", "41011166", Ok(AccountCheck::Unchecked)),
+            // Exception 2 & 9, with the substituted weights, or the second check
+            ("309070", "12345677", Ok(AccountCheck::Valid)),
+            ("309070", "99345694", Ok(AccountCheck::Valid)),
+            ("309070", "76496171", Ok(AccountCheck::Valid)),
+            (
+                "309070",
+                "12345678",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            // Exception 3, without the second check for `c` 6 or 9
+            ("820000", "14686740", Ok(AccountCheck::Valid)),
+            (
+                "820000",
+                "29322146",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            // Exception 5, with the check digits `g` and `h`
+            ("938063", "55065200", Ok(AccountCheck::Valid)),
+            (
+                "938063",
+                "15764273",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            (
+                "938063",
+                "15764264",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            (
+                "938063",
+                "15764234",
+                Err(IdentifierError::InvalidCheckDigits),
+            ),
+            ("938611", "55065200", Ok(AccountCheck::Valid)),
+            // Unknown exceptions cannot be checked
+            ("999999", "12345678", Ok(AccountCheck::Unchecked)),
+        ] {
+            assert_eq!(
+                gb.validate_account(sort_code, account),
+                result,
+                "{} {}",
+                sort_code,
+                account
+            );
+        }
+
+        assert!(matches!(
+            GbSortCode::default().load_substitutes("938611"),
+            Err(Error::InvalidTable { line: 1, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_de_blz() -> Result<(), Error> {
+        let line = format!("{:<150}{}{:<16}", "37040044", "06", "");
+        let de = DeBlz::default()
+            .load_banks(&line)?
+            .with_method("10010010", "00")
+            .with_method("10020030", "09")
+            .with_method("10020040", "E4");
+        assert_eq!(de.methods.get("37040044").map(|m| m.as_str()), Some("06"));
+
+        assert_eq!(de.validate_member_id("37040044"), Ok(()));
+        assert_eq!(
+            de.validate_member_id("07040044"),
+            Err(IdentifierError::InvalidFormat("leading zero"))
+        );
+
+        for account in ["9290701", "539290858", "1501824", "1501832"] {
+            assert_eq!(
+                de.validate_account("10010010", account),
+                Ok(AccountCheck::Valid),
+                "{}",
+                account
+            );
+        }
+        assert_eq!(
+            de.validate_account("10010010", "9290702"),
+            Err(IdentifierError::InvalidCheckDigits)
+        );
+        assert_eq!(
+            de.validate_account("37040044", "94012341"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            de.validate_account("37040044", "5073321010"),
+            Ok(AccountCheck::Valid)
+        );
+        assert_eq!(
+            de.validate_account("10020030", "1234567890"),
+            Ok(AccountCheck::NoCheckDigits)
+        );
+        // Banks missing from the table, or with an unsupported method, cannot be checked
+        assert_eq!(
+            de.validate_account("20020020", "1234567890"),
+            Ok(AccountCheck::Unchecked)
+        );
+        assert_eq!(
+            de.validate_account("10020040", "1234567890"),
+            Ok(AccountCheck::Unchecked)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ca_cpa_au_bsb() {
+        assert_eq!(CaCpa.validate_member_id("000412345"), Ok(()));
+        assert_eq!(
+            CaCpa.validate_member_id("100412345"),
+            Err(IdentifierError::InvalidFormat("missing leading zero"))
+        );
+        assert_eq!(
+            CaCpa.validate_account("000412345", "1234567"),
+            Ok(AccountCheck::NoCheckDigits)
+        );
+
+        assert_eq!(AuBsb.validate_member_id("062-000"), Ok(()));
+        assert_eq!(AuBsb.validate_member_id("062000"), Ok(()));
+        assert_eq!(
+            AuBsb.validate_member_id("0620001"),
+            Err(IdentifierError::InvalidLength(7))
+        );
+        assert_eq!(
+            AuBsb.validate_account("062000", "1234"),
+            Err(IdentifierError::InvalidLength(4))
+        );
+    }
+
+    #[test]
+    fn test_check_identifiers() -> Result<(), Error> {
+        let gb = GbSortCode::default().load_weights(VALACDOS)?;
+        let doc = TestDocument {
+            cdt_trf_tx_inf: vec![
+                transaction("USABA", "021000021", "123456789"),
+                transaction("USABA", "021000022", "123456789"),
+                transaction("GBDSC", "089999", "66374958"),
+                transaction("GBDSC", "089999", "66374959"),
+                transaction("CHBCC", "00230", "X"),
+            ],
+        };
+
+        assert_eq!(
+            check_identifiers(&doc, &[&UsAba, &gb])?,
+            vec![
+                InvalidIdentifier {
+                    clr_sys_id: "USABA".to_string(),
+                    mmb_id: "021000022".to_string(),
+                    acct_id: None,
+                    error: IdentifierError::InvalidCheckDigits,
+                },
+                InvalidIdentifier {
+                    clr_sys_id: "GBDSC".to_string(),
+                    mmb_id: "089999".to_string(),
+                    acct_id: Some("66374959".to_string()),
+                    error: IdentifierError::InvalidCheckDigits,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+}
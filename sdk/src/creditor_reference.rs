@@ -0,0 +1,585 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Creditor References
+//!
+//! Structured creditor references, i.e. the `CdtrRefInf/Ref` of the structured remittance
+//! information, with the type code `SCOR`, are ISO 11649 `RF` creditor references, or
+//! references of a national scheme agreed with the creditor. Each scheme implements the
+//! `ReferenceScheme` trait, to generate the check digits of a reference, e.g. from an
+//! invoice number, and to validate existing references:
+//!
+//! - `Iso11649`, the `RF` creditor reference, see also `RfCreditorReference`
+//! - `FinnishReference`, the Finnish national reference (viitenumero)
+//! - `NorwegianKid`, the Norwegian KID, with modulus 10 or 11 check digits
+//! - `SwissQrReference`, the Swiss QR reference
+//!
+//! ```rust
+//! use iso_20022_sdk::creditor_reference::{check_creditor_references, RfCreditorReference};
+//!
+//! let reference = RfCreditorReference::generate("INV2023001")?;
+//! assert_eq!(reference.as_str(), "RF15INV2023001");
+//!
+//! // Validate the SCOR references of a document, allowing RF references only
+//! let invalid = check_creditor_references(&doc, &[])?;
+//! assert!(invalid.is_empty());
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+use sxd_document::parser;
+use sxd_xpath::evaluate_xpath;
+
+/// Prefix of the ISO 11649 creditor reference.
+const RF_PREFIX: &str = "RF";
+
+/// Maximum length of the ISO 11649 creditor reference.
+const RF_MAX_LENGTH: usize = 25;
+
+/// XPath to the references of the structured creditor references with the type code `SCOR`.
+const SCOR_REFERENCE_X_PATH: &str = "//*[local-name()='CdtrRefInf'][*[local-name()='Tp']/*[local-name()='CdOrPrtry']/*[local-name()='Cd']='SCOR']/*[local-name()='Ref']";
+
+/// Table of the recursive modulus 10 check digit of the Swiss QR reference.
+const MOD_10_RECURSIVE: [u32; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// SXD Document Error
+    #[error(transparent)]
+    XsdDocument(#[from] sxd_document::parser::Error),
+    /// SXD XPath Error
+    #[error(transparent)]
+    XsdXPath(#[from] sxd_xpath::Error),
+}
+
+/// Error of a creditor reference, or of the base of a reference to generate.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReferenceError {
+    /// The reference contains a character not allowed by the scheme
+    #[error("invalid character {0:?}")]
+    InvalidCharacter(char),
+    /// The length of the reference is not allowed by the scheme
+    #[error("invalid length {0}")]
+    InvalidLength(usize),
+    /// The reference does not start with the prefix of the scheme, e.g. `RF`
+    #[error("missing prefix {0}")]
+    MissingPrefix(&'static str),
+    /// The check digits of the reference are not valid
+    #[error("invalid check digits")]
+    InvalidCheckDigits,
+}
+
+/// Creditor reference scheme.
+pub trait ReferenceScheme {
+    /// Return the name of the scheme.
+    fn name(&self) -> &'static str;
+
+    /// Return the reference, with its check digits, of the base, e.g. an invoice number.
+    fn generate(&self, base: &str) -> Result<String, ReferenceError>;
+
+    /// Validate the characters, length and check digits of the reference.
+    fn validate(&self, reference: &str) -> Result<(), ReferenceError>;
+}
+
+/// ISO 11649 `RF` creditor reference scheme, i.e. `RF`, two modulus 97 check digits,
+/// and up to 21 alphanumeric characters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Iso11649;
+
+impl ReferenceScheme for Iso11649 {
+    fn name(&self) -> &'static str {
+        "ISO 11649"
+    }
+
+    fn generate(&self, base: &str) -> Result<String, ReferenceError> {
+        let base = electronic_format(base);
+
+        if base.is_empty() || base.len() > RF_MAX_LENGTH - 4 {
+            return Err(ReferenceError::InvalidLength(base.len()));
+        }
+
+        let check_digits = 98 - mod_97(&format!("{base}{RF_PREFIX}00"))?;
+
+        Ok(format!("{RF_PREFIX}{check_digits:02}{base}"))
+    }
+
+    fn validate(&self, reference: &str) -> Result<(), ReferenceError> {
+        let reference = electronic_format(reference);
+
+        if !reference.starts_with(RF_PREFIX) {
+            return Err(ReferenceError::MissingPrefix(RF_PREFIX));
+        }
+        if let Some(c) = reference.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(ReferenceError::InvalidCharacter(c));
+        }
+        if reference.len() < 5 || reference.len() > RF_MAX_LENGTH {
+            return Err(ReferenceError::InvalidLength(reference.len()));
+        }
+        if let Some(c) = reference[2..4].chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ReferenceError::InvalidCharacter(c));
+        }
+
+        match mod_97(&format!("{}{}", &reference[4..], &reference[..4]))? {
+            1 => Ok(()),
+            _ => Err(ReferenceError::InvalidCheckDigits),
+        }
+    }
+}
+
+/// Finnish national reference scheme, i.e. 3 to 19 digits and a check digit, weighted
+/// 7, 3, 1 from the right.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FinnishReference;
+
+impl ReferenceScheme for FinnishReference {
+    fn name(&self) -> &'static str {
+        "Finnish reference"
+    }
+
+    fn generate(&self, base: &str) -> Result<String, ReferenceError> {
+        let base = digits(base, 3..=19)?;
+        let sum = weighted_sum(&base, &[7, 3, 1]);
+
+        Ok(format!("{base}{}", (10 - sum % 10) % 10))
+    }
+
+    fn validate(&self, reference: &str) -> Result<(), ReferenceError> {
+        let reference = digits(reference, 4..=20)?;
+
+        check(
+            self.generate(&reference[..reference.len() - 1])?,
+            &reference,
+        )
+    }
+}
+
+/// Norwegian KID (kundeidentifikasjon) scheme, i.e. up to 24 digits and a check digit, with
+/// the modulus agreed with the creditor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NorwegianKid {
+    /// Modulus 10 (Luhn) check digit
+    Mod10,
+    /// Modulus 11 check digit, weighted 2 to 7 from the right, where `-` is the check digit 10
+    Mod11,
+}
+
+impl ReferenceScheme for NorwegianKid {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Mod10 => "KID (MOD10)",
+            Self::Mod11 => "KID (MOD11)",
+        }
+    }
+
+    fn generate(&self, base: &str) -> Result<String, ReferenceError> {
+        let base = digits(base, 1..=24)?;
+
+        let check_digit = match self {
+            Self::Mod10 => {
+                let sum = base
+                    .chars()
+                    .rev()
+                    .filter_map(|c| c.to_digit(10))
+                    .enumerate()
+                    .map(|(index, digit)| match index % 2 {
+                        0 if digit > 4 => digit * 2 - 9,
+                        0 => digit * 2,
+                        _ => digit,
+                    })
+                    .sum::<u32>();
+
+                char::from(b'0' + ((10 - sum % 10) % 10) as u8)
+            }
+            Self::Mod11 => match 11 - weighted_sum(&base, &[2, 3, 4, 5, 6, 7]) % 11 {
+                11 => '0',
+                10 => '-',
+                check_digit => char::from(b'0' + check_digit as u8),
+            },
+        };
+
+        Ok(format!("{base}{check_digit}"))
+    }
+
+    fn validate(&self, reference: &str) -> Result<(), ReferenceError> {
+        let reference = electronic_format(reference);
+
+        if let Some(c) = reference.chars().find(|c| !c.is_ascii_digit() && *c != '-') {
+            return Err(ReferenceError::InvalidCharacter(c));
+        }
+        if reference.len() < 2 || reference.len() > 25 {
+            return Err(ReferenceError::InvalidLength(reference.len()));
+        }
+
+        check(
+            self.generate(&reference[..reference.len() - 1])?,
+            &reference,
+        )
+    }
+}
+
+/// Swiss QR reference scheme, i.e. 26 digits and a recursive modulus 10 check digit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SwissQrReference;
+
+impl ReferenceScheme for SwissQrReference {
+    fn name(&self) -> &'static str {
+        "QR reference"
+    }
+
+    /// The base is padded with leading zeros to 26 digits.
+    fn generate(&self, base: &str) -> Result<String, ReferenceError> {
+        let base = format!("{:0>26}", digits(base, 1..=26)?);
+        let carry = base
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .fold(0, |carry, digit| {
+                MOD_10_RECURSIVE[((carry + digit) % 10) as usize]
+            });
+
+        Ok(format!("{base}{}", (10 - carry) % 10))
+    }
+
+    fn validate(&self, reference: &str) -> Result<(), ReferenceError> {
+        let reference = digits(reference, 27..=27)?;
+
+        check(self.generate(&reference[..26])?, &reference)
+    }
+}
+
+/// ISO 11649 `RF` creditor reference, in electronic format, i.e. upper case without spaces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RfCreditorReference(String);
+
+impl RfCreditorReference {
+    /// Return the creditor reference of the base, e.g. an invoice number.
+    pub fn generate(base: &str) -> Result<Self, ReferenceError> {
+        Iso11649.generate(base).map(Self)
+    }
+
+    /// Return the creditor reference in electronic format, e.g. `RF18539007547034`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Return the base of the creditor reference, without the prefix and check digits.
+    pub fn base(&self) -> &str {
+        &self.0[4..]
+    }
+
+    /// Return the creditor reference in print format, in groups of four characters,
+    /// e.g. `RF18 5390 0754 7034`.
+    pub fn print_format(&self) -> String {
+        self.0
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl FromStr for RfCreditorReference {
+    type Err = ReferenceError;
+
+    fn from_str(reference: &str) -> Result<Self, Self::Err> {
+        Iso11649.validate(reference)?;
+
+        Ok(Self(electronic_format(reference)))
+    }
+}
+
+impl fmt::Display for RfCreditorReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Invalid structured creditor reference of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCreditorReference {
+    /// Reference, as in the document
+    pub reference: String,
+    /// Error of the `RF` creditor reference, or of the first national scheme
+    pub error: ReferenceError,
+}
+
+/// Validate the structured creditor references with the type code `SCOR` of the document.
+///
+/// A reference starting with `RF` is validated as an ISO 11649 creditor reference. Any other
+/// reference is valid if one of the national schemes, agreed with the creditor, validates it.
+pub fn check_creditor_references<D: Serialize>(
+    document: &D,
+    schemes: &[&dyn ReferenceScheme],
+) -> Result<Vec<InvalidCreditorReference>, Error> {
+    let xml = quick_xml::se::to_string(document)?;
+    let package = parser::parse(&xml)?;
+
+    let references: Vec<String> =
+        match evaluate_xpath(&package.as_document(), SCOR_REFERENCE_X_PATH)? {
+            sxd_xpath::Value::Nodeset(nodes) => nodes
+                .document_order()
+                .iter()
+                .map(|node| node.string_value().trim().to_string())
+                .collect(),
+            _ => vec![],
+        };
+
+    Ok(references
+        .into_iter()
+        .filter_map(|reference| {
+            validate_reference(&reference, schemes)
+                .err()
+                .map(|error| InvalidCreditorReference { reference, error })
+        })
+        .collect())
+}
+
+/// Validate a reference as an `RF` creditor reference, or with one of the national schemes.
+pub fn validate_reference(
+    reference: &str,
+    schemes: &[&dyn ReferenceScheme],
+) -> Result<(), ReferenceError> {
+    if electronic_format(reference).starts_with(RF_PREFIX) || schemes.is_empty() {
+        return Iso11649.validate(reference);
+    }
+
+    if schemes
+        .iter()
+        .any(|scheme| scheme.validate(reference).is_ok())
+    {
+        Ok(())
+    } else {
+        schemes[0].validate(reference)
+    }
+}
+
+/// Return the reference without spaces, in upper case.
+fn electronic_format(reference: &str) -> String {
+    reference
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// Return the digits of the reference, without spaces, with a length in the range.
+fn digits(
+    reference: &str,
+    length: std::ops::RangeInclusive<usize>,
+) -> Result<String, ReferenceError> {
+    let reference = electronic_format(reference);
+
+    if let Some(c) = reference.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(ReferenceError::InvalidCharacter(c));
+    }
+    if !length.contains(&reference.len()) {
+        return Err(ReferenceError::InvalidLength(reference.len()));
+    }
+
+    Ok(reference)
+}
+
+/// Return the sum of the digits, weighted from the right with the repeated weights.
+fn weighted_sum(digits: &str, weights: &[u32]) -> u32 {
+    digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .zip(weights.iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum()
+}
+
+/// Compare the generated reference with the reference.
+fn check(generated: String, reference: &str) -> Result<(), ReferenceError> {
+    if generated == reference {
+        Ok(())
+    } else {
+        Err(ReferenceError::InvalidCheckDigits)
+    }
+}
+
+/// Return the modulus 97 of the alphanumeric value, where letters are 10 to 35.
+fn mod_97(value: &str) -> Result<u32, ReferenceError> {
+    value.chars().try_fold(0, |remainder, c| {
+        let digit = c.to_digit(36).ok_or(ReferenceError::InvalidCharacter(c))?;
+
+        Ok(match digit {
+            0..=9 => (remainder * 10 + digit) % 97,
+            _ => (remainder * 100 + digit) % 97,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize)]
+    #[serde(rename = "Document")]
+    struct TestDocument {
+        #[serde(rename = "Strd")]
+        strd: Vec<TestStructured>,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestStructured {
+        #[serde(rename = "CdtrRefInf")]
+        cdtr_ref_inf: TestCreditorReference,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestCreditorReference {
+        #[serde(rename = "Tp")]
+        tp: TestType,
+        #[serde(rename = "Ref")]
+        r#ref: String,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestType {
+        #[serde(rename = "CdOrPrtry")]
+        cd_or_prtry: TestCode,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct TestCode {
+        #[serde(rename = "Cd")]
+        cd: String,
+    }
+
+    fn structured(cd: &str, r#ref: &str) -> TestStructured {
+        TestStructured {
+            cdtr_ref_inf: TestCreditorReference {
+                tp: TestType {
+                    cd_or_prtry: TestCode { cd: cd.to_string() },
+                },
+                r#ref: r#ref.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_rf_creditor_reference() {
+        let reference = RfCreditorReference::generate("5390 0754 7034").unwrap();
+        assert_eq!(reference.as_str(), "RF18539007547034");
+        assert_eq!(reference.base(), "539007547034");
+        assert_eq!(reference.print_format(), "RF18 5390 0754 7034");
+
+        assert_eq!(
+            RfCreditorReference::generate("inv2023001")
+                .unwrap()
+                .as_str(),
+            "RF15INV2023001"
+        );
+        assert_eq!(
+            "rf18 5390 0754 7034".parse::<RfCreditorReference>(),
+            Ok(reference)
+        );
+
+        assert_eq!(
+            "RF19539007547034".parse::<RfCreditorReference>(),
+            Err(ReferenceError::InvalidCheckDigits)
+        );
+        assert_eq!(
+            "539007547034".parse::<RfCreditorReference>(),
+            Err(ReferenceError::MissingPrefix("RF"))
+        );
+        assert_eq!(
+            "RF18-5390".parse::<RfCreditorReference>(),
+            Err(ReferenceError::InvalidCharacter('-'))
+        );
+        assert_eq!(
+            RfCreditorReference::generate("1234567890123456789012"),
+            Err(ReferenceError::InvalidLength(22))
+        );
+    }
+
+    #[test]
+    fn test_national_schemes() {
+        assert_eq!(FinnishReference.generate("123").unwrap(), "1232");
+        assert_eq!(FinnishReference.generate("1234561").unwrap(), "12345614");
+        assert!(FinnishReference.validate("1234 5614").is_ok());
+        assert_eq!(
+            FinnishReference.validate("12345615"),
+            Err(ReferenceError::InvalidCheckDigits)
+        );
+
+        assert_eq!(
+            NorwegianKid::Mod10.generate("123456789").unwrap(),
+            "1234567897"
+        );
+        assert_eq!(
+            NorwegianKid::Mod11.generate("123456789").unwrap(),
+            "1234567892"
+        );
+        assert!(NorwegianKid::Mod11.validate("1234567892").is_ok());
+        assert_eq!(
+            NorwegianKid::Mod10.validate("1234567892"),
+            Err(ReferenceError::InvalidCheckDigits)
+        );
+
+        assert_eq!(
+            SwissQrReference
+                .generate("21000000000313947143000901")
+                .unwrap(),
+            "210000000003139471430009017"
+        );
+        assert!(SwissQrReference
+            .validate("21 00000 00003 13947 14300 09017")
+            .is_ok());
+        assert_eq!(
+            SwissQrReference.validate("21000000000313947143000901"),
+            Err(ReferenceError::InvalidLength(26))
+        );
+    }
+
+    #[test]
+    fn test_check_creditor_references() {
+        let document = TestDocument {
+            strd: vec![
+                structured("SCOR", "RF18539007547034"),
+                structured("SCOR", "RF18539007547035"),
+                structured("SCOR", "1234567897"),
+                structured("RPIN", "not checked"),
+            ],
+        };
+
+        let invalid = check_creditor_references(&document, &[]).unwrap();
+        assert_eq!(
+            invalid,
+            vec![
+                InvalidCreditorReference {
+                    reference: "RF18539007547035".to_string(),
+                    error: ReferenceError::InvalidCheckDigits,
+                },
+                InvalidCreditorReference {
+                    reference: "1234567897".to_string(),
+                    error: ReferenceError::MissingPrefix("RF"),
+                },
+            ]
+        );
+
+        let invalid =
+            check_creditor_references(&document, &[&FinnishReference, &NorwegianKid::Mod10])
+                .unwrap();
+        assert_eq!(invalid.len(), 1);
+    }
+}
@@ -20,6 +20,10 @@ use iso_20022_dsig::{dsig::DigestValueType, xpath::XPath};
 #[cfg(feature = "ecdsa")]
 pub mod ecdsa;
 
+/// XML Encryption of message payloads.
+#[cfg(feature = "xmlenc")]
+pub mod xmlenc;
+
 /// Default canonicalization method algorithm.
 const DEFAULT_CANONICALIZATION_METHOD_ALGORITHM: &str = "http://www.w3.org/2001/10/xml-exc-c14n#";
 
@@ -0,0 +1,850 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # XML Encryption
+//!
+//! W3C XML Encryption 1.1 of message payloads.
+//!
+//! The content is encrypted with a random AES-256-GCM content encryption key,
+//! which is then transported to the recipient either with RSA-OAEP (SHA-256) or
+//! with an ephemeral-static ECDH key agreement on P-256 followed by an AES-256
+//! key wrap. The result is an `xenc:EncryptedData` element carrying the
+//! `xenc:EncryptedKey` in its `ds:KeyInfo`.
+//!
+//! ```rust
+//! use iso_20022_sdk::crypto::xmlenc::{self, DecryptionKey, Recipient};
+//!
+//! let recipient = Recipient::from_certificate_pem(&certificate)?;
+//!
+//! // Encrypt the `Document` element of an XML string
+//! let xml = xmlenc::encrypt_xml(&xml, "/Document", &recipient)?;
+//!
+//! // Replace every `EncryptedData` element with its plaintext
+//! let xml = xmlenc::decrypt_xml(&xml, &DecryptionKey::Rsa(private_key))?;
+//! ```
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use const_oid::db::rfc5912::{ID_EC_PUBLIC_KEY, RSA_ENCRYPTION};
+use elliptic_curve::sec1::ToEncodedPoint;
+use iso_20022_dsig::xenc::{
+    self, AgreementMethod, CipherData, CipherValue, ConcatKdfParams, DigestMethod, EcKeyValue,
+    EncryptedData, EncryptedKey, EncryptionMethod, KeyDerivationMethod, KeyInfo, KeyValue, Mgf,
+    NamedCurve, OriginatorKeyInfo, RecipientKeyInfo, X509Data,
+};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use sxd_document::dom::{self, ChildOfElement, ChildOfRoot, ParentOfChild};
+use sxd_document::{parser, writer, QName};
+use sxd_xpath::{evaluate_xpath, Value};
+use x509_cert::der::{Decode, DecodePem, Encode};
+
+/// AES-256-GCM block encryption algorithm.
+pub const AES_256_GCM: &str = "http://www.w3.org/2009/xmlenc11#aes256-gcm";
+
+/// RSA-OAEP key transport algorithm.
+pub const RSA_OAEP: &str = "http://www.w3.org/2009/xmlenc11#rsa-oaep";
+
+/// MGF1 with SHA-256 mask generation function for RSA-OAEP.
+pub const MGF1_SHA256: &str = "http://www.w3.org/2009/xmlenc11#mgf1sha256";
+
+/// Ephemeral-static Elliptic Curve Diffie-Hellman key agreement.
+pub const ECDH_ES: &str = "http://www.w3.org/2009/xmlenc11#ECDH-ES";
+
+/// Concatenation key derivation function.
+pub const CONCAT_KDF: &str = "http://www.w3.org/2009/xmlenc11#ConcatKDF";
+
+/// AES-256 key wrap algorithm.
+pub const KW_AES_256: &str = "http://www.w3.org/2001/04/xmlenc#kw-aes256";
+
+/// SHA-256 digest algorithm.
+pub const SHA_256: &str = "http://www.w3.org/2001/04/xmlenc#sha256";
+
+/// Named curve URI of NIST P-256.
+const P256_NAMED_CURVE: &str = "urn:oid:1.2.840.10045.3.1.7";
+
+/// Length of the AES-GCM initialization vector in bytes.
+const GCM_IV_LENGTH: usize = 12;
+
+/// Length of the AES-256 content encryption key in bytes.
+const CEK_LENGTH: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// SXD Document Error
+    #[error(transparent)]
+    XsdDocument(#[from] sxd_document::parser::Error),
+    /// SXD XPath Error
+    #[error(transparent)]
+    XsdXPath(#[from] sxd_xpath::Error),
+    /// Base64 Decoding Error
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    /// Certificate or Public Key Decoding Error
+    #[error(transparent)]
+    Der(#[from] x509_cert::der::Error),
+    /// RSA Key Transport Error
+    #[error(transparent)]
+    Rsa(#[from] rsa::Error),
+    /// Content could not be encrypted or authenticated
+    #[error("content encryption failed")]
+    Aead,
+    /// Content encryption key could not be wrapped or unwrapped
+    #[error("key wrap failed")]
+    KeyWrap,
+    /// The certificate holds a public key of an unsupported algorithm
+    #[error("unsupported public key algorithm: {0}")]
+    UnsupportedKey(String),
+    /// The `EncryptedData` uses an algorithm this module does not implement
+    #[error("unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    /// The `EncryptedData` lacks an element required for decryption
+    #[error("missing element: {0}")]
+    Missing(&'static str),
+    /// The decryption key does not match the key transport of the `EncryptedKey`
+    #[error("decryption key does not match the key transport algorithm")]
+    KeyMismatch,
+    /// The XPath expression did not select any element
+    #[error("xpath selected no element: {0}")]
+    NoElement(String),
+    /// The decrypted content is not valid UTF-8
+    #[error(transparent)]
+    Utf8(#[from] std::string::FromUtf8Error),
+}
+
+/// Public key used to transport the content encryption key to the recipient.
+#[derive(Debug, Clone)]
+pub enum RecipientKey {
+    /// RSA-OAEP key transport
+    Rsa(rsa::RsaPublicKey),
+    /// ECDH-ES key agreement with AES-256 key wrap
+    P256(p256::PublicKey),
+}
+
+/// Recipient of an encrypted payload.
+#[derive(Debug, Clone)]
+pub struct Recipient {
+    /// Key transport public key of the recipient
+    pub key: RecipientKey,
+    /// DER encoded certificate of the recipient, included in the `KeyInfo`
+    pub certificate: Option<Vec<u8>>,
+    /// Subject name of the certificate
+    pub subject_name: Option<String>,
+    /// Optional key name hint for the recipient
+    pub key_name: Option<String>,
+}
+
+impl Recipient {
+    /// Create a recipient from an RSA public key.
+    pub fn rsa(key: rsa::RsaPublicKey) -> Self {
+        Self {
+            key: RecipientKey::Rsa(key),
+            certificate: None,
+            subject_name: None,
+            key_name: None,
+        }
+    }
+
+    /// Create a recipient from a P-256 public key.
+    pub fn p256(key: p256::PublicKey) -> Self {
+        Self {
+            key: RecipientKey::P256(key),
+            certificate: None,
+            subject_name: None,
+            key_name: None,
+        }
+    }
+
+    /// Create a recipient from a DER encoded X.509 certificate.
+    /// RSA and P-256 subject public keys are supported.
+    pub fn from_certificate_der(der: &[u8]) -> Result<Self, Error> {
+        Self::from_certificate(x509_cert::Certificate::from_der(der)?)
+    }
+
+    /// Create a recipient from a PEM encoded X.509 certificate.
+    pub fn from_certificate_pem(pem: &str) -> Result<Self, Error> {
+        Self::from_certificate(x509_cert::Certificate::from_pem(pem)?)
+    }
+
+    fn from_certificate(cert: x509_cert::Certificate) -> Result<Self, Error> {
+        use rsa::pkcs8::DecodePublicKey;
+
+        let spki = &cert.tbs_certificate.subject_public_key_info;
+        let spki_der = spki.to_der()?;
+
+        let key = match spki.algorithm.oid {
+            RSA_ENCRYPTION => RecipientKey::Rsa(
+                rsa::RsaPublicKey::from_public_key_der(&spki_der)
+                    .map_err(|e| Error::UnsupportedKey(e.to_string()))?,
+            ),
+            ID_EC_PUBLIC_KEY => RecipientKey::P256(
+                p256::PublicKey::from_public_key_der(&spki_der)
+                    .map_err(|e| Error::UnsupportedKey(e.to_string()))?,
+            ),
+            oid => return Err(Error::UnsupportedKey(oid.to_string())),
+        };
+
+        Ok(Self {
+            key,
+            certificate: Some(cert.to_der()?),
+            subject_name: Some(cert.tbs_certificate.subject.to_string()),
+            key_name: None,
+        })
+    }
+
+    /// Set the key name hint included in the `KeyInfo` of the `EncryptedKey`.
+    pub fn with_key_name(self, key_name: impl Into<String>) -> Self {
+        Self {
+            key_name: Some(key_name.into()),
+            ..self
+        }
+    }
+
+    fn x_509_data(&self) -> Option<X509Data> {
+        self.certificate.as_ref().map(|der| X509Data {
+            x_509_certificate: Some(BASE64.encode(der)),
+            x_509_subject_name: self.subject_name.clone(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Private key of the recipient used to recover the content encryption key.
+#[derive(Debug, Clone)]
+pub enum DecryptionKey {
+    /// RSA-OAEP key transport
+    Rsa(rsa::RsaPrivateKey),
+    /// ECDH-ES key agreement with AES-256 key wrap
+    P256(p256::SecretKey),
+}
+
+/// Encrypt the plaintext for the recipient.
+/// The `type` is the `Type` attribute of the `EncryptedData`, e.g. [`xenc::TYPE_ELEMENT`].
+pub fn encrypt(
+    plaintext: &[u8],
+    recipient: &Recipient,
+    r#type: Option<&str>,
+) -> Result<EncryptedData, Error> {
+    let mut cek = [0u8; CEK_LENGTH];
+    OsRng.fill_bytes(&mut cek);
+
+    let mut iv = [0u8; GCM_IV_LENGTH];
+    OsRng.fill_bytes(&mut iv);
+
+    let cipher = Aes256Gcm::new_from_slice(&cek).map_err(|_| Error::Aead)?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&iv), plaintext)
+        .map_err(|_| Error::Aead)?;
+
+    // The cipher value is the IV followed by the ciphertext and the authentication tag
+    let cipher_value = [iv.as_slice(), ciphertext.as_slice()].concat();
+
+    Ok(EncryptedData {
+        id: Some(format!("ED-{}", uuid::Uuid::new_v4())),
+        r#type: r#type.map(String::from),
+        encryption_method: Some(EncryptionMethod {
+            algorithm: AES_256_GCM.to_string(),
+            ..Default::default()
+        }),
+        key_info: Some(KeyInfo {
+            encrypted_key: Some(Box::new(encrypt_key(&cek, recipient)?)),
+            xmlns: xenc::namespace_dsig(),
+            ..Default::default()
+        }),
+        cipher_data: cipher_data(&cipher_value),
+        xmlns: xenc::namespace(),
+        ..Default::default()
+    })
+}
+
+/// Decrypt the `EncryptedData` with the private key of the recipient.
+pub fn decrypt(data: &EncryptedData, key: &DecryptionKey) -> Result<Vec<u8>, Error> {
+    let algorithm = data
+        .encryption_method
+        .as_ref()
+        .map(|method| method.algorithm.as_str())
+        .ok_or(Error::Missing("EncryptionMethod"))?;
+
+    if algorithm != AES_256_GCM {
+        return Err(Error::UnsupportedAlgorithm(algorithm.to_string()));
+    }
+
+    let encrypted_key = data
+        .key_info
+        .as_ref()
+        .and_then(|key_info| key_info.encrypted_key.as_ref())
+        .ok_or(Error::Missing("EncryptedKey"))?;
+
+    let cek = decrypt_key(encrypted_key, key)?;
+    let cipher_value = cipher_value(&data.cipher_data)?;
+
+    if cipher_value.len() < GCM_IV_LENGTH {
+        return Err(Error::Aead);
+    }
+
+    let (iv, ciphertext) = cipher_value.split_at(GCM_IV_LENGTH);
+    let cipher = Aes256Gcm::new_from_slice(&cek).map_err(|_| Error::Aead)?;
+
+    cipher
+        .decrypt(Nonce::from_slice(iv), ciphertext)
+        .map_err(|_| Error::Aead)
+}
+
+/// Encrypt every element selected by the XPath expression, e.g. `/Document`
+/// or `//*[local-name()='Acct']`, replacing it with an `EncryptedData` element.
+pub fn encrypt_xml(xml: &str, x_path: &str, recipient: &Recipient) -> Result<String, Error> {
+    let package = parser::parse(xml)?;
+    let doc = package.as_document();
+
+    let elements = match evaluate_xpath(&doc, x_path)? {
+        Value::Nodeset(nodes) => nodes
+            .document_order()
+            .into_iter()
+            .filter_map(|node| node.element())
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+
+    if elements.is_empty() {
+        return Err(Error::NoElement(x_path.to_string()));
+    }
+
+    for element in elements {
+        let mut plaintext = String::new();
+        write_element(element, None, &mut plaintext);
+
+        let data = encrypt(plaintext.as_bytes(), recipient, Some(xenc::TYPE_ELEMENT))?;
+        let data = quick_xml::se::to_string(&data)?;
+
+        let encrypted = parser::parse(&data)?;
+        let encrypted = root_element(&encrypted.as_document())?;
+
+        replace_element(
+            element,
+            vec![ChildOfElement::Element(import_element(
+                doc, encrypted, None,
+            ))],
+        );
+    }
+
+    Ok(write_document(doc))
+}
+
+/// Decrypt every `EncryptedData` element of the XML string, replacing it with its plaintext.
+///
+/// The plaintext of an `EncryptedData` with the `Type` [`xenc::TYPE_CONTENT`] is the
+/// content of the parent element, e.g. several elements or text, and replaces the
+/// `EncryptedData` within the parent. Any other `Type` is decrypted as a single element.
+pub fn decrypt_xml(xml: &str, key: &DecryptionKey) -> Result<String, Error> {
+    let package = parser::parse(xml)?;
+    let doc = package.as_document();
+
+    let x_path = format!(
+        "//*[local-name()='EncryptedData' and namespace-uri()='{}']",
+        xenc::namespace()
+    );
+
+    let elements = match evaluate_xpath(&doc, &x_path)? {
+        Value::Nodeset(nodes) => nodes
+            .document_order()
+            .into_iter()
+            .filter_map(|node| node.element())
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+
+    for element in elements {
+        let mut encrypted = String::new();
+        write_element(element, None, &mut encrypted);
+
+        let data: EncryptedData = quick_xml::de::from_str(&encrypted)?;
+        let plaintext = String::from_utf8(decrypt(&data, key)?)?;
+
+        let parent_ns = element
+            .parent()
+            .and_then(|parent| parent.element())
+            .and_then(|parent| parent.name().namespace_uri());
+
+        let children = if data.r#type.as_deref() == Some(xenc::TYPE_CONTENT) {
+            // Parse the content within a wrapper element in the namespace of the parent
+            let content = format!(
+                "<Content xmlns=\"{}\">{}</Content>",
+                escape(parent_ns.unwrap_or_default()),
+                plaintext
+            );
+            let decrypted = parser::parse(&content)?;
+            let decrypted = root_element(&decrypted.as_document())?;
+
+            import_children(doc, decrypted, parent_ns)
+        } else {
+            let decrypted = parser::parse(&plaintext)?;
+            let decrypted = root_element(&decrypted.as_document())?;
+
+            vec![ChildOfElement::Element(import_element(
+                doc, decrypted, parent_ns,
+            ))]
+        };
+
+        replace_element(element, children);
+    }
+
+    Ok(write_document(doc))
+}
+
+/// Wrap the content encryption key for the recipient.
+fn encrypt_key(cek: &[u8], recipient: &Recipient) -> Result<EncryptedKey, Error> {
+    match &recipient.key {
+        RecipientKey::Rsa(public_key) => {
+            let wrapped =
+                public_key.encrypt(&mut OsRng, rsa::Oaep::new::<Sha256>(), cek)?;
+
+            Ok(EncryptedKey {
+                encryption_method: Some(EncryptionMethod {
+                    algorithm: RSA_OAEP.to_string(),
+                    digest_method: Some(DigestMethod {
+                        algorithm: SHA_256.to_string(),
+                        xmlns: xenc::namespace_dsig(),
+                    }),
+                    mgf: Some(Mgf {
+                        algorithm: MGF1_SHA256.to_string(),
+                        xmlns: xenc::namespace_11(),
+                    }),
+                    ..Default::default()
+                }),
+                key_info: Some(KeyInfo {
+                    key_name: recipient.key_name.clone(),
+                    x_509_data: recipient.x_509_data(),
+                    xmlns: xenc::namespace_dsig(),
+                    ..Default::default()
+                }),
+                cipher_data: cipher_data(&wrapped),
+                xmlns: xenc::namespace(),
+                ..Default::default()
+            })
+        }
+        RecipientKey::P256(public_key) => {
+            let ephemeral = p256::ecdh::EphemeralSecret::random(&mut OsRng);
+            let shared = ephemeral.diffie_hellman(public_key);
+
+            let params = concat_kdf_params();
+            let kek = concat_kdf(shared.raw_secret_bytes(), &params)?;
+            let wrapped = aes_kw::KekAes256::from(kek)
+                .wrap_vec(cek)
+                .map_err(|_| Error::KeyWrap)?;
+
+            let originator = ephemeral.public_key().to_encoded_point(false);
+
+            Ok(EncryptedKey {
+                encryption_method: Some(EncryptionMethod {
+                    algorithm: KW_AES_256.to_string(),
+                    ..Default::default()
+                }),
+                key_info: Some(KeyInfo {
+                    agreement_method: Some(Box::new(AgreementMethod {
+                        algorithm: ECDH_ES.to_string(),
+                        key_derivation_method: Some(KeyDerivationMethod {
+                            algorithm: CONCAT_KDF.to_string(),
+                            concat_kdf_params: Some(params),
+                            xmlns: xenc::namespace_11(),
+                        }),
+                        originator_key_info: Some(OriginatorKeyInfo {
+                            key_value: Some(ec_key_value(originator.as_bytes())),
+                            xmlns: xenc::namespace_dsig(),
+                            ..Default::default()
+                        }),
+                        recipient_key_info: Some(RecipientKeyInfo {
+                            key_name: recipient.key_name.clone(),
+                            x_509_data: recipient.x_509_data(),
+                            key_value: recipient
+                                .certificate
+                                .is_none()
+                                .then(|| {
+                                    ec_key_value(public_key.to_encoded_point(false).as_bytes())
+                                }),
+                            xmlns: xenc::namespace_dsig(),
+                        }),
+                        xmlns: xenc::namespace(),
+                        ..Default::default()
+                    })),
+                    xmlns: xenc::namespace_dsig(),
+                    ..Default::default()
+                }),
+                cipher_data: cipher_data(&wrapped),
+                xmlns: xenc::namespace(),
+                ..Default::default()
+            })
+        }
+    }
+}
+
+/// Recover the content encryption key from the `EncryptedKey`.
+fn decrypt_key(encrypted_key: &EncryptedKey, key: &DecryptionKey) -> Result<Vec<u8>, Error> {
+    let algorithm = encrypted_key
+        .encryption_method
+        .as_ref()
+        .map(|method| method.algorithm.as_str())
+        .ok_or(Error::Missing("EncryptionMethod"))?;
+
+    let wrapped = cipher_value(&encrypted_key.cipher_data)?;
+
+    match (algorithm, key) {
+        (RSA_OAEP, DecryptionKey::Rsa(private_key)) => {
+            Ok(private_key.decrypt(rsa::Oaep::new::<Sha256>(), &wrapped)?)
+        }
+        (KW_AES_256, DecryptionKey::P256(secret_key)) => {
+            let agreement = encrypted_key
+                .key_info
+                .as_ref()
+                .and_then(|key_info| key_info.agreement_method.as_ref())
+                .ok_or(Error::Missing("AgreementMethod"))?;
+
+            if agreement.algorithm != ECDH_ES {
+                return Err(Error::UnsupportedAlgorithm(agreement.algorithm.clone()));
+            }
+
+            let originator = agreement
+                .originator_key_info
+                .as_ref()
+                .and_then(|key_info| key_info.key_value.as_ref())
+                .and_then(|key_value| key_value.ec_key_value.as_ref())
+                .ok_or(Error::Missing("OriginatorKeyInfo"))?;
+
+            let originator = p256::PublicKey::from_sec1_bytes(
+                &BASE64.decode(originator.public_key.trim())?,
+            )
+            .map_err(|_| Error::UnsupportedKey(originator.named_curve.uri.clone()))?;
+
+            let params = agreement
+                .key_derivation_method
+                .as_ref()
+                .and_then(|method| method.concat_kdf_params.as_ref())
+                .ok_or(Error::Missing("ConcatKDFParams"))?;
+
+            let shared = p256::ecdh::diffie_hellman(
+                secret_key.to_nonzero_scalar(),
+                originator.as_affine(),
+            );
+            let kek = concat_kdf(shared.raw_secret_bytes(), params)?;
+
+            aes_kw::KekAes256::from(kek)
+                .unwrap_vec(&wrapped)
+                .map_err(|_| Error::KeyWrap)
+        }
+        (RSA_OAEP, _) | (KW_AES_256, _) => Err(Error::KeyMismatch),
+        (algorithm, _) => Err(Error::UnsupportedAlgorithm(algorithm.to_string())),
+    }
+}
+
+/// ConcatKDF parameters used for the ECDH-ES key agreement.
+/// Bit string attributes are hex encoded with a leading padding byte.
+fn concat_kdf_params() -> ConcatKdfParams {
+    ConcatKdfParams {
+        digest_method: DigestMethod {
+            algorithm: SHA_256.to_string(),
+            xmlns: xenc::namespace_dsig(),
+        },
+        algorithm_id: Some(format!("00{}", hex::encode(KW_AES_256))),
+        party_u_info: Some("00".to_string()),
+        party_v_info: Some("00".to_string()),
+    }
+}
+
+/// Derive a 256 bit key encryption key with the SHA-256 ConcatKDF (NIST SP 800-56A).
+fn concat_kdf(z: &[u8], params: &ConcatKdfParams) -> Result<[u8; 32], Error> {
+    if params.digest_method.algorithm != SHA_256 {
+        return Err(Error::UnsupportedAlgorithm(
+            params.digest_method.algorithm.clone(),
+        ));
+    }
+
+    let bit_string = |value: &Option<String>| -> Result<Vec<u8>, Error> {
+        let bytes = hex::decode(value.as_deref().unwrap_or_default())
+            .map_err(|_| Error::Missing("ConcatKDFParams"))?;
+
+        // Strip the padding byte
+        Ok(bytes.into_iter().skip(1).collect())
+    };
+
+    let other_info = [
+        bit_string(&params.algorithm_id)?,
+        bit_string(&params.party_u_info)?,
+        bit_string(&params.party_v_info)?,
+    ]
+    .concat();
+
+    // A single round of SHA-256 yields the 256 bit key
+    let mut hasher = Sha256::new();
+    hasher.update(1u32.to_be_bytes());
+    hasher.update(z);
+    hasher.update(other_info);
+
+    Ok(hasher.finalize().into())
+}
+
+fn ec_key_value(point: &[u8]) -> KeyValue {
+    KeyValue {
+        ec_key_value: Some(EcKeyValue {
+            named_curve: NamedCurve {
+                uri: P256_NAMED_CURVE.to_string(),
+            },
+            public_key: BASE64.encode(point),
+            xmlns: xenc::namespace_dsig_11(),
+        }),
+    }
+}
+
+fn cipher_data(value: &[u8]) -> CipherData {
+    CipherData {
+        cipher_value: Some(CipherValue {
+            value: BASE64.encode(value),
+        }),
+        cipher_reference: None,
+    }
+}
+
+fn cipher_value(cipher_data: &CipherData) -> Result<Vec<u8>, Error> {
+    let value = cipher_data
+        .cipher_value
+        .as_ref()
+        .ok_or(Error::Missing("CipherValue"))?;
+
+    // Base64 content may be wrapped across lines
+    let value = value
+        .value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+    Ok(BASE64.decode(value)?)
+}
+
+fn root_element<'d>(doc: &dom::Document<'d>) -> Result<dom::Element<'d>, Error> {
+    doc.root()
+        .children()
+        .into_iter()
+        .find_map(|child| child.element())
+        .ok_or(Error::Missing("root element"))
+}
+
+/// Replace the element in its parent with the new children.
+/// A document root keeps only the new elements.
+fn replace_element<'d>(old: dom::Element<'d>, new: Vec<ChildOfElement<'d>>) {
+    match old.parent() {
+        Some(ParentOfChild::Element(parent)) => {
+            let children = parent
+                .children()
+                .into_iter()
+                .flat_map(|child| match child {
+                    ChildOfElement::Element(element) if element == old => new.clone(),
+                    child => vec![child],
+                })
+                .collect::<Vec<_>>();
+
+            parent.replace_children(children);
+        }
+        Some(ParentOfChild::Root(root)) => {
+            let children = root
+                .children()
+                .into_iter()
+                .flat_map(|child| match child {
+                    ChildOfRoot::Element(element) if element == old => new
+                        .iter()
+                        .filter_map(|child| child.element())
+                        .map(ChildOfRoot::Element)
+                        .collect(),
+                    child => vec![child],
+                })
+                .collect::<Vec<_>>();
+
+            root.replace_children(children);
+        }
+        None => {}
+    }
+}
+
+/// Deep copy an element of another package into the document.
+fn import_element<'d>(
+    doc: dom::Document<'d>,
+    source: dom::Element<'_>,
+    parent_ns: Option<&str>,
+) -> dom::Element<'d> {
+    let ns = source.name().namespace_uri();
+    let element = doc.create_element(QName::with_namespace_uri(ns, source.name().local_part()));
+
+    if ns != parent_ns {
+        element.set_default_namespace_uri(ns);
+    }
+
+    for attribute in source.attributes() {
+        element.set_attribute_value(
+            QName::with_namespace_uri(
+                attribute.name().namespace_uri(),
+                attribute.name().local_part(),
+            ),
+            attribute.value(),
+        );
+    }
+
+    for child in import_children(doc, source, ns) {
+        element.append_child(child);
+    }
+
+    element
+}
+
+/// Deep copy the child elements and text of an element of another package into the document.
+fn import_children<'d>(
+    doc: dom::Document<'d>,
+    source: dom::Element<'_>,
+    parent_ns: Option<&str>,
+) -> Vec<ChildOfElement<'d>> {
+    source
+        .children()
+        .into_iter()
+        .filter_map(|child| match child {
+            ChildOfElement::Element(child) => Some(ChildOfElement::Element(import_element(
+                doc, child, parent_ns,
+            ))),
+            ChildOfElement::Text(text) => Some(ChildOfElement::Text(doc.create_text(text.text()))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Serialize the element and its descendants, declaring the
+/// default namespace wherever it differs from the parent.
+fn write_element(element: dom::Element, parent_ns: Option<&str>, out: &mut String) {
+    let ns = element.name().namespace_uri();
+    let name = element.name().local_part();
+
+    out.push('<');
+    out.push_str(name);
+
+    if ns != parent_ns {
+        out.push_str(&format!(" xmlns=\"{}\"", escape(ns.unwrap_or_default())));
+    }
+
+    for (i, attribute) in element.attributes().into_iter().enumerate() {
+        match attribute.name().namespace_uri() {
+            Some(uri) => out.push_str(&format!(
+                " xmlns:a{i}=\"{}\" a{i}:{}=\"{}\"",
+                escape(uri),
+                attribute.name().local_part(),
+                escape(attribute.value())
+            )),
+            None => out.push_str(&format!(
+                " {}=\"{}\"",
+                attribute.name().local_part(),
+                escape(attribute.value())
+            )),
+        }
+    }
+
+    out.push('>');
+
+    for child in element.children() {
+        match child {
+            ChildOfElement::Element(child) => write_element(child, ns, out),
+            ChildOfElement::Text(text) => out.push_str(&escape(text.text())),
+            _ => {}
+        }
+    }
+
+    out.push_str(&format!("</{}>", name));
+}
+
+fn write_document(doc: dom::Document) -> String {
+    let mut buf = Vec::new();
+
+    // Writing to an in-memory buffer does not fail
+    writer::format_document(&doc, &mut buf).ok();
+
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = r#"<BizMsgEnvlp xmlns="urn:iso:std:iso:20022:tech:xsd:nvlp.001.001.01"><Doc><Document xmlns="urn:iso:std:iso:20022:tech:xsd:acmt.001.001.08"><AcctOpngInstr><MsgId><Id>MSG-1</Id></MsgId></AcctOpngInstr></Document></Doc></BizMsgEnvlp>"#;
+
+    #[test]
+    fn test_rsa_oaep_round_trip() -> Result<(), Error> {
+        let private_key = rsa::RsaPrivateKey::new(&mut OsRng, 1024)?;
+        let recipient = Recipient::rsa(private_key.to_public_key());
+
+        let encrypted = encrypt_xml(XML, "//*[local-name()='Document']", &recipient)?;
+        assert!(!encrypted.contains("MSG-1"));
+        assert!(encrypted.contains(RSA_OAEP));
+
+        let decrypted = decrypt_xml(&encrypted, &DecryptionKey::Rsa(private_key))?;
+        assert!(decrypted.contains("<Id>MSG-1</Id>"));
+        assert!(decrypted.contains("acmt.001.001.08"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ecdh_es_round_trip() -> Result<(), Error> {
+        let secret_key = p256::SecretKey::random(&mut OsRng);
+        let recipient = Recipient::p256(secret_key.public_key()).with_key_name("recipient");
+
+        let data = encrypt(b"<Document/>", &recipient, Some(xenc::TYPE_ELEMENT))?;
+        let xml = quick_xml::se::to_string(&data)?;
+        assert!(xml.contains(ECDH_ES));
+
+        let data: EncryptedData = quick_xml::de::from_str(&xml)?;
+        assert_eq!(decrypt(&data, &DecryptionKey::P256(secret_key))?, b"<Document/>");
+
+        let other = p256::SecretKey::random(&mut OsRng);
+        assert!(decrypt(&data, &DecryptionKey::P256(other)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_content() -> Result<(), Error> {
+        let secret_key = p256::SecretKey::random(&mut OsRng);
+        let recipient = Recipient::p256(secret_key.public_key());
+
+        let content = "<MsgId><Id>MSG-1</Id></MsgId><Nb>2</Nb>";
+        let data = encrypt(content.as_bytes(), &recipient, Some(xenc::TYPE_CONTENT))?;
+        let xml = format!(
+            r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:acmt.001.001.08"><AcctOpngInstr>{}</AcctOpngInstr></Document>"#,
+            quick_xml::se::to_string(&data)?
+        );
+
+        let decrypted = decrypt_xml(&xml, &DecryptionKey::P256(secret_key))?;
+        assert!(decrypted.contains("<Id>MSG-1</Id>"));
+        assert!(decrypted.contains("<Nb>2</Nb>"));
+        assert!(!decrypted.contains("EncryptedData"));
+        assert!(!decrypted.contains("<Content"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,487 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Duplicate Detection
+//!
+//! The `DuplicateDetector` trait records the identifiers of inbound messages, and
+//! classifies messages as new, duplicate, possible duplicate or copy.
+//!
+//! ```rust
+//! use iso_20022_sdk::duplicate::{DuplicateDetector, DuplicateStatus, FileDuplicateDetector};
+//!
+//! let mut detector = FileDuplicateDetector::open("duplicates.log")?;
+//!
+//! // Classify the message and record its identifiers if it is new
+//! match detector.check_and_record(&msg)? {
+//!     DuplicateStatus::New => { /* process the message */ }
+//!     status => { /* do not process the message twice */ }
+//! }
+//! ```
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::header::{AnyAppHdr, BusinessHeader};
+use crate::message::Message;
+
+/// XPath to the instructing agent of the document group header.
+const INSTG_AGT_X_PATH: &str =
+    "normalize-space((//*[local-name()='GrpHdr']/*[local-name()='InstgAgt'])[1])";
+
+/// XPath to the unique end-to-end transaction references of the document.
+const UETR_X_PATH: &str = "//*[local-name()='PmtId']/*[local-name()='UETR']";
+
+/// XPath to the end-to-end identifications of the document.
+const END_TO_END_ID_X_PATH: &str = "//*[local-name()='PmtId']/*[local-name()='EndToEndId']";
+
+/// End-to-end identification used when no identification is provided by the initiating party.
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// Identifier recorded by a duplicate detector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DuplicateKey {
+    /// Sender `Fr` and business message identifier `BizMsgIdr` of the application header
+    BizMsgIdr { sender: String, biz_msg_idr: String },
+    /// Instructing agent `InstgAgt` and message identification `MsgId` of the document
+    MsgId { instg_agt: String, msg_id: String },
+    /// Unique end-to-end transaction reference `UETR` of a transaction
+    Uetr(String),
+    /// Instructing agent `InstgAgt` and end-to-end identification `EndToEndId` of a transaction
+    EndToEndId {
+        instg_agt: String,
+        end_to_end_id: String,
+    },
+}
+
+impl DuplicateKey {
+    /// Return the identifiers of the message, from the application header and the document.
+    pub fn from_message<'a, Doc, Sig>(msg: &Message<'a, Doc, Sig>) -> Vec<Self>
+    where
+        Doc: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate,
+        Sig: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate
+            + crate::crypto::XmlSignature,
+    {
+        let mut keys = vec![];
+
+        if let Some(app_hdr) = msg.any_app_hdr() {
+            if !app_hdr.biz_msg_idr().is_empty() {
+                let sender = match &app_hdr {
+                    AnyAppHdr::V01(hdr) => quick_xml::se::to_string(&hdr.value.fr),
+                    AnyAppHdr::V02(hdr) => quick_xml::se::to_string(&hdr.value.fr),
+                    AnyAppHdr::V03(hdr) => quick_xml::se::to_string(&hdr.value.fr),
+                };
+
+                keys.push(Self::BizMsgIdr {
+                    sender: sender.unwrap_or_default(),
+                    biz_msg_idr: app_hdr.biz_msg_idr().to_string(),
+                });
+            }
+        }
+
+        let instg_agt = msg
+            .document_values(INSTG_AGT_X_PATH)
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        if let Some(msg_id) = msg.document_msg_id() {
+            keys.push(Self::MsgId {
+                instg_agt: instg_agt.clone(),
+                msg_id,
+            });
+        }
+
+        keys.extend(msg.document_values(UETR_X_PATH).into_iter().map(Self::Uetr));
+
+        keys.extend(
+            msg.document_values(END_TO_END_ID_X_PATH)
+                .into_iter()
+                .filter(|end_to_end_id| end_to_end_id != NOT_PROVIDED)
+                .map(|end_to_end_id| Self::EndToEndId {
+                    instg_agt: instg_agt.clone(),
+                    end_to_end_id,
+                }),
+        );
+
+        keys
+    }
+
+    /// Return whether the key identifies a transaction, rather than a message.
+    pub fn is_transaction(&self) -> bool {
+        matches!(self, Self::Uetr(_) | Self::EndToEndId { .. })
+    }
+
+    /// Return the key as a single line of tab separated values.
+    fn to_line(&self) -> String {
+        let fields = match self {
+            Self::BizMsgIdr {
+                sender,
+                biz_msg_idr,
+            } => vec!["BizMsgIdr", sender.as_str(), biz_msg_idr.as_str()],
+            Self::MsgId { instg_agt, msg_id } => vec!["MsgId", instg_agt.as_str(), msg_id.as_str()],
+            Self::Uetr(uetr) => vec!["UETR", uetr.as_str()],
+            Self::EndToEndId {
+                instg_agt,
+                end_to_end_id,
+            } => vec!["EndToEndId", instg_agt.as_str(), end_to_end_id.as_str()],
+        };
+
+        fields
+            .into_iter()
+            .map(|field| field.replace(['\t', '\r', '\n'], " "))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+
+    /// Parse the key from a line of tab separated values.
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split('\t').map(String::from).collect::<Vec<_>>();
+
+        let key = match fields.as_slice() {
+            [kind, sender, biz_msg_idr] if kind == "BizMsgIdr" => Self::BizMsgIdr {
+                sender: sender.clone(),
+                biz_msg_idr: biz_msg_idr.clone(),
+            },
+            [kind, instg_agt, msg_id] if kind == "MsgId" => Self::MsgId {
+                instg_agt: instg_agt.clone(),
+                msg_id: msg_id.clone(),
+            },
+            [kind, uetr] if kind == "UETR" => Self::Uetr(uetr.clone()),
+            [kind, instg_agt, end_to_end_id] if kind == "EndToEndId" => Self::EndToEndId {
+                instg_agt: instg_agt.clone(),
+                end_to_end_id: end_to_end_id.clone(),
+            },
+            _ => return None,
+        };
+
+        Some(key)
+    }
+}
+
+/// Duplicate classification of an inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateStatus {
+    /// None of the message identifiers have been recorded.
+    New,
+    /// The message identifiers `BizMsgIdr` or `MsgId` have been recorded, or
+    /// transaction identifiers of a message flagged as a resend.
+    Duplicate,
+    /// Only transaction identifiers `UETR` or `EndToEndId` have been recorded
+    /// and the message is not flagged as a resend, e.g. a transaction sent
+    /// again in a new message.
+    PossibleDuplicate,
+    /// The message is a copy `COPY` or copy duplicate `CODU` of a message sent
+    /// to another party, and is for information only.
+    Copy,
+}
+
+/// Duplicate detector records the identifiers of processed messages, and
+/// classifies inbound messages against the recorded identifiers.
+pub trait DuplicateDetector {
+    /// Error of the underlying store
+    type Error;
+
+    /// Return whether the key has been recorded.
+    fn contains(&self, key: &DuplicateKey) -> Result<bool, Self::Error>;
+
+    /// Record the key.
+    fn insert(&mut self, key: DuplicateKey) -> Result<(), Self::Error>;
+
+    /// Classify the message against the recorded identifiers and the
+    /// copy duplicate `CpyDplct` and possible duplicate `PssblDplct` flags
+    /// of the application header.
+    ///
+    /// A message with the duplicate `DUPL` or `PssblDplct` flag is a resend:
+    /// it is classified as a duplicate if any of its identifiers have been
+    /// recorded, and as new if none have been recorded, i.e. the original
+    /// message was not received. A message without the flags is classified as
+    /// a possible duplicate if only its transaction identifiers have been recorded.
+    fn classify<'a, Doc, Sig>(
+        &self,
+        msg: &Message<'a, Doc, Sig>,
+    ) -> Result<DuplicateStatus, Self::Error>
+    where
+        Doc: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate,
+        Sig: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate
+            + crate::crypto::XmlSignature,
+    {
+        let app_hdr = msg.any_app_hdr();
+
+        if app_hdr.as_ref().is_some_and(BusinessHeader::is_copy) {
+            return Ok(DuplicateStatus::Copy);
+        }
+
+        let resend = app_hdr
+            .as_ref()
+            .is_some_and(BusinessHeader::is_possible_duplicate);
+
+        let mut status = DuplicateStatus::New;
+
+        for key in DuplicateKey::from_message(msg) {
+            if !self.contains(&key)? {
+                continue;
+            }
+
+            if !key.is_transaction() || resend {
+                return Ok(DuplicateStatus::Duplicate);
+            }
+
+            status = DuplicateStatus::PossibleDuplicate;
+        }
+
+        Ok(status)
+    }
+
+    /// Classify the message, and record its identifiers if the message is new.
+    fn check_and_record<'a, Doc, Sig>(
+        &mut self,
+        msg: &Message<'a, Doc, Sig>,
+    ) -> Result<DuplicateStatus, Self::Error>
+    where
+        Doc: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate,
+        Sig: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate
+            + crate::crypto::XmlSignature,
+    {
+        let status = self.classify(msg)?;
+
+        if status == DuplicateStatus::New {
+            for key in DuplicateKey::from_message(msg) {
+                self.insert(key)?;
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+/// In-memory duplicate detector.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryDuplicateDetector {
+    keys: HashSet<DuplicateKey>,
+}
+
+impl MemoryDuplicateDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DuplicateDetector for MemoryDuplicateDetector {
+    type Error = std::convert::Infallible;
+
+    fn contains(&self, key: &DuplicateKey) -> Result<bool, Self::Error> {
+        Ok(self.keys.contains(key))
+    }
+
+    fn insert(&mut self, key: DuplicateKey) -> Result<(), Self::Error> {
+        self.keys.insert(key);
+
+        Ok(())
+    }
+}
+
+/// File-backed duplicate detector.
+///
+/// Recorded keys are appended to the file, one key per line, and loaded
+/// into memory when the file is opened, so the records survive restarts.
+#[derive(Debug)]
+pub struct FileDuplicateDetector {
+    path: PathBuf,
+    file: File,
+    keys: HashSet<DuplicateKey>,
+}
+
+impl FileDuplicateDetector {
+    /// Open the duplicate detector file, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+
+        let keys = BufReader::new(&file)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()?
+            .iter()
+            .filter_map(|line| DuplicateKey::from_line(line))
+            .collect();
+
+        Ok(Self { path, file, keys })
+    }
+
+    /// Return the path of the duplicate detector file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl DuplicateDetector for FileDuplicateDetector {
+    type Error = std::io::Error;
+
+    fn contains(&self, key: &DuplicateKey) -> Result<bool, Self::Error> {
+        Ok(self.keys.contains(key))
+    }
+
+    fn insert(&mut self, key: DuplicateKey) -> Result<(), Self::Error> {
+        if !self.keys.contains(&key) {
+            writeln!(self.file, "{}", key.to_line())?;
+            self.file.sync_data()?;
+            self.keys.insert(key);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecdsa::EcdsaSignature;
+    use crate::head::head_001_001_03::{self as head};
+
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        PartialEq,
+        ::serde::Serialize,
+        ::serde::Deserialize,
+        ::validator::Validate,
+    )]
+    #[serde(rename = "Document")]
+    struct TestDocument {
+        #[serde(rename = "@xmlns")]
+        xmlns: String,
+        #[serde(rename = "MsgId")]
+        msg_id: String,
+        #[serde(rename = "PmtId")]
+        pmt_id: Vec<TestPaymentIdentification>,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    struct TestPaymentIdentification {
+        #[serde(rename = "EndToEndId")]
+        end_to_end_id: String,
+        #[serde(rename = "UETR")]
+        uetr: String,
+    }
+
+    fn message<'a>(
+        biz_msg_idr: &str,
+        msg_id: &str,
+        uetr: &str,
+    ) -> Message<'a, TestDocument, EcdsaSignature> {
+        Message::builder()
+            .set_document(TestDocument {
+                xmlns: "urn:iso:std:iso:20022:tech:xsd:pacs.008.001.10".to_string(),
+                msg_id: msg_id.to_string(),
+                pmt_id: vec![TestPaymentIdentification {
+                    end_to_end_id: NOT_PROVIDED.to_string(),
+                    uetr: uetr.to_string(),
+                }],
+            })
+            .set_biz_msg_idr(head::Max35Text {
+                value: biz_msg_idr.to_string(),
+            })
+    }
+
+    #[test]
+    fn test_memory_duplicate_detector() -> Result<(), std::convert::Infallible> {
+        let mut detector = MemoryDuplicateDetector::new();
+        let uetr = "eb6305c9-1f7f-49de-aed0-16487c27b42d";
+
+        let msg = message("MSG-1", "MSG-1", uetr);
+        assert_eq!(detector.check_and_record(&msg)?, DuplicateStatus::New);
+        assert_eq!(detector.check_and_record(&msg)?, DuplicateStatus::Duplicate);
+
+        let msg = message("MSG-2", "MSG-2", uetr);
+        assert_eq!(detector.classify(&msg)?, DuplicateStatus::PossibleDuplicate);
+
+        // A resend of the transaction is a duplicate
+        let mut app_hdr = msg.app_hdr().unwrap_or_default();
+        app_hdr.value.pssbl_dplct = Some(head::YesNoIndicator { value: true });
+        let msg = msg.set_app_hdr(app_hdr);
+        assert_eq!(detector.classify(&msg)?, DuplicateStatus::Duplicate);
+
+        // A resend of a message that was not received is new
+        let resend = message("MSG-3", "MSG-3", "0d2ebc4b-5f8a-4a27-9b53-53e1d6ed4bc1");
+        let mut app_hdr = resend.app_hdr().unwrap_or_default();
+        app_hdr.value.pssbl_dplct = Some(head::YesNoIndicator { value: true });
+        let resend = resend.set_app_hdr(app_hdr);
+        assert_eq!(detector.classify(&resend)?, DuplicateStatus::New);
+
+        let mut app_hdr = msg.app_hdr().unwrap_or_default();
+        app_hdr.value.cpy_dplct = Some(head::CopyDuplicate1Code::Copy);
+        let msg = msg.set_app_hdr(app_hdr);
+        assert_eq!(detector.classify(&msg)?, DuplicateStatus::Copy);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_duplicate_detector() -> Result<(), std::io::Error> {
+        let path = std::env::temp_dir().join(format!("duplicates-{}.log", uuid::Uuid::new_v4()));
+        let msg = message("MSG-1", "MSG-1", "eb6305c9-1f7f-49de-aed0-16487c27b42d");
+
+        let mut detector = FileDuplicateDetector::open(&path)?;
+        assert_eq!(detector.check_and_record(&msg)?, DuplicateStatus::New);
+
+        // Records are loaded when the file is reopened
+        let detector = FileDuplicateDetector::open(&path)?;
+        assert_eq!(detector.classify(&msg)?, DuplicateStatus::Duplicate);
+
+        std::fs::remove_file(path)
+    }
+}
@@ -14,6 +14,14 @@
 // WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+/// Serializes an external code as the text content of an element.
+#[cfg(feature = "camt")]
+#[derive(::serde::Serialize)]
+pub(crate) struct Code<'a, T: ::serde::Serialize> {
+    #[serde(rename = "$text")]
+    pub(crate) value: &'a T,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
 pub enum ExternalChannel1Code {
     #[serde(rename = "COUR")]
@@ -0,0 +1,781 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Business Application Header
+//!
+//! The `head.001.001.01`, `head.001.001.02` and `head.001.001.03` business application
+//! header versions share the `BusinessHeader` trait, and can be handled as one type
+//! using the `AnyAppHdr` enumeration.
+//!
+//! ```rust
+//! use iso_20022_sdk::header::{AnyAppHdr, BusinessHeader, HeaderVersion};
+//!
+//! // Detect the header version from the `AppHdr` namespace
+//! let hdr = AnyAppHdr::<EcdsaSignature>::from_xml(&xml)?;
+//!
+//! // The sender and receiver are returned as a version-neutral `HeaderParty`
+//! println!("sender: {:?}", hdr.sender().bic);
+//!
+//! // Downgrade the header, e.g. for a CBPR+ `head.001.001.02` counterparty
+//! let (hdr, dropped) = hdr.convert(HeaderVersion::V02)?;
+//!
+//! // Elements that are not supported by the target version, e.g. `AppHdr/Rltd/MktPrctc/Id`
+//! println!("dropped: {:?}", dropped);
+//! ```
+use std::collections::HashMap;
+use std::io::BufReader;
+
+use crate::head::{head_001_001_01 as v01, head_001_001_02 as v02, head_001_001_03 as v03};
+
+use xml::{reader::XmlEvent, EventReader};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// Error Reading XML
+    #[error(transparent)]
+    XmlReader(#[from] xml::reader::Error),
+    /// The `AppHdr` element was not found, or its namespace is not supported
+    #[error("unsupported application header namespace: {0:?}")]
+    UnsupportedNamespace(Option<String>),
+}
+
+/// Business application header versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderVersion {
+    /// `head.001.001.01`
+    V01,
+    /// `head.001.001.02`
+    V02,
+    /// `head.001.001.03`
+    V03,
+}
+
+impl HeaderVersion {
+    /// Return the namespace of the header version.
+    pub fn namespace(&self) -> String {
+        match self {
+            Self::V01 => v01::namespace(),
+            Self::V02 => v02::namespace(),
+            Self::V03 => v03::namespace(),
+        }
+    }
+
+    /// Return the header version of the namespace, if supported.
+    pub fn from_namespace(namespace: &str) -> Option<Self> {
+        [Self::V01, Self::V02, Self::V03]
+            .into_iter()
+            .find(|version| version.namespace() == namespace)
+    }
+
+    /// Detect the header version from the namespace of the first `AppHdr` element in the xml string.
+    pub fn detect(xml_string: &str) -> Result<Self, Error> {
+        let buf_reader = BufReader::new(xml_string.as_bytes());
+        let event_reader = EventReader::new(buf_reader);
+
+        for e in event_reader {
+            if let XmlEvent::StartElement {
+                name:
+                    xml::name::OwnedName {
+                        local_name,
+                        namespace,
+                        ..
+                    },
+                ..
+            } = e?
+            {
+                if local_name == "AppHdr" {
+                    return namespace
+                        .as_deref()
+                        .and_then(Self::from_namespace)
+                        .ok_or(Error::UnsupportedNamespace(namespace));
+                }
+            }
+        }
+
+        Err(Error::UnsupportedNamespace(None))
+    }
+}
+
+/// Sender or receiver of a business application header, independent of the header version.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeaderParty {
+    /// BIC of the financial institution `FIId/FinInstnId/BICFI`,
+    /// or of the organisation `OrgId/Id/OrgId/AnyBIC`
+    pub bic: Option<String>,
+    /// LEI of the financial institution or organisation.
+    /// Always `None` for `head.001.001.01`, which does not define the element.
+    pub lei: Option<String>,
+    /// Name `Nm` of the financial institution or organisation
+    pub name: Option<String>,
+}
+
+/// Return the `HeaderParty` of a `Party9Choice` or `Party44Choice`, which share
+/// their element names across the header versions. The `lei` argument reads the
+/// `LEI` elements of `head.001.001.02` and later versions.
+macro_rules! header_party {
+    ($party:expr $(, $lei:ident)?) => {{
+        let fin_instn_id = $party.value.fi_id.as_ref().map(|fi_id| &fi_id.fin_instn_id);
+        let org_id = $party.value.org_id.as_ref();
+        let org_ids = org_id
+            .and_then(|org_id| org_id.id.as_ref())
+            .and_then(|id| id.value.org_id.as_ref());
+
+        #[allow(unused_mut)]
+        let mut party = HeaderParty {
+            bic: fin_instn_id
+                .and_then(|fin_instn_id| fin_instn_id.bicfi.as_ref())
+                .map(|bic| bic.value.clone())
+                .or_else(|| {
+                    org_ids
+                        .and_then(|org_ids| org_ids.any_bic.as_ref())
+                        .map(|bic| bic.value.clone())
+                }),
+            lei: None,
+            name: fin_instn_id
+                .and_then(|fin_instn_id| fin_instn_id.nm.as_ref())
+                .or_else(|| org_id.and_then(|org_id| org_id.nm.as_ref()))
+                .map(|nm| nm.value.clone()),
+        };
+
+        $(
+            party.lei = fin_instn_id
+                .and_then(|fin_instn_id| fin_instn_id.$lei.as_ref())
+                .or_else(|| org_ids.and_then(|org_ids| org_ids.$lei.as_ref()))
+                .map(|lei| lei.value.clone());
+        )?
+
+        party
+    }};
+}
+
+/// Return the header as a related header `Rltd`, e.g. `BusinessApplicationHeader7`.
+/// Additional elements of the related header type, e.g. `MktPrctc`, are passed by name.
+macro_rules! related_header {
+    ($hdr:expr, $($related:ident)::+ $(, $field:ident)*) => {
+        $($related)::+ {
+            char_set: $hdr.char_set.clone(),
+            fr: $hdr.fr.clone(),
+            to: $hdr.to.clone(),
+            biz_msg_idr: $hdr.biz_msg_idr.clone(),
+            msg_def_idr: $hdr.msg_def_idr.clone(),
+            biz_svc: $hdr.biz_svc.clone(),
+            cre_dt: $hdr.cre_dt.clone(),
+            cpy_dplct: $hdr.cpy_dplct.clone(),
+            pssbl_dplct: $hdr.pssbl_dplct.clone(),
+            prty: $hdr.prty.clone(),
+            sgntr: $hdr.sgntr.clone(),
+            $($field: $hdr.$field.clone(),)*
+        }
+    };
+}
+
+/// Common interface of the business application header versions.
+pub trait BusinessHeader {
+    /// Signature type of the header
+    type Signature;
+
+    /// Return the header version
+    fn version(&self) -> HeaderVersion;
+    /// Return the sender `Fr` of the message
+    fn sender(&self) -> HeaderParty;
+    /// Return the receiver `To` of the message
+    fn receiver(&self) -> HeaderParty;
+    /// Return the business message identifier `BizMsgIdr`
+    fn biz_msg_idr(&self) -> &str;
+    /// Return the message definition identifier `MsgDefIdr`, e.g. `pacs.008.001.10`
+    fn msg_def_idr(&self) -> &str;
+    /// Return the business service `BizSvc`
+    fn biz_svc(&self) -> Option<&str>;
+    /// Return the creation date time `CreDt`
+    fn cre_dt(&self) -> chrono::DateTime<chrono::Utc>;
+    /// Return the business processing date time `BizPrcgDt`.
+    /// Always `None` for `head.001.001.01`, which does not define the element.
+    fn biz_prcg_dt(&self) -> Option<chrono::DateTime<chrono::Utc>>;
+    /// Return the signature `Sgntr`
+    fn signature(&self) -> Option<&Self::Signature>;
+    /// Return whether the message is a copy `COPY` or copy duplicate `CODU`
+    /// of a message sent to another party, see `CpyDplct`
+    fn is_copy(&self) -> bool;
+    /// Return whether the message is flagged as the resend of a message,
+    /// i.e. the duplicate `DUPL` flag of `CpyDplct` or the `PssblDplct` flag
+    fn is_possible_duplicate(&self) -> bool;
+}
+
+impl<A, B> BusinessHeader for v01::AppHdr<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    type Signature = A;
+
+    fn version(&self) -> HeaderVersion {
+        HeaderVersion::V01
+    }
+
+    fn sender(&self) -> HeaderParty {
+        header_party!(self.value.fr)
+    }
+
+    fn receiver(&self) -> HeaderParty {
+        header_party!(self.value.to)
+    }
+
+    fn biz_msg_idr(&self) -> &str {
+        &self.value.biz_msg_idr.value
+    }
+
+    fn msg_def_idr(&self) -> &str {
+        &self.value.msg_def_idr.value
+    }
+
+    fn biz_svc(&self) -> Option<&str> {
+        self.value.biz_svc.as_ref().map(|svc| svc.value.as_str())
+    }
+
+    fn cre_dt(&self) -> chrono::DateTime<chrono::Utc> {
+        self.value.cre_dt.value
+    }
+
+    fn biz_prcg_dt(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        None
+    }
+
+    fn signature(&self) -> Option<&Self::Signature> {
+        self.value.sgntr.as_ref().map(|sgntr| &sgntr.value)
+    }
+
+    fn is_copy(&self) -> bool {
+        matches!(
+            self.value.cpy_dplct,
+            Some(v01::CopyDuplicate1Code::Copy | v01::CopyDuplicate1Code::Codu)
+        )
+    }
+
+    fn is_possible_duplicate(&self) -> bool {
+        matches!(self.value.cpy_dplct, Some(v01::CopyDuplicate1Code::Dupl))
+            || matches!(
+                self.value.pssbl_dplct,
+                Some(v01::YesNoIndicator { value: true })
+            )
+    }
+}
+
+impl<A, B> BusinessHeader for v02::AppHdr<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    type Signature = A;
+
+    fn version(&self) -> HeaderVersion {
+        HeaderVersion::V02
+    }
+
+    fn sender(&self) -> HeaderParty {
+        header_party!(self.value.fr, lei)
+    }
+
+    fn receiver(&self) -> HeaderParty {
+        header_party!(self.value.to, lei)
+    }
+
+    fn biz_msg_idr(&self) -> &str {
+        &self.value.biz_msg_idr.value
+    }
+
+    fn msg_def_idr(&self) -> &str {
+        &self.value.msg_def_idr.value
+    }
+
+    fn biz_svc(&self) -> Option<&str> {
+        self.value.biz_svc.as_ref().map(|svc| svc.value.as_str())
+    }
+
+    fn cre_dt(&self) -> chrono::DateTime<chrono::Utc> {
+        self.value.cre_dt.value
+    }
+
+    fn biz_prcg_dt(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.value.biz_prcg_dt.as_ref().map(|dt| dt.value)
+    }
+
+    fn signature(&self) -> Option<&Self::Signature> {
+        self.value.sgntr.as_ref().map(|sgntr| &sgntr.value)
+    }
+
+    fn is_copy(&self) -> bool {
+        matches!(
+            self.value.cpy_dplct,
+            Some(v02::CopyDuplicate1Code::Copy | v02::CopyDuplicate1Code::Codu)
+        )
+    }
+
+    fn is_possible_duplicate(&self) -> bool {
+        matches!(self.value.cpy_dplct, Some(v02::CopyDuplicate1Code::Dupl))
+            || matches!(
+                self.value.pssbl_dplct,
+                Some(v02::YesNoIndicator { value: true })
+            )
+    }
+}
+
+impl<A, B> BusinessHeader for v03::AppHdr<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    type Signature = A;
+
+    fn version(&self) -> HeaderVersion {
+        HeaderVersion::V03
+    }
+
+    fn sender(&self) -> HeaderParty {
+        header_party!(self.value.fr, lei)
+    }
+
+    fn receiver(&self) -> HeaderParty {
+        header_party!(self.value.to, lei)
+    }
+
+    fn biz_msg_idr(&self) -> &str {
+        &self.value.biz_msg_idr.value
+    }
+
+    fn msg_def_idr(&self) -> &str {
+        &self.value.msg_def_idr.value
+    }
+
+    fn biz_svc(&self) -> Option<&str> {
+        self.value.biz_svc.as_ref().map(|svc| svc.value.as_str())
+    }
+
+    fn cre_dt(&self) -> chrono::DateTime<chrono::Utc> {
+        self.value.cre_dt.value
+    }
+
+    fn biz_prcg_dt(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.value.biz_prcg_dt.as_ref().map(|dt| dt.value)
+    }
+
+    fn signature(&self) -> Option<&Self::Signature> {
+        self.value.sgntr.as_ref().map(|sgntr| &sgntr.value)
+    }
+
+    fn is_copy(&self) -> bool {
+        matches!(
+            self.value.cpy_dplct,
+            Some(v03::CopyDuplicate1Code::Copy | v03::CopyDuplicate1Code::Codu)
+        )
+    }
+
+    fn is_possible_duplicate(&self) -> bool {
+        matches!(self.value.cpy_dplct, Some(v03::CopyDuplicate1Code::Dupl))
+            || matches!(
+                self.value.pssbl_dplct,
+                Some(v03::YesNoIndicator { value: true })
+            )
+    }
+}
+
+/// Business application header of any supported version.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, ::serde::Serialize)]
+#[serde(untagged)]
+pub enum AnyAppHdr<
+    Sig: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    V01(v01::AppHdr<Sig, Sig>),
+    V02(v02::AppHdr<Sig, Sig>),
+    V03(v03::AppHdr<Sig, Sig>),
+}
+
+impl<Sig> Default for AnyAppHdr<Sig>
+where
+    Sig: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    fn default() -> Self {
+        Self::V03(Default::default())
+    }
+}
+
+impl<Sig> ::validator::Validate for AnyAppHdr<Sig>
+where
+    Sig: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    fn validate(&self) -> Result<(), ::validator::ValidationErrors> {
+        match self {
+            Self::V01(hdr) => hdr.validate(),
+            Self::V02(hdr) => hdr.validate(),
+            Self::V03(hdr) => hdr.validate(),
+        }
+    }
+}
+
+/// Evaluate the expression with the header of any version, e.g. to set `BizMsgIdr`.
+/// The header versions share their element names, the expression is type checked for each version.
+macro_rules! for_each_version {
+    ($any:expr, $hdr:ident => $body:expr) => {
+        match $any {
+            AnyAppHdr::V01($hdr) => $body,
+            AnyAppHdr::V02($hdr) => $body,
+            AnyAppHdr::V03($hdr) => $body,
+        }
+    };
+}
+
+impl<Sig> BusinessHeader for AnyAppHdr<Sig>
+where
+    Sig: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    type Signature = Sig;
+
+    fn version(&self) -> HeaderVersion {
+        for_each_version!(self, hdr => hdr.version())
+    }
+
+    fn sender(&self) -> HeaderParty {
+        for_each_version!(self, hdr => hdr.sender())
+    }
+
+    fn receiver(&self) -> HeaderParty {
+        for_each_version!(self, hdr => hdr.receiver())
+    }
+
+    fn biz_msg_idr(&self) -> &str {
+        for_each_version!(self, hdr => hdr.biz_msg_idr())
+    }
+
+    fn msg_def_idr(&self) -> &str {
+        for_each_version!(self, hdr => hdr.msg_def_idr())
+    }
+
+    fn biz_svc(&self) -> Option<&str> {
+        for_each_version!(self, hdr => hdr.biz_svc())
+    }
+
+    fn cre_dt(&self) -> chrono::DateTime<chrono::Utc> {
+        for_each_version!(self, hdr => hdr.cre_dt())
+    }
+
+    fn biz_prcg_dt(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        for_each_version!(self, hdr => hdr.biz_prcg_dt())
+    }
+
+    fn signature(&self) -> Option<&Self::Signature> {
+        for_each_version!(self, hdr => hdr.signature())
+    }
+
+    fn is_copy(&self) -> bool {
+        for_each_version!(self, hdr => hdr.is_copy())
+    }
+
+    fn is_possible_duplicate(&self) -> bool {
+        for_each_version!(self, hdr => hdr.is_possible_duplicate())
+    }
+}
+
+impl<Sig> AnyAppHdr<Sig>
+where
+    Sig: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+{
+    /// Return the header version
+    pub fn version(&self) -> HeaderVersion {
+        match self {
+            Self::V01(_) => HeaderVersion::V01,
+            Self::V02(_) => HeaderVersion::V02,
+            Self::V03(_) => HeaderVersion::V03,
+        }
+    }
+
+    /// Return the serialized xml string of the header.
+    pub fn to_xml(&self) -> Result<String, Error> {
+        Ok(quick_xml::se::to_string(self)?)
+    }
+
+    /// Set the business message identifier `BizMsgIdr`
+    pub fn set_biz_msg_idr(&mut self, biz_msg_idr: String) {
+        for_each_version!(self, hdr => hdr.value.biz_msg_idr.value = biz_msg_idr)
+    }
+
+    /// Set the message definition identifier `MsgDefIdr`, e.g. `pacs.008.001.10`
+    pub fn set_msg_def_idr(&mut self, msg_def_idr: String) {
+        for_each_version!(self, hdr => hdr.value.msg_def_idr.value = msg_def_idr)
+    }
+
+    /// Set the creation date time `CreDt`
+    pub fn set_cre_dt(&mut self, cre_dt: chrono::DateTime<chrono::Utc>) {
+        for_each_version!(self, hdr => hdr.value.cre_dt.value = cre_dt)
+    }
+
+    /// Set the signature `Sgntr`, overwriting any existing signature.
+    pub fn set_signature(&mut self, signature: Sig) {
+        match self {
+            Self::V01(hdr) => hdr.value.sgntr = Some(v01::SignatureEnvelope { value: signature }),
+            Self::V02(hdr) => hdr.value.sgntr = Some(v02::SignatureEnvelope { value: signature }),
+            Self::V03(hdr) => hdr.value.sgntr = Some(v03::SignatureEnvelope { value: signature }),
+        }
+    }
+
+    /// Set the namespace of the header to the namespace of its version.
+    pub fn set_namespace(&mut self) {
+        let namespace = self.version().namespace();
+
+        for_each_version!(self, hdr => hdr.value.xmlns = namespace)
+    }
+
+    /// Return a reply header in the same version.
+    ///
+    /// The sender `Fr` and receiver `To` are swapped, the business service and
+    /// market practice are carried over and the header is added as the related
+    /// header `Rltd`. The `BizMsgIdr`, `MsgDefIdr` and `CreDt` of the reply are
+    /// left to the caller.
+    pub fn reply(&self) -> Self {
+        match self {
+            Self::V01(hdr) => Self::V01(v01::AppHdr {
+                value: v01::BusinessApplicationHeaderV01 {
+                    fr: hdr.value.to.clone(),
+                    to: hdr.value.fr.clone(),
+                    biz_svc: hdr.value.biz_svc.clone(),
+                    rltd: Some(related_header!(hdr.value, v01::BusinessApplicationHeader1)),
+                    xmlns: v01::namespace(),
+                    ..Default::default()
+                },
+            }),
+            Self::V02(hdr) => Self::V02(v02::AppHdr {
+                value: v02::BusinessApplicationHeaderV02 {
+                    fr: hdr.value.to.clone(),
+                    to: hdr.value.fr.clone(),
+                    biz_svc: hdr.value.biz_svc.clone(),
+                    mkt_prctc: hdr.value.mkt_prctc.clone(),
+                    rltd: vec![related_header!(hdr.value, v02::BusinessApplicationHeader5)],
+                    xmlns: v02::namespace(),
+                    ..Default::default()
+                },
+            }),
+            Self::V03(hdr) => Self::V03(v03::AppHdr {
+                value: v03::BusinessApplicationHeaderV03 {
+                    fr: hdr.value.to.clone(),
+                    to: hdr.value.fr.clone(),
+                    biz_svc: hdr.value.biz_svc.clone(),
+                    mkt_prctc: hdr.value.mkt_prctc.clone(),
+                    rltd: vec![related_header!(
+                        hdr.value,
+                        v03::BusinessApplicationHeader7,
+                        mkt_prctc,
+                        biz_prcg_dt
+                    )],
+                    xmlns: v03::namespace(),
+                    ..Default::default()
+                },
+            }),
+        }
+    }
+}
+
+impl<Sig> AnyAppHdr<Sig>
+where
+    Sig: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::de::DeserializeOwned
+        + ::validator::Validate,
+{
+    /// Parse the header from the xml string, detecting the version
+    /// from the namespace of the `AppHdr` element.
+    pub fn from_xml(xml_string: &str) -> Result<Self, Error> {
+        Self::from_xml_version(xml_string, HeaderVersion::detect(xml_string)?)
+    }
+
+    /// Parse the header from the xml string as the header version.
+    /// The namespace of the header is set to the namespace of the version.
+    pub fn from_xml_version(xml_string: &str, version: HeaderVersion) -> Result<Self, Error> {
+        let hdr = match version {
+            HeaderVersion::V01 => {
+                let mut hdr: v01::AppHdr<Sig, Sig> = quick_xml::de::from_str(xml_string)?;
+                hdr.value.xmlns = version.namespace();
+                Self::V01(hdr)
+            }
+            HeaderVersion::V02 => {
+                let mut hdr: v02::AppHdr<Sig, Sig> = quick_xml::de::from_str(xml_string)?;
+                hdr.value.xmlns = version.namespace();
+                Self::V02(hdr)
+            }
+            HeaderVersion::V03 => {
+                let mut hdr: v03::AppHdr<Sig, Sig> = quick_xml::de::from_str(xml_string)?;
+                hdr.value.xmlns = version.namespace();
+                Self::V03(hdr)
+            }
+        };
+
+        Ok(hdr)
+    }
+
+    /// Convert the header to the header version.
+    ///
+    /// Returns the converted header and the paths of the elements, e.g.
+    /// `AppHdr/Rltd/BizPrcgDt`, that are not supported by the target version
+    /// and were dropped in the conversion. `head.001.001.01` supports a single
+    /// related header `Rltd`, any additional related headers are dropped.
+    pub fn convert(&self, version: HeaderVersion) -> Result<(Self, Vec<String>), Error> {
+        let source = self.to_xml()?;
+
+        let input = match self {
+            Self::V02(hdr) if version == HeaderVersion::V01 => {
+                let mut hdr = hdr.clone();
+                hdr.value.rltd.truncate(1);
+                quick_xml::se::to_string(&hdr)?
+            }
+            Self::V03(hdr) if version == HeaderVersion::V01 => {
+                let mut hdr = hdr.clone();
+                hdr.value.rltd.truncate(1);
+                quick_xml::se::to_string(&hdr)?
+            }
+            _ => source.clone(),
+        };
+
+        let hdr = Self::from_xml_version(&input, version)?;
+        let dropped = dropped_elements(&source, &hdr.to_xml()?)?;
+
+        Ok((hdr, dropped))
+    }
+
+    /// Return the header as `head.001.001.03`, converting it if required.
+    /// `head.001.001.03` supports all elements of the previous versions.
+    pub fn into_v03(self) -> Result<v03::AppHdr<Sig, Sig>, Error> {
+        match self {
+            Self::V03(hdr) => Ok(hdr),
+            hdr => match hdr.convert(HeaderVersion::V03)?.0 {
+                Self::V03(hdr) => Ok(hdr),
+                _ => unreachable!("header converted to head.001.001.03"),
+            },
+        }
+    }
+}
+
+/// Return the paths of the text elements in the source xml string
+/// that are not found in the target xml string.
+fn dropped_elements(source: &str, target: &str) -> Result<Vec<String>, Error> {
+    let mut target_paths =
+        text_element_paths(target)?
+            .into_iter()
+            .fold(HashMap::new(), |mut paths, path| {
+                *paths.entry(path).or_insert(0) += 1;
+                paths
+            });
+
+    Ok(text_element_paths(source)?
+        .into_iter()
+        .filter(|path| match target_paths.get_mut(path) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect())
+}
+
+/// Return the paths of the elements with text content in the xml string, e.g. `AppHdr/BizMsgIdr`
+fn text_element_paths(xml_string: &str) -> Result<Vec<String>, Error> {
+    let buf_reader = BufReader::new(xml_string.as_bytes());
+    let event_reader = EventReader::new(buf_reader);
+
+    let mut stack = Vec::new();
+    let mut paths = Vec::new();
+
+    for e in event_reader {
+        match e? {
+            XmlEvent::StartElement { name, .. } => stack.push(name.local_name),
+            XmlEvent::EndElement { .. } => {
+                stack.pop();
+            }
+            XmlEvent::Characters(_) | XmlEvent::CData(_) => paths.push(stack.join("/")),
+            _ => (),
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecdsa::EcdsaSignature;
+
+    const V03_XML: &str = r#"<AppHdr xmlns="urn:iso:std:iso:20022:tech:xsd:head.001.001.03"><Fr><FIId><FinInstnId><BICFI>AAAAUS33XXX</BICFI></FinInstnId></FIId></Fr><To><FIId><FinInstnId><BICFI>BBBBGB22XXX</BICFI></FinInstnId></FIId></To><BizMsgIdr>MSG-1</BizMsgIdr><MsgDefIdr>pacs.008.001.08</MsgDefIdr><BizSvc>swift.cbprplus.02</BizSvc><MktPrctc><Regy>CBPR+</Regy><Id>2023</Id></MktPrctc><CreDt>2023-04-18T17:02:14Z</CreDt></AppHdr>"#;
+
+    #[test]
+    fn test_detect_version() -> Result<(), Error> {
+        assert_eq!(HeaderVersion::detect(V03_XML)?, HeaderVersion::V03);
+        assert!(HeaderVersion::detect("<AppHdr xmlns=\"urn:example\"/>").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_header() -> Result<(), Error> {
+        let hdr = AnyAppHdr::<EcdsaSignature>::from_xml(V03_XML)?;
+
+        let (v02, dropped) = hdr.convert(HeaderVersion::V02)?;
+        assert_eq!(v02.version(), HeaderVersion::V02);
+        assert!(dropped.is_empty());
+
+        let (v01, dropped) = v02.convert(HeaderVersion::V01)?;
+        assert_eq!(dropped, vec!["AppHdr/MktPrctc/Regy", "AppHdr/MktPrctc/Id"]);
+
+        match v01 {
+            AnyAppHdr::V01(hdr) => {
+                assert_eq!(hdr.biz_msg_idr(), "MSG-1");
+                assert_eq!(hdr.sender().bic.as_deref(), Some("AAAAUS33XXX"));
+                assert_eq!(hdr.receiver().bic.as_deref(), Some("BBBBGB22XXX"));
+                assert_eq!(hdr.biz_svc(), Some("swift.cbprplus.02"));
+                assert_eq!(hdr.value.xmlns, v01::namespace());
+            }
+            _ => panic!("expected head.001.001.01 header"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply_header() -> Result<(), Error> {
+        let (hdr, _) =
+            AnyAppHdr::<EcdsaSignature>::from_xml(V03_XML)?.convert(HeaderVersion::V02)?;
+        let reply = hdr.reply();
+
+        assert_eq!(reply.version(), HeaderVersion::V02);
+        assert_eq!(reply.sender(), hdr.receiver());
+        assert_eq!(reply.receiver(), hdr.sender());
+        assert_eq!(reply.biz_svc(), Some("swift.cbprplus.02"));
+
+        match reply {
+            AnyAppHdr::V02(reply) => {
+                assert_eq!(reply.value.rltd.len(), 1);
+                assert_eq!(reply.value.rltd[0].biz_msg_idr.value, "MSG-1");
+                assert_eq!(reply.value.xmlns, v02::namespace());
+            }
+            _ => panic!("expected head.001.001.02 header"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,723 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Exceptions and Investigations
+//!
+//! The `CaseManager` tracks exceptions and investigations cases through their life cycle,
+//! i.e. opened, assigned, resolved, rejected or cancelled. It ingests the `camt.026` to
+//! `camt.039`, `camt.056` and `camt.087` documents of a case, links them to the case by
+//! `Case/Id` or `Assgnmt/Id`, or to the underlying payment by UETR and end-to-end
+//! identification, and rejects messages that are not legal in the state of the case.
+//!
+//! ```rust
+//! use iso_20022_sdk::external_codes::ExternalInvestigationExecutionConfirmation1Code;
+//! use iso_20022_sdk::investigation::{CaseManager, CaseStatus};
+//!
+//! let mut cases = CaseManager::default();
+//!
+//! // Open a case from a received camt.056 cancellation request
+//! let case_id = cases.ingest(&camt_056_xml)?;
+//!
+//! // Answer the case, e.g. accepting the cancellation
+//! let camt_029 = cases.resolve::<Dmkr>(
+//!     &case_id,
+//!     &ExternalInvestigationExecutionConfirmation1Code::Cncl,
+//! )?;
+//!
+//! assert_eq!(cases.case(&case_id).unwrap().status, CaseStatus::Resolved);
+//! ```
+use std::collections::HashMap;
+
+use iso_20022_camt::camt_029_001_11 as camt_029;
+use serde::Serialize;
+use sxd_document::{parser, Package};
+use sxd_xpath::evaluate_xpath;
+
+use crate::external_codes::{Code, ExternalInvestigationExecutionConfirmation1Code};
+
+/// Namespace prefix of the ISO 20022 documents.
+const NAMESPACE_PREFIX: &str = "urn:iso:std:iso:20022:tech:xsd:";
+
+/// XPath to the namespace of the document.
+const NAMESPACE_X_PATH: &str = "namespace-uri(/*)";
+
+/// XPath to the case identification of the document.
+const CASE_ID_X_PATH: &str = "normalize-space((/*/*/*[local-name()='Case' or local-name()='RslvdCase']/*[local-name()='Id'])[1])";
+
+/// XPath to the assignment identification of the document.
+const ASSIGNMENT_ID_X_PATH: &str =
+    "normalize-space(/*/*/*[local-name()='Assgnmt']/*[local-name()='Id'])";
+
+/// XPath to the BIC of the assigner of the document.
+const ASSIGNER_X_PATH: &str = "normalize-space((/*/*/*[local-name()='Assgnmt']/*[local-name()='Assgnr']//*[local-name()='BICFI' or local-name()='AnyBIC'])[1])";
+
+/// XPath to the BIC of the assignee of the document.
+const ASSIGNEE_X_PATH: &str = "normalize-space((/*/*/*[local-name()='Assgnmt']/*[local-name()='Assgne']//*[local-name()='BICFI' or local-name()='AnyBIC'])[1])";
+
+/// XPath to the BIC of the creator of the case.
+const CREATOR_X_PATH: &str = "normalize-space((/*/*/*[local-name()='Case']/*[local-name()='Cretr']//*[local-name()='BICFI' or local-name()='AnyBIC'])[1])";
+
+/// XPath to the unique end-to-end transaction reference of the underlying payment.
+const UETR_X_PATH: &str =
+    "normalize-space((//*[local-name()='OrgnlUETR' or local-name()='UETR'])[1])";
+
+/// XPath to the end-to-end identification of the underlying payment.
+const END_TO_END_ID_X_PATH: &str =
+    "normalize-space((//*[local-name()='OrgnlEndToEndId' or local-name()='EndToEndId'])[1])";
+
+/// XPath to the reopen case indicator of the case.
+const REOPEN_X_PATH: &str =
+    "normalize-space(/*/*/*[local-name()='Case']/*[local-name()='ReopCaseIndctn'])";
+
+/// XPath to the investigation status of a resolution of investigation.
+const STATUS_X_PATH: &str = "normalize-space(/*/*/*[local-name()='Sts']/*[local-name()='Conf'] | /*/*/*[local-name()='Sts']/*[local-name()='RjctdMod']/*/*/*[local-name()='Cd'])";
+
+/// XPath to the rejected modification of a resolution of investigation.
+const REJECTED_MODIFICATION_X_PATH: &str =
+    "count(/*/*/*[local-name()='Sts']/*[local-name()='RjctdMod'])";
+
+/// XPath to the assignment cancellation confirmation of a resolution of investigation.
+const ASSIGNMENT_CANCELLATION_X_PATH: &str =
+    "normalize-space(/*/*/*[local-name()='Sts']/*[local-name()='AssgnmtCxlConf'])";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// SXD Document Error
+    #[error(transparent)]
+    XsdDocument(#[from] sxd_document::parser::Error),
+    /// SXD XPath Error
+    #[error(transparent)]
+    XsdXPath(#[from] sxd_xpath::Error),
+    /// The document is not an exceptions and investigations message
+    #[error("unsupported investigation message: {0:?}")]
+    UnsupportedMessage(Option<String>),
+    /// The document does not refer to a case or underlying payment
+    #[error("{0:?} does not refer to a case or underlying payment")]
+    MissingCaseReference(CaseMessage),
+    /// No case is known for the reference
+    #[error("unknown case: {0}")]
+    UnknownCase(String),
+    /// The message is not legal in the state of the case
+    #[error("{message:?} is not legal for case {case_id} in status {status:?}")]
+    IllegalTransition {
+        case_id: String,
+        status: CaseStatus,
+        message: CaseMessage,
+    },
+}
+
+/// Exceptions and investigations messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseMessage {
+    /// `camt.026` UnableToApply
+    UnableToApply,
+    /// `camt.027` ClaimNonReceipt
+    ClaimNonReceipt,
+    /// `camt.028` AdditionalPaymentInformation
+    AdditionalPaymentInformation,
+    /// `camt.029` ResolutionOfInvestigation
+    ResolutionOfInvestigation,
+    /// `camt.030` NotificationOfCaseAssignment
+    NotificationOfCaseAssignment,
+    /// `camt.031` RejectInvestigation
+    RejectInvestigation,
+    /// `camt.032` CancelCaseAssignment
+    CancelCaseAssignment,
+    /// `camt.033` RequestForDuplicate
+    RequestForDuplicate,
+    /// `camt.034` Duplicate
+    Duplicate,
+    /// `camt.035` ProprietaryFormatInvestigation
+    ProprietaryFormatInvestigation,
+    /// `camt.036` DebitAuthorisationResponse
+    DebitAuthorisationResponse,
+    /// `camt.037` DebitAuthorisationRequest
+    DebitAuthorisationRequest,
+    /// `camt.038` CaseStatusReportRequest
+    CaseStatusReportRequest,
+    /// `camt.039` CaseStatusReport
+    CaseStatusReport,
+    /// `camt.056` FIToFIPaymentCancellationRequest
+    FiToFiPaymentCancellationRequest,
+    /// `camt.087` RequestToModifyPayment
+    RequestToModifyPayment,
+}
+
+impl CaseMessage {
+    /// Return the message of a namespace, of any version, e.g.
+    /// `urn:iso:std:iso:20022:tech:xsd:camt.056.001.10`
+    pub fn from_namespace(namespace: &str) -> Option<Self> {
+        let msg_def_idr = namespace.strip_prefix(NAMESPACE_PREFIX)?;
+
+        Some(match msg_def_idr.get(..8)? {
+            "camt.026" => Self::UnableToApply,
+            "camt.027" => Self::ClaimNonReceipt,
+            "camt.028" => Self::AdditionalPaymentInformation,
+            "camt.029" => Self::ResolutionOfInvestigation,
+            "camt.030" => Self::NotificationOfCaseAssignment,
+            "camt.031" => Self::RejectInvestigation,
+            "camt.032" => Self::CancelCaseAssignment,
+            "camt.033" => Self::RequestForDuplicate,
+            "camt.034" => Self::Duplicate,
+            "camt.035" => Self::ProprietaryFormatInvestigation,
+            "camt.036" => Self::DebitAuthorisationResponse,
+            "camt.037" => Self::DebitAuthorisationRequest,
+            "camt.038" => Self::CaseStatusReportRequest,
+            "camt.039" => Self::CaseStatusReport,
+            "camt.056" => Self::FiToFiPaymentCancellationRequest,
+            "camt.087" => Self::RequestToModifyPayment,
+            _ => return None,
+        })
+    }
+
+    /// Return true if the message opens a case.
+    pub fn is_opening(&self) -> bool {
+        matches!(
+            self,
+            Self::UnableToApply
+                | Self::ClaimNonReceipt
+                | Self::ProprietaryFormatInvestigation
+                | Self::DebitAuthorisationRequest
+                | Self::FiToFiPaymentCancellationRequest
+                | Self::RequestToModifyPayment
+        )
+    }
+}
+
+/// Status of a case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseStatus {
+    /// The case is opened by its creator
+    Opened,
+    /// The case is assigned to, or being processed by, the next party
+    Assigned,
+    /// The case is resolved, e.g. the payment is cancelled or modified
+    Resolved,
+    /// The investigation, cancellation or modification is rejected
+    Rejected,
+    /// The case assignment is cancelled
+    Cancelled,
+}
+
+impl CaseStatus {
+    /// Return true if the case is closed, i.e. resolved, rejected or cancelled.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Resolved | Self::Rejected | Self::Cancelled)
+    }
+}
+
+/// Exceptions and investigations case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    /// Case identification, i.e. `Case/Id`, or the `Assgnmt/Id` of the opening message
+    pub id: String,
+    /// Current status of the case
+    pub status: CaseStatus,
+    /// Unique end-to-end transaction reference of the underlying payment
+    pub uetr: Option<String>,
+    /// End-to-end identification of the underlying payment
+    pub end_to_end_id: Option<String>,
+    /// BIC of the creator of the case
+    pub cretr: Option<String>,
+    /// BIC of the assigner of the latest assignment
+    pub assgnr: Option<String>,
+    /// BIC of the assignee of the latest assignment
+    pub assgne: Option<String>,
+    /// Messages of the case, with the resulting status, in order
+    pub history: Vec<(CaseMessage, CaseStatus)>,
+}
+
+impl Case {
+    /// Return the message opening the case.
+    pub fn opened_by(&self) -> Option<CaseMessage> {
+        self.history.first().map(|(message, _)| *message)
+    }
+}
+
+/// Manager of exceptions and investigations cases.
+#[derive(Debug, Default, Clone)]
+pub struct CaseManager {
+    cases: HashMap<String, Case>,
+}
+
+impl CaseManager {
+    /// Return the case of the identification.
+    pub fn case(&self, case_id: &str) -> Option<&Case> {
+        self.cases.get(case_id)
+    }
+
+    /// Return the cases of an underlying payment, by UETR or end-to-end identification.
+    pub fn cases_of_payment<'a>(&'a self, reference: &'a str) -> impl Iterator<Item = &'a Case> {
+        self.cases.values().filter(move |case| {
+            case.uetr.as_deref() == Some(reference)
+                || case.end_to_end_id.as_deref() == Some(reference)
+        })
+    }
+
+    /// Return the cases that are not closed.
+    pub fn open_cases(&self) -> impl Iterator<Item = &Case> {
+        self.cases.values().filter(|case| !case.status.is_closed())
+    }
+
+    /// Ingest an exceptions and investigations document, returning the identification of its case.
+    pub fn ingest(&mut self, document: &str) -> Result<String, Error> {
+        let document = CaseDocument::parse(document)?;
+        let message = document.message;
+
+        let case_id = document.value(CASE_ID_X_PATH)?;
+        let assignment_id = document.value(ASSIGNMENT_ID_X_PATH)?;
+        let uetr = document.value(UETR_X_PATH)?;
+        let end_to_end_id = document.value(END_TO_END_ID_X_PATH)?;
+
+        let existing = [&case_id, &assignment_id]
+            .into_iter()
+            .flatten()
+            .find(|id| self.cases.contains_key(*id))
+            .cloned()
+            .or_else(|| {
+                if message.is_opening() {
+                    None
+                } else {
+                    self.find_by_payment(uetr.as_deref(), end_to_end_id.as_deref())
+                }
+            });
+
+        let case_id = match existing {
+            Some(case_id) => case_id,
+            None if message.is_opening() => {
+                let case_id = case_id
+                    .or(assignment_id)
+                    .ok_or(Error::MissingCaseReference(message))?;
+
+                self.cases.insert(
+                    case_id.clone(),
+                    Case {
+                        id: case_id.clone(),
+                        status: CaseStatus::Opened,
+                        uetr: uetr.clone(),
+                        end_to_end_id: end_to_end_id.clone(),
+                        cretr: document.value(CREATOR_X_PATH)?,
+                        assgnr: document.value(ASSIGNER_X_PATH)?,
+                        assgne: document.value(ASSIGNEE_X_PATH)?,
+                        history: vec![(message, CaseStatus::Opened)],
+                    },
+                );
+
+                return Ok(case_id);
+            }
+            None => {
+                return Err(match case_id.or(assignment_id).or(uetr).or(end_to_end_id) {
+                    Some(reference) => Error::UnknownCase(reference),
+                    None => Error::MissingCaseReference(message),
+                })
+            }
+        };
+
+        let investigation_status = match message {
+            CaseMessage::ResolutionOfInvestigation => Some(document.investigation_status()?),
+            _ => None,
+        };
+        let reopen = document.value(REOPEN_X_PATH)?.as_deref() == Some("true");
+
+        let case = self
+            .cases
+            .get_mut(&case_id)
+            .ok_or_else(|| Error::UnknownCase(case_id.clone()))?;
+
+        let status = if reopen && message.is_opening() && case.status.is_closed() {
+            CaseStatus::Opened
+        } else {
+            transition(case.status, message, investigation_status.as_deref()).ok_or(
+                Error::IllegalTransition {
+                    case_id: case_id.clone(),
+                    status: case.status,
+                    message,
+                },
+            )?
+        };
+
+        if message.is_opening() || message == CaseMessage::NotificationOfCaseAssignment {
+            case.assgnr = document.value(ASSIGNER_X_PATH)?.or(case.assgnr.take());
+            case.assgne = document.value(ASSIGNEE_X_PATH)?.or(case.assgne.take());
+        }
+        case.uetr = case.uetr.take().or(uetr);
+        case.end_to_end_id = case.end_to_end_id.take().or(end_to_end_id);
+        case.status = status;
+        case.history.push((message, status));
+
+        Ok(case_id)
+    }
+
+    /// Build the `camt.029.001.11` resolution of investigation of a case, and apply it to the case.
+    ///
+    /// The resolution is sent by the assignee of the case to its assigner. The cancellation
+    /// details of a cancellation request case carry the transaction cancellation status,
+    /// i.e. `ACCR` for `CNCL`, `RJCR` for `RJCR` and `PDCR` for `PDCR`.
+    pub fn resolve<A>(
+        &mut self,
+        case_id: &str,
+        conf: &ExternalInvestigationExecutionConfirmation1Code,
+    ) -> Result<camt_029::Document<A>, Error>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        let case = self
+            .cases
+            .get_mut(case_id)
+            .ok_or_else(|| Error::UnknownCase(case_id.to_string()))?;
+
+        let conf: camt_029::ExternalInvestigationExecutionConfirmation1Code =
+            quick_xml::de::from_str(&quick_xml::se::to_string(&Code { value: conf })?)?;
+
+        let message = CaseMessage::ResolutionOfInvestigation;
+        let status = transition(case.status, message, Some(&conf.value)).ok_or(
+            Error::IllegalTransition {
+                case_id: case_id.to_string(),
+                status: case.status,
+                message,
+            },
+        )?;
+
+        let rslvd_case = camt_029::Case5 {
+            id: camt_029::Max35Text {
+                value: case.id.clone(),
+            },
+            cretr: agent(case.cretr.as_ref().or(case.assgnr.as_ref())),
+            reop_case_indctn: None,
+        };
+
+        let cxl_dtls = match case.opened_by() {
+            Some(CaseMessage::FiToFiPaymentCancellationRequest) => {
+                vec![camt_029::UnderlyingTransaction29 {
+                    tx_inf_and_sts: vec![camt_029::PaymentTransaction138 {
+                        rslvd_case: Some(rslvd_case.clone()),
+                        orgnl_end_to_end_id: case.end_to_end_id.as_ref().map(|value| {
+                            camt_029::Max35Text {
+                                value: value.clone(),
+                            }
+                        }),
+                        orgnl_uetr: case.uetr.as_ref().map(|value| camt_029::UuiDv4Identifier {
+                            value: value.clone(),
+                        }),
+                        tx_cxl_sts: match conf.value.as_str() {
+                            "CNCL" => Some(camt_029::CancellationIndividualStatus1Code::Accr),
+                            "RJCR" => Some(camt_029::CancellationIndividualStatus1Code::Rjcr),
+                            "PDCR" => Some(camt_029::CancellationIndividualStatus1Code::Pdcr),
+                            _ => None,
+                        },
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }]
+            }
+            _ => Vec::new(),
+        };
+
+        let document = camt_029::Document {
+            rsltn_of_invstgtn: camt_029::ResolutionOfInvestigationV11 {
+                assgnmt: camt_029::CaseAssignment5 {
+                    id: camt_029::Max35Text {
+                        value: uuid::Uuid::new_v4().simple().to_string(),
+                    },
+                    assgnr: agent(case.assgne.as_ref()),
+                    assgne: agent(case.assgnr.as_ref()),
+                    cre_dt_tm: camt_029::IsoDateTime {
+                        value: chrono::Utc::now(),
+                    },
+                },
+                rslvd_case: Some(rslvd_case),
+                sts: camt_029::InvestigationStatus5Choice {
+                    value: camt_029::InvestigationStatus5ChoiceEnum {
+                        conf: Some(conf),
+                        ..Default::default()
+                    },
+                },
+                cxl_dtls,
+                ..Default::default()
+            },
+            xmlns: camt_029::namespace(),
+        };
+
+        case.status = status;
+        case.history.push((message, status));
+
+        Ok(document)
+    }
+
+    /// Find the open case of an underlying payment, preferring the UETR.
+    fn find_by_payment(&self, uetr: Option<&str>, end_to_end_id: Option<&str>) -> Option<String> {
+        let find = |reference: Option<&str>| {
+            let reference = reference?;
+            let mut cases = self
+                .cases_of_payment(reference)
+                .filter(|case| !case.status.is_closed());
+
+            cases.next().map(|case| case.id.clone())
+        };
+
+        find(uetr).or_else(|| find(end_to_end_id))
+    }
+}
+
+/// Return the status of a case after a message, or `None` if the message is not legal.
+///
+/// The investigation status of a `camt.029` resolution of investigation, e.g. `CNCL`, `RJCR`
+/// or `PDCR`, determines whether the case is resolved, rejected or still pending.
+pub fn transition(
+    status: CaseStatus,
+    message: CaseMessage,
+    investigation_status: Option<&str>,
+) -> Option<CaseStatus> {
+    use CaseMessage::*;
+
+    match (status.is_closed(), message) {
+        // Status requests are answered for closed cases
+        (_, CaseStatusReportRequest | CaseStatusReport) => Some(status),
+        (true, _) => None,
+        // A case is opened only once, unless it is reopened after being closed
+        (false, message) if message.is_opening() => None,
+        (false, NotificationOfCaseAssignment) => Some(CaseStatus::Assigned),
+        (false, AdditionalPaymentInformation | RequestForDuplicate | Duplicate) => Some(status),
+        (false, RejectInvestigation) => Some(CaseStatus::Rejected),
+        (false, CancelCaseAssignment) => Some(CaseStatus::Cancelled),
+        (false, DebitAuthorisationResponse) => Some(CaseStatus::Resolved),
+        (false, ResolutionOfInvestigation) => Some(match investigation_status {
+            Some("RJCR" | "RJNR" | "RJVA" | "RJMD") => CaseStatus::Rejected,
+            Some("PDCR" | "PECR" | "PDNG" | "CWFW" | "MWFW" | "UWFW") => CaseStatus::Assigned,
+            Some("ACNC") => CaseStatus::Cancelled,
+            _ => CaseStatus::Resolved,
+        }),
+        (false, _) => None,
+    }
+}
+
+/// Parsed exceptions and investigations document.
+struct CaseDocument {
+    message: CaseMessage,
+    package: Package,
+}
+
+impl CaseDocument {
+    fn parse(xml: &str) -> Result<Self, Error> {
+        let package = parser::parse(xml)?;
+        let namespace = evaluate_xpath(&package.as_document(), NAMESPACE_X_PATH)?.into_string();
+        let message = CaseMessage::from_namespace(&namespace)
+            .ok_or(Error::UnsupportedMessage(Some(namespace)))?;
+
+        Ok(Self { message, package })
+    }
+
+    /// Evaluate an xpath expression against the document, returning a non-empty string value.
+    fn value(&self, x_path: &str) -> Result<Option<String>, Error> {
+        let value = evaluate_xpath(&self.package.as_document(), x_path)?.into_string();
+
+        Ok((!value.is_empty()).then_some(value))
+    }
+
+    /// Return the investigation status of a resolution of investigation, where a rejected
+    /// modification is reported as `RJMD` and a cancelled assignment as `ACNC`.
+    fn investigation_status(&self) -> Result<String, Error> {
+        if self.value(REJECTED_MODIFICATION_X_PATH)?.as_deref() != Some("0") {
+            return Ok("RJMD".to_string());
+        }
+        if self.value(ASSIGNMENT_CANCELLATION_X_PATH)?.as_deref() == Some("true") {
+            return Ok("ACNC".to_string());
+        }
+
+        Ok(self.value(STATUS_X_PATH)?.unwrap_or_default())
+    }
+}
+
+/// Return the `Party40Choice` agent of a BIC.
+fn agent(bicfi: Option<&String>) -> camt_029::Party40Choice {
+    camt_029::Party40Choice {
+        value: camt_029::Party40ChoiceEnum {
+            agt: Some(camt_029::BranchAndFinancialInstitutionIdentification6 {
+                fin_instn_id: camt_029::FinancialInstitutionIdentification18 {
+                    bicfi: bicfi.map(|bicfi| camt_029::BicfiDec2014Identifier {
+                        value: bicfi.clone(),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            pty: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    const UETR: &str = "8a562c67-ca16-48ba-b074-65581be6f001";
+
+    fn document(msg: &str, root: &str, body: &str) -> String {
+        format!(
+            "<Document xmlns=\"{NAMESPACE_PREFIX}{msg}\"><{root}>\
+            <Assgnmt><Id>ASSGNMT-1</Id>\
+            <Assgnr><Agt><FinInstnId><BICFI>BANKGB2L</BICFI></FinInstnId></Agt></Assgnr>\
+            <Assgne><Agt><FinInstnId><BICFI>BANKDEFF</BICFI></FinInstnId></Agt></Assgne>\
+            <CreDtTm>2023-06-01T10:00:00Z</CreDtTm></Assgnmt>{body}</{root}></Document>"
+        )
+    }
+
+    fn cancellation_request() -> String {
+        document(
+            "camt.056.001.10",
+            "FIToFIPmtCxlReq",
+            &format!(
+                "<Case><Id>CASE-1</Id>\
+                <Cretr><Agt><FinInstnId><BICFI>BANKGB2L</BICFI></FinInstnId></Agt></Cretr></Case>\
+                <Undrlyg><TxInf><OrgnlEndToEndId>E2E-1</OrgnlEndToEndId>\
+                <OrgnlUETR>{UETR}</OrgnlUETR></TxInf></Undrlyg>"
+            ),
+        )
+    }
+
+    #[test]
+    fn test_case_life_cycle() {
+        let mut cases = CaseManager::default();
+
+        let case_id = cases.ingest(&cancellation_request()).unwrap();
+        assert_eq!(case_id, "CASE-1");
+
+        let case = cases.case(&case_id).unwrap();
+        assert_eq!(case.status, CaseStatus::Opened);
+        assert_eq!(case.uetr.as_deref(), Some(UETR));
+        assert_eq!(case.end_to_end_id.as_deref(), Some("E2E-1"));
+        assert_eq!(cases.cases_of_payment("E2E-1").count(), 1);
+
+        // A status report request refers to the payment only
+        let request = document(
+            "camt.038.001.04",
+            "CaseStsRptReq",
+            &format!("<OrgnlUETR>{UETR}</OrgnlUETR>"),
+        );
+        assert_eq!(cases.ingest(&request).unwrap(), "CASE-1");
+
+        // The case cannot be opened twice
+        assert!(matches!(
+            cases.ingest(&cancellation_request()),
+            Err(Error::IllegalTransition {
+                status: CaseStatus::Opened,
+                message: CaseMessage::FiToFiPaymentCancellationRequest,
+                ..
+            })
+        ));
+
+        let pending = document(
+            "camt.029.001.11",
+            "RsltnOfInvstgtn",
+            "<RslvdCase><Id>CASE-1</Id></RslvdCase><Sts><Conf>PDCR</Conf></Sts>",
+        );
+        cases.ingest(&pending).unwrap();
+        assert_eq!(cases.case(&case_id).unwrap().status, CaseStatus::Assigned);
+
+        let camt_029 = cases
+            .resolve::<Dmkr>(
+                &case_id,
+                &ExternalInvestigationExecutionConfirmation1Code::Cncl,
+            )
+            .unwrap();
+        let rsltn = &camt_029.rsltn_of_invstgtn;
+        assert_eq!(
+            rsltn
+                .assgnmt
+                .assgnr
+                .value
+                .agt
+                .as_ref()
+                .unwrap()
+                .fin_instn_id
+                .bicfi,
+            Some(camt_029::BicfiDec2014Identifier {
+                value: "BANKDEFF".to_string()
+            })
+        );
+        assert_eq!(rsltn.sts.value.conf.as_ref().unwrap().value, "CNCL");
+        assert_eq!(
+            rsltn.cxl_dtls[0].tx_inf_and_sts[0].tx_cxl_sts,
+            Some(camt_029::CancellationIndividualStatus1Code::Accr)
+        );
+
+        let case = cases.case(&case_id).unwrap();
+        assert_eq!(case.status, CaseStatus::Resolved);
+        assert_eq!(case.history.len(), 4);
+        assert_eq!(cases.open_cases().count(), 0);
+
+        // A closed case accepts no further resolution
+        assert!(matches!(
+            cases.ingest(&pending),
+            Err(Error::IllegalTransition {
+                status: CaseStatus::Resolved,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_and_unsupported_messages() {
+        let mut cases = CaseManager::default();
+
+        let rejection = document(
+            "camt.031.001.06",
+            "RjctInvstgtn",
+            "<Case><Id>CASE-2</Id></Case>",
+        );
+        assert!(matches!(
+            cases.ingest(&rejection),
+            Err(Error::UnknownCase(case_id)) if case_id == "CASE-2"
+        ));
+
+        let unsupported = document("pacs.008.001.10", "FIToFICstmrCdtTrf", "");
+        assert!(matches!(
+            cases.ingest(&unsupported),
+            Err(Error::UnsupportedMessage(Some(_)))
+        ));
+    }
+
+    #[test]
+    fn test_transition() {
+        use CaseMessage::*;
+
+        assert_eq!(
+            transition(CaseStatus::Opened, NotificationOfCaseAssignment, None),
+            Some(CaseStatus::Assigned)
+        );
+        assert_eq!(
+            transition(
+                CaseStatus::Assigned,
+                ResolutionOfInvestigation,
+                Some("RJCR")
+            ),
+            Some(CaseStatus::Rejected)
+        );
+        assert_eq!(
+            transition(CaseStatus::Assigned, CancelCaseAssignment, None),
+            Some(CaseStatus::Cancelled)
+        );
+        assert_eq!(
+            transition(CaseStatus::Rejected, CaseStatusReportRequest, None),
+            Some(CaseStatus::Rejected)
+        );
+        assert_eq!(transition(CaseStatus::Cancelled, Duplicate, None), None);
+    }
+}
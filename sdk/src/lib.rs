@@ -141,13 +141,38 @@
 //! > - `tsmt`
 //! > - `tsrv`
 //!
+pub mod address;
+#[cfg(all(feature = "pain", feature = "pacs", feature = "camt"))]
+pub mod batch;
+#[cfg(all(feature = "pacs", feature = "camt"))]
+pub mod bulk;
+pub mod calendar;
+pub mod clearing_system;
+pub mod creditor_reference;
 #[cfg(feature = "crypto")]
 pub mod crypto;
 #[allow(non_camel_case_types)]
 pub mod documents;
+#[cfg(feature = "msg")]
+pub mod duplicate;
 pub mod external_codes;
+#[cfg(feature = "head")]
+pub mod header;
+#[cfg(feature = "camt")]
+pub mod investigation;
+#[cfg(all(feature = "pain", feature = "pacs"))]
+pub mod mapping;
 #[cfg(feature = "msg")]
 pub mod message;
+#[cfg(any(feature = "pacs", feature = "camt", feature = "sese", feature = "seev"))]
+pub mod mt;
+#[cfg(feature = "camt")]
+pub mod reconciliation;
+#[cfg(feature = "msg")]
+pub mod saa;
+#[cfg(feature = "camt")]
+pub mod statement;
+pub mod tracker;
 
 // Re-exports
 #[cfg(feature = "nvlp")]
@@ -0,0 +1,551 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Credit Transfer Mapping
+//!
+//! Maps a `pain.001.001.11` customer credit transfer initiation into a `pacs.008.001.10`
+//! FI to FI customer credit transfer. Each `PmtInf/CdtTrfTxInf` becomes an interbank
+//! `CdtTrfTxInf`, carrying over the debtor of its payment information block, and the
+//! settlement information is taken from the caller-supplied `RoutingContext`.
+//!
+//! ```rust
+//! use iso_20022_sdk::mapping::credit_transfer::{map_credit_transfer, RoutingContext};
+//!
+//! let ctx = RoutingContext {
+//!     sttlm_inf: SettlementInstruction11 {
+//!         sttlm_mtd: SettlementMethod1Code::Clrg,
+//!         ..Default::default()
+//!     },
+//!     ..Default::default()
+//! };
+//!
+//! let mapping = map_credit_transfer(&pain_001.cstmr_cdt_trf_initn, &ctx)?;
+//!
+//! // Rejected transactions and elements that have no place in the pacs.008
+//! println!("issues: {:?}", mapping.issues);
+//! ```
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Serialize};
+
+use iso_20022_pacs::pacs_008_001_10 as pacs;
+use iso_20022_pain::pain_001_001_11 as pain;
+
+use super::{convert, convert_option, convert_vec, sum, unmapped, Error, MappingIssue};
+
+/// Settlement and routing data assigned by the debtor agent to the interbank message.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RoutingContext {
+    /// Message identification `GrpHdr/MsgId`, defaults to a new UUID
+    pub msg_id: Option<String>,
+    /// Settlement information `GrpHdr/SttlmInf`
+    pub sttlm_inf: pacs::SettlementInstruction11,
+    /// Instructing agent `GrpHdr/InstgAgt`
+    pub instg_agt: Option<pacs::BranchAndFinancialInstitutionIdentification6>,
+    /// Instructed agent `GrpHdr/InstdAgt`
+    pub instd_agt: Option<pacs::BranchAndFinancialInstitutionIdentification6>,
+    /// Interbank settlement date, defaults to the requested execution date of the payment information
+    pub intr_bk_sttlm_dt: Option<NaiveDate>,
+}
+
+/// Result of mapping a customer credit transfer initiation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditTransferMapping<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    /// The interbank credit transfer, containing the transactions that could be mapped
+    pub fi_to_fi_cstmr_cdt_trf: pacs::FiToFiCustomerCreditTransferV10<A, B>,
+    /// Rejected transactions and information that could not be mapped
+    pub issues: Vec<MappingIssue>,
+}
+
+impl<A, B> CreditTransferMapping<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    /// Return the `pacs.008.001.10` document of the interbank credit transfer.
+    pub fn document(&self) -> pacs::Document<A, B> {
+        pacs::Document {
+            fi_to_fi_cstmr_cdt_trf: self.fi_to_fi_cstmr_cdt_trf.clone(),
+            xmlns: pacs::namespace(),
+        }
+    }
+}
+
+/// Map a customer credit transfer initiation into an interbank customer credit transfer.
+///
+/// Transactions are rejected, and reported in the mapping issues, when they
+/// - are cheque payments (`PmtMtd` `CHK`),
+/// - are expressed as an equivalent amount (`Amt/EqvtAmt`), or
+/// - have no creditor or creditor agent.
+pub fn map_credit_transfer<A, B>(
+    initn: &pain::CustomerCreditTransferInitiationV11<A, B>,
+    ctx: &RoutingContext,
+) -> Result<CreditTransferMapping<A, B>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+    B: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    let grp_hdr = &initn.grp_hdr;
+    let mut issues = Vec::new();
+
+    if !grp_hdr.authstn.is_empty() {
+        issues.push(unmapped(&grp_hdr.msg_id.value, "GrpHdr/Authstn"));
+    }
+    if grp_hdr.fwdg_agt.is_some() {
+        issues.push(unmapped(&grp_hdr.msg_id.value, "GrpHdr/FwdgAgt"));
+    }
+    if grp_hdr.initn_src.is_some() {
+        issues.push(unmapped(&grp_hdr.msg_id.value, "GrpHdr/InitnSrc"));
+    }
+
+    let initg_pty: pacs::PartyIdentification135 = convert(&grp_hdr.initg_pty)?;
+
+    let mut cdt_trf_tx_inf = Vec::new();
+
+    for pmt_inf in initn.pmt_inf.iter() {
+        let reference = &pmt_inf.pmt_inf_id.value;
+
+        if pmt_inf.reqd_advc_tp.is_some() {
+            issues.push(unmapped(reference, "PmtInf/ReqdAdvcTp"));
+        }
+        if pmt_inf.instr_for_dbtr_agt.is_some() {
+            issues.push(unmapped(reference, "PmtInf/InstrForDbtrAgt"));
+        }
+        if pmt_inf.chrgs_acct.is_some() {
+            issues.push(unmapped(reference, "PmtInf/ChrgsAcct"));
+        }
+        if pmt_inf.chrgs_acct_agt.is_some() {
+            issues.push(unmapped(reference, "PmtInf/ChrgsAcctAgt"));
+        }
+
+        for tx in pmt_inf.cdt_trf_tx_inf.iter() {
+            let reference = format!("{}/{}", reference, tx.pmt_id.end_to_end_id.value);
+
+            match map_transaction(pmt_inf, tx, &initg_pty, ctx, &reference, &mut issues)? {
+                Ok(tx) => cdt_trf_tx_inf.push(tx),
+                Err(reason) => issues.push(MappingIssue::Rejected { reference, reason }),
+            }
+        }
+    }
+
+    // The total interbank settlement amount requires the interbank settlement date of the
+    // group, which replaces the dates of the transactions, see the
+    // TotalInterbankSettlementAmountAndDateRule and GroupHeaderInterbankSettlementDateRule
+    let (ttl_intr_bk_sttlm_amt, intr_bk_sttlm_dt) = match total_intr_bk_sttlm_amt(&cdt_trf_tx_inf) {
+        Some((ttl_intr_bk_sttlm_amt, intr_bk_sttlm_dt)) => {
+            for tx in cdt_trf_tx_inf.iter_mut() {
+                tx.intr_bk_sttlm_dt = None;
+            }
+            (Some(ttl_intr_bk_sttlm_amt), Some(intr_bk_sttlm_dt))
+        }
+        None => (None, None),
+    };
+
+    let grp_hdr = pacs::GroupHeader96 {
+        msg_id: pacs::Max35Text {
+            value: ctx
+                .msg_id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()),
+        },
+        cre_dt_tm: pacs::IsoDateTime {
+            value: chrono::Utc::now(),
+        },
+        btch_bookg: None,
+        nb_of_txs: pacs::Max15NumericText {
+            value: cdt_trf_tx_inf.len().to_string(),
+        },
+        ctrl_sum: Some(pacs::DecimalNumber {
+            value: sum(cdt_trf_tx_inf
+                .iter()
+                .map(|tx| tx.intr_bk_sttlm_amt.value.value)),
+        }),
+        ttl_intr_bk_sttlm_amt,
+        intr_bk_sttlm_dt,
+        sttlm_inf: ctx.sttlm_inf.clone(),
+        pmt_tp_inf: None,
+        instg_agt: ctx.instg_agt.clone(),
+        instd_agt: ctx.instd_agt.clone(),
+    };
+
+    Ok(CreditTransferMapping {
+        fi_to_fi_cstmr_cdt_trf: pacs::FiToFiCustomerCreditTransferV10 {
+            grp_hdr,
+            cdt_trf_tx_inf,
+            splmtry_data: convert_vec(&initn.splmtry_data)?,
+        },
+        issues,
+    })
+}
+
+/// Map a transaction of a payment information block, or return the reason for rejecting it.
+fn map_transaction<A>(
+    pmt_inf: &pain::PaymentInstruction40<A>,
+    tx: &pain::CreditTransferTransaction54<A>,
+    initg_pty: &pacs::PartyIdentification135,
+    ctx: &RoutingContext,
+    reference: &str,
+    issues: &mut Vec<MappingIssue>,
+) -> Result<Result<pacs::CreditTransferTransaction50<A>, String>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    if pmt_inf.pmt_mtd == pain::PaymentMethod3Code::Chk {
+        return Ok(Err(
+            "cheque payments (PmtMtd CHK) are not interbank credit transfers".into(),
+        ));
+    }
+
+    let instd_amt = match (&tx.amt.value.instd_amt, &tx.amt.value.eqvt_amt) {
+        (Some(instd_amt), _) => instd_amt,
+        (None, Some(_)) => {
+            return Ok(Err(
+                "equivalent amount (Amt/EqvtAmt) requires a currency conversion".into(),
+            ))
+        }
+        (None, None) => return Ok(Err("missing instructed amount (Amt/InstdAmt)".into())),
+    };
+
+    let (cdtr, cdtr_agt) = match (&tx.cdtr, &tx.cdtr_agt) {
+        (Some(cdtr), Some(cdtr_agt)) => (cdtr, cdtr_agt),
+        _ => {
+            return Ok(Err(
+                "missing creditor (Cdtr) or creditor agent (CdtrAgt)".into()
+            ))
+        }
+    };
+
+    if tx.chq_instr.is_some() {
+        issues.push(unmapped(reference, "PmtInf/CdtTrfTxInf/ChqInstr"));
+    }
+    if tx.instr_for_dbtr_agt.is_some() {
+        issues.push(unmapped(reference, "PmtInf/CdtTrfTxInf/InstrForDbtrAgt"));
+    }
+    if let Some(xchg_rate_inf) = &tx.xchg_rate_inf {
+        if xchg_rate_inf.unit_ccy.is_some() {
+            issues.push(unmapped(
+                reference,
+                "PmtInf/CdtTrfTxInf/XchgRateInf/UnitCcy",
+            ));
+        }
+        if xchg_rate_inf.rate_tp.is_some() {
+            issues.push(unmapped(reference, "PmtInf/CdtTrfTxInf/XchgRateInf/RateTp"));
+        }
+        if xchg_rate_inf.ctrct_id.is_some() {
+            issues.push(unmapped(
+                reference,
+                "PmtInf/CdtTrfTxInf/XchgRateInf/CtrctId",
+            ));
+        }
+    }
+
+    let intr_bk_sttlm_dt = ctx.intr_bk_sttlm_dt.or_else(|| {
+        let reqd_exctn_dt = &pmt_inf.reqd_exctn_dt.value;
+        reqd_exctn_dt.dt.as_ref().map(|dt| dt.value).or_else(|| {
+            reqd_exctn_dt
+                .dt_tm
+                .as_ref()
+                .map(|dt_tm| dt_tm.value.date_naive())
+        })
+    });
+
+    Ok(Ok(pacs::CreditTransferTransaction50 {
+        pmt_id: pacs::PaymentIdentification13 {
+            instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            end_to_end_id: convert(&tx.pmt_id.end_to_end_id)?,
+            tx_id: None,
+            uetr: Some(pacs::UuiDv4Identifier {
+                value: tx
+                    .pmt_id
+                    .uetr
+                    .as_ref()
+                    .map(|uetr| uetr.value.clone())
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            }),
+            clr_sys_ref: None,
+        },
+        pmt_tp_inf: convert_option(&tx.pmt_tp_inf.as_ref().or(pmt_inf.pmt_tp_inf.as_ref()))?,
+        intr_bk_sttlm_amt: pacs::ActiveCurrencyAndAmount {
+            value: pacs::ActiveCurrencyAndAmountSimpleType {
+                value: instd_amt.value.value,
+            },
+            ccy: pacs::ActiveCurrencyCode {
+                value: instd_amt.ccy.value.clone(),
+            },
+        },
+        intr_bk_sttlm_dt: intr_bk_sttlm_dt.map(|value| pacs::IsoDate { value }),
+        poolg_adjstmnt_dt: convert_option(&pmt_inf.poolg_adjstmnt_dt)?,
+        instd_amt: Some(convert(instd_amt)?),
+        xchg_rate: convert_option(
+            &tx.xchg_rate_inf
+                .as_ref()
+                .and_then(|xchg_rate_inf| xchg_rate_inf.xchg_rate.as_ref()),
+        )?,
+        chrg_br: charge_bearer(tx.chrg_br.as_ref().or(pmt_inf.chrg_br.as_ref())),
+        mndt_rltd_inf: convert_option(&tx.mndt_rltd_inf)?,
+        intrmy_agt_1: convert_option(&tx.intrmy_agt_1)?,
+        intrmy_agt_1_acct: convert_option(&tx.intrmy_agt_1_acct)?,
+        intrmy_agt_2: convert_option(&tx.intrmy_agt_2)?,
+        intrmy_agt_2_acct: convert_option(&tx.intrmy_agt_2_acct)?,
+        intrmy_agt_3: convert_option(&tx.intrmy_agt_3)?,
+        intrmy_agt_3_acct: convert_option(&tx.intrmy_agt_3_acct)?,
+        ultmt_dbtr: convert_option(&tx.ultmt_dbtr.as_ref().or(pmt_inf.ultmt_dbtr.as_ref()))?,
+        initg_pty: Some(initg_pty.clone()),
+        dbtr: convert(&pmt_inf.dbtr)?,
+        dbtr_acct: Some(convert(&pmt_inf.dbtr_acct)?),
+        dbtr_agt: convert(&pmt_inf.dbtr_agt)?,
+        dbtr_agt_acct: convert_option(&pmt_inf.dbtr_agt_acct)?,
+        cdtr_agt: convert(cdtr_agt)?,
+        cdtr_agt_acct: convert_option(&tx.cdtr_agt_acct)?,
+        cdtr: convert(cdtr)?,
+        cdtr_acct: convert_option(&tx.cdtr_acct)?,
+        ultmt_cdtr: convert_option(&tx.ultmt_cdtr)?,
+        instr_for_cdtr_agt: convert_vec(&tx.instr_for_cdtr_agt)?,
+        purp: convert_option(&tx.purp)?,
+        rgltry_rptg: convert_vec(&tx.rgltry_rptg)?,
+        tax: convert_option(&tx.tax)?,
+        rltd_rmt_inf: convert_vec(&tx.rltd_rmt_inf)?,
+        rmt_inf: convert_option(&tx.rmt_inf)?,
+        splmtry_data: convert_vec(&tx.splmtry_data)?,
+        ..Default::default()
+    }))
+}
+
+/// Return the charge bearer of the transaction, defaulting to shared charges `SHAR`.
+fn charge_bearer(chrg_br: Option<&pain::ChargeBearerType1Code>) -> pacs::ChargeBearerType1Code {
+    match chrg_br {
+        Some(pain::ChargeBearerType1Code::Debt) => pacs::ChargeBearerType1Code::Debt,
+        Some(pain::ChargeBearerType1Code::Cred) => pacs::ChargeBearerType1Code::Cred,
+        Some(pain::ChargeBearerType1Code::Slev) => pacs::ChargeBearerType1Code::Slev,
+        _ => pacs::ChargeBearerType1Code::Shar,
+    }
+}
+
+/// Return the total interbank settlement amount and the interbank settlement date, if all
+/// transactions settle in the same currency on the same date.
+fn total_intr_bk_sttlm_amt<A>(
+    cdt_trf_tx_inf: &[pacs::CreditTransferTransaction50<A>],
+) -> Option<(pacs::ActiveCurrencyAndAmount, pacs::IsoDate)>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let first = cdt_trf_tx_inf.first()?;
+    let ccy = &first.intr_bk_sttlm_amt.ccy;
+    let intr_bk_sttlm_dt = first.intr_bk_sttlm_dt.as_ref()?;
+
+    cdt_trf_tx_inf
+        .iter()
+        .all(|tx| {
+            &tx.intr_bk_sttlm_amt.ccy == ccy
+                && tx.intr_bk_sttlm_dt.as_ref() == Some(intr_bk_sttlm_dt)
+        })
+        .then(|| {
+            (
+                pacs::ActiveCurrencyAndAmount {
+                    value: pacs::ActiveCurrencyAndAmountSimpleType {
+                        value: sum(cdt_trf_tx_inf
+                            .iter()
+                            .map(|tx| tx.intr_bk_sttlm_amt.value.value)),
+                    },
+                    ccy: ccy.clone(),
+                },
+                intr_bk_sttlm_dt.clone(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    fn party(nm: &str) -> pain::PartyIdentification135 {
+        pain::PartyIdentification135 {
+            nm: Some(pain::Max140Text {
+                value: nm.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn agent(bicfi: &str) -> pain::BranchAndFinancialInstitutionIdentification6 {
+        pain::BranchAndFinancialInstitutionIdentification6 {
+            fin_instn_id: pain::FinancialInstitutionIdentification18 {
+                bicfi: Some(pain::BicfiDec2014Identifier {
+                    value: bicfi.to_string(),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn transaction(
+        end_to_end_id: &str,
+        amount: f64,
+        cdtr: Option<&str>,
+    ) -> pain::CreditTransferTransaction54<Dmkr> {
+        pain::CreditTransferTransaction54 {
+            pmt_id: pain::PaymentIdentification6 {
+                end_to_end_id: pain::Max35Text {
+                    value: end_to_end_id.to_string(),
+                },
+                ..Default::default()
+            },
+            amt: pain::AmountType4Choice {
+                value: pain::AmountType4ChoiceEnum {
+                    instd_amt: Some(pain::ActiveOrHistoricCurrencyAndAmount {
+                        value: pain::ActiveOrHistoricCurrencyAndAmountSimpleType { value: amount },
+                        ccy: pain::ActiveOrHistoricCurrencyCode {
+                            value: "EUR".to_string(),
+                        },
+                    }),
+                    ..Default::default()
+                },
+            },
+            cdtr_agt: cdtr.map(|_| agent("BBBBDEFFXXX")),
+            cdtr: cdtr.map(party),
+            rmt_inf: Some(pain::RemittanceInformation21 {
+                ustrd: vec![pain::Max140Text {
+                    value: "INVOICE 42".to_string(),
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_map_credit_transfer() -> Result<(), Error> {
+        let initn = pain::CustomerCreditTransferInitiationV11::<Dmkr, Dmkr> {
+            grp_hdr: pain::GroupHeader95 {
+                msg_id: pain::Max35Text {
+                    value: "PAIN-1".to_string(),
+                },
+                initg_pty: party("Initiating Corp"),
+                fwdg_agt: Some(agent("CCCCFRPPXXX")),
+                ..Default::default()
+            },
+            pmt_inf: vec![pain::PaymentInstruction40 {
+                pmt_inf_id: pain::Max35Text {
+                    value: "PMT-1".to_string(),
+                },
+                pmt_mtd: pain::PaymentMethod3Code::Trf,
+                reqd_exctn_dt: pain::DateAndDateTime2Choice {
+                    value: pain::DateAndDateTime2ChoiceEnum {
+                        dt: Some(pain::IsoDate {
+                            value: NaiveDate::from_ymd_opt(2023, 5, 2).unwrap(),
+                        }),
+                        ..Default::default()
+                    },
+                },
+                dbtr: party("Debtor Corp"),
+                dbtr_agt: agent("AAAAGB2LXXX"),
+                chrg_br: Some(pain::ChargeBearerType1Code::Slev),
+                cdt_trf_tx_inf: vec![
+                    transaction("E2E-1", 100.5, Some("Creditor One")),
+                    transaction("E2E-2", 200.0, Some("Creditor Two")),
+                    transaction("E2E-3", 50.0, None),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let ctx = RoutingContext {
+            msg_id: Some("PACS-1".to_string()),
+            sttlm_inf: pacs::SettlementInstruction11 {
+                sttlm_mtd: pacs::SettlementMethod1Code::Clrg,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mapping = map_credit_transfer(&initn, &ctx)?;
+        let cdt_trf = &mapping.fi_to_fi_cstmr_cdt_trf;
+
+        assert_eq!(cdt_trf.grp_hdr.msg_id.value, "PACS-1");
+        assert_eq!(cdt_trf.grp_hdr.nb_of_txs.value, "2");
+        assert_eq!(cdt_trf.grp_hdr.ctrl_sum.as_ref().unwrap().value, 300.5);
+        assert_eq!(
+            cdt_trf
+                .grp_hdr
+                .ttl_intr_bk_sttlm_amt
+                .as_ref()
+                .unwrap()
+                .ccy
+                .value,
+            "EUR"
+        );
+
+        // The shared interbank settlement date is set once, in the group header
+        assert_eq!(
+            cdt_trf.grp_hdr.intr_bk_sttlm_dt.as_ref().unwrap().value,
+            NaiveDate::from_ymd_opt(2023, 5, 2).unwrap()
+        );
+
+        let tx = &cdt_trf.cdt_trf_tx_inf[0];
+        assert_eq!(tx.pmt_id.end_to_end_id.value, "E2E-1");
+        assert!(tx.pmt_id.uetr.is_some());
+        assert_eq!(tx.intr_bk_sttlm_amt.value.value, 100.5);
+        assert!(tx.intr_bk_sttlm_dt.is_none());
+        assert_eq!(tx.chrg_br, pacs::ChargeBearerType1Code::Slev);
+        assert_eq!(tx.dbtr.nm.as_ref().unwrap().value, "Debtor Corp");
+        assert_eq!(tx.cdtr.nm.as_ref().unwrap().value, "Creditor One");
+        assert_eq!(
+            tx.initg_pty.as_ref().unwrap().nm.as_ref().unwrap().value,
+            "Initiating Corp"
+        );
+        assert_eq!(
+            tx.rmt_inf.as_ref().unwrap().ustrd[0].value,
+            "INVOICE 42".to_string()
+        );
+
+        assert_eq!(
+            mapping.issues,
+            vec![
+                unmapped("PAIN-1", "GrpHdr/FwdgAgt"),
+                MappingIssue::Rejected {
+                    reference: "PMT-1/E2E-3".to_string(),
+                    reason: "missing creditor (Cdtr) or creditor agent (CdtrAgt)".to_string(),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+}
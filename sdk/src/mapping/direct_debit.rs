@@ -0,0 +1,567 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Direct Debit Mapping
+//!
+//! Maps a `pain.008.001.10` customer direct debit initiation into `pacs.003.001.09`
+//! FI to FI customer direct debit batches. Transactions are bulked by debtor agent,
+//! sequence type (`FRST`, `RCUR`, `OOFF`, `FNAL`) and requested collection date, and
+//! every batch carries its own control sum.
+//!
+//! ```rust
+//! use iso_20022_sdk::mapping::direct_debit::{map_direct_debit, CollectionContext};
+//!
+//! let ctx = CollectionContext {
+//!     sttlm_inf: SettlementInstruction14 {
+//!         sttlm_mtd: SettlementMethod2Code::Clrg,
+//!         ..Default::default()
+//!     },
+//!     ..Default::default()
+//! };
+//!
+//! let mapping = map_direct_debit(&pain_008.cstmr_drct_dbt_initn, &ctx)?;
+//!
+//! for batch in mapping.batches.iter() {
+//!     // Send one pacs.003 per debtor agent, sequence type and collection date
+//! }
+//!
+//! // Transactions that cannot be collected, e.g. without mandate signature date
+//! println!("issues: {:?}", mapping.issues);
+//! ```
+use serde::{de::DeserializeOwned, Serialize};
+
+use iso_20022_pacs::pacs_003_001_09 as pacs;
+use iso_20022_pain::pain_008_001_10 as pain;
+
+use super::{convert, convert_option, convert_vec, sum, unmapped, Error, MappingIssue};
+
+/// Settlement and routing data assigned by the creditor agent to the interbank batches.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CollectionContext {
+    /// Settlement information `GrpHdr/SttlmInf` of every batch
+    pub sttlm_inf: pacs::SettlementInstruction14,
+    /// Instructing agent `GrpHdr/InstgAgt` of every batch
+    pub instg_agt: Option<pacs::BranchAndFinancialInstitutionIdentification6>,
+}
+
+/// Result of mapping a customer direct debit initiation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectDebitMapping<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    /// Interbank direct debits, one per debtor agent, sequence type and collection date
+    pub batches: Vec<pacs::FiToFiCustomerDirectDebitV09<A, B>>,
+    /// Uncollectable transactions and information that could not be mapped
+    pub issues: Vec<MappingIssue>,
+}
+
+impl<A, B> DirectDebitMapping<A, B>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    /// Return the `pacs.003.001.09` documents of the interbank direct debit batches.
+    pub fn documents(&self) -> Vec<pacs::Document<A, B>> {
+        self.batches
+            .iter()
+            .map(|batch| pacs::Document {
+                fi_to_fi_cstmr_drct_dbt: batch.clone(),
+                xmlns: pacs::namespace(),
+            })
+            .collect()
+    }
+}
+
+/// Map a customer direct debit initiation into interbank direct debit batches.
+///
+/// Transactions are not collected, and reported in the mapping issues, when
+/// - the payment method is not `DD`,
+/// - the sequence type is missing,
+/// - the mandate identification or mandate signature date is missing,
+/// - the mandate is signed after the requested collection date,
+/// - the mandate is amended (`AmdmntInd`) without amendment details, or
+/// - the creditor scheme identification is missing.
+pub fn map_direct_debit<A, B>(
+    initn: &pain::CustomerDirectDebitInitiationV10<A, B>,
+    ctx: &CollectionContext,
+) -> Result<DirectDebitMapping<A, B>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+    B: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    let grp_hdr = &initn.grp_hdr;
+    let mut issues = Vec::new();
+
+    if grp_hdr.fwdg_agt.is_some() {
+        issues.push(unmapped(&grp_hdr.msg_id.value, "GrpHdr/FwdgAgt"));
+    }
+
+    let initg_pty: pacs::PartyIdentification135 = convert(&grp_hdr.initg_pty)?;
+    let splmtry_data: Vec<pacs::SupplementaryData1<B>> = convert_vec(&initn.splmtry_data)?;
+
+    let mut batches: Vec<pacs::FiToFiCustomerDirectDebitV09<A, B>> = Vec::new();
+
+    for pmt_inf in initn.pmt_inf.iter() {
+        let reference = &pmt_inf.pmt_inf_id.value;
+
+        if pmt_inf.reqd_advc_tp.is_some() {
+            issues.push(unmapped(reference, "PmtInf/ReqdAdvcTp"));
+        }
+        if pmt_inf.chrgs_acct.is_some() {
+            issues.push(unmapped(reference, "PmtInf/ChrgsAcct"));
+        }
+        if pmt_inf.chrgs_acct_agt.is_some() {
+            issues.push(unmapped(reference, "PmtInf/ChrgsAcctAgt"));
+        }
+
+        for tx in pmt_inf.drct_dbt_tx_inf.iter() {
+            let reference = format!("{}/{}", reference, tx.pmt_id.end_to_end_id.value);
+
+            let tx = match map_transaction(pmt_inf, tx, &initg_pty, &reference, &mut issues)? {
+                Ok(tx) => tx,
+                Err(reason) => {
+                    issues.push(MappingIssue::Rejected { reference, reason });
+                    continue;
+                }
+            };
+
+            match batches.iter_mut().find(|batch| {
+                let first = &batch.drct_dbt_tx_inf[0];
+                first.dbtr_agt == tx.dbtr_agt
+                    && sequence_type(first) == sequence_type(&tx)
+                    && first.reqd_colltn_dt == tx.reqd_colltn_dt
+            }) {
+                Some(batch) => batch.drct_dbt_tx_inf.push(tx),
+                None => batches.push(pacs::FiToFiCustomerDirectDebitV09 {
+                    grp_hdr: pacs::GroupHeader98 {
+                        authstn: convert_vec(&grp_hdr.authstn)?,
+                        intr_bk_sttlm_dt: tx.reqd_colltn_dt.clone(),
+                        sttlm_inf: ctx.sttlm_inf.clone(),
+                        instg_agt: ctx.instg_agt.clone(),
+                        instd_agt: Some(tx.dbtr_agt.clone()),
+                        ..Default::default()
+                    },
+                    drct_dbt_tx_inf: vec![tx],
+                    splmtry_data: splmtry_data.clone(),
+                }),
+            }
+        }
+    }
+
+    for batch in batches.iter_mut() {
+        let drct_dbt_tx_inf = &batch.drct_dbt_tx_inf;
+        let ccy = &drct_dbt_tx_inf[0].intr_bk_sttlm_amt.ccy;
+        let ctrl_sum = sum(drct_dbt_tx_inf
+            .iter()
+            .map(|tx| tx.intr_bk_sttlm_amt.value.value));
+
+        batch.grp_hdr.msg_id = pacs::Max35Text {
+            value: uuid::Uuid::new_v4().simple().to_string(),
+        };
+        batch.grp_hdr.cre_dt_tm = pacs::IsoDateTime {
+            value: chrono::Utc::now(),
+        };
+        batch.grp_hdr.nb_of_txs = pacs::Max15NumericText {
+            value: drct_dbt_tx_inf.len().to_string(),
+        };
+        batch.grp_hdr.ctrl_sum = Some(pacs::DecimalNumber { value: ctrl_sum });
+        batch.grp_hdr.ttl_intr_bk_sttlm_amt = drct_dbt_tx_inf
+            .iter()
+            .all(|tx| &tx.intr_bk_sttlm_amt.ccy == ccy)
+            .then(|| pacs::ActiveCurrencyAndAmount {
+                value: pacs::ActiveCurrencyAndAmountSimpleType { value: ctrl_sum },
+                ccy: ccy.clone(),
+            });
+    }
+
+    Ok(DirectDebitMapping { batches, issues })
+}
+
+/// Map a transaction of a payment information block, or return the reason it cannot be collected.
+fn map_transaction<A>(
+    pmt_inf: &pain::PaymentInstruction39<A>,
+    tx: &pain::DirectDebitTransactionInformation28<A>,
+    initg_pty: &pacs::PartyIdentification135,
+    reference: &str,
+    issues: &mut Vec<MappingIssue>,
+) -> Result<Result<pacs::DirectDebitTransactionInformation29<A>, String>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    if pmt_inf.pmt_mtd != pain::PaymentMethod2Code::Dd {
+        return Ok(Err("payment method (PmtMtd) is not DD".into()));
+    }
+
+    let pmt_tp_inf = tx.pmt_tp_inf.as_ref().or(pmt_inf.pmt_tp_inf.as_ref());
+
+    if pmt_tp_inf
+        .and_then(|pmt_tp_inf| pmt_tp_inf.seq_tp.as_ref())
+        .is_none()
+    {
+        return Ok(Err("missing sequence type (PmtTpInf/SeqTp)".into()));
+    }
+
+    let mndt_rltd_inf = match tx
+        .drct_dbt_tx
+        .as_ref()
+        .and_then(|drct_dbt_tx| drct_dbt_tx.mndt_rltd_inf.as_ref())
+    {
+        Some(mndt_rltd_inf) => mndt_rltd_inf,
+        None => {
+            return Ok(Err(
+                "missing mandate related information (MndtRltdInf)".into()
+            ))
+        }
+    };
+
+    if mndt_rltd_inf.mndt_id.is_none() {
+        return Ok(Err(
+            "missing mandate identification (MndtRltdInf/MndtId)".into()
+        ));
+    }
+
+    match &mndt_rltd_inf.dt_of_sgntr {
+        None => {
+            return Ok(Err(
+                "missing mandate signature date (MndtRltdInf/DtOfSgntr)".into(),
+            ))
+        }
+        Some(dt_of_sgntr) if dt_of_sgntr.value > pmt_inf.reqd_colltn_dt.value => {
+            return Ok(Err(
+                "mandate signature date (MndtRltdInf/DtOfSgntr) is after the collection date"
+                    .into(),
+            ))
+        }
+        _ => (),
+    }
+
+    let amdmnt_ind = mndt_rltd_inf
+        .amdmnt_ind
+        .as_ref()
+        .map(|amdmnt_ind| amdmnt_ind.value)
+        .unwrap_or(false);
+
+    if amdmnt_ind && mndt_rltd_inf.amdmnt_inf_dtls.is_none() {
+        return Ok(Err(
+            "amended mandate (MndtRltdInf/AmdmntInd) without amendment details (AmdmntInfDtls)"
+                .into(),
+        ));
+    }
+
+    let cdtr_schme_id = match tx
+        .drct_dbt_tx
+        .as_ref()
+        .and_then(|drct_dbt_tx| drct_dbt_tx.cdtr_schme_id.as_ref())
+        .or(pmt_inf.cdtr_schme_id.as_ref())
+    {
+        Some(cdtr_schme_id) => cdtr_schme_id,
+        None => {
+            return Ok(Err(
+                "missing creditor scheme identification (CdtrSchmeId)".into()
+            ))
+        }
+    };
+
+    if tx.instr_for_cdtr_agt.is_some() {
+        issues.push(unmapped(reference, "PmtInf/DrctDbtTxInf/InstrForCdtrAgt"));
+    }
+    if tx.tax.is_some() {
+        issues.push(unmapped(reference, "PmtInf/DrctDbtTxInf/Tax"));
+    }
+
+    let mut drct_dbt_tx: pacs::DirectDebitTransaction11 =
+        convert_option(&tx.drct_dbt_tx)?.unwrap_or_default();
+    drct_dbt_tx.cdtr_schme_id = Some(convert(cdtr_schme_id)?);
+
+    Ok(Ok(pacs::DirectDebitTransactionInformation29 {
+        pmt_id: pacs::PaymentIdentification13 {
+            instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            end_to_end_id: convert(&tx.pmt_id.end_to_end_id)?,
+            tx_id: None,
+            uetr: Some(pacs::UuiDv4Identifier {
+                value: tx
+                    .pmt_id
+                    .uetr
+                    .as_ref()
+                    .map(|uetr| uetr.value.clone())
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            }),
+            clr_sys_ref: None,
+        },
+        pmt_tp_inf: convert_option(&pmt_tp_inf)?,
+        intr_bk_sttlm_amt: pacs::ActiveCurrencyAndAmount {
+            value: pacs::ActiveCurrencyAndAmountSimpleType {
+                value: tx.instd_amt.value.value,
+            },
+            ccy: pacs::ActiveCurrencyCode {
+                value: tx.instd_amt.ccy.value.clone(),
+            },
+        },
+        // The batch carries the interbank settlement date in its group header, see the
+        // GroupHeaderInterbankSettlementDateRule
+        intr_bk_sttlm_dt: None,
+        instd_amt: Some(convert(&tx.instd_amt)?),
+        chrg_br: charge_bearer(tx.chrg_br.as_ref().or(pmt_inf.chrg_br.as_ref())),
+        reqd_colltn_dt: Some(convert(&pmt_inf.reqd_colltn_dt)?),
+        drct_dbt_tx: Some(drct_dbt_tx),
+        cdtr: convert(&pmt_inf.cdtr)?,
+        cdtr_acct: Some(convert(&pmt_inf.cdtr_acct)?),
+        cdtr_agt: convert(&pmt_inf.cdtr_agt)?,
+        cdtr_agt_acct: convert_option(&pmt_inf.cdtr_agt_acct)?,
+        ultmt_cdtr: convert_option(&tx.ultmt_cdtr.as_ref().or(pmt_inf.ultmt_cdtr.as_ref()))?,
+        initg_pty: Some(initg_pty.clone()),
+        dbtr: convert(&tx.dbtr)?,
+        dbtr_acct: convert(&tx.dbtr_acct)?,
+        dbtr_agt: convert(&tx.dbtr_agt)?,
+        dbtr_agt_acct: convert_option(&tx.dbtr_agt_acct)?,
+        ultmt_dbtr: convert_option(&tx.ultmt_dbtr)?,
+        purp: convert_option(&tx.purp)?,
+        rgltry_rptg: convert_vec(&tx.rgltry_rptg)?,
+        rltd_rmt_inf: convert_vec(&tx.rltd_rmt_inf)?,
+        rmt_inf: convert_option(&tx.rmt_inf)?,
+        splmtry_data: convert_vec(&tx.splmtry_data)?,
+        ..Default::default()
+    }))
+}
+
+/// Return the sequence type of a mapped transaction.
+fn sequence_type<A>(
+    tx: &pacs::DirectDebitTransactionInformation29<A>,
+) -> Option<&pacs::SequenceType3Code>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    tx.pmt_tp_inf
+        .as_ref()
+        .and_then(|pmt_tp_inf| pmt_tp_inf.seq_tp.as_ref())
+}
+
+/// Return the charge bearer of the transaction, defaulting to service level charges `SLEV`.
+fn charge_bearer(chrg_br: Option<&pain::ChargeBearerType1Code>) -> pacs::ChargeBearerType1Code {
+    match chrg_br {
+        Some(pain::ChargeBearerType1Code::Debt) => pacs::ChargeBearerType1Code::Debt,
+        Some(pain::ChargeBearerType1Code::Cred) => pacs::ChargeBearerType1Code::Cred,
+        Some(pain::ChargeBearerType1Code::Shar) => pacs::ChargeBearerType1Code::Shar,
+        _ => pacs::ChargeBearerType1Code::Slev,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+    use chrono::NaiveDate;
+
+    fn party(nm: &str) -> pain::PartyIdentification135 {
+        pain::PartyIdentification135 {
+            nm: Some(pain::Max140Text {
+                value: nm.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn agent(bicfi: &str) -> pain::BranchAndFinancialInstitutionIdentification6 {
+        pain::BranchAndFinancialInstitutionIdentification6 {
+            fin_instn_id: pain::FinancialInstitutionIdentification18 {
+                bicfi: Some(pain::BicfiDec2014Identifier {
+                    value: bicfi.to_string(),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn date(day: u32) -> pain::IsoDate {
+        pain::IsoDate {
+            value: NaiveDate::from_ymd_opt(2023, 5, day).unwrap(),
+        }
+    }
+
+    fn transaction(
+        end_to_end_id: &str,
+        amount: f64,
+        dbtr_agt: &str,
+        dt_of_sgntr: Option<pain::IsoDate>,
+    ) -> pain::DirectDebitTransactionInformation28<Dmkr> {
+        pain::DirectDebitTransactionInformation28 {
+            pmt_id: pain::PaymentIdentification6 {
+                end_to_end_id: pain::Max35Text {
+                    value: end_to_end_id.to_string(),
+                },
+                ..Default::default()
+            },
+            instd_amt: pain::ActiveOrHistoricCurrencyAndAmount {
+                value: pain::ActiveOrHistoricCurrencyAndAmountSimpleType { value: amount },
+                ccy: pain::ActiveOrHistoricCurrencyCode {
+                    value: "EUR".to_string(),
+                },
+            },
+            drct_dbt_tx: Some(pain::DirectDebitTransaction11 {
+                mndt_rltd_inf: Some(pain::MandateRelatedInformation15 {
+                    mndt_id: Some(pain::Max35Text {
+                        value: format!("MNDT-{}", end_to_end_id),
+                    }),
+                    dt_of_sgntr,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            dbtr_agt: agent(dbtr_agt),
+            dbtr: party("Debtor"),
+            ..Default::default()
+        }
+    }
+
+    fn payment_information(
+        pmt_inf_id: &str,
+        seq_tp: pain::SequenceType3Code,
+        drct_dbt_tx_inf: Vec<pain::DirectDebitTransactionInformation28<Dmkr>>,
+    ) -> pain::PaymentInstruction39<Dmkr> {
+        pain::PaymentInstruction39 {
+            pmt_inf_id: pain::Max35Text {
+                value: pmt_inf_id.to_string(),
+            },
+            pmt_mtd: pain::PaymentMethod2Code::Dd,
+            pmt_tp_inf: Some(pain::PaymentTypeInformation29 {
+                seq_tp: Some(seq_tp),
+                ..Default::default()
+            }),
+            reqd_colltn_dt: date(10),
+            cdtr: party("Creditor Corp"),
+            cdtr_agt: agent("AAAADEFFXXX"),
+            cdtr_schme_id: Some(party("DE98ZZZ09999999999")),
+            drct_dbt_tx_inf,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_map_direct_debit() -> Result<(), Error> {
+        let initn = pain::CustomerDirectDebitInitiationV10::<Dmkr, Dmkr> {
+            grp_hdr: pain::GroupHeader83 {
+                msg_id: pain::Max35Text {
+                    value: "PAIN-8".to_string(),
+                },
+                initg_pty: party("Creditor Corp"),
+                ..Default::default()
+            },
+            pmt_inf: vec![
+                payment_information(
+                    "PMT-FRST",
+                    pain::SequenceType3Code::Frst,
+                    vec![
+                        transaction("E2E-1", 10.0, "BBBBFRPPXXX", Some(date(1))),
+                        transaction("E2E-2", 20.0, "CCCCNL2AXXX", Some(date(1))),
+                        transaction("E2E-3", 30.0, "BBBBFRPPXXX", None),
+                    ],
+                ),
+                payment_information(
+                    "PMT-RCUR",
+                    pain::SequenceType3Code::Rcur,
+                    vec![
+                        transaction("E2E-4", 40.0, "BBBBFRPPXXX", Some(date(1))),
+                        transaction("E2E-5", 50.5, "BBBBFRPPXXX", Some(date(2))),
+                    ],
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let mapping = map_direct_debit(&initn, &CollectionContext::default())?;
+
+        // FRST for two debtor agents, and RCUR for one
+        assert_eq!(mapping.batches.len(), 3);
+
+        let rcur = &mapping.batches[2];
+        assert_eq!(rcur.grp_hdr.nb_of_txs.value, "2");
+        assert_eq!(rcur.grp_hdr.ctrl_sum.as_ref().unwrap().value, 90.5);
+        assert_eq!(
+            rcur.grp_hdr.intr_bk_sttlm_dt.as_ref().unwrap().value,
+            date(10).value
+        );
+        assert_eq!(
+            rcur.grp_hdr
+                .instd_agt
+                .as_ref()
+                .unwrap()
+                .fin_instn_id
+                .bicfi
+                .as_ref()
+                .unwrap()
+                .value,
+            "BBBBFRPPXXX"
+        );
+
+        let tx = &rcur.drct_dbt_tx_inf[0];
+        assert!(tx.intr_bk_sttlm_dt.is_none());
+        let drct_dbt_tx = tx.drct_dbt_tx.as_ref().unwrap();
+        assert_eq!(
+            drct_dbt_tx
+                .mndt_rltd_inf
+                .as_ref()
+                .unwrap()
+                .mndt_id
+                .as_ref()
+                .unwrap()
+                .value,
+            "MNDT-E2E-4"
+        );
+        assert_eq!(
+            drct_dbt_tx
+                .cdtr_schme_id
+                .as_ref()
+                .unwrap()
+                .nm
+                .as_ref()
+                .unwrap()
+                .value,
+            "DE98ZZZ09999999999"
+        );
+        assert_eq!(tx.chrg_br, pacs::ChargeBearerType1Code::Slev);
+
+        assert_eq!(
+            mapping.issues,
+            vec![MappingIssue::Rejected {
+                reference: "PMT-FRST/E2E-3".to_string(),
+                reason: "missing mandate signature date (MndtRltdInf/DtOfSgntr)".to_string(),
+            }]
+        );
+
+        Ok(())
+    }
+}
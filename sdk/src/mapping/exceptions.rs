@@ -0,0 +1,507 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Payment Exceptions
+//!
+//! Builds the messages that unwind an original transaction, filling the `Orgnl*`
+//! references from an `OriginalTransaction`:
+//!
+//! - `payment_return`: `pacs.004.001.11` payment return, e.g. of a received `pacs.008`
+//! - `payment_reversal`: `pacs.007.001.11` payment reversal, e.g. of a sent `pacs.003`
+//! - `cancellation_request`: `camt.056.001.10` FI to FI payment cancellation request,
+//!   requires the `camt` feature
+//!
+//! ```rust
+//! use iso_20022_sdk::external_codes::ExternalReturnReason1Code;
+//! use iso_20022_sdk::mapping::exceptions::payment_return;
+//! use iso_20022_sdk::mapping::original::OriginalTransaction;
+//!
+//! let cdt_trf = &pacs_008.fi_to_fi_cstmr_cdt_trf;
+//! let orgnl_tx = OriginalTransaction::from_pacs_008(&cdt_trf.grp_hdr, &cdt_trf.cdt_trf_tx_inf[0])?;
+//!
+//! // Return the transaction today, deducting the charges of the returning agent
+//! let pacs_004 = payment_return(
+//!     &orgnl_tx,
+//!     &ExternalReturnReason1Code::Ac04,
+//!     charges,
+//!     chrono::Utc::now().date_naive(),
+//! )?;
+//! ```
+use chrono::NaiveDate;
+use serde::Serialize;
+
+#[cfg(feature = "camt")]
+use iso_20022_camt::camt_056_001_10 as camt_056;
+use iso_20022_pacs::pacs_002_001_12 as sts;
+use iso_20022_pacs::pacs_004_001_11 as pacs_004;
+use iso_20022_pacs::pacs_007_001_11 as pacs_007;
+
+use super::original::OriginalTransaction;
+use super::{convert, convert_option, sum, Code, Error};
+#[cfg(feature = "camt")]
+use crate::external_codes::ExternalCancellationReason1Code;
+use crate::external_codes::{ExternalReturnReason1Code, ExternalReversalReason1Code};
+
+/// Build the `pacs.004.001.11` payment return of an original transaction.
+///
+/// The returned interbank settlement amount is the original amount less the charges
+/// in the same currency, settled on the interbank settlement date of the group header.
+/// Returns `Error::ChargesExceedAmount` if the charges exceed the original amount. The
+/// return is sent back to the instructing agent of the original transaction.
+pub fn payment_return<A, B>(
+    orgnl_tx: &OriginalTransaction,
+    reason: &ExternalReturnReason1Code,
+    chrgs_inf: Vec<pacs_004::Charges7>,
+    intr_bk_sttlm_dt: NaiveDate,
+) -> Result<pacs_004::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let amount = orgnl_tx.amount().ok_or(Error::MissingAmount)?;
+    let returned = charges_deducted(
+        amount,
+        chrgs_inf
+            .iter()
+            .map(|chrgs| (&chrgs.amt.ccy.value, chrgs.amt.value.value)),
+    )?;
+
+    let rtrd_intr_bk_sttlm_amt = pacs_004::ActiveCurrencyAndAmount {
+        value: pacs_004::ActiveCurrencyAndAmountSimpleType { value: returned },
+        ccy: pacs_004::ActiveCurrencyCode {
+            value: amount.ccy.value.clone(),
+        },
+    };
+
+    Ok(pacs_004::Document {
+        pmt_rtr: pacs_004::PaymentReturnV11 {
+            grp_hdr: pacs_004::GroupHeader99 {
+                msg_id: pacs_004::Max35Text { value: new_id() },
+                cre_dt_tm: pacs_004::IsoDateTime {
+                    value: chrono::Utc::now(),
+                },
+                nb_of_txs: pacs_004::Max15NumericText {
+                    value: "1".to_string(),
+                },
+                ttl_rtrd_intr_bk_sttlm_amt: Some(rtrd_intr_bk_sttlm_amt.clone()),
+                intr_bk_sttlm_dt: Some(pacs_004::IsoDate {
+                    value: intr_bk_sttlm_dt,
+                }),
+                sttlm_inf: convert_option(&orgnl_tx.orgnl_tx_ref.sttlm_inf)?.unwrap_or_default(),
+                instg_agt: convert_option(&orgnl_tx.instd_agt)?,
+                instd_agt: convert_option(&orgnl_tx.instg_agt)?,
+                ..Default::default()
+            },
+            orgnl_grp_inf: None,
+            tx_inf: vec![pacs_004::PaymentTransaction133 {
+                rtr_id: Some(pacs_004::Max35Text { value: new_id() }),
+                orgnl_grp_inf: Some(convert(&orgnl_tx.orgnl_grp_inf)?),
+                orgnl_instr_id: convert_option(&orgnl_tx.orgnl_instr_id)?,
+                orgnl_end_to_end_id: convert_option(&orgnl_tx.orgnl_end_to_end_id)?,
+                orgnl_tx_id: convert_option(&orgnl_tx.orgnl_tx_id)?,
+                orgnl_uetr: convert_option(&orgnl_tx.orgnl_uetr)?,
+                orgnl_clr_sys_ref: convert_option(&orgnl_tx.orgnl_clr_sys_ref)?,
+                orgnl_intr_bk_sttlm_amt: convert_option(&orgnl_tx.orgnl_intr_bk_sttlm_amt)?,
+                orgnl_intr_bk_sttlm_dt: convert_option(&orgnl_tx.orgnl_intr_bk_sttlm_dt)?,
+                rtrd_intr_bk_sttlm_amt,
+                rtrd_instd_amt: Some(convert(amount)?),
+                chrgs_inf,
+                rtr_rsn_inf: vec![pacs_004::PaymentReturnReason6 {
+                    rsn: Some(pacs_004::ReturnReason5Choice {
+                        value: pacs_004::ReturnReason5ChoiceEnum {
+                            cd: Some(convert(&Code { value: reason })?),
+                            prtry: None,
+                        },
+                    }),
+                    ..Default::default()
+                }],
+                orgnl_tx_ref: Some(convert(&orgnl_tx.orgnl_tx_ref)?),
+                ..Default::default()
+            }],
+            splmtry_data: Vec::new(),
+        },
+        xmlns: pacs_004::namespace(),
+    })
+}
+
+/// Build the `pacs.007.001.11` payment reversal of an original transaction.
+///
+/// The reversed interbank settlement amount is the original amount less the charges
+/// in the same currency, settled on the interbank settlement date of the group header.
+/// Returns `Error::ChargesExceedAmount` if the charges exceed the original amount. The
+/// reversal follows the route of the original transaction.
+pub fn payment_reversal<A, B>(
+    orgnl_tx: &OriginalTransaction,
+    reason: &ExternalReversalReason1Code,
+    chrgs_inf: Vec<pacs_007::Charges7>,
+    intr_bk_sttlm_dt: NaiveDate,
+) -> Result<pacs_007::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let amount = orgnl_tx.amount().ok_or(Error::MissingAmount)?;
+    let reversed = charges_deducted(
+        amount,
+        chrgs_inf
+            .iter()
+            .map(|chrgs| (&chrgs.amt.ccy.value, chrgs.amt.value.value)),
+    )?;
+
+    let rvsd_intr_bk_sttlm_amt = pacs_007::ActiveCurrencyAndAmount {
+        value: pacs_007::ActiveCurrencyAndAmountSimpleType { value: reversed },
+        ccy: pacs_007::ActiveCurrencyCode {
+            value: amount.ccy.value.clone(),
+        },
+    };
+
+    Ok(pacs_007::Document {
+        fi_to_fi_pmt_rvsl: pacs_007::FiToFiPaymentReversalV11 {
+            grp_hdr: pacs_007::GroupHeader97 {
+                msg_id: pacs_007::Max35Text { value: new_id() },
+                cre_dt_tm: pacs_007::IsoDateTime {
+                    value: chrono::Utc::now(),
+                },
+                nb_of_txs: pacs_007::Max15NumericText {
+                    value: "1".to_string(),
+                },
+                ttl_rvsd_intr_bk_sttlm_amt: Some(rvsd_intr_bk_sttlm_amt.clone()),
+                intr_bk_sttlm_dt: Some(pacs_007::IsoDate {
+                    value: intr_bk_sttlm_dt,
+                }),
+                sttlm_inf: convert_option(&orgnl_tx.orgnl_tx_ref.sttlm_inf)?.unwrap_or_default(),
+                instg_agt: convert_option(&orgnl_tx.instg_agt)?,
+                instd_agt: convert_option(&orgnl_tx.instd_agt)?,
+                ..Default::default()
+            },
+            orgnl_grp_inf: None,
+            tx_inf: vec![pacs_007::PaymentTransaction135 {
+                rvsl_id: Some(pacs_007::Max35Text { value: new_id() }),
+                orgnl_grp_inf: Some(convert(&orgnl_tx.orgnl_grp_inf)?),
+                orgnl_instr_id: convert_option(&orgnl_tx.orgnl_instr_id)?,
+                orgnl_end_to_end_id: convert_option(&orgnl_tx.orgnl_end_to_end_id)?,
+                orgnl_tx_id: convert_option(&orgnl_tx.orgnl_tx_id)?,
+                orgnl_uetr: convert_option(&orgnl_tx.orgnl_uetr)?,
+                orgnl_clr_sys_ref: convert_option(&orgnl_tx.orgnl_clr_sys_ref)?,
+                orgnl_intr_bk_sttlm_amt: convert_option(&orgnl_tx.orgnl_intr_bk_sttlm_amt)?,
+                rvsd_intr_bk_sttlm_amt,
+                rvsd_instd_amt: Some(convert(amount)?),
+                chrgs_inf,
+                rvsl_rsn_inf: vec![pacs_007::PaymentReversalReason9 {
+                    rsn: Some(pacs_007::ReversalReason4Choice {
+                        value: pacs_007::ReversalReason4ChoiceEnum {
+                            cd: Some(convert(&Code { value: reason })?),
+                            prtry: None,
+                        },
+                    }),
+                    ..Default::default()
+                }],
+                orgnl_tx_ref: Some(convert(&orgnl_tx.orgnl_tx_ref)?),
+                ..Default::default()
+            }],
+            splmtry_data: Vec::new(),
+        },
+        xmlns: pacs_007::namespace(),
+    })
+}
+
+/// Build the `camt.056.001.10` cancellation request of an original transaction.
+///
+/// The case is assigned by the instructing agent to the instructed agent of the
+/// original transaction.
+#[cfg(feature = "camt")]
+pub fn cancellation_request<A, B>(
+    orgnl_tx: &OriginalTransaction,
+    reason: &ExternalCancellationReason1Code,
+) -> Result<camt_056::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let agent = |agt: &Option<sts::BranchAndFinancialInstitutionIdentification6>| {
+        convert::<_, camt_056::Party40Choice>(&sts::Party40Choice {
+            value: sts::Party40ChoiceEnum {
+                agt: agt.clone(),
+                pty: None,
+            },
+        })
+    };
+
+    Ok(camt_056::Document {
+        fi_to_fi_pmt_cxl_req: camt_056::FiToFiPaymentCancellationRequestV10 {
+            assgnmt: camt_056::CaseAssignment5 {
+                id: camt_056::Max35Text { value: new_id() },
+                assgnr: agent(&orgnl_tx.instg_agt)?,
+                assgne: agent(&orgnl_tx.instd_agt)?,
+                cre_dt_tm: camt_056::IsoDateTime {
+                    value: chrono::Utc::now(),
+                },
+            },
+            case: None,
+            ctrl_data: Some(camt_056::ControlData1 {
+                nb_of_txs: camt_056::Max15NumericText {
+                    value: "1".to_string(),
+                },
+                ctrl_sum: None,
+            }),
+            undrlyg: vec![camt_056::UnderlyingTransaction28 {
+                orgnl_grp_inf_and_cxl: None,
+                tx_inf: vec![camt_056::PaymentTransaction137 {
+                    cxl_id: Some(camt_056::Max35Text { value: new_id() }),
+                    orgnl_grp_inf: Some(convert(&orgnl_tx.orgnl_grp_inf)?),
+                    orgnl_instr_id: convert_option(&orgnl_tx.orgnl_instr_id)?,
+                    orgnl_end_to_end_id: convert_option(&orgnl_tx.orgnl_end_to_end_id)?,
+                    orgnl_tx_id: convert_option(&orgnl_tx.orgnl_tx_id)?,
+                    orgnl_uetr: convert_option(&orgnl_tx.orgnl_uetr)?,
+                    orgnl_clr_sys_ref: convert_option(&orgnl_tx.orgnl_clr_sys_ref)?,
+                    orgnl_intr_bk_sttlm_amt: convert_option(&orgnl_tx.orgnl_intr_bk_sttlm_amt)?,
+                    orgnl_intr_bk_sttlm_dt: convert_option(&orgnl_tx.orgnl_intr_bk_sttlm_dt)?,
+                    cxl_rsn_inf: vec![camt_056::PaymentCancellationReason5 {
+                        rsn: Some(camt_056::CancellationReason33Choice {
+                            value: camt_056::CancellationReason33ChoiceEnum {
+                                cd: Some(convert(&Code { value: reason })?),
+                                prtry: None,
+                            },
+                        }),
+                        ..Default::default()
+                    }],
+                    orgnl_tx_ref: Some(convert(&orgnl_tx.orgnl_tx_ref)?),
+                    ..Default::default()
+                }],
+            }],
+            splmtry_data: Vec::new(),
+        },
+        xmlns: camt_056::namespace(),
+    })
+}
+
+/// Return the amount less the charges in the same currency, or
+/// `Error::ChargesExceedAmount` if the charges exceed the amount.
+fn charges_deducted<'a>(
+    amount: &sts::ActiveOrHistoricCurrencyAndAmount,
+    charges: impl Iterator<Item = (&'a String, f64)>,
+) -> Result<f64, Error> {
+    let charges = sum(charges
+        .filter(|(ccy, _)| **ccy == amount.ccy.value)
+        .map(|(_, charge)| charge));
+
+    if charges > amount.value.value {
+        return Err(Error::ChargesExceedAmount {
+            amount: amount.value.value,
+            charges,
+        });
+    }
+
+    Ok(sum([amount.value.value, -charges].into_iter()))
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+    use iso_20022_pacs::pacs_008_001_10 as pacs_008;
+
+    fn agent<T: serde::de::DeserializeOwned>(bicfi: &str) -> T {
+        quick_xml::de::from_str(&format!(
+            "<Agt><FinInstnId><BICFI>{}</BICFI></FinInstnId></Agt>",
+            bicfi
+        ))
+        .unwrap()
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 6, 5).unwrap()
+    }
+
+    fn original_transaction() -> Result<OriginalTransaction, Error> {
+        let grp_hdr = pacs_008::GroupHeader96 {
+            msg_id: pacs_008::Max35Text {
+                value: "PACS-8".to_string(),
+            },
+            instg_agt: Some(agent("AAAAGB2LXXX")),
+            instd_agt: Some(agent("BBBBDEFFXXX")),
+            ..Default::default()
+        };
+
+        let tx = pacs_008::CreditTransferTransaction50::<Dmkr> {
+            pmt_id: pacs_008::PaymentIdentification13 {
+                instr_id: Some(pacs_008::Max35Text {
+                    value: "INSTR-1".to_string(),
+                }),
+                end_to_end_id: pacs_008::Max35Text {
+                    value: "E2E-1".to_string(),
+                },
+                uetr: Some(pacs_008::UuiDv4Identifier {
+                    value: "8a562c67-ca16-48ba-b074-65581be6f001".to_string(),
+                }),
+                ..Default::default()
+            },
+            intr_bk_sttlm_amt: pacs_008::ActiveCurrencyAndAmount {
+                value: pacs_008::ActiveCurrencyAndAmountSimpleType { value: 100.0 },
+                ccy: pacs_008::ActiveCurrencyCode {
+                    value: "EUR".to_string(),
+                },
+            },
+            ..Default::default()
+        };
+
+        OriginalTransaction::from_pacs_008(&grp_hdr, &tx)
+    }
+
+    #[test]
+    fn test_payment_return() -> Result<(), Error> {
+        let orgnl_tx = original_transaction()?;
+
+        let chrgs_inf = vec![pacs_004::Charges7 {
+            amt: pacs_004::ActiveOrHistoricCurrencyAndAmount {
+                value: pacs_004::ActiveOrHistoricCurrencyAndAmountSimpleType { value: 2.5 },
+                ccy: pacs_004::ActiveOrHistoricCurrencyCode {
+                    value: "EUR".to_string(),
+                },
+            },
+            agt: agent("BBBBDEFFXXX"),
+        }];
+
+        let pmt_rtr = payment_return::<Dmkr, Dmkr>(
+            &orgnl_tx,
+            &ExternalReturnReason1Code::Ac04,
+            chrgs_inf,
+            date(),
+        )?
+        .pmt_rtr;
+
+        assert_eq!(
+            pmt_rtr
+                .grp_hdr
+                .ttl_rtrd_intr_bk_sttlm_amt
+                .as_ref()
+                .unwrap()
+                .value
+                .value,
+            97.5
+        );
+        assert_eq!(
+            pmt_rtr.grp_hdr.intr_bk_sttlm_dt.as_ref().unwrap().value,
+            date()
+        );
+
+        assert_eq!(
+            pmt_rtr
+                .grp_hdr
+                .instd_agt
+                .as_ref()
+                .unwrap()
+                .fin_instn_id
+                .bicfi
+                .as_ref()
+                .unwrap()
+                .value,
+            "AAAAGB2LXXX"
+        );
+
+        let tx = &pmt_rtr.tx_inf[0];
+        assert_eq!(
+            tx.orgnl_grp_inf.as_ref().unwrap().orgnl_msg_nm_id.value,
+            "pacs.008.001.10"
+        );
+        assert_eq!(tx.orgnl_instr_id.as_ref().unwrap().value, "INSTR-1");
+        assert_eq!(tx.orgnl_end_to_end_id.as_ref().unwrap().value, "E2E-1");
+        assert_eq!(
+            tx.orgnl_uetr.as_ref().unwrap().value,
+            "8a562c67-ca16-48ba-b074-65581be6f001"
+        );
+        assert_eq!(
+            tx.orgnl_intr_bk_sttlm_amt.as_ref().unwrap().value.value,
+            100.0
+        );
+        assert_eq!(tx.rtrd_intr_bk_sttlm_amt.value.value, 97.5);
+        assert_eq!(
+            tx.rtr_rsn_inf[0]
+                .rsn
+                .as_ref()
+                .unwrap()
+                .value
+                .cd
+                .as_ref()
+                .unwrap()
+                .value,
+            "AC04"
+        );
+        assert!(tx.orgnl_tx_ref.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_payment_reversal() -> Result<(), Error> {
+        let orgnl_tx = original_transaction()?;
+
+        let rvsl = payment_reversal::<Dmkr, Dmkr>(
+            &orgnl_tx,
+            &ExternalReversalReason1Code::Am05,
+            Vec::new(),
+            date(),
+        )?
+        .fi_to_fi_pmt_rvsl;
+
+        assert_eq!(
+            rvsl.grp_hdr.intr_bk_sttlm_dt.as_ref().unwrap().value,
+            date()
+        );
+
+        let tx = &rvsl.tx_inf[0];
+        assert_eq!(tx.rvsd_intr_bk_sttlm_amt.value.value, 100.0);
+        assert_eq!(tx.orgnl_end_to_end_id.as_ref().unwrap().value, "E2E-1");
+        assert_eq!(
+            rvsl.grp_hdr
+                .instg_agt
+                .as_ref()
+                .unwrap()
+                .fin_instn_id
+                .bicfi
+                .as_ref()
+                .unwrap()
+                .value,
+            "AAAAGB2LXXX"
+        );
+
+        // The charges cannot exceed the reversed amount
+        let chrgs_inf = vec![pacs_007::Charges7 {
+            amt: pacs_007::ActiveOrHistoricCurrencyAndAmount {
+                value: pacs_007::ActiveOrHistoricCurrencyAndAmountSimpleType { value: 100.5 },
+                ccy: pacs_007::ActiveOrHistoricCurrencyCode {
+                    value: "EUR".to_string(),
+                },
+            },
+            agt: agent("AAAAGB2LXXX"),
+        }];
+        assert!(matches!(
+            payment_reversal::<Dmkr, Dmkr>(
+                &orgnl_tx,
+                &ExternalReversalReason1Code::Am05,
+                chrgs_inf,
+                date(),
+            ),
+            Err(Error::ChargesExceedAmount { .. })
+        ));
+
+        Ok(())
+    }
+}
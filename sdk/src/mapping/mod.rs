@@ -0,0 +1,104 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Message Mapping
+//!
+//! Every message set crate defines its own copy of the shared ISO 20022 components, e.g.
+//! `Max35Text` or `PartyIdentification135`. The mapping modules convert customer-to-bank
+//! messages into their interbank counterparts, and report the information of the source
+//! message that could not be carried over.
+//!
+//! - `credit_transfer`: `pain.001.001.11` to `pacs.008.001.10`
+//! - `direct_debit`: `pain.008.001.10` to `pacs.003.001.09`
+//! - `exceptions`: `pacs.004.001.11` returns, `pacs.007.001.11` reversals and `camt.056.001.10`
+//!   cancellation requests of an original transaction
+//! - `original`: references of a transaction of an original message
+//! - `status_report`: `pacs.002.001.12` and `pain.002.001.12` status reports of inbound messages
+pub mod credit_transfer;
+pub mod direct_debit;
+pub mod exceptions;
+pub mod original;
+pub mod status_report;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub(crate) use crate::external_codes::Code;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// The number of transaction outcomes does not match the original transactions
+    #[error("expected {expected} transaction outcomes, found {found}")]
+    OutcomeCount { expected: usize, found: usize },
+    /// The original transaction has no amount, e.g. an equivalent amount initiation
+    #[error("the original transaction has no amount")]
+    MissingAmount,
+    /// The charges deducted from the amount of the original transaction exceed it
+    #[error("charges of {charges} exceed the amount of {amount}")]
+    ChargesExceedAmount { amount: f64, charges: f64 },
+}
+
+/// Information of the source message that was not carried over to the target message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingIssue {
+    /// Element of the source message without equivalent in the target message,
+    /// e.g. `PmtInf/CdtTrfTxInf/ChqInstr`
+    Unmapped { reference: String, element: String },
+    /// Transaction that could not be mapped, and is not included in the target message
+    Rejected { reference: String, reason: String },
+}
+
+/// Convert a component into the structurally identical component of another message set.
+pub(crate) fn convert<S, T>(source: &S) -> Result<T, Error>
+where
+    S: Serialize,
+    T: DeserializeOwned,
+{
+    Ok(quick_xml::de::from_str(&quick_xml::se::to_string(source)?)?)
+}
+
+/// Convert an optional component, see `convert`.
+pub(crate) fn convert_option<S, T>(source: &Option<S>) -> Result<Option<T>, Error>
+where
+    S: Serialize,
+    T: DeserializeOwned,
+{
+    source.as_ref().map(convert).transpose()
+}
+
+/// Convert repeated components, see `convert`.
+pub(crate) fn convert_vec<S, T>(source: &[S]) -> Result<Vec<T>, Error>
+where
+    S: Serialize,
+    T: DeserializeOwned,
+{
+    source.iter().map(convert).collect()
+}
+
+/// Sum amounts, rounded to the 5 fraction digits of an ISO 20022 amount, e.g. a control sum.
+pub(crate) fn sum(amounts: impl Iterator<Item = f64>) -> f64 {
+    (amounts.sum::<f64>() * 100_000.0).round() / 100_000.0
+}
+
+/// Report an element of the source message that was not carried over.
+pub(crate) fn unmapped(reference: &str, element: &str) -> MappingIssue {
+    MappingIssue::Unmapped {
+        reference: reference.to_string(),
+        element: element.to_string(),
+    }
+}
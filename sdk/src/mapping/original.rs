@@ -0,0 +1,437 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Original Transaction
+//!
+//! Identification and references of a transaction of an original message, as needed by
+//! the messages that refer back to it, e.g. status reports, returns, reversals and
+//! cancellation requests. The references are held in their `pacs.002.001.12` form, and
+//! converted into the structurally identical components of the referring message.
+use serde::Serialize;
+
+use iso_20022_pacs::pacs_002_001_12 as sts;
+use iso_20022_pacs::pacs_003_001_09 as pacs_003;
+use iso_20022_pacs::pacs_008_001_10 as pacs_008;
+use iso_20022_pacs::pacs_009_001_10 as pacs_009;
+use iso_20022_pain::pain_001_001_11 as pain_001;
+use iso_20022_pain::pain_008_001_10 as pain_008;
+
+use super::{convert, convert_option, Error};
+
+/// Transaction of an original message.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OriginalTransaction {
+    /// Original group information `OrgnlGrpInf`
+    pub orgnl_grp_inf: sts::OriginalGroupInformation29,
+    /// Original instruction identification `OrgnlInstrId`
+    pub orgnl_instr_id: Option<sts::Max35Text>,
+    /// Original end to end identification `OrgnlEndToEndId`
+    pub orgnl_end_to_end_id: Option<sts::Max35Text>,
+    /// Original transaction identification `OrgnlTxId`
+    pub orgnl_tx_id: Option<sts::Max35Text>,
+    /// Original unique end-to-end transaction reference `OrgnlUETR`
+    pub orgnl_uetr: Option<sts::UuiDv4Identifier>,
+    /// Original clearing system reference `OrgnlClrSysRef`
+    pub orgnl_clr_sys_ref: Option<sts::Max35Text>,
+    /// Original interbank settlement amount `OrgnlIntrBkSttlmAmt`, of interbank messages only
+    pub orgnl_intr_bk_sttlm_amt: Option<sts::ActiveOrHistoricCurrencyAndAmount>,
+    /// Original interbank settlement date `OrgnlIntrBkSttlmDt`, of interbank messages only
+    pub orgnl_intr_bk_sttlm_dt: Option<sts::IsoDate>,
+    /// Instructing agent of the original transaction
+    pub instg_agt: Option<sts::BranchAndFinancialInstitutionIdentification6>,
+    /// Instructed agent of the original transaction
+    pub instd_agt: Option<sts::BranchAndFinancialInstitutionIdentification6>,
+    /// Original transaction reference `OrgnlTxRef`
+    pub orgnl_tx_ref: sts::OriginalTransactionReference35,
+}
+
+impl OriginalTransaction {
+    /// Return the amount of the original transaction, i.e. its interbank settlement amount,
+    /// or the instructed amount of a customer initiation.
+    pub fn amount(&self) -> Option<&sts::ActiveOrHistoricCurrencyAndAmount> {
+        self.orgnl_intr_bk_sttlm_amt.as_ref().or_else(|| {
+            self.orgnl_tx_ref
+                .amt
+                .as_ref()
+                .and_then(|amt| amt.value.instd_amt.as_ref())
+        })
+    }
+
+    /// Original transaction of a `pacs.008.001.10` FI to FI customer credit transfer.
+    pub fn from_pacs_008<A>(
+        grp_hdr: &pacs_008::GroupHeader96,
+        tx: &pacs_008::CreditTransferTransaction50<A>,
+    ) -> Result<Self, Error>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        let intr_bk_sttlm_amt = Some(sts::ActiveOrHistoricCurrencyAndAmount {
+            value: sts::ActiveOrHistoricCurrencyAndAmountSimpleType {
+                value: tx.intr_bk_sttlm_amt.value.value,
+            },
+            ccy: sts::ActiveOrHistoricCurrencyCode {
+                value: tx.intr_bk_sttlm_amt.ccy.value.clone(),
+            },
+        });
+        let intr_bk_sttlm_dt: Option<sts::IsoDate> = convert_option(
+            &tx.intr_bk_sttlm_dt
+                .as_ref()
+                .or(grp_hdr.intr_bk_sttlm_dt.as_ref()),
+        )?;
+
+        Ok(Self {
+            orgnl_grp_inf: sts::OriginalGroupInformation29 {
+                orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+                orgnl_msg_nm_id: message_name("pacs.008.001.10"),
+                orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+            },
+            orgnl_instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            orgnl_end_to_end_id: Some(convert(&tx.pmt_id.end_to_end_id)?),
+            orgnl_tx_id: convert_option(&tx.pmt_id.tx_id)?,
+            orgnl_uetr: convert_option(&tx.pmt_id.uetr)?,
+            orgnl_clr_sys_ref: convert_option(&tx.pmt_id.clr_sys_ref)?,
+            orgnl_intr_bk_sttlm_amt: intr_bk_sttlm_amt.clone(),
+            orgnl_intr_bk_sttlm_dt: intr_bk_sttlm_dt.clone(),
+            instg_agt: convert_option(&tx.instg_agt.as_ref().or(grp_hdr.instg_agt.as_ref()))?,
+            instd_agt: convert_option(&tx.instd_agt.as_ref().or(grp_hdr.instd_agt.as_ref()))?,
+            orgnl_tx_ref: sts::OriginalTransactionReference35 {
+                intr_bk_sttlm_amt,
+                intr_bk_sttlm_dt,
+                sttlm_inf: Some(convert(&grp_hdr.sttlm_inf)?),
+                pmt_tp_inf: convert_option(
+                    &tx.pmt_tp_inf.as_ref().or(grp_hdr.pmt_tp_inf.as_ref()),
+                )?,
+                mndt_rltd_inf: tx
+                    .mndt_rltd_inf
+                    .as_ref()
+                    .map(credit_transfer_mandate)
+                    .transpose()?,
+                rmt_inf: convert_option(&tx.rmt_inf)?,
+                ultmt_dbtr: party_option(&tx.ultmt_dbtr)?,
+                dbtr: Some(party(&tx.dbtr)?),
+                dbtr_acct: convert_option(&tx.dbtr_acct)?,
+                dbtr_agt: Some(convert(&tx.dbtr_agt)?),
+                dbtr_agt_acct: convert_option(&tx.dbtr_agt_acct)?,
+                cdtr_agt: Some(convert(&tx.cdtr_agt)?),
+                cdtr_agt_acct: convert_option(&tx.cdtr_agt_acct)?,
+                cdtr: Some(party(&tx.cdtr)?),
+                cdtr_acct: convert_option(&tx.cdtr_acct)?,
+                ultmt_cdtr: party_option(&tx.ultmt_cdtr)?,
+                purp: convert_option(&tx.purp)?,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Original transaction of a `pacs.009.001.10` financial institution credit transfer.
+    pub fn from_pacs_009<A>(
+        grp_hdr: &pacs_009::GroupHeader96,
+        tx: &pacs_009::CreditTransferTransaction56<A>,
+    ) -> Result<Self, Error>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        let intr_bk_sttlm_amt = Some(sts::ActiveOrHistoricCurrencyAndAmount {
+            value: sts::ActiveOrHistoricCurrencyAndAmountSimpleType {
+                value: tx.intr_bk_sttlm_amt.value.value,
+            },
+            ccy: sts::ActiveOrHistoricCurrencyCode {
+                value: tx.intr_bk_sttlm_amt.ccy.value.clone(),
+            },
+        });
+        let intr_bk_sttlm_dt: Option<sts::IsoDate> = convert_option(
+            &tx.intr_bk_sttlm_dt
+                .as_ref()
+                .or(grp_hdr.intr_bk_sttlm_dt.as_ref()),
+        )?;
+
+        Ok(Self {
+            orgnl_grp_inf: sts::OriginalGroupInformation29 {
+                orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+                orgnl_msg_nm_id: message_name("pacs.009.001.10"),
+                orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+            },
+            orgnl_instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            orgnl_end_to_end_id: Some(convert(&tx.pmt_id.end_to_end_id)?),
+            orgnl_tx_id: convert_option(&tx.pmt_id.tx_id)?,
+            orgnl_uetr: convert_option(&tx.pmt_id.uetr)?,
+            orgnl_clr_sys_ref: convert_option(&tx.pmt_id.clr_sys_ref)?,
+            orgnl_intr_bk_sttlm_amt: intr_bk_sttlm_amt.clone(),
+            orgnl_intr_bk_sttlm_dt: intr_bk_sttlm_dt.clone(),
+            instg_agt: convert_option(&tx.instg_agt.as_ref().or(grp_hdr.instg_agt.as_ref()))?,
+            instd_agt: convert_option(&tx.instd_agt.as_ref().or(grp_hdr.instd_agt.as_ref()))?,
+            orgnl_tx_ref: sts::OriginalTransactionReference35 {
+                intr_bk_sttlm_amt,
+                intr_bk_sttlm_dt,
+                sttlm_inf: Some(convert(&grp_hdr.sttlm_inf)?),
+                pmt_tp_inf: convert_option(
+                    &tx.pmt_tp_inf.as_ref().or(grp_hdr.pmt_tp_inf.as_ref()),
+                )?,
+                rmt_inf: convert_option(&tx.rmt_inf)?,
+                ultmt_dbtr: agent_option(&tx.ultmt_dbtr)?,
+                dbtr: Some(agent(&tx.dbtr)?),
+                dbtr_acct: convert_option(&tx.dbtr_acct)?,
+                dbtr_agt: convert_option(&tx.dbtr_agt)?,
+                dbtr_agt_acct: convert_option(&tx.dbtr_agt_acct)?,
+                cdtr_agt: convert_option(&tx.cdtr_agt)?,
+                cdtr_agt_acct: convert_option(&tx.cdtr_agt_acct)?,
+                cdtr: Some(agent(&tx.cdtr)?),
+                cdtr_acct: convert_option(&tx.cdtr_acct)?,
+                ultmt_cdtr: agent_option(&tx.ultmt_cdtr)?,
+                purp: convert_option(&tx.purp)?,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Original transaction of a `pacs.003.001.09` FI to FI customer direct debit.
+    pub fn from_pacs_003<A>(
+        grp_hdr: &pacs_003::GroupHeader98,
+        tx: &pacs_003::DirectDebitTransactionInformation29<A>,
+    ) -> Result<Self, Error>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        let intr_bk_sttlm_amt = Some(sts::ActiveOrHistoricCurrencyAndAmount {
+            value: sts::ActiveOrHistoricCurrencyAndAmountSimpleType {
+                value: tx.intr_bk_sttlm_amt.value.value,
+            },
+            ccy: sts::ActiveOrHistoricCurrencyCode {
+                value: tx.intr_bk_sttlm_amt.ccy.value.clone(),
+            },
+        });
+        let intr_bk_sttlm_dt: Option<sts::IsoDate> = convert_option(
+            &tx.intr_bk_sttlm_dt
+                .as_ref()
+                .or(grp_hdr.intr_bk_sttlm_dt.as_ref()),
+        )?;
+        let drct_dbt_tx = tx.drct_dbt_tx.as_ref();
+
+        Ok(Self {
+            orgnl_grp_inf: sts::OriginalGroupInformation29 {
+                orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+                orgnl_msg_nm_id: message_name("pacs.003.001.09"),
+                orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+            },
+            orgnl_instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            orgnl_end_to_end_id: Some(convert(&tx.pmt_id.end_to_end_id)?),
+            orgnl_tx_id: convert_option(&tx.pmt_id.tx_id)?,
+            orgnl_uetr: convert_option(&tx.pmt_id.uetr)?,
+            orgnl_clr_sys_ref: convert_option(&tx.pmt_id.clr_sys_ref)?,
+            orgnl_intr_bk_sttlm_amt: intr_bk_sttlm_amt.clone(),
+            orgnl_intr_bk_sttlm_dt: intr_bk_sttlm_dt.clone(),
+            instg_agt: convert_option(&tx.instg_agt.as_ref().or(grp_hdr.instg_agt.as_ref()))?,
+            instd_agt: convert_option(&tx.instd_agt.as_ref().or(grp_hdr.instd_agt.as_ref()))?,
+            orgnl_tx_ref: sts::OriginalTransactionReference35 {
+                intr_bk_sttlm_amt,
+                intr_bk_sttlm_dt,
+                reqd_colltn_dt: convert_option(&tx.reqd_colltn_dt)?,
+                cdtr_schme_id: convert_option(
+                    &drct_dbt_tx.and_then(|drct_dbt_tx| drct_dbt_tx.cdtr_schme_id.as_ref()),
+                )?,
+                sttlm_inf: Some(convert(&grp_hdr.sttlm_inf)?),
+                pmt_tp_inf: convert_option(
+                    &tx.pmt_tp_inf.as_ref().or(grp_hdr.pmt_tp_inf.as_ref()),
+                )?,
+                pmt_mtd: Some(sts::PaymentMethod4Code::Dd),
+                mndt_rltd_inf: drct_dbt_tx
+                    .and_then(|drct_dbt_tx| drct_dbt_tx.mndt_rltd_inf.as_ref())
+                    .map(direct_debit_mandate)
+                    .transpose()?,
+                rmt_inf: convert_option(&tx.rmt_inf)?,
+                ultmt_dbtr: party_option(&tx.ultmt_dbtr)?,
+                dbtr: Some(party(&tx.dbtr)?),
+                dbtr_acct: Some(convert(&tx.dbtr_acct)?),
+                dbtr_agt: Some(convert(&tx.dbtr_agt)?),
+                dbtr_agt_acct: convert_option(&tx.dbtr_agt_acct)?,
+                cdtr_agt: Some(convert(&tx.cdtr_agt)?),
+                cdtr_agt_acct: convert_option(&tx.cdtr_agt_acct)?,
+                cdtr: Some(party(&tx.cdtr)?),
+                cdtr_acct: convert_option(&tx.cdtr_acct)?,
+                ultmt_cdtr: party_option(&tx.ultmt_cdtr)?,
+                purp: convert_option(&tx.purp)?,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Original transaction of a `pain.001.001.11` customer credit transfer initiation.
+    pub fn from_pain_001<A>(
+        grp_hdr: &pain_001::GroupHeader95,
+        pmt_inf: &pain_001::PaymentInstruction40<A>,
+        tx: &pain_001::CreditTransferTransaction54<A>,
+    ) -> Result<Self, Error>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        Ok(Self {
+            orgnl_grp_inf: sts::OriginalGroupInformation29 {
+                orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+                orgnl_msg_nm_id: message_name("pain.001.001.11"),
+                orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+            },
+            orgnl_instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            orgnl_end_to_end_id: Some(convert(&tx.pmt_id.end_to_end_id)?),
+            orgnl_uetr: convert_option(&tx.pmt_id.uetr)?,
+            orgnl_tx_ref: sts::OriginalTransactionReference35 {
+                amt: Some(convert(&tx.amt)?),
+                reqd_exctn_dt: Some(convert(&pmt_inf.reqd_exctn_dt)?),
+                pmt_tp_inf: convert_option(
+                    &tx.pmt_tp_inf.as_ref().or(pmt_inf.pmt_tp_inf.as_ref()),
+                )?,
+                pmt_mtd: Some(match pmt_inf.pmt_mtd {
+                    pain_001::PaymentMethod3Code::Chk => sts::PaymentMethod4Code::Chk,
+                    pain_001::PaymentMethod3Code::Tra => sts::PaymentMethod4Code::Tra,
+                    _ => sts::PaymentMethod4Code::Trf,
+                }),
+                mndt_rltd_inf: tx
+                    .mndt_rltd_inf
+                    .as_ref()
+                    .map(credit_transfer_mandate)
+                    .transpose()?,
+                rmt_inf: convert_option(&tx.rmt_inf)?,
+                ultmt_dbtr: party_option(&tx.ultmt_dbtr.as_ref().or(pmt_inf.ultmt_dbtr.as_ref()))?,
+                dbtr: Some(party(&pmt_inf.dbtr)?),
+                dbtr_acct: Some(convert(&pmt_inf.dbtr_acct)?),
+                dbtr_agt: Some(convert(&pmt_inf.dbtr_agt)?),
+                dbtr_agt_acct: convert_option(&pmt_inf.dbtr_agt_acct)?,
+                cdtr_agt: convert_option(&tx.cdtr_agt)?,
+                cdtr_agt_acct: convert_option(&tx.cdtr_agt_acct)?,
+                cdtr: party_option(&tx.cdtr)?,
+                cdtr_acct: convert_option(&tx.cdtr_acct)?,
+                ultmt_cdtr: party_option(&tx.ultmt_cdtr)?,
+                purp: convert_option(&tx.purp)?,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    /// Original transaction of a `pain.008.001.10` customer direct debit initiation.
+    pub fn from_pain_008<A>(
+        grp_hdr: &pain_008::GroupHeader83,
+        pmt_inf: &pain_008::PaymentInstruction39<A>,
+        tx: &pain_008::DirectDebitTransactionInformation28<A>,
+    ) -> Result<Self, Error>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        let drct_dbt_tx = tx.drct_dbt_tx.as_ref();
+
+        Ok(Self {
+            orgnl_grp_inf: sts::OriginalGroupInformation29 {
+                orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+                orgnl_msg_nm_id: message_name("pain.008.001.10"),
+                orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+            },
+            orgnl_instr_id: convert_option(&tx.pmt_id.instr_id)?,
+            orgnl_end_to_end_id: Some(convert(&tx.pmt_id.end_to_end_id)?),
+            orgnl_uetr: convert_option(&tx.pmt_id.uetr)?,
+            orgnl_tx_ref: sts::OriginalTransactionReference35 {
+                amt: Some(sts::AmountType4Choice {
+                    value: sts::AmountType4ChoiceEnum {
+                        instd_amt: Some(convert(&tx.instd_amt)?),
+                        eqvt_amt: None,
+                    },
+                }),
+                reqd_colltn_dt: Some(convert(&pmt_inf.reqd_colltn_dt)?),
+                cdtr_schme_id: convert_option(
+                    &drct_dbt_tx
+                        .and_then(|drct_dbt_tx| drct_dbt_tx.cdtr_schme_id.as_ref())
+                        .or(pmt_inf.cdtr_schme_id.as_ref()),
+                )?,
+                pmt_tp_inf: convert_option(
+                    &tx.pmt_tp_inf.as_ref().or(pmt_inf.pmt_tp_inf.as_ref()),
+                )?,
+                pmt_mtd: Some(sts::PaymentMethod4Code::Dd),
+                mndt_rltd_inf: drct_dbt_tx
+                    .and_then(|drct_dbt_tx| drct_dbt_tx.mndt_rltd_inf.as_ref())
+                    .map(direct_debit_mandate)
+                    .transpose()?,
+                rmt_inf: convert_option(&tx.rmt_inf)?,
+                ultmt_dbtr: party_option(&tx.ultmt_dbtr)?,
+                dbtr: Some(party(&tx.dbtr)?),
+                dbtr_acct: Some(convert(&tx.dbtr_acct)?),
+                dbtr_agt: Some(convert(&tx.dbtr_agt)?),
+                dbtr_agt_acct: convert_option(&tx.dbtr_agt_acct)?,
+                cdtr_agt: Some(convert(&pmt_inf.cdtr_agt)?),
+                cdtr_agt_acct: convert_option(&pmt_inf.cdtr_agt_acct)?,
+                cdtr: Some(party(&pmt_inf.cdtr)?),
+                cdtr_acct: Some(convert(&pmt_inf.cdtr_acct)?),
+                ultmt_cdtr: party_option(&tx.ultmt_cdtr.as_ref().or(pmt_inf.ultmt_cdtr.as_ref()))?,
+                purp: convert_option(&tx.purp)?,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+}
+
+fn message_name(msg_nm_id: &str) -> sts::Max35Text {
+    sts::Max35Text {
+        value: msg_nm_id.to_string(),
+    }
+}
+
+fn credit_transfer_mandate<S: Serialize>(
+    mndt_rltd_inf: &S,
+) -> Result<sts::MandateRelatedData2Choice, Error> {
+    Ok(sts::MandateRelatedData2Choice {
+        value: sts::MandateRelatedData2ChoiceEnum {
+            cdt_trf_mndt: Some(convert(mndt_rltd_inf)?),
+            drct_dbt_mndt: None,
+        },
+    })
+}
+
+fn direct_debit_mandate<S: Serialize>(
+    mndt_rltd_inf: &S,
+) -> Result<sts::MandateRelatedData2Choice, Error> {
+    Ok(sts::MandateRelatedData2Choice {
+        value: sts::MandateRelatedData2ChoiceEnum {
+            cdt_trf_mndt: None,
+            drct_dbt_mndt: Some(convert(mndt_rltd_inf)?),
+        },
+    })
+}
+
+/// Wrap a party identification of another message set as a `Party40Choice` party.
+fn party<S: Serialize>(pty: &S) -> Result<sts::Party40Choice, Error> {
+    Ok(sts::Party40Choice {
+        value: sts::Party40ChoiceEnum {
+            pty: Some(convert(pty)?),
+            agt: None,
+        },
+    })
+}
+
+fn party_option<S: Serialize>(pty: &Option<S>) -> Result<Option<sts::Party40Choice>, Error> {
+    pty.as_ref().map(party).transpose()
+}
+
+/// Wrap a financial institution identification of another message set as a `Party40Choice` agent.
+fn agent<S: Serialize>(agt: &S) -> Result<sts::Party40Choice, Error> {
+    Ok(sts::Party40Choice {
+        value: sts::Party40ChoiceEnum {
+            pty: None,
+            agt: Some(convert(agt)?),
+        },
+    })
+}
+
+fn agent_option<S: Serialize>(agt: &Option<S>) -> Result<Option<sts::Party40Choice>, Error> {
+    agt.as_ref().map(agent).transpose()
+}
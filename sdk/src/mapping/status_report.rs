@@ -0,0 +1,614 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Status Report Mapping
+//!
+//! Builds the status report answering an inbound payment message from the outcome of
+//! each of its transactions, given in the order of the original transactions:
+//!
+//! - `pacs.008.001.10` and `pacs.003.001.09` are answered with `pacs.002.001.12`
+//! - `pain.001.001.11` is answered with `pain.002.001.12`
+//!
+//! The group status is derived from the transaction statuses, e.g. `PART` when some,
+//! but not all, transactions are rejected.
+//!
+//! ```rust
+//! use iso_20022_sdk::external_codes::ExternalStatusReason1Code;
+//! use iso_20022_sdk::mapping::status_report::{pacs_008_status_report, TransactionOutcome};
+//!
+//! let outcomes = vec![
+//!     TransactionOutcome::Accepted,
+//!     TransactionOutcome::Rejected {
+//!         reason: ExternalStatusReason1Code::Ac01,
+//!         addtl_inf: vec!["creditor account closed".to_string()],
+//!     },
+//! ];
+//!
+//! let pacs_002 = pacs_008_status_report(&pacs_008.fi_to_fi_cstmr_cdt_trf, &outcomes)?;
+//! ```
+use serde::Serialize;
+
+use iso_20022_pacs::pacs_002_001_12 as sts;
+use iso_20022_pacs::pacs_003_001_09 as pacs_003;
+use iso_20022_pacs::pacs_008_001_10 as pacs_008;
+use iso_20022_pain::pain_001_001_11 as pain_001;
+use iso_20022_pain::pain_002_001_12 as pain_002;
+
+use super::original::OriginalTransaction;
+use super::{convert, convert_option, convert_vec, Code, Error};
+use crate::external_codes::ExternalStatusReason1Code;
+
+/// Processing outcome of an original transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionOutcome {
+    /// Accepted for processing, `ACCP`
+    Accepted,
+    /// Settlement completed, `ACSC`
+    Settled,
+    /// Processing is pending, `PDNG`
+    Pending,
+    /// Rejected, `RJCT`, for the reason and with the additional information provided
+    Rejected {
+        reason: ExternalStatusReason1Code,
+        addtl_inf: Vec<String>,
+    },
+}
+
+impl TransactionOutcome {
+    /// Return the `ExternalPaymentTransactionStatus1Code` of the outcome.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Accepted => "ACCP",
+            Self::Settled => "ACSC",
+            Self::Pending => "PDNG",
+            Self::Rejected { .. } => "RJCT",
+        }
+    }
+}
+
+/// Derive the `ExternalPaymentGroupStatus1Code` of a group from the outcomes of its transactions.
+///
+/// - `RJCT` when all transactions are rejected
+/// - `PART` when some transactions are rejected
+/// - `PDNG` when any transaction is pending
+/// - `ACSC` when all transactions are settled
+/// - `ACCP` otherwise
+pub fn group_status(outcomes: &[TransactionOutcome]) -> &'static str {
+    let rejected = outcomes
+        .iter()
+        .filter(|outcome| matches!(outcome, TransactionOutcome::Rejected { .. }))
+        .count();
+
+    if rejected > 0 && rejected == outcomes.len() {
+        "RJCT"
+    } else if rejected > 0 {
+        "PART"
+    } else if outcomes.contains(&TransactionOutcome::Pending) {
+        "PDNG"
+    } else if !outcomes.is_empty() && outcomes.iter().all(|o| o == &TransactionOutcome::Settled) {
+        "ACSC"
+    } else {
+        "ACCP"
+    }
+}
+
+/// Build the `pacs.002.001.12` status report of an FI to FI customer credit transfer.
+pub fn pacs_008_status_report<A, B>(
+    orgnl: &pacs_008::FiToFiCustomerCreditTransferV10<A, B>,
+    outcomes: &[TransactionOutcome],
+) -> Result<sts::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let grp_hdr = &orgnl.grp_hdr;
+    check_outcomes(orgnl.cdt_trf_tx_inf.len(), outcomes)?;
+
+    let amounts = orgnl
+        .cdt_trf_tx_inf
+        .iter()
+        .map(|tx| tx.intr_bk_sttlm_amt.value.value)
+        .collect::<Vec<_>>();
+
+    let tx_inf_and_sts = orgnl
+        .cdt_trf_tx_inf
+        .iter()
+        .zip(outcomes)
+        .map(|(tx, outcome)| {
+            transaction_information_and_status(
+                &OriginalTransaction::from_pacs_008(grp_hdr, tx)?,
+                outcome,
+            )
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let orgnl_grp_inf_and_sts = sts::OriginalGroupHeader17 {
+        orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+        orgnl_msg_nm_id: sts::Max35Text {
+            value: "pacs.008.001.10".to_string(),
+        },
+        orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+        orgnl_nb_of_txs: Some(convert(&grp_hdr.nb_of_txs)?),
+        orgnl_ctrl_sum: convert_option(&grp_hdr.ctrl_sum)?,
+        grp_sts: Some(sts::ExternalPaymentGroupStatus1Code {
+            value: group_status(outcomes).to_string(),
+        }),
+        nb_of_txs_per_sts: number_of_transactions_per_status(outcomes, &amounts),
+        ..Default::default()
+    };
+
+    fi_to_fi_payment_status_report(
+        orgnl_grp_inf_and_sts,
+        convert_option(&grp_hdr.instd_agt)?,
+        convert_option(&grp_hdr.instg_agt)?,
+        tx_inf_and_sts,
+    )
+}
+
+/// Build the `pacs.002.001.12` status report of an FI to FI customer direct debit.
+pub fn pacs_003_status_report<A, B>(
+    orgnl: &pacs_003::FiToFiCustomerDirectDebitV09<A, B>,
+    outcomes: &[TransactionOutcome],
+) -> Result<sts::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let grp_hdr = &orgnl.grp_hdr;
+    check_outcomes(orgnl.drct_dbt_tx_inf.len(), outcomes)?;
+
+    let amounts = orgnl
+        .drct_dbt_tx_inf
+        .iter()
+        .map(|tx| tx.intr_bk_sttlm_amt.value.value)
+        .collect::<Vec<_>>();
+
+    let tx_inf_and_sts = orgnl
+        .drct_dbt_tx_inf
+        .iter()
+        .zip(outcomes)
+        .map(|(tx, outcome)| {
+            transaction_information_and_status(
+                &OriginalTransaction::from_pacs_003(grp_hdr, tx)?,
+                outcome,
+            )
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let orgnl_grp_inf_and_sts = sts::OriginalGroupHeader17 {
+        orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+        orgnl_msg_nm_id: sts::Max35Text {
+            value: "pacs.003.001.09".to_string(),
+        },
+        orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+        orgnl_nb_of_txs: Some(convert(&grp_hdr.nb_of_txs)?),
+        orgnl_ctrl_sum: convert_option(&grp_hdr.ctrl_sum)?,
+        grp_sts: Some(sts::ExternalPaymentGroupStatus1Code {
+            value: group_status(outcomes).to_string(),
+        }),
+        nb_of_txs_per_sts: number_of_transactions_per_status(outcomes, &amounts),
+        ..Default::default()
+    };
+
+    fi_to_fi_payment_status_report(
+        orgnl_grp_inf_and_sts,
+        convert_option(&grp_hdr.instd_agt)?,
+        convert_option(&grp_hdr.instg_agt)?,
+        tx_inf_and_sts,
+    )
+}
+
+/// Build the `pain.002.001.12` status report of a customer credit transfer initiation.
+///
+/// The outcomes are given for the transactions of all payment information blocks, in order.
+pub fn pain_001_status_report<A, B>(
+    orgnl: &pain_001::CustomerCreditTransferInitiationV11<A, B>,
+    outcomes: &[TransactionOutcome],
+) -> Result<pain_002::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let grp_hdr = &orgnl.grp_hdr;
+    check_outcomes(
+        orgnl
+            .pmt_inf
+            .iter()
+            .map(|pmt_inf| pmt_inf.cdt_trf_tx_inf.len())
+            .sum(),
+        outcomes,
+    )?;
+
+    let mut remaining = outcomes;
+    let mut amounts = Vec::new();
+    let mut orgnl_pmt_inf_and_sts = Vec::new();
+
+    for pmt_inf in orgnl.pmt_inf.iter() {
+        let (pmt_inf_outcomes, rest) = remaining.split_at(pmt_inf.cdt_trf_tx_inf.len());
+        remaining = rest;
+
+        let mut pmt_inf_amounts = Vec::new();
+        let mut tx_inf_and_sts = Vec::new();
+
+        for (tx, outcome) in pmt_inf.cdt_trf_tx_inf.iter().zip(pmt_inf_outcomes) {
+            let orgnl_tx = OriginalTransaction::from_pain_001(grp_hdr, pmt_inf, tx)?;
+
+            pmt_inf_amounts.push(
+                orgnl_tx
+                    .amount()
+                    .or_else(|| {
+                        orgnl_tx
+                            .orgnl_tx_ref
+                            .amt
+                            .as_ref()
+                            .and_then(|amt| amt.value.eqvt_amt.as_ref())
+                            .map(|eqvt_amt| &eqvt_amt.amt)
+                    })
+                    .map(|amt| amt.value.value)
+                    .unwrap_or_default(),
+            );
+
+            tx_inf_and_sts.push(pain_002::PaymentTransaction129 {
+                orgnl_instr_id: convert_option(&orgnl_tx.orgnl_instr_id)?,
+                orgnl_end_to_end_id: convert_option(&orgnl_tx.orgnl_end_to_end_id)?,
+                orgnl_uetr: convert_option(&orgnl_tx.orgnl_uetr)?,
+                tx_sts: Some(pain_002::ExternalPaymentTransactionStatus1Code {
+                    value: outcome.status().to_string(),
+                }),
+                sts_rsn_inf: convert_vec(&status_reason_information(outcome)?)?,
+                orgnl_tx_ref: Some(convert(&orgnl_tx.orgnl_tx_ref)?),
+                ..Default::default()
+            });
+        }
+
+        orgnl_pmt_inf_and_sts.push(pain_002::OriginalPaymentInstruction40 {
+            orgnl_pmt_inf_id: convert(&pmt_inf.pmt_inf_id)?,
+            orgnl_nb_of_txs: convert_option(&pmt_inf.nb_of_txs)?,
+            orgnl_ctrl_sum: convert_option(&pmt_inf.ctrl_sum)?,
+            pmt_inf_sts: Some(pain_002::ExternalPaymentGroupStatus1Code {
+                value: group_status(pmt_inf_outcomes).to_string(),
+            }),
+            nb_of_txs_per_sts: convert_vec(&number_of_transactions_per_status(
+                pmt_inf_outcomes,
+                &pmt_inf_amounts,
+            ))?,
+            tx_inf_and_sts,
+            ..Default::default()
+        });
+
+        amounts.extend(pmt_inf_amounts);
+    }
+
+    let dbtr_agt = orgnl
+        .pmt_inf
+        .first()
+        .map(|first| &first.dbtr_agt)
+        .filter(|dbtr_agt| {
+            orgnl
+                .pmt_inf
+                .iter()
+                .all(|pmt_inf| &pmt_inf.dbtr_agt == *dbtr_agt)
+        });
+
+    Ok(pain_002::Document {
+        cstmr_pmt_sts_rpt: pain_002::CustomerPaymentStatusReportV12 {
+            grp_hdr: pain_002::GroupHeader86 {
+                msg_id: pain_002::Max35Text {
+                    value: uuid::Uuid::new_v4().simple().to_string(),
+                },
+                cre_dt_tm: pain_002::IsoDateTime {
+                    value: chrono::Utc::now(),
+                },
+                dbtr_agt: convert_option(&dbtr_agt)?,
+                ..Default::default()
+            },
+            orgnl_grp_inf_and_sts: pain_002::OriginalGroupHeader17 {
+                orgnl_msg_id: convert(&grp_hdr.msg_id)?,
+                orgnl_msg_nm_id: pain_002::Max35Text {
+                    value: "pain.001.001.11".to_string(),
+                },
+                orgnl_cre_dt_tm: Some(convert(&grp_hdr.cre_dt_tm)?),
+                orgnl_nb_of_txs: Some(convert(&grp_hdr.nb_of_txs)?),
+                orgnl_ctrl_sum: convert_option(&grp_hdr.ctrl_sum)?,
+                grp_sts: Some(pain_002::ExternalPaymentGroupStatus1Code {
+                    value: group_status(outcomes).to_string(),
+                }),
+                nb_of_txs_per_sts: convert_vec(&number_of_transactions_per_status(
+                    outcomes, &amounts,
+                ))?,
+                ..Default::default()
+            },
+            orgnl_pmt_inf_and_sts,
+            splmtry_data: Vec::new(),
+        },
+        xmlns: pain_002::namespace(),
+    })
+}
+
+/// Assemble the `pacs.002.001.12` document of an original group.
+fn fi_to_fi_payment_status_report<A, B>(
+    orgnl_grp_inf_and_sts: sts::OriginalGroupHeader17,
+    instg_agt: Option<sts::BranchAndFinancialInstitutionIdentification6>,
+    instd_agt: Option<sts::BranchAndFinancialInstitutionIdentification6>,
+    tx_inf_and_sts: Vec<sts::PaymentTransaction130<A>>,
+) -> Result<sts::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    Ok(sts::Document {
+        fi_to_fi_pmt_sts_rpt: sts::FiToFiPaymentStatusReportV12 {
+            grp_hdr: sts::GroupHeader101 {
+                msg_id: sts::Max35Text {
+                    value: uuid::Uuid::new_v4().simple().to_string(),
+                },
+                cre_dt_tm: sts::IsoDateTime {
+                    value: chrono::Utc::now(),
+                },
+                instg_agt,
+                instd_agt,
+                orgnl_biz_qry: None,
+            },
+            orgnl_grp_inf_and_sts: vec![orgnl_grp_inf_and_sts],
+            tx_inf_and_sts,
+            splmtry_data: Vec::new(),
+        },
+        xmlns: sts::namespace(),
+    })
+}
+
+/// Return the status of an original interbank transaction.
+fn transaction_information_and_status<A>(
+    orgnl_tx: &OriginalTransaction,
+    outcome: &TransactionOutcome,
+) -> Result<sts::PaymentTransaction130<A>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    Ok(sts::PaymentTransaction130 {
+        orgnl_instr_id: orgnl_tx.orgnl_instr_id.clone(),
+        orgnl_end_to_end_id: orgnl_tx.orgnl_end_to_end_id.clone(),
+        orgnl_tx_id: orgnl_tx.orgnl_tx_id.clone(),
+        orgnl_uetr: orgnl_tx.orgnl_uetr.clone(),
+        tx_sts: Some(transaction_status(outcome)),
+        sts_rsn_inf: status_reason_information(outcome)?,
+        clr_sys_ref: orgnl_tx.orgnl_clr_sys_ref.clone(),
+        orgnl_tx_ref: Some(orgnl_tx.orgnl_tx_ref.clone()),
+        ..Default::default()
+    })
+}
+
+/// Check that an outcome is given for every original transaction.
+fn check_outcomes(expected: usize, outcomes: &[TransactionOutcome]) -> Result<(), Error> {
+    if outcomes.len() != expected {
+        return Err(Error::OutcomeCount {
+            expected,
+            found: outcomes.len(),
+        });
+    }
+
+    Ok(())
+}
+
+fn transaction_status(outcome: &TransactionOutcome) -> sts::ExternalPaymentTransactionStatus1Code {
+    sts::ExternalPaymentTransactionStatus1Code {
+        value: outcome.status().to_string(),
+    }
+}
+
+/// Return the status reason information of a rejected transaction.
+fn status_reason_information(
+    outcome: &TransactionOutcome,
+) -> Result<Vec<sts::StatusReasonInformation12>, Error> {
+    match outcome {
+        TransactionOutcome::Rejected { reason, addtl_inf } => {
+            Ok(vec![sts::StatusReasonInformation12 {
+                orgtr: None,
+                rsn: Some(sts::StatusReason6Choice {
+                    value: sts::StatusReason6ChoiceEnum {
+                        cd: Some(convert(&Code { value: reason })?),
+                        prtry: None,
+                    },
+                }),
+                addtl_inf: addtl_inf
+                    .iter()
+                    .map(|addtl_inf| sts::Max105Text {
+                        value: addtl_inf.clone(),
+                    })
+                    .collect(),
+            }])
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Return the number of transactions and control sum per transaction status, in order of appearance.
+fn number_of_transactions_per_status(
+    outcomes: &[TransactionOutcome],
+    amounts: &[f64],
+) -> Vec<sts::NumberOfTransactionsPerStatus5> {
+    let mut per_status: Vec<(&str, usize, f64)> = Vec::new();
+
+    for (outcome, amount) in outcomes.iter().zip(amounts) {
+        match per_status
+            .iter_mut()
+            .find(|(status, _, _)| *status == outcome.status())
+        {
+            Some((_, count, sum)) => {
+                *count += 1;
+                *sum += amount;
+            }
+            None => per_status.push((outcome.status(), 1, *amount)),
+        }
+    }
+
+    per_status
+        .into_iter()
+        .map(|(status, count, sum)| sts::NumberOfTransactionsPerStatus5 {
+            dtld_nb_of_txs: sts::Max15NumericText {
+                value: count.to_string(),
+            },
+            dtld_sts: sts::ExternalPaymentTransactionStatus1Code {
+                value: status.to_string(),
+            },
+            dtld_ctrl_sum: Some(sts::DecimalNumber { value: sum }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    fn transaction(
+        end_to_end_id: &str,
+        amount: f64,
+    ) -> pacs_008::CreditTransferTransaction50<Dmkr> {
+        pacs_008::CreditTransferTransaction50 {
+            pmt_id: pacs_008::PaymentIdentification13 {
+                end_to_end_id: pacs_008::Max35Text {
+                    value: end_to_end_id.to_string(),
+                },
+                uetr: Some(pacs_008::UuiDv4Identifier {
+                    value: "8a562c67-ca16-48ba-b074-65581be6f001".to_string(),
+                }),
+                ..Default::default()
+            },
+            intr_bk_sttlm_amt: pacs_008::ActiveCurrencyAndAmount {
+                value: pacs_008::ActiveCurrencyAndAmountSimpleType { value: amount },
+                ccy: pacs_008::ActiveCurrencyCode {
+                    value: "EUR".to_string(),
+                },
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_status() {
+        let rejected = TransactionOutcome::Rejected {
+            reason: ExternalStatusReason1Code::Ac01,
+            addtl_inf: Vec::new(),
+        };
+
+        assert_eq!(group_status(&[rejected.clone(), rejected.clone()]), "RJCT");
+        assert_eq!(
+            group_status(&[TransactionOutcome::Settled, rejected]),
+            "PART"
+        );
+        assert_eq!(
+            group_status(&[TransactionOutcome::Accepted, TransactionOutcome::Pending]),
+            "PDNG"
+        );
+        assert_eq!(group_status(&[TransactionOutcome::Settled]), "ACSC");
+        assert_eq!(
+            group_status(&[TransactionOutcome::Settled, TransactionOutcome::Accepted]),
+            "ACCP"
+        );
+    }
+
+    #[test]
+    fn test_pacs_008_status_report() -> Result<(), Error> {
+        let orgnl = pacs_008::FiToFiCustomerCreditTransferV10::<Dmkr, Dmkr> {
+            grp_hdr: pacs_008::GroupHeader96 {
+                msg_id: pacs_008::Max35Text {
+                    value: "PACS-8".to_string(),
+                },
+                nb_of_txs: pacs_008::Max15NumericText {
+                    value: "2".to_string(),
+                },
+                ..Default::default()
+            },
+            cdt_trf_tx_inf: vec![transaction("E2E-1", 10.0), transaction("E2E-2", 20.0)],
+            ..Default::default()
+        };
+
+        let outcomes = vec![
+            TransactionOutcome::Accepted,
+            TransactionOutcome::Rejected {
+                reason: ExternalStatusReason1Code::Ac01,
+                addtl_inf: vec!["incorrect account number".to_string()],
+            },
+        ];
+
+        assert!(matches!(
+            pacs_008_status_report(&orgnl, &outcomes[..1]),
+            Err(Error::OutcomeCount {
+                expected: 2,
+                found: 1
+            })
+        ));
+
+        let report = pacs_008_status_report(&orgnl, &outcomes)?.fi_to_fi_pmt_sts_rpt;
+
+        let orgnl_grp_inf_and_sts = &report.orgnl_grp_inf_and_sts[0];
+        assert_eq!(orgnl_grp_inf_and_sts.orgnl_msg_id.value, "PACS-8");
+        assert_eq!(
+            orgnl_grp_inf_and_sts.orgnl_msg_nm_id.value,
+            "pacs.008.001.10"
+        );
+        assert_eq!(
+            orgnl_grp_inf_and_sts.grp_sts.as_ref().unwrap().value,
+            "PART"
+        );
+        assert_eq!(orgnl_grp_inf_and_sts.nb_of_txs_per_sts.len(), 2);
+        assert_eq!(
+            orgnl_grp_inf_and_sts.nb_of_txs_per_sts[1]
+                .dtld_ctrl_sum
+                .as_ref()
+                .unwrap()
+                .value,
+            20.0
+        );
+
+        let tx = &report.tx_inf_and_sts[1];
+        assert_eq!(tx.orgnl_end_to_end_id.as_ref().unwrap().value, "E2E-2");
+        assert_eq!(
+            tx.orgnl_uetr.as_ref().unwrap().value,
+            "8a562c67-ca16-48ba-b074-65581be6f001"
+        );
+        assert_eq!(tx.tx_sts.as_ref().unwrap().value, "RJCT");
+
+        let sts_rsn_inf = &tx.sts_rsn_inf[0];
+        assert_eq!(
+            sts_rsn_inf
+                .rsn
+                .as_ref()
+                .unwrap()
+                .value
+                .cd
+                .as_ref()
+                .unwrap()
+                .value,
+            "AC01"
+        );
+        assert_eq!(sts_rsn_inf.addtl_inf[0].value, "incorrect account number");
+        assert_eq!(
+            tx.orgnl_tx_ref
+                .as_ref()
+                .unwrap()
+                .intr_bk_sttlm_amt
+                .as_ref()
+                .unwrap()
+                .value
+                .value,
+            20.0
+        );
+
+        Ok(())
+    }
+}
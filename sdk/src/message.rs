@@ -45,15 +45,18 @@
 //!     // In practice, the document type will likely be the result of the document
 //!     // builder for the target namespace, e.g.
 //!     //
-//!     // `documents::pacs::pacs_008_001_07::Document`
+//!     // `documents::pacs::pacs_008_001_10::Document`
 //!     //
 //!     // The example below uses the default values for the document builder
-//!     // for the `pacs.008.001.07` namespace.
+//!     // for the `pacs.008.001.10` namespace.
 //!     //
 //!     // NOTE: document namespaces are feature gated and must be enabled
 //!     // for the example to work, e.g. `pacs` feature must be enabled in
 //!     // Cargo.toml file.
-//!     .set_document(Document::from_namespace("pacs.008.001.07"))
+//!     //
+//!     // The application header `MsgDefIdr`, `BizMsgIdr` and `CreDt` are
+//!     // populated from the document.
+//!     .set_document(Document::from_namespace("pacs.008.001.10"))
 //!     // Call the `to_xml` method to serialize the `Message` type to XML
 //!     .to_xml();
 //!
@@ -90,6 +93,15 @@ use sxd_xpath::evaluate_xpath;
 use xml::{reader::XmlEvent, EventReader};
 
 use crate::documents::{Dmkr, Document};
+use crate::header::{AnyAppHdr, BusinessHeader, HeaderVersion};
+
+/// XPath to the namespace of the first iso-20022 element in the document.
+const DOCUMENT_NAMESPACE_X_PATH: &str =
+    "namespace-uri((//*[starts-with(namespace-uri(), 'urn:iso:std:iso:20022:tech:xsd:')])[1])";
+
+/// XPath to the message identification of the document. The `MsgId` is
+/// either a text element or a message identification with an `Id` element.
+const DOCUMENT_MSG_ID_X_PATH: &str = "normalize-space(((//*[local-name()='MsgId'])[1]/*[local-name()='Id'] | (//*[local-name()='MsgId'])[1][not(*)])[1])";
 
 /// Default Envelope Type
 pub type DefaultMsgEnvlp<Sig> = nvlp::BizMsgEnvlp<head::AppHdr<Sig, Sig>, Document, Dmkr, Dmkr>;
@@ -108,6 +120,34 @@ pub enum Error {
     /// Signing Error
     #[error(transparent)]
     Signing(#[from] signature::Error),
+    /// Application Header Error
+    #[error(transparent)]
+    Header(#[from] crate::header::Error),
+    /// Encryption Error
+    #[cfg(feature = "xmlenc")]
+    #[error(transparent)]
+    Encryption(#[from] crate::crypto::xmlenc::Error),
+}
+
+/// Header profile used to populate the business service `BizSvc` and
+/// market practice `MktPrctc` of the application header, e.g. for CBPR+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderProfile {
+    /// Business service, e.g. `swift.cbprplus.02`
+    pub biz_svc: Option<head::Max35Text>,
+    /// Market practice registry and identifier
+    pub mkt_prctc: Option<head::ImplementationSpecification1>,
+}
+
+/// Mismatch between an application header element and the value derived from the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderMismatch {
+    /// Header element name, e.g. `MsgDefIdr`
+    pub element: &'static str,
+    /// Value of the header element
+    pub header: String,
+    /// Value derived from the document
+    pub document: String,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -135,8 +175,9 @@ pub struct Message<
     /// use the `to_xml()` method to get the XML string representation of the message
     /// inner type.
     pub xml_string: &'a str,
-    /// Internal representation of the message envelope
-    pub inner: nvlp::BizMsgEnvlp<head::AppHdr<Sig, Sig>, Doc, Dmkr, Dmkr>,
+    /// Internal representation of the message envelope.
+    /// The application header is kept in its header version.
+    pub inner: nvlp::BizMsgEnvlp<AnyAppHdr<Sig>, Doc, Dmkr, Dmkr>,
 }
 
 impl<'a, Doc, Sig> Message<'a, Doc, Sig>
@@ -164,14 +205,30 @@ where
         envlp.set_namespace()
     }
 
-    /// Return the application header from the message envelope
+    /// Return the `head.001.001.03` application header from the message envelope.
+    /// Returns `None` for earlier header versions, use `any_app_hdr()` to
+    /// return the header in any version.
     pub fn app_hdr(&self) -> Option<head::AppHdr<Sig, Sig>> {
-        self.inner.value.hdr.clone().map(|hdr| hdr.value)
+        match self.any_app_hdr() {
+            Some(AnyAppHdr::V03(app_hdr)) => Some(app_hdr),
+            _ => None,
+        }
     }
 
     /// Set the application header AppHdr of the message
     /// Note, this will overwrite the existing AppHdr
     pub fn set_app_hdr(self, app_hdr: head::AppHdr<Sig, Sig>) -> Self {
+        self.set_any_app_hdr(AnyAppHdr::V03(app_hdr))
+    }
+
+    /// Return the application header from the message envelope in its header version.
+    pub fn any_app_hdr(&self) -> Option<AnyAppHdr<Sig>> {
+        self.inner.value.hdr.clone().map(|hdr| hdr.value)
+    }
+
+    /// Set the application header AppHdr of the message in any header version.
+    /// Note, this will overwrite the existing AppHdr
+    pub fn set_any_app_hdr(self, app_hdr: AnyAppHdr<Sig>) -> Self {
         let mut msg = self;
 
         // Set the AppHdr
@@ -180,12 +237,39 @@ where
         msg
     }
 
+    /// Update the application header of the message in its header version,
+    /// starting from a default `head.001.001.03` header if the message has none.
+    fn update_any_app_hdr(self, update: impl FnOnce(&mut AnyAppHdr<Sig>)) -> Self {
+        let mut msg = self;
+
+        update(
+            &mut msg
+                .inner
+                .value
+                .hdr
+                .get_or_insert_with(Default::default)
+                .value,
+        );
+
+        msg
+    }
+
+    /// Update the `head.001.001.03` application header of the message.
+    ///
+    /// The parties, header profile and related headers are `head.001.001.03`
+    /// types, so earlier header versions are left unchanged. Build the header
+    /// in `head.001.001.03` and convert it with `set_header_version()`.
+    fn update_app_hdr(self, update: impl FnOnce(&mut head::AppHdr<Sig, Sig>)) -> Self {
+        self.update_any_app_hdr(|app_hdr| {
+            if let AnyAppHdr::V03(app_hdr) = app_hdr {
+                update(app_hdr);
+            }
+        })
+    }
+
     /// Set the recipient of the message
     pub fn set_recipient(self, recipient: head::Party44Choice) -> Self {
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.to = recipient;
-
-        self.set_app_hdr(app_hdr)
+        self.update_app_hdr(|app_hdr| app_hdr.value.to = recipient)
     }
 
     /// Set the recipient organization id of the message.
@@ -252,10 +336,7 @@ where
 
     /// Set the sender of the message
     pub fn set_sender(self, sender: head::Party44Choice) -> Self {
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.fr = sender;
-
-        self.set_app_hdr(app_hdr)
+        self.update_app_hdr(|app_hdr| app_hdr.value.fr = sender)
     }
 
     /// Set the sender organization id of the message.
@@ -322,29 +403,18 @@ where
 
     /// e.g. `Document`
     pub fn set_biz_msg_idr(self, idr: head::Max35Text) -> Self {
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.biz_msg_idr = idr;
-
-        self.set_app_hdr(app_hdr)
+        self.update_any_app_hdr(|app_hdr| app_hdr.set_biz_msg_idr(idr.value))
     }
 
-    /// e.g. `pacs.008.001.07`
+    /// e.g. `pacs.008.001.10`
     pub fn set_msg_def_idr(self, idr: head::Max35Text) -> Self {
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.msg_def_idr = idr;
-
-        self.set_app_hdr(app_hdr)
+        self.update_any_app_hdr(|app_hdr| app_hdr.set_msg_def_idr(idr.value))
     }
 
     /// Set the created date time of the message.
     /// This will be set to the current UTC time.
     pub fn set_cre_dt(self) -> Self {
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.cre_dt = head::IsoDateTime {
-            value: chrono::Utc::now(),
-        };
-
-        self.set_app_hdr(app_hdr)
+        self.update_any_app_hdr(|app_hdr| app_hdr.set_cre_dt(chrono::Utc::now()))
     }
 
     /// Set the xml namespace of the message and business header.
@@ -354,11 +424,17 @@ where
         // Set the envelope namespace
         envlp.inner.value.xmlns = nvlp::namespace();
 
-        // Set the header namespace
-        let mut app_hdr = envlp.app_hdr().unwrap_or_default();
-        app_hdr.value.xmlns = head::namespace();
+        // Set the header namespace of the header version
+        envlp.update_any_app_hdr(AnyAppHdr::set_namespace)
+    }
 
-        envlp.set_app_hdr(app_hdr)
+    /// Set the business service `BizSvc` and market practice `MktPrctc`
+    /// of the application header from a header profile.
+    pub fn set_header_profile(self, profile: &HeaderProfile) -> Self {
+        self.update_app_hdr(|app_hdr| {
+            app_hdr.value.biz_svc = profile.biz_svc.clone();
+            app_hdr.value.mkt_prctc = profile.mkt_prctc.clone();
+        })
     }
 
     /// Set the document of the message.
@@ -366,11 +442,128 @@ where
     /// By default, all root iso-20022 message documents have
     /// an attribute field, `xmlns`, that is used to set the document namespace.
     /// The document namespace must be set before calling this method.
+    ///
+    /// The application header is populated from the document:
+    /// `MsgDefIdr` is derived from the document namespace, `BizMsgIdr`
+    /// is copied from the document `MsgId` and `CreDt` is set to the current
+    /// UTC time. Header values that cannot be derived from the document are
+    /// left unchanged.
     pub fn set_document(self, doc: Doc) -> Self {
         let mut envlp = self;
         envlp.inner.value.doc.value = doc;
 
-        envlp
+        let msg_def_idr = envlp.document_msg_def_idr();
+        let msg_id = envlp.document_msg_id();
+
+        envlp.update_any_app_hdr(|app_hdr| {
+            if let Some(msg_def_idr) = msg_def_idr {
+                app_hdr.set_msg_def_idr(msg_def_idr);
+            }
+
+            if let Some(msg_id) = msg_id {
+                app_hdr.set_biz_msg_idr(msg_id);
+            }
+
+            app_hdr.set_cre_dt(chrono::Utc::now());
+        })
+    }
+
+    /// Return a new message for the document, with the application header
+    /// populated from the document and the header profile.
+    /// ```rust
+    /// use iso_20022_sdk::prelude::*;
+    ///
+    /// let profile = HeaderProfile {
+    ///     biz_svc: Some(head::Max35Text { value: "swift.cbprplus.02".to_string() }),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let msg = Message::for_document(Document::from_namespace("pacs.008.001.10"), &profile);
+    /// ```
+    pub fn for_document(doc: Doc, profile: &HeaderProfile) -> Self {
+        Self::builder()
+            .set_header_profile(profile)
+            .set_document(doc)
+    }
+
+    /// Return the message definition identifier of the document, derived
+    /// from the document namespace, e.g. `pacs.008.001.10`
+    pub fn document_msg_def_idr(&self) -> Option<String> {
+        self.evaluate_document(DOCUMENT_NAMESPACE_X_PATH)
+            .map(|ns| ns.replace(crate::documents::DEFAULT_XLMNS_PREFIX, ""))
+    }
+
+    /// Return the message identification `MsgId` of the document.
+    pub fn document_msg_id(&self) -> Option<String> {
+        self.evaluate_document(DOCUMENT_MSG_ID_X_PATH)
+    }
+
+    /// Check the application header against the document of an inbound message.
+    /// Returns the list of header elements that do not match the values
+    /// derived from the document. An empty list means the header is consistent.
+    pub fn check_header_consistency(&self) -> Vec<HeaderMismatch> {
+        let app_hdr = self.any_app_hdr().unwrap_or_default();
+
+        [
+            (
+                "MsgDefIdr",
+                app_hdr.msg_def_idr().to_string(),
+                self.document_msg_def_idr(),
+            ),
+            (
+                "BizMsgIdr",
+                app_hdr.biz_msg_idr().to_string(),
+                self.document_msg_id(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(element, header, document)| match document {
+            Some(document) if document != header => Some(HeaderMismatch {
+                element,
+                header,
+                document,
+            }),
+            _ => None,
+        })
+        .collect()
+    }
+
+    /// Evaluate an xpath expression against the serialized document,
+    /// returning `None` if the document cannot be evaluated or the result is empty.
+    fn evaluate_document(&self, x_path: &str) -> Option<String> {
+        let xml = quick_xml::se::to_string(&self.document()).ok()?;
+        let package = parser::parse(&xml).ok()?;
+        let value = evaluate_xpath(&package.as_document(), x_path)
+            .ok()?
+            .into_string();
+
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Evaluate an xpath expression against the serialized document,
+    /// returning the non-empty string values of the selected nodes.
+    pub(crate) fn document_values(&self, x_path: &str) -> Vec<String> {
+        let values = || -> Option<Vec<String>> {
+            let xml = quick_xml::se::to_string(&self.document()).ok()?;
+            let package = parser::parse(&xml).ok()?;
+
+            let values = match evaluate_xpath(&package.as_document(), x_path).ok()? {
+                sxd_xpath::Value::Nodeset(nodes) => nodes
+                    .document_order()
+                    .iter()
+                    .map(|node| node.string_value().trim().to_string())
+                    .collect(),
+                value => vec![value.into_string()],
+            };
+
+            Some(values)
+        };
+
+        values()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|value| !value.is_empty())
+            .collect()
     }
 
     /// Sign the document at an optional xpath, e.g. `/Document/AcctOpngInstr`
@@ -402,20 +595,68 @@ where
 
         // signature.set_signed_info(uri, x_path_transformations, digest_value, public_key)
 
-        // Set the signature in the application header
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.sgntr = Some(head::SignatureEnvelope { value: signature });
-
-        // Set the application header and return the envelope
-        Ok(self.set_app_hdr(app_hdr))
+        // Set the signature in the application header and return the envelope
+        Ok(self.update_any_app_hdr(|app_hdr| app_hdr.set_signature(signature)))
     }
 
     /// Set the related business reference of the message.
     pub fn set_rltd(self, rltd: head::BusinessApplicationHeader7<Sig>) -> Self {
-        let mut app_hdr = self.app_hdr().unwrap_or_default();
-        app_hdr.value.rltd.push(rltd);
+        self.update_app_hdr(|app_hdr| app_hdr.value.rltd.push(rltd))
+    }
 
-        self.set_app_hdr(app_hdr)
+    /// Return the `head.001.001.03` application header of the message as a
+    /// related header `Rltd`, e.g. to reference the message in a reply.
+    pub fn related_header(&self) -> head::BusinessApplicationHeader7<Sig> {
+        let app_hdr = self.app_hdr().unwrap_or_default().value;
+
+        head::BusinessApplicationHeader7 {
+            char_set: app_hdr.char_set,
+            fr: app_hdr.fr,
+            to: app_hdr.to,
+            biz_msg_idr: app_hdr.biz_msg_idr,
+            msg_def_idr: app_hdr.msg_def_idr,
+            biz_svc: app_hdr.biz_svc,
+            mkt_prctc: app_hdr.mkt_prctc,
+            cre_dt: app_hdr.cre_dt,
+            biz_prcg_dt: app_hdr.biz_prcg_dt,
+            cpy_dplct: app_hdr.cpy_dplct,
+            pssbl_dplct: app_hdr.pssbl_dplct,
+            prty: app_hdr.prty,
+            sgntr: app_hdr.sgntr,
+        }
+    }
+
+    /// Return a reply to the message with the document, e.g. a `pacs.002` status
+    /// report in reply to a `pacs.008`.
+    ///
+    /// The reply header has the header version of the message. The sender `Fr`
+    /// and recipient `To` of the message are swapped, the `BizMsgIdr` is set to
+    /// a new identifier (or the `MsgId` of the document) and the application
+    /// header of the message is added as a related header `Rltd`. The business
+    /// service and market practice are carried over, while the `CpyDplct` and
+    /// `PssblDplct` flags of the reply are cleared.
+    /// ```rust
+    /// use iso_20022_sdk::prelude::*;
+    ///
+    /// let msg = Message::<Document, EcdsaSignature>::from_xml(&xml)?;
+    /// let reply = msg.reply_with(Document::from_namespace("pacs.002.001.12"));
+    /// ```
+    pub fn reply_with<Reply>(&self, doc: Reply) -> Message<'a, Reply, Sig>
+    where
+        Reply: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate,
+    {
+        let mut app_hdr = self.any_app_hdr().unwrap_or_default().reply();
+        app_hdr.set_biz_msg_idr(uuid::Uuid::new_v4().simple().to_string());
+
+        Message::<Reply, Sig>::builder()
+            .set_any_app_hdr(app_hdr)
+            .set_document(doc)
     }
 
     /// Return the envelope document.
@@ -463,6 +704,11 @@ where
         Ok(data.as_bytes().to_vec())
     }
 
+    /// Return the version of the application header.
+    pub fn header_version(&self) -> Option<HeaderVersion> {
+        self.any_app_hdr().map(|app_hdr| app_hdr.version())
+    }
+
     /// Return the serialized xml string of the inner type.
     pub fn to_xml(&self) -> Result<String, Error> {
         let xml_string = quick_xml::se::to_string(&self.inner)?;
@@ -471,8 +717,13 @@ where
     }
 
     /// parse the header from the xml string
+    /// The application header version is detected from the `AppHdr` namespace.
     pub fn from_xml(xml_string: &'a str) -> Result<Self, Error> {
-        let inner = quick_xml::de::from_str(xml_string)?;
+        let inner = match HeaderVersion::detect(xml_string) {
+            Ok(HeaderVersion::V01) => Self::parse_envelope(xml_string, AnyAppHdr::V01)?,
+            Ok(HeaderVersion::V02) => Self::parse_envelope(xml_string, AnyAppHdr::V02)?,
+            _ => Self::parse_envelope(xml_string, AnyAppHdr::V03)?,
+        };
 
         println!("inner: {:?}", inner);
 
@@ -486,6 +737,42 @@ where
         Ok(msg)
     }
 
+    /// Deserialize the envelope with the application header type of the
+    /// header version, e.g. `head_001_001_02::AppHdr`
+    fn parse_envelope<Hdr>(
+        xml_string: &'a str,
+        any_app_hdr: fn(Hdr) -> AnyAppHdr<Sig>,
+    ) -> Result<nvlp::BizMsgEnvlp<AnyAppHdr<Sig>, Doc, Dmkr, Dmkr>, Error>
+    where
+        Hdr: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::Deserialize<'a>
+            + ::validator::Validate,
+    {
+        // The header is flattened into `Hdr` and its types are transparent, so it is
+        // deserialized from the `AppHdr` element rather than with the envelope.
+        let envlp: nvlp::BizMsgEnvlp<Dmkr, Doc, Dmkr, Dmkr> = quick_xml::de::from_str(xml_string)?;
+        let hdr = match app_hdr_xml(xml_string)? {
+            Some(app_hdr) => Some(nvlp::LaxPayload {
+                value: any_app_hdr(quick_xml::de::from_str::<Hdr>(app_hdr)?),
+            }),
+            None => None,
+        };
+
+        Ok(nvlp::BizMsgEnvlp {
+            value: nvlp::BusinessMessageEnvelopeV01 {
+                hdr,
+                doc: envlp.value.doc,
+                r#ref: envlp.value.r#ref,
+                splmtry_data: envlp.value.splmtry_data,
+                xmlns: envlp.value.xmlns,
+            },
+        })
+    }
+
     fn parse(&mut self) -> Result<(), Error> {
         // Use xml-reader to parse the xml string and find the `MsgDefIdr` element in the `head.001.001.03` namespace.
         let buf_reader = BufReader::new(self.xml_string.as_bytes());
@@ -522,6 +809,173 @@ where
     }
 }
 
+impl<'a, Doc, Sig> Message<'a, Doc, Sig>
+where
+    Doc: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::Deserialize<'a>
+        + ::validator::Validate,
+    Sig: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::de::DeserializeOwned
+        + ::validator::Validate
+        + crate::crypto::XmlSignature,
+{
+    /// Convert the application header to the header version, e.g. `head.001.001.02` for CBPR+.
+    /// Returns the message and the paths of the header elements that are not
+    /// supported by the version and were dropped, e.g. `AppHdr/MktPrctc/Id`
+    ///
+    /// The conversion round-trips the header through xml, so the signature type
+    /// must be deserializable from an owned string.
+    pub fn set_header_version(self, version: HeaderVersion) -> Result<(Self, Vec<String>), Error> {
+        let (app_hdr, dropped) = self.any_app_hdr().unwrap_or_default().convert(version)?;
+
+        Ok((self.set_any_app_hdr(app_hdr), dropped))
+    }
+}
+
+#[cfg(feature = "xmlenc")]
+impl<'a, Doc, Sig> Message<'a, Doc, Sig>
+where
+    Doc: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::Deserialize<'a>
+        + ::validator::Validate,
+    Sig: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::Deserialize<'a>
+        + ::validator::Validate
+        + crate::crypto::XmlSignature,
+{
+    /// Encrypt the envelope document for the recipient, replacing the `Doc`
+    /// payload with an `xenc:EncryptedData` element.
+    ///
+    /// The application header is left in clear text, so a document signed
+    /// with `sign_document()` before encryption keeps its signature
+    /// (sign-then-encrypt). Use `crypto::xmlenc::encrypt_xml()` to encrypt
+    /// individual elements selected by an XPath expression.
+    ///
+    /// The source `xml_string` still holds the plaintext document and is
+    /// not carried over; use `to_xml()` to serialize the encrypted message.
+    /// ```rust
+    /// use iso_20022_sdk::prelude::*;
+    /// use iso_20022_sdk::crypto::xmlenc::Recipient;
+    ///
+    /// let msg = Message::<_>::builder()
+    ///     .set_document(doc)
+    ///     .sign_document(&signer, None, vec![])?
+    ///     .encrypt_document(&Recipient::from_certificate_pem(&certificate)?)?;
+    /// ```
+    pub fn encrypt_document(
+        self,
+        recipient: &crate::crypto::xmlenc::Recipient,
+    ) -> Result<Message<'a, iso_20022_dsig::xenc::EncryptedData, Sig>, Error> {
+        let plaintext = quick_xml::se::to_string(&self.document())?;
+        let encrypted = crate::crypto::xmlenc::encrypt(
+            plaintext.as_bytes(),
+            recipient,
+            Some(iso_20022_dsig::xenc::TYPE_ELEMENT),
+        )?;
+
+        Ok(Message {
+            xml_string: "",
+            inner: nvlp::BizMsgEnvlp {
+                value: nvlp::BusinessMessageEnvelopeV01 {
+                    hdr: self.inner.value.hdr,
+                    doc: nvlp::LaxPayload { value: encrypted },
+                    r#ref: self.inner.value.r#ref,
+                    splmtry_data: self.inner.value.splmtry_data,
+                    xmlns: self.inner.value.xmlns,
+                },
+            },
+        })
+    }
+}
+
+#[cfg(feature = "xmlenc")]
+impl<'a, Sig> Message<'a, iso_20022_dsig::xenc::EncryptedData, Sig>
+where
+    Sig: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::Deserialize<'a>
+        + ::validator::Validate
+        + crate::crypto::XmlSignature,
+{
+    /// Decrypt the `xenc:EncryptedData` payload of the envelope into the document type.
+    /// Verify any header signature after decryption.
+    ///
+    /// The source `xml_string` of the encrypted message is not carried over,
+    /// use `to_xml()` to serialize the decrypted message.
+    pub fn decrypt_document<Doc>(
+        self,
+        key: &crate::crypto::xmlenc::DecryptionKey,
+    ) -> Result<Message<'a, Doc, Sig>, Error>
+    where
+        Doc: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::de::DeserializeOwned
+            + ::validator::Validate,
+    {
+        let plaintext = crate::crypto::xmlenc::decrypt(&self.inner.value.doc.value, key)?;
+        let plaintext = String::from_utf8(plaintext).map_err(crate::crypto::xmlenc::Error::from)?;
+        let doc = quick_xml::de::from_str(&plaintext)?;
+
+        Ok(Message {
+            xml_string: "",
+            inner: nvlp::BizMsgEnvlp {
+                value: nvlp::BusinessMessageEnvelopeV01 {
+                    hdr: self.inner.value.hdr,
+                    doc: nvlp::LaxPayload { value: doc },
+                    r#ref: self.inner.value.r#ref,
+                    splmtry_data: self.inner.value.splmtry_data,
+                    xmlns: self.inner.value.xmlns,
+                },
+            },
+        })
+    }
+}
+
+/// Return the `AppHdr` element of the envelope header, if any.
+fn app_hdr_xml(xml_string: &str) -> Result<Option<&str>, Error> {
+    let mut reader = quick_xml::Reader::from_str(xml_string);
+
+    loop {
+        let start = reader.buffer_position();
+
+        match reader.read_event().map_err(quick_xml::de::DeError::from)? {
+            quick_xml::events::Event::Start(element)
+                if element.local_name().as_ref() == b"AppHdr" =>
+            {
+                reader
+                    .read_to_end(element.name())
+                    .map_err(quick_xml::de::DeError::from)?;
+
+                return Ok(Some(&xml_string[start..reader.buffer_position()]));
+            }
+            quick_xml::events::Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -569,6 +1023,170 @@ mod tests {
         Ok(())
     }
 
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        PartialEq,
+        ::serde::Serialize,
+        ::serde::Deserialize,
+        ::validator::Validate,
+    )]
+    #[serde(rename = "Document")]
+    struct TestDocument {
+        #[serde(rename = "@xmlns")]
+        xmlns: String,
+        #[serde(rename = "GrpHdr")]
+        grp_hdr: TestGroupHeader,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    struct TestGroupHeader {
+        #[serde(rename = "MsgId")]
+        msg_id: String,
+    }
+
+    #[test]
+    fn test_set_document_header() -> Result<(), Error> {
+        let doc = TestDocument {
+            xmlns: "urn:iso:std:iso:20022:tech:xsd:pacs.008.001.10".to_string(),
+            grp_hdr: TestGroupHeader {
+                msg_id: "MSG-1".to_string(),
+            },
+        };
+
+        let profile = HeaderProfile {
+            biz_svc: Some(head::Max35Text {
+                value: "swift.cbprplus.02".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let msg = Message::<_, EcdsaSignature>::for_document(doc, &profile);
+        let app_hdr = msg.app_hdr().unwrap_or_default().value;
+
+        assert_eq!(app_hdr.msg_def_idr.value, "pacs.008.001.10");
+        assert_eq!(app_hdr.biz_msg_idr.value, "MSG-1");
+        assert_eq!(app_hdr.biz_svc, profile.biz_svc);
+        assert!(msg.check_header_consistency().is_empty());
+
+        let msg = msg.set_msg_def_idr(head::Max35Text {
+            value: "pacs.008.001.08".to_string(),
+        });
+
+        assert_eq!(
+            msg.check_header_consistency(),
+            vec![HeaderMismatch {
+                element: "MsgDefIdr",
+                header: "pacs.008.001.08".to_string(),
+                document: "pacs.008.001.10".to_string(),
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply_with() -> Result<(), Error> {
+        let org_id = |id: &str| head::OrganisationIdentification29 {
+            othr: vec![head::GenericOrganisationIdentification1 {
+                id: head::Max35Text {
+                    value: id.to_string(),
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let msg = Message::<Dmkr, EcdsaSignature>::builder()
+            .set_sender_org_id(org_id("SENDER"))
+            .set_recipient_org_id(org_id("RECIPIENT"))
+            .set_biz_msg_idr(head::Max35Text {
+                value: "MSG-1".to_string(),
+            });
+
+        let mut app_hdr = msg.app_hdr().unwrap_or_default();
+        app_hdr.value.pssbl_dplct = Some(head::YesNoIndicator { value: true });
+        let msg = msg.set_app_hdr(app_hdr);
+
+        let reply = msg.reply_with(Dmkr::default());
+        let original = msg.app_hdr().unwrap_or_default().value;
+        let app_hdr = reply.app_hdr().unwrap_or_default().value;
+
+        assert_eq!(app_hdr.fr, original.to);
+        assert_eq!(app_hdr.to, original.fr);
+        assert_ne!(app_hdr.biz_msg_idr, original.biz_msg_idr);
+        assert_eq!(app_hdr.pssbl_dplct, None);
+        assert_eq!(app_hdr.rltd, vec![msg.related_header()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_version() -> Result<(), Error> {
+        let xml = r#"<BizMsgEnvlp xmlns="urn:iso:std:iso:20022:tech:xsd:nvlp.001.001.01"><Hdr><AppHdr xmlns="urn:iso:std:iso:20022:tech:xsd:head.001.001.01"><Fr><FIId><FinInstnId><BICFI>AAAAUS33XXX</BICFI></FinInstnId></FIId></Fr><To><FIId><FinInstnId><BICFI>BBBBGB22XXX</BICFI></FinInstnId></FIId></To><BizMsgIdr>MSG-1</BizMsgIdr><MsgDefIdr>pacs.008.001.08</MsgDefIdr><CreDt>2023-04-18T17:02:14Z</CreDt></AppHdr></Hdr><Doc><TestPayload>Hello, World!</TestPayload></Doc></BizMsgEnvlp>"#;
+
+        let msg = Message::<Dmkr, EcdsaSignature>::from_xml(xml)?;
+        assert_eq!(msg.header_version(), Some(HeaderVersion::V01));
+        assert_eq!(msg.app_hdr(), None);
+        assert!(msg.to_xml()?.contains("head.001.001.01"));
+
+        let app_hdr = msg.any_app_hdr().unwrap_or_default();
+        assert_eq!(app_hdr.sender().bic.as_deref(), Some("AAAAUS33XXX"));
+        assert_eq!(app_hdr.receiver().bic.as_deref(), Some("BBBBGB22XXX"));
+
+        let (msg, dropped) = msg.set_header_version(HeaderVersion::V03)?;
+        assert!(dropped.is_empty());
+
+        let mut app_hdr = msg.app_hdr().unwrap_or_default();
+        app_hdr.value.biz_prcg_dt = Some(app_hdr.value.cre_dt.clone());
+
+        let (msg, dropped) = msg
+            .set_app_hdr(app_hdr)
+            .set_header_version(HeaderVersion::V02)?;
+
+        assert!(dropped.is_empty());
+        assert_eq!(msg.header_version(), Some(HeaderVersion::V02));
+
+        let (msg, dropped) = msg.set_header_version(HeaderVersion::V01)?;
+        assert_eq!(dropped, vec!["AppHdr/BizPrcgDt"]);
+        assert_eq!(msg.any_app_hdr().unwrap_or_default().biz_prcg_dt(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_version_round_trip() -> Result<(), Error> {
+        let rltd = "<Rltd><Fr><FIId><FinInstnId><BICFI>BBBBGB22XXX</BICFI></FinInstnId></FIId></Fr><To><FIId><FinInstnId><BICFI>AAAAUS33XXX</BICFI></FinInstnId></FIId></To><BizMsgIdr>MSG-0</BizMsgIdr><MsgDefIdr>pacs.008.001.08</MsgDefIdr><CreDt>2023-04-18T17:02:14Z</CreDt></Rltd>";
+        let xml = format!(
+            r#"<BizMsgEnvlp xmlns="urn:iso:std:iso:20022:tech:xsd:nvlp.001.001.01"><Hdr><AppHdr xmlns="urn:iso:std:iso:20022:tech:xsd:head.001.001.02"><Fr><FIId><FinInstnId><BICFI>AAAAUS33XXX</BICFI></FinInstnId></FIId></Fr><To><FIId><FinInstnId><BICFI>BBBBGB22XXX</BICFI></FinInstnId></FIId></To><BizMsgIdr>MSG-1</BizMsgIdr><MsgDefIdr>pacs.002.001.10</MsgDefIdr><CreDt>2023-04-18T17:02:14Z</CreDt>{rltd}{rltd}</AppHdr></Hdr><Doc><TestPayload>Hello, World!</TestPayload></Doc></BizMsgEnvlp>"#
+        );
+
+        let msg = Message::<Dmkr, EcdsaSignature>::from_xml(&xml)?;
+        assert_eq!(msg.header_version(), Some(HeaderVersion::V02));
+
+        // The header is kept in its version, including both related headers
+        let xml = msg.to_xml()?;
+        assert!(xml.contains("head.001.001.02"));
+        assert!(!xml.contains("head.001.001.03"));
+        assert_eq!(xml.matches("<Rltd>").count(), 2);
+
+        // The reply keeps the header version of the message
+        let reply = msg.reply_with(Dmkr::default());
+        assert_eq!(reply.header_version(), Some(HeaderVersion::V02));
+        assert_eq!(
+            reply
+                .any_app_hdr()
+                .unwrap_or_default()
+                .sender()
+                .bic
+                .as_deref(),
+            Some("BBBBGB22XXX")
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_message() -> Result<(), Error> {
         let file = std::fs::read_to_string("examples/nvlp.xml").expect("Unable to read file");
@@ -578,4 +1196,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "xmlenc")]
+    #[test]
+    fn test_encrypt_document() -> Result<(), Error> {
+        use crate::crypto::xmlenc::{DecryptionKey, Recipient};
+
+        let secret_key = p256::SecretKey::random(&mut rand_core::OsRng);
+
+        let xml = Message::<Dmkr, EcdsaSignature>::builder()
+            .set_biz_msg_idr(head::Max35Text {
+                value: "MSG-1".to_string(),
+            })
+            .set_document(Dmkr {
+                value: Some("confidential".to_string()),
+            })
+            .to_xml()?;
+
+        let msg = Message::<Dmkr, EcdsaSignature>::from_xml(&xml)?
+            .encrypt_document(&Recipient::p256(secret_key.public_key()))?;
+        assert!(!msg.xml_string.contains("confidential"));
+
+        let xml = msg.to_xml()?;
+        assert!(!xml.contains("confidential"));
+        assert!(xml.contains("MSG-1"));
+
+        let msg = msg.decrypt_document::<Dmkr>(&DecryptionKey::P256(secret_key))?;
+        assert_eq!(msg.document().value.as_deref(), Some("confidential"));
+
+        Ok(())
+    }
 }
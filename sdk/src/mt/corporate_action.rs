@@ -0,0 +1,1260 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Corporate Action Translation
+//!
+//! Translates the ISO 15022 corporate action notification `MT564` into a `seev.031.001.12`
+//! corporate action notification, and the corporate action confirmation `MT566` into a
+//! `seev.036.001.13` corporate action movement confirmation, following the SMPG corporate
+//! action market practice.
+//!
+//! | MT564                                    | seev.031.001.12                                |
+//! |------------------------------------------|------------------------------------------------|
+//! | `GENL` `20C::CORP`, `20C::COAF`          | `CorpActnGnlInf/CorpActnEvtId`, `OffclCorpActnEvtId` |
+//! | `GENL` `23G`, `25D::PROC`                | `NtfctnGnlInf/NtfctnTp`, `PrcgSts`             |
+//! | `GENL` `22F::CAEV`, `22F::CAMV`          | `CorpActnGnlInf/EvtTp`, `MndtryVlntryEvtTp`    |
+//! | `GENL/LINK` `20C::PREV`                  | `PrvsNtfctnId`                                 |
+//! | `GENL/LINK` `20C::RELA` of an `MT565`    | `InstrId`                                      |
+//! | `USECU` `35B`                            | `CorpActnGnlInf/UndrlygScty/FinInstrmId`       |
+//! | `USECU/ACCTINFO` `97a::SAFE`             | `AcctDtls`                                     |
+//! | `CADETL` `98a::ANOU`, `XDTE`, `RDTE`, `EFFD`, `PAYD` | `CorpActnDtls/DtDtls`              |
+//! | `CADETL` `22F::DIVI`                     | `CorpActnDtls/DvddTp`                          |
+//! | `CAOPTN` `13A::CAON`, `22F::CAOP`        | `CorpActnOptnDtls/OptnNb`, `OptnTp`            |
+//! | `CAOPTN` `11A::OPTN`, `17B::DFLT`        | `CorpActnOptnDtls/CcyOptn`, `DfltPrcgOrStgInstr` |
+//! | `CAOPTN` `98a::MKDT`, `RDDT`, `EXPI`     | `CorpActnOptnDtls/DtDtls`                      |
+//! | `CAOPTN/SECMOVE`                         | `CorpActnOptnDtls/SctiesMvmntDtls`             |
+//! | `CAOPTN/CASHMOVE`                        | `CorpActnOptnDtls/CshMvmntDtls`                |
+//!
+//! | MT566                                    | seev.036.001.13                                |
+//! |------------------------------------------|------------------------------------------------|
+//! | `GENL` `20C::CORP`, `20C::COAF`          | `CorpActnGnlInf/CorpActnEvtId`, `OffclCorpActnEvtId` |
+//! | `GENL` `22F::CAEV`                       | `CorpActnGnlInf/EvtTp`                         |
+//! | `GENL/LINK` `20C::RELA`                  | `NtfctnId`, or `InstrId` of an `MT565`         |
+//! | `USECU` `35B`                            | `CorpActnGnlInf/FinInstrmId`                   |
+//! | `USECU/ACCTINFO` `97A::SAFE`, `93B::CONB` | `AcctDtls/SfkpgAcct`, `AcctDtls/Bal/ConfdBal` |
+//! | `CADETL` `98a::XDTE`, `RDTE`             | `CorpActnDtls/DtDtls`                          |
+//! | `CACONF` `13A::CAON`, `22F::CAOP`, `11A::OPTN` | `CorpActnConfDtls/OptnNb`, `OptnTp`, `CcyOptn` |
+//! | `CACONF/SECMOVE`                         | `CorpActnConfDtls/SctiesMvmntDtls`             |
+//! | `CACONF/CASHMOVE`                        | `CorpActnConfDtls/CshMvmntDtls`                |
+//!
+//! The movements of securities map their credit or debit `22H::CRDB`, the financial instrument
+//! `35B`, its quantity `36B::ENTL` or `36B::PSTA`, and their payment and posting dates
+//! `98a::PAYD` and `98a::POST`, and the movements of cash their credit or debit, the cash
+//! account `97a::CASH`, the amounts `19B::ENTL`, `PSTA`, `GRSS` and `NETT`, the dates
+//! `98a::PAYD`, `POST` and `VALU`, and the rates `92F::GRSS`, `92F::NETT` and `92A::TAXR`.
+//!
+//! The message reference `20C::SEME` and the preparation date `98a::PREP` belong to the
+//! business application header of the ISO 20022 message, and are reported, as the other
+//! qualified fields without counterpart, e.g. `:22F::ADDB//CAPA`, as
+//! `TranslationIssue::Unmapped` with their sequence.
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+use iso_20022_seev::seev_031_001_12 as seev_031;
+use iso_20022_seev::seev_036_001_13 as seev_036;
+
+use super::iso15022::{
+    cash_account, choice, date_time, function, parse_code, security, GenericField, IsoTranslation,
+    Sequence,
+};
+use super::{convert, invalid_field, parse_amount, Block4, Error, Field};
+
+/// Function of a new confirmation of field `23G`.
+const NEW: &str = "NEWM";
+
+/// Message type of field `13A::LINK` of a corporate action instruction.
+const INSTRUCTION: &str = "565";
+
+lazy_static! {
+    /// Amount of field `19B`, or rate of field `92F`, e.g. `EUR1234,56`.
+    static ref AMOUNT: Regex = Regex::new(r"^([A-Z]{3})(\d{1,15},\d{0,})$").unwrap();
+    /// Quantity of field `36B`, or balance of field `93B`, with its sign, e.g. `UNIT/N1000,`.
+    static ref QUANTITY: Regex = Regex::new(r"^(UNIT|FAMT|AMOR)/(N)?(\d{1,15},\d{0,})$").unwrap();
+    /// Rate of field `92A`, with its sign, e.g. `N0,5`.
+    static ref RATE: Regex = Regex::new(r"^(N)?(\d{1,15},\d{0,})$").unwrap();
+    /// Ratio of field `92D`, of the quantities, e.g. `1,/10,`.
+    static ref RATIO: Regex = Regex::new(r"^(\d{1,15},\d{0,})/(\d{1,15},\d{0,})$").unwrap();
+}
+
+/// Translate the block 4 of an `MT564` into a `seev.031.001.12`.
+pub fn mt564_to_seev_031<A>(block4: &Block4) -> Result<IsoTranslation<seev_031::Document<A>>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut message = Sequence::parse(block4)?;
+    let mut ntfctn = seev_031::CorporateActionNotificationV12::<A>::default();
+
+    let genl = message.mandatory_sequence("16R:GENL")?;
+    ntfctn.corp_actn_gnl_inf.corp_actn_evt_id = seev_031::Max35Text {
+        value: genl.mandatory("20C::CORP")?.data,
+    };
+    ntfctn.corp_actn_gnl_inf.offcl_corp_actn_evt_id = genl
+        .take("20C::COAF")
+        .map(|field| seev_031::Max35Text { value: field.data });
+    let ntfctn_tp = genl.take_field("23G").ok_or(Error::MissingField("23G"))?;
+    ntfctn.ntfctn_gnl_inf.ntfctn_tp = parse_code(&ntfctn_tp.value)
+        .ok_or_else(|| invalid_field(&ntfctn_tp.tag, &ntfctn_tp.value))?;
+    ntfctn.ntfctn_gnl_inf.prcg_sts = processing_status(&genl.mandatory("25D::PROC")?)?;
+    ntfctn.corp_actn_gnl_inf.evt_tp = seev_031::CorporateActionEventType84Choice {
+        value: choice(&genl.mandatory("22F::CAEV")?)?,
+    };
+    ntfctn.corp_actn_gnl_inf.mndtry_vlntry_evt_tp =
+        seev_031::CorporateActionMandatoryVoluntary3Choice {
+            value: choice(&genl.mandatory("22F::CAMV")?)?,
+        };
+    for link in genl.sequences_mut("LINK") {
+        if let Some(prev) = link.take("20C::PREV") {
+            link.take("13A::LINK");
+            ntfctn.prvs_ntfctn_id = Some(seev_031::DocumentIdentification31 {
+                id: seev_031::Max35Text { value: prev.data },
+                lkg_tp: linkage_type(link)?,
+            });
+        } else if let Some(rela) = instruction(link) {
+            ntfctn.instr_id = Some(seev_031::DocumentIdentification9 {
+                id: seev_031::Max35Text { value: rela.data },
+            });
+        }
+    }
+
+    let usecu = message.mandatory_sequence("16R:USECU")?;
+    ntfctn.corp_actn_gnl_inf.undrlyg_scty.fin_instrm_id =
+        security(&usecu.take_field("35B").ok_or(Error::MissingField("35B"))?)?;
+    let acctinfo = usecu.mandatory_sequence("16R:ACCTINFO")?;
+    let sfkpg_acct = acctinfo.mandatory("97a::SAFE")?;
+    let acct_dtls = match sfkpg_acct.tag.as_str() {
+        "97C" => seev_031::AccountIdentification47ChoiceEnum {
+            for_all_accts: Some(seev_031::AccountIdentification10 {
+                id_cd: parse_code(&sfkpg_acct.data).ok_or_else(|| sfkpg_acct.invalid())?,
+            }),
+            ..Default::default()
+        },
+        _ => seev_031::AccountIdentification47ChoiceEnum {
+            accts_list_and_bal_dtls: Some(seev_031::AccountAndBalance47 {
+                sfkpg_acct: Some(seev_031::Max35Text {
+                    value: sfkpg_acct.data,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    };
+    ntfctn.acct_dtls = seev_031::AccountIdentification47Choice { value: acct_dtls };
+
+    if let Some(cadetl) = message.sequence_mut("CADETL") {
+        let dt_dtls = seev_031::CorporateActionDate61 {
+            anncmnt_dt: notification_date(cadetl, "ANOU")?,
+            ex_dvdd_dt: notification_date(cadetl, "XDTE")?,
+            rcrd_dt: notification_date(cadetl, "RDTE")?,
+            fctv_dt: notification_date(cadetl, "EFFD")?,
+            pmt_dt: notification_date(cadetl, "PAYD")?,
+            ..Default::default()
+        };
+        ntfctn.corp_actn_dtls = Some(seev_031::CorporateAction60 {
+            dt_dtls: (dt_dtls != Default::default()).then_some(dt_dtls),
+            dvdd_tp: cadetl
+                .take("22F::DIVI")
+                .map(|field| choice(&field))
+                .transpose()?
+                .map(|value| seev_031::DividendTypeFormat9Choice { value }),
+            ..Default::default()
+        });
+    }
+
+    for caoptn in message.sequences_mut("CAOPTN") {
+        ntfctn
+            .corp_actn_optn_dtls
+            .push(notification_option(caoptn)?);
+    }
+
+    let mut issues = vec![];
+    message.unmapped("", &mut issues);
+
+    Ok(IsoTranslation {
+        document: seev_031::Document {
+            corp_actn_ntfctn: ntfctn,
+            xmlns: seev_031::namespace(),
+        },
+        issues,
+    })
+}
+
+/// Translate the block 4 of an `MT566` into a `seev.036.001.13`.
+pub fn mt566_to_seev_036<A>(block4: &Block4) -> Result<IsoTranslation<seev_036::Document<A>>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut message = Sequence::parse(block4)?;
+    let mut conf = seev_036::CorporateActionMovementConfirmationV13::<A>::default();
+
+    let genl = message.mandatory_sequence("16R:GENL")?;
+    conf.corp_actn_gnl_inf.corp_actn_evt_id = seev_036::Max35Text {
+        value: genl.mandatory("20C::CORP")?.data,
+    };
+    conf.corp_actn_gnl_inf.offcl_corp_actn_evt_id = genl
+        .take("20C::COAF")
+        .map(|field| seev_036::Max35Text { value: field.data });
+    function(genl, NEW)?;
+    conf.corp_actn_gnl_inf.evt_tp = seev_036::CorporateActionEventType87Choice {
+        value: choice(&genl.mandatory("22F::CAEV")?)?,
+    };
+    for link in genl.sequences_mut("LINK") {
+        if let Some(rela) = instruction(link) {
+            conf.instr_id = Some(seev_036::DocumentIdentification9 {
+                id: seev_036::Max35Text { value: rela.data },
+            });
+        } else if let Some(rela) = link.take("20C::RELA") {
+            link.take("13A::LINK");
+            conf.ntfctn_id = Some(seev_036::DocumentIdentification31 {
+                id: seev_036::Max35Text { value: rela.data },
+                lkg_tp: linkage_type(link)?
+                    .map(|lkg_tp| convert(&lkg_tp.value))
+                    .transpose()?
+                    .map(|value| seev_036::ProcessingPosition7Choice { value }),
+            });
+        }
+    }
+
+    let usecu = message.mandatory_sequence("16R:USECU")?;
+    conf.corp_actn_gnl_inf.fin_instrm_id =
+        security(&usecu.take_field("35B").ok_or(Error::MissingField("35B"))?)?;
+    let acctinfo = usecu.mandatory_sequence("16R:ACCTINFO")?;
+    conf.acct_dtls.sfkpg_acct = acctinfo
+        .take("97A::SAFE")
+        .map(|field| seev_036::Max35Text { value: field.data });
+    let (qty, short) = quantity(&acctinfo.mandatory("93B::CONB")?)?;
+    let shrt_lng_pos = if short {
+        seev_036::ShortLong1Code::Shor
+    } else {
+        seev_036::ShortLong1Code::Long
+    };
+    conf.acct_dtls.bal.confd_bal = seev_036::BalanceFormat11Choice {
+        value: seev_036::BalanceFormat11ChoiceEnum {
+            bal: Some(seev_036::SignedQuantityFormat11 {
+                shrt_lng_pos,
+                qty_chc: seev_036::Quantity48Choice {
+                    value: seev_036::Quantity48ChoiceEnum {
+                        qty: Some(seev_036::FinancialInstrumentQuantity33Choice {
+                            value: convert(&qty)?,
+                        }),
+                        ..Default::default()
+                    },
+                },
+            }),
+            ..Default::default()
+        },
+    };
+
+    if let Some(cadetl) = message.sequence_mut("CADETL") {
+        let dt_dtls = seev_036::CorporateActionDate59 {
+            rcrd_dt: confirmation_date(cadetl, "RDTE")?,
+            ex_dvdd_dt: confirmation_date(cadetl, "XDTE")?,
+        };
+        conf.corp_actn_dtls = Some(seev_036::CorporateAction62 {
+            dt_dtls: (dt_dtls != Default::default()).then_some(dt_dtls),
+            ..Default::default()
+        });
+    }
+
+    let caconf = message.mandatory_sequence("16R:CACONF")?;
+    conf.corp_actn_conf_dtls = seev_036::CorporateActionOption195 {
+        optn_nb: seev_036::OptionNumber1Choice {
+            value: seev_036::OptionNumber1ChoiceEnum {
+                nb: Some(seev_036::Exact3NumericText {
+                    value: caconf.mandatory("13A::CAON")?.data,
+                }),
+                ..Default::default()
+            },
+        },
+        optn_tp: seev_036::CorporateActionOption33Choice {
+            value: choice(&caconf.mandatory("22F::CAOP")?)?,
+        },
+        ccy_optn: caconf
+            .take("11A::OPTN")
+            .map(|field| seev_036::ActiveCurrencyCode { value: field.data }),
+        ..Default::default()
+    };
+    for secmove in caconf.sequences_mut("SECMOVE") {
+        let option = confirmation_securities_option(secmove)?;
+        conf.corp_actn_conf_dtls.scties_mvmnt_dtls.push(option);
+    }
+    for cashmove in caconf.sequences_mut("CASHMOVE") {
+        let option = confirmation_cash_option(cashmove)?;
+        conf.corp_actn_conf_dtls.csh_mvmnt_dtls.push(option);
+    }
+
+    let mut issues = vec![];
+    message.unmapped("", &mut issues);
+
+    Ok(IsoTranslation {
+        document: seev_036::Document {
+            corp_actn_mvmnt_conf: conf,
+            xmlns: seev_036::namespace(),
+        },
+        issues,
+    })
+}
+
+/// Parse the processing status of field `25D::PROC`, i.e. whether the event details are
+/// complete and confirmed, e.g. `PREU` for preliminary and unconfirmed details.
+fn processing_status(
+    field: &GenericField,
+) -> Result<seev_031::CorporateActionProcessingStatus5Choice, Error> {
+    let value = match &field.issuer {
+        Some(issr) => seev_031::CorporateActionProcessingStatus5ChoiceEnum {
+            prtry: Some(seev_031::GenericIdentification30 {
+                id: seev_031::Exact4AlphaNumericText {
+                    value: field.data.clone(),
+                },
+                issr: seev_031::Max35Text {
+                    value: issr.clone(),
+                },
+                schme_nm: None,
+            }),
+            ..Default::default()
+        },
+        None => {
+            let (evt_cmpltns_sts, evt_conf_sts) = match field.data.as_str() {
+                "COMP" => (
+                    seev_031::EventCompletenessStatus1Code::Comp,
+                    seev_031::EventConfirmationStatus1Code::Conf,
+                ),
+                "COMU" => (
+                    seev_031::EventCompletenessStatus1Code::Comp,
+                    seev_031::EventConfirmationStatus1Code::Ucon,
+                ),
+                "PREC" => (
+                    seev_031::EventCompletenessStatus1Code::Inco,
+                    seev_031::EventConfirmationStatus1Code::Conf,
+                ),
+                "PREU" => (
+                    seev_031::EventCompletenessStatus1Code::Inco,
+                    seev_031::EventConfirmationStatus1Code::Ucon,
+                ),
+                _ => return Err(field.invalid()),
+            };
+
+            seev_031::CorporateActionProcessingStatus5ChoiceEnum {
+                cd: Some(seev_031::CorporateActionEventStatus1 {
+                    evt_cmpltns_sts,
+                    evt_conf_sts,
+                }),
+                ..Default::default()
+            }
+        }
+    };
+
+    Ok(seev_031::CorporateActionProcessingStatus5Choice { value })
+}
+
+/// Remove and return the related reference `20C::RELA` of a `LINK` sequence to a corporate
+/// action instruction, i.e. of the message type `565` of field `13A::LINK`.
+fn instruction(link: &mut Sequence) -> Option<GenericField> {
+    if !matches!(link.generic("13", "LINK"), Some(field) if field.data == INSTRUCTION) {
+        return None;
+    }
+
+    let rela = link.take("20C::RELA")?;
+    link.take("13A::LINK");
+
+    Some(rela)
+}
+
+/// Parse the linkage type of field `22F::LINK` of a `LINK` sequence, e.g. `WITH`.
+fn linkage_type(link: &mut Sequence) -> Result<Option<seev_031::ProcessingPosition7Choice>, Error> {
+    Ok(link
+        .take("22F::LINK")
+        .map(|field| choice(&field))
+        .transpose()?
+        .map(|value| seev_031::ProcessingPosition7Choice { value }))
+}
+
+/// Parse the option of a `CAOPTN` sequence of an `MT564`.
+fn notification_option(caoptn: &mut Sequence) -> Result<seev_031::CorporateActionOption193, Error> {
+    let dflt = caoptn.mandatory("17B::DFLT")?;
+    let dflt_optn_ind = match dflt.data.as_str() {
+        "Y" => true,
+        "N" => false,
+        _ => return Err(dflt.invalid()),
+    };
+
+    let dt_dtls = seev_031::CorporateActionDate77 {
+        mkt_ddln: notification_date(caoptn, "MKDT")?,
+        rspn_ddln: take_date(caoptn, "RDDT")?.map(|date| seev_031::DateFormat44Choice {
+            value: seev_031::DateFormat44ChoiceEnum {
+                dt: date.dt,
+                dt_cd: date.dt_cd,
+                ..Default::default()
+            },
+        }),
+        xpry_dt: notification_date(caoptn, "EXPI")?,
+        ..Default::default()
+    };
+    let mut option = seev_031::CorporateActionOption193 {
+        optn_nb: seev_031::Exact3NumericText {
+            value: caoptn.mandatory("13A::CAON")?.data,
+        },
+        optn_tp: seev_031::CorporateActionOption37Choice {
+            value: choice(&caoptn.mandatory("22F::CAOP")?)?,
+        },
+        ccy_optn: caoptn
+            .take("11A::OPTN")
+            .map(|field| seev_031::ActiveCurrencyCode { value: field.data }),
+        dflt_prcg_or_stg_instr: seev_031::DefaultProcessingOrStandingInstruction1Choice {
+            value: seev_031::DefaultProcessingOrStandingInstruction1ChoiceEnum {
+                dflt_optn_ind: Some(seev_031::YesNoIndicator {
+                    value: dflt_optn_ind,
+                }),
+                ..Default::default()
+            },
+        },
+        dt_dtls: (dt_dtls != Default::default()).then_some(dt_dtls),
+        ..Default::default()
+    };
+
+    for secmove in caoptn.sequences_mut("SECMOVE") {
+        let qty = secmove
+            .take("36B::ENTL")
+            .map(|field| financial_instrument_quantity(&field))
+            .transpose()?;
+        let addtl_qty_for_exstg_scties = take_option(secmove, "92D", "ADEX")
+            .first()
+            .map(ratio)
+            .transpose()?;
+
+        option.scties_mvmnt_dtls.push(seev_031::SecuritiesOption77 {
+            cdt_dbt_ind: credit_debit(secmove)?,
+            scty_dtls: seev_031::FinancialInstrumentAttributes107 {
+                fin_instrm_id: security(
+                    &secmove
+                        .take_field("35B")
+                        .ok_or(Error::MissingField("35B"))?,
+                )?,
+                ..Default::default()
+            },
+            entitld_qty: qty.map(|value| seev_031::Quantity51Choice {
+                value: seev_031::Quantity51ChoiceEnum {
+                    qty: Some(seev_031::FinancialInstrumentQuantity33Choice { value }),
+                    ..Default::default()
+                },
+            }),
+            dt_dtls: seev_031::SecurityDate16 {
+                pmt_dt: seev_031::DateFormat43Choice {
+                    value: take_date(secmove, "PAYD")?.ok_or(Error::MissingField("98a::PAYD"))?,
+                },
+                ..Default::default()
+            },
+            rate_dtls: addtl_qty_for_exstg_scties.map(|value| seev_031::CorporateActionRate89 {
+                addtl_qty_for_exstg_scties: Some(seev_031::RatioFormat17Choice { value }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+
+    for cashmove in caoptn.sequences_mut("CASHMOVE") {
+        let amt_dtls = seev_031::CorporateActionAmounts54 {
+            entitld_amt: take_amount(cashmove, "ENTL")?,
+            grss_csh_amt: take_amount(cashmove, "GRSS")?,
+            net_csh_amt: take_amount(cashmove, "NETT")?,
+            ..Default::default()
+        };
+        let rates = Rates::take(cashmove)?;
+        let rate_and_amt_dtls = seev_031::Rate36 {
+            grss_dvdd_rate: rates
+                .gross
+                .iter()
+                .map(|amt| seev_031::GrossDividendRateFormat38Choice {
+                    value: seev_031::GrossDividendRateFormat38ChoiceEnum {
+                        amt: Some(amt.clone()),
+                        ..Default::default()
+                    },
+                })
+                .collect(),
+            net_dvdd_rate: rates
+                .net
+                .iter()
+                .map(|amt| seev_031::NetDividendRateFormat39Choice {
+                    value: seev_031::NetDividendRateFormat39ChoiceEnum {
+                        amt: Some(amt.clone()),
+                        ..Default::default()
+                    },
+                })
+                .collect(),
+            whldg_tax_rate: rates
+                .withholding_tax
+                .iter()
+                .map(|rate| seev_031::RateAndAmountFormat41Choice {
+                    value: seev_031::RateAndAmountFormat41ChoiceEnum {
+                        rate: Some(rate.clone()),
+                        ..Default::default()
+                    },
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        option.csh_mvmnt_dtls.push(seev_031::CashOption77 {
+            cdt_dbt_ind: credit_debit(cashmove)?,
+            csh_acct_id: cashmove
+                .take("97a::CASH")
+                .map(cash_account)
+                .transpose()?
+                .map(|value| seev_031::CashAccountIdentification5Choice { value }),
+            amt_dtls: (amt_dtls != Default::default()).then_some(amt_dtls),
+            dt_dtls: seev_031::CorporateActionDate62 {
+                pmt_dt: seev_031::DateFormat43Choice {
+                    value: take_date(cashmove, "PAYD")?.ok_or(Error::MissingField("98a::PAYD"))?,
+                },
+                ..Default::default()
+            },
+            rate_and_amt_dtls: (rate_and_amt_dtls != Default::default())
+                .then_some(rate_and_amt_dtls),
+            ..Default::default()
+        });
+    }
+
+    Ok(option)
+}
+
+/// Parse the movement of securities of a `SECMOVE` sequence of an `MT566`.
+fn confirmation_securities_option(
+    secmove: &mut Sequence,
+) -> Result<seev_036::SecuritiesOption78, Error> {
+    let cdt_dbt_ind = match credit_debit(secmove)? {
+        seev_031::CreditDebitCode::Crdt => seev_036::CreditDebitCode::Crdt,
+        _ => seev_036::CreditDebitCode::Dbit,
+    };
+
+    Ok(seev_036::SecuritiesOption78 {
+        fin_instrm_id: security(
+            &secmove
+                .take_field("35B")
+                .ok_or(Error::MissingField("35B"))?,
+        )?,
+        cdt_dbt_ind,
+        pstng_qty: seev_036::Quantity51Choice {
+            value: seev_036::Quantity51ChoiceEnum {
+                qty: Some(seev_036::FinancialInstrumentQuantity33Choice {
+                    value: convert(&financial_instrument_quantity(
+                        &secmove.mandatory("36B::PSTA")?,
+                    )?)?,
+                }),
+                ..Default::default()
+            },
+        },
+        dt_dtls: seev_036::SecurityDate15 {
+            pstng_dt: seev_036::DateAndDateTime2Choice {
+                value: date_time(&secmove.mandatory("98a::POST")?)?,
+            },
+            pmt_dt: confirmation_date(secmove, "PAYD")?,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// Parse the movement of cash of a `CASHMOVE` sequence of an `MT566`.
+fn confirmation_cash_option(cashmove: &mut Sequence) -> Result<seev_036::CashOption79, Error> {
+    let cdt_dbt_ind = match credit_debit(cashmove)? {
+        seev_031::CreditDebitCode::Crdt => seev_036::CreditDebitCode::Crdt,
+        _ => seev_036::CreditDebitCode::Dbit,
+    };
+    let pstng_amt = take_amount(cashmove, "PSTA")?.ok_or(Error::MissingField("19B::PSTA"))?;
+    let rates = Rates::take(cashmove)?;
+    let rate_and_amt_dtls = seev_036::Rate35 {
+        grss_dvdd_rate: rates
+            .gross
+            .iter()
+            .map(|amt| -> Result<_, Error> {
+                Ok(seev_036::GrossDividendRateFormat37Choice {
+                    value: seev_036::GrossDividendRateFormat37ChoiceEnum {
+                        amt: Some(convert(amt)?),
+                        ..Default::default()
+                    },
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        net_dvdd_rate: rates
+            .net
+            .iter()
+            .map(|amt| -> Result<_, Error> {
+                Ok(seev_036::NetDividendRateFormat40Choice {
+                    value: seev_036::NetDividendRateFormat40ChoiceEnum {
+                        amt: Some(convert(amt)?),
+                        ..Default::default()
+                    },
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        whldg_tax_rate: rates
+            .withholding_tax
+            .iter()
+            .map(|rate| -> Result<_, Error> {
+                Ok(seev_036::RateAndAmountFormat40Choice {
+                    value: seev_036::RateAndAmountFormat40ChoiceEnum {
+                        rate: Some(seev_036::PercentageRate { value: rate.value }),
+                        ..Default::default()
+                    },
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        ..Default::default()
+    };
+
+    Ok(seev_036::CashOption79 {
+        cdt_dbt_ind,
+        acct: cashmove
+            .take("97a::CASH")
+            .map(cash_account)
+            .transpose()?
+            .map(|value| seev_036::Account8Choice {
+                value: seev_036::Account8ChoiceEnum {
+                    csh_acct: Some(seev_036::CashAccountIdentification5Choice { value }),
+                    ..Default::default()
+                },
+            }),
+        amt_dtls: seev_036::CorporateActionAmounts56 {
+            pstng_amt: convert(&pstng_amt)?,
+            grss_csh_amt: take_amount(cashmove, "GRSS")?
+                .map(|amt| convert(&amt))
+                .transpose()?,
+            net_csh_amt: take_amount(cashmove, "NETT")?
+                .map(|amt| convert(&amt))
+                .transpose()?,
+            ..Default::default()
+        },
+        dt_dtls: seev_036::CorporateActionDate65 {
+            pstng_dt: seev_036::DateAndDateTime2Choice {
+                value: date_time(&cashmove.mandatory("98a::POST")?)?,
+            },
+            val_dt: cashmove
+                .take("98a::VALU")
+                .map(|field| -> Result<_, Error> {
+                    Ok(seev_036::DateAndDateTime2Choice {
+                        value: date_time(&field)?,
+                    })
+                })
+                .transpose()?,
+            pmt_dt: cashmove
+                .take("98a::PAYD")
+                .map(|field| -> Result<_, Error> {
+                    Ok(seev_036::DateAndDateTime2Choice {
+                        value: date_time(&field)?,
+                    })
+                })
+                .transpose()?,
+            ..Default::default()
+        },
+        rate_and_amt_dtls: (rate_and_amt_dtls != Default::default()).then_some(rate_and_amt_dtls),
+        ..Default::default()
+    })
+}
+
+/// Rates of a `CASHMOVE` sequence, i.e. the gross and net dividend rates of the fields
+/// `92F::GRSS` and `92F::NETT`, and the withholding tax rates of the fields `92A::TAXR`.
+struct Rates {
+    gross: Vec<seev_031::ActiveCurrencyAnd13DecimalAmount>,
+    net: Vec<seev_031::ActiveCurrencyAnd13DecimalAmount>,
+    withholding_tax: Vec<seev_031::PercentageRate>,
+}
+
+impl Rates {
+    /// Remove and parse the rates of a sequence, leaving their other options, e.g. the rate
+    /// not specified of field `:92K::GRSS//UKWN`.
+    fn take(sequence: &mut Sequence) -> Result<Self, Error> {
+        Ok(Self {
+            gross: take_option(sequence, "92F", "GRSS")
+                .iter()
+                .map(rate_amount)
+                .collect::<Result<_, _>>()?,
+            net: take_option(sequence, "92F", "NETT")
+                .iter()
+                .map(rate_amount)
+                .collect::<Result<_, _>>()?,
+            withholding_tax: take_option(sequence, "92A", "TAXR")
+                .iter()
+                .map(rate)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Remove and return the generic fields of the tag and qualifier, e.g. `92F` and `GRSS`,
+/// leaving the other options of the field in the sequence.
+fn take_option(sequence: &mut Sequence, tag: &str, qualifier: &str) -> Vec<GenericField> {
+    let (taken, fields): (Vec<Field>, Vec<Field>) = std::mem::take(&mut sequence.fields)
+        .into_iter()
+        .partition(|field| {
+            matches!(GenericField::parse(field), Some(field) if field.tag == tag && field.qualifier == qualifier)
+        });
+    sequence.fields = fields;
+
+    taken.iter().filter_map(GenericField::parse).collect()
+}
+
+/// Parse the credit or debit of field `22H::CRDB`, i.e. `CRED` or `DEBT`.
+fn credit_debit(sequence: &mut Sequence) -> Result<seev_031::CreditDebitCode, Error> {
+    let field = sequence.mandatory("22H::CRDB")?;
+
+    match field.data.as_str() {
+        "CRED" => Ok(seev_031::CreditDebitCode::Crdt),
+        "DEBT" => Ok(seev_031::CreditDebitCode::Dbit),
+        _ => Err(field.invalid()),
+    }
+}
+
+/// Remove and parse the amount of the qualifier of field `19B`, e.g. `19B::ENTL`.
+fn take_amount(
+    sequence: &mut Sequence,
+    qualifier: &str,
+) -> Result<Option<seev_031::ActiveCurrencyAndAmount>, Error> {
+    let field = match sequence.take(&format!("19B::{}", qualifier)) {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+    let captures = AMOUNT
+        .captures(&field.data)
+        .ok_or_else(|| field.invalid())?;
+
+    Ok(Some(seev_031::ActiveCurrencyAndAmount {
+        value: seev_031::ActiveCurrencyAndAmountSimpleType {
+            value: parse_amount(&field.tag, &captures[2])?,
+        },
+        ccy: seev_031::ActiveCurrencyCode {
+            value: captures[1].to_string(),
+        },
+    }))
+}
+
+/// Parse the rate of field `92F` of an amount per security, e.g. `EUR0,25`.
+fn rate_amount(field: &GenericField) -> Result<seev_031::ActiveCurrencyAnd13DecimalAmount, Error> {
+    let captures = AMOUNT
+        .captures(&field.data)
+        .ok_or_else(|| field.invalid())?;
+
+    Ok(seev_031::ActiveCurrencyAnd13DecimalAmount {
+        value: seev_031::ActiveCurrencyAnd13DecimalAmountSimpleType {
+            value: parse_amount(&field.tag, &captures[2])?,
+        },
+        ccy: seev_031::ActiveCurrencyCode {
+            value: captures[1].to_string(),
+        },
+    })
+}
+
+/// Parse the percentage rate of field `92A`, e.g. `15,` or `N0,5`.
+fn rate(field: &GenericField) -> Result<seev_031::PercentageRate, Error> {
+    let captures = RATE.captures(&field.data).ok_or_else(|| field.invalid())?;
+    let value = parse_amount(&field.tag, &captures[2])?;
+
+    Ok(seev_031::PercentageRate {
+        value: if captures.get(1).is_some() {
+            -value
+        } else {
+            value
+        },
+    })
+}
+
+/// Parse the ratio of field `92D` of the new to the existing quantities, e.g. `1,/10,`.
+fn ratio(field: &GenericField) -> Result<seev_031::RatioFormat17ChoiceEnum, Error> {
+    let captures = RATIO.captures(&field.data).ok_or_else(|| field.invalid())?;
+
+    Ok(seev_031::RatioFormat17ChoiceEnum {
+        qty_to_qty: Some(seev_031::QuantityToQuantityRatio1 {
+            qty_1: seev_031::DecimalNumber {
+                value: parse_amount(&field.tag, &captures[1])?,
+            },
+            qty_2: seev_031::DecimalNumber {
+                value: parse_amount(&field.tag, &captures[2])?,
+            },
+        }),
+        ..Default::default()
+    })
+}
+
+/// Parse the quantity of field `36B` or the balance of field `93B`, e.g. `UNIT/1000,`, and
+/// whether it is negative, e.g. `UNIT/N1000,` for a short balance.
+fn quantity(
+    field: &GenericField,
+) -> Result<(seev_031::FinancialInstrumentQuantity33ChoiceEnum, bool), Error> {
+    let captures = QUANTITY
+        .captures(&field.data)
+        .ok_or_else(|| field.invalid())?;
+    let value = parse_amount(&field.tag, &captures[3])?;
+
+    let qty = match &captures[1] {
+        "UNIT" => seev_031::FinancialInstrumentQuantity33ChoiceEnum {
+            unit: Some(seev_031::DecimalNumber { value }),
+            ..Default::default()
+        },
+        "FAMT" => seev_031::FinancialInstrumentQuantity33ChoiceEnum {
+            face_amt: Some(seev_031::ImpliedCurrencyAndAmount { value }),
+            ..Default::default()
+        },
+        _ => seev_031::FinancialInstrumentQuantity33ChoiceEnum {
+            amtsd_val: Some(seev_031::ImpliedCurrencyAndAmount { value }),
+            ..Default::default()
+        },
+    };
+
+    Ok((qty, captures.get(2).is_some()))
+}
+
+/// Parse the quantity of financial instrument of field `36B`, e.g. `UNIT/1000,`.
+fn financial_instrument_quantity(
+    field: &GenericField,
+) -> Result<seev_031::FinancialInstrumentQuantity33ChoiceEnum, Error> {
+    match quantity(field)? {
+        (qty, false) => Ok(qty),
+        (_, true) => Err(field.invalid()),
+    }
+}
+
+/// Remove and parse the date of the qualifier of field `98a`, e.g. `98a::XDTE`, or its date
+/// code of field `98B`, e.g. `:98B::XDTE//UKWN`.
+fn take_date(
+    sequence: &mut Sequence,
+    qualifier: &str,
+) -> Result<Option<seev_031::DateFormat43ChoiceEnum>, Error> {
+    let field = match sequence.take(&format!("98a::{}", qualifier)) {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+
+    let date = match field.tag.as_str() {
+        "98B" => seev_031::DateFormat43ChoiceEnum {
+            dt_cd: Some(seev_031::DateCode19Choice {
+                value: choice(&field)?,
+            }),
+            ..Default::default()
+        },
+        _ => seev_031::DateFormat43ChoiceEnum {
+            dt: Some(seev_031::DateAndDateTime2Choice {
+                value: date_time(&field)?,
+            }),
+            ..Default::default()
+        },
+    };
+
+    Ok(Some(date))
+}
+
+/// Remove and parse the date of the qualifier of field `98a` of an `MT564`.
+fn notification_date(
+    sequence: &mut Sequence,
+    qualifier: &str,
+) -> Result<Option<seev_031::DateFormat43Choice>, Error> {
+    Ok(take_date(sequence, qualifier)?.map(|value| seev_031::DateFormat43Choice { value }))
+}
+
+/// Remove and parse the date of the qualifier of field `98a` of an `MT566`.
+fn confirmation_date(
+    sequence: &mut Sequence,
+    qualifier: &str,
+) -> Result<Option<seev_036::DateFormat43Choice>, Error> {
+    let field = match sequence.take(&format!("98a::{}", qualifier)) {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+
+    let value = match field.tag.as_str() {
+        "98B" => seev_036::DateFormat43ChoiceEnum {
+            dt_cd: Some(seev_036::DateCode19Choice {
+                value: choice(&field)?,
+            }),
+            ..Default::default()
+        },
+        _ => seev_036::DateFormat43ChoiceEnum {
+            dt: Some(seev_036::DateAndDateTime2Choice {
+                value: date_time(&field)?,
+            }),
+            ..Default::default()
+        },
+    };
+
+    Ok(Some(seev_036::DateFormat43Choice { value }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+    use crate::mt::TranslationIssue;
+
+    fn block4(lines: &[&str]) -> Block4 {
+        lines.join("\n").parse().unwrap()
+    }
+
+    #[test]
+    fn test_mt564() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::CORP//DVCA-2304",
+            ":20C::SEME//NTF-2304-01",
+            ":20C::COAF//US0378331005DVCA01",
+            ":23G:NEWM",
+            ":22F::CAEV//DVCA",
+            ":22F::CAMV//MAND",
+            ":98C::PREP//20230320091500",
+            ":25D::PROC//PREU",
+            ":16R:LINK",
+            ":22F::LINK//AFTE",
+            ":13A::LINK//564",
+            ":20C::PREV//NTF-2303-07",
+            ":16S:LINK",
+            ":16S:GENL",
+            ":16R:USECU",
+            ":35B:ISIN US0378331005",
+            "APPLE INC",
+            ":16R:ACCTINFO",
+            ":97A::SAFE//111222333",
+            ":16S:ACCTINFO",
+            ":16S:USECU",
+            ":16R:CADETL",
+            ":98A::XDTE//20230410",
+            ":98A::RDTE//20230411",
+            ":98B::PAYD//UKWN",
+            ":22F::DIVI//REGR",
+            ":16S:CADETL",
+            ":16R:CAOPTN",
+            ":13A::CAON//001",
+            ":22F::CAOP//CASH",
+            ":11A::OPTN//USD",
+            ":17B::DFLT//Y",
+            ":98C::RDDT//20230412170000",
+            ":16R:CASHMOVE",
+            ":22H::CRDB//CRED",
+            ":97A::CASH//444555666",
+            ":19B::ENTL//USD240,",
+            ":98A::PAYD//20230415",
+            ":92F::GRSS//USD0,24",
+            ":92A::TAXR//15,",
+            ":16S:CASHMOVE",
+            ":16S:CAOPTN",
+        ]);
+        let translation = mt564_to_seev_031::<Dmkr>(&block4).unwrap();
+        assert_eq!(
+            translation.issues,
+            vec![
+                TranslationIssue::Unmapped {
+                    field: "GENL/:20C::SEME//NTF-2304-01".to_string()
+                },
+                TranslationIssue::Unmapped {
+                    field: "GENL/:98C::PREP//20230320091500".to_string()
+                },
+            ]
+        );
+
+        let ntfctn = &translation.document.corp_actn_ntfctn;
+        assert_eq!(
+            ntfctn.ntfctn_gnl_inf.ntfctn_tp,
+            seev_031::CorporateActionNotificationType1Code::Newm
+        );
+        let sts = ntfctn.ntfctn_gnl_inf.prcg_sts.value.cd.as_ref().unwrap();
+        assert_eq!(
+            sts.evt_cmpltns_sts,
+            seev_031::EventCompletenessStatus1Code::Inco
+        );
+        assert_eq!(
+            sts.evt_conf_sts,
+            seev_031::EventConfirmationStatus1Code::Ucon
+        );
+        let prvs_ntfctn_id = ntfctn.prvs_ntfctn_id.as_ref().unwrap();
+        assert_eq!(prvs_ntfctn_id.id.value, "NTF-2303-07");
+        assert_eq!(
+            prvs_ntfctn_id.lkg_tp.as_ref().unwrap().value.cd,
+            Some(seev_031::ProcessingPosition3Code::Afte)
+        );
+        let gnl_inf = &ntfctn.corp_actn_gnl_inf;
+        assert_eq!(gnl_inf.corp_actn_evt_id.value, "DVCA-2304");
+        assert_eq!(
+            gnl_inf.evt_tp.value.cd,
+            Some(seev_031::CorporateActionEventType31Code::Dvca)
+        );
+        assert_eq!(
+            gnl_inf.mndtry_vlntry_evt_tp.value.cd,
+            Some(seev_031::CorporateActionMandatoryVoluntary1Code::Mand)
+        );
+        let fin_instrm_id = &gnl_inf.undrlyg_scty.fin_instrm_id;
+        assert_eq!(fin_instrm_id.isin.as_ref().unwrap().value, "US0378331005");
+        assert_eq!(fin_instrm_id.desc.as_ref().unwrap().value, "APPLE INC");
+        let acct = ntfctn.acct_dtls.value.accts_list_and_bal_dtls.as_ref();
+        assert_eq!(
+            acct.unwrap().sfkpg_acct.as_ref().unwrap().value,
+            "111222333"
+        );
+
+        let dtls = ntfctn.corp_actn_dtls.as_ref().unwrap();
+        let dt_dtls = dtls.dt_dtls.as_ref().unwrap();
+        assert!(dt_dtls.ex_dvdd_dt.as_ref().unwrap().value.dt.is_some());
+        assert_eq!(
+            dt_dtls
+                .pmt_dt
+                .as_ref()
+                .unwrap()
+                .value
+                .dt_cd
+                .as_ref()
+                .unwrap()
+                .value
+                .cd,
+            Some(seev_031::DateType8Code::Ukwn)
+        );
+        assert_eq!(
+            dtls.dvdd_tp.as_ref().unwrap().value.cd,
+            Some(seev_031::CorporateActionFrequencyType5Code::Regr)
+        );
+
+        let optn = &ntfctn.corp_actn_optn_dtls[0];
+        assert_eq!(optn.optn_nb.value, "001");
+        assert_eq!(optn.ccy_optn.as_ref().unwrap().value, "USD");
+        assert!(
+            optn.dflt_prcg_or_stg_instr
+                .value
+                .dflt_optn_ind
+                .as_ref()
+                .unwrap()
+                .value
+        );
+        let rspn_ddln = &optn.dt_dtls.as_ref().unwrap().rspn_ddln;
+        assert!(rspn_ddln.as_ref().unwrap().value.dt.is_some());
+        let csh = &optn.csh_mvmnt_dtls[0];
+        assert_eq!(csh.cdt_dbt_ind, seev_031::CreditDebitCode::Crdt);
+        assert_eq!(
+            csh.csh_acct_id
+                .as_ref()
+                .unwrap()
+                .value
+                .prtry
+                .as_ref()
+                .unwrap()
+                .value,
+            "444555666"
+        );
+        let entitld_amt = csh.amt_dtls.as_ref().unwrap().entitld_amt.as_ref();
+        assert_eq!(entitld_amt.unwrap().value.value, 240.);
+        let rates = csh.rate_and_amt_dtls.as_ref().unwrap();
+        let grss_dvdd_rate = rates.grss_dvdd_rate[0].value.amt.as_ref().unwrap();
+        assert_eq!(grss_dvdd_rate.value.value, 0.24);
+        assert_eq!(grss_dvdd_rate.ccy.value, "USD");
+        assert_eq!(
+            rates.whldg_tax_rate[0].value.rate.as_ref().unwrap().value,
+            15.
+        );
+    }
+
+    #[test]
+    fn test_mt564_unmapped() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::CORP//DVCA-2304",
+            ":23G:REPL",
+            ":22F::CAEV//DVCA",
+            ":22F::CAMV//MAND",
+            ":25D::PROC//COMP",
+            ":16S:GENL",
+            ":16R:USECU",
+            ":35B:ISIN US0378331005",
+            ":16R:ACCTINFO",
+            ":97C::SAFE//GENR",
+            ":16S:ACCTINFO",
+            ":16S:USECU",
+            ":16R:CADETL",
+            ":22F::ADDB//CAPA",
+            ":16S:CADETL",
+            ":16R:CAOPTN",
+            ":13A::CAON//001",
+            ":22F::CAOP//CASH",
+            ":17B::DFLT//N",
+            ":16R:CASHMOVE",
+            ":22H::CRDB//CRED",
+            ":98A::PAYD//20230415",
+            ":92K::GRSS//UKWN",
+            ":16S:CASHMOVE",
+            ":16S:CAOPTN",
+        ]);
+        let translation = mt564_to_seev_031::<Dmkr>(&block4).unwrap();
+        assert_eq!(
+            translation.issues,
+            vec![
+                TranslationIssue::Unmapped {
+                    field: "CADETL/:22F::ADDB//CAPA".to_string()
+                },
+                TranslationIssue::Unmapped {
+                    field: "CAOPTN/CASHMOVE/:92K::GRSS//UKWN".to_string()
+                },
+            ]
+        );
+
+        let ntfctn = &translation.document.corp_actn_ntfctn;
+        assert_eq!(
+            ntfctn.ntfctn_gnl_inf.ntfctn_tp,
+            seev_031::CorporateActionNotificationType1Code::Repl
+        );
+        assert_eq!(
+            ntfctn.acct_dtls.value.for_all_accts.as_ref().unwrap().id_cd,
+            seev_031::SafekeepingAccountIdentification1Code::Genr
+        );
+        assert!(ntfctn.corp_actn_dtls.as_ref().unwrap().dt_dtls.is_none());
+        let optn = &ntfctn.corp_actn_optn_dtls[0];
+        assert!(
+            !optn
+                .dflt_prcg_or_stg_instr
+                .value
+                .dflt_optn_ind
+                .as_ref()
+                .unwrap()
+                .value
+        );
+        assert!(optn.csh_mvmnt_dtls[0].rate_and_amt_dtls.is_none());
+    }
+
+    #[test]
+    fn test_mt564_invalid() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::CORP//DVCA-2304",
+            ":23G:NEWM",
+            ":22F::CAEV//DVCA",
+            ":22F::CAMV//MAND",
+            ":25D::PROC//DONE",
+            ":16S:GENL",
+        ]);
+        assert!(matches!(
+            mt564_to_seev_031::<Dmkr>(&block4),
+            Err(Error::InvalidField { tag, .. }) if tag == "25D"
+        ));
+    }
+
+    #[test]
+    fn test_mt566() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::CORP//DVCA-2304",
+            ":20C::SEME//CNF-2304-01",
+            ":23G:NEWM",
+            ":22F::CAEV//DVCA",
+            ":16R:LINK",
+            ":13A::LINK//564",
+            ":20C::RELA//NTF-2304-01",
+            ":16S:LINK",
+            ":16S:GENL",
+            ":16R:USECU",
+            ":35B:ISIN US0378331005",
+            ":16R:ACCTINFO",
+            ":97A::SAFE//111222333",
+            ":93B::CONB//UNIT/1000,",
+            ":16S:ACCTINFO",
+            ":16S:USECU",
+            ":16R:CADETL",
+            ":98A::RDTE//20230411",
+            ":16S:CADETL",
+            ":16R:CACONF",
+            ":13A::CAON//001",
+            ":22F::CAOP//CASH",
+            ":16R:CASHMOVE",
+            ":22H::CRDB//CRED",
+            ":97E::CASH//DE89370400440532013000",
+            ":19B::PSTA//USD204,",
+            ":19B::GRSS//USD240,",
+            ":98A::POST//20230415",
+            ":98A::VALU//20230415",
+            ":92F::GRSS//USD0,24",
+            ":92A::TAXR//15,",
+            ":16S:CASHMOVE",
+            ":16S:CACONF",
+        ]);
+        let translation = mt566_to_seev_036::<Dmkr>(&block4).unwrap();
+        assert_eq!(
+            translation.issues,
+            vec![TranslationIssue::Unmapped {
+                field: "GENL/:20C::SEME//CNF-2304-01".to_string()
+            }]
+        );
+
+        let conf = &translation.document.corp_actn_mvmnt_conf;
+        assert_eq!(conf.ntfctn_id.as_ref().unwrap().id.value, "NTF-2304-01");
+        assert!(conf.instr_id.is_none());
+        assert_eq!(
+            conf.corp_actn_gnl_inf.evt_tp.value.cd,
+            Some(seev_036::CorporateActionEventType30Code::Dvca)
+        );
+        let bal = conf.acct_dtls.bal.confd_bal.value.bal.as_ref().unwrap();
+        assert_eq!(bal.shrt_lng_pos, seev_036::ShortLong1Code::Long);
+        let qty = bal.qty_chc.value.qty.as_ref().unwrap();
+        assert_eq!(qty.value.unit.as_ref().unwrap().value, 1000.);
+        let dt_dtls = conf.corp_actn_dtls.as_ref().unwrap().dt_dtls.as_ref();
+        assert!(dt_dtls.unwrap().rcrd_dt.is_some());
+
+        let conf_dtls = &conf.corp_actn_conf_dtls;
+        assert_eq!(conf_dtls.optn_nb.value.nb.as_ref().unwrap().value, "001");
+        assert_eq!(
+            conf_dtls.optn_tp.value.cd,
+            Some(seev_036::CorporateActionOption12Code::Cash)
+        );
+        let csh = &conf_dtls.csh_mvmnt_dtls[0];
+        assert_eq!(csh.cdt_dbt_ind, seev_036::CreditDebitCode::Crdt);
+        let csh_acct = csh.acct.as_ref().unwrap().value.csh_acct.as_ref();
+        assert_eq!(
+            csh_acct.unwrap().value.iban.as_ref().unwrap().value,
+            "DE89370400440532013000"
+        );
+        assert_eq!(csh.amt_dtls.pstng_amt.value.value, 204.);
+        assert_eq!(
+            csh.amt_dtls.grss_csh_amt.as_ref().unwrap().value.value,
+            240.
+        );
+        assert!(csh.dt_dtls.val_dt.is_some());
+        let rates = csh.rate_and_amt_dtls.as_ref().unwrap();
+        assert_eq!(
+            rates.grss_dvdd_rate[0]
+                .value
+                .amt
+                .as_ref()
+                .unwrap()
+                .value
+                .value,
+            0.24
+        );
+        assert_eq!(
+            rates.whldg_tax_rate[0].value.rate.as_ref().unwrap().value,
+            15.
+        );
+    }
+
+    #[test]
+    fn test_missing_sequence() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::CORP//DVCA-2304",
+            ":23G:NEWM",
+            ":22F::CAEV//DVCA",
+            ":16S:GENL",
+            ":16R:USECU",
+            ":35B:ISIN US0378331005",
+            ":16R:ACCTINFO",
+            ":93B::CONB//UNIT/1000,",
+            ":16S:ACCTINFO",
+            ":16S:USECU",
+        ]);
+        assert!(matches!(
+            mt566_to_seev_036::<Dmkr>(&block4),
+            Err(Error::MissingField("16R:CACONF"))
+        ));
+    }
+}
@@ -0,0 +1,818 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Customer Transfer Translation
+//!
+//! Translates an `MT103` single customer credit transfer into a `pacs.008.001.10` FI to FI
+//! customer credit transfer, and each transaction of a `pacs.008.001.10` back into an `MT103`.
+//!
+//! | MT103           | pacs.008.001.10                                          |
+//! |-----------------|----------------------------------------------------------|
+//! | `20`            | `GrpHdr/MsgId`, `PmtId/InstrId`                          |
+//! | `23E`           | `InstrForCdtrAgt`, `InstrForNxtAgt`, `PmtTpInf`          |
+//! | `32A`           | `IntrBkSttlmDt`, `IntrBkSttlmAmt`                        |
+//! | `33B`, `36`     | `InstdAmt`, `XchgRate`                                   |
+//! | `50a`, `59a`    | `Dbtr`, `DbtrAcct`, `Cdtr`, `CdtrAcct`                   |
+//! | `52a`, `56a`, `57a` | `DbtrAgt`, `IntrmyAgt1`, `CdtrAgt`                   |
+//! | `53a`, `54a`, `55a` | `GrpHdr/SttlmInf`                                    |
+//! | `70`            | `PmtId/EndToEndId` as `/ROC/`, `RmtInf/Ustrd`            |
+//! | `71A`, `71F`, `71G` | `ChrgBr`, `ChrgsInf`                                 |
+//! | `72`            | `PrvsInstgAgt1`, `InstrForCdtrAgt`, `InstrForNxtAgt`     |
+use chrono::Utc;
+use serde::Serialize;
+
+use iso_20022_pacs::pacs_008_001_10 as pacs;
+
+use super::{
+    account_id, agent_bic, bic_agent, format_amount, format_decimal, invalid_field, lost,
+    parse_32a, parse_amount, parse_currency_amount, render_32a, truncate, truncated, wrap, Block4,
+    Error, Field, FinHeader, Instructions, MessageType, MtTranslation, PartyField,
+    TranslationIssue, LINE_LENGTH,
+};
+
+/// End to end identification of a transaction without one.
+pub(crate) const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// Code of the end to end identification in field `70`.
+const ROC: &str = "/ROC/";
+
+/// Instruction codes of field `23E` for the creditor agent.
+const CREDITOR_AGENT_CODES: [&str; 4] = ["CHQB", "HOLD", "PHOB", "TELB"];
+
+/// Instruction codes of field `23E` of the category purpose.
+const CATEGORY_PURPOSE_CODES: [&str; 2] = ["CORT", "INTC"];
+
+/// Service level code of field `23E`.
+const SAME_DAY_VALUE: &str = "SDVA";
+
+/// Translate the block 4 of an `MT103` into a `pacs.008.001.10`.
+///
+/// The sender and receiver of the header are the instructing and instructed agents, and the
+/// default debtor agent and creditor agent, i.e. without field `52a` or `57a`. The UETR of the
+/// header is mandatory, i.e. field `121` of the user header block 3.
+pub fn mt103_to_pacs_008<A, B>(
+    header: &FinHeader,
+    block4: &Block4,
+) -> Result<pacs::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let reference = &block4.mandatory("20")?.value;
+    let uetr = header.uetr.clone().ok_or(Error::MissingField("121"))?;
+    let (intr_bk_sttlm_dt, intr_bk_sttlm_amt) = parse_32a(block4.mandatory("32A")?)?;
+    let dbtr = PartyField::parse(block4.option("50").ok_or(Error::MissingField("50a"))?);
+    let cdtr = PartyField::parse(block4.option("59").ok_or(Error::MissingField("59a"))?);
+    let dbtr_agt = block4.option("52").map(PartyField::parse);
+    let intrmy_agt_1 = block4.option("56").map(PartyField::parse);
+    let cdtr_agt = block4.option("57").map(PartyField::parse);
+
+    let chrg_br = match block4.mandatory("71A")?.value.as_str() {
+        "OUR" => pacs::ChargeBearerType1Code::Debt,
+        "BEN" => pacs::ChargeBearerType1Code::Cred,
+        "SHA" => pacs::ChargeBearerType1Code::Shar,
+        value => return Err(invalid_field("71A", value)),
+    };
+
+    let mut chrgs_inf = vec![];
+    for (tag, agt) in [("71F", &header.sender), ("71G", &header.receiver)] {
+        for field in block4.fields_of(tag) {
+            chrgs_inf.push(pacs::Charges7 {
+                amt: parse_currency_amount(field)?,
+                agt: bic_agent(agt),
+            });
+        }
+    }
+
+    let mut instructions = Instructions::parse(block4.field("72"));
+    let mut svc_lvl = vec![];
+    let mut ctgy_purp = None;
+
+    for field in block4.fields_of("23E") {
+        let (code, instr_inf) = match field.value.split_once('/') {
+            Some((code, instr_inf)) => (code, Some(instr_inf)),
+            None => (field.value.as_str(), None),
+        };
+        let instr_inf = instr_inf.map(|value| pacs::Max140Text {
+            value: value.to_string(),
+        });
+
+        if CREDITOR_AGENT_CODES.contains(&code) {
+            instructions
+                .instr_for_cdtr_agt
+                .push(pacs::InstructionForCreditorAgent3 {
+                    cd: Some(pacs::ExternalCreditorAgentInstruction1Code {
+                        value: code.to_string(),
+                    }),
+                    instr_inf,
+                });
+        } else if CATEGORY_PURPOSE_CODES.contains(&code) {
+            ctgy_purp = Some(pacs::CategoryPurpose1Choice {
+                value: pacs::CategoryPurpose1ChoiceEnum {
+                    cd: Some(pacs::ExternalCategoryPurpose1Code {
+                        value: code.to_string(),
+                    }),
+                    ..Default::default()
+                },
+            });
+        } else if code == SAME_DAY_VALUE {
+            svc_lvl.push(pacs::ServiceLevel8Choice {
+                value: pacs::ServiceLevel8ChoiceEnum {
+                    cd: Some(pacs::ExternalServiceLevel1Code {
+                        value: code.to_string(),
+                    }),
+                    ..Default::default()
+                },
+            });
+        } else {
+            let cd = match code {
+                "PHOI" | "PHON" => Some(pacs::Instruction4Code::Phoa),
+                "TELI" | "TELE" => Some(pacs::Instruction4Code::Tela),
+                _ => None,
+            };
+
+            instructions
+                .instr_for_nxt_agt
+                .push(pacs::InstructionForNextAgent1 {
+                    instr_inf: if cd.is_some() {
+                        instr_inf
+                    } else {
+                        Some(pacs::Max140Text {
+                            value: field.value.clone(),
+                        })
+                    },
+                    cd,
+                });
+        }
+    }
+
+    let (end_to_end_id, rmt_inf) = parse_remittance_field(block4.field("70"));
+
+    let pmt_tp_inf = if svc_lvl.is_empty() && ctgy_purp.is_none() {
+        None
+    } else {
+        Some(pacs::PaymentTypeInformation28 {
+            svc_lvl,
+            ctgy_purp,
+            ..Default::default()
+        })
+    };
+
+    let cdt_trf_tx_inf = pacs::CreditTransferTransaction50 {
+        pmt_id: pacs::PaymentIdentification13 {
+            instr_id: Some(pacs::Max35Text {
+                value: reference.clone(),
+            }),
+            end_to_end_id: pacs::Max35Text {
+                value: end_to_end_id.unwrap_or_else(|| NOT_PROVIDED.to_string()),
+            },
+            uetr: Some(pacs::UuiDv4Identifier { value: uetr }),
+            ..Default::default()
+        },
+        pmt_tp_inf,
+        intr_bk_sttlm_amt,
+        intr_bk_sttlm_dt: Some(pacs::IsoDate {
+            value: intr_bk_sttlm_dt,
+        }),
+        instd_amt: block4.field("33B").map(parse_currency_amount).transpose()?,
+        xchg_rate: block4
+            .field("36")
+            .map(|field| parse_amount(&field.tag, &field.value))
+            .transpose()?
+            .map(|value| pacs::BaseOneRate { value }),
+        chrg_br,
+        chrgs_inf,
+        prvs_instg_agt_1: instructions.prvs_instg_agt_1,
+        intrmy_agt_1: intrmy_agt_1.as_ref().map(PartyField::agent),
+        intrmy_agt_1_acct: intrmy_agt_1.as_ref().and_then(PartyField::account),
+        dbtr: dbtr.party(),
+        dbtr_acct: dbtr.account(),
+        dbtr_agt: dbtr_agt
+            .as_ref()
+            .map(PartyField::agent)
+            .unwrap_or_else(|| bic_agent(&header.sender)),
+        dbtr_agt_acct: dbtr_agt.as_ref().and_then(PartyField::account),
+        cdtr_agt: cdtr_agt
+            .as_ref()
+            .map(PartyField::agent)
+            .unwrap_or_else(|| bic_agent(&header.receiver)),
+        cdtr_agt_acct: cdtr_agt.as_ref().and_then(PartyField::account),
+        cdtr: cdtr.party(),
+        cdtr_acct: cdtr.account(),
+        instr_for_cdtr_agt: instructions.instr_for_cdtr_agt,
+        instr_for_nxt_agt: instructions.instr_for_nxt_agt,
+        rmt_inf,
+        ..Default::default()
+    };
+
+    Ok(pacs::Document {
+        fi_to_fi_cstmr_cdt_trf: pacs::FiToFiCustomerCreditTransferV10 {
+            grp_hdr: pacs::GroupHeader96 {
+                msg_id: pacs::Max35Text {
+                    value: reference.clone(),
+                },
+                cre_dt_tm: pacs::IsoDateTime { value: Utc::now() },
+                nb_of_txs: pacs::Max15NumericText {
+                    value: "1".to_string(),
+                },
+                sttlm_inf: settlement_information(block4),
+                instg_agt: Some(bic_agent(&header.sender)),
+                instd_agt: Some(bic_agent(&header.receiver)),
+                ..Default::default()
+            },
+            cdt_trf_tx_inf: vec![cdt_trf_tx_inf],
+            splmtry_data: vec![],
+        },
+        xmlns: pacs::namespace(),
+    })
+}
+
+/// Translate each transaction of a `pacs.008.001.10` into an `MT103`.
+///
+/// The instructing and instructed agents, of the transaction or the group header, must be
+/// identified by their BIC, as the sender and receiver of the `MT103`.
+pub fn pacs_008_to_mt103<A, B>(document: &pacs::Document<A, B>) -> Result<Vec<MtTranslation>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let cdt_trf = &document.fi_to_fi_cstmr_cdt_trf;
+    let grp_hdr = &cdt_trf.grp_hdr;
+
+    cdt_trf
+        .cdt_trf_tx_inf
+        .iter()
+        .map(|tx| transaction_to_mt103(grp_hdr, tx))
+        .collect()
+}
+
+/// Translate a transaction of a `pacs.008.001.10` into an `MT103`.
+fn transaction_to_mt103<A>(
+    grp_hdr: &pacs::GroupHeader96,
+    tx: &pacs::CreditTransferTransaction50<A>,
+) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+    let mut block4 = Block4::default();
+
+    let header = FinHeader {
+        sender: tx
+            .instg_agt
+            .as_ref()
+            .or(grp_hdr.instg_agt.as_ref())
+            .and_then(agent_bic)
+            .ok_or(Error::MissingElement("InstgAgt/FinInstnId/BICFI"))?
+            .to_string(),
+        receiver: tx
+            .instd_agt
+            .as_ref()
+            .or(grp_hdr.instd_agt.as_ref())
+            .and_then(agent_bic)
+            .ok_or(Error::MissingElement("InstdAgt/FinInstnId/BICFI"))?
+            .to_string(),
+        uetr: tx.pmt_id.uetr.as_ref().map(|uetr| uetr.value.clone()),
+    };
+
+    let reference = tx
+        .pmt_id
+        .instr_id
+        .as_ref()
+        .or(tx.pmt_id.tx_id.as_ref())
+        .unwrap_or(&grp_hdr.msg_id);
+    block4.push(
+        "20",
+        truncate(
+            &reference.value,
+            16,
+            "20",
+            "CdtTrfTxInf/PmtId/InstrId",
+            &mut issues,
+        ),
+    );
+    block4.push("23B", "CRED");
+
+    for instr in tx.instr_for_cdtr_agt.iter() {
+        if let Some(cd) = &instr.cd {
+            block4.push("23E", instruction_code(&cd.value, instr.instr_inf.as_ref()));
+        }
+    }
+    for instr in tx.instr_for_nxt_agt.iter() {
+        let cd = match instr.cd {
+            Some(pacs::Instruction4Code::Phoa) => "PHOI",
+            Some(pacs::Instruction4Code::Tela) => "TELI",
+            _ => continue,
+        };
+        block4.push("23E", instruction_code(cd, instr.instr_inf.as_ref()));
+    }
+    if let Some(pmt_tp_inf) = &tx.pmt_tp_inf {
+        payment_type_codes(pmt_tp_inf, &mut block4, &mut issues);
+    }
+
+    let intr_bk_sttlm_dt = tx
+        .intr_bk_sttlm_dt
+        .as_ref()
+        .or(grp_hdr.intr_bk_sttlm_dt.as_ref())
+        .ok_or(Error::MissingElement("IntrBkSttlmDt"))?;
+    block4
+        .fields
+        .push(render_32a(&intr_bk_sttlm_dt.value, &tx.intr_bk_sttlm_amt));
+
+    if let Some(instd_amt) = &tx.instd_amt {
+        block4.push(
+            "33B",
+            format!(
+                "{}{}",
+                instd_amt.ccy.value,
+                format_amount(instd_amt.value.value, &instd_amt.ccy.value)
+            ),
+        );
+    }
+    if let Some(xchg_rate) = &tx.xchg_rate {
+        block4.push("36", format_decimal(xchg_rate.value));
+    }
+
+    block4.fields.push(
+        PartyField::from_party(&tx.dbtr, tx.dbtr_acct.as_ref()).render_party(
+            "50",
+            "CdtTrfTxInf/Dbtr",
+            &mut issues,
+        ),
+    );
+
+    if agent_bic(&tx.dbtr_agt) != Some(header.sender.as_str()) || tx.dbtr_agt_acct.is_some() {
+        block4.fields.push(
+            PartyField::from_agent(&tx.dbtr_agt, tx.dbtr_agt_acct.as_ref()).render_agent(
+                "52",
+                "CdtTrfTxInf/DbtrAgt",
+                &mut issues,
+            ),
+        );
+    }
+
+    settlement_fields(&grp_hdr.sttlm_inf, &mut block4, &mut issues);
+
+    if let Some(intrmy_agt_1) = &tx.intrmy_agt_1 {
+        block4.fields.push(
+            PartyField::from_agent(intrmy_agt_1, tx.intrmy_agt_1_acct.as_ref()).render_agent(
+                "56",
+                "CdtTrfTxInf/IntrmyAgt1",
+                &mut issues,
+            ),
+        );
+    }
+
+    if agent_bic(&tx.cdtr_agt) != Some(header.receiver.as_str()) || tx.cdtr_agt_acct.is_some() {
+        block4.fields.push(
+            PartyField::from_agent(&tx.cdtr_agt, tx.cdtr_agt_acct.as_ref()).render_agent(
+                "57",
+                "CdtTrfTxInf/CdtrAgt",
+                &mut issues,
+            ),
+        );
+    }
+
+    block4.fields.push(
+        PartyField::from_party(&tx.cdtr, tx.cdtr_acct.as_ref()).render_party(
+            "59",
+            "CdtTrfTxInf/Cdtr",
+            &mut issues,
+        ),
+    );
+
+    block4.fields.extend(remittance_field(
+        "CdtTrfTxInf",
+        &tx.pmt_id.end_to_end_id.value,
+        tx.rmt_inf.as_ref(),
+        &mut issues,
+    ));
+
+    block4.push(
+        "71A",
+        match tx.chrg_br {
+            pacs::ChargeBearerType1Code::Debt => "OUR",
+            pacs::ChargeBearerType1Code::Cred => "BEN",
+            _ => "SHA",
+        },
+    );
+
+    let (receiver_chrgs, sender_chrgs): (Vec<_>, Vec<_>) = tx
+        .chrgs_inf
+        .iter()
+        .partition(|chrgs| agent_bic(&chrgs.agt) == Some(header.receiver.as_str()));
+    for (tag, chrgs_inf) in [("71F", sender_chrgs), ("71G", receiver_chrgs)] {
+        for chrgs in chrgs_inf {
+            block4.push(
+                tag,
+                format!(
+                    "{}{}",
+                    chrgs.amt.ccy.value,
+                    format_amount(chrgs.amt.value.value, &chrgs.amt.ccy.value)
+                ),
+            );
+        }
+    }
+
+    let instructions = Instructions {
+        prvs_instg_agt_1: tx.prvs_instg_agt_1.clone(),
+        instr_for_cdtr_agt: tx.instr_for_cdtr_agt.clone(),
+        instr_for_nxt_agt: tx.instr_for_nxt_agt.clone(),
+    };
+    block4.fields.extend(instructions.render(&mut issues));
+
+    issues.extend(lost_elements(tx));
+
+    Ok(MtTranslation {
+        message_type: MessageType::Mt103,
+        header,
+        block4,
+        issues,
+    })
+}
+
+/// Return the settlement information of fields `53a`, `54a` and `55a`.
+pub(crate) fn settlement_information(block4: &Block4) -> pacs::SettlementInstruction11 {
+    let mut sttlm_inf = pacs::SettlementInstruction11 {
+        sttlm_mtd: pacs::SettlementMethod1Code::Inda,
+        ..Default::default()
+    };
+
+    if let Some(field) = block4.option("53") {
+        let agent = PartyField::parse(field);
+
+        if field.option() == Some('B') {
+            sttlm_inf.sttlm_acct = agent.account();
+        } else {
+            sttlm_inf.instg_rmbrsmnt_agt = Some(agent.agent());
+            sttlm_inf.instg_rmbrsmnt_agt_acct = agent.account();
+        }
+    }
+    if let Some(field) = block4.option("54") {
+        let agent = PartyField::parse(field);
+        sttlm_inf.instd_rmbrsmnt_agt = Some(agent.agent());
+        sttlm_inf.instd_rmbrsmnt_agt_acct = agent.account();
+    }
+    if let Some(field) = block4.option("55") {
+        let agent = PartyField::parse(field);
+        sttlm_inf.thrd_rmbrsmnt_agt = Some(agent.agent());
+        sttlm_inf.thrd_rmbrsmnt_agt_acct = agent.account();
+    }
+
+    // Reimbursement agents settle the transfer by cover
+    if sttlm_inf.instg_rmbrsmnt_agt.is_some() || sttlm_inf.instd_rmbrsmnt_agt.is_some() {
+        sttlm_inf.sttlm_mtd = pacs::SettlementMethod1Code::Cove;
+    }
+
+    sttlm_inf
+}
+
+/// Render fields `53a`, `54a` and `55a` of the settlement information.
+pub(crate) fn settlement_fields(
+    sttlm_inf: &pacs::SettlementInstruction11,
+    block4: &mut Block4,
+    issues: &mut Vec<TranslationIssue>,
+) {
+    if let Some(instg_rmbrsmnt_agt) = &sttlm_inf.instg_rmbrsmnt_agt {
+        block4.fields.push(
+            PartyField::from_agent(
+                instg_rmbrsmnt_agt,
+                sttlm_inf.instg_rmbrsmnt_agt_acct.as_ref(),
+            )
+            .render_agent("53", "GrpHdr/SttlmInf/InstgRmbrsmntAgt", issues),
+        );
+
+        if sttlm_inf.sttlm_acct.is_some() {
+            issues.push(lost("GrpHdr/SttlmInf/SttlmAcct"));
+        }
+    } else if let Some(sttlm_acct) = sttlm_inf.sttlm_acct.as_ref().and_then(account_id) {
+        block4.push(
+            "53B",
+            format!(
+                "/{}",
+                truncate(&sttlm_acct, 34, "53B", "GrpHdr/SttlmInf/SttlmAcct", issues)
+            ),
+        );
+    }
+
+    if let Some(instd_rmbrsmnt_agt) = &sttlm_inf.instd_rmbrsmnt_agt {
+        block4.fields.push(
+            PartyField::from_agent(
+                instd_rmbrsmnt_agt,
+                sttlm_inf.instd_rmbrsmnt_agt_acct.as_ref(),
+            )
+            .render_agent("54", "GrpHdr/SttlmInf/InstdRmbrsmntAgt", issues),
+        );
+    }
+
+    if let Some(thrd_rmbrsmnt_agt) = &sttlm_inf.thrd_rmbrsmnt_agt {
+        block4.fields.push(
+            PartyField::from_agent(thrd_rmbrsmnt_agt, sttlm_inf.thrd_rmbrsmnt_agt_acct.as_ref())
+                .render_agent("55", "GrpHdr/SttlmInf/ThrdRmbrsmntAgt", issues),
+        );
+    }
+
+    if sttlm_inf.clr_sys.is_some() {
+        issues.push(lost("GrpHdr/SttlmInf/ClrSys"));
+    }
+}
+
+/// Parse field `70`, into the end to end identification of its first line `/ROC/`, and the
+/// unstructured remittance information of the other lines.
+pub(crate) fn parse_remittance_field(
+    field: Option<&Field>,
+) -> (Option<String>, Option<pacs::RemittanceInformation21>) {
+    let mut end_to_end_id = None;
+    let mut ustrd = String::new();
+
+    for (index, line) in field.iter().flat_map(|field| field.lines()).enumerate() {
+        match line.strip_prefix(ROC) {
+            Some(roc) if index == 0 => end_to_end_id = Some(roc.to_string()),
+            _ => ustrd.push_str(line),
+        }
+    }
+
+    let rmt_inf = (!ustrd.is_empty()).then(|| pacs::RemittanceInformation21 {
+        ustrd: vec![pacs::Max140Text { value: ustrd }],
+        ..Default::default()
+    });
+
+    (end_to_end_id, rmt_inf)
+}
+
+/// Render field `70`, of the end to end identification as `/ROC/` and the unstructured
+/// remittance information, on up to four lines of 35 characters.
+pub(crate) fn remittance_field(
+    element: &str,
+    end_to_end_id: &str,
+    rmt_inf: Option<&pacs::RemittanceInformation21>,
+    issues: &mut Vec<TranslationIssue>,
+) -> Option<Field> {
+    let mut lines = vec![];
+
+    if end_to_end_id != NOT_PROVIDED {
+        lines.push(format!(
+            "{}{}",
+            ROC,
+            truncate(
+                end_to_end_id,
+                LINE_LENGTH - ROC.len(),
+                "70",
+                &format!("{}/PmtId/EndToEndId", element),
+                issues
+            )
+        ));
+    }
+
+    if let Some(rmt_inf) = rmt_inf {
+        let ustrd = rmt_inf
+            .ustrd
+            .iter()
+            .map(|ustrd| ustrd.value.as_str())
+            .collect::<String>();
+        let available = 4 - lines.len();
+        let chunks = wrap(&ustrd, LINE_LENGTH, LINE_LENGTH).collect::<Vec<_>>();
+
+        if chunks.len() > available {
+            issues.push(truncated(
+                "70",
+                &format!("{}/RmtInf/Ustrd", element),
+                &ustrd,
+            ));
+        }
+        lines.extend(chunks.into_iter().take(available));
+
+        if !rmt_inf.strd.is_empty() {
+            issues.push(lost(&format!("{}/RmtInf/Strd", element)));
+        }
+    }
+
+    (!lines.is_empty()).then(|| Field::new("70", lines.join("\n")))
+}
+
+/// Render the value of field `23E`, of an instruction code and its additional information.
+fn instruction_code(cd: &str, instr_inf: Option<&pacs::Max140Text>) -> String {
+    match instr_inf {
+        Some(instr_inf) => format!(
+            "{}/{}",
+            cd,
+            instr_inf.value.chars().take(30).collect::<String>()
+        ),
+        None => cd.to_string(),
+    }
+}
+
+/// Render the fields `23E` of the service level and category purpose.
+fn payment_type_codes(
+    pmt_tp_inf: &pacs::PaymentTypeInformation28,
+    block4: &mut Block4,
+    issues: &mut Vec<TranslationIssue>,
+) {
+    for svc_lvl in pmt_tp_inf.svc_lvl.iter() {
+        match &svc_lvl.value.cd {
+            Some(cd) if cd.value == SAME_DAY_VALUE => block4.push("23E", SAME_DAY_VALUE),
+            _ => issues.push(lost("CdtTrfTxInf/PmtTpInf/SvcLvl")),
+        }
+    }
+
+    if let Some(ctgy_purp) = &pmt_tp_inf.ctgy_purp {
+        match &ctgy_purp.value.cd {
+            Some(cd) if CATEGORY_PURPOSE_CODES.contains(&cd.value.as_str()) => {
+                block4.push("23E", cd.value.as_str())
+            }
+            _ => issues.push(lost("CdtTrfTxInf/PmtTpInf/CtgyPurp")),
+        }
+    }
+
+    if pmt_tp_inf.instr_prty.is_some() {
+        issues.push(lost("CdtTrfTxInf/PmtTpInf/InstrPrty"));
+    }
+    if pmt_tp_inf.clr_chanl.is_some() {
+        issues.push(lost("CdtTrfTxInf/PmtTpInf/ClrChanl"));
+    }
+    if pmt_tp_inf.lcl_instrm.is_some() {
+        issues.push(lost("CdtTrfTxInf/PmtTpInf/LclInstrm"));
+    }
+}
+
+/// Report the elements of the transaction without place in the `MT103`.
+fn lost_elements<A>(tx: &pacs::CreditTransferTransaction50<A>) -> Vec<TranslationIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let elements = [
+        ("SttlmPrty", tx.sttlm_prty.is_some()),
+        ("SttlmTmIndctn", tx.sttlm_tm_indctn.is_some()),
+        ("SttlmTmReq", tx.sttlm_tm_req.is_some()),
+        ("AccptncDtTm", tx.accptnc_dt_tm.is_some()),
+        ("PoolgAdjstmntDt", tx.poolg_adjstmnt_dt.is_some()),
+        ("MndtRltdInf", tx.mndt_rltd_inf.is_some()),
+        ("PrvsInstgAgt2", tx.prvs_instg_agt_2.is_some()),
+        ("PrvsInstgAgt3", tx.prvs_instg_agt_3.is_some()),
+        ("IntrmyAgt2", tx.intrmy_agt_2.is_some()),
+        ("IntrmyAgt3", tx.intrmy_agt_3.is_some()),
+        ("UltmtDbtr", tx.ultmt_dbtr.is_some()),
+        ("InitgPty", tx.initg_pty.is_some()),
+        ("UltmtCdtr", tx.ultmt_cdtr.is_some()),
+        ("Purp", tx.purp.is_some()),
+        ("RgltryRptg", !tx.rgltry_rptg.is_empty()),
+        ("Tax", tx.tax.is_some()),
+        ("RltdRmtInf", !tx.rltd_rmt_inf.is_empty()),
+        ("SplmtryData", !tx.splmtry_data.is_empty()),
+    ];
+
+    elements
+        .into_iter()
+        .filter(|(_, present)| *present)
+        .map(|(element, _)| lost(&format!("CdtTrfTxInf/{}", element)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    const MT103: &str = ":20:REF123
+:23B:CRED
+:23E:PHOI
+:32A:230401EUR1234,56
+:33B:EUR1234,56
+:50F:/DE89370400440532013000
+1/ACME GMBH
+2/HAUPTSTRASSE 1
+3/DE/10115 BERLIN
+:57A:CCCCCHZZXXX
+:59:/CH9300762011623852957
+MUSTER AG
+BAHNHOFSTRASSE 45
+8001 ZURICH
+:70:/ROC/E2E-42
+INVOICE 4711
+:71A:SHA
+:71F:EUR10,
+:72:/INS/DDDDGB2LXXX
+/ACC/BENEFICIARY TO BE NOTIFIED";
+
+    fn header() -> FinHeader {
+        FinHeader {
+            sender: "AAAADEFFXXX".to_string(),
+            receiver: "BBBBCHZZXXX".to_string(),
+            uetr: Some("e2a6b5cd-0b1f-4a5f-9a58-2a4b7c3e9f01".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_mt103_round_trip() {
+        let block4: Block4 = MT103.parse().unwrap();
+        let document = mt103_to_pacs_008::<Dmkr, Dmkr>(&header(), &block4).unwrap();
+
+        let tx = &document.fi_to_fi_cstmr_cdt_trf.cdt_trf_tx_inf[0];
+        assert_eq!(tx.pmt_id.end_to_end_id.value, "E2E-42");
+        assert_eq!(tx.intr_bk_sttlm_amt.value.value, 1234.56);
+        assert_eq!(tx.chrg_br, pacs::ChargeBearerType1Code::Shar);
+        assert_eq!(agent_bic(&tx.dbtr_agt), Some("AAAADEFFXXX"));
+        assert_eq!(agent_bic(&tx.cdtr_agt), Some("CCCCCHZZXXX"));
+        assert_eq!(
+            tx.dbtr
+                .pstl_adr
+                .as_ref()
+                .unwrap()
+                .twn_nm
+                .as_ref()
+                .unwrap()
+                .value,
+            "BERLIN"
+        );
+        assert_eq!(tx.rmt_inf.as_ref().unwrap().ustrd[0].value, "INVOICE 4711");
+        assert_eq!(
+            tx.instr_for_nxt_agt[0].cd,
+            Some(pacs::Instruction4Code::Phoa)
+        );
+        assert_eq!(
+            document.fi_to_fi_cstmr_cdt_trf.grp_hdr.sttlm_inf.sttlm_mtd,
+            pacs::SettlementMethod1Code::Inda
+        );
+
+        let translations = pacs_008_to_mt103(&document).unwrap();
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].message_type, MessageType::Mt103);
+        assert_eq!(translations[0].header, header());
+        assert_eq!(translations[0].block4, block4);
+        assert!(translations[0].issues.is_empty());
+    }
+
+    #[test]
+    fn test_pacs_008_truncation() {
+        let block4: Block4 = MT103.parse().unwrap();
+        let mut document = mt103_to_pacs_008::<Dmkr, Dmkr>(&header(), &block4).unwrap();
+
+        let tx = &mut document.fi_to_fi_cstmr_cdt_trf.cdt_trf_tx_inf[0];
+        tx.dbtr.nm = Some(pacs::Max140Text {
+            value: "ACME INTERNATIONAL TRADING AND LOGISTICS GMBH".to_string(),
+        });
+        tx.ultmt_cdtr = Some(tx.cdtr.clone());
+        tx.rmt_inf.as_mut().unwrap().strd.push(Default::default());
+        tx.xchg_rate = Some(pacs::BaseOneRate { value: 0.9123 });
+
+        let translations = pacs_008_to_mt103(&document).unwrap();
+        let issues = &translations[0].issues;
+
+        // The name continues on a second line, at the expense of the address lines
+        assert_eq!(
+            translations[0]
+                .block4
+                .field("50F")
+                .unwrap()
+                .lines()
+                .collect::<Vec<_>>(),
+            vec![
+                "/DE89370400440532013000",
+                "1/ACME INTERNATIONAL TRADING AND",
+                "1/LOGISTICS GMBH",
+                "2/HAUPTSTRASSE 1",
+                "3/DE/10115 BERLIN",
+            ]
+        );
+        assert_eq!(translations[0].block4.field("36").unwrap().value, "0,9123");
+        assert!(issues.contains(&lost("CdtTrfTxInf/RmtInf/Strd")));
+        assert!(issues.contains(&lost("CdtTrfTxInf/UltmtCdtr")));
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[test]
+    fn test_mt103_missing_field() {
+        let block4: Block4 = ":20:REF123\n:23B:CRED".parse().unwrap();
+
+        assert!(matches!(
+            mt103_to_pacs_008::<Dmkr, Dmkr>(&header(), &block4),
+            Err(Error::MissingField("32A"))
+        ));
+
+        // The UETR of the user header block 3 is mandatory, rather than generated
+        let block4: Block4 = MT103.parse().unwrap();
+        assert!(matches!(
+            mt103_to_pacs_008::<Dmkr, Dmkr>(
+                &FinHeader {
+                    uetr: None,
+                    ..header()
+                },
+                &block4
+            ),
+            Err(Error::MissingField("121"))
+        ));
+    }
+}
@@ -0,0 +1,580 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Institution Transfer Translation
+//!
+//! Translates an `MT202` general financial institution transfer, or an `MT202 COV` cover of a
+//! customer credit transfer, into a `pacs.009.001.10` financial institution credit transfer,
+//! and each transaction of a `pacs.009.001.10` back into an `MT202`, or an `MT202 COV` when it
+//! has an underlying customer credit transfer.
+//!
+//! | MT202 / MT202 COV   | pacs.009.001.10                                      |
+//! |---------------------|------------------------------------------------------|
+//! | `20`, `21`          | `PmtId/InstrId`, `PmtId/EndToEndId`                  |
+//! | `32A`               | `IntrBkSttlmDt`, `IntrBkSttlmAmt`                    |
+//! | `52a`, `58a`        | `Dbtr`, `Cdtr`                                       |
+//! | `53a`, `54a`, `55a` | `GrpHdr/SttlmInf`                                    |
+//! | `56a`, `57a`        | `IntrmyAgt1`, `CdtrAgt`                              |
+//! | `72`                | `PrvsInstgAgt1`, `InstrForCdtrAgt`, `InstrForNxtAgt` |
+//! | sequence B          | `UndrlygCstmrCdtTrf`                                 |
+use chrono::Utc;
+use serde::Serialize;
+
+use iso_20022_pacs::pacs_009_001_10 as pacs;
+
+use super::customer_transfer::{
+    parse_remittance_field, remittance_field, settlement_fields, settlement_information,
+    NOT_PROVIDED,
+};
+use super::{
+    bic_agent, convert, convert_option, convert_vec, format_amount, lost, parse_32a,
+    parse_currency_amount, render_32a, truncate, Block4, Error, FinHeader, Instructions,
+    MessageType, MtTranslation, PartyField, TranslationIssue,
+};
+
+/// Related reference of field `21` without reference.
+const NO_REFERENCE: &str = "NONREF";
+
+/// Translate the block 4 of an `MT202` or `MT202 COV` into a `pacs.009.001.10`.
+///
+/// The sender and receiver of the header are the instructing and instructed agents, and the
+/// sender is the default debtor, i.e. without field `52a`. The UETR of the header is
+/// mandatory, i.e. field `121` of the user header block 3.
+pub fn mt202_to_pacs_009<A, B>(
+    header: &FinHeader,
+    block4: &Block4,
+) -> Result<pacs::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    // Sequence B of the underlying customer credit transfer starts with its ordering customer
+    let (sequence_a, sequence_b) = block4.split_at_option("50");
+
+    let reference = &sequence_a.mandatory("20")?.value;
+    let related_reference = &sequence_a.mandatory("21")?.value;
+    let uetr = header.uetr.clone().ok_or(Error::MissingField("121"))?;
+    let (intr_bk_sttlm_dt, intr_bk_sttlm_amt) = parse_32a(sequence_a.mandatory("32A")?)?;
+    let dbtr = sequence_a.option("52").map(PartyField::parse);
+    let intrmy_agt_1 = sequence_a.option("56").map(PartyField::parse);
+    let cdtr_agt = sequence_a.option("57").map(PartyField::parse);
+    let cdtr = PartyField::parse(sequence_a.option("58").ok_or(Error::MissingField("58a"))?);
+    let instructions = Instructions::parse(sequence_a.field("72"));
+
+    let end_to_end_id = if related_reference == NO_REFERENCE {
+        NOT_PROVIDED.to_string()
+    } else {
+        related_reference.clone()
+    };
+
+    let undrlyg_cstmr_cdt_trf = if sequence_b.fields.is_empty() {
+        None
+    } else {
+        Some(underlying_customer_credit_transfer(
+            header,
+            &sequence_b,
+            &cdtr,
+        )?)
+    };
+
+    let cdt_trf_tx_inf = pacs::CreditTransferTransaction56 {
+        pmt_id: pacs::PaymentIdentification13 {
+            instr_id: Some(pacs::Max35Text {
+                value: reference.clone(),
+            }),
+            end_to_end_id: pacs::Max35Text {
+                value: end_to_end_id,
+            },
+            uetr: Some(pacs::UuiDv4Identifier { value: uetr }),
+            ..Default::default()
+        },
+        intr_bk_sttlm_amt: convert(&intr_bk_sttlm_amt)?,
+        intr_bk_sttlm_dt: Some(pacs::IsoDate {
+            value: intr_bk_sttlm_dt,
+        }),
+        prvs_instg_agt_1: convert_option(&instructions.prvs_instg_agt_1)?,
+        intrmy_agt_1: convert_option(&intrmy_agt_1.as_ref().map(PartyField::agent))?,
+        intrmy_agt_1_acct: convert_option(&intrmy_agt_1.as_ref().and_then(PartyField::account))?,
+        dbtr: match &dbtr {
+            Some(dbtr) => convert(&dbtr.agent())?,
+            None => convert(&bic_agent(&header.sender))?,
+        },
+        dbtr_acct: convert_option(&dbtr.as_ref().and_then(PartyField::account))?,
+        cdtr_agt: convert_option(&cdtr_agt.as_ref().map(PartyField::agent))?,
+        cdtr_agt_acct: convert_option(&cdtr_agt.as_ref().and_then(PartyField::account))?,
+        cdtr: convert(&cdtr.agent())?,
+        cdtr_acct: convert_option(&cdtr.account())?,
+        instr_for_cdtr_agt: convert_vec(&instructions.instr_for_cdtr_agt)?,
+        instr_for_nxt_agt: convert_vec(&instructions.instr_for_nxt_agt)?,
+        undrlyg_cstmr_cdt_trf,
+        ..Default::default()
+    };
+
+    Ok(pacs::Document {
+        fi_cdt_trf: pacs::FinancialInstitutionCreditTransferV10 {
+            grp_hdr: pacs::GroupHeader96 {
+                msg_id: pacs::Max35Text {
+                    value: reference.clone(),
+                },
+                cre_dt_tm: pacs::IsoDateTime { value: Utc::now() },
+                nb_of_txs: pacs::Max15NumericText {
+                    value: "1".to_string(),
+                },
+                sttlm_inf: convert(&settlement_information(&sequence_a))?,
+                instg_agt: Some(convert(&bic_agent(&header.sender))?),
+                instd_agt: Some(convert(&bic_agent(&header.receiver))?),
+                ..Default::default()
+            },
+            cdt_trf_tx_inf: vec![cdt_trf_tx_inf],
+            splmtry_data: vec![],
+        },
+        xmlns: pacs::namespace(),
+    })
+}
+
+/// Return the underlying customer credit transfer of sequence B of an `MT202 COV`, where
+/// the sender is the default debtor agent, and the beneficiary institution of sequence A
+/// is the default creditor agent.
+fn underlying_customer_credit_transfer(
+    header: &FinHeader,
+    sequence_b: &Block4,
+    beneficiary_institution: &PartyField,
+) -> Result<pacs::CreditTransferTransaction52, Error> {
+    let dbtr = PartyField::parse(sequence_b.option("50").ok_or(Error::MissingField("50a"))?);
+    let dbtr_agt = sequence_b.option("52").map(PartyField::parse);
+    let intrmy_agt_1 = sequence_b.option("56").map(PartyField::parse);
+    let cdtr_agt = sequence_b
+        .option("57")
+        .map(PartyField::parse)
+        .unwrap_or_else(|| beneficiary_institution.clone());
+    let cdtr = PartyField::parse(sequence_b.option("59").ok_or(Error::MissingField("59a"))?);
+    let instructions = Instructions::parse(sequence_b.field("72"));
+    let (_, rmt_inf) = parse_remittance_field(sequence_b.field("70"));
+
+    Ok(pacs::CreditTransferTransaction52 {
+        dbtr: convert(&dbtr.party())?,
+        dbtr_acct: convert_option(&dbtr.account())?,
+        dbtr_agt: match &dbtr_agt {
+            Some(dbtr_agt) => convert(&dbtr_agt.agent())?,
+            None => convert(&bic_agent(&header.sender))?,
+        },
+        dbtr_agt_acct: convert_option(&dbtr_agt.as_ref().and_then(PartyField::account))?,
+        prvs_instg_agt_1: convert_option(&instructions.prvs_instg_agt_1)?,
+        intrmy_agt_1: convert_option(&intrmy_agt_1.as_ref().map(PartyField::agent))?,
+        intrmy_agt_1_acct: convert_option(&intrmy_agt_1.as_ref().and_then(PartyField::account))?,
+        cdtr_agt: convert(&cdtr_agt.agent())?,
+        cdtr_agt_acct: convert_option(&cdtr_agt.account())?,
+        cdtr: convert(&cdtr.party())?,
+        cdtr_acct: convert_option(&cdtr.account())?,
+        instr_for_cdtr_agt: convert_vec(&instructions.instr_for_cdtr_agt)?,
+        instr_for_nxt_agt: convert_vec(&instructions.instr_for_nxt_agt)?,
+        rmt_inf: convert_option(&rmt_inf)?,
+        instd_amt: sequence_b
+            .field("33B")
+            .map(parse_currency_amount)
+            .transpose()?
+            .map(|instd_amt| convert(&instd_amt))
+            .transpose()?,
+        ..Default::default()
+    })
+}
+
+/// Translate each transaction of a `pacs.009.001.10` into an `MT202`, or an `MT202 COV`
+/// when it has an underlying customer credit transfer.
+///
+/// The instructing and instructed agents, of the transaction or the group header, must be
+/// identified by their BIC, as the sender and receiver of the MT message.
+pub fn pacs_009_to_mt202<A, B>(document: &pacs::Document<A, B>) -> Result<Vec<MtTranslation>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let cdt_trf = &document.fi_cdt_trf;
+    let grp_hdr = &cdt_trf.grp_hdr;
+
+    cdt_trf
+        .cdt_trf_tx_inf
+        .iter()
+        .map(|tx| transaction_to_mt202(grp_hdr, tx))
+        .collect()
+}
+
+/// Translate a transaction of a `pacs.009.001.10` into an `MT202` or `MT202 COV`.
+fn transaction_to_mt202<A>(
+    grp_hdr: &pacs::GroupHeader96,
+    tx: &pacs::CreditTransferTransaction56<A>,
+) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+    let mut block4 = Block4::default();
+
+    let header = FinHeader {
+        sender: tx
+            .instg_agt
+            .as_ref()
+            .or(grp_hdr.instg_agt.as_ref())
+            .and_then(bic)
+            .ok_or(Error::MissingElement("InstgAgt/FinInstnId/BICFI"))?,
+        receiver: tx
+            .instd_agt
+            .as_ref()
+            .or(grp_hdr.instd_agt.as_ref())
+            .and_then(bic)
+            .ok_or(Error::MissingElement("InstdAgt/FinInstnId/BICFI"))?,
+        uetr: tx.pmt_id.uetr.as_ref().map(|uetr| uetr.value.clone()),
+    };
+
+    let reference = tx
+        .pmt_id
+        .instr_id
+        .as_ref()
+        .or(tx.pmt_id.tx_id.as_ref())
+        .unwrap_or(&grp_hdr.msg_id);
+    block4.push(
+        "20",
+        truncate(
+            &reference.value,
+            16,
+            "20",
+            "CdtTrfTxInf/PmtId/InstrId",
+            &mut issues,
+        ),
+    );
+
+    let end_to_end_id = &tx.pmt_id.end_to_end_id.value;
+    block4.push(
+        "21",
+        if end_to_end_id == NOT_PROVIDED {
+            NO_REFERENCE.to_string()
+        } else {
+            truncate(
+                end_to_end_id,
+                16,
+                "21",
+                "CdtTrfTxInf/PmtId/EndToEndId",
+                &mut issues,
+            )
+        },
+    );
+
+    let intr_bk_sttlm_dt = tx
+        .intr_bk_sttlm_dt
+        .as_ref()
+        .or(grp_hdr.intr_bk_sttlm_dt.as_ref())
+        .ok_or(Error::MissingElement("IntrBkSttlmDt"))?;
+    block4.fields.push(render_32a(
+        &intr_bk_sttlm_dt.value,
+        &convert(&tx.intr_bk_sttlm_amt)?,
+    ));
+
+    if bic(&tx.dbtr).as_deref() != Some(header.sender.as_str()) || tx.dbtr_acct.is_some() {
+        block4.fields.push(
+            PartyField::from_agent(&convert(&tx.dbtr)?, convert_option(&tx.dbtr_acct)?.as_ref())
+                .render_agent("52", "CdtTrfTxInf/Dbtr", &mut issues),
+        );
+    }
+
+    settlement_fields(&convert(&grp_hdr.sttlm_inf)?, &mut block4, &mut issues);
+
+    if let Some(intrmy_agt_1) = &tx.intrmy_agt_1 {
+        block4.fields.push(
+            PartyField::from_agent(
+                &convert(intrmy_agt_1)?,
+                convert_option(&tx.intrmy_agt_1_acct)?.as_ref(),
+            )
+            .render_agent("56", "CdtTrfTxInf/IntrmyAgt1", &mut issues),
+        );
+    }
+    if let Some(cdtr_agt) = &tx.cdtr_agt {
+        block4.fields.push(
+            PartyField::from_agent(
+                &convert(cdtr_agt)?,
+                convert_option(&tx.cdtr_agt_acct)?.as_ref(),
+            )
+            .render_agent("57", "CdtTrfTxInf/CdtrAgt", &mut issues),
+        );
+    }
+    block4.fields.push(
+        PartyField::from_agent(&convert(&tx.cdtr)?, convert_option(&tx.cdtr_acct)?.as_ref())
+            .render_agent("58", "CdtTrfTxInf/Cdtr", &mut issues),
+    );
+
+    let instructions = Instructions {
+        prvs_instg_agt_1: convert_option(&tx.prvs_instg_agt_1)?,
+        instr_for_cdtr_agt: convert_vec(&tx.instr_for_cdtr_agt)?,
+        instr_for_nxt_agt: convert_vec(&tx.instr_for_nxt_agt)?,
+    };
+    block4.fields.extend(instructions.render(&mut issues));
+    issues.extend(lost_instruction_codes("CdtTrfTxInf", &instructions));
+
+    issues.extend(lost_elements(tx));
+
+    let message_type = match &tx.undrlyg_cstmr_cdt_trf {
+        Some(undrlyg) => {
+            underlying_fields(undrlyg, &mut block4, &mut issues)?;
+            MessageType::Mt202Cov
+        }
+        None => MessageType::Mt202,
+    };
+
+    Ok(MtTranslation {
+        message_type,
+        header,
+        block4,
+        issues,
+    })
+}
+
+/// Render sequence B of an `MT202 COV`, of the underlying customer credit transfer.
+fn underlying_fields(
+    undrlyg: &pacs::CreditTransferTransaction52,
+    block4: &mut Block4,
+    issues: &mut Vec<TranslationIssue>,
+) -> Result<(), Error> {
+    const ELEMENT: &str = "CdtTrfTxInf/UndrlygCstmrCdtTrf";
+
+    block4.fields.push(
+        PartyField::from_party(
+            &convert(&undrlyg.dbtr)?,
+            convert_option(&undrlyg.dbtr_acct)?.as_ref(),
+        )
+        .render_party("50", &format!("{}/Dbtr", ELEMENT), issues),
+    );
+    block4.fields.push(
+        PartyField::from_agent(
+            &convert(&undrlyg.dbtr_agt)?,
+            convert_option(&undrlyg.dbtr_agt_acct)?.as_ref(),
+        )
+        .render_agent("52", &format!("{}/DbtrAgt", ELEMENT), issues),
+    );
+    if let Some(intrmy_agt_1) = &undrlyg.intrmy_agt_1 {
+        block4.fields.push(
+            PartyField::from_agent(
+                &convert(intrmy_agt_1)?,
+                convert_option(&undrlyg.intrmy_agt_1_acct)?.as_ref(),
+            )
+            .render_agent("56", &format!("{}/IntrmyAgt1", ELEMENT), issues),
+        );
+    }
+    block4.fields.push(
+        PartyField::from_agent(
+            &convert(&undrlyg.cdtr_agt)?,
+            convert_option(&undrlyg.cdtr_agt_acct)?.as_ref(),
+        )
+        .render_agent("57", &format!("{}/CdtrAgt", ELEMENT), issues),
+    );
+    block4.fields.push(
+        PartyField::from_party(
+            &convert(&undrlyg.cdtr)?,
+            convert_option(&undrlyg.cdtr_acct)?.as_ref(),
+        )
+        .render_party("59", &format!("{}/Cdtr", ELEMENT), issues),
+    );
+
+    block4.fields.extend(remittance_field(
+        ELEMENT,
+        NOT_PROVIDED,
+        convert_option(&undrlyg.rmt_inf)?.as_ref(),
+        issues,
+    ));
+
+    let instructions = Instructions {
+        prvs_instg_agt_1: convert_option(&undrlyg.prvs_instg_agt_1)?,
+        instr_for_cdtr_agt: convert_vec(&undrlyg.instr_for_cdtr_agt)?,
+        instr_for_nxt_agt: convert_vec(&undrlyg.instr_for_nxt_agt)?,
+    };
+    block4.fields.extend(instructions.render(issues));
+    issues.extend(lost_instruction_codes(ELEMENT, &instructions));
+
+    if let Some(instd_amt) = &undrlyg.instd_amt {
+        block4.push(
+            "33B",
+            format!(
+                "{}{}",
+                instd_amt.ccy.value,
+                format_amount(instd_amt.value.value, &instd_amt.ccy.value)
+            ),
+        );
+    }
+
+    let elements = [
+        ("UltmtDbtr", undrlyg.ultmt_dbtr.is_some()),
+        ("InitgPty", undrlyg.initg_pty.is_some()),
+        ("PrvsInstgAgt2", undrlyg.prvs_instg_agt_2.is_some()),
+        ("PrvsInstgAgt3", undrlyg.prvs_instg_agt_3.is_some()),
+        ("IntrmyAgt2", undrlyg.intrmy_agt_2.is_some()),
+        ("IntrmyAgt3", undrlyg.intrmy_agt_3.is_some()),
+        ("UltmtCdtr", undrlyg.ultmt_cdtr.is_some()),
+        ("Tax", undrlyg.tax.is_some()),
+    ];
+    issues.extend(
+        elements
+            .into_iter()
+            .filter(|(_, present)| *present)
+            .map(|(element, _)| lost(&format!("{}/{}", ELEMENT, element))),
+    );
+
+    Ok(())
+}
+
+/// Report the instructions with code, without field `23E` in the `MT202`.
+fn lost_instruction_codes(element: &str, instructions: &Instructions) -> Vec<TranslationIssue> {
+    let instr_for_cdtr_agt = instructions
+        .instr_for_cdtr_agt
+        .iter()
+        .filter(|instr| instr.cd.is_some())
+        .map(|_| lost(&format!("{}/InstrForCdtrAgt/Cd", element)));
+    let instr_for_nxt_agt = instructions
+        .instr_for_nxt_agt
+        .iter()
+        .filter(|instr| instr.cd.is_some())
+        .map(|_| lost(&format!("{}/InstrForNxtAgt/Cd", element)));
+
+    instr_for_cdtr_agt.chain(instr_for_nxt_agt).collect()
+}
+
+/// Report the elements of the transaction without place in the `MT202`.
+fn lost_elements<A>(tx: &pacs::CreditTransferTransaction56<A>) -> Vec<TranslationIssue>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let elements = [
+        ("PmtTpInf", tx.pmt_tp_inf.is_some()),
+        ("SttlmPrty", tx.sttlm_prty.is_some()),
+        ("SttlmTmIndctn", tx.sttlm_tm_indctn.is_some()),
+        ("SttlmTmReq", tx.sttlm_tm_req.is_some()),
+        ("PrvsInstgAgt2", tx.prvs_instg_agt_2.is_some()),
+        ("PrvsInstgAgt3", tx.prvs_instg_agt_3.is_some()),
+        ("IntrmyAgt2", tx.intrmy_agt_2.is_some()),
+        ("IntrmyAgt3", tx.intrmy_agt_3.is_some()),
+        ("UltmtDbtr", tx.ultmt_dbtr.is_some()),
+        ("DbtrAgt", tx.dbtr_agt.is_some()),
+        ("UltmtCdtr", tx.ultmt_cdtr.is_some()),
+        ("Purp", tx.purp.is_some()),
+        ("RmtInf", tx.rmt_inf.is_some()),
+        ("SplmtryData", !tx.splmtry_data.is_empty()),
+    ];
+
+    elements
+        .into_iter()
+        .filter(|(_, present)| *present)
+        .map(|(element, _)| lost(&format!("CdtTrfTxInf/{}", element)))
+        .collect()
+}
+
+/// Return the BIC of an agent, if identified by its BIC.
+fn bic(agent: &pacs::BranchAndFinancialInstitutionIdentification6) -> Option<String> {
+    agent
+        .fin_instn_id
+        .bicfi
+        .as_ref()
+        .map(|bicfi| bicfi.value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    const MT202_COV: &str = ":20:COV123
+:21:REF123
+:32A:230401EUR1234,56
+:57A:CCCCCHZZXXX
+:58A:DDDDCHZZXXX
+:50F:/DE89370400440532013000
+1/ACME GMBH
+2/HAUPTSTRASSE 1
+3/DE/10115 BERLIN
+:52A:AAAADEFFXXX
+:57A:DDDDCHZZXXX
+:59:/CH9300762011623852957
+MUSTER AG
+:70:INVOICE 4711
+:33B:EUR1234,56";
+
+    fn header() -> FinHeader {
+        FinHeader {
+            sender: "AAAADEFFXXX".to_string(),
+            receiver: "BBBBCHZZXXX".to_string(),
+            uetr: Some("e2a6b5cd-0b1f-4a5f-9a58-2a4b7c3e9f01".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_mt202_cov_round_trip() {
+        let block4: Block4 = MT202_COV.parse().unwrap();
+        let document = mt202_to_pacs_009::<Dmkr, Dmkr>(&header(), &block4).unwrap();
+
+        let tx = &document.fi_cdt_trf.cdt_trf_tx_inf[0];
+        assert_eq!(tx.pmt_id.end_to_end_id.value, "REF123");
+        assert_eq!(bic(&tx.dbtr).as_deref(), Some("AAAADEFFXXX"));
+        assert_eq!(bic(&tx.cdtr).as_deref(), Some("DDDDCHZZXXX"));
+
+        let undrlyg = tx.undrlyg_cstmr_cdt_trf.as_ref().unwrap();
+        assert_eq!(undrlyg.dbtr.nm.as_ref().unwrap().value, "ACME GMBH");
+        assert_eq!(undrlyg.cdtr.nm.as_ref().unwrap().value, "MUSTER AG");
+        assert_eq!(bic(&undrlyg.cdtr_agt).as_deref(), Some("DDDDCHZZXXX"));
+        assert_eq!(undrlyg.instd_amt.as_ref().unwrap().value.value, 1234.56);
+
+        let translations = pacs_009_to_mt202(&document).unwrap();
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].message_type, MessageType::Mt202Cov);
+        assert_eq!(translations[0].header, header());
+        assert_eq!(translations[0].block4, block4);
+        assert!(translations[0].issues.is_empty());
+    }
+
+    #[test]
+    fn test_mt202_round_trip() {
+        let block4: Block4 =
+            ":20:FI123\n:21:NONREF\n:32A:230401USD1000000,\n:53A:EEEEUS33XXX\n:58A:DDDDCHZZXXX\n:72:/REC/PAYMENT OF THE INTEREST\n// FOR MARCH"
+                .parse()
+                .unwrap();
+        let document = mt202_to_pacs_009::<Dmkr, Dmkr>(&header(), &block4).unwrap();
+
+        let grp_hdr = &document.fi_cdt_trf.grp_hdr;
+        assert_eq!(
+            grp_hdr.sttlm_inf.sttlm_mtd,
+            pacs::SettlementMethod1Code::Cove
+        );
+        assert_eq!(
+            bic(grp_hdr.sttlm_inf.instg_rmbrsmnt_agt.as_ref().unwrap()).as_deref(),
+            Some("EEEEUS33XXX")
+        );
+
+        let tx = &document.fi_cdt_trf.cdt_trf_tx_inf[0];
+        assert_eq!(tx.pmt_id.end_to_end_id.value, NOT_PROVIDED);
+        assert_eq!(
+            tx.instr_for_nxt_agt[0].instr_inf.as_ref().unwrap().value,
+            "/REC/PAYMENT OF THE INTEREST FOR MARCH"
+        );
+
+        let mut translations = pacs_009_to_mt202(&document).unwrap();
+        assert_eq!(translations[0].message_type, MessageType::Mt202);
+        assert_eq!(
+            translations[0].block4.field("72").unwrap().value,
+            "/REC/PAYMENT OF THE INTEREST FOR MA\n//RCH"
+        );
+
+        // The continuation of field 72 is rewrapped at 35 characters
+        translations[0].block4.fields.pop();
+        let mut expected = block4.clone();
+        expected.fields.pop();
+        assert_eq!(translations[0].block4, expected);
+    }
+}
@@ -0,0 +1,1271 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Investigation Translation
+//!
+//! Translates the `MTn92` request for cancellation into a `camt.056.001.10` FI to FI payment
+//! cancellation request, the `MTn96` answers into a `camt.029.001.11` resolution of
+//! investigation, and back, and the `MTn95` queries into a `camt.026.001.09` unable to apply or
+//! a `camt.087.001.08` request to modify payment. The `n` is `1` for customer transfers, e.g.
+//! `MT192`, and `2` for institution transfers, e.g. `MT292`. The free format `MTn99` has no
+//! ISO 20022 counterpart, and is not translated.
+//!
+//! | MTn92 / MTn95 / MTn96 | camt.056.001.10 / camt.026.001.09 / camt.029.001.11           |
+//! |-----------------------|---------------------------------------------------------------|
+//! | `20`                  | `Assgnmt/Id`, `Case/Id`, `CxlId` / `CxlStsId`                 |
+//! | `21`                  | `OrgnlInstrId`, `OrgnlGrpInf/OrgnlMsgId` / `RslvdCase/Id`     |
+//! | `11S`, `11R`          | `OrgnlGrpInf/OrgnlMsgNmId`, `OrgnlGrpInf/OrgnlCreDtTm`        |
+//! | `79` of `MTn92`       | `CxlRsnInf/Rsn`, `CxlRsnInf/AddtlInf`                         |
+//! | `75` of `MTn95`       | `Justfn`, or `Mod` of a `camt.087.001.08`                     |
+//! | `77A` of `MTn95`      | `InstrForAssgne/InstrInf` of a `camt.087.001.08`              |
+//! | `76` of `MTn96`       | `Sts/Conf`, `TxCxlSts`, `CxlStsRsnInf`                        |
+//! | copy of `32A`         | `OrgnlIntrBkSttlmAmt`, `OrgnlIntrBkSttlmDt`                   |
+//!
+//! The narrative of the answer of field `76` starts with the investigation execution
+//! confirmation code, followed by the reason code, e.g. `/RJCR/AC04`, the transaction
+//! cancellation status being `ACCR` for `CNCL`, `PDCR` for `PDCR` and `RJCR` for `RJCR`. The
+//! reason codes of the ISO 20022 code sets are carried as codes, the others as proprietary
+//! reasons.
+use chrono::{DateTime, NaiveDate, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::{value, DeserializeOwned, IntoDeserializer};
+use serde::Serialize;
+
+use iso_20022_camt::camt_026_001_09 as camt_026;
+use iso_20022_camt::camt_029_001_11 as camt_029;
+use iso_20022_camt::camt_056_001_10 as camt_056;
+use iso_20022_camt::camt_087_001_08 as camt_087;
+
+use crate::external_codes::{
+    ExternalCancellationReason1Code, ExternalInvestigationExecutionConfirmation1Code,
+    ExternalPaymentCancellationRejection1Code, ExternalUnableToApplyIncorrectData1Code,
+    ExternalUnableToApplyMissingData1Code,
+};
+
+use super::{
+    convert, format_amount, invalid_field, lost, parse_date_currency_amount, truncate, truncated,
+    wrap, Block4, Error, Field, FinHeader, MessageType, MtTranslation, TranslationIssue, IBAN,
+};
+
+/// Maximum length of the references of fields `20` and `21`.
+const REFERENCE_LENGTH: usize = 16;
+
+/// Maximum length of a line of the narrative of field `79`.
+const NARRATIVE_LINE_LENGTH: usize = 50;
+
+/// Maximum number of lines of the narrative of field `79`.
+const NARRATIVE_LINES: usize = 35;
+
+/// Maximum length of a line of the answers of field `76`.
+const ANSWER_LINE_LENGTH: usize = 35;
+
+/// Maximum number of lines of the answers of field `76`.
+const ANSWER_LINES: usize = 6;
+
+/// Maximum length of the additional information of the reasons.
+const ADDITIONAL_INFORMATION_LENGTH: usize = 105;
+
+/// Maximum length of the texts of the queries and of the requested modification.
+const TEXT_LENGTH: usize = 140;
+
+/// Query codes of field `75` of a request to modify the payment.
+const MODIFICATION_QUERIES: [&str; 5] = ["CDTR", "CACC", "DBTR", "RMTI", "PURP"];
+
+/// Query code of field `75` of a possible duplicate instruction.
+const DUPLICATE: &str = "DUPL";
+
+/// Confirmation codes of field `76` with a transaction cancellation status.
+const CANCELLATION_STATUSES: [(&str, camt_029::CancellationIndividualStatus1Code); 3] = [
+    ("CNCL", camt_029::CancellationIndividualStatus1Code::Accr),
+    ("PDCR", camt_029::CancellationIndividualStatus1Code::Pdcr),
+    ("RJCR", camt_029::CancellationIndividualStatus1Code::Rjcr),
+];
+
+/// Message name identifications of the original payments of `camt.056.001.10`, without MT
+/// message number, e.g. of a payment initiated in ISO 20022.
+const MESSAGE_NUMBERS: [(&str, &str); 2] = [("pacs.008", "103"), ("pacs.009", "202")];
+
+lazy_static! {
+    /// Code of a narrative line, with its text, e.g. `/DUPL/` or `/CDTR/ACME GMBH`.
+    static ref CODE: Regex = Regex::new(r"^/([A-Z0-9]{4})/(.*)$").unwrap();
+    /// Answer of field `76`, with its reason code and text, e.g. `/RJCR/AC04 ACCOUNT CLOSED`.
+    static ref ANSWER: Regex = Regex::new(r"^/([A-Z0-9]{4})/([A-Z0-9]{4}\b)?\s*(.*)$").unwrap();
+}
+
+/// ISO 20022 query of an `MTn95`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query<A>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    /// Missing or incorrect information of the payment, possible duplicate or any information
+    UnableToApply(camt_026::Document<A>),
+    /// Modification of the creditor, creditor account, debtor, remittance information or
+    /// purpose of the payment
+    RequestToModifyPayment(camt_087::Document<A>),
+}
+
+/// Translate the block 4 of an `MT192` or `MT292` into a `camt.056.001.10`.
+///
+/// The sender of the header is the assigner, and creator, of the case, and the receiver its
+/// assignee.
+pub fn mt192_to_camt_056<A, B>(
+    header: &FinHeader,
+    block4: &Block4,
+) -> Result<camt_056::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let id = &block4.mandatory("20")?.value;
+    let orgnl_id = &block4.mandatory("21")?.value;
+    let orgnl_grp_inf = original_group(block4.mandatory("11S")?, orgnl_id)?;
+    let (orgnl_intr_bk_sttlm_dt, orgnl_intr_bk_sttlm_amt) = copy_of_32a(block4)?.unzip();
+
+    let cxl_rsn_inf = block4
+        .field("79")
+        .map(|field| {
+            let (code, text) = narrative(field);
+
+            camt_056::PaymentCancellationReason5 {
+                orgtr: None,
+                rsn: code.map(|code| camt_056::CancellationReason33Choice {
+                    value: if is_code::<ExternalCancellationReason1Code>(&code) {
+                        camt_056::CancellationReason33ChoiceEnum {
+                            cd: Some(camt_056::ExternalCancellationReason1Code { value: code }),
+                            ..Default::default()
+                        }
+                    } else {
+                        camt_056::CancellationReason33ChoiceEnum {
+                            prtry: Some(camt_056::Max35Text { value: code }),
+                            ..Default::default()
+                        }
+                    },
+                }),
+                addtl_inf: wrap(
+                    &text,
+                    ADDITIONAL_INFORMATION_LENGTH,
+                    ADDITIONAL_INFORMATION_LENGTH,
+                )
+                .map(|value| camt_056::Max105Text { value })
+                .collect(),
+            }
+        })
+        .into_iter()
+        .collect();
+
+    let tx_inf = camt_056::PaymentTransaction137 {
+        cxl_id: Some(max_35_text(id)),
+        case: Some(case(header, id)),
+        orgnl_grp_inf: Some(orgnl_grp_inf),
+        orgnl_instr_id: Some(max_35_text(orgnl_id)),
+        orgnl_uetr: header.uetr.as_ref().map(|uetr| camt_056::UuiDv4Identifier {
+            value: uetr.clone(),
+        }),
+        orgnl_intr_bk_sttlm_amt,
+        orgnl_intr_bk_sttlm_dt,
+        cxl_rsn_inf,
+        ..Default::default()
+    };
+
+    Ok(camt_056::Document {
+        fi_to_fi_pmt_cxl_req: camt_056::FiToFiPaymentCancellationRequestV10 {
+            assgnmt: assignment(header, id),
+            case: Some(case(header, id)),
+            ctrl_data: Some(camt_056::ControlData1 {
+                nb_of_txs: camt_056::Max15NumericText {
+                    value: "1".to_string(),
+                },
+                ctrl_sum: None,
+            }),
+            undrlyg: vec![camt_056::UnderlyingTransaction28 {
+                orgnl_grp_inf_and_cxl: None,
+                tx_inf: vec![tx_inf],
+            }],
+            splmtry_data: vec![],
+        },
+        xmlns: camt_056::namespace(),
+    })
+}
+
+/// Translate each transaction of a `camt.056.001.10` into an `MT192`, or an `MT292` for the
+/// cancellation of an institution transfer.
+///
+/// The reason of the cancellation is the code of the first line of field `79`, and its
+/// additional information the narrative of the following lines.
+pub fn camt_056_to_mt192<A, B>(
+    document: &camt_056::Document<A, B>,
+) -> Result<Vec<MtTranslation>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let req = &document.fi_to_fi_pmt_cxl_req;
+    let header = FinHeader {
+        sender: party_bic(&req.assgnmt.assgnr)
+            .ok_or(Error::MissingElement("Assgnmt/Assgnr/Agt/FinInstnId/BICFI"))?,
+        receiver: party_bic(&req.assgnmt.assgne)
+            .ok_or(Error::MissingElement("Assgnmt/Assgne/Agt/FinInstnId/BICFI"))?,
+        uetr: None,
+    };
+
+    let mut translations = vec![];
+
+    for undrlyg in req.undrlyg.iter() {
+        for tx in undrlyg.tx_inf.iter() {
+            let mut issues = vec![];
+            let mut block4 = Block4::default();
+
+            let id = tx
+                .cxl_id
+                .as_ref()
+                .or(tx.case.as_ref().map(|case| &case.id))
+                .or(req.case.as_ref().map(|case| &case.id))
+                .unwrap_or(&req.assgnmt.id);
+            block4.push(
+                "20",
+                truncate(&id.value, REFERENCE_LENGTH, "20", "CxlId", &mut issues),
+            );
+
+            let orgnl_grp_inf = tx.orgnl_grp_inf.clone().or_else(|| {
+                undrlyg.orgnl_grp_inf_and_cxl.as_ref().map(|grp| {
+                    camt_056::OriginalGroupInformation29 {
+                        orgnl_msg_id: grp.orgnl_msg_id.clone(),
+                        orgnl_msg_nm_id: grp.orgnl_msg_nm_id.clone(),
+                        orgnl_cre_dt_tm: grp.orgnl_cre_dt_tm.clone(),
+                    }
+                })
+            });
+
+            let orgnl_id = tx
+                .orgnl_instr_id
+                .as_ref()
+                .or(tx.orgnl_end_to_end_id.as_ref())
+                .or(orgnl_grp_inf.as_ref().map(|grp| &grp.orgnl_msg_id))
+                .ok_or(Error::MissingElement("OrgnlInstrId"))?;
+            block4.push(
+                "21",
+                truncate(
+                    &orgnl_id.value,
+                    REFERENCE_LENGTH,
+                    "21",
+                    "OrgnlInstrId",
+                    &mut issues,
+                ),
+            );
+
+            let orgnl_grp_inf =
+                orgnl_grp_inf.ok_or(Error::MissingElement("OrgnlGrpInf/OrgnlMsgNmId"))?;
+            let number = message_number(&orgnl_grp_inf.orgnl_msg_nm_id.value)
+                .ok_or(Error::MissingElement("OrgnlGrpInf/OrgnlMsgNmId"))?;
+            let dt = orgnl_grp_inf
+                .orgnl_cre_dt_tm
+                .as_ref()
+                .map(|cre_dt_tm| cre_dt_tm.value.date_naive())
+                .or(tx.orgnl_intr_bk_sttlm_dt.as_ref().map(|dt| dt.value))
+                .ok_or(Error::MissingElement("OrgnlGrpInf/OrgnlCreDtTm"))?;
+            block4.push("11S", format!("{}\n{}", number, dt.format("%y%m%d")));
+
+            let mut cxl_rsn_inf = tx.cxl_rsn_inf.iter();
+            if let Some(rsn_inf) = cxl_rsn_inf.next() {
+                let code = rsn_inf.rsn.as_ref().and_then(|rsn| {
+                    rsn.value
+                        .cd
+                        .as_ref()
+                        .map(|cd| cd.value.clone())
+                        .or_else(|| rsn.value.prtry.as_ref().map(|prtry| prtry.value.clone()))
+                });
+                let text = rsn_inf
+                    .addtl_inf
+                    .iter()
+                    .map(|addtl_inf| addtl_inf.value.as_str())
+                    .collect::<String>();
+
+                if let Some(field) = narrative_field(
+                    "79",
+                    code.map(|code| format!("/{}/", code)),
+                    &text,
+                    (NARRATIVE_LINE_LENGTH, NARRATIVE_LINES),
+                    "CxlRsnInf/AddtlInf",
+                    &mut issues,
+                ) {
+                    block4.fields.push(field);
+                }
+            }
+            if cxl_rsn_inf.next().is_some() {
+                issues.push(lost("CxlRsnInf"));
+            }
+
+            if let (Some(amt), Some(dt)) = (&tx.orgnl_intr_bk_sttlm_amt, &tx.orgnl_intr_bk_sttlm_dt)
+            {
+                block4.push(
+                    "32A",
+                    format!(
+                        "{}{}{}",
+                        dt.value.format("%y%m%d"),
+                        amt.ccy.value,
+                        format_amount(amt.value.value, &amt.ccy.value)
+                    ),
+                );
+            }
+
+            let message_type = if number.starts_with('2') {
+                MessageType::Mt292
+            } else {
+                MessageType::Mt192
+            };
+
+            translations.push(MtTranslation {
+                message_type,
+                header: FinHeader {
+                    uetr: tx.orgnl_uetr.as_ref().map(|uetr| uetr.value.clone()),
+                    ..header.clone()
+                },
+                block4,
+                issues,
+            });
+        }
+    }
+
+    Ok(translations)
+}
+
+/// Translate the block 4 of an `MT196` or `MT296` into a `camt.029.001.11`.
+///
+/// The sender of the header is the assigner of the resolution, i.e. the assignee of the
+/// resolved case, and the receiver the creator of the resolved case. The reason and the
+/// narrative of the answer are carried in the cancellation details.
+pub fn mt196_to_camt_029<A>(
+    header: &FinHeader,
+    block4: &Block4,
+) -> Result<camt_029::Document<A>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let id = &block4.mandatory("20")?.value;
+    let case_id = &block4.mandatory("21")?.value;
+    let answer = block4.mandatory("76")?;
+
+    let mut lines = answer.lines();
+    let captures = lines
+        .next()
+        .and_then(|line| ANSWER.captures(line))
+        .ok_or_else(|| invalid_field(&answer.tag, &answer.value))?;
+
+    let conf = captures[1].to_string();
+    if !is_code::<ExternalInvestigationExecutionConfirmation1Code>(&conf) {
+        return Err(invalid_field(&answer.tag, &answer.value));
+    }
+
+    let reason = captures.get(2).map(|reason| reason.as_str().to_string());
+    let text = std::iter::once(&captures[3])
+        .chain(lines)
+        .collect::<String>();
+
+    let rslvd_case: camt_029::Case5 = convert(&camt_056::Case5 {
+        id: max_35_text(case_id),
+        cretr: agent(&header.receiver),
+        reop_case_indctn: None,
+    })?;
+    let (orgnl_intr_bk_sttlm_dt, orgnl_intr_bk_sttlm_amt) = copy_of_32a(block4)?.unzip();
+
+    let cxl_sts_rsn_inf = (reason.is_some() || !text.is_empty())
+        .then(|| camt_029::CancellationStatusReason4 {
+            orgtr: None,
+            rsn: reason.map(|reason| camt_029::CancellationStatusReason3Choice {
+                value: if is_code::<ExternalPaymentCancellationRejection1Code>(&reason) {
+                    camt_029::CancellationStatusReason3ChoiceEnum {
+                        cd: Some(camt_029::ExternalPaymentCancellationRejection1Code {
+                            value: reason,
+                        }),
+                        ..Default::default()
+                    }
+                } else {
+                    camt_029::CancellationStatusReason3ChoiceEnum {
+                        prtry: Some(camt_029::Max35Text { value: reason }),
+                        ..Default::default()
+                    }
+                },
+            }),
+            addtl_inf: wrap(
+                &text,
+                ADDITIONAL_INFORMATION_LENGTH,
+                ADDITIONAL_INFORMATION_LENGTH,
+            )
+            .map(|value| camt_029::Max105Text { value })
+            .collect(),
+        })
+        .into_iter()
+        .collect();
+
+    let tx_inf_and_sts = camt_029::PaymentTransaction138 {
+        cxl_sts_id: Some(camt_029::Max35Text { value: id.clone() }),
+        rslvd_case: Some(rslvd_case.clone()),
+        orgnl_uetr: header.uetr.as_ref().map(|uetr| camt_029::UuiDv4Identifier {
+            value: uetr.clone(),
+        }),
+        tx_cxl_sts: CANCELLATION_STATUSES
+            .iter()
+            .find(|(code, _)| *code == conf)
+            .map(|(_, tx_cxl_sts)| tx_cxl_sts.clone()),
+        cxl_sts_rsn_inf,
+        orgnl_intr_bk_sttlm_amt: orgnl_intr_bk_sttlm_amt
+            .map(|amt| convert(&amt))
+            .transpose()?,
+        orgnl_intr_bk_sttlm_dt: orgnl_intr_bk_sttlm_dt
+            .map(|dt| camt_029::IsoDate { value: dt.value }),
+        ..Default::default()
+    };
+
+    Ok(camt_029::Document {
+        rsltn_of_invstgtn: camt_029::ResolutionOfInvestigationV11 {
+            assgnmt: convert(&assignment(header, id))?,
+            rslvd_case: Some(rslvd_case),
+            sts: camt_029::InvestigationStatus5Choice {
+                value: camt_029::InvestigationStatus5ChoiceEnum {
+                    conf: Some(camt_029::ExternalInvestigationExecutionConfirmation1Code {
+                        value: conf,
+                    }),
+                    ..Default::default()
+                },
+            },
+            cxl_dtls: vec![camt_029::UnderlyingTransaction29 {
+                tx_inf_and_sts: vec![tx_inf_and_sts],
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        xmlns: camt_029::namespace(),
+    })
+}
+
+/// Translate a `camt.029.001.11` into an `MT196`, or an `MT296` when the original payment of
+/// the cancellation details is an institution transfer.
+///
+/// The answer of field `76` is the confirmation code of the status, or the transaction
+/// cancellation status of the first cancellation details, with the reason and the additional
+/// information of their first reason.
+pub fn camt_029_to_mt196<A>(document: &camt_029::Document<A>) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let rsltn = &document.rsltn_of_invstgtn;
+    let assgnmt: camt_056::CaseAssignment5 = convert(&rsltn.assgnmt)?;
+
+    let mut issues = vec![];
+    let mut block4 = Block4::default();
+
+    block4.push(
+        "20",
+        truncate(
+            &assgnmt.id.value,
+            REFERENCE_LENGTH,
+            "20",
+            "Assgnmt/Id",
+            &mut issues,
+        ),
+    );
+
+    let mut txs = rsltn
+        .cxl_dtls
+        .iter()
+        .flat_map(|cxl_dtls| cxl_dtls.tx_inf_and_sts.iter());
+    let tx = txs.next();
+    if txs.next().is_some() {
+        issues.push(lost("CxlDtls/TxInfAndSts"));
+    }
+
+    let case_id = rsltn
+        .rslvd_case
+        .as_ref()
+        .or(tx.and_then(|tx| tx.rslvd_case.as_ref()))
+        .map(|case| case.id.value.as_str())
+        .ok_or(Error::MissingElement("RslvdCase/Id"))?;
+    block4.push(
+        "21",
+        truncate(case_id, REFERENCE_LENGTH, "21", "RslvdCase/Id", &mut issues),
+    );
+
+    let conf = rsltn
+        .sts
+        .value
+        .conf
+        .as_ref()
+        .map(|conf| conf.value.clone())
+        .or_else(|| {
+            let tx_cxl_sts = tx?.tx_cxl_sts.as_ref()?;
+            CANCELLATION_STATUSES
+                .iter()
+                .find(|(_, status)| status == tx_cxl_sts)
+                .map(|(code, _)| code.to_string())
+        })
+        .ok_or(Error::MissingElement("Sts/Conf"))?;
+
+    let mut rsn_infs = tx.into_iter().flat_map(|tx| tx.cxl_sts_rsn_inf.iter());
+    let rsn_inf = rsn_infs.next();
+    if rsn_infs.next().is_some() {
+        issues.push(lost("CxlStsRsnInf"));
+    }
+
+    let reason = rsn_inf
+        .and_then(|rsn_inf| rsn_inf.rsn.as_ref())
+        .and_then(|rsn| {
+            rsn.value
+                .cd
+                .as_ref()
+                .map(|cd| cd.value.clone())
+                .or_else(|| rsn.value.prtry.as_ref().map(|prtry| prtry.value.clone()))
+        })
+        .unwrap_or_default();
+    let text = rsn_inf
+        .into_iter()
+        .flat_map(|rsn_inf| rsn_inf.addtl_inf.iter())
+        .map(|addtl_inf| addtl_inf.value.as_str())
+        .collect::<String>();
+
+    if let Some(field) = narrative_field(
+        "76",
+        Some(format!("/{}/{}", conf, reason)),
+        &text,
+        (ANSWER_LINE_LENGTH, ANSWER_LINES),
+        "CxlStsRsnInf/AddtlInf",
+        &mut issues,
+    ) {
+        block4.fields.push(field);
+    }
+
+    if let Some(tx) = tx {
+        if let (Some(amt), Some(dt)) = (&tx.orgnl_intr_bk_sttlm_amt, &tx.orgnl_intr_bk_sttlm_dt) {
+            block4.push(
+                "32A",
+                format!(
+                    "{}{}{}",
+                    dt.value.format("%y%m%d"),
+                    amt.ccy.value,
+                    format_amount(amt.value.value, &amt.ccy.value)
+                ),
+            );
+        }
+    }
+
+    let number = tx
+        .and_then(|tx| tx.orgnl_grp_inf.as_ref())
+        .and_then(|grp| message_number(&grp.orgnl_msg_nm_id.value));
+    let institution_transfer = matches!(number, Some(number) if number.starts_with('2'));
+    let message_type = if institution_transfer {
+        MessageType::Mt296
+    } else {
+        MessageType::Mt196
+    };
+
+    Ok(MtTranslation {
+        message_type,
+        header: FinHeader {
+            sender: party_bic(&assgnmt.assgnr)
+                .ok_or(Error::MissingElement("Assgnmt/Assgnr/Agt/FinInstnId/BICFI"))?,
+            receiver: party_bic(&assgnmt.assgne)
+                .ok_or(Error::MissingElement("Assgnmt/Assgne/Agt/FinInstnId/BICFI"))?,
+            uetr: tx
+                .and_then(|tx| tx.orgnl_uetr.as_ref())
+                .map(|uetr| uetr.value.clone()),
+        },
+        block4,
+        issues,
+    })
+}
+
+/// Translate the block 4 of an `MT195` or `MT295` into a `camt.087.001.08` for the queries of
+/// field `75` on the creditor `CDTR`, creditor account `CACC`, debtor `DBTR`, remittance
+/// information `RMTI` or purpose `PURP` of the payment, or else into a `camt.026.001.09`.
+///
+/// The queries of a `camt.026.001.09` are the missing information codes, e.g. `/MS01/`, the
+/// incorrect information codes, e.g. `/IN01/`, with their narrative, the possible duplicate
+/// `/DUPL/`, and else any information. The original payment is identified by field `21` and
+/// the copy of its field `32A`.
+pub fn mt195_to_camt<A>(header: &FinHeader, block4: &Block4) -> Result<Query<A>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let id = &block4.mandatory("20")?.value;
+    let orgnl_id = &block4.mandatory("21")?.value;
+    let queries = queries(block4.mandatory("75")?);
+    let (orgnl_intr_bk_sttlm_dt, orgnl_intr_bk_sttlm_amt) =
+        copy_of_32a(block4)?.ok_or(Error::MissingField("32A"))?;
+    let orgnl_grp_inf = block4
+        .field("11S")
+        .or_else(|| block4.field("11R"))
+        .map(|field| original_group(field, orgnl_id))
+        .transpose()?;
+
+    let intr_bk = camt_026::UnderlyingPaymentTransaction6 {
+        orgnl_grp_inf: orgnl_grp_inf.map(|grp| convert(&grp)).transpose()?,
+        orgnl_instr_id: Some(camt_026::Max35Text {
+            value: orgnl_id.clone(),
+        }),
+        orgnl_uetr: header.uetr.as_ref().map(|uetr| camt_026::UuiDv4Identifier {
+            value: uetr.clone(),
+        }),
+        orgnl_intr_bk_sttlm_amt: convert(&orgnl_intr_bk_sttlm_amt)?,
+        orgnl_intr_bk_sttlm_dt: camt_026::IsoDate {
+            value: orgnl_intr_bk_sttlm_dt.value,
+        },
+        ..Default::default()
+    };
+
+    let modification = queries.iter().any(
+        |(code, _)| matches!(code.as_deref(), Some(code) if MODIFICATION_QUERIES.contains(&code)),
+    );
+
+    if modification {
+        return Ok(Query::RequestToModifyPayment(camt_087::Document {
+            req_to_modfy_pmt: camt_087::RequestToModifyPaymentV08 {
+                assgnmt: convert(&assignment(header, id))?,
+                case: Some(convert(&case(header, id))?),
+                undrlyg: camt_087::UnderlyingTransaction7Choice {
+                    value: camt_087::UnderlyingTransaction7ChoiceEnum {
+                        intr_bk: Some(convert(&intr_bk)?),
+                        ..Default::default()
+                    },
+                },
+                r#mod: requested_modification(&queries),
+                instr_for_assgne: block4.field("77A").map(|field| {
+                    camt_087::InstructionForAssignee1 {
+                        cd: None,
+                        instr_inf: Some(camt_087::Max140Text {
+                            value: max_140_text(field.lines().collect()),
+                        }),
+                    }
+                }),
+                splmtry_data: vec![],
+            },
+            xmlns: camt_087::namespace(),
+        }));
+    }
+
+    let mut mssng_or_incrrct_inf = camt_026::MissingOrIncorrectData1::default();
+    for (code, narrative) in queries.iter() {
+        let code = match code {
+            Some(code) if code != DUPLICATE => code.clone(),
+            _ => continue,
+        };
+        let narrative = Some(max_140_text(narrative.clone()))
+            .filter(|narrative| !narrative.is_empty())
+            .map(|value| camt_026::Max140Text { value });
+
+        if is_code::<ExternalUnableToApplyMissingData1Code>(&code) {
+            mssng_or_incrrct_inf
+                .mssng_inf
+                .push(camt_026::UnableToApplyMissing2 {
+                    tp: camt_026::MissingData1Choice {
+                        value: camt_026::MissingData1ChoiceEnum {
+                            cd: Some(camt_026::ExternalUnableToApplyMissingData1Code {
+                                value: code,
+                            }),
+                            ..Default::default()
+                        },
+                    },
+                    addtl_mssng_inf: narrative,
+                });
+        } else if is_code::<ExternalUnableToApplyIncorrectData1Code>(&code) {
+            mssng_or_incrrct_inf
+                .incrrct_inf
+                .push(camt_026::UnableToApplyIncorrect2 {
+                    tp: camt_026::IncorrectData1Choice {
+                        value: camt_026::IncorrectData1ChoiceEnum {
+                            cd: Some(camt_026::ExternalUnableToApplyIncorrectData1Code {
+                                value: code,
+                            }),
+                            ..Default::default()
+                        },
+                    },
+                    addtl_incrrct_inf: narrative,
+                });
+        } else {
+            mssng_or_incrrct_inf
+                .mssng_inf
+                .push(camt_026::UnableToApplyMissing2 {
+                    tp: camt_026::MissingData1Choice {
+                        value: camt_026::MissingData1ChoiceEnum {
+                            prtry: Some(camt_026::Max35Text { value: code }),
+                            ..Default::default()
+                        },
+                    },
+                    addtl_mssng_inf: narrative,
+                });
+        }
+    }
+
+    let justfn = if !mssng_or_incrrct_inf.mssng_inf.is_empty()
+        || !mssng_or_incrrct_inf.incrrct_inf.is_empty()
+    {
+        camt_026::UnableToApplyJustification4ChoiceEnum {
+            mssng_or_incrrct_inf: Some(mssng_or_incrrct_inf),
+            ..Default::default()
+        }
+    } else if queries
+        .iter()
+        .any(|(code, _)| code.as_deref() == Some(DUPLICATE))
+    {
+        camt_026::UnableToApplyJustification4ChoiceEnum {
+            pssbl_dplct_instr: Some(camt_026::TrueFalseIndicator { value: true }),
+            ..Default::default()
+        }
+    } else {
+        camt_026::UnableToApplyJustification4ChoiceEnum {
+            any_inf: Some(camt_026::YesNoIndicator { value: true }),
+            ..Default::default()
+        }
+    };
+
+    Ok(Query::UnableToApply(camt_026::Document {
+        ubl_to_apply: camt_026::UnableToApplyV09 {
+            assgnmt: convert(&assignment(header, id))?,
+            case: Some(convert(&case(header, id))?),
+            undrlyg: camt_026::UnderlyingTransaction7Choice {
+                value: camt_026::UnderlyingTransaction7ChoiceEnum {
+                    intr_bk: Some(intr_bk),
+                    ..Default::default()
+                },
+            },
+            justfn: camt_026::UnableToApplyJustification4Choice { value: justfn },
+            splmtry_data: vec![],
+        },
+        xmlns: camt_026::namespace(),
+    }))
+}
+
+/// Return the requested modification of the queries of field `75`.
+fn requested_modification(
+    queries: &[(Option<String>, String)],
+) -> camt_087::RequestedModification10 {
+    let mut r#mod = camt_087::RequestedModification10::default();
+
+    for (code, narrative) in queries.iter() {
+        match code.as_deref() {
+            Some("CDTR") => r#mod.cdtr = Some(party(narrative)),
+            Some("CACC") => r#mod.cdtr_acct = Some(cash_account(narrative)),
+            Some("DBTR") => r#mod.dbtr = Some(party(narrative)),
+            Some("RMTI") => {
+                r#mod.rmt_inf = Some(camt_087::RemittanceInformation21 {
+                    ustrd: wrap(narrative, TEXT_LENGTH, TEXT_LENGTH)
+                        .map(|value| camt_087::Max140Text { value })
+                        .collect(),
+                    strd: vec![],
+                })
+            }
+            Some("PURP") => {
+                r#mod.purp = Some(camt_087::Purpose2Choice {
+                    value: camt_087::Purpose2ChoiceEnum {
+                        cd: Some(camt_087::ExternalPurpose1Code {
+                            value: narrative.clone(),
+                        }),
+                        ..Default::default()
+                    },
+                })
+            }
+            _ => {}
+        }
+    }
+
+    r#mod
+}
+
+fn party(name: &str) -> camt_087::PartyIdentification135 {
+    camt_087::PartyIdentification135 {
+        nm: Some(camt_087::Max140Text {
+            value: max_140_text(name.to_string()),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Return the cash account, identified by an IBAN or a proprietary identification.
+fn cash_account(id: &str) -> camt_087::CashAccount40 {
+    let value = if IBAN.is_match(id) {
+        camt_087::AccountIdentification4ChoiceEnum {
+            iban: Some(camt_087::Iban2007Identifier {
+                value: id.to_string(),
+            }),
+            ..Default::default()
+        }
+    } else {
+        camt_087::AccountIdentification4ChoiceEnum {
+            othr: Some(camt_087::GenericAccountIdentification1 {
+                id: camt_087::Max34Text {
+                    value: id.to_string(),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    };
+
+    camt_087::CashAccount40 {
+        id: Some(camt_087::AccountIdentification4Choice { value }),
+        ..Default::default()
+    }
+}
+
+/// Parse the queries of field `75`, i.e. the lines starting with a code, e.g. `/CDTR/`, with
+/// their narrative continued on the following lines.
+fn queries(field: &Field) -> Vec<(Option<String>, String)> {
+    let mut queries: Vec<(Option<String>, String)> = vec![];
+
+    for line in field.lines() {
+        if let Some(captures) = CODE.captures(line) {
+            queries.push((Some(captures[1].to_string()), captures[2].to_string()));
+        } else if let Some((_, narrative)) = queries.last_mut() {
+            narrative.push_str(line);
+        } else {
+            queries.push((None, line.to_string()));
+        }
+    }
+
+    queries
+}
+
+/// Parse the code of the first line, e.g. `/DUPL/`, and the narrative of a field.
+fn narrative(field: &Field) -> (Option<String>, String) {
+    let mut lines = field.lines();
+
+    match lines.next().and_then(|line| CODE.captures(line)) {
+        Some(captures) => (
+            Some(captures[1].to_string()),
+            std::iter::once(&captures[2]).chain(lines).collect(),
+        ),
+        None => (None, field.lines().collect()),
+    }
+}
+
+/// Render a narrative field, of its first line, e.g. `/DUPL/`, and the narrative of the
+/// following lines, truncated to the number of lines.
+fn narrative_field(
+    tag: &str,
+    first: Option<String>,
+    text: &str,
+    (length, lines): (usize, usize),
+    element: &str,
+    issues: &mut Vec<TranslationIssue>,
+) -> Option<Field> {
+    let mut value = first.into_iter().collect::<Vec<_>>();
+    let narrative = wrap(text, length, length).collect::<Vec<_>>();
+
+    if value.len() + narrative.len() > lines {
+        issues.push(truncated(tag, element, text));
+    }
+    value.extend(
+        narrative
+            .into_iter()
+            .take(lines.saturating_sub(value.len())),
+    );
+
+    (!value.is_empty()).then(|| Field::new(tag, value.join("\n")))
+}
+
+/// Parse the `MT` number and date of the original message of field `11S` or `11R`, e.g.
+/// `103\n230401`.
+fn original_group(
+    field: &Field,
+    orgnl_msg_id: &str,
+) -> Result<camt_056::OriginalGroupInformation29, Error> {
+    let mut lines = field.lines();
+    let (number, dt) = match (lines.next(), lines.next()) {
+        (Some(number), Some(dt)) if number.len() == 3 => (number, dt),
+        _ => return Err(invalid_field(&field.tag, &field.value)),
+    };
+
+    let dt = NaiveDate::parse_from_str(dt, "%y%m%d")
+        .ok()
+        .and_then(|dt| dt.and_hms_opt(0, 0, 0))
+        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+
+    Ok(camt_056::OriginalGroupInformation29 {
+        orgnl_msg_id: max_35_text(orgnl_msg_id),
+        orgnl_msg_nm_id: max_35_text(&format!("MT{}", number)),
+        orgnl_cre_dt_tm: Some(camt_056::IsoDateTime {
+            value: DateTime::<Utc>::from_utc(dt, Utc),
+        }),
+    })
+}
+
+/// Return the `MT` number of the message name identification of the original message, e.g.
+/// `103` of `MT103` or of `pacs.008.001.10`.
+fn message_number(orgnl_msg_nm_id: &str) -> Option<String> {
+    orgnl_msg_nm_id
+        .strip_prefix("MT")
+        .filter(|number| number.len() == 3 && number.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+        .or_else(|| {
+            MESSAGE_NUMBERS
+                .iter()
+                .find(|(name, _)| orgnl_msg_nm_id.starts_with(name))
+                .map(|(_, number)| number.to_string())
+        })
+}
+
+/// Parse the copy of field `32A` of the original payment, if any.
+fn copy_of_32a(
+    block4: &Block4,
+) -> Result<
+    Option<(
+        camt_056::IsoDate,
+        camt_056::ActiveOrHistoricCurrencyAndAmount,
+    )>,
+    Error,
+> {
+    block4
+        .field("32A")
+        .map(|field| {
+            let (dt, ccy, value) = parse_date_currency_amount(field)?;
+
+            Ok((
+                camt_056::IsoDate { value: dt },
+                camt_056::ActiveOrHistoricCurrencyAndAmount {
+                    value: camt_056::ActiveOrHistoricCurrencyAndAmountSimpleType { value },
+                    ccy: camt_056::ActiveOrHistoricCurrencyCode { value: ccy },
+                },
+            ))
+        })
+        .transpose()
+}
+
+/// Return the assignment of the case, from the sender to the receiver of the header.
+fn assignment(header: &FinHeader, id: &str) -> camt_056::CaseAssignment5 {
+    camt_056::CaseAssignment5 {
+        id: max_35_text(id),
+        assgnr: agent(&header.sender),
+        assgne: agent(&header.receiver),
+        cre_dt_tm: camt_056::IsoDateTime { value: Utc::now() },
+    }
+}
+
+/// Return the case, created by the sender of the header.
+fn case(header: &FinHeader, id: &str) -> camt_056::Case5 {
+    camt_056::Case5 {
+        id: max_35_text(id),
+        cretr: agent(&header.sender),
+        reop_case_indctn: None,
+    }
+}
+
+/// Return the `Party40Choice` agent of a BIC.
+fn agent(bic: &str) -> camt_056::Party40Choice {
+    camt_056::Party40Choice {
+        value: camt_056::Party40ChoiceEnum {
+            agt: Some(camt_056::BranchAndFinancialInstitutionIdentification6 {
+                fin_instn_id: camt_056::FinancialInstitutionIdentification18 {
+                    bicfi: Some(camt_056::BicfiDec2014Identifier {
+                        value: bic.to_string(),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            pty: None,
+        },
+    }
+}
+
+/// Return the BIC of a `Party40Choice` agent.
+fn party_bic(party: &camt_056::Party40Choice) -> Option<String> {
+    party
+        .value
+        .agt
+        .as_ref()?
+        .fin_instn_id
+        .bicfi
+        .as_ref()
+        .map(|bicfi| bicfi.value.clone())
+}
+
+/// Return whether a code is of an ISO 20022 external code set, e.g. `DUPL` of the
+/// `ExternalCancellationReason1Code`.
+fn is_code<T: DeserializeOwned>(code: &str) -> bool {
+    let deserializer: value::StrDeserializer<value::Error> = code.into_deserializer();
+
+    T::deserialize(deserializer).is_ok()
+}
+
+/// Truncate a text to the length of a `Max140Text`.
+fn max_140_text(value: String) -> String {
+    value.chars().take(TEXT_LENGTH).collect()
+}
+
+fn max_35_text(value: &str) -> camt_056::Max35Text {
+    camt_056::Max35Text {
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    const UETR: &str = "8a562c67-ca16-48ba-b074-65581be6f001";
+
+    fn header() -> FinHeader {
+        FinHeader {
+            sender: "AAAADEFFXXX".to_string(),
+            receiver: "BBBBCHZZXXX".to_string(),
+            uetr: Some(UETR.to_string()),
+        }
+    }
+
+    fn block4(lines: &[&str]) -> Block4 {
+        lines.join("\n").parse().unwrap()
+    }
+
+    #[test]
+    fn test_mt192_round_trip() {
+        let block4 = block4(&[
+            ":20:CXL230401",
+            ":21:PAY-REF-1",
+            ":11S:103",
+            "230401",
+            ":79:/DUPL/",
+            "DUPLICATE PAYMENT PLEASE RETURN THE FUNDS TO OUR A",
+            "CCOUNT",
+            ":32A:230401EUR1234,56",
+        ]);
+        let document = mt192_to_camt_056::<Dmkr, Dmkr>(&header(), &block4).unwrap();
+
+        let req = &document.fi_to_fi_pmt_cxl_req;
+        assert_eq!(req.assgnmt.id.value, "CXL230401");
+        assert_eq!(party_bic(&req.assgnmt.assgne).unwrap(), "BBBBCHZZXXX");
+
+        let tx = &req.undrlyg[0].tx_inf[0];
+        assert_eq!(tx.orgnl_instr_id.as_ref().unwrap().value, "PAY-REF-1");
+        assert_eq!(tx.orgnl_uetr.as_ref().unwrap().value, UETR);
+        assert_eq!(
+            tx.orgnl_grp_inf.as_ref().unwrap().orgnl_msg_nm_id.value,
+            "MT103"
+        );
+        assert_eq!(
+            tx.orgnl_intr_bk_sttlm_amt.as_ref().unwrap().value.value,
+            1234.56
+        );
+
+        let rsn_inf = &tx.cxl_rsn_inf[0];
+        let rsn = &rsn_inf.rsn.as_ref().unwrap().value;
+        assert_eq!(rsn.cd.as_ref().unwrap().value, "DUPL");
+        assert_eq!(
+            rsn_inf.addtl_inf[0].value,
+            "DUPLICATE PAYMENT PLEASE RETURN THE FUNDS TO OUR ACCOUNT"
+        );
+
+        let translations = camt_056_to_mt192(&document).unwrap();
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].message_type, MessageType::Mt192);
+        assert_eq!(translations[0].header, header());
+        assert_eq!(translations[0].block4, block4);
+        assert!(translations[0].issues.is_empty());
+    }
+
+    #[test]
+    fn test_mt292_proprietary_reason() {
+        let block4 = block4(&[
+            ":20:CXL230401",
+            ":21:INST-REF-1",
+            ":11S:202",
+            "230401",
+            ":79:/OTHR/",
+            "SENT IN ERROR",
+        ]);
+        let document = mt192_to_camt_056::<Dmkr, Dmkr>(&header(), &block4).unwrap();
+
+        let tx = &document.fi_to_fi_pmt_cxl_req.undrlyg[0].tx_inf[0];
+        let rsn = &tx.cxl_rsn_inf[0].rsn.as_ref().unwrap().value;
+        assert_eq!(rsn.cd, None);
+        assert_eq!(rsn.prtry.as_ref().unwrap().value, "OTHR");
+
+        let translations = camt_056_to_mt192(&document).unwrap();
+        assert_eq!(translations[0].message_type, MessageType::Mt292);
+        assert_eq!(translations[0].block4, block4);
+    }
+
+    #[test]
+    fn test_mt196_round_trip() {
+        let block4 = block4(&[
+            ":20:ANS230402",
+            ":21:CXL230401",
+            ":76:/RJCR/AC04",
+            "ACCOUNT CLOSED, FUNDS ALREADY CREDI",
+            "TED",
+        ]);
+        let document = mt196_to_camt_029::<Dmkr>(&header(), &block4).unwrap();
+
+        let rsltn = &document.rsltn_of_invstgtn;
+        assert_eq!(rsltn.sts.value.conf.as_ref().unwrap().value, "RJCR");
+        assert_eq!(rsltn.rslvd_case.as_ref().unwrap().id.value, "CXL230401");
+
+        let tx = &rsltn.cxl_dtls[0].tx_inf_and_sts[0];
+        assert_eq!(
+            tx.tx_cxl_sts,
+            Some(camt_029::CancellationIndividualStatus1Code::Rjcr)
+        );
+        let rsn_inf = &tx.cxl_sts_rsn_inf[0];
+        let rsn = &rsn_inf.rsn.as_ref().unwrap().value;
+        assert_eq!(rsn.cd.as_ref().unwrap().value, "AC04");
+        assert_eq!(
+            rsn_inf.addtl_inf[0].value,
+            "ACCOUNT CLOSED, FUNDS ALREADY CREDITED"
+        );
+
+        let translation = camt_029_to_mt196(&document).unwrap();
+        assert_eq!(translation.message_type, MessageType::Mt196);
+        assert_eq!(translation.header, header());
+        assert_eq!(translation.block4, block4);
+    }
+
+    #[test]
+    fn test_mt196_answer_codes() {
+        let document =
+            mt196_to_camt_029::<Dmkr>(&header(), &block4(&[":20:A", ":21:B", ":76:/CNCL/"]))
+                .unwrap();
+        let tx = &document.rsltn_of_invstgtn.cxl_dtls[0].tx_inf_and_sts[0];
+        assert_eq!(
+            tx.tx_cxl_sts,
+            Some(camt_029::CancellationIndividualStatus1Code::Accr)
+        );
+        assert!(tx.cxl_sts_rsn_inf.is_empty());
+
+        let document = mt196_to_camt_029::<Dmkr>(
+            &header(),
+            &block4(&[":20:A", ":21:B", ":76:/RJCR/LEGAL REASONS"]),
+        )
+        .unwrap();
+        let rsn_inf = &document.rsltn_of_invstgtn.cxl_dtls[0].tx_inf_and_sts[0].cxl_sts_rsn_inf[0];
+        assert_eq!(rsn_inf.rsn, None);
+        assert_eq!(rsn_inf.addtl_inf[0].value, "LEGAL REASONS");
+
+        assert!(mt196_to_camt_029::<Dmkr>(
+            &header(),
+            &block4(&[":20:A", ":21:B", ":76:NO ANSWER"])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_mt195_to_camt_026() {
+        let block4 = block4(&[
+            ":20:QRY230402",
+            ":21:PAY-REF-1",
+            ":11S:103",
+            "230401",
+            ":75:/MS01/",
+            "/IN07/INVALID CREDITOR ACCOUNT",
+            ":32A:230401EUR1234,56",
+        ]);
+
+        let document = match mt195_to_camt::<Dmkr>(&header(), &block4).unwrap() {
+            Query::UnableToApply(document) => document,
+            query => panic!("unexpected query {:?}", query),
+        };
+
+        let ubl_to_apply = &document.ubl_to_apply;
+        let intr_bk = ubl_to_apply.undrlyg.value.intr_bk.as_ref().unwrap();
+        assert_eq!(intr_bk.orgnl_instr_id.as_ref().unwrap().value, "PAY-REF-1");
+        assert_eq!(intr_bk.orgnl_intr_bk_sttlm_amt.ccy.value, "EUR");
+
+        let inf = ubl_to_apply
+            .justfn
+            .value
+            .mssng_or_incrrct_inf
+            .as_ref()
+            .unwrap();
+        assert_eq!(inf.mssng_inf[0].tp.value.cd.as_ref().unwrap().value, "MS01");
+        assert_eq!(
+            inf.incrrct_inf[0].tp.value.cd.as_ref().unwrap().value,
+            "IN07"
+        );
+        assert_eq!(
+            inf.incrrct_inf[0].addtl_incrrct_inf.as_ref().unwrap().value,
+            "INVALID CREDITOR ACCOUNT"
+        );
+
+        let duplicate = block4
+            .fields
+            .iter()
+            .map(|field| match field.tag.as_str() {
+                "75" => Field::new("75", "/DUPL/"),
+                _ => field.clone(),
+            })
+            .collect();
+        match mt195_to_camt::<Dmkr>(&header(), &Block4 { fields: duplicate }).unwrap() {
+            Query::UnableToApply(document) => assert_eq!(
+                document.ubl_to_apply.justfn.value.pssbl_dplct_instr,
+                Some(camt_026::TrueFalseIndicator { value: true })
+            ),
+            query => panic!("unexpected query {:?}", query),
+        }
+    }
+
+    #[test]
+    fn test_mt195_to_camt_087() {
+        let block4 = block4(&[
+            ":20:QRY230402",
+            ":21:PAY-REF-1",
+            ":75:/CDTR/ACME GMBH",
+            "/CACC/DE89370400440532013000",
+            ":77A:PLEASE AMEND",
+            ":32A:230401EUR1234,56",
+        ]);
+
+        let document = match mt195_to_camt::<Dmkr>(&header(), &block4).unwrap() {
+            Query::RequestToModifyPayment(document) => document,
+            query => panic!("unexpected query {:?}", query),
+        };
+
+        let r#mod = &document.req_to_modfy_pmt.r#mod;
+        assert_eq!(
+            r#mod.cdtr.as_ref().unwrap().nm.as_ref().unwrap().value,
+            "ACME GMBH"
+        );
+        let id = &r#mod.cdtr_acct.as_ref().unwrap().id.as_ref().unwrap().value;
+        assert_eq!(id.iban.as_ref().unwrap().value, "DE89370400440532013000");
+        assert_eq!(
+            document
+                .req_to_modfy_pmt
+                .instr_for_assgne
+                .as_ref()
+                .unwrap()
+                .instr_inf
+                .as_ref()
+                .unwrap()
+                .value,
+            "PLEASE AMEND"
+        );
+
+        let missing = Block4 {
+            fields: block4.fields[..4].to_vec(),
+        };
+        assert!(matches!(
+            mt195_to_camt::<Dmkr>(&header(), &missing),
+            Err(Error::MissingField("32A"))
+        ));
+    }
+
+    #[test]
+    fn test_message_number() {
+        assert_eq!(message_number("MT103").unwrap(), "103");
+        assert_eq!(message_number("pacs.009.001.10").unwrap(), "202");
+        assert_eq!(message_number("camt.053.001.10"), None);
+        assert!(is_code::<ExternalCancellationReason1Code>("DUPL"));
+        assert!(!is_code::<ExternalCancellationReason1Code>("OTHR"));
+    }
+}
@@ -0,0 +1,586 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # ISO 15022 Sequences
+//!
+//! The text block 4 of an ISO 15022 message, e.g. of an `MT540` or `MT564`, is a tree of
+//! `Sequence`s, delimited by the fields `:16R:` and `:16S:` of their name, e.g. `GENL`, of
+//! `GenericField`s, i.e. fields of a qualifier, an optional data source scheme and their data,
+//! e.g. `:98A::SETT//20230403`.
+//!
+//! The translations into ISO 20022 take the fields they map from their sequence, the fields
+//! left in the sequences being reported as `TranslationIssue::Unmapped`.
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::{value, DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Serialize};
+
+use super::{convert, invalid_field, Block4, Error, Field, TranslationIssue};
+
+lazy_static! {
+    /// Generic field of a qualifier, data source scheme and data, e.g. `:22F::SETR/ABCD/TRAD`.
+    static ref GENERIC_FIELD: Regex = Regex::new(r"(?s)^:([A-Z0-9]{4})/([A-Z0-9]{1,8})?/(.*)$").unwrap();
+}
+
+/// Generic field of an ISO 15022 message, of a qualifier, an optional data source scheme and
+/// its data, e.g. `:22F::SETR/ABCD/TRAD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericField {
+    /// Tag of the field, with its option letter, e.g. `22F`
+    pub tag: String,
+    /// Qualifier of the field, e.g. `SETR`
+    pub qualifier: String,
+    /// Data source scheme, i.e. the issuer of a proprietary code, e.g. `ABCD`
+    pub issuer: Option<String>,
+    /// Data of the field, e.g. `TRAD`
+    pub data: String,
+}
+
+impl GenericField {
+    pub fn new(tag: &str, qualifier: &str, data: impl Into<String>) -> Self {
+        Self {
+            tag: tag.to_string(),
+            qualifier: qualifier.to_string(),
+            issuer: None,
+            data: data.into(),
+        }
+    }
+
+    /// Parse a generic field, or return `None` for a field without qualifier, e.g. `:35B:`.
+    pub fn parse(field: &Field) -> Option<Self> {
+        let captures = GENERIC_FIELD.captures(&field.value)?;
+
+        Some(Self {
+            tag: field.tag.clone(),
+            qualifier: captures[1].to_string(),
+            issuer: captures.get(2).map(|issuer| issuer.as_str().to_string()),
+            data: captures[3].to_string(),
+        })
+    }
+
+    /// Return the number of the tag, e.g. `22` of `22F`.
+    pub fn number(&self) -> &str {
+        self.tag.get(..2).unwrap_or(&self.tag)
+    }
+
+    /// Return the field of the block 4.
+    pub fn field(&self) -> Field {
+        Field::new(
+            &self.tag,
+            format!(
+                ":{}/{}/{}",
+                self.qualifier,
+                self.issuer.as_deref().unwrap_or_default(),
+                self.data
+            ),
+        )
+    }
+
+    pub(crate) fn invalid(&self) -> Error {
+        let field = self.field();
+        invalid_field(&field.tag, &field.value)
+    }
+}
+
+/// Sequence of an ISO 15022 message, delimited by the fields `:16R:` and `:16S:` of its name,
+/// e.g. `GENL`, of its fields and subsequences.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    /// Name of the sequence, empty for the text block 4
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub sequences: Vec<Sequence>,
+}
+
+impl Sequence {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse the sequences of a text block 4.
+    pub fn parse(block4: &Block4) -> Result<Self, Error> {
+        let mut stack = vec![Sequence::default()];
+
+        for field in block4.fields.iter() {
+            match field.tag.as_str() {
+                "16R" => stack.push(Sequence::new(&field.value)),
+                "16S" => {
+                    let sequence = stack
+                        .pop()
+                        .filter(|sequence| !stack.is_empty() && sequence.name == field.value)
+                        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+                    if let Some(parent) = stack.last_mut() {
+                        parent.sequences.push(sequence);
+                    }
+                }
+                _ => {
+                    if let Some(sequence) = stack.last_mut() {
+                        sequence.fields.push(field.clone());
+                    }
+                }
+            }
+        }
+
+        match (stack.pop(), stack.is_empty()) {
+            (Some(block4), true) => Ok(block4),
+            _ => Err(Error::MissingField("16S")),
+        }
+    }
+
+    /// Return the text block 4 of the sequences, with their fields before their subsequences.
+    pub fn block4(&self) -> Block4 {
+        let mut block4 = Block4::default();
+        self.render(&mut block4);
+
+        block4
+    }
+
+    /// Return the first subsequence with the name, e.g. `LINK`.
+    pub fn sequence(&self, name: &str) -> Option<&Sequence> {
+        self.sequences.iter().find(|sequence| sequence.name == name)
+    }
+
+    /// Return the subsequences with the name, e.g. the repeated `SETPRTY`.
+    pub fn sequences_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Sequence> {
+        self.sequences
+            .iter()
+            .filter(move |sequence| sequence.name == name)
+    }
+
+    /// Return the first generic field with the number, in any option, and the qualifier, e.g.
+    /// `:98A::SETT//` or `:98C::SETT//` for `98` and `SETT`.
+    pub fn generic(&self, number: &str, qualifier: &str) -> Option<GenericField> {
+        self.fields
+            .iter()
+            .filter_map(GenericField::parse)
+            .find(|field| field.number() == number && field.qualifier == qualifier)
+    }
+
+    pub fn push(&mut self, tag: &str, value: impl Into<String>) {
+        self.fields.push(Field::new(tag, value));
+    }
+
+    pub fn push_generic(&mut self, field: GenericField) {
+        self.fields.push(field.field());
+    }
+
+    /// Push a subsequence, unless without fields.
+    pub(crate) fn push_sequence(&mut self, sequence: Sequence) {
+        if !sequence.fields.is_empty() || !sequence.sequences.is_empty() {
+            self.sequences.push(sequence);
+        }
+    }
+
+    /// Return the mutable subsequence with the name.
+    pub(crate) fn sequence_mut(&mut self, name: &str) -> Option<&mut Sequence> {
+        self.sequences
+            .iter_mut()
+            .find(|sequence| sequence.name == name)
+    }
+
+    /// Return the mandatory subsequence, e.g. `16R:GENL` for the `GENL` sequence.
+    pub(crate) fn mandatory_sequence(
+        &mut self,
+        field: &'static str,
+    ) -> Result<&mut Sequence, Error> {
+        let name = field.trim_start_matches("16R:");
+
+        self.sequence_mut(name).ok_or(Error::MissingField(field))
+    }
+
+    /// Return the mutable subsequences with the name.
+    pub(crate) fn sequences_mut<'a>(
+        &'a mut self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a mut Sequence> {
+        self.sequences
+            .iter_mut()
+            .filter(move |sequence| sequence.name == name)
+    }
+
+    /// Remove and return the first generic field of the number and qualifier, e.g. `98a::SETT`
+    /// for `:98A::SETT//` or `:98C::SETT//`.
+    pub(crate) fn take(&mut self, field: &str) -> Option<GenericField> {
+        let (number, qualifier) = (field.get(..2)?, field.get(5..)?);
+        let index = self.fields.iter().position(|field| {
+            matches!(GenericField::parse(field), Some(field) if field.number() == number && field.qualifier == qualifier)
+        })?;
+
+        GenericField::parse(&self.fields.remove(index))
+    }
+
+    /// Remove and return the generic fields of the number and qualifier, e.g. the repeated
+    /// `22F::STCO`.
+    pub(crate) fn take_all(&mut self, field: &str) -> Vec<GenericField> {
+        std::iter::from_fn(|| self.take(field)).collect()
+    }
+
+    /// Remove and return the mandatory generic field of the number and qualifier.
+    pub(crate) fn mandatory(&mut self, field: &'static str) -> Result<GenericField, Error> {
+        self.take(field).ok_or(Error::MissingField(field))
+    }
+
+    /// Remove and return the first field with the tag, e.g. `35B`.
+    pub(crate) fn take_field(&mut self, tag: &str) -> Option<Field> {
+        let index = self.fields.iter().position(|field| field.tag == tag)?;
+
+        Some(self.fields.remove(index))
+    }
+
+    /// Report the remaining fields, i.e. not taken by the translation, as unmapped.
+    pub(crate) fn unmapped(&self, path: &str, issues: &mut Vec<TranslationIssue>) {
+        let path = join_path(path, &self.name);
+
+        for field in self.fields.iter() {
+            issues.push(TranslationIssue::Unmapped {
+                field: join_path(&path, &format!(":{}:{}", field.tag, field.value)),
+            });
+        }
+        for sequence in self.sequences.iter() {
+            sequence.unmapped(&path, issues);
+        }
+    }
+
+    fn render(&self, block4: &mut Block4) {
+        if !self.name.is_empty() {
+            block4.push("16R", &self.name);
+        }
+        block4.fields.extend(self.fields.iter().cloned());
+        for sequence in self.sequences.iter() {
+            sequence.render(block4);
+        }
+        if !self.name.is_empty() {
+            block4.push("16S", &self.name);
+        }
+    }
+}
+
+/// ISO 20022 message translated from an ISO 15022 message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsoTranslation<D> {
+    pub document: D,
+    /// Qualified fields without counterpart in the ISO 20022 message
+    pub issues: Vec<TranslationIssue>,
+}
+
+/// Code or proprietary code of a choice, e.g. of a `SecuritiesTransactionType47Choice`, the
+/// issuer of the proprietary code being the data source scheme of the generic field.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Code {
+    #[serde(rename = "Cd", skip_serializing_if = "Option::is_none")]
+    cd: Option<String>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    prtry: Option<Proprietary>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Proprietary {
+    #[serde(rename = "Id")]
+    id: String,
+    #[serde(rename = "Issr")]
+    issr: String,
+}
+
+/// Date or date and time of a `DateAndDateTime2Choice`.
+#[derive(Debug, Default, Serialize)]
+struct DateAndDateTime {
+    #[serde(rename = "Dt", skip_serializing_if = "Option::is_none")]
+    dt: Option<NaiveDate>,
+    #[serde(rename = "DtTm", skip_serializing_if = "Option::is_none")]
+    dt_tm: Option<DateTime<Utc>>,
+}
+
+/// Financial instrument of a `SecurityIdentification19`.
+#[derive(Debug, Default, Serialize)]
+struct Security {
+    #[serde(rename = "ISIN", skip_serializing_if = "Option::is_none")]
+    isin: Option<String>,
+    #[serde(rename = "OthrId")]
+    othr_id: Vec<OtherIdentification>,
+    #[serde(rename = "Desc", skip_serializing_if = "Option::is_none")]
+    desc: Option<String>,
+}
+
+/// Other identification of a financial instrument, with its proprietary type.
+#[derive(Debug, Default, Serialize)]
+struct OtherIdentification {
+    #[serde(rename = "Id")]
+    id: String,
+    #[serde(rename = "Tp")]
+    tp: IdentificationSource,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct IdentificationSource {
+    #[serde(rename = "Prtry")]
+    prtry: String,
+}
+
+/// IBAN or proprietary identification of a `CashAccountIdentification5Choice`.
+#[derive(Debug, Default, Serialize)]
+struct CashAccount {
+    #[serde(rename = "IBAN", skip_serializing_if = "Option::is_none")]
+    iban: Option<String>,
+    #[serde(rename = "Prtry", skip_serializing_if = "Option::is_none")]
+    prtry: Option<String>,
+}
+
+impl Code {
+    pub(crate) fn parse(field: &GenericField) -> Self {
+        match &field.issuer {
+            Some(issr) => Self {
+                cd: None,
+                prtry: Some(Proprietary {
+                    id: field.data.clone(),
+                    issr: issr.clone(),
+                }),
+            },
+            None => Self {
+                cd: Some(field.data.clone()),
+                prtry: None,
+            },
+        }
+    }
+
+    /// Return the generic field of the code, or `None` for a choice without code.
+    pub(crate) fn field(self, tag: &str, qualifier: &str) -> Option<GenericField> {
+        match (self.cd, self.prtry) {
+            (Some(cd), _) => Some(GenericField::new(tag, qualifier, cd)),
+            (None, Some(prtry)) => Some(GenericField {
+                issuer: Some(prtry.issr),
+                ..GenericField::new(tag, qualifier, prtry.id)
+            }),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Convert the code of a generic field into the content of a choice, e.g. into a
+/// `SecuritiesTransactionType47ChoiceEnum`, failing for a code outside of its code set.
+pub(crate) fn choice<T: DeserializeOwned>(field: &GenericField) -> Result<T, Error> {
+    convert(&Code::parse(field)).map_err(|_| field.invalid())
+}
+
+/// Return the generic field of the content of a choice, e.g. of a
+/// `SecuritiesTransactionType47ChoiceEnum`.
+pub(crate) fn choice_field<T: Serialize>(
+    choice: &T,
+    tag: &str,
+    qualifier: &str,
+) -> Result<Option<GenericField>, Error> {
+    Ok(convert::<T, Code>(choice)?.field(tag, qualifier))
+}
+
+/// Parse a code of a code set, e.g. `NPAR` of the `SettlementTransactionCondition5Code`.
+pub(crate) fn parse_code<T: DeserializeOwned>(code: &str) -> Option<T> {
+    let deserializer: value::StrDeserializer<value::Error> = code.into_deserializer();
+
+    T::deserialize(deserializer).ok()
+}
+
+/// Parse the date of field `98A`, e.g. `20230403`, or the date and time of field `98C`, e.g.
+/// `20230403101500`, into the content of a `DateAndDateTime2Choice`.
+pub(crate) fn date_time<T: DeserializeOwned>(field: &GenericField) -> Result<T, Error> {
+    let dt = match field.tag.as_str() {
+        "98A" => DateAndDateTime {
+            dt: Some(
+                NaiveDate::parse_from_str(&field.data, "%Y%m%d").map_err(|_| field.invalid())?,
+            ),
+            dt_tm: None,
+        },
+        "98C" => {
+            let dt_tm = NaiveDateTime::parse_from_str(&field.data, "%Y%m%d%H%M%S")
+                .map_err(|_| field.invalid())?;
+
+            DateAndDateTime {
+                dt: None,
+                dt_tm: Some(DateTime::<Utc>::from_utc(dt_tm, Utc)),
+            }
+        }
+        _ => return Err(field.invalid()),
+    };
+
+    convert(&dt)
+}
+
+/// Parse the financial instrument of field `35B`, i.e. its ISIN, e.g. `ISIN US0378331005`,
+/// its other identifications, e.g. `/US/037833100`, and its description, into a
+/// `SecurityIdentification19`.
+pub(crate) fn security<T: DeserializeOwned>(field: &Field) -> Result<T, Error> {
+    let mut lines = field.lines().peekable();
+
+    let isin = lines
+        .next_if(|line| line.starts_with("ISIN "))
+        .map(|line| line["ISIN ".len()..].to_string());
+    let mut othr_id = vec![];
+    while let Some(id) = lines.peek().and_then(|line| other_identification(line)) {
+        othr_id.push(id);
+        lines.next();
+    }
+    let desc = lines.collect::<String>();
+
+    convert(&Security {
+        isin,
+        othr_id,
+        desc: (!desc.is_empty()).then_some(desc),
+    })
+}
+
+/// Parse another identification of a financial instrument, of its type and identification,
+/// e.g. `/US/037833100`.
+fn other_identification(line: &str) -> Option<OtherIdentification> {
+    let (tp, id) = line.strip_prefix('/')?.split_once('/')?;
+
+    Some(OtherIdentification {
+        id: id.to_string(),
+        tp: IdentificationSource {
+            prtry: tp.to_string(),
+        },
+    })
+}
+
+/// Parse the cash account of field `97A::CASH`, or the IBAN of field `97E::CASH`, into the
+/// content of a `CashAccountIdentification5Choice`.
+pub(crate) fn cash_account<T: DeserializeOwned>(field: GenericField) -> Result<T, Error> {
+    let account = match field.tag.as_str() {
+        "97E" => CashAccount {
+            iban: Some(field.data),
+            prtry: None,
+        },
+        _ => CashAccount {
+            iban: None,
+            prtry: Some(field.data),
+        },
+    };
+
+    convert(&account)
+}
+
+/// Remove the function of the message of field `23G`, failing for another function, e.g. a
+/// cancellation `CANC`.
+pub(crate) fn function(genl: &mut Sequence, function: &str) -> Result<(), Error> {
+    let field = genl.take_field("23G").ok_or(Error::MissingField("23G"))?;
+
+    if field.value != function {
+        return Err(invalid_field(&field.tag, &field.value));
+    }
+
+    Ok(())
+}
+
+/// Join a path of sequences, e.g. `SETDET/SETPRTY`, skipping the empty names.
+fn join_path(path: &str, name: &str) -> String {
+    [path, name]
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block4(lines: &[&str]) -> Block4 {
+        lines.join("\n").parse().unwrap()
+    }
+
+    #[test]
+    fn test_sequence() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::SEME//REF-1",
+            ":16R:LINK",
+            ":20C::PREV//REF-0",
+            ":16S:LINK",
+            ":16S:GENL",
+        ]);
+        let message = Sequence::parse(&block4).unwrap();
+
+        let genl = message.sequence("GENL").unwrap();
+        assert_eq!(genl.generic("20", "SEME").unwrap().data, "REF-1");
+        let link = genl.sequences_of("LINK").next().unwrap();
+        assert_eq!(
+            link.generic("20", "PREV"),
+            Some(GenericField::new("20C", "PREV", "REF-0"))
+        );
+        assert_eq!(message.block4(), block4);
+    }
+
+    #[test]
+    fn test_sequence_mismatch() {
+        assert!(matches!(
+            Sequence::parse(&block4(&[":16R:GENL", ":16S:LINK"])),
+            Err(Error::InvalidField { tag, .. }) if tag == "16S"
+        ));
+        assert!(matches!(
+            Sequence::parse(&block4(&[":16R:GENL"])),
+            Err(Error::MissingField("16S"))
+        ));
+    }
+
+    #[cfg(feature = "sese")]
+    #[test]
+    fn test_components() -> Result<(), Error> {
+        use iso_20022_sese::sese_023_001_11 as sese_023;
+
+        let fin_instrm_id: sese_023::SecurityIdentification19 = security(&Field::new(
+            "35B",
+            "ISIN US0378331005\n/US/037833100\nAPPLE INC",
+        ))?;
+        assert_eq!(fin_instrm_id.isin.unwrap().value, "US0378331005");
+        assert_eq!(fin_instrm_id.othr_id[0].id.value, "037833100");
+        assert_eq!(
+            fin_instrm_id.othr_id[0]
+                .tp
+                .value
+                .prtry
+                .as_ref()
+                .unwrap()
+                .value,
+            "US"
+        );
+        assert_eq!(fin_instrm_id.desc.unwrap().value, "APPLE INC");
+
+        let dt: sese_023::DateAndDateTime2ChoiceEnum =
+            date_time(&GenericField::new("98C", "SETT", "20230403101500"))?;
+        assert_eq!(
+            dt.dt_tm.unwrap().value.to_rfc3339(),
+            "2023-04-03T10:15:00+00:00"
+        );
+        let dt: sese_023::DateAndDateTime2ChoiceEnum =
+            date_time(&GenericField::new("98A", "SETT", "20230403"))?;
+        assert_eq!(dt.dt.unwrap().value.to_string(), "2023-04-03");
+        assert!(
+            date_time::<sese_023::DateAndDateTime2ChoiceEnum>(&GenericField::new(
+                "98A",
+                "SETT",
+                "2023-04-03"
+            ))
+            .is_err()
+        );
+
+        let acct: sese_023::CashAccountIdentification5ChoiceEnum =
+            cash_account(GenericField::new("97E", "CASH", "GB33BUKB20201555555555"))?;
+        assert_eq!(acct.iban.unwrap().value, "GB33BUKB20201555555555");
+
+        Ok(())
+    }
+}
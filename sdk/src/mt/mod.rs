@@ -0,0 +1,1331 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # SWIFT MT Translation
+//!
+//! During the coexistence of FIN MT and ISO 20022 messages, the MT modules translate the text
+//! block 4 of FIN messages into their ISO 20022 counterparts, and back, following the CBPR+
+//! translation rules.
+//!
+//! - `customer_transfer`: `MT103` to and from `pacs.008.001.10`
+//! - `institution_transfer`: `MT202` and `MT202 COV` to and from `pacs.009.001.10`, where the
+//!   underlying customer credit transfer of the `MT202 COV` is its sequence B
+//! - `statement`: `MT940` and `MT950` to and from `camt.053.001.10`, and `MT942` to
+//!   `camt.052.001.10`
+//! - `investigation`: `MTn92` to and from `camt.056.001.10`, `MTn96` to and from
+//!   `camt.029.001.11`, and `MTn95` to `camt.026.001.09` or `camt.087.001.08`
+//! - `securities`: the ISO 15022 `MT540` to `MT543` to and from `sese.023.001.11`, `MT544` to
+//!   `MT547` to and from `sese.025.001.11`, and `MT548` to and from `sese.024.001.12`
+//! - `corporate_action`: the ISO 15022 `MT564` to `seev.031.001.12`, and `MT566` to
+//!   `seev.036.001.13`
+//! - `iso15022`: the sequences and generic fields of the ISO 15022 messages
+//!
+//! The sender, receiver and UETR of a FIN message are in its header blocks, and are passed
+//! along with block 4 as a `FinHeader`. The translation to MT reports the `TranslationIssue`s,
+//! i.e. the information truncated to fit the fields, e.g. names longer than 35 characters,
+//! and the information without place in the MT message, e.g. structured remittance information.
+//!
+//! ```rust
+//! use iso_20022_sdk::mt::{customer_transfer, Block4, FinHeader};
+//!
+//! let header = FinHeader {
+//!     sender: "AAAADEFFXXX".to_string(),
+//!     receiver: "BBBBCHZZXXX".to_string(),
+//!     uetr: Some("e2a6b5cd-0b1f-4a5f-9a58-2a4b7c3e9f01".to_string()),
+//! };
+//!
+//! let block4: Block4 = mt103.parse()?;
+//! let pacs_008 = customer_transfer::mt103_to_pacs_008::<Dmkr, Dmkr>(&header, &block4)?;
+//!
+//! for translation in customer_transfer::pacs_008_to_mt103(&pacs_008)? {
+//!     println!("{}\n{:?}", translation.block4, translation.issues);
+//! }
+//! ```
+#[cfg(feature = "seev")]
+pub mod corporate_action;
+#[cfg(feature = "pacs")]
+pub mod customer_transfer;
+#[cfg(feature = "pacs")]
+pub mod institution_transfer;
+#[cfg(feature = "camt")]
+pub mod investigation;
+#[cfg(any(feature = "sese", feature = "seev"))]
+pub mod iso15022;
+#[cfg(feature = "sese")]
+pub mod securities;
+#[cfg(feature = "camt")]
+pub mod statement;
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "pacs")]
+use iso_20022_pacs::pacs_008_001_10 as pacs;
+
+/// Maximum length of a line of a name and address field, e.g. `:50K:` or `:59:`.
+#[cfg(feature = "pacs")]
+const LINE_LENGTH: usize = 35;
+
+/// Maximum number of name and address lines of a party field.
+#[cfg(feature = "pacs")]
+const PARTY_LINES: usize = 4;
+
+/// Maximum length of a line of the numbered lines of option F, after the line number.
+#[cfg(feature = "pacs")]
+const OPTION_F_LINE_LENGTH: usize = 33;
+
+lazy_static! {
+    static ref FIELD: Regex = Regex::new(r"^:(\d{2}[A-Z]?):(.*)$").unwrap();
+    static ref IBAN: Regex = Regex::new(r"^[A-Z]{2}[0-9]{2}[A-Z0-9]{1,30}$").unwrap();
+    static ref CURRENCY_AMOUNT: Regex = Regex::new(r"^([A-Z]{3})(\d{1,14},\d{0,})$").unwrap();
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// Mandatory field of the MT message is missing
+    #[error("missing mandatory field :{0}:")]
+    MissingField(&'static str),
+    /// Field of the MT message does not match its format
+    #[error("invalid field :{tag}: {value}")]
+    InvalidField { tag: String, value: String },
+    /// Mandatory element of the ISO 20022 message, without default, is missing
+    #[error("missing element {0}")]
+    MissingElement(&'static str),
+    /// Message type not translated by the function, e.g. an `MT548` into a `sese.023.001.11`
+    #[error("unsupported message type MT{0}")]
+    UnsupportedMessageType(MessageType),
+}
+
+/// Information that was not carried over by the translation, i.e. of the ISO 20022 message to
+/// the MT message, or of the qualified fields of an ISO 15022 message to the ISO 20022 message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranslationIssue {
+    /// Element truncated to fit the field, with its original value,
+    /// e.g. `CdtTrfTxInf/Dbtr/Nm` longer than 35 characters
+    Truncated {
+        field: String,
+        element: String,
+        value: String,
+    },
+    /// Element without place in the MT message, e.g. `CdtTrfTxInf/RmtInf/Strd`
+    Lost { element: String },
+    /// Field of the ISO 15022 message without counterpart in the ISO 20022 message, with its
+    /// sequence, e.g. `SETDET/:22F::RTGS//YRTG`
+    Unmapped { field: String },
+}
+
+/// FIN message types of the translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageType {
+    /// Single customer credit transfer
+    Mt103,
+    /// General financial institution transfer
+    Mt202,
+    /// General financial institution transfer, cover of a customer credit transfer
+    Mt202Cov,
+    /// Request for cancellation of a customer transfer
+    Mt192,
+    /// Queries on a customer transfer
+    Mt195,
+    /// Answers on a customer transfer
+    Mt196,
+    /// Request for cancellation of an institution transfer
+    Mt292,
+    /// Queries on an institution transfer
+    Mt295,
+    /// Answers on an institution transfer
+    Mt296,
+    /// Receive free
+    Mt540,
+    /// Receive against payment
+    Mt541,
+    /// Deliver free
+    Mt542,
+    /// Deliver against payment
+    Mt543,
+    /// Receive free confirmation
+    Mt544,
+    /// Receive against payment confirmation
+    Mt545,
+    /// Deliver free confirmation
+    Mt546,
+    /// Deliver against payment confirmation
+    Mt547,
+    /// Settlement status and processing advice
+    Mt548,
+    /// Corporate action notification
+    Mt564,
+    /// Corporate action confirmation
+    Mt566,
+    /// Customer statement message
+    Mt940,
+    /// Interim transaction report
+    Mt942,
+    /// Cash flow statement message
+    Mt950,
+}
+
+impl fmt::Display for MessageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageType::Mt103 => write!(f, "103"),
+            MessageType::Mt202 => write!(f, "202"),
+            MessageType::Mt202Cov => write!(f, "202COV"),
+            MessageType::Mt192 => write!(f, "192"),
+            MessageType::Mt195 => write!(f, "195"),
+            MessageType::Mt196 => write!(f, "196"),
+            MessageType::Mt292 => write!(f, "292"),
+            MessageType::Mt295 => write!(f, "295"),
+            MessageType::Mt296 => write!(f, "296"),
+            MessageType::Mt540 => write!(f, "540"),
+            MessageType::Mt541 => write!(f, "541"),
+            MessageType::Mt542 => write!(f, "542"),
+            MessageType::Mt543 => write!(f, "543"),
+            MessageType::Mt544 => write!(f, "544"),
+            MessageType::Mt545 => write!(f, "545"),
+            MessageType::Mt546 => write!(f, "546"),
+            MessageType::Mt547 => write!(f, "547"),
+            MessageType::Mt548 => write!(f, "548"),
+            MessageType::Mt564 => write!(f, "564"),
+            MessageType::Mt566 => write!(f, "566"),
+            MessageType::Mt940 => write!(f, "940"),
+            MessageType::Mt942 => write!(f, "942"),
+            MessageType::Mt950 => write!(f, "950"),
+        }
+    }
+}
+
+/// Sender, receiver and UETR of a FIN message, i.e. of its basic header block 1, application
+/// header block 2 and user header block 3 field `121`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FinHeader {
+    /// BIC of the sender, i.e. the instructing agent
+    pub sender: String,
+    /// BIC of the receiver, i.e. the instructed agent
+    pub receiver: String,
+    /// Unique end-to-end transaction reference
+    pub uetr: Option<String>,
+}
+
+/// Field of the text block 4, e.g. `:32A:230401EUR1000,`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// Tag of the field, with its option letter, e.g. `50K`
+    pub tag: String,
+    /// Value of the field, with its lines separated by `\n`
+    pub value: String,
+}
+
+impl Field {
+    pub fn new(tag: &str, value: impl Into<String>) -> Self {
+        Self {
+            tag: tag.to_string(),
+            value: value.into(),
+        }
+    }
+
+    /// Return the option letter of the field, e.g. `K` of `50K`.
+    pub fn option(&self) -> Option<char> {
+        self.tag.chars().nth(2)
+    }
+
+    /// Return the lines of the value.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.value.lines()
+    }
+}
+
+/// Text block 4 of a FIN message.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Block4 {
+    pub fields: Vec<Field>,
+}
+
+impl Block4 {
+    /// Return the first field with the tag, e.g. `32A`.
+    pub fn field(&self, tag: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.tag == tag)
+    }
+
+    /// Return the fields with the tag, e.g. the repeated `71F`.
+    pub fn fields_of<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Field> {
+        self.fields.iter().filter(move |field| field.tag == tag)
+    }
+
+    /// Return the first field with the number, in any option, e.g. `50K` for `50`.
+    pub fn option(&self, number: &str) -> Option<&Field> {
+        self.fields
+            .iter()
+            .find(|field| field.tag.get(..2) == Some(number))
+    }
+
+    pub fn push(&mut self, tag: &str, value: impl Into<String>) {
+        self.fields.push(Field::new(tag, value));
+    }
+
+    /// Split the block at the first field with the number, e.g. sequence A and B of an
+    /// `MT202 COV` at field `50a`.
+    pub fn split_at_option(&self, number: &str) -> (Block4, Block4) {
+        let index = self
+            .fields
+            .iter()
+            .position(|field| field.tag.get(..2) == Some(number))
+            .unwrap_or(self.fields.len());
+
+        (
+            Block4 {
+                fields: self.fields[..index].to_vec(),
+            },
+            Block4 {
+                fields: self.fields[index..].to_vec(),
+            },
+        )
+    }
+
+    /// Return the mandatory field with the tag.
+    fn mandatory(&self, tag: &'static str) -> Result<&Field, Error> {
+        self.field(tag).ok_or(Error::MissingField(tag))
+    }
+}
+
+impl FromStr for Block4 {
+    type Err = Error;
+
+    /// Parse the text block 4, with or without its `{4:` and `-}` delimiters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let text = text.strip_prefix("{4:").unwrap_or(text);
+        let text = text.strip_suffix("-}").unwrap_or(text);
+
+        let mut block4 = Block4::default();
+
+        for line in text.lines().map(|line| line.trim_end_matches('\r')) {
+            if let Some(captures) = FIELD.captures(line) {
+                block4.push(&captures[1], &captures[2]);
+            } else if let Some(field) = block4.fields.last_mut() {
+                field.value.push('\n');
+                field.value.push_str(line);
+            } else if !line.trim().is_empty() {
+                return Err(Error::InvalidField {
+                    tag: String::new(),
+                    value: line.to_string(),
+                });
+            }
+        }
+
+        Ok(block4)
+    }
+}
+
+impl fmt::Display for Block4 {
+    /// Render the text block 4, with its `{4:` and `-}` delimiters and CRLF line endings.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{4:\r\n")?;
+        for field in self.fields.iter() {
+            write!(
+                f,
+                ":{}:{}\r\n",
+                field.tag,
+                field.value.replace('\n', "\r\n")
+            )?;
+        }
+        write!(f, "-}}")
+    }
+}
+
+/// MT message translated from an ISO 20022 message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtTranslation {
+    pub message_type: MessageType,
+    pub header: FinHeader,
+    pub block4: Block4,
+    /// Information truncated or lost in the translation
+    pub issues: Vec<TranslationIssue>,
+}
+
+/// Party or agent of an option field, e.g. `:50K:`, `:59F:` or `:57A:`.
+#[cfg(feature = "pacs")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct PartyField {
+    /// Account, i.e. the line starting with `/`
+    account: Option<String>,
+    /// Clearing system member identification, i.e. the line starting with `//`
+    clr_sys_mmb_id: Option<String>,
+    /// Party identifier of option F, e.g. `CUST/DE/ABC/1234`
+    party_id: Option<String>,
+    /// BIC of option A
+    bic: Option<String>,
+    name: Option<String>,
+    /// Address lines, i.e. the unstructured address or the street of option F
+    adr_line: Vec<String>,
+    pst_cd: Option<String>,
+    twn_nm: Option<String>,
+    ctry: Option<String>,
+}
+
+#[cfg(feature = "pacs")]
+impl PartyField {
+    /// Parse a party or agent field, in option A, D, F, K or without option.
+    pub(crate) fn parse(field: &Field) -> Self {
+        let mut party = PartyField::default();
+        let mut lines = field.lines().peekable();
+
+        if let Some(line) = lines.peek().copied() {
+            if let Some(clr_sys_mmb_id) = line.strip_prefix("//") {
+                party.clr_sys_mmb_id = Some(clr_sys_mmb_id.to_string());
+                lines.next();
+            } else if let Some(account) = line.strip_prefix('/') {
+                // Debit / credit marks of the account, e.g. `/C/12345`
+                let account = account
+                    .strip_prefix("C/")
+                    .or_else(|| account.strip_prefix("D/"))
+                    .unwrap_or(account);
+                party.account = Some(account.to_string());
+                lines.next();
+            } else if field.option() == Some('F') && line.get(1..2) != Some("/") {
+                party.party_id = Some(line.to_string());
+                lines.next();
+            }
+        }
+
+        match field.option() {
+            Some('A') => party.bic = lines.next().map(str::to_string),
+            Some('F') => {
+                for line in lines {
+                    match line.split_once('/') {
+                        Some(("1", name)) => {
+                            party.name = Some(match party.name.take() {
+                                Some(previous) => format!("{} {}", previous, name),
+                                None => name.to_string(),
+                            })
+                        }
+                        Some(("2", adr_line)) => party.adr_line.push(adr_line.to_string()),
+                        Some(("3", town)) => {
+                            let (ctry, town) = town.split_once('/').unwrap_or((town, ""));
+                            party.ctry = Some(ctry.to_string());
+
+                            let town = town.trim();
+                            match town.split_once(' ') {
+                                Some((pst_cd, twn_nm)) if pst_cd.contains(char::is_numeric) => {
+                                    party.pst_cd = Some(pst_cd.to_string());
+                                    party.twn_nm = Some(twn_nm.trim().to_string());
+                                }
+                                _ if !town.is_empty() => party.twn_nm = Some(town.to_string()),
+                                _ => {}
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {
+                party.name = lines.next().map(str::to_string);
+                party.adr_line = lines.map(str::to_string).collect();
+            }
+        }
+
+        party
+    }
+
+    /// Return the postal address, if any.
+    fn postal_address(&self) -> Option<pacs::PostalAddress24> {
+        if self.adr_line.is_empty() && self.twn_nm.is_none() && self.ctry.is_none() {
+            return None;
+        }
+
+        Some(pacs::PostalAddress24 {
+            pst_cd: self.pst_cd.clone().map(|value| pacs::Max16Text { value }),
+            twn_nm: self.twn_nm.clone().map(|value| pacs::Max35Text { value }),
+            ctry: self.ctry.clone().map(|value| pacs::CountryCode { value }),
+            adr_line: self
+                .adr_line
+                .iter()
+                .map(|line| pacs::Max70Text {
+                    value: line.clone(),
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Return the party, e.g. the debtor of field `50a`.
+    pub(crate) fn party(&self) -> pacs::PartyIdentification135 {
+        let org_id = match (&self.bic, &self.party_id) {
+            (None, None) => None,
+            (bic, party_id) => Some(pacs::OrganisationIdentification29 {
+                any_bic: bic
+                    .clone()
+                    .map(|value| pacs::AnyBicDec2014Identifier { value }),
+                othr: party_id
+                    .iter()
+                    .map(|party_id| pacs::GenericOrganisationIdentification1 {
+                        id: pacs::Max35Text {
+                            value: party_id.clone(),
+                        },
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+        };
+
+        pacs::PartyIdentification135 {
+            nm: self.name.clone().map(|value| pacs::Max140Text { value }),
+            pstl_adr: self.postal_address(),
+            id: org_id.map(|org_id| pacs::Party38Choice {
+                value: pacs::Party38ChoiceEnum {
+                    org_id: Some(org_id),
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Return the agent, e.g. the creditor agent of field `57a`.
+    pub(crate) fn agent(&self) -> pacs::BranchAndFinancialInstitutionIdentification6 {
+        pacs::BranchAndFinancialInstitutionIdentification6 {
+            fin_instn_id: pacs::FinancialInstitutionIdentification18 {
+                bicfi: self
+                    .bic
+                    .clone()
+                    .map(|value| pacs::BicfiDec2014Identifier { value }),
+                clr_sys_mmb_id: self.clr_sys_mmb_id.clone().map(|value| {
+                    pacs::ClearingSystemMemberIdentification2 {
+                        mmb_id: pacs::Max35Text { value },
+                        ..Default::default()
+                    }
+                }),
+                nm: self.name.clone().map(|value| pacs::Max140Text { value }),
+                pstl_adr: self.postal_address(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Return the account of the party or agent, if any.
+    pub(crate) fn account(&self) -> Option<pacs::CashAccount40> {
+        self.account.as_deref().map(account)
+    }
+
+    /// Return the field of a party, e.g. the debtor of field `50a`.
+    pub(crate) fn from_party(
+        party: &pacs::PartyIdentification135,
+        acct: Option<&pacs::CashAccount40>,
+    ) -> Self {
+        let org_id = party.id.as_ref().and_then(|id| id.value.org_id.as_ref());
+
+        PartyField {
+            account: acct.and_then(account_id),
+            bic: org_id
+                .and_then(|org_id| org_id.any_bic.as_ref())
+                .map(|bic| bic.value.clone()),
+            party_id: org_id
+                .and_then(|org_id| org_id.othr.first())
+                .map(|othr| othr.id.value.clone()),
+            name: party.nm.as_ref().map(|nm| nm.value.clone()),
+            ..PartyField::default()
+        }
+        .with_address(party.pstl_adr.as_ref())
+    }
+
+    /// Return the field of an agent, e.g. the creditor agent of field `57a`.
+    pub(crate) fn from_agent(
+        agent: &pacs::BranchAndFinancialInstitutionIdentification6,
+        acct: Option<&pacs::CashAccount40>,
+    ) -> Self {
+        let fin_instn_id = &agent.fin_instn_id;
+
+        PartyField {
+            account: acct.and_then(account_id),
+            clr_sys_mmb_id: fin_instn_id
+                .clr_sys_mmb_id
+                .as_ref()
+                .map(|clr_sys_mmb_id| clr_sys_mmb_id.mmb_id.value.clone()),
+            bic: fin_instn_id.bicfi.as_ref().map(|bicfi| bicfi.value.clone()),
+            name: fin_instn_id.nm.as_ref().map(|nm| nm.value.clone()),
+            ..PartyField::default()
+        }
+        .with_address(fin_instn_id.pstl_adr.as_ref())
+    }
+
+    fn with_address(mut self, pstl_adr: Option<&pacs::PostalAddress24>) -> Self {
+        if let Some(pstl_adr) = pstl_adr {
+            let street = [
+                pstl_adr
+                    .strt_nm
+                    .as_ref()
+                    .map(|strt_nm| strt_nm.value.as_str()),
+                pstl_adr
+                    .bldg_nb
+                    .as_ref()
+                    .map(|bldg_nb| bldg_nb.value.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+            if !street.is_empty() {
+                self.adr_line.push(street);
+            }
+            self.adr_line
+                .extend(pstl_adr.adr_line.iter().map(|line| line.value.clone()));
+            self.pst_cd = pstl_adr.pst_cd.as_ref().map(|pst_cd| pst_cd.value.clone());
+            self.twn_nm = pstl_adr.twn_nm.as_ref().map(|twn_nm| twn_nm.value.clone());
+            self.ctry = pstl_adr.ctry.as_ref().map(|ctry| ctry.value.clone());
+        }
+
+        self
+    }
+
+    /// Render the field of a party, in option A for an identifier code without name,
+    /// option F for a structured address, or else option K for field `50`, and without
+    /// option for field `59`.
+    pub(crate) fn render_party(
+        &self,
+        number: &str,
+        element: &str,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> Field {
+        if self.bic.is_some() && self.name.is_none() {
+            return self.render_option_a(number, element, issues);
+        }
+
+        // Option F of field `50` requires an account or a party identifier
+        if self.twn_nm.is_some()
+            && self.ctry.is_some()
+            && (number != "50" || self.account.is_some() || self.party_id.is_some())
+        {
+            return self.render_option_f(number, element, issues);
+        }
+
+        let option = if number == "50" { "K" } else { "" };
+
+        self.render_name_and_address(&format!("{}{}", number, option), element, issues)
+    }
+
+    /// Render the field of an agent, in option A for an identifier code, or else option D.
+    pub(crate) fn render_agent(
+        &self,
+        number: &str,
+        element: &str,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> Field {
+        if self.bic.is_some() {
+            return self.render_option_a(number, element, issues);
+        }
+
+        self.render_name_and_address(&format!("{}D", number), element, issues)
+    }
+
+    fn render_account(
+        &self,
+        field: &str,
+        element: &str,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> Option<String> {
+        if let Some(clr_sys_mmb_id) = &self.clr_sys_mmb_id {
+            if self.account.is_some() {
+                issues.push(lost(&format!("{}Acct", element)));
+            }
+
+            return Some(format!(
+                "//{}",
+                truncate(
+                    clr_sys_mmb_id,
+                    33,
+                    field,
+                    &format!("{}/FinInstnId/ClrSysMmbId", element),
+                    issues
+                )
+            ));
+        }
+
+        self.account.as_ref().map(|account| {
+            format!(
+                "/{}",
+                truncate(account, 34, field, &format!("{}Acct", element), issues)
+            )
+        })
+    }
+
+    fn render_option_a(
+        &self,
+        number: &str,
+        element: &str,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> Field {
+        let tag = format!("{}A", number);
+        let mut lines = vec![];
+
+        lines.extend(self.render_account(&tag, element, issues));
+        lines.extend(self.bic.clone());
+
+        if self.name.is_some() {
+            issues.push(lost(&format!("{}/Nm", element)));
+        }
+        if !self.adr_line.is_empty() || self.twn_nm.is_some() || self.ctry.is_some() {
+            issues.push(lost(&format!("{}/PstlAdr", element)));
+        }
+
+        Field::new(&tag, lines.join("\n"))
+    }
+
+    fn render_option_f(
+        &self,
+        number: &str,
+        element: &str,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> Field {
+        let tag = format!("{}F", number);
+        let mut lines = vec![];
+
+        match self.render_account(&tag, element, issues) {
+            Some(account) => lines.push(account),
+            None => lines.extend(self.party_id.as_ref().map(|party_id| {
+                truncate(
+                    party_id,
+                    LINE_LENGTH,
+                    &tag,
+                    &format!("{}/Id", element),
+                    issues,
+                )
+            })),
+        }
+
+        // The name continues on further `1/` lines, up to the line of the country and town
+        let mut name_lines = self
+            .name
+            .as_deref()
+            .map(|name| wrap_words(name, OPTION_F_LINE_LENGTH))
+            .unwrap_or_default();
+        if name_lines.len() > PARTY_LINES - 1 {
+            issues.push(truncated(
+                &tag,
+                &format!("{}/Nm", element),
+                self.name.as_deref().unwrap_or_default(),
+            ));
+            name_lines.truncate(PARTY_LINES - 1);
+        }
+
+        // The numbered lines are the name, the address lines and the country and town
+        let adr_lines = PARTY_LINES - name_lines.len() - 1;
+        lines.extend(name_lines.iter().map(|name| format!("1/{}", name)));
+        let element_adr_line = format!("{}/PstlAdr/AdrLine", element);
+
+        for adr_line in self.adr_line.iter().take(adr_lines) {
+            lines.push(format!(
+                "2/{}",
+                truncate(
+                    adr_line,
+                    OPTION_F_LINE_LENGTH,
+                    &tag,
+                    &element_adr_line,
+                    issues
+                )
+            ));
+        }
+        for adr_line in self.adr_line.iter().skip(adr_lines) {
+            issues.push(truncated(&tag, &element_adr_line, adr_line));
+        }
+
+        let town = [&self.pst_cd, &self.twn_nm]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let town = format!("{}/{}", self.ctry.as_deref().unwrap_or_default(), town);
+
+        lines.push(format!(
+            "3/{}",
+            truncate(
+                &town,
+                OPTION_F_LINE_LENGTH,
+                &tag,
+                &format!("{}/PstlAdr/TwnNm", element),
+                issues
+            )
+        ));
+
+        Field::new(&tag, lines.join("\n"))
+    }
+
+    fn render_name_and_address(
+        &self,
+        tag: &str,
+        element: &str,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> Field {
+        let mut lines = vec![];
+
+        lines.extend(self.render_account(tag, element, issues));
+        let account_lines = lines.len();
+
+        if self.bic.is_some() {
+            issues.push(lost(&format!("{}/Id/OrgId/AnyBIC", element)));
+        }
+        if self.party_id.is_some() {
+            issues.push(lost(&format!("{}/Id/OrgId/Othr", element)));
+        }
+
+        if let Some(name) = &self.name {
+            lines.push(truncate(
+                name,
+                LINE_LENGTH,
+                tag,
+                &format!("{}/Nm", element),
+                issues,
+            ));
+        }
+
+        let town = [&self.pst_cd, &self.twn_nm, &self.ctry]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let adr_lines = self
+            .adr_line
+            .iter()
+            .cloned()
+            .chain(Some(town).filter(|town| !town.is_empty()));
+
+        let element_adr_line = format!("{}/PstlAdr", element);
+
+        for adr_line in adr_lines {
+            if lines.len() - account_lines < PARTY_LINES {
+                lines.push(truncate(
+                    &adr_line,
+                    LINE_LENGTH,
+                    tag,
+                    &element_adr_line,
+                    issues,
+                ));
+            } else {
+                issues.push(truncated(tag, &element_adr_line, &adr_line));
+            }
+        }
+
+        Field::new(tag, lines.join("\n"))
+    }
+}
+
+/// Convert a component into the structurally identical component of another message.
+pub(crate) fn convert<S, T>(source: &S) -> Result<T, Error>
+where
+    S: Serialize,
+    T: DeserializeOwned,
+{
+    Ok(quick_xml::de::from_str(&quick_xml::se::to_string(source)?)?)
+}
+
+/// Convert an optional component, see `convert`.
+#[cfg(feature = "pacs")]
+pub(crate) fn convert_option<S, T>(source: &Option<S>) -> Result<Option<T>, Error>
+where
+    S: Serialize,
+    T: DeserializeOwned,
+{
+    source.as_ref().map(convert).transpose()
+}
+
+/// Convert repeated components, see `convert`.
+#[cfg(feature = "pacs")]
+pub(crate) fn convert_vec<S, T>(source: &[S]) -> Result<Vec<T>, Error>
+where
+    S: Serialize,
+    T: DeserializeOwned,
+{
+    source.iter().map(convert).collect()
+}
+
+/// Return the account, as an IBAN or a proprietary identification.
+#[cfg(feature = "pacs")]
+pub(crate) fn account(id: &str) -> pacs::CashAccount40 {
+    let id = if IBAN.is_match(id) {
+        pacs::AccountIdentification4ChoiceEnum {
+            iban: Some(pacs::Iban2007Identifier {
+                value: id.to_string(),
+            }),
+            ..Default::default()
+        }
+    } else {
+        pacs::AccountIdentification4ChoiceEnum {
+            othr: Some(pacs::GenericAccountIdentification1 {
+                id: pacs::Max34Text {
+                    value: id.to_string(),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    };
+
+    pacs::CashAccount40 {
+        id: Some(pacs::AccountIdentification4Choice { value: id }),
+        ..Default::default()
+    }
+}
+
+/// Return the identification of an account, i.e. the IBAN or the proprietary identification.
+#[cfg(feature = "pacs")]
+pub(crate) fn account_id(account: &pacs::CashAccount40) -> Option<String> {
+    let id = &account.id.as_ref()?.value;
+
+    id.iban
+        .as_ref()
+        .map(|iban| iban.value.clone())
+        .or_else(|| id.othr.as_ref().map(|othr| othr.id.value.clone()))
+}
+
+/// Return the BIC of an agent, if identified by its BIC.
+#[cfg(feature = "pacs")]
+pub(crate) fn agent_bic(
+    agent: &pacs::BranchAndFinancialInstitutionIdentification6,
+) -> Option<&str> {
+    agent
+        .fin_instn_id
+        .bicfi
+        .as_ref()
+        .map(|bicfi| bicfi.value.as_str())
+}
+
+/// Return the agent identified by its BIC.
+#[cfg(feature = "pacs")]
+pub(crate) fn bic_agent(bic: &str) -> pacs::BranchAndFinancialInstitutionIdentification6 {
+    pacs::BranchAndFinancialInstitutionIdentification6 {
+        fin_instn_id: pacs::FinancialInstitutionIdentification18 {
+            bicfi: Some(pacs::BicfiDec2014Identifier {
+                value: bic.to_string(),
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Parse an MT amount, e.g. `1234,56`.
+pub(crate) fn parse_amount(tag: &str, value: &str) -> Result<f64, Error> {
+    value
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| invalid_field(tag, value))
+}
+
+/// Format an MT amount, with the decimals of the minor unit of the currency, a decimal comma
+/// and without trailing zeros, e.g. `1234,5` in `EUR` or `1234,567` in `KWD`.
+pub(crate) fn format_amount(value: f64, ccy: &str) -> String {
+    format_decimal_comma(&format!("{:.*}", minor_units(ccy), value))
+}
+
+/// Format an MT decimal number, e.g. an exchange rate or a quantity, with all its decimals,
+/// e.g. `0,9123`.
+#[cfg(any(feature = "pacs", feature = "sese"))]
+pub(crate) fn format_decimal(value: f64) -> String {
+    format_decimal_comma(&value.to_string())
+}
+
+/// Replace the decimal point of a formatted number with a comma, without trailing zeros,
+/// e.g. `1000,` of `1000.00`.
+fn format_decimal_comma(value: &str) -> String {
+    match value.split_once('.') {
+        Some((integer, fraction)) => format!("{},{}", integer, fraction.trim_end_matches('0')),
+        None => format!("{},", value),
+    }
+}
+
+/// Return the number of decimals of the minor unit of an ISO 4217 currency, e.g. `0` for
+/// `JPY` or `3` for `KWD`.
+fn minor_units(ccy: &str) -> usize {
+    match ccy {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        "CLF" | "UYW" => 4,
+        _ => 2,
+    }
+}
+
+/// Parse the currency and amount of a field, e.g. `:33B:EUR1234,56`.
+#[cfg(feature = "pacs")]
+pub(crate) fn parse_currency_amount(
+    field: &Field,
+) -> Result<pacs::ActiveOrHistoricCurrencyAndAmount, Error> {
+    let captures = CURRENCY_AMOUNT
+        .captures(&field.value)
+        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+
+    Ok(pacs::ActiveOrHistoricCurrencyAndAmount {
+        value: pacs::ActiveOrHistoricCurrencyAndAmountSimpleType {
+            value: parse_amount(&field.tag, &captures[2])?,
+        },
+        ccy: pacs::ActiveOrHistoricCurrencyCode {
+            value: captures[1].to_string(),
+        },
+    })
+}
+
+/// Parse the date, currency and amount of a field, e.g. `:32A:230401EUR1234,56`.
+pub(crate) fn parse_date_currency_amount(field: &Field) -> Result<(NaiveDate, String, f64), Error> {
+    let (dt, currency_amount) = match (field.value.get(..6), field.value.get(6..)) {
+        (Some(dt), Some(currency_amount)) => (dt, currency_amount),
+        _ => return Err(invalid_field(&field.tag, &field.value)),
+    };
+
+    let dt = NaiveDate::parse_from_str(dt, "%y%m%d")
+        .map_err(|_| invalid_field(&field.tag, &field.value))?;
+    let captures = CURRENCY_AMOUNT
+        .captures(currency_amount)
+        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+
+    Ok((
+        dt,
+        captures[1].to_string(),
+        parse_amount(&field.tag, &captures[2])?,
+    ))
+}
+
+/// Parse the value date, currency and interbank settled amount of field `32A`.
+#[cfg(feature = "pacs")]
+pub(crate) fn parse_32a(
+    field: &Field,
+) -> Result<(NaiveDate, pacs::ActiveCurrencyAndAmount), Error> {
+    let (dt, ccy, value) = parse_date_currency_amount(field)?;
+
+    Ok((
+        dt,
+        pacs::ActiveCurrencyAndAmount {
+            value: pacs::ActiveCurrencyAndAmountSimpleType { value },
+            ccy: pacs::ActiveCurrencyCode { value: ccy },
+        },
+    ))
+}
+
+/// Render field `32A`.
+#[cfg(feature = "pacs")]
+pub(crate) fn render_32a(dt: &NaiveDate, amt: &pacs::ActiveCurrencyAndAmount) -> Field {
+    Field::new(
+        "32A",
+        format!(
+            "{}{}{}",
+            dt.format("%y%m%d"),
+            amt.ccy.value,
+            format_amount(amt.value.value, &amt.ccy.value)
+        ),
+    )
+}
+
+/// Instructions of the sender to receiver information of field `72`.
+#[cfg(feature = "pacs")]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Instructions {
+    /// Previous instructing agent, i.e. code `/INS/`
+    pub(crate) prvs_instg_agt_1: Option<pacs::BranchAndFinancialInstitutionIdentification6>,
+    /// Instructions for the creditor agent, i.e. code `/ACC/`
+    pub(crate) instr_for_cdtr_agt: Vec<pacs::InstructionForCreditorAgent3>,
+    /// Instructions for the next agent, i.e. the other codes, e.g. `/INT/` or `/REC/`
+    pub(crate) instr_for_nxt_agt: Vec<pacs::InstructionForNextAgent1>,
+}
+
+#[cfg(feature = "pacs")]
+impl Instructions {
+    pub(crate) fn parse(field: Option<&Field>) -> Self {
+        let mut instructions = Instructions::default();
+
+        for (code, narrative) in field.map(parse_72).unwrap_or_default() {
+            match code.as_str() {
+                "INS" if instructions.prvs_instg_agt_1.is_none() => {
+                    instructions.prvs_instg_agt_1 = Some(bic_agent(&narrative))
+                }
+                "ACC" => instructions
+                    .instr_for_cdtr_agt
+                    .push(pacs::InstructionForCreditorAgent3 {
+                        cd: None,
+                        instr_inf: Some(pacs::Max140Text { value: narrative }),
+                    }),
+                "" => instructions
+                    .instr_for_nxt_agt
+                    .push(pacs::InstructionForNextAgent1 {
+                        cd: None,
+                        instr_inf: Some(pacs::Max140Text { value: narrative }),
+                    }),
+                _ => instructions
+                    .instr_for_nxt_agt
+                    .push(pacs::InstructionForNextAgent1 {
+                        cd: None,
+                        instr_inf: Some(pacs::Max140Text {
+                            value: format!("/{}/{}", code, narrative),
+                        }),
+                    }),
+            }
+        }
+
+        instructions
+    }
+
+    /// Render field `72`, of the previous instructing agent and the instructions without code,
+    /// where the instructions with code are fields of their own, e.g. `23E`.
+    pub(crate) fn render(&self, issues: &mut Vec<TranslationIssue>) -> Option<Field> {
+        let mut codes = vec![];
+
+        if let Some(prvs_instg_agt_1) = &self.prvs_instg_agt_1 {
+            match agent_bic(prvs_instg_agt_1) {
+                Some(bic) => codes.push(("INS".to_string(), bic.to_string())),
+                None => issues.push(lost("CdtTrfTxInf/PrvsInstgAgt1")),
+            }
+        }
+
+        codes.extend(
+            self.instr_for_cdtr_agt
+                .iter()
+                .filter(|instr| instr.cd.is_none())
+                .filter_map(|instr| instr.instr_inf.as_ref())
+                .map(|instr_inf| ("ACC".to_string(), instr_inf.value.clone())),
+        );
+
+        for instr_inf in self
+            .instr_for_nxt_agt
+            .iter()
+            .filter(|instr| instr.cd.is_none())
+            .filter_map(|instr| instr.instr_inf.as_ref())
+        {
+            codes.extend(parse_72(&Field::new("72", instr_inf.value.as_str())));
+        }
+
+        render_72(&codes, issues)
+    }
+}
+
+/// Parse the codes of the sender to receiver information of field `72`, e.g. `/ACC/`, as
+/// pairs of code and narrative, where the continuation lines `//` are appended to the
+/// narrative of their code.
+#[cfg(feature = "pacs")]
+fn parse_72(field: &Field) -> Vec<(String, String)> {
+    let mut codes: Vec<(String, String)> = vec![];
+
+    for line in field.lines() {
+        if let Some(continuation) = line.strip_prefix("//") {
+            if let Some((_, narrative)) = codes.last_mut() {
+                narrative.push_str(continuation);
+                continue;
+            }
+        }
+
+        let code = line.strip_prefix('/').and_then(|line| line.split_once('/'));
+
+        match code {
+            Some((code, narrative)) => codes.push((code.to_string(), narrative.to_string())),
+            None => codes.push((String::new(), line.to_string())),
+        }
+    }
+
+    codes
+}
+
+/// Render field `72` of pairs of code and narrative, on up to six lines of 35 characters.
+#[cfg(feature = "pacs")]
+fn render_72(codes: &[(String, String)], issues: &mut Vec<TranslationIssue>) -> Option<Field> {
+    let mut lines: Vec<String> = vec![];
+
+    for (code, narrative) in codes {
+        let mut chunks = wrap(narrative, LINE_LENGTH - code.len() - 2, LINE_LENGTH - 2);
+        let first = chunks.next().unwrap_or_default();
+
+        if code.is_empty() {
+            lines.push(first);
+        } else {
+            lines.push(format!("/{}/{}", code, first));
+        }
+        lines.extend(chunks.map(|chunk| format!("//{}", chunk)));
+    }
+
+    if lines.len() > 6 {
+        for line in lines.drain(6..) {
+            issues.push(truncated("72", "InstrForNxtAgt", &line));
+        }
+    }
+
+    (!lines.is_empty()).then(|| Field::new("72", lines.join("\n")))
+}
+
+/// Split a text into chunks, of the first and the following lengths.
+pub(crate) fn wrap(text: &str, first: usize, length: usize) -> impl Iterator<Item = String> + '_ {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut index = 0;
+    let mut chunk = first;
+
+    std::iter::from_fn(move || {
+        if index >= chars.len() {
+            return None;
+        }
+
+        let end = (index + chunk).min(chars.len());
+        let value = chars[index..end].iter().collect::<String>();
+        index = end;
+        chunk = length;
+
+        Some(value)
+    })
+}
+
+/// Split a text into lines of a maximum length at spaces, and words longer than the length
+/// into chunks, e.g. a name continued on further lines.
+#[cfg(any(feature = "pacs", feature = "camt"))]
+pub(crate) fn wrap_words(text: &str, length: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= length => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.extend(wrap(word, length, length)),
+        }
+    }
+
+    lines
+}
+
+/// Truncate a value to the length of a field, reporting the original value.
+pub(crate) fn truncate(
+    value: &str,
+    length: usize,
+    field: &str,
+    element: &str,
+    issues: &mut Vec<TranslationIssue>,
+) -> String {
+    if value.chars().count() <= length {
+        return value.to_string();
+    }
+
+    issues.push(truncated(field, element, value));
+
+    value.chars().take(length).collect()
+}
+
+pub(crate) fn truncated(field: &str, element: &str, value: &str) -> TranslationIssue {
+    TranslationIssue::Truncated {
+        field: field.to_string(),
+        element: element.to_string(),
+        value: value.to_string(),
+    }
+}
+
+pub(crate) fn lost(element: &str) -> TranslationIssue {
+    TranslationIssue::Lost {
+        element: element.to_string(),
+    }
+}
+
+pub(crate) fn invalid_field(tag: &str, value: &str) -> Error {
+    Error::InvalidField {
+        tag: tag.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block4() {
+        let text = "{4:\r\n:20:REF123\r\n:32A:230401EUR1234,56\r\n:50K:/12345678\r\nACME GMBH\r\nHAUPTSTRASSE 1\r\n:71F:EUR10,\r\n:71F:EUR2,5\r\n-}";
+
+        let block4: Block4 = text.parse().unwrap();
+        assert_eq!(block4.fields.len(), 5);
+        assert_eq!(block4.option("50").unwrap().tag, "50K");
+        assert_eq!(
+            block4.option("50").unwrap().value,
+            "/12345678\nACME GMBH\nHAUPTSTRASSE 1"
+        );
+        assert_eq!(block4.fields_of("71F").count(), 2);
+        assert_eq!(block4.to_string(), text);
+
+        assert!(":20:REF123\n:32A:230401EUR1234,56"
+            .parse::<Block4>()
+            .is_ok());
+        assert!("REF123\n:20:REF123".parse::<Block4>().is_err());
+    }
+
+    #[cfg(feature = "pacs")]
+    #[test]
+    fn test_party_field() {
+        let party = PartyField::parse(&Field::new("50K", "/12345678\nACME GMBH\nHAUPTSTRASSE 1"));
+        assert_eq!(party.account.as_deref(), Some("12345678"));
+        assert_eq!(party.name.as_deref(), Some("ACME GMBH"));
+        assert_eq!(party.adr_line, vec!["HAUPTSTRASSE 1".to_string()]);
+
+        let agent = PartyField::parse(&Field::new("57A", "//CH123456\nBBBBCHZZXXX"));
+        assert_eq!(agent.clr_sys_mmb_id.as_deref(), Some("CH123456"));
+        assert_eq!(agent.bic.as_deref(), Some("BBBBCHZZXXX"));
+
+        let mut issues = vec![];
+        let field = PartyField {
+            name: Some("A NAME THAT IS LONGER THAN THIRTY-FIVE CHARACTERS".to_string()),
+            adr_line: vec![
+                "LINE 1".into(),
+                "LINE 2".into(),
+                "LINE 3".into(),
+                "LINE 4".into(),
+            ],
+            ..Default::default()
+        }
+        .render_party("59", "Cdtr", &mut issues);
+
+        assert_eq!(field.tag, "59");
+        assert_eq!(field.lines().count(), 4);
+        assert_eq!(
+            issues,
+            vec![
+                truncated(
+                    "59",
+                    "Cdtr/Nm",
+                    "A NAME THAT IS LONGER THAN THIRTY-FIVE CHARACTERS"
+                ),
+                truncated("59", "Cdtr/PstlAdr", "LINE 4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_amount() {
+        assert_eq!(format_amount(1234.56, "EUR"), "1234,56");
+        assert_eq!(format_amount(1000.0, "EUR"), "1000,");
+        assert_eq!(format_amount(0.5, "EUR"), "0,5");
+        assert_eq!(format_amount(1234.567, "KWD"), "1234,567");
+        assert_eq!(format_amount(1234.0, "JPY"), "1234,");
+        #[cfg(any(feature = "pacs", feature = "sese"))]
+        {
+            assert_eq!(format_decimal(0.9123), "0,9123");
+            assert_eq!(format_decimal(2.0), "2,");
+        }
+        assert_eq!(parse_amount("32A", "1000,").unwrap(), 1000.0);
+        assert!(parse_amount("32A", "1.000,00").is_err());
+    }
+}
@@ -0,0 +1,2554 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Securities Settlement Translation
+//!
+//! Translates the ISO 15022 settlement instructions `MT540` to `MT543` into a `sese.023.001.11`
+//! securities settlement transaction instruction, the settlement confirmations `MT544` to
+//! `MT547` into a `sese.025.001.11` securities settlement transaction confirmation, and the
+//! settlement status and processing advice `MT548` into a `sese.024.001.12` securities
+//! settlement transaction status advice, and back, following the SMPG coexistence rules.
+//!
+//! The block 4 is parsed into the `Sequence`s of the `iso15022` module.
+//!
+//! | MT540 to MT547                          | sese.023.001.11 / sese.025.001.11               |
+//! |-----------------------------------------|-------------------------------------------------|
+//! | `GENL` `20C::SEME`                      | `TxId` / `TxIdDtls/AcctSvcrTxId`                |
+//! | `GENL/LINK` `20C::RELA`                 | `Lnkgs/Ref` / `TxIdDtls/AcctOwnrTxId`           |
+//! | `GENL/LINK` `20C::PREV`                 | `Lnkgs/Ref` / `Lnkgs/SctiesSttlmTxId`           |
+//! | `GENL/LINK` `22F::LINK`, `13A::LINK`    | `Lnkgs/PrcgPos`, `Lnkgs/MsgNb`                  |
+//! | `GENL/LINK` `20C::COMM`                 | `CmonId`                                        |
+//! | `TRADDET` `98a::TRAD`, `98a::SETT`      | `TradDtls/TradDt`, `TradDtls/SttlmDt`           |
+//! | `TRADDET` `98a::ESET`                   | `TradDtls/FctvSttlmDt`                          |
+//! | `TRADDET` `35B`                         | `FinInstrmId`                                   |
+//! | `TRADDET` `70E::SPRO`                   | `TradDtls/SttlmInstrPrcgAddtlDtls`              |
+//! | `FIAC` `36B::SETT`, `36B::ESTT`         | `QtyAndAcctDtls/SttlmQty`, `SttldQty`           |
+//! | `FIAC` `95a::ACOW`                      | `QtyAndAcctDtls/AcctOwnr`                       |
+//! | `FIAC` `97A::SAFE`, `97a::CASH`         | `QtyAndAcctDtls/SfkpgAcct`, `CshAcct`           |
+//! | `SETDET` `22F::SETR`                    | `SttlmParams/SctiesTxTp`                        |
+//! | `SETDET` `22F::STCO`                    | `SttlmParams/PrtlSttlmInd`, `SttlmTxCond`       |
+//! | `SETDET/SETPRTY` `95a::PSET`            | `DlvrgSttlmPties/Dpstry`, `RcvgSttlmPties/Dpstry` |
+//! | `SETDET/SETPRTY` `95a::DEAG` to `SELL`  | `DlvrgSttlmPties/Pty1` to `Pty5`                |
+//! | `SETDET/SETPRTY` `95a::REAG` to `BUYR`  | `RcvgSttlmPties/Pty1` to `Pty5`                 |
+//! | `SETDET/AMT` `19A::SETT`, `19A::ESTT`   | `SttlmAmt` / `SttldAmt`                         |
+//!
+//! | MT548                                   | sese.024.001.12                                 |
+//! |-----------------------------------------|-------------------------------------------------|
+//! | `GENL` `20C::SEME`                      | `TxId/AcctSvcrTxId`                             |
+//! | `GENL/LINK` `20C::RELA`                 | `TxId/AcctOwnrTxId`                             |
+//! | `GENL/STAT` `25D::IPRC`                 | `PrcgSts`                                       |
+//! | `GENL/STAT` `25D::MTCH`                 | `MtchgSts`                                      |
+//! | `GENL/STAT` `25D::SETT`                 | `SttlmSts`                                      |
+//! | `GENL/STAT/REAS` `24B`, `70D::REAS`     | `Rsn/Cd`, `Rsn/AddtlRsnInf` of the status       |
+//! | `SETTRAN` `35B`, `36B::SETT`, `97A::SAFE` | `TxDtls/FinInstrmId`, `SttlmQty`, `SfkpgAcct` |
+//! | `SETTRAN` `22F::SETR`, `22H::REDE`, `22H::PAYM` | `TxDtls/SttlmParams/SctiesTxTp`, `SctiesMvmntTp`, `Pmt` |
+//! | `SETTRAN` `98a::SETT`, `98a::TRAD`, `19A::SETT` | `TxDtls/SttlmDt`, `TradDt`, `SttlmAmt`  |
+//!
+//! The qualified fields without counterpart, e.g. `:22F::RTGS//YRTG`, are reported as
+//! `TranslationIssue::Unmapped` with their sequence. The codes of the data source scheme of
+//! the ISO 15022 message are carried as proprietary codes, with the scheme as their issuer.
+//!
+//! The ISO 20022 messages have no header, so the sender and receiver of the translated MT
+//! messages are passed as a `FinHeader`.
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use iso_20022_sese::sese_023_001_11 as sese_023;
+use iso_20022_sese::sese_024_001_12 as sese_024;
+use iso_20022_sese::sese_025_001_11 as sese_025;
+
+use super::iso15022::{
+    cash_account, choice, choice_field, date_time, function, parse_code, security, Code,
+    GenericField, IsoTranslation, Sequence,
+};
+use super::{
+    convert, format_amount, format_decimal, lost, parse_amount, truncate, wrap, Block4, Error,
+    Field, FinHeader, MessageType, MtTranslation, TranslationIssue,
+};
+
+/// Maximum length of the references of field `20C`.
+const REFERENCE_LENGTH: usize = 16;
+
+/// Maximum length of a line of the narratives of fields `70D` and `70E`.
+const NARRATIVE_LINE_LENGTH: usize = 35;
+
+/// Maximum number of lines of the reason narrative of field `70D`.
+const REASON_LINES: usize = 6;
+
+/// Maximum number of lines of the processing narrative of field `70E`.
+const PROCESSING_LINES: usize = 10;
+
+/// Maximum number of lines of the name and address of field `95Q`.
+const NAME_LINES: usize = 4;
+
+/// Maximum number of lines of the description of the financial instrument of field `35B`.
+const DESCRIPTION_LINES: usize = 4;
+
+/// Reference of field `20C` of a missing identification.
+const NONREF: &str = "NONREF";
+
+/// Function of the message of field `23G` of the instructions and confirmations.
+const NEW: &str = "NEWM";
+
+/// Function of the message of field `23G` of the status advices on instructions.
+const INSTRUCTION: &str = "INST";
+
+/// Reason code of a narrative reason of field `24B`, and its ISO 20022 counterpart.
+const NARRATIVE: (&str, &str) = ("NARR", "OTHR");
+
+/// Qualifiers of the settlement parties of the delivering and of the receiving side, in the
+/// order of `Pty1` to `Pty5`.
+const PARTIES: [(&str, &str); 5] = [
+    ("DEAG", "REAG"),
+    ("DECU", "RECU"),
+    ("DEI1", "REI1"),
+    ("DEI2", "REI2"),
+    ("SELL", "BUYR"),
+];
+
+/// Qualifier of the place of settlement, i.e. the depository of the settlement parties.
+const PLACE_OF_SETTLEMENT: &str = "PSET";
+
+/// Statuses of field `25D` of an `MT548` with a counterpart in a `sese.024.001.12`.
+const STATUSES: [(&str, &str); 10] = [
+    ("IPRC", "PACK"),
+    ("IPRC", "REJT"),
+    ("IPRC", "REPR"),
+    ("IPRC", "PPRC"),
+    ("IPRC", "CAND"),
+    ("IPRC", "CANP"),
+    ("MTCH", "MACH"),
+    ("MTCH", "NMAT"),
+    ("SETT", "PEND"),
+    ("SETT", "PENF"),
+];
+
+lazy_static! {
+    /// Amount of field `19A`, with its sign, e.g. `NEUR1234,56`.
+    static ref AMOUNT: Regex = Regex::new(r"^(N)?([A-Z]{3})(\d{1,15},\d{0,})$").unwrap();
+}
+
+/// Translate the block 4 of an `MT540` to `MT543` into a `sese.023.001.11`, the message type
+/// giving the securities movement and the payment, e.g. `RECE` and `APMT` for an `MT541`.
+pub fn mt540_to_sese_023<A>(
+    message_type: MessageType,
+    block4: &Block4,
+) -> Result<IsoTranslation<sese_023::Document<A>>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let (scties_mvmnt_tp, pmt) = match message_type {
+        MessageType::Mt540 => (
+            sese_023::ReceiveDelivery1Code::Rece,
+            sese_023::DeliveryReceiptType2Code::Free,
+        ),
+        MessageType::Mt541 => (
+            sese_023::ReceiveDelivery1Code::Rece,
+            sese_023::DeliveryReceiptType2Code::Apmt,
+        ),
+        MessageType::Mt542 => (
+            sese_023::ReceiveDelivery1Code::Deli,
+            sese_023::DeliveryReceiptType2Code::Free,
+        ),
+        MessageType::Mt543 => (
+            sese_023::ReceiveDelivery1Code::Deli,
+            sese_023::DeliveryReceiptType2Code::Apmt,
+        ),
+        _ => return Err(Error::UnsupportedMessageType(message_type)),
+    };
+    let receive = scties_mvmnt_tp == sese_023::ReceiveDelivery1Code::Rece;
+    let mut message = Sequence::parse(block4)?;
+
+    let genl = message.mandatory_sequence("16R:GENL")?;
+    let tx_id = genl.mandatory("20C::SEME")?;
+    function(genl, NEW)?;
+
+    let mut lnkgs = vec![];
+    let mut cmon_id = None;
+    for link in genl.sequences_mut("LINK") {
+        let reference = match ["PREV", "RELA", "POOL", "COMM"]
+            .iter()
+            .find_map(|qualifier| link.take(&format!("20C::{}", qualifier)))
+        {
+            Some(reference) => reference,
+            None => continue,
+        };
+        let id = Some(sese_023::Max35Text {
+            value: reference.data,
+        });
+
+        let r#ref = match reference.qualifier.as_str() {
+            "PREV" => sese_023::References41ChoiceEnum {
+                scties_sttlm_tx_id: id,
+                ..Default::default()
+            },
+            "RELA" => sese_023::References41ChoiceEnum {
+                othr_tx_id: id,
+                ..Default::default()
+            },
+            "POOL" => sese_023::References41ChoiceEnum {
+                pool_id: id,
+                ..Default::default()
+            },
+            _ => {
+                cmon_id = id;
+                continue;
+            }
+        };
+        let prcg_pos = link
+            .take("22F::LINK")
+            .map(|field| choice(&field))
+            .transpose()?
+            .map(|value| sese_023::ProcessingPosition7Choice { value });
+        let msg_nb = link
+            .take("13A::LINK")
+            .map(|field| sese_023::DocumentNumber5Choice {
+                value: sese_023::DocumentNumber5ChoiceEnum {
+                    shrt_nb: Some(sese_023::Exact3NumericText { value: field.data }),
+                    ..Default::default()
+                },
+            });
+
+        lnkgs.push(sese_023::Linkages64 {
+            prcg_pos,
+            msg_nb,
+            r#ref: sese_023::References41Choice { value: r#ref },
+            ..Default::default()
+        });
+    }
+
+    let traddet = message.mandatory_sequence("16R:TRADDET")?;
+    let trad_dtls = sese_023::SecuritiesTradeDetails119 {
+        trad_dt: traddet
+            .take("98a::TRAD")
+            .map(|field| trade_date(&field))
+            .transpose()?,
+        sttlm_dt: sese_023::SettlementDate17Choice {
+            value: sese_023::SettlementDate17ChoiceEnum {
+                dt: Some(sese_023::DateAndDateTime2Choice {
+                    value: date_time(&traddet.mandatory("98a::SETT")?)?,
+                }),
+                ..Default::default()
+            },
+        },
+        sttlm_instr_prcg_addtl_dtls: traddet.take("70E::SPRO").map(|field| sese_023::Max350Text {
+            value: field.data.lines().collect(),
+        }),
+        ..Default::default()
+    };
+    let fin_instrm_id: sese_023::SecurityIdentification19 = security(
+        &traddet
+            .take_field("35B")
+            .ok_or(Error::MissingField("35B"))?,
+    )?;
+
+    let fiac = message.mandatory_sequence("16R:FIAC")?;
+    let qty_and_acct_dtls = sese_023::QuantityAndAccount95 {
+        sttlm_qty: Quantity::parse(&fiac.mandatory("36B::SETT")?)?.into(),
+        acct_ownr: account_owner(fiac)?,
+        sfkpg_acct: fiac.take("97A::SAFE").map(safekeeping_account),
+        csh_acct: fiac
+            .take("97a::CASH")
+            .map(cash_account)
+            .map(|value| sese_023::CashAccountIdentification5Choice { value }),
+        ..Default::default()
+    };
+
+    let setdet = message.mandatory_sequence("16R:SETDET")?;
+    let (prtl_sttlm_ind, sttlm_tx_cond) = settlement_conditions(setdet)?;
+    let sttlm_params = sese_023::SettlementDetails201 {
+        scties_tx_tp: sese_023::SecuritiesTransactionType47Choice {
+            value: choice(&setdet.mandatory("22F::SETR")?)?,
+        },
+        sttlm_tx_cond: sttlm_tx_cond
+            .into_iter()
+            .map(|value| sese_023::SettlementTransactionCondition33Choice { value })
+            .collect(),
+        prtl_sttlm_ind,
+        ..Default::default()
+    };
+    let (dlvrg_sttlm_pties, rcvg_sttlm_pties) = settlement_parties(setdet, receive)?;
+    let sttlm_amt = settlement_amount(setdet, "19A::SETT", receive)?;
+
+    let mut issues = vec![];
+    message.unmapped("", &mut issues);
+
+    Ok(IsoTranslation {
+        document: sese_023::Document {
+            scties_sttlm_tx_instr: sese_023::SecuritiesSettlementTransactionInstructionV11 {
+                tx_id: sese_023::Max35Text { value: tx_id.data },
+                sttlm_tp_and_addtl_params: sese_023::SettlementTypeAndAdditionalParameters21 {
+                    scties_mvmnt_tp,
+                    pmt,
+                    cmon_id,
+                    ..Default::default()
+                },
+                lnkgs,
+                trad_dtls,
+                fin_instrm_id,
+                qty_and_acct_dtls,
+                sttlm_params,
+                dlvrg_sttlm_pties,
+                rcvg_sttlm_pties,
+                sttlm_amt,
+                ..Default::default()
+            },
+            xmlns: sese_023::namespace(),
+        },
+        issues,
+    })
+}
+
+/// Translate a `sese.023.001.11` into an `MT540` to `MT543`, of its securities movement and
+/// payment, reporting the elements without place in the MT message.
+pub fn sese_023_to_mt540<A>(
+    header: &FinHeader,
+    document: &sese_023::Document<A>,
+) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let instr = &document.scties_sttlm_tx_instr;
+    let params = &instr.sttlm_tp_and_addtl_params;
+
+    let (message_type, receive) = match (&params.scties_mvmnt_tp, &params.pmt) {
+        (sese_023::ReceiveDelivery1Code::Rece, sese_023::DeliveryReceiptType2Code::Free) => {
+            (MessageType::Mt540, true)
+        }
+        (sese_023::ReceiveDelivery1Code::Rece, sese_023::DeliveryReceiptType2Code::Apmt) => {
+            (MessageType::Mt541, true)
+        }
+        (sese_023::ReceiveDelivery1Code::Deli, sese_023::DeliveryReceiptType2Code::Free) => {
+            (MessageType::Mt542, false)
+        }
+        (sese_023::ReceiveDelivery1Code::Deli, sese_023::DeliveryReceiptType2Code::Apmt) => {
+            (MessageType::Mt543, false)
+        }
+        _ => return Err(Error::MissingElement("SttlmTpAndAddtlParams/SctiesMvmntTp")),
+    };
+
+    let qty = &instr.qty_and_acct_dtls;
+    let mut issues = lost_elements(&[
+        ("NbCounts", instr.nb_counts.is_some()),
+        ("FinInstrmAttrbts", instr.fin_instrm_attrbts.is_some()),
+        ("QtyAndAcctDtls/QtyBrkdwn", !qty.qty_brkdwn.is_empty()),
+        ("QtyAndAcctDtls/SfkpgPlc", qty.sfkpg_plc.is_some()),
+        ("StgSttlmInstrDtls", instr.stg_sttlm_instr_dtls.is_some()),
+        ("CshPties", instr.csh_pties.is_some()),
+        ("OthrAmts", instr.othr_amts.is_some()),
+        ("OthrBizPties", instr.othr_biz_pties.is_some()),
+        (
+            "AddtlPhysOrRegnDtls",
+            instr.addtl_phys_or_regn_dtls.is_some(),
+        ),
+        ("SplmtryData", !instr.splmtry_data.is_empty()),
+    ]);
+    let mut message = Sequence::default();
+
+    let mut genl = Sequence::new("GENL");
+    genl.push_generic(reference("SEME", &instr.tx_id.value, "TxId", &mut issues));
+    genl.push("23G", NEW);
+    for lnkg in instr.lnkgs.iter() {
+        let r#ref = &lnkg.r#ref.value;
+        let reference = [
+            ("PREV", "SctiesSttlmTxId", &r#ref.scties_sttlm_tx_id),
+            ("RELA", "OthrTxId", &r#ref.othr_tx_id),
+            ("POOL", "PoolId", &r#ref.pool_id),
+        ]
+        .into_iter()
+        .find_map(|(qualifier, element, id)| Some((qualifier, element, id.as_ref()?)));
+        let (qualifier, element, id) = match reference {
+            Some(reference) => reference,
+            None => {
+                issues.push(lost("Lnkgs/Ref"));
+                continue;
+            }
+        };
+
+        let mut link = Sequence::new("LINK");
+        if let Some(prcg_pos) = &lnkg.prcg_pos {
+            if let Some(field) = choice_field(&prcg_pos.value, "22F", "LINK")? {
+                link.push_generic(field);
+            }
+        }
+        if let Some(shrt_nb) = lnkg
+            .msg_nb
+            .as_ref()
+            .and_then(|msg_nb| msg_nb.value.shrt_nb.as_ref())
+        {
+            link.push_generic(GenericField::new("13A", "LINK", &shrt_nb.value));
+        }
+        link.push_generic(reference(
+            qualifier,
+            &id.value,
+            &format!("Lnkgs/Ref/{}", element),
+            &mut issues,
+        ));
+        genl.push_sequence(link);
+    }
+    if let Some(cmon_id) = &params.cmon_id {
+        let mut link = Sequence::new("LINK");
+        link.push_generic(reference(
+            "COMM",
+            &cmon_id.value,
+            "SttlmTpAndAddtlParams/CmonId",
+            &mut issues,
+        ));
+        genl.push_sequence(link);
+    }
+    message.push_sequence(genl);
+
+    let trad_dtls = &instr.trad_dtls;
+    let mut traddet = Sequence::new("TRADDET");
+    let sttlm_dt = trad_dtls
+        .sttlm_dt
+        .value
+        .dt
+        .as_ref()
+        .and_then(|dt| date_time_field("SETT", &dt.value))
+        .ok_or(Error::MissingElement("TradDtls/SttlmDt/Dt"))?;
+    traddet.push_generic(sttlm_dt);
+    if let Some(field) = trad_dtls
+        .trad_dt
+        .as_ref()
+        .and_then(|trad_dt| trad_dt.value.dt.as_ref())
+        .and_then(|dt| date_time_field("TRAD", &dt.value))
+    {
+        traddet.push_generic(field);
+    }
+    traddet
+        .fields
+        .push(security_field(&instr.fin_instrm_id, &mut issues));
+    if let Some(dtls) = &trad_dtls.sttlm_instr_prcg_addtl_dtls {
+        traddet.push_generic(GenericField::new(
+            "70E",
+            "SPRO",
+            narrative(
+                &dtls.value,
+                PROCESSING_LINES,
+                "70E",
+                "TradDtls/SttlmInstrPrcgAddtlDtls",
+                &mut issues,
+            ),
+        ));
+    }
+    message.push_sequence(traddet);
+
+    let mut fiac = Sequence::new("FIAC");
+    let sttlm_qty = Quantity::from_sese_023(&qty.sttlm_qty)
+        .ok_or(Error::MissingElement("QtyAndAcctDtls/SttlmQty/Qty"))?;
+    fiac.push_generic(sttlm_qty.field("SETT"));
+    account_fields(
+        &mut fiac,
+        qty.acct_ownr.as_ref(),
+        qty.sfkpg_acct.as_ref(),
+        qty.csh_acct.as_ref().map(|csh_acct| &csh_acct.value),
+        &mut issues,
+    );
+    message.push_sequence(fiac);
+
+    let mut setdet = Sequence::new("SETDET");
+    settlement_parameter_fields(&mut setdet, &instr.sttlm_params)?;
+    for setprty in settlement_party_sequences(
+        instr.dlvrg_sttlm_pties.clone(),
+        instr.rcvg_sttlm_pties.clone(),
+        &mut issues,
+    ) {
+        setdet.push_sequence(setprty);
+    }
+    if let Some(sttlm_amt) = &instr.sttlm_amt {
+        setdet.push_sequence(amount_sequence("SETT", sttlm_amt, receive));
+    }
+    message.push_sequence(setdet);
+
+    Ok(MtTranslation {
+        message_type,
+        header: header.clone(),
+        block4: message.block4(),
+        issues,
+    })
+}
+
+/// Translate the block 4 of an `MT544` to `MT547` into a `sese.025.001.11`, the message type
+/// giving the securities movement and the payment, e.g. `DELI` and `APMT` for an `MT547`.
+///
+/// The account owner transaction identification is the related reference `20C::RELA` of the
+/// confirmed instruction, or `NONREF`.
+pub fn mt544_to_sese_025<A>(
+    message_type: MessageType,
+    block4: &Block4,
+) -> Result<IsoTranslation<sese_025::Document<A>>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let (scties_mvmnt_tp, pmt) = match message_type {
+        MessageType::Mt544 => (
+            sese_025::ReceiveDelivery1Code::Rece,
+            sese_025::DeliveryReceiptType2Code::Free,
+        ),
+        MessageType::Mt545 => (
+            sese_025::ReceiveDelivery1Code::Rece,
+            sese_025::DeliveryReceiptType2Code::Apmt,
+        ),
+        MessageType::Mt546 => (
+            sese_025::ReceiveDelivery1Code::Deli,
+            sese_025::DeliveryReceiptType2Code::Free,
+        ),
+        MessageType::Mt547 => (
+            sese_025::ReceiveDelivery1Code::Deli,
+            sese_025::DeliveryReceiptType2Code::Apmt,
+        ),
+        _ => return Err(Error::UnsupportedMessageType(message_type)),
+    };
+    let receive = scties_mvmnt_tp == sese_025::ReceiveDelivery1Code::Rece;
+    let mut message = Sequence::parse(block4)?;
+
+    let genl = message.mandatory_sequence("16R:GENL")?;
+    let mut tx_id_dtls = sese_025::SettlementTypeAndIdentification27 {
+        acct_ownr_tx_id: sese_025::Max35Text {
+            value: NONREF.to_string(),
+        },
+        acct_svcr_tx_id: Some(sese_025::Max35Text {
+            value: genl.mandatory("20C::SEME")?.data,
+        }),
+        scties_mvmnt_tp,
+        pmt,
+        ..Default::default()
+    };
+    function(genl, NEW)?;
+
+    let mut lnkgs = None;
+    for link in genl.sequences_mut("LINK") {
+        let reference = match ["RELA", "PREV", "POOL", "COMM"]
+            .iter()
+            .find_map(|qualifier| link.take(&format!("20C::{}", qualifier)))
+        {
+            Some(reference) => reference,
+            None => continue,
+        };
+        let id = sese_025::Max35Text {
+            value: reference.data,
+        };
+
+        match reference.qualifier.as_str() {
+            "RELA" => tx_id_dtls.acct_ownr_tx_id = id,
+            "PREV" => {
+                let prcg_pos = link
+                    .take("22F::LINK")
+                    .map(|field| choice(&field))
+                    .transpose()?
+                    .map(|value| sese_025::ProcessingPosition9Choice { value });
+                lnkgs = Some(sese_025::Linkages41 {
+                    prcg_pos,
+                    scties_sttlm_tx_id: id,
+                });
+            }
+            "POOL" => tx_id_dtls.pool_id = Some(id),
+            _ => tx_id_dtls.cmon_id = Some(id),
+        }
+    }
+
+    let traddet = message.mandatory_sequence("16R:TRADDET")?;
+    let fctv_sttlm_dt: sese_025::DateAndDateTime2ChoiceEnum =
+        date_time(&traddet.mandatory("98a::ESET")?)?;
+    let trad_dtls = sese_025::SecuritiesTradeDetails118 {
+        trad_dt: traddet
+            .take("98a::TRAD")
+            .map(|field| -> Result<_, Error> {
+                Ok(sese_025::TradeDate8Choice {
+                    value: convert(&trade_date(&field)?.value)?,
+                })
+            })
+            .transpose()?,
+        sttlm_dt: traddet
+            .take("98a::SETT")
+            .map(|field| -> Result<_, Error> {
+                Ok(sese_025::SettlementDate17Choice {
+                    value: sese_025::SettlementDate17ChoiceEnum {
+                        dt: Some(sese_025::DateAndDateTime2Choice {
+                            value: date_time(&field)?,
+                        }),
+                        ..Default::default()
+                    },
+                })
+            })
+            .transpose()?,
+        fctv_sttlm_dt: sese_025::SettlementDate18Choice {
+            value: sese_025::SettlementDate18ChoiceEnum {
+                dt: Some(sese_025::DateAndDateTime2Choice {
+                    value: fctv_sttlm_dt,
+                }),
+                ..Default::default()
+            },
+        },
+        sttlm_instr_prcg_addtl_dtls: traddet.take("70E::SPRO").map(|field| sese_025::Max350Text {
+            value: field.data.lines().collect(),
+        }),
+        ..Default::default()
+    };
+    let fin_instrm_id: sese_025::SecurityIdentification19 = security(
+        &traddet
+            .take_field("35B")
+            .ok_or(Error::MissingField("35B"))?,
+    )?;
+
+    let fiac = message.mandatory_sequence("16R:FIAC")?;
+    let qty_and_acct_dtls = sese_025::QuantityAndAccount96 {
+        sttld_qty: Quantity::parse(&fiac.mandatory("36B::ESTT")?)?.into(),
+        acct_ownr: account_owner(fiac)?
+            .map(|acct_ownr| convert(&acct_ownr))
+            .transpose()?,
+        sfkpg_acct: fiac
+            .take("97A::SAFE")
+            .map(|field| convert(&safekeeping_account(field)))
+            .transpose()?,
+        csh_acct: fiac
+            .take("97a::CASH")
+            .map(cash_account)
+            .transpose()?
+            .map(|value| sese_025::CashAccountIdentification5Choice { value }),
+        ..Default::default()
+    };
+
+    let setdet = message.mandatory_sequence("16R:SETDET")?;
+    let (prtl_sttlm_ind, sttlm_tx_cond) = settlement_conditions(setdet)?;
+    let sttlm_params = sese_025::SettlementDetails203 {
+        scties_tx_tp: sese_025::SecuritiesTransactionType43Choice {
+            value: choice(&setdet.mandatory("22F::SETR")?)?,
+        },
+        sttlm_tx_cond: sttlm_tx_cond
+            .into_iter()
+            .map(|value| sese_025::SettlementTransactionCondition34Choice { value })
+            .collect(),
+        prtl_sttlm_ind,
+        ..Default::default()
+    };
+    let (dlvrg_sttlm_pties, rcvg_sttlm_pties) = settlement_parties(setdet, receive)?;
+    let sttld_amt = settlement_amount(setdet, "19A::ESTT", receive)?;
+
+    let mut issues = vec![];
+    message.unmapped("", &mut issues);
+
+    Ok(IsoTranslation {
+        document: sese_025::Document {
+            scties_sttlm_tx_conf: sese_025::SecuritiesSettlementTransactionConfirmationV11 {
+                tx_id_dtls,
+                lnkgs,
+                trad_dtls,
+                fin_instrm_id,
+                qty_and_acct_dtls,
+                sttlm_params,
+                dlvrg_sttlm_pties: dlvrg_sttlm_pties
+                    .map(|parties| convert(&parties))
+                    .transpose()?,
+                rcvg_sttlm_pties: rcvg_sttlm_pties
+                    .map(|parties| convert(&parties))
+                    .transpose()?,
+                sttld_amt: sttld_amt.map(|amount| convert(&amount)).transpose()?,
+                ..Default::default()
+            },
+            xmlns: sese_025::namespace(),
+        },
+        issues,
+    })
+}
+
+/// Translate a `sese.025.001.11` into an `MT544` to `MT547`, of its securities movement and
+/// payment, reporting the elements without place in the MT message.
+pub fn sese_025_to_mt544<A>(
+    header: &FinHeader,
+    document: &sese_025::Document<A>,
+) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let conf = &document.scties_sttlm_tx_conf;
+    let tx_id_dtls = &conf.tx_id_dtls;
+
+    let (message_type, receive) = match (&tx_id_dtls.scties_mvmnt_tp, &tx_id_dtls.pmt) {
+        (sese_025::ReceiveDelivery1Code::Rece, sese_025::DeliveryReceiptType2Code::Free) => {
+            (MessageType::Mt544, true)
+        }
+        (sese_025::ReceiveDelivery1Code::Rece, sese_025::DeliveryReceiptType2Code::Apmt) => {
+            (MessageType::Mt545, true)
+        }
+        (sese_025::ReceiveDelivery1Code::Deli, sese_025::DeliveryReceiptType2Code::Free) => {
+            (MessageType::Mt546, false)
+        }
+        (sese_025::ReceiveDelivery1Code::Deli, sese_025::DeliveryReceiptType2Code::Apmt) => {
+            (MessageType::Mt547, false)
+        }
+        _ => return Err(Error::MissingElement("TxIdDtls/SctiesMvmntTp")),
+    };
+
+    let qty = &conf.qty_and_acct_dtls;
+    let mut issues = lost_elements(&[
+        ("AddtlParams", conf.addtl_params.is_some()),
+        ("FinInstrmAttrbts", conf.fin_instrm_attrbts.is_some()),
+        ("QtyAndAcctDtls/QtyBrkdwn", !qty.qty_brkdwn.is_empty()),
+        ("QtyAndAcctDtls/SfkpgPlc", qty.sfkpg_plc.is_some()),
+        ("StgSttlmInstrDtls", conf.stg_sttlm_instr_dtls.is_some()),
+        ("CshPties", conf.csh_pties.is_some()),
+        ("OthrAmts", conf.othr_amts.is_some()),
+        ("OthrBizPties", conf.othr_biz_pties.is_some()),
+        (
+            "AddtlPhysOrRegnDtls",
+            conf.addtl_phys_or_regn_dtls.is_some(),
+        ),
+        ("SplmtryData", !conf.splmtry_data.is_empty()),
+    ]);
+    let mut message = Sequence::default();
+
+    let mut genl = Sequence::new("GENL");
+    let acct_svcr_tx_id = tx_id_dtls
+        .acct_svcr_tx_id
+        .as_ref()
+        .map(|id| id.value.as_str())
+        .unwrap_or(NONREF);
+    genl.push_generic(reference(
+        "SEME",
+        acct_svcr_tx_id,
+        "TxIdDtls/AcctSvcrTxId",
+        &mut issues,
+    ));
+    genl.push("23G", NEW);
+    let references = [
+        (
+            "RELA",
+            "TxIdDtls/AcctOwnrTxId",
+            Some(&tx_id_dtls.acct_ownr_tx_id).filter(|id| id.value != NONREF),
+        ),
+        (
+            "PREV",
+            "Lnkgs/SctiesSttlmTxId",
+            conf.lnkgs.as_ref().map(|lnkgs| &lnkgs.scties_sttlm_tx_id),
+        ),
+        ("POOL", "TxIdDtls/PoolId", tx_id_dtls.pool_id.as_ref()),
+        ("COMM", "TxIdDtls/CmonId", tx_id_dtls.cmon_id.as_ref()),
+    ];
+    for (qualifier, element, id) in references {
+        let id = match id {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let mut link = Sequence::new("LINK");
+        if let Some(prcg_pos) = conf
+            .lnkgs
+            .as_ref()
+            .and_then(|lnkgs| lnkgs.prcg_pos.as_ref())
+            .filter(|_| qualifier == "PREV")
+        {
+            if let Some(field) = choice_field(&prcg_pos.value, "22F", "LINK")? {
+                link.push_generic(field);
+            }
+        }
+        link.push_generic(reference(qualifier, &id.value, element, &mut issues));
+        genl.push_sequence(link);
+    }
+    message.push_sequence(genl);
+
+    let trad_dtls = &conf.trad_dtls;
+    let mut traddet = Sequence::new("TRADDET");
+    if let Some(dt) = trad_dtls
+        .sttlm_dt
+        .as_ref()
+        .and_then(|sttlm_dt| sttlm_dt.value.dt.as_ref())
+    {
+        if let Some(field) = converted_date_time_field("SETT", &dt.value)? {
+            traddet.push_generic(field);
+        }
+    }
+    let fctv_sttlm_dt = match &trad_dtls.fctv_sttlm_dt.value.dt {
+        Some(dt) => converted_date_time_field("ESET", &dt.value)?,
+        None => None,
+    };
+    traddet.push_generic(fctv_sttlm_dt.ok_or(Error::MissingElement("TradDtls/FctvSttlmDt/Dt"))?);
+    if let Some(dt) = trad_dtls
+        .trad_dt
+        .as_ref()
+        .and_then(|trad_dt| trad_dt.value.dt.as_ref())
+    {
+        if let Some(field) = converted_date_time_field("TRAD", &dt.value)? {
+            traddet.push_generic(field);
+        }
+    }
+    traddet
+        .fields
+        .push(security_field(&convert(&conf.fin_instrm_id)?, &mut issues));
+    if let Some(dtls) = &trad_dtls.sttlm_instr_prcg_addtl_dtls {
+        traddet.push_generic(GenericField::new(
+            "70E",
+            "SPRO",
+            narrative(
+                &dtls.value,
+                PROCESSING_LINES,
+                "70E",
+                "TradDtls/SttlmInstrPrcgAddtlDtls",
+                &mut issues,
+            ),
+        ));
+    }
+    message.push_sequence(traddet);
+
+    let mut fiac = Sequence::new("FIAC");
+    let sttld_qty = Quantity::from_sese_025(&qty.sttld_qty)
+        .ok_or(Error::MissingElement("QtyAndAcctDtls/SttldQty/Qty"))?;
+    fiac.push_generic(sttld_qty.field("ESTT"));
+    let acct_ownr = qty.acct_ownr.as_ref().map(convert).transpose()?;
+    let sfkpg_acct = qty.sfkpg_acct.as_ref().map(convert).transpose()?;
+    let csh_acct = qty
+        .csh_acct
+        .as_ref()
+        .map(|csh_acct| convert(&csh_acct.value))
+        .transpose()?;
+    account_fields(
+        &mut fiac,
+        acct_ownr.as_ref(),
+        sfkpg_acct.as_ref(),
+        csh_acct.as_ref(),
+        &mut issues,
+    );
+    message.push_sequence(fiac);
+
+    let mut setdet = Sequence::new("SETDET");
+    settlement_parameter_fields(&mut setdet, &conf.sttlm_params)?;
+    for setprty in settlement_party_sequences(
+        conf.dlvrg_sttlm_pties.as_ref().map(convert).transpose()?,
+        conf.rcvg_sttlm_pties.as_ref().map(convert).transpose()?,
+        &mut issues,
+    ) {
+        setdet.push_sequence(setprty);
+    }
+    if let Some(sttld_amt) = &conf.sttld_amt {
+        setdet.push_sequence(amount_sequence("ESTT", &convert(sttld_amt)?, receive));
+    }
+    message.push_sequence(setdet);
+
+    Ok(MtTranslation {
+        message_type,
+        header: header.clone(),
+        block4: message.block4(),
+        issues,
+    })
+}
+
+/// Translate the block 4 of an `MT548` on an instruction into a `sese.024.001.12`, of its
+/// processing, matching and settlement statuses.
+///
+/// The statuses without counterpart, e.g. `:25D::CPRC//`, and the reasons of a matched status
+/// are reported as unmapped.
+pub fn mt548_to_sese_024<A>(block4: &Block4) -> Result<IsoTranslation<sese_024::Document<A>>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut message = Sequence::parse(block4)?;
+    let mut advice = sese_024::SecuritiesSettlementTransactionStatusAdviceV12::<A>::default();
+
+    let genl = message.mandatory_sequence("16R:GENL")?;
+    advice.tx_id = sese_024::TransactionIdentifications47 {
+        acct_ownr_tx_id: sese_024::Max35Text {
+            value: NONREF.to_string(),
+        },
+        acct_svcr_tx_id: Some(sese_024::Max35Text {
+            value: genl.mandatory("20C::SEME")?.data,
+        }),
+        ..Default::default()
+    };
+    function(genl, INSTRUCTION)?;
+    for link in genl.sequences_mut("LINK") {
+        if let Some(rela) = link.take("20C::RELA") {
+            advice.tx_id.acct_ownr_tx_id = sese_024::Max35Text { value: rela.data };
+        }
+    }
+    for stat in genl.sequences_mut("STAT") {
+        status(stat, &mut advice)?;
+    }
+
+    advice.tx_dtls = message
+        .sequence_mut("SETTRAN")
+        .map(transaction_details)
+        .transpose()?;
+
+    let mut issues = vec![];
+    message.unmapped("", &mut issues);
+
+    Ok(IsoTranslation {
+        document: sese_024::Document {
+            scties_sttlm_tx_sts_advc: advice,
+            xmlns: sese_024::namespace(),
+        },
+        issues,
+    })
+}
+
+/// Translate a `sese.024.001.12` into an `MT548`, reporting the elements without place in the
+/// MT message.
+pub fn sese_024_to_mt548<A>(
+    header: &FinHeader,
+    document: &sese_024::Document<A>,
+) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let advice = &document.scties_sttlm_tx_sts_advc;
+
+    let mut issues = lost_elements(&[
+        ("Lnkgs", advice.lnkgs.is_some()),
+        ("IfrrdMtchgSts", advice.ifrrd_mtchg_sts.is_some()),
+        ("SplmtryData", !advice.splmtry_data.is_empty()),
+    ]);
+    let mut message = Sequence::default();
+
+    let mut genl = Sequence::new("GENL");
+    let acct_svcr_tx_id = advice
+        .tx_id
+        .acct_svcr_tx_id
+        .as_ref()
+        .map(|id| id.value.as_str())
+        .unwrap_or(NONREF);
+    genl.push_generic(reference(
+        "SEME",
+        acct_svcr_tx_id,
+        "TxId/AcctSvcrTxId",
+        &mut issues,
+    ));
+    genl.push("23G", INSTRUCTION);
+    if advice.tx_id.acct_ownr_tx_id.value != NONREF {
+        let mut link = Sequence::new("LINK");
+        link.push_generic(reference(
+            "RELA",
+            &advice.tx_id.acct_ownr_tx_id.value,
+            "TxId/AcctOwnrTxId",
+            &mut issues,
+        ));
+        genl.push_sequence(link);
+    }
+
+    let mut statuses = vec![];
+    if let Some(prcg_sts) = &advice.prcg_sts {
+        let value = &prcg_sts.value;
+        statuses.extend(
+            [
+                (
+                    "PACK",
+                    value.ackd_accptd.as_ref().map(|sts| convert(&sts.value)),
+                ),
+                ("REJT", value.rjctd.as_ref().map(|sts| convert(&sts.value))),
+                ("REPR", value.rpr.as_ref().map(|sts| convert(&sts.value))),
+                (
+                    "PPRC",
+                    value.pdg_prcg.as_ref().map(|sts| convert(&sts.value)),
+                ),
+                ("CAND", value.canc.as_ref().map(|sts| convert(&sts.value))),
+                (
+                    "CANP",
+                    value.pdg_cxl.as_ref().map(|sts| convert(&sts.value)),
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(code, reason)| Some(("IPRC", code, reason?))),
+        );
+        issues.extend(lost_elements(&[
+            ("PrcgSts/ModReqd", value.mod_reqd.is_some()),
+            ("PrcgSts/CxlReqd", value.cxl_reqd.is_some()),
+            ("PrcgSts/Prtry", value.prtry.is_some()),
+        ]));
+    }
+    if let Some(mtchg_sts) = &advice.mtchg_sts {
+        let value = &mtchg_sts.value;
+        if let Some(mtchd) = &value.mtchd {
+            statuses.push(("MTCH", "MACH", Ok(StatusReason::default())));
+            if mtchd.rsn.is_some() || mtchd.addtl_rsn_inf.is_some() {
+                issues.push(lost("MtchgSts/Mtchd"));
+            }
+        }
+        if let Some(umtchd) = &value.umtchd {
+            statuses.push(("MTCH", "NMAT", convert(&umtchd.value)));
+        }
+        issues.extend(lost_elements(&[("MtchgSts/Prtry", value.prtry.is_some())]));
+    }
+    if let Some(sttlm_sts) = &advice.sttlm_sts {
+        let value = &sttlm_sts.value;
+        if let Some(pdg) = &value.pdg {
+            statuses.push(("SETT", "PEND", convert(&pdg.value)));
+        }
+        if let Some(flng) = &value.flng {
+            statuses.push(("SETT", "PENF", convert(&flng.value)));
+        }
+        issues.extend(lost_elements(&[("SttlmSts/Prtry", value.prtry.is_some())]));
+    }
+    for (qualifier, code, reason) in statuses {
+        genl.push_sequence(status_sequence(qualifier, code, reason?, &mut issues));
+    }
+    message.push_sequence(genl);
+
+    if let Some(tx_dtls) = &advice.tx_dtls {
+        message.push_sequence(transaction_sequence(tx_dtls, &mut issues)?);
+    }
+
+    Ok(MtTranslation {
+        message_type: MessageType::Mt548,
+        header: header.clone(),
+        block4: message.block4(),
+        issues,
+    })
+}
+
+/// Translate the status of field `25D` of a `STAT` sequence, with the reason of its first
+/// `REAS` sequence, into a processing, matching or settlement status of the advice.
+fn status<A>(
+    stat: &mut Sequence,
+    advice: &mut sese_024::SecuritiesSettlementTransactionStatusAdviceV12<A>,
+) -> Result<(), Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let field = match stat
+        .fields
+        .iter()
+        .filter_map(GenericField::parse)
+        .find(|field| field.tag == "25D" && field.issuer.is_none())
+    {
+        Some(field) => field,
+        None => return Ok(()),
+    };
+    let status = (field.qualifier.as_str(), field.data.as_str());
+    if !STATUSES.contains(&status) {
+        return Ok(());
+    }
+
+    stat.take(&format!("25D::{}", field.qualifier));
+    let reason = match status {
+        ("MTCH", "MACH") => Reason::default(),
+        _ => Reason::take(stat),
+    };
+    let no_spcfd_rsn = reason.no_specified_reason();
+
+    let processing = |value| Some(sese_024::ProcessingStatus88Choice { value });
+    match status {
+        ("IPRC", "PACK") => {
+            advice.prcg_sts = processing(sese_024::ProcessingStatus88ChoiceEnum {
+                ackd_accptd: Some(sese_024::AcknowledgedAcceptedStatus21Choice {
+                    value: sese_024::AcknowledgedAcceptedStatus21ChoiceEnum {
+                        rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                            sese_024::AcknowledgementReason9 {
+                                cd: sese_024::AcknowledgementReason12Choice { value: cd },
+                                addtl_rsn_inf,
+                            }
+                        }),
+                        no_spcfd_rsn,
+                    },
+                }),
+                ..Default::default()
+            })
+        }
+        ("IPRC", "REJT") => {
+            advice.prcg_sts = processing(sese_024::ProcessingStatus88ChoiceEnum {
+                rjctd: Some(sese_024::RejectionStatus39Choice {
+                    value: sese_024::RejectionStatus39ChoiceEnum {
+                        rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                            sese_024::RejectionReason59 {
+                                cd: sese_024::RejectionReason44Choice { value: cd },
+                                addtl_rsn_inf,
+                            }
+                        }),
+                        no_spcfd_rsn,
+                    },
+                }),
+                ..Default::default()
+            })
+        }
+        ("IPRC", "REPR") => {
+            advice.prcg_sts = processing(sese_024::ProcessingStatus88ChoiceEnum {
+                rpr: Some(sese_024::RepairStatus12Choice {
+                    value: sese_024::RepairStatus12ChoiceEnum {
+                        rsn: reason
+                            .reason()?
+                            .map(|(cd, addtl_rsn_inf)| sese_024::RepairReason8 {
+                                cd: sese_024::RepairReason10Choice { value: cd },
+                                addtl_rsn_inf,
+                            }),
+                        no_spcfd_rsn,
+                    },
+                }),
+                ..Default::default()
+            })
+        }
+        ("IPRC", "PPRC") => {
+            advice.prcg_sts = processing(sese_024::ProcessingStatus88ChoiceEnum {
+                pdg_prcg: Some(sese_024::PendingProcessingStatus18Choice {
+                    value: sese_024::PendingProcessingStatus18ChoiceEnum {
+                        rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                            sese_024::PendingProcessingReason15 {
+                                cd: sese_024::PendingProcessingReason17Choice { value: cd },
+                                addtl_rsn_inf,
+                            }
+                        }),
+                        no_spcfd_rsn,
+                    },
+                }),
+                ..Default::default()
+            })
+        }
+        ("IPRC", "CAND") => {
+            advice.prcg_sts = processing(sese_024::ProcessingStatus88ChoiceEnum {
+                canc: Some(sese_024::CancellationStatus24Choice {
+                    value: sese_024::CancellationStatus24ChoiceEnum {
+                        rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                            sese_024::CancellationReason22 {
+                                cd: sese_024::CancellationReason36Choice { value: cd },
+                                addtl_rsn_inf,
+                            }
+                        }),
+                        no_spcfd_rsn,
+                    },
+                }),
+                ..Default::default()
+            })
+        }
+        ("IPRC", _) => {
+            advice.prcg_sts = processing(sese_024::ProcessingStatus88ChoiceEnum {
+                pdg_cxl: Some(sese_024::PendingStatus38Choice {
+                    value: sese_024::PendingStatus38ChoiceEnum {
+                        rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                            sese_024::PendingReason16 {
+                                cd: sese_024::PendingReason28Choice { value: cd },
+                                addtl_rsn_inf,
+                            }
+                        }),
+                        no_spcfd_rsn,
+                    },
+                }),
+                ..Default::default()
+            })
+        }
+        ("MTCH", "MACH") => {
+            advice.mtchg_sts = Some(sese_024::MatchingStatus24Choice {
+                value: sese_024::MatchingStatus24ChoiceEnum {
+                    mtchd: Some(sese_024::ProprietaryReason4::default()),
+                    ..Default::default()
+                },
+            })
+        }
+        ("MTCH", _) => {
+            advice.mtchg_sts = Some(sese_024::MatchingStatus24Choice {
+                value: sese_024::MatchingStatus24ChoiceEnum {
+                    umtchd: Some(sese_024::UnmatchedStatus16Choice {
+                        value: sese_024::UnmatchedStatus16ChoiceEnum {
+                            rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                                sese_024::UnmatchedReason15 {
+                                    cd: sese_024::UnmatchedReason21Choice { value: cd },
+                                    addtl_rsn_inf,
+                                }
+                            }),
+                            no_spcfd_rsn,
+                        },
+                    }),
+                    ..Default::default()
+                },
+            })
+        }
+        (_, "PEND") => {
+            advice.sttlm_sts = Some(sese_024::SettlementStatus30Choice {
+                value: sese_024::SettlementStatus30ChoiceEnum {
+                    pdg: Some(sese_024::PendingStatus67Choice {
+                        value: sese_024::PendingStatus67ChoiceEnum {
+                            rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                                sese_024::PendingReason30 {
+                                    cd: sese_024::PendingReason63Choice { value: cd },
+                                    addtl_rsn_inf,
+                                }
+                            }),
+                            no_spcfd_rsn,
+                        },
+                    }),
+                    ..Default::default()
+                },
+            })
+        }
+        _ => {
+            advice.sttlm_sts = Some(sese_024::SettlementStatus30Choice {
+                value: sese_024::SettlementStatus30ChoiceEnum {
+                    flng: Some(sese_024::FailingStatus13Choice {
+                        value: sese_024::FailingStatus13ChoiceEnum {
+                            rsn: reason.reason()?.map(|(cd, addtl_rsn_inf)| {
+                                sese_024::FailingReason11 {
+                                    cd: sese_024::FailingReason16Choice { value: cd },
+                                    addtl_rsn_inf,
+                                }
+                            }),
+                            no_spcfd_rsn,
+                        },
+                    }),
+                    ..Default::default()
+                },
+            })
+        }
+    }
+
+    Ok(())
+}
+
+/// Return the `STAT` sequence of a status, e.g. `:25D::IPRC//REJT`, with the `REAS` sequence
+/// of its reason, the qualifier of field `24B` being the status, e.g. `:24B::REJT//SAFE`.
+fn status_sequence(
+    qualifier: &str,
+    code: &str,
+    reason: StatusReason,
+    issues: &mut Vec<TranslationIssue>,
+) -> Sequence {
+    let mut stat = Sequence::new("STAT");
+    stat.push_generic(GenericField::new("25D", qualifier, code));
+
+    let rsn = match reason.rsn {
+        Some(rsn) => rsn,
+        None => return stat,
+    };
+    if let Some(mut field) = rsn.cd.field("24B", code) {
+        if field.issuer.is_none() && field.data == NARRATIVE.1 {
+            field.data = NARRATIVE.0.to_string();
+        }
+
+        let mut reas = Sequence::new("REAS");
+        reas.push_generic(field);
+        if let Some(addtl_rsn_inf) = &rsn.addtl_rsn_inf {
+            reas.push_generic(GenericField::new(
+                "70D",
+                "REAS",
+                narrative(
+                    addtl_rsn_inf,
+                    REASON_LINES,
+                    "70D",
+                    "Rsn/AddtlRsnInf",
+                    issues,
+                ),
+            ));
+        }
+        stat.push_sequence(reas);
+    }
+
+    stat
+}
+
+/// Parse the `SETTRAN` sequence of an `MT548` into the transaction details.
+fn transaction_details(settran: &mut Sequence) -> Result<sese_024::TransactionDetails148, Error> {
+    let rede = settran.mandatory("22H::REDE")?;
+    let scties_mvmnt_tp: sese_024::ReceiveDelivery1Code =
+        parse_code(&rede.data).ok_or_else(|| rede.invalid())?;
+    let paym = settran.mandatory("22H::PAYM")?;
+    let pmt: sese_024::DeliveryReceiptType2Code =
+        parse_code(&paym.data).ok_or_else(|| paym.invalid())?;
+    let receive = scties_mvmnt_tp == sese_024::ReceiveDelivery1Code::Rece;
+
+    Ok(sese_024::TransactionDetails148 {
+        acct_ownr: account_owner(settran)?
+            .map(|acct_ownr| convert(&acct_ownr))
+            .transpose()?,
+        sfkpg_acct: settran
+            .take("97A::SAFE")
+            .map(|field| convert(&safekeeping_account(field)))
+            .transpose()?,
+        fin_instrm_id: security(
+            &settran
+                .take_field("35B")
+                .ok_or(Error::MissingField("35B"))?,
+        )?,
+        sttlm_qty: Quantity::parse(&settran.mandatory("36B::SETT")?)?.into(),
+        sttlm_amt: settran
+            .take("19A::SETT")
+            .map(|field| convert(&amount(&field, receive)?))
+            .transpose()?,
+        sttlm_dt: sese_024::SettlementDate19Choice {
+            value: sese_024::SettlementDate19ChoiceEnum {
+                dt: Some(sese_024::DateAndDateTime2Choice {
+                    value: date_time(&settran.mandatory("98a::SETT")?)?,
+                }),
+                ..Default::default()
+            },
+        },
+        trad_dt: settran
+            .take("98a::TRAD")
+            .map(|field| -> Result<_, Error> {
+                Ok(sese_024::TradeDate8Choice {
+                    value: convert(&trade_date(&field)?.value)?,
+                })
+            })
+            .transpose()?,
+        scties_mvmnt_tp,
+        pmt,
+        sttlm_params: sese_024::SettlementDetails202 {
+            scties_tx_tp: sese_024::SecuritiesTransactionType44Choice {
+                value: choice(&settran.mandatory("22F::SETR")?)?,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// Return the `SETTRAN` sequence of the transaction details.
+fn transaction_sequence(
+    tx_dtls: &sese_024::TransactionDetails148,
+    issues: &mut Vec<TranslationIssue>,
+) -> Result<Sequence, Error> {
+    let (redelivery, receive) = match tx_dtls.scties_mvmnt_tp {
+        sese_024::ReceiveDelivery1Code::Rece => ("RECE", true),
+        sese_024::ReceiveDelivery1Code::Deli => ("DELI", false),
+        sese_024::ReceiveDelivery1Code::Unknown => {
+            return Err(Error::MissingElement("TxDtls/SctiesMvmntTp"))
+        }
+    };
+    let payment = match tx_dtls.pmt {
+        sese_024::DeliveryReceiptType2Code::Apmt => "APMT",
+        sese_024::DeliveryReceiptType2Code::Free => "FREE",
+        sese_024::DeliveryReceiptType2Code::Unknown => {
+            return Err(Error::MissingElement("TxDtls/Pmt"))
+        }
+    };
+
+    issues.extend(lost_elements(&[
+        ("TxDtls/SfkpgPlc", tx_dtls.sfkpg_plc.is_some()),
+        ("TxDtls/PlcOfTrad", tx_dtls.plc_of_trad.is_some()),
+        ("TxDtls/PlcOfClr", tx_dtls.plc_of_clr.is_some()),
+        ("TxDtls/PrtlyRlsdQty", tx_dtls.prtly_rlsd_qty.is_some()),
+        ("TxDtls/RcvgSttlmPties", tx_dtls.rcvg_sttlm_pties.is_some()),
+        (
+            "TxDtls/DlvrgSttlmPties",
+            tx_dtls.dlvrg_sttlm_pties.is_some(),
+        ),
+        ("TxDtls/Invstr", tx_dtls.invstr.is_some()),
+        ("TxDtls/QlfdFrgnIntrmy", tx_dtls.qlfd_frgn_intrmy.is_some()),
+    ]));
+
+    let mut settran = Sequence::new("SETTRAN");
+    settran
+        .fields
+        .push(security_field(&convert(&tx_dtls.fin_instrm_id)?, issues));
+    let sttlm_qty = Quantity::from_sese_024(&tx_dtls.sttlm_qty)
+        .ok_or(Error::MissingElement("TxDtls/SttlmQty/Qty"))?;
+    settran.push_generic(sttlm_qty.field("SETT"));
+    if let Some(sttlm_amt) = &tx_dtls.sttlm_amt {
+        settran.push_generic(amount_field("SETT", &convert(sttlm_amt)?, receive));
+    }
+    let acct_ownr = tx_dtls.acct_ownr.as_ref().map(convert).transpose()?;
+    let sfkpg_acct = tx_dtls.sfkpg_acct.as_ref().map(convert).transpose()?;
+    account_fields(
+        &mut settran,
+        acct_ownr.as_ref(),
+        sfkpg_acct.as_ref(),
+        None,
+        issues,
+    );
+    let scties_tx_tp = choice_field(&tx_dtls.sttlm_params.scties_tx_tp.value, "22F", "SETR")?
+        .ok_or(Error::MissingElement("TxDtls/SttlmParams/SctiesTxTp"))?;
+    settran.push_generic(scties_tx_tp);
+    settran.push_generic(GenericField::new("22H", "REDE", redelivery));
+    settran.push_generic(GenericField::new("22H", "PAYM", payment));
+    let sttlm_dt = match &tx_dtls.sttlm_dt.value.dt {
+        Some(dt) => converted_date_time_field("SETT", &dt.value)?,
+        None => None,
+    };
+    settran.push_generic(sttlm_dt.ok_or(Error::MissingElement("TxDtls/SttlmDt/Dt"))?);
+    if let Some(dt) = tx_dtls
+        .trad_dt
+        .as_ref()
+        .and_then(|trad_dt| trad_dt.value.dt.as_ref())
+    {
+        if let Some(field) = converted_date_time_field("TRAD", &dt.value)? {
+            settran.push_generic(field);
+        }
+    }
+
+    Ok(settran)
+}
+
+/// Settlement parameters of the fields `22F` of the `SETDET` sequence.
+#[derive(Debug, Default, Deserialize)]
+struct SettlementParameters {
+    #[serde(rename = "SctiesTxTp", default)]
+    scties_tx_tp: Code,
+    #[serde(rename = "SttlmTxCond", default)]
+    sttlm_tx_cond: Vec<Code>,
+    #[serde(rename = "PrtlSttlmInd")]
+    prtl_sttlm_ind: Option<String>,
+}
+
+/// Reason of a status of an `MT548`, i.e. its code of field `24B` and its narrative of field
+/// `70D::REAS`, of the first `REAS` sequence of its `STAT` sequence.
+#[derive(Debug, Default)]
+struct Reason {
+    code: Option<GenericField>,
+    narrative: Option<String>,
+}
+
+impl Reason {
+    fn take(stat: &mut Sequence) -> Self {
+        let reas = match stat.sequence_mut("REAS") {
+            Some(reas) => reas,
+            None => return Self::default(),
+        };
+
+        let narrative = reas
+            .take("70D::REAS")
+            .map(|field| field.data.lines().collect::<String>());
+        let code = reas
+            .fields
+            .iter()
+            .position(|field| field.tag == "24B")
+            .and_then(|index| GenericField::parse(&reas.fields.remove(index)))
+            .or_else(|| {
+                narrative
+                    .as_ref()
+                    .map(|_| GenericField::new("24B", "", NARRATIVE.0))
+            });
+
+        Self { code, narrative }
+    }
+
+    /// Return the reason code, e.g. of a `RejectionReason44ChoiceEnum`, and the additional
+    /// information of the status, or `None` for a status without reason.
+    fn reason<C: DeserializeOwned>(
+        &self,
+    ) -> Result<Option<(C, Option<sese_024::Max210Text>)>, Error> {
+        let code = match &self.code {
+            Some(code) => code,
+            None => return Ok(None),
+        };
+
+        let cd = if code.issuer.is_none() && code.data == NARRATIVE.0 {
+            choice(&GenericField::new(&code.tag, &code.qualifier, NARRATIVE.1))?
+        } else {
+            choice(code)?
+        };
+        let addtl_rsn_inf = self
+            .narrative
+            .clone()
+            .map(|value| sese_024::Max210Text { value });
+
+        Ok(Some((cd, addtl_rsn_inf)))
+    }
+
+    fn no_specified_reason(&self) -> Option<sese_024::NoReasonCode> {
+        self.code.is_none().then_some(sese_024::NoReasonCode::Nore)
+    }
+}
+
+/// Status and reason of a status choice of a `sese.024.001.12`, e.g. of a
+/// `RejectionStatus39Choice`.
+#[derive(Debug, Default, Deserialize)]
+struct StatusReason {
+    #[serde(rename = "Rsn")]
+    rsn: Option<ReasonCode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ReasonCode {
+    #[serde(rename = "Cd", default)]
+    cd: Code,
+    #[serde(rename = "AddtlRsnInf")]
+    addtl_rsn_inf: Option<String>,
+}
+
+/// Quantity of financial instrument of field `36B`, e.g. `:36B::SETT//UNIT/1000,`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Quantity {
+    Unit(f64),
+    FaceAmount(f64),
+    AmortisedValue(f64),
+}
+
+impl Quantity {
+    fn parse(field: &GenericField) -> Result<Self, Error> {
+        let (tp, qty) = field.data.split_once('/').ok_or_else(|| field.invalid())?;
+        let value = parse_amount(&field.tag, qty)?;
+
+        match tp {
+            "UNIT" => Ok(Quantity::Unit(value)),
+            "FAMT" => Ok(Quantity::FaceAmount(value)),
+            "AMOR" => Ok(Quantity::AmortisedValue(value)),
+            _ => Err(field.invalid()),
+        }
+    }
+
+    fn field(&self, qualifier: &str) -> GenericField {
+        let (tp, value) = match self {
+            Quantity::Unit(value) => ("UNIT", value),
+            Quantity::FaceAmount(value) => ("FAMT", value),
+            Quantity::AmortisedValue(value) => ("AMOR", value),
+        };
+
+        GenericField::new(
+            "36B",
+            qualifier,
+            format!("{}/{}", tp, format_decimal(*value)),
+        )
+    }
+
+    fn from_sese_023(qty: &sese_023::Quantity51Choice) -> Option<Self> {
+        let qty = &qty.value.qty.as_ref()?.value;
+
+        qty.unit
+            .as_ref()
+            .map(|unit| Quantity::Unit(unit.value))
+            .or_else(|| {
+                qty.face_amt
+                    .as_ref()
+                    .map(|face_amt| Quantity::FaceAmount(face_amt.value))
+            })
+            .or_else(|| {
+                qty.amtsd_val
+                    .as_ref()
+                    .map(|amtsd_val| Quantity::AmortisedValue(amtsd_val.value))
+            })
+    }
+
+    fn from_sese_024(qty: &sese_024::Quantity51Choice) -> Option<Self> {
+        let qty = &qty.value.qty.as_ref()?.value;
+
+        qty.unit
+            .as_ref()
+            .map(|unit| Quantity::Unit(unit.value))
+            .or_else(|| {
+                qty.face_amt
+                    .as_ref()
+                    .map(|face_amt| Quantity::FaceAmount(face_amt.value))
+            })
+            .or_else(|| {
+                qty.amtsd_val
+                    .as_ref()
+                    .map(|amtsd_val| Quantity::AmortisedValue(amtsd_val.value))
+            })
+    }
+
+    fn from_sese_025(qty: &sese_025::Quantity51Choice) -> Option<Self> {
+        let qty = &qty.value.qty.as_ref()?.value;
+
+        qty.unit
+            .as_ref()
+            .map(|unit| Quantity::Unit(unit.value))
+            .or_else(|| {
+                qty.face_amt
+                    .as_ref()
+                    .map(|face_amt| Quantity::FaceAmount(face_amt.value))
+            })
+            .or_else(|| {
+                qty.amtsd_val
+                    .as_ref()
+                    .map(|amtsd_val| Quantity::AmortisedValue(amtsd_val.value))
+            })
+    }
+}
+
+impl From<Quantity> for sese_023::Quantity51Choice {
+    fn from(quantity: Quantity) -> Self {
+        let qty = match quantity {
+            Quantity::Unit(value) => sese_023::FinancialInstrumentQuantity33ChoiceEnum {
+                unit: Some(sese_023::DecimalNumber { value }),
+                ..Default::default()
+            },
+            Quantity::FaceAmount(value) => sese_023::FinancialInstrumentQuantity33ChoiceEnum {
+                face_amt: Some(sese_023::ImpliedCurrencyAndAmount { value }),
+                ..Default::default()
+            },
+            Quantity::AmortisedValue(value) => sese_023::FinancialInstrumentQuantity33ChoiceEnum {
+                amtsd_val: Some(sese_023::ImpliedCurrencyAndAmount { value }),
+                ..Default::default()
+            },
+        };
+
+        sese_023::Quantity51Choice {
+            value: sese_023::Quantity51ChoiceEnum {
+                qty: Some(sese_023::FinancialInstrumentQuantity33Choice { value: qty }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<Quantity> for sese_024::Quantity51Choice {
+    fn from(quantity: Quantity) -> Self {
+        let qty = match quantity {
+            Quantity::Unit(value) => sese_024::FinancialInstrumentQuantity33ChoiceEnum {
+                unit: Some(sese_024::DecimalNumber { value }),
+                ..Default::default()
+            },
+            Quantity::FaceAmount(value) => sese_024::FinancialInstrumentQuantity33ChoiceEnum {
+                face_amt: Some(sese_024::ImpliedCurrencyAndAmount { value }),
+                ..Default::default()
+            },
+            Quantity::AmortisedValue(value) => sese_024::FinancialInstrumentQuantity33ChoiceEnum {
+                amtsd_val: Some(sese_024::ImpliedCurrencyAndAmount { value }),
+                ..Default::default()
+            },
+        };
+
+        sese_024::Quantity51Choice {
+            value: sese_024::Quantity51ChoiceEnum {
+                qty: Some(sese_024::FinancialInstrumentQuantity33Choice { value: qty }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<Quantity> for sese_025::Quantity51Choice {
+    fn from(quantity: Quantity) -> Self {
+        let qty = match quantity {
+            Quantity::Unit(value) => sese_025::FinancialInstrumentQuantity33ChoiceEnum {
+                unit: Some(sese_025::DecimalNumber { value }),
+                ..Default::default()
+            },
+            Quantity::FaceAmount(value) => sese_025::FinancialInstrumentQuantity33ChoiceEnum {
+                face_amt: Some(sese_025::ImpliedCurrencyAndAmount { value }),
+                ..Default::default()
+            },
+            Quantity::AmortisedValue(value) => sese_025::FinancialInstrumentQuantity33ChoiceEnum {
+                amtsd_val: Some(sese_025::ImpliedCurrencyAndAmount { value }),
+                ..Default::default()
+            },
+        };
+
+        sese_025::Quantity51Choice {
+            value: sese_025::Quantity51ChoiceEnum {
+                qty: Some(sese_025::FinancialInstrumentQuantity33Choice { value: qty }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Party of field `95a`, i.e. the BIC of option `P`, the proprietary code of option `R`, the
+/// name and address of option `Q` or the country of option `C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Party {
+    Bic(String),
+    Proprietary { id: String, issr: String },
+    NameAndAddress(String),
+    Country(String),
+}
+
+impl Party {
+    fn parse(field: &GenericField) -> Result<Self, Error> {
+        match (field.tag.as_str(), &field.issuer) {
+            ("95P", _) => Ok(Party::Bic(field.data.clone())),
+            ("95R", Some(issr)) => Ok(Party::Proprietary {
+                id: field.data.clone(),
+                issr: issr.clone(),
+            }),
+            ("95Q", _) => Ok(Party::NameAndAddress(field.data.lines().collect())),
+            ("95C", _) => Ok(Party::Country(field.data.clone())),
+            _ => Err(field.invalid()),
+        }
+    }
+
+    fn field(&self, qualifier: &str, issues: &mut Vec<TranslationIssue>) -> GenericField {
+        match self {
+            Party::Bic(bic) => GenericField::new("95P", qualifier, bic),
+            Party::Proprietary { id, issr } => GenericField {
+                issuer: Some(issr.clone()),
+                ..GenericField::new("95R", qualifier, id)
+            },
+            Party::NameAndAddress(nm) => GenericField::new(
+                "95Q",
+                qualifier,
+                narrative(nm, NAME_LINES, "95Q", "Id/NmAndAdr/Nm", issues),
+            ),
+            Party::Country(ctry) => GenericField::new("95C", qualifier, ctry),
+        }
+    }
+
+    /// Return the identification of a settlement party, e.g. of `Pty1`.
+    fn identification(self) -> Option<sese_023::PartyIdentification120Choice> {
+        let value = match self {
+            Party::Bic(value) => sese_023::PartyIdentification120ChoiceEnum {
+                any_bic: Some(sese_023::AnyBicDec2014Identifier { value }),
+                ..Default::default()
+            },
+            Party::Proprietary { id, issr } => sese_023::PartyIdentification120ChoiceEnum {
+                prtry_id: Some(sese_023::GenericIdentification36 {
+                    id: sese_023::Max35Text { value: id },
+                    issr: sese_023::Max35Text { value: issr },
+                    schme_nm: None,
+                }),
+                ..Default::default()
+            },
+            Party::NameAndAddress(value) => sese_023::PartyIdentification120ChoiceEnum {
+                nm_and_adr: Some(sese_023::NameAndAddress5 {
+                    nm: sese_023::Max350Text { value },
+                    adr: None,
+                }),
+                ..Default::default()
+            },
+            Party::Country(_) => return None,
+        };
+
+        Some(sese_023::PartyIdentification120Choice { value })
+    }
+
+    /// Return the identification of a depository, i.e. of the place of settlement.
+    fn depository(self) -> Option<sese_023::PartyIdentification122Choice> {
+        let value = match self {
+            Party::Bic(value) => sese_023::PartyIdentification122ChoiceEnum {
+                any_bic: Some(sese_023::AnyBicDec2014Identifier { value }),
+                ..Default::default()
+            },
+            Party::NameAndAddress(value) => sese_023::PartyIdentification122ChoiceEnum {
+                nm_and_adr: Some(sese_023::NameAndAddress5 {
+                    nm: sese_023::Max350Text { value },
+                    adr: None,
+                }),
+                ..Default::default()
+            },
+            Party::Country(value) => sese_023::PartyIdentification122ChoiceEnum {
+                ctry: Some(sese_023::CountryCode { value }),
+                ..Default::default()
+            },
+            Party::Proprietary { .. } => return None,
+        };
+
+        Some(sese_023::PartyIdentification122Choice { value })
+    }
+
+    /// Return the identification of an account owner.
+    fn owner(self) -> Option<sese_023::PartyIdentification127Choice> {
+        let value = match self {
+            Party::Bic(value) => sese_023::PartyIdentification127ChoiceEnum {
+                any_bic: Some(sese_023::AnyBicDec2014Identifier { value }),
+                ..Default::default()
+            },
+            Party::Proprietary { id, issr } => sese_023::PartyIdentification127ChoiceEnum {
+                prtry_id: Some(sese_023::GenericIdentification36 {
+                    id: sese_023::Max35Text { value: id },
+                    issr: sese_023::Max35Text { value: issr },
+                    schme_nm: None,
+                }),
+                ..Default::default()
+            },
+            _ => return None,
+        };
+
+        Some(sese_023::PartyIdentification127Choice { value })
+    }
+
+    fn from_identification(id: &sese_023::PartyIdentification120Choice) -> Option<Self> {
+        let id = &id.value;
+
+        id.any_bic
+            .as_ref()
+            .map(|any_bic| Party::Bic(any_bic.value.clone()))
+            .or_else(|| id.prtry_id.as_ref().map(Party::from_proprietary))
+            .or_else(|| {
+                id.nm_and_adr
+                    .as_ref()
+                    .map(|nm_and_adr| Party::NameAndAddress(nm_and_adr.nm.value.clone()))
+            })
+    }
+
+    fn from_depository(id: &sese_023::PartyIdentification122Choice) -> Option<Self> {
+        let id = &id.value;
+
+        id.any_bic
+            .as_ref()
+            .map(|any_bic| Party::Bic(any_bic.value.clone()))
+            .or_else(|| {
+                id.nm_and_adr
+                    .as_ref()
+                    .map(|nm_and_adr| Party::NameAndAddress(nm_and_adr.nm.value.clone()))
+            })
+            .or_else(|| {
+                id.ctry
+                    .as_ref()
+                    .map(|ctry| Party::Country(ctry.value.clone()))
+            })
+    }
+
+    fn from_owner(id: &sese_023::PartyIdentification127Choice) -> Option<Self> {
+        let id = &id.value;
+
+        id.any_bic
+            .as_ref()
+            .map(|any_bic| Party::Bic(any_bic.value.clone()))
+            .or_else(|| id.prtry_id.as_ref().map(Party::from_proprietary))
+    }
+
+    fn from_proprietary(prtry_id: &sese_023::GenericIdentification36) -> Self {
+        Party::Proprietary {
+            id: prtry_id.id.value.clone(),
+            issr: prtry_id.issr.value.clone(),
+        }
+    }
+}
+
+/// Return the lines of a narrative, e.g. of field `70E`, truncated to the number of lines.
+fn narrative(
+    text: &str,
+    lines: usize,
+    field: &str,
+    element: &str,
+    issues: &mut Vec<TranslationIssue>,
+) -> String {
+    let text = truncate(text, lines * NARRATIVE_LINE_LENGTH, field, element, issues);
+
+    wrap(&text, NARRATIVE_LINE_LENGTH, NARRATIVE_LINE_LENGTH)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Return the generic field of a reference of field `20C`, e.g. `:20C::SEME//`.
+fn reference(
+    qualifier: &str,
+    value: &str,
+    element: &str,
+    issues: &mut Vec<TranslationIssue>,
+) -> GenericField {
+    GenericField::new(
+        "20C",
+        qualifier,
+        truncate(value, REFERENCE_LENGTH, "20C", element, issues),
+    )
+}
+
+/// Return the field `98A` of a date, or `98C` of a date and time.
+fn date_time_field(
+    qualifier: &str,
+    dt: &sese_023::DateAndDateTime2ChoiceEnum,
+) -> Option<GenericField> {
+    dt.dt
+        .as_ref()
+        .map(|dt| GenericField::new("98A", qualifier, dt.value.format("%Y%m%d").to_string()))
+        .or_else(|| {
+            dt.dt_tm.as_ref().map(|dt_tm| {
+                GenericField::new(
+                    "98C",
+                    qualifier,
+                    dt_tm.value.format("%Y%m%d%H%M%S").to_string(),
+                )
+            })
+        })
+}
+
+/// Return the field `98a` of a date choice of another message, e.g. of a
+/// `sese.025.001.11`.
+fn converted_date_time_field<T: Serialize>(
+    qualifier: &str,
+    dt: &T,
+) -> Result<Option<GenericField>, Error> {
+    let dt: sese_023::DateAndDateTime2ChoiceEnum = convert(dt)?;
+
+    Ok(date_time_field(qualifier, &dt))
+}
+
+/// Parse the trade date of field `98a::TRAD`.
+fn trade_date(field: &GenericField) -> Result<sese_023::TradeDate8Choice, Error> {
+    Ok(sese_023::TradeDate8Choice {
+        value: sese_023::TradeDate8ChoiceEnum {
+            dt: Some(sese_023::DateAndDateTime2Choice {
+                value: date_time(field)?,
+            }),
+            ..Default::default()
+        },
+    })
+}
+
+/// Return the field `35B` of a financial instrument.
+fn security_field(
+    fin_instrm_id: &sese_023::SecurityIdentification19,
+    issues: &mut Vec<TranslationIssue>,
+) -> Field {
+    let mut lines = vec![];
+
+    if let Some(isin) = &fin_instrm_id.isin {
+        lines.push(format!("ISIN {}", isin.value));
+    }
+    for othr_id in fin_instrm_id.othr_id.iter() {
+        let tp = &othr_id.tp.value;
+        let tp = tp
+            .prtry
+            .as_ref()
+            .map(|prtry| prtry.value.as_str())
+            .or_else(|| tp.cd.as_ref().map(|cd| cd.value.as_str()))
+            .unwrap_or_default();
+        lines.push(format!("/{}/{}", tp, othr_id.id.value));
+    }
+    if let Some(desc) = &fin_instrm_id.desc {
+        lines.push(narrative(
+            &desc.value,
+            DESCRIPTION_LINES,
+            "35B",
+            "FinInstrmId/Desc",
+            issues,
+        ));
+    }
+
+    Field::new("35B", lines.join("\n"))
+}
+
+/// Parse the account owner of field `95a::ACOW`.
+fn account_owner(
+    sequence: &mut Sequence,
+) -> Result<Option<sese_023::PartyIdentification144>, Error> {
+    let field = match sequence.take("95a::ACOW") {
+        Some(field) => field,
+        None => return Ok(None),
+    };
+
+    let id = Party::parse(&field)?
+        .owner()
+        .ok_or_else(|| field.invalid())?;
+
+    Ok(Some(sese_023::PartyIdentification144 { id, lei: None }))
+}
+
+fn safekeeping_account(field: GenericField) -> sese_023::SecuritiesAccount19 {
+    sese_023::SecuritiesAccount19 {
+        id: sese_023::Max35Text { value: field.data },
+        ..Default::default()
+    }
+}
+
+/// Push the fields of the account owner `95a::ACOW`, the safekeeping account `97A::SAFE` and
+/// the cash account `97a::CASH`.
+fn account_fields(
+    sequence: &mut Sequence,
+    acct_ownr: Option<&sese_023::PartyIdentification144>,
+    sfkpg_acct: Option<&sese_023::SecuritiesAccount19>,
+    csh_acct: Option<&sese_023::CashAccountIdentification5ChoiceEnum>,
+    issues: &mut Vec<TranslationIssue>,
+) {
+    if let Some(acct_ownr) = acct_ownr {
+        match Party::from_owner(&acct_ownr.id) {
+            Some(party) => sequence.push_generic(party.field("ACOW", issues)),
+            None => issues.push(lost("QtyAndAcctDtls/AcctOwnr")),
+        }
+    }
+    if let Some(sfkpg_acct) = sfkpg_acct {
+        sequence.push_generic(GenericField::new("97A", "SAFE", &sfkpg_acct.id.value));
+    }
+    if let Some(csh_acct) = csh_acct {
+        let iban = csh_acct
+            .iban
+            .as_ref()
+            .map(|iban| GenericField::new("97E", "CASH", &iban.value));
+        let prtry = csh_acct
+            .prtry
+            .as_ref()
+            .map(|prtry| GenericField::new("97A", "CASH", &prtry.value));
+        if let Some(field) = iban.or(prtry) {
+            sequence.push_generic(field);
+        }
+    }
+}
+
+/// Parse the amount of field `19A`, e.g. `EUR1234,56`, a debit for a receipt against payment,
+/// and a credit for a delivery against payment, unless negative, e.g. `NEUR1234,56`.
+fn amount(field: &GenericField, receive: bool) -> Result<sese_023::AmountAndDirection94, Error> {
+    let captures = AMOUNT
+        .captures(&field.data)
+        .ok_or_else(|| field.invalid())?;
+    let cdt_dbt_ind = if receive == captures.get(1).is_some() {
+        sese_023::CreditDebitCode::Crdt
+    } else {
+        sese_023::CreditDebitCode::Dbit
+    };
+
+    Ok(sese_023::AmountAndDirection94 {
+        amt: sese_023::ActiveCurrencyAndAmount {
+            value: sese_023::ActiveCurrencyAndAmountSimpleType {
+                value: parse_amount(&field.tag, &captures[3])?,
+            },
+            ccy: sese_023::ActiveCurrencyCode {
+                value: captures[2].to_string(),
+            },
+        },
+        cdt_dbt_ind,
+        ..Default::default()
+    })
+}
+
+/// Return the field `19A` of an amount.
+fn amount_field(
+    qualifier: &str,
+    amount: &sese_023::AmountAndDirection94,
+    receive: bool,
+) -> GenericField {
+    let credit = amount.cdt_dbt_ind == sese_023::CreditDebitCode::Crdt;
+    let sign = if credit == receive { "N" } else { "" };
+
+    GenericField::new(
+        "19A",
+        qualifier,
+        format!(
+            "{}{}{}",
+            sign,
+            amount.amt.ccy.value,
+            format_amount(amount.amt.value.value, &amount.amt.ccy.value)
+        ),
+    )
+}
+
+/// Parse the amount of the first `AMT` sequence with the field, e.g. `19A::SETT`.
+fn settlement_amount(
+    setdet: &mut Sequence,
+    field: &str,
+    receive: bool,
+) -> Result<Option<sese_023::AmountAndDirection94>, Error> {
+    setdet
+        .sequences_mut("AMT")
+        .find_map(|amt| amt.take(field))
+        .map(|field| amount(&field, receive))
+        .transpose()
+}
+
+/// Return the `AMT` sequence of an amount.
+fn amount_sequence(
+    qualifier: &str,
+    amount: &sese_023::AmountAndDirection94,
+    receive: bool,
+) -> Sequence {
+    let mut amt = Sequence::new("AMT");
+    amt.push_generic(amount_field(qualifier, amount, receive));
+
+    amt
+}
+
+/// Split the settlement transaction conditions of the fields `22F::STCO` into the partial
+/// settlement indicator, e.g. `NPAR`, and the contents of the other conditions.
+fn settlement_conditions<P, C>(setdet: &mut Sequence) -> Result<(Option<P>, Vec<C>), Error>
+where
+    P: DeserializeOwned,
+    C: DeserializeOwned,
+{
+    let mut prtl_sttlm_ind = None;
+    let mut sttlm_tx_cond = vec![];
+
+    for field in setdet.take_all("22F::STCO") {
+        match (&field.issuer, parse_code::<P>(&field.data)) {
+            (None, Some(code)) => prtl_sttlm_ind = Some(code),
+            _ => sttlm_tx_cond.push(choice(&field)?),
+        }
+    }
+
+    Ok((prtl_sttlm_ind, sttlm_tx_cond))
+}
+
+/// Push the fields `22F::SETR` and `22F::STCO` of the settlement details of a
+/// `sese.023.001.11` or `sese.025.001.11`.
+fn settlement_parameter_fields<T: Serialize>(
+    setdet: &mut Sequence,
+    sttlm_params: &T,
+) -> Result<(), Error> {
+    let params: SettlementParameters = convert(sttlm_params)?;
+
+    let scties_tx_tp = params
+        .scties_tx_tp
+        .field("22F", "SETR")
+        .ok_or(Error::MissingElement("SttlmParams/SctiesTxTp"))?;
+    setdet.push_generic(scties_tx_tp);
+    if let Some(prtl_sttlm_ind) = params.prtl_sttlm_ind {
+        setdet.push_generic(GenericField::new("22F", "STCO", prtl_sttlm_ind));
+    }
+    for sttlm_tx_cond in params.sttlm_tx_cond {
+        if let Some(field) = sttlm_tx_cond.field("22F", "STCO") {
+            setdet.push_generic(field);
+        }
+    }
+
+    Ok(())
+}
+
+/// Return the settlement parties `Pty1` to `Pty5`, in the order of `PARTIES`.
+fn levels(
+    parties: &mut sese_023::SettlementParties100,
+) -> [&mut Option<sese_023::PartyIdentificationAndAccount196>; 5] {
+    [
+        &mut parties.pty_1,
+        &mut parties.pty_2,
+        &mut parties.pty_3,
+        &mut parties.pty_4,
+        &mut parties.pty_5,
+    ]
+}
+
+/// Parse the `SETPRTY` sequences into the delivering and the receiving settlement parties, the
+/// place of settlement `PSET` being the depository of the counterparty, i.e. of the delivering
+/// settlement parties of a receipt.
+fn settlement_parties(
+    setdet: &mut Sequence,
+    receive: bool,
+) -> Result<
+    (
+        Option<sese_023::SettlementParties100>,
+        Option<sese_023::SettlementParties100>,
+    ),
+    Error,
+> {
+    let mut dlvrg = sese_023::SettlementParties100::default();
+    let mut rcvg = sese_023::SettlementParties100::default();
+
+    for setprty in setdet.sequences_mut("SETPRTY") {
+        let qualifier = match setprty
+            .fields
+            .iter()
+            .filter_map(GenericField::parse)
+            .find(|field| field.number() == "95")
+        {
+            Some(field) => field.qualifier,
+            None => continue,
+        };
+
+        if qualifier == PLACE_OF_SETTLEMENT {
+            let field = setprty.mandatory("95a::PSET")?;
+            let id = Party::parse(&field)?
+                .depository()
+                .ok_or_else(|| field.invalid())?;
+            let parties = if receive { &mut dlvrg } else { &mut rcvg };
+            parties.dpstry = Some(sese_023::PartyIdentification146 {
+                id,
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let level = match PARTIES
+            .iter()
+            .position(|(delivering, receiving)| qualifier == *delivering || qualifier == *receiving)
+        {
+            Some(level) => level,
+            None => continue,
+        };
+        let field = setprty
+            .take(&format!("95a::{}", qualifier))
+            .ok_or(Error::MissingField("95a"))?;
+        let id = Party::parse(&field)?
+            .identification()
+            .ok_or_else(|| field.invalid())?;
+        let party = sese_023::PartyIdentificationAndAccount196 {
+            id,
+            sfkpg_acct: setprty.take("97A::SAFE").map(safekeeping_account),
+            prcg_id: setprty
+                .take("20C::PROC")
+                .map(|field| sese_023::Max35Text { value: field.data }),
+            ..Default::default()
+        };
+
+        let parties = if qualifier == PARTIES[level].0 {
+            &mut dlvrg
+        } else {
+            &mut rcvg
+        };
+        *levels(parties)[level] = Some(party);
+    }
+
+    let empty = sese_023::SettlementParties100::default();
+
+    Ok((
+        (dlvrg != empty).then_some(dlvrg),
+        (rcvg != empty).then_some(rcvg),
+    ))
+}
+
+/// Return the `SETPRTY` sequences of the delivering settlement parties, then of the receiving
+/// settlement parties, from `Pty1` to `Pty5`, and of the place of settlement.
+fn settlement_party_sequences(
+    dlvrg: Option<sese_023::SettlementParties100>,
+    rcvg: Option<sese_023::SettlementParties100>,
+    issues: &mut Vec<TranslationIssue>,
+) -> Vec<Sequence> {
+    let mut sequences = vec![];
+    let mut dpstry = vec![];
+
+    for (element, mut parties, delivering) in [
+        ("DlvrgSttlmPties", dlvrg, true),
+        ("RcvgSttlmPties", rcvg, false),
+    ]
+    .into_iter()
+    .filter_map(|(element, parties, delivering)| Some((element, parties?, delivering)))
+    {
+        if let Some(id) = parties.dpstry.take() {
+            dpstry.push((element, id));
+        }
+
+        for (level, party) in levels(&mut parties).into_iter().enumerate() {
+            let party = match party.take() {
+                Some(party) => party,
+                None => continue,
+            };
+            let qualifier = if delivering {
+                PARTIES[level].0
+            } else {
+                PARTIES[level].1
+            };
+            let element = format!("{}/Pty{}", element, level + 1);
+
+            let mut setprty = Sequence::new("SETPRTY");
+            match Party::from_identification(&party.id) {
+                Some(id) => setprty.push_generic(id.field(qualifier, issues)),
+                None => {
+                    issues.push(lost(&element));
+                    continue;
+                }
+            }
+            if let Some(sfkpg_acct) = &party.sfkpg_acct {
+                setprty.push_generic(GenericField::new("97A", "SAFE", &sfkpg_acct.id.value));
+            }
+            if let Some(prcg_id) = &party.prcg_id {
+                setprty.push_generic(reference(
+                    "PROC",
+                    &prcg_id.value,
+                    &format!("{}/PrcgId", element),
+                    issues,
+                ));
+            }
+            sequences.push(setprty);
+        }
+    }
+
+    let mut dpstry = dpstry.into_iter();
+    if let Some((element, id)) = dpstry.next() {
+        match Party::from_depository(&id.id) {
+            Some(party) => {
+                let mut setprty = Sequence::new("SETPRTY");
+                setprty.push_generic(party.field(PLACE_OF_SETTLEMENT, issues));
+                sequences.push(setprty);
+            }
+            None => issues.push(lost(&format!("{}/Dpstry", element))),
+        }
+    }
+    for (element, _) in dpstry {
+        issues.push(lost(&format!("{}/Dpstry", element)));
+    }
+
+    sequences
+}
+
+/// Report the elements present in the ISO 20022 message without place in the MT message.
+fn lost_elements(elements: &[(&str, bool)]) -> Vec<TranslationIssue> {
+    elements
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(element, _)| lost(element))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    fn header() -> FinHeader {
+        FinHeader {
+            sender: "AAAADEFFXXX".to_string(),
+            receiver: "BBBBCHZZXXX".to_string(),
+            uetr: None,
+        }
+    }
+
+    fn block4(lines: &[&str]) -> Block4 {
+        lines.join("\n").parse().unwrap()
+    }
+
+    #[test]
+    fn test_mt541_round_trip() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::SEME//INSTR-2304-01",
+            ":23G:NEWM",
+            ":16R:LINK",
+            ":22F::LINK//AFTE",
+            ":13A::LINK//540",
+            ":20C::PREV//INSTR-2303-99",
+            ":16S:LINK",
+            ":16R:LINK",
+            ":20C::COMM//TRADE-778",
+            ":16S:LINK",
+            ":16S:GENL",
+            ":16R:TRADDET",
+            ":98A::SETT//20230405",
+            ":98A::TRAD//20230403",
+            ":35B:ISIN US0378331005",
+            "APPLE INC",
+            ":70E::SPRO//SETTLE BEFORE NOON",
+            ":16S:TRADDET",
+            ":16R:FIAC",
+            ":36B::SETT//UNIT/1000,",
+            ":95P::ACOW//AAAADEFFXXX",
+            ":97A::SAFE//111222333",
+            ":97A::CASH//444555666",
+            ":16S:FIAC",
+            ":16R:SETDET",
+            ":22F::SETR//TRAD",
+            ":22F::STCO//NPAR",
+            ":22F::STCO/ABCD/XYZW",
+            ":16R:SETPRTY",
+            ":95P::DEAG//CCCCGB2LXXX",
+            ":97A::SAFE//777888",
+            ":16S:SETPRTY",
+            ":16R:SETPRTY",
+            ":95R::SELL/ABCD/SELLER-1",
+            ":16S:SETPRTY",
+            ":16R:SETPRTY",
+            ":95P::REAG//DDDDFRPPXXX",
+            ":20C::PROC//PROC-1",
+            ":16S:SETPRTY",
+            ":16R:SETPRTY",
+            ":95P::PSET//DAKVDEFFXXX",
+            ":16S:SETPRTY",
+            ":16R:AMT",
+            ":19A::SETT//EUR25000,",
+            ":16S:AMT",
+            ":16S:SETDET",
+        ]);
+        let translation = mt540_to_sese_023::<Dmkr>(MessageType::Mt541, &block4).unwrap();
+        assert_eq!(translation.issues, vec![]);
+
+        let instr = &translation.document.scties_sttlm_tx_instr;
+        assert_eq!(instr.tx_id.value, "INSTR-2304-01");
+        let params = &instr.sttlm_tp_and_addtl_params;
+        assert_eq!(params.scties_mvmnt_tp, sese_023::ReceiveDelivery1Code::Rece);
+        assert_eq!(params.pmt, sese_023::DeliveryReceiptType2Code::Apmt);
+        assert_eq!(params.cmon_id.as_ref().unwrap().value, "TRADE-778");
+        let r#ref = &instr.lnkgs[0].r#ref.value;
+        assert_eq!(
+            r#ref.scties_sttlm_tx_id.as_ref().unwrap().value,
+            "INSTR-2303-99"
+        );
+        assert_eq!(
+            instr.fin_instrm_id.isin.as_ref().unwrap().value,
+            "US0378331005"
+        );
+        assert_eq!(
+            Quantity::from_sese_023(&instr.qty_and_acct_dtls.sttlm_qty),
+            Some(Quantity::Unit(1000.))
+        );
+        assert_eq!(
+            instr.sttlm_params.prtl_sttlm_ind,
+            Some(sese_023::SettlementTransactionCondition5Code::Npar)
+        );
+        let dlvrg = instr.dlvrg_sttlm_pties.as_ref().unwrap();
+        assert!(dlvrg.dpstry.is_some());
+        assert_eq!(
+            dlvrg
+                .pty_1
+                .as_ref()
+                .unwrap()
+                .sfkpg_acct
+                .as_ref()
+                .unwrap()
+                .id
+                .value,
+            "777888"
+        );
+        assert!(dlvrg.pty_5.is_some());
+        let amount = instr.sttlm_amt.as_ref().unwrap();
+        assert_eq!(amount.amt.value.value, 25000.);
+        assert_eq!(amount.cdt_dbt_ind, sese_023::CreditDebitCode::Dbit);
+
+        let mt = sese_023_to_mt540(&header(), &translation.document).unwrap();
+        assert_eq!(mt.message_type, MessageType::Mt541);
+        assert_eq!(mt.issues, vec![]);
+        assert_eq!(mt.block4, block4);
+    }
+
+    #[test]
+    fn test_unmapped() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::SEME//INSTR-1",
+            ":23G:NEWM",
+            ":16S:GENL",
+            ":16R:TRADDET",
+            ":98A::SETT//20230405",
+            ":35B:ISIN US0378331005",
+            ":16S:TRADDET",
+            ":16R:FIAC",
+            ":36B::SETT//FAMT/50000,",
+            ":16S:FIAC",
+            ":16R:SETDET",
+            ":22F::SETR//TRAD",
+            ":22F::RTGS//YRTG",
+            ":16S:SETDET",
+            ":16R:OTHRPRTY",
+            ":95P::INVE//EEEEDEFFXXX",
+            ":16S:OTHRPRTY",
+        ]);
+        let translation = mt540_to_sese_023::<Dmkr>(MessageType::Mt540, &block4).unwrap();
+        assert_eq!(
+            translation.issues,
+            vec![
+                TranslationIssue::Unmapped {
+                    field: "SETDET/:22F::RTGS//YRTG".to_string()
+                },
+                TranslationIssue::Unmapped {
+                    field: "OTHRPRTY/:95P::INVE//EEEEDEFFXXX".to_string()
+                },
+            ]
+        );
+
+        assert!(matches!(
+            mt540_to_sese_023::<Dmkr>(MessageType::Mt548, &block4),
+            Err(Error::UnsupportedMessageType(MessageType::Mt548))
+        ));
+    }
+
+    #[test]
+    fn test_missing_sequence() {
+        assert!(matches!(
+            mt540_to_sese_023::<Dmkr>(MessageType::Mt540, &block4(&[":20C::SEME//X"])),
+            Err(Error::MissingField("16R:GENL"))
+        ));
+    }
+
+    #[test]
+    fn test_mt545_round_trip() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::SEME//CONF-2304-01",
+            ":23G:NEWM",
+            ":16R:LINK",
+            ":20C::RELA//INSTR-2304-01",
+            ":16S:LINK",
+            ":16S:GENL",
+            ":16R:TRADDET",
+            ":98A::SETT//20230405",
+            ":98C::ESET//20230405101500",
+            ":35B:ISIN US0378331005",
+            ":16S:TRADDET",
+            ":16R:FIAC",
+            ":36B::ESTT//UNIT/1000,",
+            ":97A::SAFE//111222333",
+            ":16S:FIAC",
+            ":16R:SETDET",
+            ":22F::SETR//TRAD",
+            ":16R:SETPRTY",
+            ":95P::PSET//DAKVDEFFXXX",
+            ":16S:SETPRTY",
+            ":16R:AMT",
+            ":19A::ESTT//EUR25000,",
+            ":16S:AMT",
+            ":16S:SETDET",
+        ]);
+        let translation = mt544_to_sese_025::<Dmkr>(MessageType::Mt545, &block4).unwrap();
+        assert_eq!(translation.issues, vec![]);
+
+        let conf = &translation.document.scties_sttlm_tx_conf;
+        assert_eq!(conf.tx_id_dtls.acct_ownr_tx_id.value, "INSTR-2304-01");
+        assert_eq!(
+            conf.tx_id_dtls.acct_svcr_tx_id.as_ref().unwrap().value,
+            "CONF-2304-01"
+        );
+        assert!(conf.trad_dtls.fctv_sttlm_dt.value.dt.is_some());
+        assert_eq!(
+            Quantity::from_sese_025(&conf.qty_and_acct_dtls.sttld_qty),
+            Some(Quantity::Unit(1000.))
+        );
+        assert!(conf.dlvrg_sttlm_pties.as_ref().unwrap().dpstry.is_some());
+
+        let mt = sese_025_to_mt544(&header(), &translation.document).unwrap();
+        assert_eq!(mt.message_type, MessageType::Mt545);
+        assert_eq!(mt.issues, vec![]);
+        assert_eq!(mt.block4, block4);
+    }
+
+    #[test]
+    fn test_mt548_round_trip() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::SEME//STAT-2304-01",
+            ":23G:INST",
+            ":16R:LINK",
+            ":20C::RELA//INSTR-2304-01",
+            ":16S:LINK",
+            ":16R:STAT",
+            ":25D::IPRC//REJT",
+            ":16R:REAS",
+            ":24B::REJT//SAFE",
+            ":70D::REAS//SAFEKEEPING ACCOUNT UNKNOWN",
+            ":16S:REAS",
+            ":16S:STAT",
+            ":16R:STAT",
+            ":25D::MTCH//NMAT",
+            ":16S:STAT",
+            ":16S:GENL",
+            ":16R:SETTRAN",
+            ":35B:ISIN US0378331005",
+            ":36B::SETT//UNIT/1000,",
+            ":19A::SETT//EUR25000,",
+            ":97A::SAFE//111222333",
+            ":22F::SETR//TRAD",
+            ":22H::REDE//RECE",
+            ":22H::PAYM//APMT",
+            ":98A::SETT//20230405",
+            ":98A::TRAD//20230403",
+            ":16S:SETTRAN",
+        ]);
+        let translation = mt548_to_sese_024::<Dmkr>(&block4).unwrap();
+        assert_eq!(translation.issues, vec![]);
+
+        let advice = &translation.document.scties_sttlm_tx_sts_advc;
+        assert_eq!(advice.tx_id.acct_ownr_tx_id.value, "INSTR-2304-01");
+        let rjctd = &advice.prcg_sts.as_ref().unwrap().value.rjctd;
+        let rsn = rjctd.as_ref().unwrap().value.rsn.as_ref().unwrap();
+        assert_eq!(rsn.cd.value.cd, Some(sese_024::RejectionReason75Code::Safe));
+        assert_eq!(
+            rsn.addtl_rsn_inf.as_ref().unwrap().value,
+            "SAFEKEEPING ACCOUNT UNKNOWN"
+        );
+        let umtchd = &advice.mtchg_sts.as_ref().unwrap().value.umtchd;
+        assert_eq!(
+            umtchd.as_ref().unwrap().value.no_spcfd_rsn,
+            Some(sese_024::NoReasonCode::Nore)
+        );
+        assert!(advice.sttlm_sts.is_none());
+        let tx_dtls = advice.tx_dtls.as_ref().unwrap();
+        assert_eq!(
+            tx_dtls.scties_mvmnt_tp,
+            sese_024::ReceiveDelivery1Code::Rece
+        );
+        assert_eq!(tx_dtls.pmt, sese_024::DeliveryReceiptType2Code::Apmt);
+
+        let mt = sese_024_to_mt548(&header(), &translation.document).unwrap();
+        assert_eq!(mt.message_type, MessageType::Mt548);
+        assert_eq!(mt.issues, vec![]);
+        assert_eq!(mt.block4, block4);
+    }
+
+    #[test]
+    fn test_mt548_unmapped_status() {
+        let block4 = block4(&[
+            ":16R:GENL",
+            ":20C::SEME//STAT-1",
+            ":23G:INST",
+            ":16R:STAT",
+            ":25D::CPRC//DEND",
+            ":16S:STAT",
+            ":16S:GENL",
+        ]);
+        let translation = mt548_to_sese_024::<Dmkr>(&block4).unwrap();
+
+        let advice = &translation.document.scties_sttlm_tx_sts_advc;
+        assert_eq!(advice.tx_id.acct_ownr_tx_id.value, NONREF);
+        assert!(advice.prcg_sts.is_none());
+        assert_eq!(
+            translation.issues,
+            vec![TranslationIssue::Unmapped {
+                field: "GENL/STAT/:25D::CPRC//DEND".to_string()
+            }]
+        );
+    }
+}
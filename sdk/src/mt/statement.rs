@@ -0,0 +1,1686 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Statement Translation
+//!
+//! Translates the `MT940` customer statement and the `MT950` statement message into a
+//! `camt.053.001.10` bank to customer statement, the `MT942` interim transaction report into a
+//! `camt.052.001.10` account report, and each statement of a `camt.053.001.10` back into an
+//! `MT940` or `MT950`.
+//!
+//! | MT940 / MT942       | camt.053.001.10 / camt.052.001.10                          |
+//! |---------------------|------------------------------------------------------------|
+//! | `20`                | `GrpHdr/MsgId`, `Id`                                       |
+//! | `25`, `25P`         | `Acct/Id`, `Acct/Svcr`                                     |
+//! | `28C`               | `ElctrncSeqNb`, `StmtPgntn`                                |
+//! | `13D`               | `CreDtTm`                                                  |
+//! | `60F`, `60M`        | `Bal` of type `OPBD`, `PRCD`                               |
+//! | `61`                | `Ntry`, `BkTxCd/Prtry` and `BkTxCd/Domn` of the type code  |
+//! | `86` of a `61`      | `NtryDtls/TxDtls`, see `InformationLayout`                 |
+//! | `62F`, `62M`        | `Bal` of type `CLBD`, `ITBD`                               |
+//! | `64`, `65`          | `Bal` of type `CLAV`, `FWAV`                               |
+//! | `86`                | `AddtlStmtInf`                                             |
+//! | `90D`, `90C`        | `TxsSummry/TtlDbtNtries`, `TxsSummry/TtlCdtNtries`         |
+//!
+//! The layout of the information to account owner of field `86` is bank-specific, and is
+//! passed as an `InformationLayout`, e.g. `Unstructured`, `GermanSubfields` or `SlashCodes`.
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{de::DeserializeOwned, Serialize};
+
+use iso_20022_camt::camt_052_001_10 as camt_052;
+use iso_20022_camt::camt_053_001_10 as camt_053;
+
+use super::{
+    convert, format_amount, invalid_field, lost, parse_amount, truncate, truncated, wrap,
+    wrap_words, Block4, Error, Field, FinHeader, MessageType, MtTranslation, TranslationIssue,
+    IBAN,
+};
+
+/// Maximum number of lines of field `86`.
+const INFORMATION_LINES: usize = 6;
+
+/// Maximum length of a line of field `86`.
+const INFORMATION_LINE_LENGTH: usize = 65;
+
+/// Reference for the account owner of field `61` of an entry without one.
+const NONREF: &str = "NONREF";
+
+/// Issuer of the transaction type codes of field `61`, as proprietary bank transaction codes.
+const SWIFT: &str = "SWIFT";
+
+/// Entry status of booked entries.
+const BOOKED: &str = "BOOK";
+
+/// Balance types of the balance fields, in the order of the fields.
+const BALANCE_TYPES: [(&str, &str); 6] = [
+    ("60F", "OPBD"),
+    ("60M", "PRCD"),
+    ("62F", "CLBD"),
+    ("62M", "ITBD"),
+    ("64", "CLAV"),
+    ("65", "FWAV"),
+];
+
+/// Bank transaction codes `Domain/Family/SubFamily` of the transaction type codes of field
+/// `61`, of credits and of debits.
+const TRANSACTION_TYPES: [(&str, [&str; 3], [&str; 3]); 7] = [
+    ("TRF", ["PMNT", "RCDT", "OTHR"], ["PMNT", "ICDT", "OTHR"]),
+    ("STO", ["PMNT", "RCDT", "STDO"], ["PMNT", "ICDT", "STDO"]),
+    ("CHK", ["PMNT", "RCHQ", "OTHR"], ["PMNT", "ICHQ", "OTHR"]),
+    ("DDT", ["PMNT", "RDDT", "OTHR"], ["PMNT", "IDDT", "OTHR"]),
+    ("CHG", ["ACMT", "MDOP", "CHRG"], ["ACMT", "MDOP", "CHRG"]),
+    ("COM", ["ACMT", "MDOP", "COMM"], ["ACMT", "MDOP", "COMM"]),
+    ("INT", ["ACMT", "MDOP", "INTR"], ["ACMT", "MDOP", "INTR"]),
+];
+
+/// Bank transaction code of the transaction type codes without mapping.
+const NOT_AVAILABLE: [&str; 3] = ["XTND", "NTAV", "NTAV"];
+
+/// Sub-family of the bank transaction codes mapped by their family only.
+const OTHER: &str = "OTHR";
+
+/// Transaction type code of an entry without a mapped bank transaction code.
+const MISCELLANEOUS: &str = "NMSC";
+
+/// Business transaction code of the German layout of unstructured information.
+const UNSTRUCTURED_GVC: &str = "999";
+
+/// Maximum length of a subfield of the German layout.
+const SUBFIELD_LENGTH: usize = 27;
+
+/// Purpose subfields of the German layout.
+const PURPOSE_SUBFIELDS: [&str; 14] = [
+    "20", "21", "22", "23", "24", "25", "26", "27", "28", "29", "60", "61", "62", "63",
+];
+
+lazy_static! {
+    static ref BALANCE: Regex =
+        Regex::new(r"^([CD])(\d{6})([A-Z]{3})(\d{1,14},\d{0,2})$").unwrap();
+    static ref STATEMENT_LINE: Regex = Regex::new(
+        r"^(\d{6})(\d{4})?(R?[CD])([A-Z])?(\d{1,14},\d{0,2})([NSF][A-Z0-9]{3})(.{1,16}?)(?://(.{1,16}))?(?:\n(.{1,34}))?$"
+    )
+    .unwrap();
+    static ref STATEMENT_NUMBER: Regex = Regex::new(r"^(\d{1,5})(?:/(\d{1,5}))?$").unwrap();
+    static ref FLOOR_LIMIT: Regex = Regex::new(r"^([A-Z]{3})[CD]?(\d{1,14},\d{0,2})$").unwrap();
+    static ref NUMBER_AND_SUM: Regex = Regex::new(r"^(\d{1,5})([A-Z]{3})(\d{1,14},\d{0,2})$").unwrap();
+    static ref DATE_TIME_INDICATION: Regex = Regex::new(r"^\d{10}[+-]\d{4}$").unwrap();
+    static ref SEPA_KEYWORD: Regex =
+        Regex::new(r"(EREF|KREF|MREF|CRED|DEBT|SVWZ|PURP|ABWA|ABWE)\+").unwrap();
+    static ref SLASH_CODE: Regex =
+        Regex::new(r"/(TRCD|EREF|MARF|PURP|NAME|IBAN|BIC|REMI|ADDI)/").unwrap();
+}
+
+/// Information to account owner of field `86` of an entry.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InformationToAccountOwner {
+    /// Bank-specific business transaction code, e.g. `166` of the German layout
+    pub gvc: Option<String>,
+    /// End-to-end identification
+    pub end_to_end_id: Option<String>,
+    /// Mandate identification of a direct debit
+    pub mndt_id: Option<String>,
+    /// Purpose code, e.g. `SALA`
+    pub purp: Option<String>,
+    /// Unstructured remittance information
+    pub rmt_inf: Option<String>,
+    /// Name of the counterparty, i.e. the debtor of a credit and the creditor of a debit
+    pub ctr_pty_nm: Option<String>,
+    /// Account of the counterparty
+    pub ctr_pty_acct: Option<String>,
+    /// BIC of the agent of the counterparty
+    pub ctr_pty_agt: Option<String>,
+    /// Additional information, e.g. the posting text
+    pub addtl_inf: Option<String>,
+}
+
+/// Bank-specific layout of the information to account owner of field `86`.
+///
+/// The lines of field `86` are wrapped at 65 characters, so the layout joins the lines into
+/// one value to parse, and wraps the rendered value into lines.
+pub trait InformationLayout {
+    /// Join the lines of field `86`, by default with a space as the lines are wrapped at word
+    /// boundaries.
+    fn join(&self, lines: &[&str]) -> String {
+        lines.join(" ")
+    }
+
+    /// Wrap the value of field `86` into lines of 65 characters, by default at word
+    /// boundaries.
+    fn wrap(&self, value: &str) -> Vec<String> {
+        wrap_words(value, INFORMATION_LINE_LENGTH)
+    }
+
+    /// Parse the value of field `86`.
+    fn parse(&self, value: &str) -> InformationToAccountOwner;
+
+    /// Render the value of field `86`, reporting the information truncated to fit the layout.
+    fn render(
+        &self,
+        information: &InformationToAccountOwner,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> String;
+}
+
+/// Unstructured information, i.e. the remittance information.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Unstructured;
+
+impl InformationLayout for Unstructured {
+    fn parse(&self, value: &str) -> InformationToAccountOwner {
+        InformationToAccountOwner {
+            rmt_inf: non_empty(value),
+            ..Default::default()
+        }
+    }
+
+    fn render(
+        &self,
+        information: &InformationToAccountOwner,
+        _issues: &mut Vec<TranslationIssue>,
+    ) -> String {
+        information
+            .rmt_inf
+            .clone()
+            .or_else(|| information.addtl_inf.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Layout of the German banking industry, i.e. the business transaction code followed by the
+/// subfields `?00` posting text, `?20` to `?29` and `?60` to `?63` purpose, with the SEPA
+/// keywords `EREF+`, `MREF+`, `PURP+` and `SVWZ+`, and `?30` BIC, `?31` account and `?32` to
+/// `?33` name of the counterparty.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GermanSubfields;
+
+impl InformationLayout for GermanSubfields {
+    /// Join the lines without a separator, as the subfields continue across the lines.
+    fn join(&self, lines: &[&str]) -> String {
+        lines.concat()
+    }
+
+    fn wrap(&self, value: &str) -> Vec<String> {
+        wrap(value, INFORMATION_LINE_LENGTH, INFORMATION_LINE_LENGTH).collect()
+    }
+
+    fn parse(&self, value: &str) -> InformationToAccountOwner {
+        let gvc = value
+            .get(..3)
+            .filter(|gvc| gvc.chars().all(|c| c.is_ascii_digit()));
+        let subfields = value.get(3..).unwrap_or_default();
+        let separator = subfields.chars().next();
+
+        let (gvc, separator) = match (gvc, separator) {
+            (Some(gvc), Some(separator))
+                if gvc != UNSTRUCTURED_GVC && !separator.is_alphanumeric() =>
+            {
+                (gvc, separator)
+            }
+            (Some(UNSTRUCTURED_GVC), _) => {
+                return InformationToAccountOwner {
+                    gvc: Some(UNSTRUCTURED_GVC.to_string()),
+                    rmt_inf: non_empty(subfields),
+                    ..Default::default()
+                }
+            }
+            _ => return Unstructured.parse(value),
+        };
+
+        let mut information = InformationToAccountOwner {
+            gvc: Some(gvc.to_string()),
+            ..Default::default()
+        };
+        let mut purpose = String::new();
+        let mut name = String::new();
+
+        for subfield in subfields.split(separator).skip(1) {
+            let text = subfield.get(2..).unwrap_or_default();
+
+            match subfield.get(..2) {
+                Some("00") => information.addtl_inf = non_empty(text),
+                Some("30") => information.ctr_pty_agt = non_empty(text),
+                Some("31") => information.ctr_pty_acct = non_empty(text),
+                Some("32") | Some("33") => name.push_str(text),
+                Some(code) if PURPOSE_SUBFIELDS.contains(&code) => purpose.push_str(text),
+                _ => {}
+            }
+        }
+
+        information.ctr_pty_nm = non_empty(&name);
+
+        let keywords = SEPA_KEYWORD.find_iter(&purpose).collect::<Vec<_>>();
+        let start = keywords.first().map(|keyword| keyword.start());
+        information.rmt_inf = non_empty(&purpose[..start.unwrap_or(purpose.len())]);
+
+        for (index, keyword) in keywords.iter().enumerate() {
+            let end = keywords
+                .get(index + 1)
+                .map(|next| next.start())
+                .unwrap_or(purpose.len());
+            let text = non_empty(&purpose[keyword.end()..end]);
+
+            match keyword.as_str() {
+                "EREF+" => information.end_to_end_id = text,
+                "MREF+" => information.mndt_id = text,
+                "PURP+" => information.purp = text,
+                "SVWZ+" => information.rmt_inf = text,
+                _ => {}
+            }
+        }
+
+        information
+    }
+
+    fn render(
+        &self,
+        information: &InformationToAccountOwner,
+        issues: &mut Vec<TranslationIssue>,
+    ) -> String {
+        let gvc = match information.gvc.as_deref() {
+            Some(gvc) if gvc != UNSTRUCTURED_GVC => gvc,
+            _ => {
+                return format!(
+                    "{}{}",
+                    UNSTRUCTURED_GVC,
+                    Unstructured.render(information, issues)
+                )
+            }
+        };
+
+        let mut subfields: Vec<(&str, String)> = vec![];
+
+        if let Some(addtl_inf) = &information.addtl_inf {
+            subfields.push((
+                "00",
+                truncate(
+                    addtl_inf,
+                    SUBFIELD_LENGTH,
+                    "86",
+                    "TxDtls/AddtlTxInf",
+                    issues,
+                ),
+            ));
+        }
+
+        let purpose = [
+            ("EREF+", &information.end_to_end_id),
+            ("MREF+", &information.mndt_id),
+            ("PURP+", &information.purp),
+            ("SVWZ+", &information.rmt_inf),
+        ]
+        .into_iter()
+        .filter_map(|(keyword, text)| text.as_ref().map(|text| format!("{}{}", keyword, text)))
+        .flat_map(|text| wrap(&text, SUBFIELD_LENGTH, SUBFIELD_LENGTH).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+        for (index, text) in purpose.into_iter().enumerate() {
+            match PURPOSE_SUBFIELDS.get(index) {
+                Some(code) => subfields.push((*code, text)),
+                None => issues.push(truncated("86", "TxDtls/RmtInf/Ustrd", &text)),
+            }
+        }
+
+        if let Some(ctr_pty_agt) = &information.ctr_pty_agt {
+            subfields.push(("30", ctr_pty_agt.clone()));
+        }
+        if let Some(ctr_pty_acct) = &information.ctr_pty_acct {
+            subfields.push(("31", ctr_pty_acct.clone()));
+        }
+        if let Some(ctr_pty_nm) = &information.ctr_pty_nm {
+            let name = wrap(ctr_pty_nm, SUBFIELD_LENGTH, SUBFIELD_LENGTH).collect::<Vec<_>>();
+
+            if name.len() > 2 {
+                issues.push(truncated("86", "TxDtls/RltdPties/Nm", ctr_pty_nm));
+            }
+            subfields.extend(["32", "33"].into_iter().zip(name));
+        }
+
+        subfields.sort_by_key(|(code, _)| *code);
+
+        subfields
+            .into_iter()
+            .fold(gvc.to_string(), |value, (code, text)| {
+                format!("{}?{}{}", value, code, text)
+            })
+    }
+}
+
+/// Layout of slash codes, e.g. `/EREF/E2E-1/REMI/INVOICE 4711/NAME/ACME GMBH`, of the codes
+/// `/TRCD/` business transaction code, `/EREF/` end-to-end identification, `/MARF/` mandate
+/// identification, `/PURP/` purpose, `/NAME/`, `/IBAN/` and `/BIC/` of the counterparty,
+/// `/REMI/` remittance information and `/ADDI/` additional information.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SlashCodes;
+
+impl InformationLayout for SlashCodes {
+    fn parse(&self, value: &str) -> InformationToAccountOwner {
+        let codes = SLASH_CODE.captures_iter(value).collect::<Vec<_>>();
+
+        if codes.is_empty() {
+            return Unstructured.parse(value);
+        }
+
+        let mut information = InformationToAccountOwner::default();
+
+        for (index, captures) in codes.iter().enumerate() {
+            let end = codes
+                .get(index + 1)
+                .and_then(|next| next.get(0))
+                .map(|next| next.start())
+                .unwrap_or(value.len());
+            let start = captures.get(0).map(|code| code.end()).unwrap_or(end);
+            let text = non_empty(value[start..end].trim_end_matches('/'));
+
+            match &captures[1] {
+                "TRCD" => information.gvc = text,
+                "EREF" => information.end_to_end_id = text,
+                "MARF" => information.mndt_id = text,
+                "PURP" => information.purp = text,
+                "NAME" => information.ctr_pty_nm = text,
+                "IBAN" => information.ctr_pty_acct = text,
+                "BIC" => information.ctr_pty_agt = text,
+                "REMI" => information.rmt_inf = text,
+                _ => information.addtl_inf = text,
+            }
+        }
+
+        information
+    }
+
+    fn render(
+        &self,
+        information: &InformationToAccountOwner,
+        _issues: &mut Vec<TranslationIssue>,
+    ) -> String {
+        [
+            ("TRCD", &information.gvc),
+            ("EREF", &information.end_to_end_id),
+            ("MARF", &information.mndt_id),
+            ("PURP", &information.purp),
+            ("NAME", &information.ctr_pty_nm),
+            ("IBAN", &information.ctr_pty_acct),
+            ("BIC", &information.ctr_pty_agt),
+            ("REMI", &information.rmt_inf),
+            ("ADDI", &information.addtl_inf),
+        ]
+        .into_iter()
+        .filter_map(|(code, text)| text.as_ref().map(|text| format!("/{}/{}", code, text)))
+        .collect()
+    }
+}
+
+/// Translate the block 4 of an `MT940` or `MT950` into a `camt.053.001.10`.
+///
+/// The sender of the header is the account servicer, i.e. without field `25P`, and the
+/// receiver is the message recipient.
+pub fn mt940_to_camt_053<A, B>(
+    header: &FinHeader,
+    block4: &Block4,
+    layout: &dyn InformationLayout,
+) -> Result<camt_053::Document<A, B>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let stmt = account_statement(header, block4, layout)?;
+
+    Ok(camt_053::Document {
+        bk_to_cstmr_stmt: camt_053::BankToCustomerStatementV10 {
+            grp_hdr: group_header(header, &stmt),
+            stmt: vec![stmt],
+            splmtry_data: vec![],
+        },
+        xmlns: camt_053::namespace(),
+    })
+}
+
+/// Translate the block 4 of an `MT942` into a `camt.052.001.10`.
+///
+/// The floor limits of field `34F` have no counterpart in the account report and are skipped.
+pub fn mt942_to_camt_052<A, B>(
+    header: &FinHeader,
+    block4: &Block4,
+    layout: &dyn InformationLayout,
+) -> Result<camt_052::Document<A, B>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let stmt = account_statement::<A>(header, block4, layout)?;
+
+    Ok(camt_052::Document {
+        bk_to_cstmr_acct_rpt: camt_052::BankToCustomerAccountReportV10 {
+            grp_hdr: convert(&group_header(header, &stmt))?,
+            rpt: vec![convert(&stmt)?],
+            splmtry_data: vec![],
+        },
+        xmlns: camt_052::namespace(),
+    })
+}
+
+/// Translate each statement of a `camt.053.001.10` into an `MT940`.
+pub fn camt_053_to_mt940<A, B>(
+    document: &camt_053::Document<A, B>,
+    layout: &dyn InformationLayout,
+) -> Result<Vec<MtTranslation>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let grp_hdr = &document.bk_to_cstmr_stmt.grp_hdr;
+
+    document
+        .bk_to_cstmr_stmt
+        .stmt
+        .iter()
+        .map(|stmt| statement_to_mt(grp_hdr, stmt, Some(layout)))
+        .collect()
+}
+
+/// Translate each statement of a `camt.053.001.10` into an `MT950`, i.e. without the
+/// information to account owner of field `86`.
+pub fn camt_053_to_mt950<A, B>(
+    document: &camt_053::Document<A, B>,
+) -> Result<Vec<MtTranslation>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let grp_hdr = &document.bk_to_cstmr_stmt.grp_hdr;
+
+    document
+        .bk_to_cstmr_stmt
+        .stmt
+        .iter()
+        .map(|stmt| statement_to_mt(grp_hdr, stmt, None))
+        .collect()
+}
+
+fn account_statement<A>(
+    header: &FinHeader,
+    block4: &Block4,
+    layout: &dyn InformationLayout,
+) -> Result<camt_053::AccountStatement11<A>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let reference = &block4.mandatory("20")?.value;
+    let statement_number = block4.mandatory("28C")?;
+    let captures = STATEMENT_NUMBER
+        .captures(&statement_number.value)
+        .ok_or_else(|| invalid_field(&statement_number.tag, &statement_number.value))?;
+
+    let mut bal = vec![];
+    for (tag, tp) in BALANCE_TYPES {
+        for field in block4.fields_of(tag) {
+            bal.push(balance(field, tp)?);
+        }
+    }
+
+    // The currency of the entries is the currency of the balances, or of the floor limit
+    let floor_limit = block4
+        .field("34F")
+        .and_then(|field| FLOOR_LIMIT.captures(&field.value))
+        .map(|captures| captures[1].to_string());
+    let ccy = bal
+        .first()
+        .map(|bal| bal.amt.ccy.value.clone())
+        .or(floor_limit)
+        .ok_or(Error::MissingField("60a"))?;
+
+    let mut ntry: Vec<camt_053::ReportEntry12<A>> = vec![];
+    let mut addtl_stmt_inf = None;
+    let mut previous = None;
+
+    for field in block4.fields.iter() {
+        match field.tag.as_str() {
+            "61" => ntry.push(entry(field, &ccy)?),
+            "86" => {
+                let lines = field.lines().collect::<Vec<_>>();
+
+                match (previous, ntry.last_mut()) {
+                    (Some("61"), Some(entry)) => {
+                        add_information(entry, layout.parse(&layout.join(&lines)))
+                    }
+                    _ => {
+                        addtl_stmt_inf = Some(camt_053::Max500Text {
+                            value: lines.join(" "),
+                        })
+                    }
+                }
+            }
+            _ => {}
+        }
+        previous = Some(field.tag.as_str());
+    }
+
+    Ok(camt_053::AccountStatement11 {
+        id: camt_053::Max35Text {
+            value: reference.clone(),
+        },
+        stmt_pgntn: captures.get(2).map(|pg_nb| camt_053::Pagination1 {
+            pg_nb: camt_053::Max5NumericText {
+                value: pg_nb.as_str().to_string(),
+            },
+            last_pg_ind: camt_053::YesNoIndicator {
+                value: block4.field("62M").is_none(),
+            },
+        }),
+        elctrnc_seq_nb: Some(camt_053::Number {
+            value: parse_amount(&statement_number.tag, &captures[1])?,
+        }),
+        cre_dt_tm: block4.field("13D").map(date_time_indication).transpose()?,
+        acct: statement_account(header, block4, &ccy)?,
+        bal,
+        txs_summry: transactions_summary(block4)?,
+        ntry,
+        addtl_stmt_inf,
+        ..Default::default()
+    })
+}
+
+fn group_header<A>(
+    header: &FinHeader,
+    stmt: &camt_053::AccountStatement11<A>,
+) -> camt_053::GroupHeader81
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let msg_rcpt = Some(&header.receiver)
+        .filter(|receiver| !receiver.is_empty())
+        .map(|receiver| camt_053::PartyIdentification135 {
+            id: Some(camt_053::Party38Choice {
+                value: camt_053::Party38ChoiceEnum {
+                    org_id: Some(camt_053::OrganisationIdentification29 {
+                        any_bic: Some(camt_053::AnyBicDec2014Identifier {
+                            value: receiver.clone(),
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        });
+
+    camt_053::GroupHeader81 {
+        msg_id: stmt.id.clone(),
+        cre_dt_tm: stmt
+            .cre_dt_tm
+            .clone()
+            .unwrap_or(camt_053::IsoDateTime { value: Utc::now() }),
+        msg_rcpt,
+        ..Default::default()
+    }
+}
+
+/// Return the account of field `25`, or of field `25P` with the BIC of the account servicer.
+fn statement_account(
+    header: &FinHeader,
+    block4: &Block4,
+    ccy: &str,
+) -> Result<camt_053::CashAccount41, Error> {
+    let (id, bic) = match (block4.field("25"), block4.field("25P")) {
+        (Some(field), _) => (field.value.clone(), None),
+        (None, Some(field)) => {
+            let mut lines = field.lines();
+            (
+                lines.next().unwrap_or_default().to_string(),
+                lines.next().map(str::to_string),
+            )
+        }
+        (None, None) => return Err(Error::MissingField("25")),
+    };
+
+    let svcr = bic.or_else(|| Some(header.sender.clone()).filter(|sender| !sender.is_empty()));
+
+    Ok(camt_053::CashAccount41 {
+        id: Some(account_identification(&id)),
+        ccy: Some(camt_053::ActiveOrHistoricCurrencyCode {
+            value: ccy.to_string(),
+        }),
+        svcr: svcr.as_deref().map(bic_agent),
+        ..Default::default()
+    })
+}
+
+/// Parse a balance field, e.g. `:60F:C230331EUR1000,`.
+fn balance(field: &Field, tp: &str) -> Result<camt_053::CashBalance8, Error> {
+    let captures = BALANCE
+        .captures(&field.value)
+        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+
+    Ok(camt_053::CashBalance8 {
+        tp: camt_053::BalanceType13 {
+            cd_or_prtry: camt_053::BalanceType10Choice {
+                value: camt_053::BalanceType10ChoiceEnum {
+                    cd: Some(camt_053::ExternalBalanceType1Code {
+                        value: tp.to_string(),
+                    }),
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        },
+        amt: amount(parse_amount(&field.tag, &captures[4])?, &captures[3]),
+        cdt_dbt_ind: credit_debit(&captures[1]),
+        dt: date(parse_date(&field.tag, &captures[2])?),
+        ..Default::default()
+    })
+}
+
+/// Parse a statement line of field `61`.
+fn entry<A>(field: &Field, ccy: &str) -> Result<camt_053::ReportEntry12<A>, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let captures = STATEMENT_LINE
+        .captures(&field.value)
+        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+
+    let val_dt = parse_date(&field.tag, &captures[1])?;
+    let bookg_dt = match captures.get(2) {
+        Some(entry_date) => parse_entry_date(&field.tag, &val_dt, entry_date.as_str())?,
+        None => val_dt,
+    };
+
+    // The reversal of a credit is a debit, and the reversal of a debit is a credit
+    let (cdt_dbt_ind, rvsl_ind) = match &captures[3] {
+        "RC" => (camt_053::CreditDebitCode::Dbit, true),
+        "RD" => (camt_053::CreditDebitCode::Crdt, true),
+        mark => (credit_debit(mark), false),
+    };
+
+    let tx_dtls = captures
+        .get(7)
+        .map(|reference| reference.as_str())
+        .filter(|reference| *reference != NONREF)
+        .map(|reference| camt_053::EntryTransaction12 {
+            refs: Some(camt_053::TransactionReferences6 {
+                end_to_end_id: Some(camt_053::Max35Text {
+                    value: reference.to_string(),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+    Ok(camt_053::ReportEntry12 {
+        amt: amount(parse_amount(&field.tag, &captures[5])?, ccy),
+        bk_tx_cd: bank_transaction_code(&captures[6], &cdt_dbt_ind),
+        cdt_dbt_ind,
+        rvsl_ind: rvsl_ind.then_some(camt_053::TrueFalseIndicator { value: true }),
+        sts: camt_053::EntryStatus1Choice {
+            value: camt_053::EntryStatus1ChoiceEnum {
+                cd: Some(camt_053::ExternalEntryStatus1Code {
+                    value: BOOKED.to_string(),
+                }),
+                ..Default::default()
+            },
+        },
+        bookg_dt: Some(date(bookg_dt)),
+        val_dt: Some(date(val_dt)),
+        acct_svcr_ref: captures.get(8).map(|acct_svcr_ref| camt_053::Max35Text {
+            value: acct_svcr_ref.as_str().to_string(),
+        }),
+        ntry_dtls: tx_dtls
+            .map(|tx_dtls| camt_053::EntryDetails11 {
+                tx_dtls: vec![tx_dtls],
+                ..Default::default()
+            })
+            .into_iter()
+            .collect(),
+        addtl_ntry_inf: captures.get(9).map(|addtl_ntry_inf| camt_053::Max500Text {
+            value: addtl_ntry_inf.as_str().to_string(),
+        }),
+        ..Default::default()
+    })
+}
+
+/// Return the bank transaction code of a transaction type code, e.g. `NTRF`, as proprietary
+/// code and mapped to its domain, family and sub-family.
+fn bank_transaction_code(
+    code: &str,
+    cdt_dbt_ind: &camt_053::CreditDebitCode,
+) -> camt_053::BankTransactionCodeStructure4 {
+    let [domn, fmly, sub_fmly] = TRANSACTION_TYPES
+        .iter()
+        .find(|(transaction_type, _, _)| code.get(1..) == Some(*transaction_type))
+        .map(|(_, credit, debit)| match cdt_dbt_ind {
+            camt_053::CreditDebitCode::Dbit => *debit,
+            _ => *credit,
+        })
+        .unwrap_or(NOT_AVAILABLE);
+
+    camt_053::BankTransactionCodeStructure4 {
+        domn: Some(camt_053::BankTransactionCodeStructure5 {
+            cd: camt_053::ExternalBankTransactionDomain1Code {
+                value: domn.to_string(),
+            },
+            fmly: camt_053::BankTransactionCodeStructure6 {
+                cd: camt_053::ExternalBankTransactionFamily1Code {
+                    value: fmly.to_string(),
+                },
+                sub_fmly_cd: camt_053::ExternalBankTransactionSubFamily1Code {
+                    value: sub_fmly.to_string(),
+                },
+            },
+        }),
+        prtry: Some(camt_053::ProprietaryBankTransactionCodeStructure1 {
+            cd: camt_053::Max35Text {
+                value: code.to_string(),
+            },
+            issr: Some(camt_053::Max35Text {
+                value: SWIFT.to_string(),
+            }),
+        }),
+    }
+}
+
+/// Return the transaction type code of a bank transaction code, i.e. the proprietary code
+/// issued by SWIFT, or else the code mapped from the domain, family and sub-family.
+fn transaction_type(bk_tx_cd: &camt_053::BankTransactionCodeStructure4) -> String {
+    let swift = bk_tx_cd.prtry.as_ref().filter(|prtry| {
+        prtry.issr.as_ref().map(|issr| issr.value.as_str()) == Some(SWIFT)
+            && prtry.cd.value.len() == 4
+    });
+    if let Some(prtry) = swift {
+        return prtry.cd.value.clone();
+    }
+
+    let domn = bk_tx_cd.domn.as_ref().map(|domn| {
+        [
+            domn.cd.value.as_str(),
+            domn.fmly.cd.value.as_str(),
+            domn.fmly.sub_fmly_cd.value.as_str(),
+        ]
+    });
+    let code = domn.and_then(|domn| {
+        let codes = || {
+            TRANSACTION_TYPES
+                .iter()
+                .flat_map(|(code, credit, debit)| [(code, credit), (code, debit)])
+        };
+
+        codes()
+            .find(|(_, codes)| **codes == domn)
+            .or_else(|| codes().find(|(_, codes)| codes[..2] == domn[..2] && codes[2] == OTHER))
+            .map(|(code, _)| format!("N{}", code))
+    });
+
+    code.unwrap_or_else(|| MISCELLANEOUS.to_string())
+}
+
+/// Add the information to account owner of field `86` to the transaction details of the
+/// entry, where the end-to-end identification applies to entries without reference for the
+/// account owner.
+fn add_information<A>(
+    entry: &mut camt_053::ReportEntry12<A>,
+    information: InformationToAccountOwner,
+) where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    if information == InformationToAccountOwner::default() {
+        return;
+    }
+
+    let credit = entry.cdt_dbt_ind == camt_053::CreditDebitCode::Crdt;
+
+    if entry.ntry_dtls.is_empty() {
+        entry.ntry_dtls.push(Default::default());
+    }
+    let ntry_dtls = &mut entry.ntry_dtls[0];
+    if ntry_dtls.tx_dtls.is_empty() {
+        ntry_dtls.tx_dtls.push(Default::default());
+    }
+    let tx_dtls = &mut ntry_dtls.tx_dtls[0];
+
+    if information.end_to_end_id.is_some() || information.mndt_id.is_some() {
+        let refs = tx_dtls.refs.get_or_insert_with(Default::default);
+
+        if refs.end_to_end_id.is_none() {
+            refs.end_to_end_id = information.end_to_end_id.map(max_35_text);
+        }
+        refs.mndt_id = information.mndt_id.map(max_35_text);
+    }
+
+    tx_dtls.bk_tx_cd = information
+        .gvc
+        .map(|gvc| camt_053::BankTransactionCodeStructure4 {
+            prtry: Some(camt_053::ProprietaryBankTransactionCodeStructure1 {
+                cd: max_35_text(gvc),
+                issr: None,
+            }),
+            ..Default::default()
+        });
+
+    let pty = information.ctr_pty_nm.map(|nm| camt_053::Party40Choice {
+        value: camt_053::Party40ChoiceEnum {
+            pty: Some(camt_053::PartyIdentification135 {
+                nm: Some(camt_053::Max140Text { value: nm }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    });
+    let acct = information.ctr_pty_acct.map(|id| camt_053::CashAccount40 {
+        id: Some(account_identification(&id)),
+        ..Default::default()
+    });
+
+    if pty.is_some() || acct.is_some() {
+        let rltd_pties = tx_dtls.rltd_pties.get_or_insert_with(Default::default);
+
+        if credit {
+            rltd_pties.dbtr = pty;
+            rltd_pties.dbtr_acct = acct;
+        } else {
+            rltd_pties.cdtr = pty;
+            rltd_pties.cdtr_acct = acct;
+        }
+    }
+
+    if let Some(agt) = information.ctr_pty_agt.as_deref().map(bic_agent) {
+        let rltd_agts = tx_dtls.rltd_agts.get_or_insert_with(Default::default);
+
+        if credit {
+            rltd_agts.dbtr_agt = Some(agt);
+        } else {
+            rltd_agts.cdtr_agt = Some(agt);
+        }
+    }
+
+    tx_dtls.purp = information.purp.map(|cd| camt_053::Purpose2Choice {
+        value: camt_053::Purpose2ChoiceEnum {
+            cd: Some(camt_053::ExternalPurpose1Code { value: cd }),
+            ..Default::default()
+        },
+    });
+    tx_dtls.rmt_inf = information
+        .rmt_inf
+        .map(|rmt_inf| camt_053::RemittanceInformation21 {
+            ustrd: wrap(&rmt_inf, 140, 140)
+                .map(|value| camt_053::Max140Text { value })
+                .collect(),
+            ..Default::default()
+        });
+    tx_dtls.addtl_tx_inf = information
+        .addtl_inf
+        .map(|value| camt_053::Max500Text { value });
+}
+
+/// Return the information to account owner of the transaction details of an entry.
+fn information<A>(
+    tx_dtls: &camt_053::EntryTransaction12<A>,
+    cdt_dbt_ind: &camt_053::CreditDebitCode,
+) -> InformationToAccountOwner
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let credit = *cdt_dbt_ind == camt_053::CreditDebitCode::Crdt;
+    let refs = tx_dtls.refs.as_ref();
+    let rltd_pties = tx_dtls.rltd_pties.as_ref();
+    let rltd_agts = tx_dtls.rltd_agts.as_ref();
+
+    let (pty, acct, agt) = if credit {
+        (
+            rltd_pties.and_then(|rltd_pties| rltd_pties.dbtr.as_ref()),
+            rltd_pties.and_then(|rltd_pties| rltd_pties.dbtr_acct.as_ref()),
+            rltd_agts.and_then(|rltd_agts| rltd_agts.dbtr_agt.as_ref()),
+        )
+    } else {
+        (
+            rltd_pties.and_then(|rltd_pties| rltd_pties.cdtr.as_ref()),
+            rltd_pties.and_then(|rltd_pties| rltd_pties.cdtr_acct.as_ref()),
+            rltd_agts.and_then(|rltd_agts| rltd_agts.cdtr_agt.as_ref()),
+        )
+    };
+
+    let ustrd = tx_dtls
+        .rmt_inf
+        .iter()
+        .flat_map(|rmt_inf| rmt_inf.ustrd.iter())
+        .map(|ustrd| ustrd.value.as_str())
+        .collect::<String>();
+
+    InformationToAccountOwner {
+        gvc: tx_dtls
+            .bk_tx_cd
+            .as_ref()
+            .and_then(|bk_tx_cd| bk_tx_cd.prtry.as_ref())
+            .map(|prtry| prtry.cd.value.clone()),
+        end_to_end_id: refs
+            .and_then(|refs| refs.end_to_end_id.as_ref())
+            .map(|end_to_end_id| end_to_end_id.value.clone()),
+        mndt_id: refs
+            .and_then(|refs| refs.mndt_id.as_ref())
+            .map(|mndt_id| mndt_id.value.clone()),
+        purp: tx_dtls.purp.as_ref().and_then(|purp| {
+            purp.value
+                .cd
+                .as_ref()
+                .map(|cd| cd.value.clone())
+                .or_else(|| purp.value.prtry.as_ref().map(|prtry| prtry.value.clone()))
+        }),
+        rmt_inf: non_empty(&ustrd),
+        ctr_pty_nm: pty
+            .and_then(|pty| pty.value.pty.as_ref())
+            .and_then(|pty| pty.nm.as_ref())
+            .map(|nm| nm.value.clone()),
+        ctr_pty_acct: acct.and_then(|acct| account_id(acct.id.as_ref())),
+        ctr_pty_agt: agt
+            .and_then(|agt| agt.fin_instn_id.bicfi.as_ref())
+            .map(|bicfi| bicfi.value.clone()),
+        addtl_inf: tx_dtls
+            .addtl_tx_inf
+            .as_ref()
+            .map(|addtl_tx_inf| addtl_tx_inf.value.clone()),
+    }
+}
+
+fn statement_to_mt<A>(
+    grp_hdr: &camt_053::GroupHeader81,
+    stmt: &camt_053::AccountStatement11<A>,
+    layout: Option<&dyn InformationLayout>,
+) -> Result<MtTranslation, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut issues = vec![];
+    let mut block4 = Block4::default();
+
+    block4.push(
+        "20",
+        truncate(&stmt.id.value, 16, "20", "Stmt/Id", &mut issues),
+    );
+
+    let acct = account_id(stmt.acct.id.as_ref()).ok_or(Error::MissingElement("Stmt/Acct/Id"))?;
+    block4.push("25", truncate(&acct, 35, "25", "Stmt/Acct/Id", &mut issues));
+
+    // The statement number is mandatory, and defaults to 1
+    let statement_number = stmt
+        .elctrnc_seq_nb
+        .as_ref()
+        .or(stmt.lgl_seq_nb.as_ref())
+        .map(|number| format!("{:.0}", number.value))
+        .unwrap_or_else(|| "1".to_string());
+    block4.push(
+        "28C",
+        match &stmt.stmt_pgntn {
+            Some(stmt_pgntn) => format!("{}/{}", statement_number, stmt_pgntn.pg_nb.value),
+            None => statement_number,
+        },
+    );
+
+    let opening = balance_fields(stmt, "60F")
+        .chain(balance_fields(stmt, "60M"))
+        .next()
+        .ok_or(Error::MissingElement("Stmt/Bal/OPBD"))?;
+    let closing = balance_fields(stmt, "62F")
+        .chain(balance_fields(stmt, "62M"))
+        .next()
+        .ok_or(Error::MissingElement("Stmt/Bal/CLBD"))?;
+
+    block4.fields.push(opening);
+
+    for ntry in stmt.ntry.iter() {
+        let sts = ntry.sts.value.cd.as_ref().map(|cd| cd.value.as_str());
+        if sts != Some(BOOKED) {
+            issues.push(lost("Stmt/Ntry/Sts"));
+            continue;
+        }
+
+        block4.fields.push(statement_line(ntry, &mut issues)?);
+
+        let mut tx_dtls = ntry
+            .ntry_dtls
+            .iter()
+            .flat_map(|ntry_dtls| ntry_dtls.tx_dtls.iter());
+
+        let information = tx_dtls
+            .next()
+            .map(|tx_dtls| information(tx_dtls, &ntry.cdt_dbt_ind))
+            .filter(|information| *information != InformationToAccountOwner::default());
+
+        if let Some(information) = information {
+            match layout {
+                Some(layout) => {
+                    let value = layout.render(&information, &mut issues);
+                    block4.fields.extend(information_field(
+                        layout.wrap(&value),
+                        "Stmt/Ntry/NtryDtls/TxDtls",
+                        &mut issues,
+                    ));
+                }
+                None => issues.push(lost("Stmt/Ntry/NtryDtls/TxDtls")),
+            }
+        }
+
+        // Field `86` is the information of one transaction, e.g. not of the batch
+        for _ in tx_dtls {
+            issues.push(lost("Stmt/Ntry/NtryDtls/TxDtls"));
+        }
+    }
+
+    block4.fields.push(closing);
+    block4.fields.extend(balance_fields(stmt, "64").take(1));
+    block4.fields.extend(balance_fields(stmt, "65"));
+
+    if let Some(addtl_stmt_inf) = &stmt.addtl_stmt_inf {
+        match layout {
+            Some(_) => block4.fields.extend(information_field(
+                wrap_words(&addtl_stmt_inf.value, INFORMATION_LINE_LENGTH),
+                "Stmt/AddtlStmtInf",
+                &mut issues,
+            )),
+            None => issues.push(lost("Stmt/AddtlStmtInf")),
+        }
+    }
+
+    let sender = stmt
+        .acct
+        .svcr
+        .as_ref()
+        .and_then(|svcr| svcr.fin_instn_id.bicfi.as_ref())
+        .map(|bicfi| bicfi.value.clone());
+    let receiver = grp_hdr
+        .msg_rcpt
+        .as_ref()
+        .and_then(|msg_rcpt| msg_rcpt.id.as_ref())
+        .and_then(|id| id.value.org_id.as_ref())
+        .and_then(|org_id| org_id.any_bic.as_ref())
+        .map(|any_bic| any_bic.value.clone());
+
+    Ok(MtTranslation {
+        message_type: if layout.is_some() {
+            MessageType::Mt940
+        } else {
+            MessageType::Mt950
+        },
+        header: FinHeader {
+            sender: sender.unwrap_or_default(),
+            receiver: receiver.unwrap_or_default(),
+            uetr: None,
+        },
+        block4,
+        issues,
+    })
+}
+
+/// Render the balance fields of the tag, e.g. `60F` of the balances of type `OPBD`.
+fn balance_fields<'a, A>(
+    stmt: &'a camt_053::AccountStatement11<A>,
+    tag: &'a str,
+) -> impl Iterator<Item = Field> + 'a
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let tp = BALANCE_TYPES
+        .iter()
+        .find(|(balance_tag, _)| *balance_tag == tag)
+        .map(|(_, tp)| *tp);
+
+    stmt.bal
+        .iter()
+        .filter(move |bal| {
+            bal.tp
+                .cd_or_prtry
+                .value
+                .cd
+                .as_ref()
+                .map(|cd| cd.value.as_str())
+                == tp
+        })
+        .filter_map(move |bal| {
+            choice_date(&bal.dt).map(|dt| {
+                Field::new(
+                    tag,
+                    format!(
+                        "{}{}{}{}",
+                        mark(&bal.cdt_dbt_ind),
+                        dt.format("%y%m%d"),
+                        bal.amt.ccy.value,
+                        format_amount(bal.amt.value.value, &bal.amt.ccy.value)
+                    ),
+                )
+            })
+        })
+}
+
+/// Render the statement line of field `61` of an entry.
+fn statement_line<A>(
+    ntry: &camt_053::ReportEntry12<A>,
+    issues: &mut Vec<TranslationIssue>,
+) -> Result<Field, Error>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let val_dt = ntry
+        .val_dt
+        .as_ref()
+        .or(ntry.bookg_dt.as_ref())
+        .and_then(choice_date)
+        .ok_or(Error::MissingElement("Stmt/Ntry/ValDt"))?;
+    let entry_date = ntry
+        .bookg_dt
+        .as_ref()
+        .and_then(choice_date)
+        .filter(|bookg_dt| *bookg_dt != val_dt)
+        .map(|bookg_dt| bookg_dt.format("%m%d").to_string());
+
+    let rvsl_ind = ntry
+        .rvsl_ind
+        .as_ref()
+        .map(|rvsl_ind| rvsl_ind.value)
+        .unwrap_or_default();
+    let cdt_dbt_ind = match (&ntry.cdt_dbt_ind, rvsl_ind) {
+        (camt_053::CreditDebitCode::Crdt, false) => "C",
+        (camt_053::CreditDebitCode::Dbit, false) => "D",
+        (camt_053::CreditDebitCode::Dbit, true) => "RC",
+        (camt_053::CreditDebitCode::Crdt, true) => "RD",
+        (camt_053::CreditDebitCode::Unknown, _) => {
+            return Err(Error::MissingElement("Stmt/Ntry/CdtDbtInd"))
+        }
+    };
+
+    let reference = ntry
+        .ntry_dtls
+        .first()
+        .and_then(|ntry_dtls| ntry_dtls.tx_dtls.first())
+        .and_then(|tx_dtls| tx_dtls.refs.as_ref())
+        .and_then(|refs| refs.end_to_end_id.as_ref())
+        .map(|end_to_end_id| {
+            truncate(
+                &end_to_end_id.value,
+                16,
+                "61",
+                "Stmt/Ntry/NtryDtls/TxDtls/Refs/EndToEndId",
+                issues,
+            )
+        })
+        .unwrap_or_else(|| NONREF.to_string());
+
+    let mut value = format!(
+        "{}{}{}{}{}{}",
+        val_dt.format("%y%m%d"),
+        entry_date.unwrap_or_default(),
+        cdt_dbt_ind,
+        format_amount(ntry.amt.value.value, &ntry.amt.ccy.value),
+        transaction_type(&ntry.bk_tx_cd),
+        reference
+    );
+
+    if let Some(acct_svcr_ref) = &ntry.acct_svcr_ref {
+        value.push_str("//");
+        value.push_str(&truncate(
+            &acct_svcr_ref.value,
+            16,
+            "61",
+            "Stmt/Ntry/AcctSvcrRef",
+            issues,
+        ));
+    }
+    if let Some(addtl_ntry_inf) = &ntry.addtl_ntry_inf {
+        value.push('\n');
+        value.push_str(&truncate(
+            &addtl_ntry_inf.value,
+            34,
+            "61",
+            "Stmt/Ntry/AddtlNtryInf",
+            issues,
+        ));
+    }
+
+    Ok(Field::new("61", value))
+}
+
+/// Render field `86`, on up to six of the lines.
+fn information_field(
+    mut lines: Vec<String>,
+    element: &str,
+    issues: &mut Vec<TranslationIssue>,
+) -> Option<Field> {
+    if lines.len() > INFORMATION_LINES {
+        for line in lines.drain(INFORMATION_LINES..) {
+            issues.push(truncated("86", element, &line));
+        }
+    }
+
+    (!lines.is_empty()).then(|| Field::new("86", lines.join("\n")))
+}
+
+/// Parse the transactions summary of fields `90D` and `90C` of an `MT942`.
+fn transactions_summary(block4: &Block4) -> Result<Option<camt_053::TotalTransactions6>, Error> {
+    let ttl_dbt_ntries = block4.field("90D").map(number_and_sum).transpose()?;
+    let ttl_cdt_ntries = block4.field("90C").map(number_and_sum).transpose()?;
+
+    if ttl_dbt_ntries.is_none() && ttl_cdt_ntries.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(camt_053::TotalTransactions6 {
+        ttl_cdt_ntries,
+        ttl_dbt_ntries,
+        ..Default::default()
+    }))
+}
+
+/// Parse the number and sum of entries, e.g. `:90D:2EUR150,5`.
+fn number_and_sum(field: &Field) -> Result<camt_053::NumberAndSumOfTransactions1, Error> {
+    let captures = NUMBER_AND_SUM
+        .captures(&field.value)
+        .ok_or_else(|| invalid_field(&field.tag, &field.value))?;
+
+    Ok(camt_053::NumberAndSumOfTransactions1 {
+        nb_of_ntries: Some(camt_053::Max15NumericText {
+            value: captures[1].to_string(),
+        }),
+        sum: Some(camt_053::DecimalNumber {
+            value: parse_amount(&field.tag, &captures[3])?,
+        }),
+    })
+}
+
+/// Parse the date time indication of field `13D`, e.g. `2304011530+0100`.
+fn date_time_indication(field: &Field) -> Result<camt_053::IsoDateTime, Error> {
+    if !DATE_TIME_INDICATION.is_match(&field.value) {
+        return Err(invalid_field(&field.tag, &field.value));
+    }
+
+    let value = DateTime::parse_from_str(&field.value, "%y%m%d%H%M%z")
+        .map_err(|_| invalid_field(&field.tag, &field.value))?;
+
+    Ok(camt_053::IsoDateTime {
+        value: value.with_timezone(&Utc),
+    })
+}
+
+/// Parse a date, e.g. `230401`.
+fn parse_date(tag: &str, value: &str) -> Result<NaiveDate, Error> {
+    NaiveDate::parse_from_str(value, "%y%m%d").map_err(|_| invalid_field(tag, value))
+}
+
+/// Parse the entry date of field `61`, i.e. a month and day without year, as the date closest
+/// to the value date, e.g. of the next year for a value date in December.
+fn parse_entry_date(tag: &str, val_dt: &NaiveDate, value: &str) -> Result<NaiveDate, Error> {
+    [val_dt.year() - 1, val_dt.year(), val_dt.year() + 1]
+        .into_iter()
+        .filter_map(|year| NaiveDate::parse_from_str(&format!("{}{}", year, value), "%Y%m%d").ok())
+        .min_by_key(|entry_date| (*entry_date - *val_dt).num_days().abs())
+        .ok_or_else(|| invalid_field(tag, value))
+}
+
+fn date(dt: NaiveDate) -> camt_053::DateAndDateTime2Choice {
+    camt_053::DateAndDateTime2Choice {
+        value: camt_053::DateAndDateTime2ChoiceEnum {
+            dt: Some(camt_053::IsoDate { value: dt }),
+            ..Default::default()
+        },
+    }
+}
+
+fn choice_date(dt: &camt_053::DateAndDateTime2Choice) -> Option<NaiveDate> {
+    dt.value.dt.as_ref().map(|dt| dt.value).or_else(|| {
+        dt.value
+            .dt_tm
+            .as_ref()
+            .map(|dt_tm| dt_tm.value.date_naive())
+    })
+}
+
+fn amount(value: f64, ccy: &str) -> camt_053::ActiveOrHistoricCurrencyAndAmount {
+    camt_053::ActiveOrHistoricCurrencyAndAmount {
+        value: camt_053::ActiveOrHistoricCurrencyAndAmountSimpleType { value },
+        ccy: camt_053::ActiveOrHistoricCurrencyCode {
+            value: ccy.to_string(),
+        },
+    }
+}
+
+/// Return the credit debit indicator of a debit / credit mark, i.e. `C` or `D`.
+fn credit_debit(mark: &str) -> camt_053::CreditDebitCode {
+    if mark == "D" {
+        camt_053::CreditDebitCode::Dbit
+    } else {
+        camt_053::CreditDebitCode::Crdt
+    }
+}
+
+/// Return the debit / credit mark of a credit debit indicator.
+fn mark(cdt_dbt_ind: &camt_053::CreditDebitCode) -> &'static str {
+    if *cdt_dbt_ind == camt_053::CreditDebitCode::Dbit {
+        "D"
+    } else {
+        "C"
+    }
+}
+
+/// Return the account identification, as an IBAN or a proprietary identification.
+fn account_identification(id: &str) -> camt_053::AccountIdentification4Choice {
+    let value = if IBAN.is_match(id) {
+        camt_053::AccountIdentification4ChoiceEnum {
+            iban: Some(camt_053::Iban2007Identifier {
+                value: id.to_string(),
+            }),
+            ..Default::default()
+        }
+    } else {
+        camt_053::AccountIdentification4ChoiceEnum {
+            othr: Some(camt_053::GenericAccountIdentification1 {
+                id: camt_053::Max34Text {
+                    value: id.to_string(),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    };
+
+    camt_053::AccountIdentification4Choice { value }
+}
+
+/// Return the IBAN or the proprietary identification of an account.
+fn account_id(id: Option<&camt_053::AccountIdentification4Choice>) -> Option<String> {
+    let id = &id?.value;
+
+    id.iban
+        .as_ref()
+        .map(|iban| iban.value.clone())
+        .or_else(|| id.othr.as_ref().map(|othr| othr.id.value.clone()))
+}
+
+fn bic_agent(bic: &str) -> camt_053::BranchAndFinancialInstitutionIdentification6 {
+    camt_053::BranchAndFinancialInstitutionIdentification6 {
+        fin_instn_id: camt_053::FinancialInstitutionIdentification18 {
+            bicfi: Some(camt_053::BicfiDec2014Identifier {
+                value: bic.to_string(),
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn max_35_text(value: String) -> camt_053::Max35Text {
+    camt_053::Max35Text { value }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+    use crate::statement::verify_statement;
+
+    fn header() -> FinHeader {
+        FinHeader {
+            sender: "AAAADEFFXXX".to_string(),
+            receiver: "BBBBDEFFXXX".to_string(),
+            uetr: None,
+        }
+    }
+
+    fn mt940() -> Block4 {
+        [
+            ":20:STMT230401",
+            ":25:DE89370400440532013000",
+            ":28C:12/1",
+            ":60F:C230331EUR1000,",
+            ":61:2304010403C250,NTRFE2E-REF-1//BANKREF1",
+            "SUPPLEMENTARY",
+            ":86:166?00SEPA GUTSCHRIFT?20EREF+E2E-REF-1?21SVWZ+INVOICE 4711?30COBA",
+            "DEFFXXX?31DE02120300000000202051?32ACME GMBH",
+            ":61:230401D100,5NCHGNONREF",
+            ":62F:C230401EUR1149,5",
+            ":64:C230401EUR1149,5",
+            ":86:STATEMENT INFORMATION",
+        ]
+        .join("\n")
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mt940_round_trip() {
+        let block4 = mt940();
+        let document =
+            mt940_to_camt_053::<Dmkr, Dmkr>(&header(), &block4, &GermanSubfields).unwrap();
+
+        let stmt = &document.bk_to_cstmr_stmt.stmt[0];
+        assert_eq!(stmt.id.value, "STMT230401");
+        assert_eq!(
+            account_id(stmt.acct.id.as_ref()).as_deref(),
+            Some("DE89370400440532013000")
+        );
+        assert_eq!(stmt.bal.len(), 3);
+        assert_eq!(stmt.ntry.len(), 2);
+        assert!(verify_statement(stmt).is_empty());
+
+        let ntry = &stmt.ntry[0];
+        assert_eq!(
+            ntry.bookg_dt.as_ref().and_then(choice_date),
+            NaiveDate::from_ymd_opt(2023, 4, 3)
+        );
+        assert_eq!(ntry.acct_svcr_ref.as_ref().unwrap().value, "BANKREF1");
+        assert_eq!(
+            ntry.bk_tx_cd.domn.as_ref().unwrap().fmly.cd.value,
+            "RCDT".to_string()
+        );
+
+        let tx_dtls = &ntry.ntry_dtls[0].tx_dtls[0];
+        let rltd_pties = tx_dtls.rltd_pties.as_ref().unwrap();
+        assert_eq!(
+            rltd_pties
+                .dbtr
+                .as_ref()
+                .unwrap()
+                .value
+                .pty
+                .as_ref()
+                .unwrap()
+                .nm,
+            Some(camt_053::Max140Text {
+                value: "ACME GMBH".to_string()
+            })
+        );
+        assert_eq!(
+            tx_dtls.rmt_inf.as_ref().unwrap().ustrd[0].value,
+            "INVOICE 4711"
+        );
+        assert_eq!(
+            stmt.ntry[1]
+                .bk_tx_cd
+                .domn
+                .as_ref()
+                .unwrap()
+                .fmly
+                .sub_fmly_cd
+                .value,
+            "CHRG"
+        );
+        assert!(stmt.ntry[1].ntry_dtls.is_empty());
+        assert_eq!(
+            stmt.addtl_stmt_inf.as_ref().unwrap().value,
+            "STATEMENT INFORMATION"
+        );
+
+        let translations = camt_053_to_mt940(&document, &GermanSubfields).unwrap();
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].message_type, MessageType::Mt940);
+        assert_eq!(translations[0].header, header());
+        assert_eq!(translations[0].block4, block4);
+        assert!(translations[0].issues.is_empty());
+    }
+
+    #[test]
+    fn test_mt942_to_camt_052() {
+        let block4: Block4 = [
+            ":20:INTRADAY1",
+            ":25P:DE89370400440532013000",
+            "COBADEFFXXX",
+            ":28C:5",
+            ":34F:EURD0,",
+            ":13D:2304011530+0200",
+            ":61:230401C50,NSTONONREF",
+            ":86:/EREF/E2E-2/REMI/RENT",
+            "APRIL/NAME/JOHN DOE/IBAN/DE44500105175407324931",
+            ":90D:0EUR0,",
+            ":90C:1EUR50,",
+        ]
+        .join("\n")
+        .parse()
+        .unwrap();
+
+        let document = mt942_to_camt_052::<Dmkr, Dmkr>(&header(), &block4, &SlashCodes).unwrap();
+
+        let rpt = &document.bk_to_cstmr_acct_rpt.rpt[0];
+        assert_eq!(
+            rpt.acct.svcr.as_ref().unwrap().fin_instn_id.bicfi,
+            Some(camt_052::BicfiDec2014Identifier {
+                value: "COBADEFFXXX".to_string()
+            })
+        );
+        assert_eq!(
+            rpt.cre_dt_tm.as_ref().unwrap().value.to_rfc3339(),
+            "2023-04-01T13:30:00+00:00"
+        );
+        assert!(rpt.bal.is_empty());
+
+        let ttl_cdt_ntries = rpt
+            .txs_summry
+            .as_ref()
+            .and_then(|txs_summry| txs_summry.ttl_cdt_ntries.as_ref())
+            .unwrap();
+        assert_eq!(ttl_cdt_ntries.nb_of_ntries.as_ref().unwrap().value, "1");
+        assert_eq!(ttl_cdt_ntries.sum.as_ref().unwrap().value, 50.0);
+
+        let ntry = &rpt.ntry[0];
+        assert_eq!(ntry.amt.ccy.value, "EUR");
+        assert_eq!(
+            ntry.bk_tx_cd.domn.as_ref().unwrap().fmly.sub_fmly_cd.value,
+            "STDO"
+        );
+
+        let tx_dtls = &ntry.ntry_dtls[0].tx_dtls[0];
+        assert_eq!(
+            tx_dtls.refs.as_ref().unwrap().end_to_end_id,
+            Some(camt_052::Max35Text {
+                value: "E2E-2".to_string()
+            })
+        );
+        assert_eq!(
+            tx_dtls.rmt_inf.as_ref().unwrap().ustrd[0].value,
+            "RENT APRIL"
+        );
+        assert!(tx_dtls
+            .rltd_pties
+            .as_ref()
+            .and_then(|rltd_pties| rltd_pties.dbtr_acct.as_ref())
+            .is_some());
+    }
+
+    #[test]
+    fn test_camt_053_to_mt950() {
+        let document =
+            mt940_to_camt_053::<Dmkr, Dmkr>(&header(), &mt940(), &GermanSubfields).unwrap();
+
+        let translations = camt_053_to_mt950(&document).unwrap();
+        assert_eq!(translations[0].message_type, MessageType::Mt950);
+        assert_eq!(translations[0].block4.fields_of("86").count(), 0);
+        assert_eq!(translations[0].block4.fields_of("61").count(), 2);
+        assert_eq!(
+            translations[0].issues,
+            vec![lost("Stmt/Ntry/NtryDtls/TxDtls"), lost("Stmt/AddtlStmtInf")]
+        );
+    }
+
+    #[test]
+    fn test_information_layouts() {
+        let information = InformationToAccountOwner {
+            gvc: Some("105".to_string()),
+            end_to_end_id: Some("E2E-3".to_string()),
+            mndt_id: Some("MANDATE-1".to_string()),
+            rmt_inf: Some("CONTRACT 42".to_string()),
+            ctr_pty_nm: Some(
+                "A CREDITOR WITH A NAME LONGER THAN FIFTY-FOUR CHARACTERS".to_string(),
+            ),
+            ..Default::default()
+        };
+
+        let mut issues = vec![];
+        let value = GermanSubfields.render(&information, &mut issues);
+        assert_eq!(
+            value,
+            "105?20EREF+E2E-3?21MREF+MANDATE-1?22SVWZ+CONTRACT 42\
+             ?32A CREDITOR WITH A NAME LONG?33ER THAN FIFTY-FOUR CHARACTE"
+        );
+        assert_eq!(
+            issues,
+            vec![truncated(
+                "86",
+                "TxDtls/RltdPties/Nm",
+                "A CREDITOR WITH A NAME LONGER THAN FIFTY-FOUR CHARACTERS"
+            )]
+        );
+        assert_eq!(
+            GermanSubfields.parse(&value).mndt_id.as_deref(),
+            Some("MANDATE-1")
+        );
+        assert_eq!(
+            GermanSubfields.parse("999UNSTRUCTURED TEXT"),
+            InformationToAccountOwner {
+                gvc: Some("999".to_string()),
+                rmt_inf: Some("UNSTRUCTURED TEXT".to_string()),
+                ..Default::default()
+            }
+        );
+
+        let value = SlashCodes.render(&information, &mut issues);
+        assert!(value.starts_with("/TRCD/105/EREF/E2E-3/MARF/MANDATE-1/NAME/"));
+        assert_eq!(SlashCodes.parse(&value), information);
+
+        assert_eq!(
+            Unstructured.parse("NO STRUCTURE").rmt_inf.as_deref(),
+            Some("NO STRUCTURE")
+        );
+    }
+
+    #[test]
+    fn test_transaction_type() {
+        let mut bk_tx_cd = bank_transaction_code("NDDT", &camt_053::CreditDebitCode::Dbit);
+        assert_eq!(transaction_type(&bk_tx_cd), "NDDT");
+
+        bk_tx_cd.prtry = None;
+        assert_eq!(transaction_type(&bk_tx_cd), "NDDT");
+
+        bk_tx_cd.domn.as_mut().unwrap().fmly.sub_fmly_cd.value = "ESDD".to_string();
+        assert_eq!(transaction_type(&bk_tx_cd), "NDDT");
+
+        bk_tx_cd.domn = None;
+        assert_eq!(transaction_type(&bk_tx_cd), MISCELLANEOUS);
+    }
+}
@@ -0,0 +1,670 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Reconciliation
+//!
+//! Matches the ledger lines of `camt.054` notifications and `camt.053` statements, see
+//! [`crate::statement`], against open items, i.e. our own payables, e.g. the transactions
+//! of a `pain.001` initiation, and the receivables we expect with their invoice references.
+//!
+//! A line is matched to an open item by end-to-end identification `EndToEndId`, account
+//! servicer reference `AcctSvcrRef` or structured creditor reference `CdtrRefInf/Ref`, or
+//! else by amount, currency and date within the tolerances of the `ReconciliationConfig`.
+//!
+//! ```rust
+//! use iso_20022_sdk::reconciliation::{reconcile, OpenItem, ReconciliationConfig};
+//! use iso_20022_sdk::statement::{ledger_lines, notification_statement};
+//!
+//! let mut items = OpenItem::payables(&pain_001.cstmr_cdt_trf_initn);
+//! items.push(OpenItem::receivable("INV-1", 100.0, "EUR").with_cdtr_ref("RF18539007547034"));
+//!
+//! let mut lines = vec![];
+//! for ntfctn in &camt_054.bk_to_cstmr_dbt_cdt_ntfctn.ntfctn {
+//!     lines.extend(ledger_lines(&notification_statement(ntfctn)?));
+//! }
+//!
+//! let reconciliation = reconcile(&items, &lines, &ReconciliationConfig::default());
+//! ```
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+#[cfg(feature = "pain")]
+use iso_20022_pain::pain_001_001_11 as pain;
+#[cfg(feature = "pain")]
+use serde::Serialize;
+
+use crate::statement::LedgerLine;
+
+/// End-to-end identification used when no identification is provided by the initiating party.
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// Direction of an open item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemDirection {
+    /// Payable, settled by a debit `DBIT` of our account
+    Payable,
+    /// Receivable, settled by a credit `CRDT` of our account
+    Receivable,
+}
+
+impl ItemDirection {
+    /// Return the sign `CdtDbtInd` of the ledger lines settling the item.
+    pub fn cdt_dbt_ind(&self) -> &'static str {
+        match self {
+            Self::Payable => "DBIT",
+            Self::Receivable => "CRDT",
+        }
+    }
+}
+
+/// Open item expected to be settled on the account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenItem {
+    /// Our reference of the item, e.g. the invoice number or the instruction identification
+    pub id: String,
+    /// Payable or receivable
+    pub direction: ItemDirection,
+    /// Amount expected to be settled
+    pub amt: f64,
+    /// Currency of the amount
+    pub ccy: String,
+    /// Expected settlement date, e.g. the requested execution date or the due date
+    pub dt: Option<NaiveDate>,
+    /// End-to-end identification of the payment
+    pub end_to_end_id: Option<String>,
+    /// Account servicer reference of the payment, when already known
+    pub acct_svcr_ref: Option<String>,
+    /// Structured creditor reference, e.g. an RF creditor reference
+    pub cdtr_ref: Option<String>,
+}
+
+impl OpenItem {
+    /// Return a payable, settled by a debit of our account.
+    pub fn payable(id: &str, amt: f64, ccy: &str) -> Self {
+        Self::new(id, ItemDirection::Payable, amt, ccy)
+    }
+
+    /// Return a receivable, settled by a credit of our account.
+    pub fn receivable(id: &str, amt: f64, ccy: &str) -> Self {
+        Self::new(id, ItemDirection::Receivable, amt, ccy)
+    }
+
+    fn new(id: &str, direction: ItemDirection, amt: f64, ccy: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            direction,
+            amt,
+            ccy: ccy.to_string(),
+            dt: None,
+            end_to_end_id: None,
+            acct_svcr_ref: None,
+            cdtr_ref: None,
+        }
+    }
+
+    /// Set the expected settlement date.
+    pub fn with_dt(mut self, dt: NaiveDate) -> Self {
+        self.dt = Some(dt);
+        self
+    }
+
+    /// Set the end-to-end identification.
+    pub fn with_end_to_end_id(mut self, end_to_end_id: &str) -> Self {
+        self.end_to_end_id = Some(end_to_end_id.to_string());
+        self
+    }
+
+    /// Set the account servicer reference.
+    pub fn with_acct_svcr_ref(mut self, acct_svcr_ref: &str) -> Self {
+        self.acct_svcr_ref = Some(acct_svcr_ref.to_string());
+        self
+    }
+
+    /// Set the structured creditor reference.
+    pub fn with_cdtr_ref(mut self, cdtr_ref: &str) -> Self {
+        self.cdtr_ref = Some(cdtr_ref.to_string());
+        self
+    }
+
+    /// Return the payables of the transactions of a `pain.001.001.11` initiation, identified by
+    /// their instruction identification, or else their end-to-end identification.
+    ///
+    /// Transactions instructed in an equivalent amount are skipped, as the amount debited is
+    /// only known after the currency conversion.
+    #[cfg(feature = "pain")]
+    pub fn payables<A, B>(initn: &pain::CustomerCreditTransferInitiationV11<A, B>) -> Vec<Self>
+    where
+        A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+        B: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+    {
+        initn
+            .pmt_inf
+            .iter()
+            .flat_map(|pmt_inf| {
+                let reqd_exctn_dt = &pmt_inf.reqd_exctn_dt.value;
+                let dt = reqd_exctn_dt.dt.as_ref().map(|dt| dt.value).or_else(|| {
+                    reqd_exctn_dt
+                        .dt_tm
+                        .as_ref()
+                        .map(|dt_tm| dt_tm.value.date_naive())
+                });
+
+                pmt_inf.cdt_trf_tx_inf.iter().filter_map(move |tx| {
+                    let instd_amt = tx.amt.value.instd_amt.as_ref()?;
+                    let end_to_end_id = &tx.pmt_id.end_to_end_id.value;
+                    let id = tx
+                        .pmt_id
+                        .instr_id
+                        .as_ref()
+                        .map_or(end_to_end_id, |instr_id| &instr_id.value);
+
+                    Some(Self {
+                        dt,
+                        end_to_end_id: Some(end_to_end_id.clone()),
+                        cdtr_ref: tx.rmt_inf.as_ref().and_then(|rmt_inf| {
+                            rmt_inf
+                                .strd
+                                .iter()
+                                .find_map(|strd| strd.cdtr_ref_inf.as_ref()?.r#ref.as_ref())
+                                .map(|r#ref| r#ref.value.clone())
+                        }),
+                        ..Self::payable(id, instd_amt.value.value, &instd_amt.ccy.value)
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// Tolerances of the reconciliation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconciliationConfig {
+    /// Difference of amounts considered equal, e.g. rounding or small charges
+    pub amt_tolerance: f64,
+    /// Days between the expected and the booking or value date of a line matched by amount
+    pub dt_tolerance_days: i64,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            amt_tolerance: 0.005,
+            dt_tolerance_days: 3,
+        }
+    }
+}
+
+/// Reference by which a line is matched to an open item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMethod {
+    /// End-to-end identification `EndToEndId`
+    EndToEndId,
+    /// Account servicer reference `AcctSvcrRef`
+    AcctSvcrRef,
+    /// Structured creditor reference `CdtrRefInf/Ref`, or the reference in the
+    /// unstructured remittance information
+    CdtrRef,
+    /// Amount, currency and date within the tolerances, where only one item qualifies
+    Amount,
+}
+
+/// Classification of a matched line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchStatus {
+    /// The line settles the outstanding amount of the item, within the amount tolerance
+    Full,
+    /// The line settles part of the outstanding amount, i.e. a short or partial payment
+    Partial,
+    /// The line settles more than the outstanding amount
+    Overpaid,
+    /// The line was already reconciled, e.g. reported by both a notification and a
+    /// statement, or the item was already fully settled
+    Duplicate,
+}
+
+/// Line matched to an open item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconciliationMatch {
+    /// Identification of the open item
+    pub item_id: String,
+    /// Matched ledger line
+    pub line: LedgerLine,
+    /// Reference by which the line is matched
+    pub method: MatchMethod,
+    /// Classification of the line
+    pub status: MatchStatus,
+    /// Outstanding amount of the item after the line
+    pub outstanding: f64,
+}
+
+/// Result of the reconciliation.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Reconciliation {
+    /// Lines matched to open items, in order of the lines
+    pub matches: Vec<ReconciliationMatch>,
+    /// Lines without an open item
+    pub unmatched_lines: Vec<LedgerLine>,
+    /// Open items without any line
+    pub unmatched_items: Vec<OpenItem>,
+}
+
+impl Reconciliation {
+    /// Return the matches of the status.
+    pub fn with_status(&self, status: MatchStatus) -> impl Iterator<Item = &ReconciliationMatch> {
+        self.matches
+            .iter()
+            .filter(move |reconciliation_match| reconciliation_match.status == status)
+    }
+}
+
+/// Reconcile the ledger lines against the open items.
+///
+/// Lines are matched in order, so the lines of the same entry reported by a `camt.054`
+/// notification and then by a `camt.053` statement are classified as duplicates. Lines
+/// without any reference, i.e. without `AcctSvcrRef`, `EndToEndId`, `TxId` or `UETR`, cannot
+/// be told apart and are never classified as duplicates of each other.
+pub fn reconcile(
+    items: &[OpenItem],
+    lines: &[LedgerLine],
+    config: &ReconciliationConfig,
+) -> Reconciliation {
+    let mut outstanding = items.iter().map(|item| item.amt).collect::<Vec<_>>();
+    let mut matched = vec![false; items.len()];
+    let mut reconciled = HashMap::new();
+    let mut reconciliation = Reconciliation::default();
+
+    for line in lines {
+        let key = line_key(line);
+        let reported = key.as_ref().and_then(|key| reconciled.get(key)).copied();
+        let found = reported
+            .or_else(|| match_by_reference(items, line))
+            .or_else(|| {
+                match_by_amount(items, &outstanding, line, config)
+                    .map(|index| (index, MatchMethod::Amount))
+            });
+
+        let (index, method) = match found {
+            Some(found) => found,
+            None => {
+                reconciliation.unmatched_lines.push(line.clone());
+                continue;
+            }
+        };
+
+        let status = if reported.is_some()
+            || (matched[index] && outstanding[index] <= config.amt_tolerance)
+        {
+            MatchStatus::Duplicate
+        } else {
+            let difference = line.amt - outstanding[index];
+            outstanding[index] -= line.amt;
+
+            if difference.abs() <= config.amt_tolerance {
+                MatchStatus::Full
+            } else if difference < 0.0 {
+                MatchStatus::Partial
+            } else {
+                MatchStatus::Overpaid
+            }
+        };
+
+        if let Some(key) = key {
+            reconciled.insert(key, (index, method));
+        }
+        matched[index] = true;
+        reconciliation.matches.push(ReconciliationMatch {
+            item_id: items[index].id.clone(),
+            line: line.clone(),
+            method,
+            status,
+            outstanding: outstanding[index].max(0.0),
+        });
+    }
+
+    reconciliation.unmatched_items = items
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(item, _)| item.clone())
+        .collect();
+
+    reconciliation
+}
+
+/// Return the first item of the direction and currency of the line with a matching reference.
+fn match_by_reference(items: &[OpenItem], line: &LedgerLine) -> Option<(usize, MatchMethod)> {
+    let candidates = || {
+        items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| settles(item, line))
+    };
+
+    let end_to_end_id = line
+        .end_to_end_id
+        .as_deref()
+        .filter(|end_to_end_id| *end_to_end_id != NOT_PROVIDED);
+
+    if let Some(end_to_end_id) = end_to_end_id {
+        let found =
+            candidates().find(|(_, item)| item.end_to_end_id.as_deref() == Some(end_to_end_id));
+
+        if let Some((index, _)) = found {
+            return Some((index, MatchMethod::EndToEndId));
+        }
+    }
+
+    if let Some(acct_svcr_ref) = &line.acct_svcr_ref {
+        let found =
+            candidates().find(|(_, item)| item.acct_svcr_ref.as_ref() == Some(acct_svcr_ref));
+
+        if let Some((index, _)) = found {
+            return Some((index, MatchMethod::AcctSvcrRef));
+        }
+    }
+
+    let cdtr_ref = line.cdtr_ref.as_deref().map(normalize);
+    let rmt_inf = line.rmt_inf.as_deref().map(tokens).unwrap_or_default();
+
+    candidates()
+        .find(|(_, item)| match item.cdtr_ref.as_deref().map(normalize) {
+            Some(item_ref) if !item_ref.is_empty() => {
+                cdtr_ref.as_deref() == Some(item_ref.as_str()) || mentions(&rmt_inf, &item_ref)
+            }
+            _ => false,
+        })
+        .map(|(index, _)| (index, MatchMethod::CdtrRef))
+}
+
+/// Return the only outstanding item of the direction, currency and amount of the line,
+/// with an expected date within the date tolerance of the line.
+fn match_by_amount(
+    items: &[OpenItem],
+    outstanding: &[f64],
+    line: &LedgerLine,
+    config: &ReconciliationConfig,
+) -> Option<usize> {
+    let line_dt = line.val_dt.or(line.bookg_dt);
+
+    let mut candidates = items.iter().enumerate().filter(|(index, item)| {
+        let within_dt = match (item.dt, line_dt) {
+            (Some(dt), Some(line_dt)) => {
+                (line_dt - dt).num_days().abs() <= config.dt_tolerance_days
+            }
+            _ => true,
+        };
+
+        settles(item, line)
+            && within_dt
+            && (outstanding[*index] - line.amt).abs() <= config.amt_tolerance
+    });
+
+    match (candidates.next(), candidates.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+/// Return true if the line is of the direction and currency of the item.
+fn settles(item: &OpenItem, line: &LedgerLine) -> bool {
+    item.direction.cdt_dbt_ind() == line.cdt_dbt_ind && item.ccy == line.ccy
+}
+
+/// Return the key identifying a line reported more than once, or `None` for a line without
+/// any reference.
+fn line_key(line: &LedgerLine) -> Option<String> {
+    let end_to_end_id = line
+        .end_to_end_id
+        .as_deref()
+        .filter(|end_to_end_id| *end_to_end_id != NOT_PROVIDED);
+
+    if line.acct_svcr_ref.is_none()
+        && end_to_end_id.is_none()
+        && line.tx_id.is_none()
+        && line.uetr.is_none()
+    {
+        return None;
+    }
+
+    let amt = line.amt.to_string();
+
+    Some(
+        [
+            line.acct.as_deref(),
+            line.acct_svcr_ref.as_deref(),
+            end_to_end_id,
+            line.tx_id.as_deref(),
+            line.uetr.as_deref(),
+            Some(line.cdt_dbt_ind.as_str()),
+            Some(line.ccy.as_str()),
+            Some(amt.as_str()),
+        ]
+        .iter()
+        .map(|field| field.unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\t"),
+    )
+}
+
+/// Split unstructured remittance information into upper case tokens, e.g. `INV-2` of
+/// `Payment for inv-2, part 1`.
+fn tokens(rmt_inf: &str) -> Vec<String> {
+    rmt_inf
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '/'))
+        .filter(|token| !token.is_empty())
+        .map(str::to_uppercase)
+        .collect()
+}
+
+/// Return true if the tokens mention the normalized reference as a whole token, or as
+/// consecutive tokens, e.g. a creditor reference written in groups of four characters.
+/// A reference is never matched as part of a token, so `INV-1` is not mentioned by `INV-10`.
+fn mentions(tokens: &[String], reference: &str) -> bool {
+    (0..tokens.len()).any(|start| {
+        let mut joined = String::new();
+
+        for token in &tokens[start..] {
+            joined.push_str(token);
+            if joined.len() >= reference.len() {
+                break;
+            }
+        }
+
+        joined == reference
+    })
+}
+
+/// Normalize a reference for comparison, i.e. without spaces and in upper case.
+fn normalize(reference: &str) -> String {
+    reference
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(amt: f64, cdt_dbt_ind: &str) -> LedgerLine {
+        LedgerLine {
+            stmt_id: "STMT-1".to_string(),
+            amt,
+            ccy: "EUR".to_string(),
+            cdt_dbt_ind: cdt_dbt_ind.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 6, day).unwrap()
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let items = vec![
+            OpenItem::receivable("INV-1", 100.0, "EUR").with_cdtr_ref("RF18 5390 0754 7034"),
+            OpenItem::receivable("INV-2", 200.0, "EUR").with_cdtr_ref("INV-2"),
+            OpenItem::payable("PAY-1", 50.0, "EUR").with_end_to_end_id("E2E-1"),
+            OpenItem::receivable("INV-3", 300.0, "EUR").with_dt(date(9)),
+            OpenItem::receivable("INV-4", 10.0, "EUR"),
+        ];
+
+        let lines = vec![
+            LedgerLine {
+                acct_svcr_ref: Some("REF-1".to_string()),
+                cdtr_ref: Some("RF18539007547034".to_string()),
+                ..line(100.0, "CRDT")
+            },
+            LedgerLine {
+                acct_svcr_ref: Some("REF-2".to_string()),
+                rmt_inf: Some("Payment for inv-2, part 1".to_string()),
+                ..line(150.0, "CRDT")
+            },
+            LedgerLine {
+                end_to_end_id: Some("E2E-1".to_string()),
+                ..line(50.0, "DBIT")
+            },
+            // The first line, reported again by the statement
+            LedgerLine {
+                acct_svcr_ref: Some("REF-1".to_string()),
+                cdtr_ref: Some("RF18539007547034".to_string()),
+                ..line(100.0, "CRDT")
+            },
+            LedgerLine {
+                val_dt: Some(date(20)),
+                ..line(75.0, "CRDT")
+            },
+            LedgerLine {
+                bookg_dt: Some(date(10)),
+                ..line(300.0, "CRDT")
+            },
+        ];
+
+        let reconciliation = reconcile(&items, &lines, &ReconciliationConfig::default());
+
+        assert_eq!(
+            reconciliation
+                .matches
+                .iter()
+                .map(|m| (m.item_id.as_str(), m.method, m.status, m.outstanding))
+                .collect::<Vec<_>>(),
+            vec![
+                ("INV-1", MatchMethod::CdtrRef, MatchStatus::Full, 0.0),
+                ("INV-2", MatchMethod::CdtrRef, MatchStatus::Partial, 50.0),
+                ("PAY-1", MatchMethod::EndToEndId, MatchStatus::Full, 0.0),
+                ("INV-1", MatchMethod::CdtrRef, MatchStatus::Duplicate, 0.0),
+                ("INV-3", MatchMethod::Amount, MatchStatus::Full, 0.0),
+            ]
+        );
+        assert_eq!(reconciliation.unmatched_lines.len(), 1);
+        assert_eq!(reconciliation.unmatched_lines[0].amt, 75.0);
+        assert_eq!(
+            reconciliation
+                .unmatched_items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["INV-4"]
+        );
+        assert_eq!(reconciliation.with_status(MatchStatus::Full).count(), 3);
+    }
+
+    #[test]
+    fn test_match_by_amount_requires_single_item() {
+        let items = vec![
+            OpenItem::receivable("INV-1", 100.0, "EUR"),
+            OpenItem::receivable("INV-2", 100.0, "EUR"),
+            OpenItem::receivable("INV-3", 100.0, "USD").with_dt(date(1)),
+        ];
+        let lines = vec![line(100.0, "CRDT")];
+
+        let reconciliation = reconcile(&items, &lines, &ReconciliationConfig::default());
+        assert!(reconciliation.matches.is_empty());
+        assert_eq!(reconciliation.unmatched_items.len(), 3);
+
+        // Only the item within the date tolerance qualifies
+        let lines = vec![LedgerLine {
+            ccy: "USD".to_string(),
+            val_dt: Some(date(3)),
+            ..line(100.004, "CRDT")
+        }];
+
+        let reconciliation = reconcile(&items, &lines, &ReconciliationConfig::default());
+        assert_eq!(reconciliation.matches[0].item_id, "INV-3");
+        assert_eq!(reconciliation.matches[0].status, MatchStatus::Full);
+    }
+
+    #[test]
+    fn test_match_by_reference_whole_token() {
+        let items = vec![
+            OpenItem::receivable("INV-1", 100.0, "EUR").with_cdtr_ref("INV-1"),
+            OpenItem::receivable("INV-10", 100.0, "EUR").with_cdtr_ref("INV-10"),
+            OpenItem::receivable("INV-11", 80.0, "EUR").with_cdtr_ref("RF18 5390 0754 7034"),
+        ];
+        let lines = vec![
+            LedgerLine {
+                rmt_inf: Some("Invoice INV-10".to_string()),
+                ..line(100.0, "CRDT")
+            },
+            LedgerLine {
+                rmt_inf: Some("Ref. RF18 5390 0754 7034.".to_string()),
+                ..line(40.0, "CRDT")
+            },
+        ];
+
+        let reconciliation = reconcile(&items, &lines, &ReconciliationConfig::default());
+        assert_eq!(
+            reconciliation
+                .matches
+                .iter()
+                .map(|m| (m.item_id.as_str(), m.method))
+                .collect::<Vec<_>>(),
+            vec![
+                ("INV-10", MatchMethod::CdtrRef),
+                ("INV-11", MatchMethod::CdtrRef)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lines_without_reference_are_not_duplicates() {
+        let items = vec![OpenItem::receivable("INV-1", 100.0, "EUR").with_cdtr_ref("INV-1")];
+        let lines = vec![
+            LedgerLine {
+                acct: Some("DE89370400440532013000".to_string()),
+                rmt_inf: Some("INV-1".to_string()),
+                ..line(50.0, "CRDT")
+            },
+            LedgerLine {
+                acct: Some("DE89370400440532013000".to_string()),
+                rmt_inf: Some("INV-1".to_string()),
+                ..line(50.0, "CRDT")
+            },
+        ];
+
+        let reconciliation = reconcile(&items, &lines, &ReconciliationConfig::default());
+        assert_eq!(
+            reconciliation
+                .matches
+                .iter()
+                .map(|m| (m.status, m.outstanding))
+                .collect::<Vec<_>>(),
+            vec![(MatchStatus::Partial, 50.0), (MatchStatus::Full, 0.0)]
+        );
+    }
+}
@@ -0,0 +1,758 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # SWIFT Alliance DataPDU
+//!
+//! The `DataPdu` type is the `Saa:DataPDU` of the SWIFT Alliance XML v2 format, which
+//! wraps the application header `AppHdr` and the `Document` of a message exchanged with
+//! Alliance Access or Alliance Lite2 through the MX file interface.
+//!
+//! The DataPDU is serialized with the `Saa` prefix, and parsed by the local names of its
+//! elements, i.e. with any prefix or the default namespace. The application header may be
+//! of any supported version, see `AnyAppHdr`.
+//!
+//! ```rust
+//! use iso_20022_sdk::prelude::*;
+//! use iso_20022_sdk::saa::{Acknowledgement, DataPdu, NetworkProfile};
+//!
+//! let profile = NetworkProfile {
+//!     service: "swift.finplus".to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! // The sender and receiver DNs are derived from the BICs of `Fr` and `To`,
+//! // and the `MessageIdentifier` from the `MsgDefIdr` of the application header
+//! let xml = DataPdu::from_message(&msg, &profile)?.to_xml()?;
+//!
+//! // Unwrap an inbound DataPDU, or read the transmission report of an outbound message
+//! let pdu = DataPdu::<Document, EcdsaSignature>::from_xml(&xml)?;
+//! match pdu.transmission_report().and_then(|report| report.acknowledgement()) {
+//!     Some(Acknowledgement::Nak { error_code }) => { /* the message was rejected */ }
+//!     _ => {}
+//! }
+//! ```
+//!
+//! #### Example XML Output
+//! ```xml
+//! <Saa:DataPDU xmlns:Saa="urn:swift:saa:xsd:saa.2.0">
+//!     <Saa:Revision>2.0.13</Saa:Revision>
+//!     <Saa:Header>
+//!         <Saa:Message>
+//!             <Saa:SenderReference>MSG-1</Saa:SenderReference>
+//!             <Saa:MessageIdentifier>pacs.008.001.08</Saa:MessageIdentifier>
+//!             <Saa:Format>MX</Saa:Format>
+//!             <Saa:Sender>
+//!                 <Saa:DN>o=aaaaus33,o=swift</Saa:DN>
+//!                 <Saa:FullName><Saa:X1>AAAAUS33XXX</Saa:X1></Saa:FullName>
+//!             </Saa:Sender>
+//!             <!-- Receiver, NetworkInfo and SecurityInfo -->
+//!         </Saa:Message>
+//!     </Saa:Header>
+//!     <Saa:Body>
+//!         <AppHdr><!-- Business Application Header --></AppHdr>
+//!         <Document><!-- Document (ISO-20022 Message) --></Document>
+//!     </Saa:Body>
+//! </Saa:DataPDU>
+//! ```
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::header::{AnyAppHdr, BusinessHeader, HeaderParty, HeaderVersion};
+use crate::message::Message;
+
+/// Namespace of the SWIFT Alliance XML v2 format.
+pub const NAMESPACE: &str = "urn:swift:saa:xsd:saa.2.0";
+
+/// Revision of the SWIFT Alliance XML v2 format.
+pub const REVISION: &str = "2.0.13";
+
+/// Format of an ISO 20022 message.
+const FORMAT: &str = "MX";
+
+/// Branch code of the main office of a BIC.
+const MAIN_OFFICE: &str = "XXX";
+
+lazy_static! {
+    /// Field `451` of a `PseudoAckNack`, i.e. `0` for an ACK and `1` for a NAK.
+    static ref ACCEPT_REJECT: Regex = Regex::new(r"\{451:([01])\}").unwrap();
+    /// Field `405` of a `PseudoAckNack`, i.e. the error code of a NAK, e.g. `T13`.
+    static ref REJECT_REASON: Regex = Regex::new(r"\{405:([^}]*)\}").unwrap();
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// The party of the application header, i.e. `Fr` or `To`, has no BIC
+    #[error("missing BIC of the application header party {0}")]
+    MissingBic(&'static str),
+    /// The BIC of the application header party is not 8 or 11 alphanumeric characters
+    #[error("invalid BIC: {0}")]
+    InvalidBic(String),
+    /// The DataPDU has no `Body`, e.g. a transmission report
+    #[error("missing DataPDU body")]
+    MissingBody,
+}
+
+/// Return the namespace of the SWIFT Alliance XML v2 format.
+pub fn namespace() -> String {
+    NAMESPACE.to_string()
+}
+
+/// Return the revision of the SWIFT Alliance XML v2 format.
+pub fn revision() -> String {
+    REVISION.to_string()
+}
+
+/// Network profile used to populate the `NetworkInfo` and `SecurityInfo` of the DataPDU.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkProfile {
+    /// SWIFTNet service, e.g. `swift.finplus`
+    pub service: String,
+    /// Whether the message is signed by Alliance Access, i.e. `IsSigned`
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize)]
+#[serde(rename = "Saa:DataPDU")]
+pub struct DataPdu<
+    Doc: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    Sig: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(
+        rename(serialize = "Saa:Revision", deserialize = "Revision"),
+        default = "revision"
+    )]
+    pub revision: String,
+    #[serde(rename(serialize = "Saa:Header", deserialize = "Header"))]
+    pub header: Header,
+    #[serde(
+        rename(serialize = "Saa:Body", deserialize = "Body"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub body: Option<Body<Doc, AnyAppHdr<Sig>>>,
+    #[serde(rename = "@xmlns:Saa", default = "namespace")]
+    pub xmlns: String,
+}
+
+/// DataPDU with the application header type of the header version, e.g. `head_001_001_02::AppHdr`,
+/// which is parsed and converted into a `DataPdu`.
+#[derive(::serde::Deserialize)]
+struct VersionedDataPdu<
+    Doc: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    Hdr: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(rename = "Revision", default = "revision")]
+    revision: String,
+    #[serde(rename = "Header")]
+    header: Header,
+    #[serde(rename = "Body")]
+    body: Option<Body<Doc, Hdr>>,
+    #[serde(rename = "@xmlns:Saa", default = "namespace")]
+    xmlns: String,
+}
+
+/// Header of the DataPDU, i.e. the header of a message or a transmission report.
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Header {
+    #[serde(
+        rename(serialize = "Saa:Message", deserialize = "Message"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub message: Option<MessageHeader>,
+    #[serde(
+        rename(
+            serialize = "Saa:TransmissionReport",
+            deserialize = "TransmissionReport"
+        ),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transmission_report: Option<TransmissionReport>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct MessageHeader {
+    /// Reference of the message, i.e. the `BizMsgIdr` of the application header
+    #[serde(rename(serialize = "Saa:SenderReference", deserialize = "SenderReference"))]
+    pub sender_reference: String,
+    /// Message definition identifier, e.g. `pacs.008.001.08`
+    #[serde(rename(serialize = "Saa:MessageIdentifier", deserialize = "MessageIdentifier"))]
+    pub message_identifier: String,
+    /// Format of the message, i.e. `MX`
+    #[serde(rename(serialize = "Saa:Format", deserialize = "Format"))]
+    pub format: String,
+    #[serde(rename(serialize = "Saa:Sender", deserialize = "Sender"))]
+    pub sender: Party,
+    #[serde(rename(serialize = "Saa:Receiver", deserialize = "Receiver"))]
+    pub receiver: Party,
+    #[serde(
+        rename(serialize = "Saa:NetworkInfo", deserialize = "NetworkInfo"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub network_info: Option<NetworkInfo>,
+    #[serde(
+        rename(serialize = "Saa:SecurityInfo", deserialize = "SecurityInfo"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub security_info: Option<SecurityInfo>,
+}
+
+/// Sender or receiver of the message.
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Party {
+    /// Distinguished name, e.g. `o=aaaaus33,o=swift`
+    #[serde(rename(serialize = "Saa:DN", deserialize = "DN"))]
+    pub dn: String,
+    #[serde(
+        rename(serialize = "Saa:FullName", deserialize = "FullName"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub full_name: Option<FullName>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct FullName {
+    /// BIC of the party, with its branch code, e.g. `AAAAUS33XXX`
+    #[serde(rename(serialize = "Saa:X1", deserialize = "X1"))]
+    pub x1: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct NetworkInfo {
+    #[serde(
+        rename(serialize = "Saa:Priority", deserialize = "Priority"),
+        default,
+        with = "text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority: Option<Priority>,
+    #[serde(
+        rename(
+            serialize = "Saa:IsPossibleDuplicate",
+            deserialize = "IsPossibleDuplicate"
+        ),
+        default
+    )]
+    pub is_possible_duplicate: bool,
+    /// SWIFTNet service, e.g. `swift.finplus`
+    #[serde(rename(serialize = "Saa:Service", deserialize = "Service"))]
+    pub service: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum Priority {
+    #[default]
+    Normal,
+    Urgent,
+    System,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct SecurityInfo {
+    #[serde(rename(
+        serialize = "Saa:SWIFTNetSecurityInfo",
+        deserialize = "SWIFTNetSecurityInfo"
+    ))]
+    pub swift_net_security_info: SwiftNetSecurityInfo,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct SwiftNetSecurityInfo {
+    #[serde(rename(serialize = "Saa:IsSigned", deserialize = "IsSigned"), default)]
+    pub is_signed: bool,
+}
+
+/// Body of the DataPDU, i.e. the application header and the document of the message.
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Body<
+    Doc: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+    Hdr: std::fmt::Debug + Default + Clone + PartialEq + ::serde::Serialize + ::validator::Validate,
+> {
+    #[serde(rename = "AppHdr", skip_serializing_if = "Option::is_none")]
+    pub app_hdr: Option<Hdr>,
+    #[serde(rename = "Document")]
+    pub document: Doc,
+}
+
+/// Transmission report of an outbound message, with the network delivery status of the
+/// message and the interventions of Alliance Access, e.g. the ACK or NAK of the network.
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct TransmissionReport {
+    /// Reference of the reported message, i.e. its `SenderReference`
+    #[serde(rename(serialize = "Saa:SenderReference", deserialize = "SenderReference"))]
+    pub sender_reference: String,
+    #[serde(
+        rename(
+            serialize = "Saa:ReconciliationInfo",
+            deserialize = "ReconciliationInfo"
+        ),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reconciliation_info: Option<String>,
+    #[serde(
+        rename(
+            serialize = "Saa:NetworkDeliveryStatus",
+            deserialize = "NetworkDeliveryStatus"
+        ),
+        with = "text"
+    )]
+    pub network_delivery_status: NetworkDeliveryStatus,
+    #[serde(
+        rename(
+            serialize = "Saa:OriginalInstanceAddressee",
+            deserialize = "OriginalInstanceAddressee"
+        ),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub original_instance_addressee: Option<FullName>,
+    #[serde(
+        rename(
+            serialize = "Saa:ReportingApplication",
+            deserialize = "ReportingApplication"
+        ),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reporting_application: Option<String>,
+    #[serde(
+        rename(serialize = "Saa:Interventions", deserialize = "Interventions"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub interventions: Option<Interventions>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+pub enum NetworkDeliveryStatus {
+    NetworkAcked,
+    NetworkNacked,
+    NetworkTimedOut,
+    NetworkAborted,
+    NetworkRejectedLocally,
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Interventions {
+    #[serde(
+        rename(serialize = "Saa:Intervention", deserialize = "Intervention"),
+        default
+    )]
+    pub intervention: Vec<Intervention>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Intervention {
+    /// Category of the intervention, e.g. `TransmissionReport`
+    #[serde(rename(serialize = "Saa:IntvCategory", deserialize = "IntvCategory"))]
+    pub intv_category: String,
+    /// Creation time of the intervention, e.g. `20230418170214`
+    #[serde(
+        rename(serialize = "Saa:CreationTime", deserialize = "CreationTime"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub creation_time: Option<String>,
+    #[serde(
+        rename(serialize = "Saa:Contents", deserialize = "Contents"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub contents: Option<Contents>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Contents {
+    #[serde(rename = "AckNack", skip_serializing_if = "Option::is_none")]
+    pub ack_nack: Option<AckNack>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct AckNack {
+    /// ACK or NAK of the network as a FIN block 4, e.g. `{1:F21...}{4:{177:...}{451:0}}`
+    #[serde(rename = "PseudoAckNack")]
+    pub pseudo_ack_nack: String,
+}
+
+/// ACK or NAK of the network of an outbound message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Acknowledgement {
+    Ack,
+    /// NAK with the error code of field `405`, e.g. `T13`
+    Nak {
+        error_code: Option<String>,
+    },
+}
+
+impl AckNack {
+    /// Return the ACK or NAK of field `451`, or `None` without the field.
+    pub fn acknowledgement(&self) -> Option<Acknowledgement> {
+        let accept_reject = ACCEPT_REJECT.captures(&self.pseudo_ack_nack)?;
+
+        match &accept_reject[1] {
+            "0" => Some(Acknowledgement::Ack),
+            _ => Some(Acknowledgement::Nak {
+                error_code: REJECT_REASON
+                    .captures(&self.pseudo_ack_nack)
+                    .map(|captures| captures[1].to_string()),
+            }),
+        }
+    }
+}
+
+impl TransmissionReport {
+    /// Return the ACK or NAK of the network, from the `PseudoAckNack` of the interventions,
+    /// or else from the network delivery status. Returns `None` for a message that was not
+    /// acknowledged, e.g. `NetworkTimedOut`.
+    pub fn acknowledgement(&self) -> Option<Acknowledgement> {
+        let acknowledgement = self
+            .interventions
+            .iter()
+            .flat_map(|interventions| interventions.intervention.iter())
+            .filter_map(|intervention| intervention.contents.as_ref()?.ack_nack.as_ref())
+            .find_map(AckNack::acknowledgement);
+
+        acknowledgement.or(match self.network_delivery_status {
+            NetworkDeliveryStatus::NetworkAcked => Some(Acknowledgement::Ack),
+            NetworkDeliveryStatus::NetworkNacked => Some(Acknowledgement::Nak { error_code: None }),
+            _ => None,
+        })
+    }
+}
+
+impl<Doc, Sig> DataPdu<Doc, Sig>
+where
+    Doc: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::de::DeserializeOwned
+        + ::validator::Validate,
+    Sig: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + ::serde::Serialize
+        + ::serde::de::DeserializeOwned
+        + ::validator::Validate
+        + crate::crypto::XmlSignature,
+{
+    /// Wrap the application header and the document of the message in a DataPDU.
+    ///
+    /// The `SenderReference` is the `BizMsgIdr`, the `MessageIdentifier` the `MsgDefIdr`,
+    /// and the sender and receiver DNs are derived from the BICs of `Fr` and `To`. The
+    /// `Prty` and the resend flags of the application header, i.e. `PssblDplct` or the
+    /// `DUPL` of `CpyDplct`, are carried in the `NetworkInfo`.
+    pub fn from_message(
+        msg: &Message<'_, Doc, Sig>,
+        profile: &NetworkProfile,
+    ) -> Result<Self, Error> {
+        let app_hdr = msg.any_app_hdr().unwrap_or_default();
+
+        let prty = match &app_hdr {
+            AnyAppHdr::V01(hdr) => hdr.value.prty.as_ref().map(|prty| prty.value.as_str()),
+            AnyAppHdr::V02(hdr) => hdr.value.prty.as_ref().map(|prty| prty.value.as_str()),
+            AnyAppHdr::V03(hdr) => hdr.value.prty.as_ref().map(|prty| prty.value.as_str()),
+        };
+        let priority = prty.map(|prty| match prty {
+            "HIGH" | "URGT" => Priority::Urgent,
+            _ => Priority::Normal,
+        });
+
+        let message = MessageHeader {
+            sender_reference: app_hdr.biz_msg_idr().to_string(),
+            message_identifier: app_hdr.msg_def_idr().to_string(),
+            format: FORMAT.to_string(),
+            sender: party(app_hdr.sender(), "Fr")?,
+            receiver: party(app_hdr.receiver(), "To")?,
+            network_info: Some(NetworkInfo {
+                priority,
+                is_possible_duplicate: app_hdr.is_possible_duplicate(),
+                service: profile.service.clone(),
+            }),
+            security_info: Some(SecurityInfo {
+                swift_net_security_info: SwiftNetSecurityInfo {
+                    is_signed: profile.signed,
+                },
+            }),
+        };
+
+        Ok(Self {
+            revision: revision(),
+            header: Header {
+                message: Some(message),
+                transmission_report: None,
+            },
+            body: Some(Body {
+                app_hdr: msg.any_app_hdr(),
+                document: msg.document(),
+            }),
+            xmlns: namespace(),
+        })
+    }
+
+    /// Unwrap the application header and the document of the DataPDU into a message.
+    /// Returns `Error::MissingBody` for a DataPDU without message, e.g. a transmission report.
+    pub fn into_message<'a>(self) -> Result<Message<'a, Doc, Sig>, Error> {
+        let body = self.body.ok_or(Error::MissingBody)?;
+
+        let mut msg = Message::builder();
+        if let Some(app_hdr) = body.app_hdr {
+            msg = msg.set_any_app_hdr(app_hdr);
+        }
+        msg.inner.value.doc.value = body.document;
+
+        Ok(msg)
+    }
+
+    /// Return the transmission report of the DataPDU, if any.
+    pub fn transmission_report(&self) -> Option<&TransmissionReport> {
+        self.header.transmission_report.as_ref()
+    }
+
+    /// Return the serialized xml string of the DataPDU.
+    pub fn to_xml(&self) -> Result<String, Error> {
+        Ok(quick_xml::se::to_string(self)?)
+    }
+
+    /// Parse a DataPDU from the xml string, detecting the version of the application
+    /// header from the namespace of the `AppHdr` element.
+    pub fn from_xml(xml_string: &str) -> Result<Self, Error> {
+        match HeaderVersion::detect(xml_string) {
+            Ok(HeaderVersion::V01) => Self::parse(xml_string, AnyAppHdr::V01),
+            Ok(HeaderVersion::V02) => Self::parse(xml_string, AnyAppHdr::V02),
+            _ => Self::parse(xml_string, AnyAppHdr::V03),
+        }
+    }
+
+    /// Deserialize the DataPDU with the application header type of the
+    /// header version, e.g. `head_001_001_02::AppHdr`
+    fn parse<Hdr>(xml_string: &str, any_app_hdr: fn(Hdr) -> AnyAppHdr<Sig>) -> Result<Self, Error>
+    where
+        Hdr: std::fmt::Debug
+            + Default
+            + Clone
+            + PartialEq
+            + ::serde::Serialize
+            + ::serde::de::DeserializeOwned
+            + ::validator::Validate,
+    {
+        let pdu: VersionedDataPdu<Doc, Hdr> = quick_xml::de::from_str(xml_string)?;
+
+        Ok(Self {
+            revision: pdu.revision,
+            header: pdu.header,
+            body: pdu.body.map(|body| Body {
+                app_hdr: body.app_hdr.map(any_app_hdr),
+                document: body.document,
+            }),
+            xmlns: pdu.xmlns,
+        })
+    }
+}
+
+/// (De)serializes an enumeration as the text content of its element, e.g.
+/// `<Saa:Priority>Urgent</Saa:Priority>`, rather than as an element named by the variant.
+mod text {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Text<T> {
+        #[serde(rename = "$text")]
+        value: T,
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Text { value }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        Text::deserialize(deserializer).map(|text| text.value)
+    }
+}
+
+/// Return the distinguished name of the BIC, e.g. `o=aaaaus33,o=swift` for `AAAAUS33XXX`,
+/// or `ou=nyc,o=aaaaus33,o=swift` for the branch `AAAAUS33NYC`. Returns `None` if the BIC
+/// is not 8 or 11 ASCII alphanumeric characters.
+pub fn distinguished_name(bic: &str) -> Option<String> {
+    if !matches!(bic.len(), 8 | 11) || !bic.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let bic = bic.to_ascii_lowercase();
+    let (bic8, branch) = bic.split_at(8);
+
+    match branch {
+        "" | "xxx" => Some(format!("o={},o=swift", bic8)),
+        branch => Some(format!("ou={},o={},o=swift", branch, bic8)),
+    }
+}
+
+/// Return the DataPDU party of the BIC of an application header party, e.g. `Fr`.
+fn party(party: HeaderParty, element: &'static str) -> Result<Party, Error> {
+    let bic = party.bic.ok_or(Error::MissingBic(element))?;
+    let dn = distinguished_name(&bic).ok_or_else(|| Error::InvalidBic(bic.clone()))?;
+    let x1 = match bic.len() {
+        8 => format!("{}{}", bic, MAIN_OFFICE),
+        _ => bic,
+    };
+
+    Ok(Party {
+        dn,
+        full_name: Some(FullName { x1 }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecdsa::EcdsaSignature;
+    use crate::documents::Dmkr;
+    use crate::head::head_001_001_03 as head;
+
+    fn fi_id(bic: &str) -> head::FinancialInstitutionIdentification18 {
+        head::FinancialInstitutionIdentification18 {
+            bicfi: Some(head::BicfiDec2014Identifier {
+                value: bic.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_distinguished_name() {
+        assert_eq!(
+            distinguished_name("AAAAUS33XXX").as_deref(),
+            Some("o=aaaaus33,o=swift")
+        );
+        assert_eq!(
+            distinguished_name("AAAAUS33").as_deref(),
+            Some("o=aaaaus33,o=swift")
+        );
+        assert_eq!(
+            distinguished_name("AAAAUS33NYC").as_deref(),
+            Some("ou=nyc,o=aaaaus33,o=swift")
+        );
+        assert_eq!(distinguished_name("AAAAUS3"), None);
+        assert_eq!(distinguished_name("AAAÄUS33"), None);
+        assert_eq!(distinguished_name("AAAAUS33İ"), None);
+    }
+
+    #[test]
+    fn test_data_pdu() -> Result<(), Error> {
+        let msg = Message::<Dmkr, EcdsaSignature>::builder()
+            .set_sender_fi_id(fi_id("AAAAUS33"))
+            .set_recipient_fi_id(fi_id("BBBBGB22LON"))
+            .set_msg_def_idr(head::Max35Text {
+                value: "pacs.008.001.08".to_string(),
+            })
+            .set_biz_msg_idr(head::Max35Text {
+                value: "MSG-1".to_string(),
+            });
+        let profile = NetworkProfile {
+            service: "swift.finplus".to_string(),
+            signed: true,
+        };
+
+        let pdu = DataPdu::from_message(&msg, &profile)?;
+        let message = pdu.header.message.as_ref().unwrap();
+        assert_eq!(message.sender_reference, "MSG-1");
+        assert_eq!(message.message_identifier, "pacs.008.001.08");
+        assert_eq!(message.sender.dn, "o=aaaaus33,o=swift");
+        assert_eq!(message.sender.full_name.as_ref().unwrap().x1, "AAAAUS33XXX");
+        assert_eq!(message.receiver.dn, "ou=lon,o=bbbbgb22,o=swift");
+
+        let xml = pdu.to_xml()?;
+        assert!(xml.starts_with(r#"<Saa:DataPDU xmlns:Saa="urn:swift:saa:xsd:saa.2.0">"#));
+        assert!(xml.contains("<Saa:Service>swift.finplus</Saa:Service>"));
+
+        let msg = pdu.into_message()?;
+        assert_eq!(
+            msg.app_hdr().unwrap_or_default().value.biz_msg_idr.value,
+            "MSG-1"
+        );
+
+        // The application header keeps its version through the DataPDU
+        let (app_hdr, _) = msg
+            .any_app_hdr()
+            .unwrap()
+            .convert(HeaderVersion::V02)
+            .unwrap();
+        let msg = msg.set_any_app_hdr(app_hdr);
+        let xml = DataPdu::from_message(&msg, &profile)?.to_xml()?;
+        let pdu = DataPdu::<Dmkr, EcdsaSignature>::from_xml(&xml)?;
+        let app_hdr = pdu.body.as_ref().unwrap().app_hdr.as_ref().unwrap();
+        assert_eq!(app_hdr.version(), HeaderVersion::V02);
+        assert_eq!(app_hdr.biz_msg_idr(), "MSG-1");
+        assert_eq!(app_hdr.sender().bic.as_deref(), Some("AAAAUS33"));
+
+        assert!(matches!(
+            DataPdu::from_message(&Message::<Dmkr, EcdsaSignature>::builder(), &profile),
+            Err(Error::MissingBic("Fr"))
+        ));
+        assert!(matches!(
+            DataPdu::from_message(
+                &Message::<Dmkr, EcdsaSignature>::builder()
+                    .set_sender_fi_id(fi_id("AAAÄUS33"))
+                    .set_recipient_fi_id(fi_id("BBBBGB22")),
+                &profile
+            ),
+            Err(Error::InvalidBic(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transmission_report() -> Result<(), Error> {
+        let xml = r#"<Saa:DataPDU xmlns:Saa="urn:swift:saa:xsd:saa.2.0"><Saa:Revision>2.0.13</Saa:Revision><Saa:Header><Saa:TransmissionReport><Saa:SenderReference>MSG-1</Saa:SenderReference><Saa:NetworkDeliveryStatus>NetworkNacked</Saa:NetworkDeliveryStatus><Saa:Interventions><Saa:Intervention><Saa:IntvCategory>TransmissionReport</Saa:IntvCategory><Saa:CreationTime>20230418170214</Saa:CreationTime><Saa:Contents><AckNack><PseudoAckNack>{1:F21AAAAUS33AXXX0000000000}{4:{177:2304181702}{451:1}{405:T13}}</PseudoAckNack></AckNack></Saa:Contents></Saa:Intervention></Saa:Interventions></Saa:TransmissionReport></Saa:Header></Saa:DataPDU>"#;
+
+        let pdu = DataPdu::<Dmkr, EcdsaSignature>::from_xml(xml)?;
+        let report = pdu.transmission_report().unwrap();
+        assert_eq!(report.sender_reference, "MSG-1");
+        assert_eq!(
+            report.network_delivery_status,
+            NetworkDeliveryStatus::NetworkNacked
+        );
+        assert_eq!(
+            report.acknowledgement(),
+            Some(Acknowledgement::Nak {
+                error_code: Some("T13".to_string())
+            })
+        );
+        assert!(matches!(pdu.into_message(), Err(Error::MissingBody)));
+
+        // The elements are matched by their local names, e.g. with the default namespace
+        let xml = r#"<DataPDU xmlns="urn:swift:saa:xsd:saa.2.0"><Revision>2.0.13</Revision><Header><TransmissionReport><SenderReference>MSG-2</SenderReference><NetworkDeliveryStatus>NetworkAcked</NetworkDeliveryStatus></TransmissionReport></Header></DataPDU>"#;
+
+        let pdu = DataPdu::<Dmkr, EcdsaSignature>::from_xml(xml)?;
+        let report = pdu.transmission_report().unwrap();
+        assert_eq!(report.sender_reference, "MSG-2");
+        assert_eq!(report.acknowledgement(), Some(Acknowledgement::Ack));
+
+        let ack = AckNack {
+            pseudo_ack_nack: "{1:F21AAAAUS33AXXX0000000000}{4:{177:2304181702}{451:0}}".to_string(),
+        };
+        assert_eq!(ack.acknowledgement(), Some(Acknowledgement::Ack));
+
+        Ok(())
+    }
+}
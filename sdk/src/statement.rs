@@ -0,0 +1,918 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Statements
+//!
+//! Utilities for the `camt.053.001.10` bank to customer statement, the `camt.052.001.10`
+//! account report and the `camt.054.001.10` debit credit notification:
+//!
+//! - `verify_statement` checks that the opening booked balance `OPBD` (or `PRCD`) plus the
+//!   booked credits minus the booked debits equals the closing booked balance `CLBD`, and
+//!   that the transactions summary `TxsSummry` matches the entries. `verify_report` checks
+//!   an intraday `camt.052` report against its interim booked balance `ITBD` instead.
+//! - `ledger_lines` flattens the entries into one `LedgerLine` per transaction detail,
+//!   which `ledger_csv` writes as CSV.
+//!
+//! ```rust
+//! use iso_20022_sdk::statement::{ledger_csv, ledger_lines, verify_statement};
+//!
+//! for stmt in &doc.bk_to_cstmr_stmt.stmt {
+//!     let discrepancies = verify_statement(stmt);
+//!     assert!(discrepancies.is_empty(), "{discrepancies:?}");
+//!
+//!     std::fs::write(format!("{}.csv", stmt.id.value), ledger_csv(&ledger_lines(stmt)))?;
+//! }
+//! ```
+use chrono::NaiveDate;
+use iso_20022_camt::camt_052_001_10 as camt_052;
+use iso_20022_camt::camt_053_001_10 as camt_053;
+use iso_20022_camt::camt_054_001_10 as camt_054;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Tolerance of amount comparisons, i.e. half of the smallest minor unit.
+const TOLERANCE: f64 = 0.005;
+
+/// Entry status of booked entries.
+const BOOKED: &str = "BOOK";
+
+/// Balance types of the opening and closing balances verified against the booked entries,
+/// which depend on the message type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceTypes {
+    /// Balance types of the opening balance, in order of precedence
+    pub opening: &'static [&'static str],
+    /// Balance types of the closing balance, in order of precedence
+    pub closing: &'static [&'static str],
+}
+
+impl BalanceTypes {
+    /// `camt.053` statement: the opening booked `OPBD` (or `PRCD`) and closing booked `CLBD` balances.
+    pub const STATEMENT: Self = Self {
+        opening: &["OPBD", "PRCD"],
+        closing: &["CLBD"],
+    };
+
+    /// `camt.052` intraday report: the opening booked `OPBD` (or `PRCD`) and interim booked
+    /// `ITBD` balances, or `CLBD` for an end of day report. The interim available balance
+    /// `ITAV` includes entries that are not booked and is not verified.
+    pub const REPORT: Self = Self {
+        opening: &["OPBD", "PRCD"],
+        closing: &["ITBD", "CLBD"],
+    };
+}
+
+/// CSV header of the ledger lines.
+const LEDGER_CSV_HEADER: [&str; 18] = [
+    "StmtId",
+    "Acct",
+    "NtryRef",
+    "BookgDt",
+    "ValDt",
+    "Amt",
+    "Ccy",
+    "CdtDbtInd",
+    "Sts",
+    "BkTxCd",
+    "AcctSvcrRef",
+    "EndToEndId",
+    "TxId",
+    "UETR",
+    "CtrPtyNm",
+    "CtrPtyAcct",
+    "RmtInf",
+    "CdtrRef",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+}
+
+/// Discrepancy found when verifying a statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementDiscrepancy {
+    /// The balance of the type, e.g. `CLBD`, is missing
+    MissingBalance { balance: &'static str },
+    /// The closing booked balance differs from the opening balance plus the booked entries
+    ClosingBalance { expected: f64, found: f64 },
+    /// The currency of the entry differs from the currency of the balances
+    EntryCurrency {
+        ntry_ref: Option<String>,
+        ccy: String,
+    },
+    /// The number of entries of the summary element differs from the entries
+    NumberOfEntries {
+        element: &'static str,
+        expected: usize,
+        found: String,
+    },
+    /// The sum of entries of the summary element differs from the entries
+    SumOfEntries {
+        element: &'static str,
+        expected: f64,
+        found: f64,
+    },
+}
+
+/// Flattened ledger line of a transaction detail, or of an entry without details.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct LedgerLine {
+    /// Statement identification
+    pub stmt_id: String,
+    /// Account of the statement, i.e. the IBAN or other identification
+    pub acct: Option<String>,
+    /// Entry reference
+    pub ntry_ref: Option<String>,
+    /// Booking date
+    pub bookg_dt: Option<NaiveDate>,
+    /// Value date
+    pub val_dt: Option<NaiveDate>,
+    /// Amount, without sign
+    pub amt: f64,
+    /// Currency of the amount
+    pub ccy: String,
+    /// Sign of the amount, i.e. `CRDT` or `DBIT`
+    pub cdt_dbt_ind: String,
+    /// Entry status, e.g. `BOOK` or `PDNG`
+    pub sts: Option<String>,
+    /// Bank transaction code, i.e. `Domain/Family/SubFamily`, or the proprietary code
+    pub bk_tx_cd: Option<String>,
+    /// Account servicer reference
+    pub acct_svcr_ref: Option<String>,
+    /// End-to-end identification
+    pub end_to_end_id: Option<String>,
+    /// Transaction identification
+    pub tx_id: Option<String>,
+    /// Unique end-to-end transaction reference
+    pub uetr: Option<String>,
+    /// Name of the counterparty, i.e. the debtor of a credit or the creditor of a debit
+    pub ctr_pty_nm: Option<String>,
+    /// Account of the counterparty
+    pub ctr_pty_acct: Option<String>,
+    /// Unstructured remittance information, or the structured creditor references
+    pub rmt_inf: Option<String>,
+    /// Structured creditor reference `CdtrRefInf/Ref`, e.g. an RF creditor reference
+    pub cdtr_ref: Option<String>,
+}
+
+impl LedgerLine {
+    /// Return the amount, negative for debits.
+    pub fn signed_amt(&self) -> f64 {
+        match self.cdt_dbt_ind.as_str() {
+            "DBIT" => -self.amt,
+            _ => self.amt,
+        }
+    }
+
+    /// Return the fields of the line, in the order of the CSV header.
+    fn csv_record(&self) -> [String; 18] {
+        let date = |date: &Option<NaiveDate>| {
+            date.map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        };
+        let text = |text: &Option<String>| text.clone().unwrap_or_default();
+
+        [
+            self.stmt_id.clone(),
+            text(&self.acct),
+            text(&self.ntry_ref),
+            date(&self.bookg_dt),
+            date(&self.val_dt),
+            self.amt.to_string(),
+            self.ccy.clone(),
+            self.cdt_dbt_ind.clone(),
+            text(&self.sts),
+            text(&self.bk_tx_cd),
+            text(&self.acct_svcr_ref),
+            text(&self.end_to_end_id),
+            text(&self.tx_id),
+            text(&self.uetr),
+            text(&self.ctr_pty_nm),
+            text(&self.ctr_pty_acct),
+            text(&self.rmt_inf),
+            text(&self.cdtr_ref),
+        ]
+    }
+}
+
+/// Return the `camt.052` account report as a `camt.053` account statement, which has the same
+/// balances, transactions summary and entries, to flatten it the same way. Use `verify_report`
+/// to verify the report, which has different balance types.
+pub fn report_statement<A>(
+    report: &camt_052::AccountReport31<A>,
+) -> Result<camt_053::AccountStatement11<A>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    Ok(quick_xml::de::from_str(&quick_xml::se::to_string(report)?)?)
+}
+
+/// Return the `camt.054` notification as a `camt.053` account statement, without balances,
+/// to flatten its entries the same way.
+pub fn notification_statement<A>(
+    ntfctn: &camt_054::AccountNotification20<A>,
+) -> Result<camt_053::AccountStatement11<A>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    Ok(quick_xml::de::from_str(&quick_xml::se::to_string(ntfctn)?)?)
+}
+
+/// Verify the balances and the transactions summary of the statement against its entries.
+pub fn verify_statement<A>(stmt: &camt_053::AccountStatement11<A>) -> Vec<StatementDiscrepancy>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    verify_balances(stmt, BalanceTypes::STATEMENT)
+}
+
+/// Verify the balances and the transactions summary of the `camt.052` account report
+/// against its entries, using the `BalanceTypes::REPORT` balance types.
+pub fn verify_report<A>(
+    report: &camt_052::AccountReport31<A>,
+) -> Result<Vec<StatementDiscrepancy>, Error>
+where
+    A: std::fmt::Debug
+        + Default
+        + Clone
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + ::validator::Validate,
+{
+    Ok(verify_balances(
+        &report_statement(report)?,
+        BalanceTypes::REPORT,
+    ))
+}
+
+/// Verify the balances of the balance types and the transactions summary of the
+/// statement against its entries.
+pub fn verify_balances<A>(
+    stmt: &camt_053::AccountStatement11<A>,
+    balance_types: BalanceTypes,
+) -> Vec<StatementDiscrepancy>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut discrepancies = vec![];
+
+    let opening = balance_types
+        .opening
+        .iter()
+        .find_map(|balance| find_balance(stmt, balance));
+    let closing = balance_types
+        .closing
+        .iter()
+        .find_map(|balance| find_balance(stmt, balance));
+
+    if opening.is_none() {
+        discrepancies.push(StatementDiscrepancy::MissingBalance {
+            balance: balance_types.opening[0],
+        });
+    }
+    if closing.is_none() {
+        discrepancies.push(StatementDiscrepancy::MissingBalance {
+            balance: balance_types.closing[0],
+        });
+    }
+
+    let ccy = opening.or(closing).map(|bal| bal.amt.ccy.value.as_str());
+
+    for ntry in &stmt.ntry {
+        if ccy.is_some() && ccy != Some(ntry.amt.ccy.value.as_str()) {
+            discrepancies.push(StatementDiscrepancy::EntryCurrency {
+                ntry_ref: ntry
+                    .ntry_ref
+                    .as_ref()
+                    .map(|ntry_ref| ntry_ref.value.clone()),
+                ccy: ntry.amt.ccy.value.clone(),
+            });
+        }
+    }
+
+    if let (Some(opening), Some(closing)) = (opening, closing) {
+        let booked = stmt
+            .ntry
+            .iter()
+            .filter(|ntry| entry_status(ntry).as_deref() == Some(BOOKED))
+            .map(|ntry| signed(ntry.amt.value.value, &ntry.cdt_dbt_ind))
+            .sum::<f64>();
+
+        let expected = signed(opening.amt.value.value, &opening.cdt_dbt_ind) + booked;
+        let found = signed(closing.amt.value.value, &closing.cdt_dbt_ind);
+
+        if (expected - found).abs() > TOLERANCE {
+            discrepancies.push(StatementDiscrepancy::ClosingBalance { expected, found });
+        }
+    }
+
+    if let Some(txs_summry) = &stmt.txs_summry {
+        let entries = |cdt_dbt_ind: Option<&camt_053::CreditDebitCode>| {
+            let ntries = stmt
+                .ntry
+                .iter()
+                .filter(|ntry| cdt_dbt_ind.is_none() || cdt_dbt_ind == Some(&ntry.cdt_dbt_ind))
+                .collect::<Vec<_>>();
+            let sum = ntries.iter().map(|ntry| ntry.amt.value.value).sum::<f64>();
+
+            (ntries.len(), sum)
+        };
+
+        let mut check = |element: &'static str,
+                         nb_of_ntries: &Option<camt_053::Max15NumericText>,
+                         sum: &Option<camt_053::DecimalNumber>,
+                         (expected_nb, expected_sum): (usize, f64)| {
+            if let Some(nb_of_ntries) = nb_of_ntries {
+                if nb_of_ntries.value.parse::<usize>().ok() != Some(expected_nb) {
+                    discrepancies.push(StatementDiscrepancy::NumberOfEntries {
+                        element,
+                        expected: expected_nb,
+                        found: nb_of_ntries.value.clone(),
+                    });
+                }
+            }
+            if let Some(sum) = sum {
+                if (sum.value - expected_sum).abs() > TOLERANCE {
+                    discrepancies.push(StatementDiscrepancy::SumOfEntries {
+                        element,
+                        expected: expected_sum,
+                        found: sum.value,
+                    });
+                }
+            }
+        };
+
+        if let Some(ttl_ntries) = &txs_summry.ttl_ntries {
+            check(
+                "TtlNtries",
+                &ttl_ntries.nb_of_ntries,
+                &ttl_ntries.sum,
+                entries(None),
+            );
+        }
+        if let Some(ttl_cdt_ntries) = &txs_summry.ttl_cdt_ntries {
+            check(
+                "TtlCdtNtries",
+                &ttl_cdt_ntries.nb_of_ntries,
+                &ttl_cdt_ntries.sum,
+                entries(Some(&camt_053::CreditDebitCode::Crdt)),
+            );
+        }
+        if let Some(ttl_dbt_ntries) = &txs_summry.ttl_dbt_ntries {
+            check(
+                "TtlDbtNtries",
+                &ttl_dbt_ntries.nb_of_ntries,
+                &ttl_dbt_ntries.sum,
+                entries(Some(&camt_053::CreditDebitCode::Dbit)),
+            );
+        }
+
+        let net_ntry = txs_summry
+            .ttl_ntries
+            .as_ref()
+            .and_then(|ttl_ntries| ttl_ntries.ttl_net_ntry.as_ref());
+
+        if let Some(ttl_net_ntry) = net_ntry {
+            let expected = stmt
+                .ntry
+                .iter()
+                .map(|ntry| signed(ntry.amt.value.value, &ntry.cdt_dbt_ind))
+                .sum::<f64>();
+            let found = signed(ttl_net_ntry.amt.value, &ttl_net_ntry.cdt_dbt_ind);
+
+            if (expected - found).abs() > TOLERANCE {
+                discrepancies.push(StatementDiscrepancy::SumOfEntries {
+                    element: "TtlNetNtry",
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    discrepancies
+}
+
+/// Flatten the entries of the statement into one ledger line per transaction detail.
+///
+/// An entry without transaction details is flattened into a single line. The amount,
+/// sign and bank transaction code of a transaction detail default to those of its entry.
+pub fn ledger_lines<A>(stmt: &camt_053::AccountStatement11<A>) -> Vec<LedgerLine>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let acct = stmt.acct.id.as_ref().map(account_identification);
+
+    stmt.ntry
+        .iter()
+        .flat_map(|ntry| {
+            let entry = LedgerLine {
+                stmt_id: stmt.id.value.clone(),
+                acct: acct.clone(),
+                ntry_ref: ntry
+                    .ntry_ref
+                    .as_ref()
+                    .map(|ntry_ref| ntry_ref.value.clone()),
+                bookg_dt: ntry.bookg_dt.as_ref().and_then(date),
+                val_dt: ntry.val_dt.as_ref().and_then(date),
+                amt: ntry.amt.value.value,
+                ccy: ntry.amt.ccy.value.clone(),
+                cdt_dbt_ind: credit_debit(&ntry.cdt_dbt_ind),
+                sts: entry_status(ntry),
+                bk_tx_cd: bank_transaction_code(&ntry.bk_tx_cd),
+                acct_svcr_ref: ntry
+                    .acct_svcr_ref
+                    .as_ref()
+                    .map(|acct_svcr_ref| acct_svcr_ref.value.clone()),
+                ..Default::default()
+            };
+
+            let tx_dtls = ntry
+                .ntry_dtls
+                .iter()
+                .flat_map(|ntry_dtls| ntry_dtls.tx_dtls.iter())
+                .collect::<Vec<_>>();
+
+            if tx_dtls.is_empty() {
+                return vec![entry];
+            }
+
+            tx_dtls
+                .into_iter()
+                .map(|tx| transaction_line(&entry, tx))
+                .collect()
+        })
+        .collect()
+}
+
+/// Write the ledger lines as CSV, with a header line.
+pub fn ledger_csv(lines: &[LedgerLine]) -> String {
+    let mut csv = LEDGER_CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+
+    for line in lines {
+        let record = line
+            .csv_record()
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        csv.push_str(&record);
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+/// Return the ledger line of a transaction detail of the entry.
+fn transaction_line<A>(entry: &LedgerLine, tx: &camt_053::EntryTransaction12<A>) -> LedgerLine
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let mut line = entry.clone();
+
+    if let Some(amt) = &tx.amt {
+        line.amt = amt.value.value;
+        line.ccy = amt.ccy.value.clone();
+    }
+    if let Some(cdt_dbt_ind) = &tx.cdt_dbt_ind {
+        line.cdt_dbt_ind = credit_debit(cdt_dbt_ind);
+    }
+    if let Some(bk_tx_cd) = &tx.bk_tx_cd {
+        line.bk_tx_cd = bank_transaction_code(bk_tx_cd).or(line.bk_tx_cd);
+    }
+
+    if let Some(refs) = &tx.refs {
+        let text =
+            |text: &Option<camt_053::Max35Text>| text.as_ref().map(|text| text.value.clone());
+
+        line.acct_svcr_ref = text(&refs.acct_svcr_ref).or(line.acct_svcr_ref);
+        line.end_to_end_id = text(&refs.end_to_end_id);
+        line.tx_id = text(&refs.tx_id);
+        line.uetr = refs.uetr.as_ref().map(|uetr| uetr.value.clone());
+    }
+
+    if let Some(rltd_pties) = &tx.rltd_pties {
+        let (pty, acct) = match line.cdt_dbt_ind.as_str() {
+            "DBIT" => (
+                rltd_pties.ultmt_cdtr.as_ref().or(rltd_pties.cdtr.as_ref()),
+                rltd_pties.cdtr_acct.as_ref(),
+            ),
+            _ => (
+                rltd_pties.ultmt_dbtr.as_ref().or(rltd_pties.dbtr.as_ref()),
+                rltd_pties.dbtr_acct.as_ref(),
+            ),
+        };
+
+        line.ctr_pty_nm = pty.and_then(party_name);
+        line.ctr_pty_acct = acct
+            .and_then(|acct| acct.id.as_ref())
+            .map(account_identification);
+    }
+
+    if let Some(rmt_inf) = &tx.rmt_inf {
+        let ustrd = rmt_inf
+            .ustrd
+            .iter()
+            .map(|ustrd| ustrd.value.as_str())
+            .collect::<Vec<_>>();
+        let strd = rmt_inf
+            .strd
+            .iter()
+            .filter_map(|strd| strd.cdtr_ref_inf.as_ref()?.r#ref.as_ref())
+            .map(|r#ref| r#ref.value.as_str())
+            .collect::<Vec<_>>();
+
+        line.rmt_inf = match (ustrd.is_empty(), strd.is_empty()) {
+            (false, _) => Some(ustrd.join(" ")),
+            (true, false) => Some(strd.join(" ")),
+            (true, true) => None,
+        };
+        line.cdtr_ref = strd.first().map(|cdtr_ref| cdtr_ref.to_string());
+    }
+
+    line
+}
+
+/// Return the balance of the type, e.g. `CLBD`.
+fn find_balance<'a, A>(
+    stmt: &'a camt_053::AccountStatement11<A>,
+    balance: &str,
+) -> Option<&'a camt_053::CashBalance8>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    stmt.bal.iter().find(|bal| {
+        bal.tp
+            .cd_or_prtry
+            .value
+            .cd
+            .as_ref()
+            .map(|cd| cd.value.as_str())
+            == Some(balance)
+    })
+}
+
+/// Return the status code of the entry, e.g. `BOOK`.
+fn entry_status<A>(ntry: &camt_053::ReportEntry12<A>) -> Option<String>
+where
+    A: std::fmt::Debug + Default + Clone + PartialEq + Serialize + ::validator::Validate,
+{
+    let sts = &ntry.sts.value;
+
+    sts.cd
+        .as_ref()
+        .map(|cd| cd.value.clone())
+        .or_else(|| sts.prtry.as_ref().map(|prtry| prtry.value.clone()))
+}
+
+/// Return the amount, negative for debits.
+fn signed(amount: f64, cdt_dbt_ind: &camt_053::CreditDebitCode) -> f64 {
+    match cdt_dbt_ind {
+        camt_053::CreditDebitCode::Dbit => -amount,
+        _ => amount,
+    }
+}
+
+fn credit_debit(cdt_dbt_ind: &camt_053::CreditDebitCode) -> String {
+    match cdt_dbt_ind {
+        camt_053::CreditDebitCode::Crdt => "CRDT".to_string(),
+        camt_053::CreditDebitCode::Dbit => "DBIT".to_string(),
+        camt_053::CreditDebitCode::Unknown => String::new(),
+    }
+}
+
+fn date(date: &camt_053::DateAndDateTime2Choice) -> Option<NaiveDate> {
+    date.value.dt.as_ref().map(|dt| dt.value).or_else(|| {
+        date.value
+            .dt_tm
+            .as_ref()
+            .map(|dt_tm| dt_tm.value.date_naive())
+    })
+}
+
+/// Return the bank transaction code, i.e. `Domain/Family/SubFamily`, or the proprietary code.
+fn bank_transaction_code(bk_tx_cd: &camt_053::BankTransactionCodeStructure4) -> Option<String> {
+    bk_tx_cd
+        .domn
+        .as_ref()
+        .map(|domn| {
+            format!(
+                "{}/{}/{}",
+                domn.cd.value, domn.fmly.cd.value, domn.fmly.sub_fmly_cd.value
+            )
+        })
+        .or_else(|| bk_tx_cd.prtry.as_ref().map(|prtry| prtry.cd.value.clone()))
+}
+
+fn account_identification(id: &camt_053::AccountIdentification4Choice) -> String {
+    id.value
+        .iban
+        .as_ref()
+        .map(|iban| iban.value.clone())
+        .or_else(|| id.value.othr.as_ref().map(|othr| othr.id.value.clone()))
+        .unwrap_or_default()
+}
+
+fn party_name(party: &camt_053::Party40Choice) -> Option<String> {
+    match (&party.value.pty, &party.value.agt) {
+        (Some(pty), _) => pty.nm.as_ref().map(|nm| nm.value.clone()),
+        (None, Some(agt)) => agt
+            .fin_instn_id
+            .nm
+            .as_ref()
+            .map(|nm| nm.value.clone())
+            .or_else(|| {
+                agt.fin_instn_id
+                    .bicfi
+                    .as_ref()
+                    .map(|bicfi| bicfi.value.clone())
+            }),
+        (None, None) => None,
+    }
+}
+
+/// Quote a CSV field containing a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Dmkr;
+
+    fn amount(value: f64) -> camt_053::ActiveOrHistoricCurrencyAndAmount {
+        camt_053::ActiveOrHistoricCurrencyAndAmount {
+            value: camt_053::ActiveOrHistoricCurrencyAndAmountSimpleType { value },
+            ccy: camt_053::ActiveOrHistoricCurrencyCode {
+                value: "EUR".to_string(),
+            },
+        }
+    }
+
+    fn balance(
+        cd: &str,
+        value: f64,
+        cdt_dbt_ind: camt_053::CreditDebitCode,
+    ) -> camt_053::CashBalance8 {
+        camt_053::CashBalance8 {
+            tp: camt_053::BalanceType13 {
+                cd_or_prtry: camt_053::BalanceType10Choice {
+                    value: camt_053::BalanceType10ChoiceEnum {
+                        cd: Some(camt_053::ExternalBalanceType1Code {
+                            value: cd.to_string(),
+                        }),
+                        prtry: None,
+                    },
+                },
+                sub_tp: None,
+            },
+            amt: amount(value),
+            cdt_dbt_ind,
+            ..Default::default()
+        }
+    }
+
+    fn entry(
+        value: f64,
+        cdt_dbt_ind: camt_053::CreditDebitCode,
+        tx_dtls: Vec<camt_053::EntryTransaction12<Dmkr>>,
+    ) -> camt_053::ReportEntry12<Dmkr> {
+        camt_053::ReportEntry12 {
+            amt: amount(value),
+            cdt_dbt_ind,
+            sts: camt_053::EntryStatus1Choice {
+                value: camt_053::EntryStatus1ChoiceEnum {
+                    cd: Some(camt_053::ExternalEntryStatus1Code {
+                        value: BOOKED.to_string(),
+                    }),
+                    prtry: None,
+                },
+            },
+            bookg_dt: Some(camt_053::DateAndDateTime2Choice {
+                value: camt_053::DateAndDateTime2ChoiceEnum {
+                    dt: Some(camt_053::IsoDate {
+                        value: NaiveDate::from_ymd_opt(2023, 6, 1).unwrap(),
+                    }),
+                    dt_tm: None,
+                },
+            }),
+            bk_tx_cd: camt_053::BankTransactionCodeStructure4 {
+                domn: None,
+                prtry: Some(camt_053::ProprietaryBankTransactionCodeStructure1 {
+                    cd: camt_053::Max35Text {
+                        value: "NTRF".to_string(),
+                    },
+                    issr: None,
+                }),
+            },
+            ntry_dtls: vec![camt_053::EntryDetails11 {
+                btch: None,
+                tx_dtls,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn transaction(
+        value: f64,
+        end_to_end_id: &str,
+        debtor: &str,
+        remittance: &str,
+    ) -> camt_053::EntryTransaction12<Dmkr> {
+        camt_053::EntryTransaction12 {
+            refs: Some(camt_053::TransactionReferences6 {
+                end_to_end_id: Some(camt_053::Max35Text {
+                    value: end_to_end_id.to_string(),
+                }),
+                ..Default::default()
+            }),
+            amt: Some(amount(value)),
+            cdt_dbt_ind: Some(camt_053::CreditDebitCode::Crdt),
+            rltd_pties: Some(camt_053::TransactionParties9 {
+                dbtr: Some(camt_053::Party40Choice {
+                    value: camt_053::Party40ChoiceEnum {
+                        pty: Some(camt_053::PartyIdentification135 {
+                            nm: Some(camt_053::Max140Text {
+                                value: debtor.to_string(),
+                            }),
+                            ..Default::default()
+                        }),
+                        agt: None,
+                    },
+                }),
+                ..Default::default()
+            }),
+            rmt_inf: Some(camt_053::RemittanceInformation21 {
+                ustrd: vec![camt_053::Max140Text {
+                    value: remittance.to_string(),
+                }],
+                strd: vec![],
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn statement() -> camt_053::AccountStatement11<Dmkr> {
+        camt_053::AccountStatement11 {
+            id: camt_053::Max35Text {
+                value: "STMT-1".to_string(),
+            },
+            bal: vec![
+                balance("OPBD", 1000.0, camt_053::CreditDebitCode::Crdt),
+                balance("CLBD", 1120.5, camt_053::CreditDebitCode::Crdt),
+            ],
+            txs_summry: Some(camt_053::TotalTransactions6 {
+                ttl_ntries: Some(camt_053::NumberAndSumOfTransactions4 {
+                    nb_of_ntries: Some(camt_053::Max15NumericText {
+                        value: "2".to_string(),
+                    }),
+                    sum: Some(camt_053::DecimalNumber { value: 179.5 }),
+                    ttl_net_ntry: Some(camt_053::AmountAndDirection35 {
+                        amt: camt_053::NonNegativeDecimalNumber { value: 120.5 },
+                        cdt_dbt_ind: camt_053::CreditDebitCode::Crdt,
+                    }),
+                }),
+                ..Default::default()
+            }),
+            ntry: vec![
+                entry(
+                    150.0,
+                    camt_053::CreditDebitCode::Crdt,
+                    vec![
+                        transaction(100.0, "E2E-1", "Debtor, Inc.", "Invoice 1"),
+                        transaction(50.0, "E2E-2", "Other Debtor", "Invoice \"2\""),
+                    ],
+                ),
+                entry(29.5, camt_053::CreditDebitCode::Dbit, vec![]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_statement() {
+        let mut stmt = statement();
+        assert_eq!(verify_statement(&stmt), vec![]);
+
+        stmt.bal[1].amt.value.value = 1100.0;
+        stmt.ntry.pop();
+
+        assert_eq!(
+            verify_statement(&stmt),
+            vec![
+                StatementDiscrepancy::ClosingBalance {
+                    expected: 1150.0,
+                    found: 1100.0,
+                },
+                StatementDiscrepancy::NumberOfEntries {
+                    element: "TtlNtries",
+                    expected: 1,
+                    found: "2".to_string(),
+                },
+                StatementDiscrepancy::SumOfEntries {
+                    element: "TtlNtries",
+                    expected: 150.0,
+                    found: 179.5,
+                },
+                StatementDiscrepancy::SumOfEntries {
+                    element: "TtlNetNtry",
+                    expected: 150.0,
+                    found: 120.5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ledger_lines() {
+        let lines = ledger_lines(&statement());
+        assert_eq!(lines.len(), 3);
+
+        assert_eq!(lines[0].amt, 100.0);
+        assert_eq!(lines[0].end_to_end_id.as_deref(), Some("E2E-1"));
+        assert_eq!(lines[0].ctr_pty_nm.as_deref(), Some("Debtor, Inc."));
+        assert_eq!(lines[0].bk_tx_cd.as_deref(), Some("NTRF"));
+        assert_eq!(lines[2].signed_amt(), -29.5);
+        assert_eq!(lines[2].rmt_inf, None);
+
+        let csv = ledger_csv(&lines);
+        let records = csv.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(records.len(), 5);
+        assert!(records[0].starts_with("StmtId,Acct,NtryRef,BookgDt"));
+        assert_eq!(
+            records[1],
+            "STMT-1,,,2023-06-01,,100,EUR,CRDT,BOOK,NTRF,,E2E-1,,,\"Debtor, Inc.\",,Invoice 1,"
+        );
+        assert!(records[2].ends_with(",Other Debtor,,\"Invoice \"\"2\"\"\","));
+    }
+
+    #[test]
+    fn test_report_statement() {
+        let report = camt_052::AccountReport31::<Dmkr> {
+            id: camt_052::Max35Text {
+                value: "RPT-1".to_string(),
+            },
+            ..Default::default()
+        };
+
+        let stmt = report_statement(&report).unwrap();
+        assert_eq!(stmt.id.value, "RPT-1");
+        assert_eq!(
+            verify_report(&report).unwrap(),
+            vec![
+                StatementDiscrepancy::MissingBalance { balance: "OPBD" },
+                StatementDiscrepancy::MissingBalance { balance: "ITBD" },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_intraday_balances() {
+        let mut stmt = statement();
+        stmt.bal[1] = balance("ITBD", 1120.5, camt_053::CreditDebitCode::Crdt);
+        stmt.bal
+            .push(balance("ITAV", 900.0, camt_053::CreditDebitCode::Crdt));
+
+        assert_eq!(verify_balances(&stmt, BalanceTypes::REPORT), vec![]);
+        assert_eq!(
+            verify_statement(&stmt),
+            vec![StatementDiscrepancy::MissingBalance { balance: "CLBD" }]
+        );
+    }
+}
@@ -0,0 +1,800 @@
+// Copyright 2023 Emergent Financial, LLC - All Rights Reserved
+//
+//
+// This software is licensed under the Emergent Financial Limited Public License Version 1.0
+// (EF-LPLv1). You may use, copy, modify, and distribute this software under the terms and
+// conditions of the EF-LPL. For more information, please refer to the full text of the license
+// at https://github.com/emergentfinancial/ef-lpl.
+//
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+//! # Payment Tracking
+//!
+//! The `PaymentTracker` correlates the messages of a payment by its unique end-to-end
+//! transaction reference `UETR`, end-to-end identification `EndToEndId`, transaction
+//! identification `TxId` and instruction identification `InstrId`, and maintains a timeline
+//! of the messages of each payment with its derived status, e.g. in progress, settled,
+//! returned, rejected or cancelled.
+//!
+//! Unlike the UETR, the `EndToEndId`, `TxId` and `InstrId` are only unique per sender, so they
+//! correlate messages within the message identification `MsgId` of the instruction, i.e. the
+//! `OrgnlMsgId` of the later messages. An `EndToEndId` of `NOTPROVIDED` is no reference.
+//!
+//! Payments are kept in a `PaymentStore`, e.g. the `MemoryPaymentStore`.
+//!
+//! ```rust
+//! use iso_20022_sdk::tracker::{MemoryPaymentStore, PaymentStatus, PaymentTracker};
+//!
+//! let mut tracker = PaymentTracker::new(MemoryPaymentStore::new());
+//!
+//! // Ingest the messages of the payments, e.g. pacs.008, pacs.002, pacs.004, camt.054
+//! tracker.ingest(&pacs_008_document)?;
+//! tracker.ingest(&pacs_002_document)?;
+//!
+//! // Where is my payment?
+//! let payment = tracker.lookup("8a562c67-ca16-48ba-b074-65581be6f001")?.unwrap();
+//! assert_eq!(payment.status, PaymentStatus::Settled);
+//! ```
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sxd_document::{parser, Package};
+use sxd_xpath::evaluate_xpath;
+
+/// Namespace prefix of the ISO 20022 documents.
+const NAMESPACE_PREFIX: &str = "urn:iso:std:iso:20022:tech:xsd:";
+
+/// XPath to the namespace of the document.
+const NAMESPACE_X_PATH: &str = "namespace-uri(/*)";
+
+/// XPath to the transactions of the document, i.e. the elements with a payment identification
+/// `PmtId`, entry references `Refs`, or a reference to an original transaction.
+const TRANSACTIONS_X_PATH: &str = "//*[*[local-name()='PmtId' or local-name()='Refs' or local-name()='OrgnlUETR' or local-name()='OrgnlEndToEndId' or local-name()='OrgnlTxId' or local-name()='OrgnlInstrId']]";
+
+/// XPath to the message identification of the document itself.
+const MSG_ID_X_PATH: &str = "normalize-space((/*/*/*[local-name()='GrpHdr' or local-name()='Assgnmt']/*[local-name()='MsgId' or local-name()='Id'])[1])";
+
+/// XPath to the original message identification of the document.
+const ORGNL_MSG_ID_X_PATH: &str = "/*/*/*[local-name()='OrgnlGrpInfAndSts' or local-name()='OrgnlGrpInf']/*[local-name()='OrgnlMsgId']";
+
+/// End-to-end identification used when no identification is provided by the initiating party.
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error Serializing / Deserializing XML
+    #[error(transparent)]
+    XmlSerDe(#[from] quick_xml::de::DeError),
+    /// SXD Document Error
+    #[error(transparent)]
+    XsdDocument(#[from] sxd_document::parser::Error),
+    /// SXD XPath Error
+    #[error(transparent)]
+    XsdXPath(#[from] sxd_xpath::Error),
+    /// The document is not a payment message
+    #[error("unsupported payment message: {0}")]
+    UnsupportedMessage(String),
+    /// Payment Store Error
+    #[error("payment store error: {0}")]
+    Store(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// Derived status of a payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentStatus {
+    /// The payment is initiated by the debtor or creditor, e.g. `pain.001`
+    Initiated,
+    /// The payment is instructed between agents, or accepted but not settled
+    InProgress,
+    /// The payment is settled, e.g. `ACSC` or `ACCC`, or notified to the account owner
+    Settled,
+    /// The payment is returned, e.g. `pacs.004`, or reversed, e.g. `pacs.007`
+    Returned,
+    /// The payment is rejected, e.g. `RJCT`
+    Rejected,
+    /// The payment is cancelled, e.g. `camt.029` with `ACCR` or `CNCL`
+    Cancelled,
+}
+
+impl PaymentStatus {
+    /// Return true if the payment is returned, rejected or cancelled.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Returned | Self::Rejected | Self::Cancelled)
+    }
+
+    /// Return the status after a message, where a status does not go backwards,
+    /// e.g. a late `ACSP` does not undo a settlement, and a final status is kept.
+    pub fn next(self, status: PaymentStatus) -> Self {
+        if self.rank() < status.rank() {
+            status
+        } else {
+            self
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Initiated => 0,
+            Self::InProgress => 1,
+            Self::Settled => 2,
+            Self::Returned | Self::Rejected | Self::Cancelled => 3,
+        }
+    }
+}
+
+/// Payment messages ingested by the tracker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentMessage {
+    /// `pain.001` CustomerCreditTransferInitiation
+    CustomerCreditTransferInitiation,
+    /// `pain.002` CustomerPaymentStatusReport
+    CustomerPaymentStatusReport,
+    /// `pain.008` CustomerDirectDebitInitiation
+    CustomerDirectDebitInitiation,
+    /// `pacs.002` FIToFIPaymentStatusReport
+    FiToFiPaymentStatusReport,
+    /// `pacs.003` FIToFICustomerDirectDebit
+    FiToFiCustomerDirectDebit,
+    /// `pacs.004` PaymentReturn
+    PaymentReturn,
+    /// `pacs.007` FIToFIPaymentReversal
+    FiToFiPaymentReversal,
+    /// `pacs.008` FIToFICustomerCreditTransfer
+    FiToFiCustomerCreditTransfer,
+    /// `pacs.009` FinancialInstitutionCreditTransfer
+    FinancialInstitutionCreditTransfer,
+    /// `camt.029` ResolutionOfInvestigation
+    ResolutionOfInvestigation,
+    /// `camt.054` BankToCustomerDebitCreditNotification
+    BankToCustomerDebitCreditNotification,
+    /// `camt.056` FIToFIPaymentCancellationRequest
+    FiToFiPaymentCancellationRequest,
+}
+
+impl PaymentMessage {
+    /// Return the message of a namespace, of any version, e.g.
+    /// `urn:iso:std:iso:20022:tech:xsd:pacs.008.001.10`
+    pub fn from_namespace(namespace: &str) -> Option<Self> {
+        let msg_def_idr = namespace.strip_prefix(NAMESPACE_PREFIX)?;
+
+        Some(match msg_def_idr.get(..8)? {
+            "pain.001" => Self::CustomerCreditTransferInitiation,
+            "pain.002" => Self::CustomerPaymentStatusReport,
+            "pain.008" => Self::CustomerDirectDebitInitiation,
+            "pacs.002" => Self::FiToFiPaymentStatusReport,
+            "pacs.003" => Self::FiToFiCustomerDirectDebit,
+            "pacs.004" => Self::PaymentReturn,
+            "pacs.007" => Self::FiToFiPaymentReversal,
+            "pacs.008" => Self::FiToFiCustomerCreditTransfer,
+            "pacs.009" => Self::FinancialInstitutionCreditTransfer,
+            "camt.029" => Self::ResolutionOfInvestigation,
+            "camt.054" => Self::BankToCustomerDebitCreditNotification,
+            "camt.056" => Self::FiToFiPaymentCancellationRequest,
+            _ => return None,
+        })
+    }
+
+    /// Return true if the message instructs the payment, rather than referring to it.
+    pub fn is_instruction(&self) -> bool {
+        matches!(
+            self,
+            Self::CustomerCreditTransferInitiation
+                | Self::CustomerDirectDebitInitiation
+                | Self::FiToFiCustomerDirectDebit
+                | Self::FiToFiCustomerCreditTransfer
+                | Self::FinancialInstitutionCreditTransfer
+        )
+    }
+
+    /// Return the XPaths, relative to a transaction, to its status or reason code,
+    /// from the least to the most specific.
+    fn code_x_paths(&self) -> &'static [&'static str] {
+        match self {
+            Self::CustomerPaymentStatusReport | Self::FiToFiPaymentStatusReport => &[
+                "/../*[local-name()='PmtInfSts']",
+                "/*[local-name()='TxSts']",
+            ],
+            Self::PaymentReturn => &["/*[local-name()='RtrRsnInf']/*[local-name()='Rsn']/*"],
+            Self::FiToFiPaymentReversal => {
+                &["/*[local-name()='RvslRsnInf']/*[local-name()='Rsn']/*"]
+            }
+            Self::ResolutionOfInvestigation => &["/*[local-name()='TxCxlSts']"],
+            Self::BankToCustomerDebitCreditNotification => {
+                &["/ancestor::*[local-name()='Ntry']/*[local-name()='Sts']"]
+            }
+            Self::FiToFiPaymentCancellationRequest => {
+                &["/*[local-name()='CxlRsnInf']/*[local-name()='Rsn']/*"]
+            }
+            _ => &[],
+        }
+    }
+
+    /// Return the XPath to the status or reason code of the whole document.
+    fn group_code_x_path(&self) -> Option<&'static str> {
+        match self {
+            Self::CustomerPaymentStatusReport | Self::FiToFiPaymentStatusReport => {
+                Some("/*/*/*[local-name()='OrgnlGrpInfAndSts']/*[local-name()='GrpSts']")
+            }
+            Self::ResolutionOfInvestigation => {
+                Some("/*/*/*[local-name()='Sts']/*[local-name()='Conf']")
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the status of a payment after the message, with its status or reason code.
+    pub fn status(&self, code: Option<&str>) -> Option<PaymentStatus> {
+        match self {
+            Self::CustomerCreditTransferInitiation | Self::CustomerDirectDebitInitiation => {
+                Some(PaymentStatus::Initiated)
+            }
+            Self::FiToFiCustomerDirectDebit
+            | Self::FiToFiCustomerCreditTransfer
+            | Self::FinancialInstitutionCreditTransfer => Some(PaymentStatus::InProgress),
+            Self::CustomerPaymentStatusReport | Self::FiToFiPaymentStatusReport => match code {
+                Some("ACSC" | "ACCC") => Some(PaymentStatus::Settled),
+                Some("RJCT") => Some(PaymentStatus::Rejected),
+                _ => Some(PaymentStatus::InProgress),
+            },
+            Self::PaymentReturn | Self::FiToFiPaymentReversal => Some(PaymentStatus::Returned),
+            Self::ResolutionOfInvestigation => match code {
+                Some("ACCR" | "CNCL") => Some(PaymentStatus::Cancelled),
+                _ => None,
+            },
+            Self::BankToCustomerDebitCreditNotification => match code {
+                Some("PDNG" | "INFO" | "FUTR") => None,
+                _ => Some(PaymentStatus::Settled),
+            },
+            Self::FiToFiPaymentCancellationRequest => None,
+        }
+    }
+}
+
+/// Key correlating the messages of a payment, where the references other than the UETR are
+/// scoped by the message identification `MsgId` of the message instructing the payment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CorrelationKey {
+    /// Unique end-to-end transaction reference `UETR`
+    Uetr(String),
+    /// Transaction identification `TxId`
+    TxId { msg_id: String, tx_id: String },
+    /// End-to-end identification `EndToEndId`
+    EndToEndId {
+        msg_id: String,
+        end_to_end_id: String,
+    },
+    /// Instruction identification `InstrId`
+    InstrId { msg_id: String, instr_id: String },
+}
+
+/// References of a payment, from the instruction or the original references of later messages.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PaymentReferences {
+    /// Unique end-to-end transaction reference `UETR`
+    pub uetr: Option<String>,
+    /// End-to-end identification `EndToEndId`
+    pub end_to_end_id: Option<String>,
+    /// Transaction identification `TxId`
+    pub tx_id: Option<String>,
+    /// Instruction identification `InstrId`
+    pub instr_id: Option<String>,
+    /// Message identification `MsgId` of the message instructing the payment
+    pub msg_id: Option<String>,
+}
+
+impl PaymentReferences {
+    /// Return the correlation keys of the references, in order of precedence. The references
+    /// other than the UETR are keys only with the message identification of the instruction.
+    pub fn keys(&self) -> Vec<CorrelationKey> {
+        let msg_id = self.msg_id.as_ref();
+        let scoped = |reference: &Option<String>| {
+            msg_id
+                .cloned()
+                .zip(reference.clone())
+                .filter(|(_, reference)| reference != NOT_PROVIDED)
+        };
+
+        [
+            self.uetr.clone().map(CorrelationKey::Uetr),
+            scoped(&self.tx_id).map(|(msg_id, tx_id)| CorrelationKey::TxId { msg_id, tx_id }),
+            scoped(&self.end_to_end_id).map(|(msg_id, end_to_end_id)| CorrelationKey::EndToEndId {
+                msg_id,
+                end_to_end_id,
+            }),
+            scoped(&self.instr_id)
+                .map(|(msg_id, instr_id)| CorrelationKey::InstrId { msg_id, instr_id }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Complete the missing references, e.g. the UETR reported in a status report.
+    pub fn merge(&mut self, other: PaymentReferences) {
+        self.uetr = self.uetr.take().or(other.uetr);
+        self.end_to_end_id = self.end_to_end_id.take().or(other.end_to_end_id);
+        self.tx_id = self.tx_id.take().or(other.tx_id);
+        self.instr_id = self.instr_id.take().or(other.instr_id);
+        self.msg_id = self.msg_id.take().or(other.msg_id);
+    }
+}
+
+/// Message in the timeline of a payment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentEvent {
+    /// Message type
+    pub message: PaymentMessage,
+    /// Message definition identifier, e.g. `pacs.002.001.12`
+    pub msg_def_idr: String,
+    /// Message identification `MsgId`, or assignment identification, of the message
+    pub msg_id: Option<String>,
+    /// Status or reason code of the transaction, e.g. `ACSC`, `RJCT` or `AC04`
+    pub code: Option<String>,
+    /// Status of the payment after the message
+    pub status: PaymentStatus,
+    /// Time the message was ingested
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Tracked payment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payment {
+    /// Payment identification, i.e. the UETR, or a generated identifier
+    pub id: String,
+    /// References of the payment
+    pub references: PaymentReferences,
+    /// Current status of the payment
+    pub status: PaymentStatus,
+    /// Messages of the payment, in order of ingestion
+    pub timeline: Vec<PaymentEvent>,
+}
+
+/// Payment store keeps the tracked payments, and finds them by correlation key.
+pub trait PaymentStore {
+    /// Error of the underlying store
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Return the identification of the payment of the key.
+    fn find(&self, key: &CorrelationKey) -> Result<Option<String>, Self::Error>;
+
+    /// Return the payment of the identification.
+    fn load(&self, id: &str) -> Result<Option<Payment>, Self::Error>;
+
+    /// Insert or update the payment, and index it by the keys of its references.
+    fn save(&mut self, payment: Payment) -> Result<(), Self::Error>;
+}
+
+/// In-memory payment store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryPaymentStore {
+    payments: HashMap<String, Payment>,
+    keys: HashMap<CorrelationKey, String>,
+}
+
+impl MemoryPaymentStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the tracked payments.
+    pub fn payments(&self) -> impl Iterator<Item = &Payment> {
+        self.payments.values()
+    }
+}
+
+impl PaymentStore for MemoryPaymentStore {
+    type Error = std::convert::Infallible;
+
+    fn find(&self, key: &CorrelationKey) -> Result<Option<String>, Self::Error> {
+        Ok(self.keys.get(key).cloned())
+    }
+
+    fn load(&self, id: &str) -> Result<Option<Payment>, Self::Error> {
+        Ok(self.payments.get(id).cloned())
+    }
+
+    fn save(&mut self, payment: Payment) -> Result<(), Self::Error> {
+        for key in payment.references.keys() {
+            self.keys.entry(key).or_insert_with(|| payment.id.clone());
+        }
+        self.payments.insert(payment.id.clone(), payment);
+
+        Ok(())
+    }
+}
+
+/// Payment tracker correlating the messages of payments.
+#[derive(Debug, Clone, Default)]
+pub struct PaymentTracker<S: PaymentStore> {
+    store: S,
+}
+
+impl<S: PaymentStore> PaymentTracker<S> {
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Return the payment store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Return the payment of a UETR.
+    pub fn lookup(&self, uetr: &str) -> Result<Option<Payment>, Error> {
+        self.load(&[CorrelationKey::Uetr(uetr.to_string())])
+    }
+
+    /// Return the payment of a reference of the message `msg_id` instructing the payment,
+    /// i.e. a `TxId`, `EndToEndId` or `InstrId`.
+    pub fn lookup_reference(
+        &self,
+        msg_id: &str,
+        reference: &str,
+    ) -> Result<Option<Payment>, Error> {
+        let references = PaymentReferences {
+            end_to_end_id: Some(reference.to_string()),
+            tx_id: Some(reference.to_string()),
+            instr_id: Some(reference.to_string()),
+            msg_id: Some(msg_id.to_string()),
+            ..Default::default()
+        };
+
+        self.load(&references.keys())
+    }
+
+    /// Ingest a parsed document, returning the identifications of its payments.
+    pub fn ingest<D: Serialize>(&mut self, document: &D) -> Result<Vec<String>, Error> {
+        self.ingest_xml(&quick_xml::se::to_string(document)?)
+    }
+
+    /// Ingest an XML document, returning the identifications of its payments.
+    pub fn ingest_xml(&mut self, document: &str) -> Result<Vec<String>, Error> {
+        let package = parser::parse(document)?;
+        let document = package.as_document();
+
+        let namespace = evaluate_xpath(&document, NAMESPACE_X_PATH)?.into_string();
+        let message = PaymentMessage::from_namespace(&namespace)
+            .ok_or_else(|| Error::UnsupportedMessage(namespace.clone()))?;
+        let msg_def_idr = namespace
+            .strip_prefix(NAMESPACE_PREFIX)
+            .unwrap_or_default()
+            .to_string();
+
+        let msg_id = value(&package, MSG_ID_X_PATH)?;
+        let group_code = match message.group_code_x_path() {
+            Some(x_path) => value(&package, &format!("normalize-space(({x_path})[1])"))?,
+            None => None,
+        };
+        let count = evaluate_xpath(&document, &format!("count({TRANSACTIONS_X_PATH})"))?
+            .into_number() as usize;
+
+        let mut ids = vec![];
+
+        for index in 1..=count {
+            let tx = format!("({TRANSACTIONS_X_PATH})[{index}]");
+            let references = transaction_references(&package, &tx, message, msg_id.clone())?;
+            let code = match message.code_x_paths() {
+                [] => None,
+                x_paths => {
+                    let x_paths = x_paths
+                        .iter()
+                        .map(|x_path| format!("{tx}{x_path}"))
+                        .collect::<Vec<_>>()
+                        .join(" | ");
+
+                    value(&package, &format!("normalize-space(({x_paths})[last()])"))?
+                }
+            }
+            .or_else(|| group_code.clone());
+            let status = message.status(code.as_deref());
+
+            let mut payment = match self.find(&references.keys())? {
+                Some(id) => self
+                    .store
+                    .load(&id)
+                    .map_err(store_error)?
+                    .ok_or_else(|| Error::Store(format!("missing payment {id}").into()))?,
+                None => Payment {
+                    id: references
+                        .uetr
+                        .clone()
+                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                    references: PaymentReferences::default(),
+                    status: status.unwrap_or(PaymentStatus::InProgress),
+                    timeline: vec![],
+                },
+            };
+
+            if let Some(status) = status {
+                payment.status = payment.status.next(status);
+            }
+            payment.references.merge(references);
+            payment.timeline.push(PaymentEvent {
+                message,
+                msg_def_idr: msg_def_idr.clone(),
+                msg_id: msg_id.clone(),
+                code,
+                status: payment.status,
+                recorded_at: Utc::now(),
+            });
+
+            ids.push(payment.id.clone());
+            self.store.save(payment).map_err(store_error)?;
+        }
+
+        Ok(ids)
+    }
+
+    /// Return the payment of the first key found in the store.
+    fn load(&self, keys: &[CorrelationKey]) -> Result<Option<Payment>, Error> {
+        match self.find(keys)? {
+            Some(id) => self.store.load(&id).map_err(store_error),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the identification of the payment of the first key found in the store.
+    fn find(&self, keys: &[CorrelationKey]) -> Result<Option<String>, Error> {
+        for key in keys {
+            if let Some(id) = self.store.find(key).map_err(store_error)? {
+                return Ok(Some(id));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Return the references of the transaction `tx` of the document.
+fn transaction_references(
+    package: &Package,
+    tx: &str,
+    message: PaymentMessage,
+    msg_id: Option<String>,
+) -> Result<PaymentReferences, Error> {
+    let reference = |name: &str| {
+        value(
+            package,
+            &format!(
+                "normalize-space(({tx}/*[local-name()='PmtId' or local-name()='Refs']/*[local-name()='{name}'] | {tx}/*[local-name()='Orgnl{name}'])[1])"
+            ),
+        )
+        .map(|reference| reference.filter(|reference| reference != NOT_PROVIDED))
+    };
+
+    let msg_id = if message.is_instruction() {
+        msg_id
+    } else {
+        value(
+            package,
+            &format!(
+                "normalize-space(({tx}/*[local-name()='OrgnlGrpInf']/*[local-name()='OrgnlMsgId'] | {tx}/*[local-name()='Refs']/*[local-name()='MsgId'] | {ORGNL_MSG_ID_X_PATH})[last()])"
+            ),
+        )?
+    };
+
+    Ok(PaymentReferences {
+        uetr: reference("UETR")?,
+        end_to_end_id: reference("EndToEndId")?,
+        tx_id: reference("TxId")?,
+        instr_id: reference("InstrId")?,
+        msg_id,
+    })
+}
+
+/// Evaluate an xpath expression against the document, returning a non-empty string value.
+fn value(package: &Package, x_path: &str) -> Result<Option<String>, Error> {
+    let value = evaluate_xpath(&package.as_document(), x_path)?.into_string();
+
+    Ok((!value.is_empty()).then_some(value))
+}
+
+fn store_error<E: std::error::Error + Send + Sync + 'static>(error: E) -> Error {
+    Error::Store(Box::new(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UETR: &str = "8a562c67-ca16-48ba-b074-65581be6f001";
+
+    fn document(msg_def_idr: &str, root: &str, body: &str) -> String {
+        format!("<Document xmlns=\"{NAMESPACE_PREFIX}{msg_def_idr}\"><{root}>{body}</{root}></Document>")
+    }
+
+    fn credit_transfer() -> String {
+        document(
+            "pacs.008.001.10",
+            "FIToFICstmrCdtTrf",
+            &format!(
+                "<GrpHdr><MsgId>MSG-1</MsgId></GrpHdr>\
+                <CdtTrfTxInf><PmtId><InstrId>INSTR-1</InstrId><EndToEndId>E2E-1</EndToEndId>\
+                <TxId>TX-1</TxId><UETR>{UETR}</UETR></PmtId></CdtTrfTxInf>\
+                <CdtTrfTxInf><PmtId><EndToEndId>NOTPROVIDED</EndToEndId>\
+                <TxId>TX-2</TxId></PmtId></CdtTrfTxInf>"
+            ),
+        )
+    }
+
+    fn status_report(sts: &str) -> String {
+        document(
+            "pacs.002.001.12",
+            "FIToFIPmtStsRpt",
+            &format!(
+                "<GrpHdr><MsgId>STS-{sts}</MsgId></GrpHdr>\
+                <OrgnlGrpInfAndSts><OrgnlMsgId>MSG-1</OrgnlMsgId></OrgnlGrpInfAndSts>\
+                <TxInfAndSts><OrgnlEndToEndId>E2E-1</OrgnlEndToEndId>\
+                <OrgnlUETR>{UETR}</OrgnlUETR><TxSts>{sts}</TxSts></TxInfAndSts>"
+            ),
+        )
+    }
+
+    #[test]
+    fn test_payment_timeline() {
+        let mut tracker = PaymentTracker::new(MemoryPaymentStore::new());
+
+        let ids = tracker.ingest_xml(&credit_transfer()).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], UETR);
+
+        tracker.ingest_xml(&status_report("ACSC")).unwrap();
+
+        let payment = tracker.lookup_reference("MSG-1", "E2E-1").unwrap().unwrap();
+        assert_eq!(payment.id, UETR);
+        assert_eq!(payment.status, PaymentStatus::Settled);
+        assert_eq!(payment.references.msg_id.as_deref(), Some("MSG-1"));
+        assert_eq!(payment.references.instr_id.as_deref(), Some("INSTR-1"));
+        assert_eq!(payment.timeline[1].code.as_deref(), Some("ACSC"));
+        assert_eq!(payment.timeline[1].msg_def_idr, "pacs.002.001.12");
+
+        let payment_return = document(
+            "pacs.004.001.11",
+            "PmtRtr",
+            &format!(
+                "<GrpHdr><MsgId>RTR-1</MsgId></GrpHdr>\
+                <TxInf><RtrId>RTR-1</RtrId><OrgnlUETR>{UETR}</OrgnlUETR>\
+                <RtrRsnInf><Rsn><Cd>AC04</Cd></Rsn></RtrRsnInf></TxInf>"
+            ),
+        );
+        tracker.ingest_xml(&payment_return).unwrap();
+
+        // A late status report does not undo the return
+        tracker.ingest_xml(&status_report("ACSP")).unwrap();
+
+        let payment = tracker.lookup(UETR).unwrap().unwrap();
+        assert_eq!(payment.status, PaymentStatus::Returned);
+        assert_eq!(
+            payment
+                .timeline
+                .iter()
+                .map(|event| (event.code.as_deref(), event.status))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, PaymentStatus::InProgress),
+                (Some("ACSC"), PaymentStatus::Settled),
+                (Some("AC04"), PaymentStatus::Returned),
+                (Some("ACSP"), PaymentStatus::Returned),
+            ]
+        );
+
+        // The second payment is tracked by its transaction identification only
+        let payment = tracker.lookup_reference("MSG-1", "TX-2").unwrap().unwrap();
+        assert_eq!(payment.status, PaymentStatus::InProgress);
+        assert_eq!(payment.references.end_to_end_id, None);
+        assert!(tracker
+            .lookup_reference("MSG-1", "NOTPROVIDED")
+            .unwrap()
+            .is_none());
+        assert!(tracker.lookup_reference("MSG-2", "TX-2").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_references_scoped_by_message() {
+        let mut tracker = PaymentTracker::new(MemoryPaymentStore::new());
+
+        // Two senders reusing the same end-to-end identification, without UETR
+        for msg_id in ["MSG-A", "MSG-B"] {
+            let credit_transfer = document(
+                "pacs.008.001.10",
+                "FIToFICstmrCdtTrf",
+                &format!(
+                    "<GrpHdr><MsgId>{msg_id}</MsgId></GrpHdr>\
+                    <CdtTrfTxInf><PmtId><EndToEndId>INVOICE-1</EndToEndId></PmtId></CdtTrfTxInf>"
+                ),
+            );
+            tracker.ingest_xml(&credit_transfer).unwrap();
+        }
+        assert_eq!(tracker.store().payments().count(), 2);
+
+        let rejection = document(
+            "pacs.002.001.12",
+            "FIToFIPmtStsRpt",
+            "<GrpHdr><MsgId>STS-1</MsgId></GrpHdr>\
+            <OrgnlGrpInfAndSts><OrgnlMsgId>MSG-B</OrgnlMsgId></OrgnlGrpInfAndSts>\
+            <TxInfAndSts><OrgnlEndToEndId>INVOICE-1</OrgnlEndToEndId><TxSts>RJCT</TxSts>\
+            </TxInfAndSts>",
+        );
+        tracker.ingest_xml(&rejection).unwrap();
+
+        let status = |msg_id| {
+            tracker
+                .lookup_reference(msg_id, "INVOICE-1")
+                .unwrap()
+                .unwrap()
+                .status
+        };
+        assert_eq!(status("MSG-A"), PaymentStatus::InProgress);
+        assert_eq!(status("MSG-B"), PaymentStatus::Rejected);
+        assert_eq!(tracker.store().payments().count(), 2);
+    }
+
+    #[test]
+    fn test_group_status_and_cancellation() {
+        let mut tracker = PaymentTracker::new(MemoryPaymentStore::new());
+
+        let initiation = document(
+            "pain.001.001.11",
+            "CstmrCdtTrfInitn",
+            "<GrpHdr><MsgId>INITN-1</MsgId></GrpHdr>\
+            <PmtInf><CdtTrfTxInf><PmtId><EndToEndId>E2E-2</EndToEndId></PmtId></CdtTrfTxInf>\
+            <CdtTrfTxInf><PmtId><EndToEndId>E2E-3</EndToEndId></PmtId></CdtTrfTxInf></PmtInf>",
+        );
+        tracker.ingest_xml(&initiation).unwrap();
+        assert_eq!(
+            tracker
+                .lookup_reference("INITN-1", "E2E-2")
+                .unwrap()
+                .unwrap()
+                .status,
+            PaymentStatus::Initiated
+        );
+
+        let rejection = document(
+            "pain.002.001.12",
+            "CstmrPmtStsRpt",
+            "<GrpHdr><MsgId>STS-1</MsgId></GrpHdr>\
+            <OrgnlGrpInfAndSts><OrgnlMsgId>INITN-1</OrgnlMsgId><GrpSts>RJCT</GrpSts></OrgnlGrpInfAndSts>\
+            <OrgnlPmtInfAndSts><TxInfAndSts><OrgnlEndToEndId>E2E-2</OrgnlEndToEndId></TxInfAndSts>\
+            </OrgnlPmtInfAndSts>",
+        );
+        tracker.ingest_xml(&rejection).unwrap();
+        assert_eq!(
+            tracker
+                .lookup_reference("INITN-1", "E2E-2")
+                .unwrap()
+                .unwrap()
+                .status,
+            PaymentStatus::Rejected
+        );
+
+        let resolution = document(
+            "camt.029.001.11",
+            "RsltnOfInvstgtn",
+            "<Assgnmt><Id>ASSGNMT-1</Id></Assgnmt><Sts><Conf>CNCL</Conf></Sts>\
+            <CxlDtls><TxInfAndSts><OrgnlGrpInf><OrgnlMsgId>INITN-1</OrgnlMsgId></OrgnlGrpInf>\
+            <OrgnlEndToEndId>E2E-3</OrgnlEndToEndId></TxInfAndSts></CxlDtls>",
+        );
+        tracker.ingest_xml(&resolution).unwrap();
+
+        let payment = tracker
+            .lookup_reference("INITN-1", "E2E-3")
+            .unwrap()
+            .unwrap();
+        assert_eq!(payment.status, PaymentStatus::Cancelled);
+        assert_eq!(payment.timeline[1].msg_id.as_deref(), Some("ASSGNMT-1"));
+        assert_eq!(tracker.store().payments().count(), 2);
+
+        assert!(matches!(
+            tracker.ingest_xml(&document("camt.053.001.10", "BkToCstmrStmt", "")),
+            Err(Error::UnsupportedMessage(_))
+        ));
+    }
+}